@@ -4,32 +4,77 @@ language = Language
 game-name = Name
 total-games = Games
 file-size = Size
+freed-space = Freed space
 file-location = Location
 overall = Overall
+# Heading for aggregate stats, shown instead of each game's details.
+summary = Summary
+# How many games are new or different since the last backup.
+changed-games = Changed
+# How many games had at least one file that couldn't be processed.
+failed-games = Failed
 status = Status
+time-remaining = ~{$time} left
 
 cli-unrecognized-games = No info for these games:
+cli-closest-games = No games matched {$pattern}. Closest matches:
+cli-fuzzy-game-match = No game is named exactly {$pattern}, so using the closest match: {$game}
 cli-unable-to-request-confirmation = Unable to request confirmation.
     .winpty-workaround = If you are using a Bash emulator (like Git Bash), try running winpty.
 cli-backup-id-with-multiple-games = Cannot specify backup ID when restoring multiple games.
+cli-invalid-time-window = The --after time must be earlier than the --before time.
 cli-invalid-backup-id = Invalid backup ID.
+cli-select-backup = Select a backup to restore for {$game} (1-{$total}):
+cli-invalid-selection = Invalid selection.
+cli-enter-zip-password = Password for encrypted zip backups:
+cli-file-not-backed-up = No backups contain this file: {$path}
+cli-backup-has-no-registry = This backup does not contain any registry data.
+cli-target-root-unavailable = Unable to use a target root for {$game} because its backup has no files to restore.
+cli-games-file-empty = This file does not list any games: {$path}
+cli-imported-external-game = Imported {$path} as {$game}
+cli-external-game-already-backed-up = Skipped {$path} because {$game} already has backups
+cli-external-game-without-save-location = Skipped {$path} because there is no known save location for {$game}
+cli-external-game-failed = Unable to import {$path} as {$game}
+cli-unmatched-external-folders = These folders did not match any game. You can choose one with --map "FOLDER=GAME":
 
 badge-failed = FAILED
+badge-failed-count = FAILED: {$total}
 badge-duplicates = DUPLICATES
 badge-duplicated = DUPLICATED
 badge-ignored = IGNORED
+badge-warning = WARNING
+badge-excluded = EXCLUDED
+badge-conflict = CONFLICT
 badge-redirected-from = FROM: {$path}
 badge-redirecting-to = TO: {$path}
+file-exceeds-max-size = Larger than the limit of {$size}
+file-below-min-size = Smaller than the limit of {$size}
+file-is-empty = Empty file
 
 some-entries-failed = Some entries failed to process; look for {badge-failed} in the output for details. Double check whether you can access those files or whether their paths are very long.
 
 cli-game-line-item-redirected = Redirected from: {$path}
 cli-game-line-item-redirecting = Redirecting to: {$path}
 
+warning-skipped-file = Skipped a file that could not be processed: {$path}
+warning-unreadable-file = Unable to read file: {$path}
+warning-future-modified-time = File was modified in the future; check your system clock: {$path}
+warning-insufficient-space = The backup may not fit in the free space. About {$required} is needed, but only {$available} is available.
+warning-inaccessible-registry = Unable to access registry key: {$key}
+warning-overlapping-roots = Root {$child} is inside of root {$parent}, so some saves may be found twice
+warning-unremovable-backup-file = Unable to remove old backup file: {$path}
+warning-skipped-destructive-restore = Skipped restoring a file because it would remove or overwrite newer live data: {$path}
+warning-game-running = The game seemed to be running, so its saves may have been in the middle of being written.
+warning-hook-failed = Hook command failed ({$message}): {$command}
+warning-recently-restored = This game was already restored {$minutes} minutes ago, so restoring again may revert newer progress.
+warning-mirror-failed = Unable to copy backup to mirror ({$message}): {$path}
+warning-redirect-fallback = Unable to use redirected location, so restored to original location: {$path} (redirect: {$redirect})
+
 button-backup = Back up
 button-preview = Preview
 button-restore = Restore
 button-nav-backup = BACKUP MODE
+button-nav-changes = CHANGES
 button-nav-restore = RESTORE MODE
 button-nav-custom-games = CUSTOM GAMES
 button-nav-other = OTHER
@@ -47,9 +92,16 @@ button-exit = Exit
 button-comment = Comment
 button-lock = Lock
 button-unlock = Unlock
+button-merge = Merge
+button-exclude-from-cloud = Don't sync to cloud
+button-include-in-cloud = Sync to cloud
+button-delete-backup = Delete backup
+button-restore-only-this = Restore only this
 # This opens a download page.
 button-get-app = Get {$app}
 button-validate = Validate
+button-preview-pruning = Preview pruning
+button-scrub = Scrub
 button-override-manifest = Override manifest
 button-extend-manifest = Extend manifest
 button-sort = Sort
@@ -57,21 +109,30 @@ button-sort = Sort
 no-roots-are-configured = Add some roots to back up even more data.
 
 config-is-invalid = Error: The config file is invalid.
+config-key-unrecognized = Error: Unrecognized config key: {$key}
+config-key-suggestions = Did you mean one of these?
+config-value-invalid = Error: Invalid value for config key: {$key}
 manifest-is-invalid = Error: The manifest file is invalid.
 manifest-cannot-be-updated = Error: Unable to check for an update to the manifest file. Is your Internet connection down?
+insufficient-space = Error: Not enough free space for the backup ({$shortfall} short). About {$required} is needed, but only {$available} is available: {$path}
 cannot-prepare-backup-target = Error: Unable to prepare backup target (either creating or emptying the folder). If you have the folder open in your file browser, try closing it: {$path}
 restoration-source-is-invalid = Error: The restoration source is invalid (either doesn't exist or isn't a directory). Please double check the location: {$path}
 registry-issue = Error: Some registry entries were skipped.
 unable-to-browse-file-system = Error: Unable to browse on your system.
 unable-to-open-directory = Error: Unable to open directory:
+unable-to-read-file = Error: Unable to read file:
+unable-to-write-file = Error: Unable to write file:
 unable-to-open-url = Error: Unable to open URL:
 unable-to-configure-cloud = Unable to configure cloud.
 unable-to-synchronize-with-cloud = Unable to synchronize with cloud.
 cloud-synchronize-conflict = Your local and cloud backups are in conflict. Perform an upload or download to resolve this.
+waiting-for-cloud-authorization = Sign in to {$app} in your browser to finish setting up cloud backups. This will time out after {$minutes} minutes.
 
 command-unlaunched = Command did not launch: {$command}
 command-terminated = Command terminated abruptly: {$command}
 command-failed = Command failed with code {$code}: {$command}
+command-cancelled = Command was cancelled: {$command}
+command-timed-out = Command did not finish in time: {$command}
 
 processed-games = {$total-games} {$total-games ->
     [one] game
@@ -88,6 +149,7 @@ field-restore-source = Restore from:
 field-custom-files = Paths:
 field-custom-registry = Registry:
 field-sort = Sort:
+field-conflict-policy = Existing files:
 field-redirect-source =
     .placeholder = Source (original location)
 field-redirect-target =
@@ -125,11 +187,16 @@ label-enabled = Enabled
 label-disabled = Disabled
 # https://en.wikipedia.org/wiki/Thread_(computing)
 label-threads = Threads
+label-size-limit = Limit (MiB)
 label-cloud = Cloud
 # A "remote" is what Rclone calls cloud systems like Google Drive.
 label-remote = Remote
 label-remote-name = Remote name
 label-folder = Folder
+label-bandwidth-limit = Bandwidth limit
+label-transfers = Transfers
+label-duplicates = Duplicates
+label-total-size = Total size
 # An executable file
 label-executable = Executable
 # Options given to a command line program
@@ -144,10 +211,24 @@ label-password = Password
 # This is a specific website or service that provides some cloud functionality.
 # For example, Nextcloud and Owncloud are providers of WebDAV services.
 label-provider = Provider
+label-bucket = Bucket
+label-region = Region
+# A shared folder on an SMB server.
+label-share = Share
+# A folder on this computer (or a mounted network drive) used in place of a cloud service.
+label-local-folder = Local folder
 label-custom = Custom
 label-none = None
+# This refers to using the general setting instead of a game-specific one.
+label-default = Default
 label-change-count = Changes: {$total}
+label-changed-games = {$total} {$total ->
+    [one] game has
+    *[other] games have
+} changes since the last backup. Run a preview to refresh this list.
 label-unscanned = Unscanned
+label-has-failures = Has failures
+label-no-failures = No failures
 # This refers to a local file on the computer
 label-file = File
 label-game = Game
@@ -160,8 +241,19 @@ label-source = Source
 label-primary-manifest = Primary manifest
 # This refers to how we integrate a custom game with the manifest data.
 label-integration = Integration
+# This refers to which game store a custom game should be treated as coming from.
+label-store = Store
+# This refers to which backup format (simple/zip/etc) to use for a specific game.
+label-backup-format = Format
 # This is a folder name where a specific game is installed
 label-installed-name = Installed name
+# This is a list of other games whose save locations are combined into a custom game.
+label-include-games = Include games
+# This is a list of patterns for subfolders/files to skip inside of a game's save folders.
+label-excluded-subpaths = Excluded subpaths
+
+# Shown when hovering over the OS badge of a backup made on a different operating system.
+backup-from-other-os = This backup was made on {$os}. Some of its paths may not exist on this system, so you may need a redirect or a Wine prefix to restore them.
 
 store-ea = EA
 store-epic = Epic
@@ -191,6 +283,8 @@ store-other = Other
 
 backup-format-simple = Simple
 backup-format-zip = Zip
+backup-format-tar = Tar
+backup-format-tar-gz = Tar (gzip)
 
 compression-none = None
 # "Deflate" is a proper noun: https://en.wikipedia.org/wiki/Deflate
@@ -198,20 +292,30 @@ compression-deflate = Deflate
 compression-bzip2 = Bzip2
 compression-zstd = Zstd
 
+conflict-policy-overwrite = Overwrite
+conflict-policy-skip = Keep
+conflict-policy-keep-newer = Keep if newer
+conflict-policy-backup = Rename and overwrite
+conflict-tooltip = This file was modified after the backup was made.
+
 theme = Theme
 theme-light = Light
 theme-dark = Dark
 
 redirect-bidirectional = Bidirectional
+redirect-regex = Regex
 reverse-redirects-when-restoring = Reverse sequence of redirects when restoring
+fall-back-when-redirect-fails = Restore to the original location when a redirect target can't be created
 
 show-disabled-games = Show disabled games
 show-unchanged-games = Show unchanged games
 show-unscanned-games = Show unscanned games
 override-max-threads = Override max threads
 synchronize-automatically = Synchronize automatically
+limit-cloud-transfers = Limit parallel transfers
 prefer-alias-display = Display alias instead of original name
 skip-unconstructive-backups = Skip backup when data would be removed, but not added or updated
+follow-symlinks = Back up symlinks as regular files
 
 explanation-for-exclude-store-screenshots =
     In backups, exclude store-specific screenshots
@@ -219,6 +323,18 @@ explanation-for-exclude-store-screenshots =
 explanation-for-exclude-cloud-games =
     Do not back up games with cloud support on these platforms
 
+explanation-for-max-file-size =
+    In backups, exclude files larger than a limit
+
+explanation-for-min-file-size =
+    In backups, exclude files smaller than a limit
+
+explanation-for-exclude-empty-files =
+    In backups, exclude empty files
+
+explanation-for-exclude-empty-dirs =
+    When restoring, remove new folders that end up empty
+
 consider-doing-a-preview =
     If you haven't already, consider doing a preview first so that there
     are no surprises.
@@ -229,10 +345,32 @@ confirm-backup =
         *[create] The target folder will be created:
     }
 
+confirm-merge =
+    Are you sure you want to proceed?
+    Differential backups will be combined into new full backups here:
+
+confirm-merge-and-prune =
+    Are you sure you want to proceed?
+    Differential backups will be combined into new full backups,
+    and the originals will be permanently deleted from here (unless they are locked):
+
+confirm-delete-backup =
+    Are you sure you want to proceed?
+    This backup of {$game} will be permanently deleted: {$name}
+    .dependents = These differential backups depend on it and will also be deleted:
+
+confirm-maintenance =
+    Are you sure you want to proceed?
+    Any backups beyond the retention limits will be permanently deleted from here:
+
 confirm-restore =
     Are you sure you want to proceed with the restoration?
     This will overwrite any current files with the backups from here:
 
+confirm-restore-recent =
+    These games were already restored within the last hour,
+    so restoring them again may revert newer progress:
+
 confirm-cloud-upload =
     Do you want to replace your cloud files with your local files?
     Your cloud files ({$cloud-path}) will become an exact copy of your local files ({$local-path}).
@@ -251,6 +389,13 @@ updating-manifest = Updating manifest...
 no-cloud-changes = No changes to synchronize
 backups-are-valid =
     Your backups are valid.
+no-backups-to-prune =
+    No backups exceed the retention limits.
+backups-to-prune =
+    These backups exceed the retention limits
+    and will be removed the next time each game is backed up.
+    Locked backups are always retained.
+label-retained = Retained
 backups-are-invalid =
     These games' backups appear to be invalid.
     Do you want to create new full backups for these games?
@@ -271,11 +416,23 @@ prefix-warning = Warning: {$message}
 cloud-app-unavailable = Cloud backups are disabled because {$app} is not available.
 cloud-not-configured = Cloud backups are disabled because no cloud system is configured.
 cloud-path-invalid = Cloud backups are disabled because the backup path is invalid.
+cloud-local-path-unavailable = Cloud backups are disabled because the local folder is not available: {$path}
+cloud-bandwidth-limit-invalid = Cloud backups are disabled because the bandwidth limit is invalid.
+export-name-invalid = Invalid export name: {$name}
+no-differential-backup-to-merge = There is no differential backup to merge.
+backup-merge-failed = Unable to merge the backups.
+backup-locked = This backup is locked: {$name}
+backup-password-missing = A password is required for encrypted zip backups. Set it in the config file or the {$variable} environment variable.
+backup-password-incorrect = The password is incorrect for this encrypted backup: {$name}
+bundle-invalid = The backup bundle is invalid: {$path}
+bundle-game-already-backed-up = There are already backups for this game: {$game}
+bundle-import-failed = Unable to import the backup bundle.
 
 game-is-unrecognized = Ludusavi does not recognize this game.
 game-has-nothing-to-restore = This game does not have a backup to restore.
 launch-game-after-error = Launch the game anyway?
 game-did-not-launch = Game failed to launch.
+game-launch-command-unknown = Ludusavi does not know how to launch this game. Please specify the command to run.
 
 back-up-specific-game =
     .confirm = Back up save data for {$game}?
@@ -287,6 +444,16 @@ restore-specific-game =
 new-version-check = Check for application updates automatically
 new-version-available = An application update is available: {$version}. Would you like to view the release notes?
 
+show-tray-icon = Show icon in system tray
+tray-backup-all = Back up all games
+tray-open = Open
+tray-backup-finished = Backup finished.
+
+newly-supported-games-found = {$total} newly supported {$total ->
+    [one] game
+    *[other] games
+} found with saves.
+
 custom-game-will-override = This custom game overrides a manifest entry
 custom-game-will-extend = This custom game extends a manifest entry
 
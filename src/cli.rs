@@ -3,7 +3,11 @@ mod parse;
 mod report;
 mod ui;
 
-use std::{collections::BTreeSet, process::Command, time::Duration};
+use std::{
+    collections::{BTreeSet, HashMap},
+    process::Command,
+    time::Duration,
+};
 
 use clap::CommandFactory;
 use indicatif::{ParallelProgressIterator, ProgressBar};
@@ -14,29 +18,41 @@ use rayon::{
 
 use crate::{
     cli::{
-        parse::{Cli, CompletionShell, ConfigSubcommand, ManifestSubcommand, Subcommand},
+        parse::{BackupsSubcommand, Cli, CompletionShell, ConfigSubcommand, ManifestSubcommand, Subcommand},
         report::{report_cloud_changes, Reporter},
     },
-    cloud::{CloudChange, Rclone, Remote},
+    cloud::{CloudChange, Rclone, Remote, RemoteChoice},
     lang::{Language, TRANSLATOR},
+    metadata,
     prelude::{
         app_dir, get_threads_from_env, initialize_rayon, register_sigint, unregister_sigint, Error, Finality,
         StrictPath, SyncDirection,
     },
-    resource::{cache::Cache, config::Config, manifest::Manifest, ResourceFile, SaveableResourceFile},
+    resource::{
+        cache::Cache,
+        config::{BackupFormat, Config, ZipCompression},
+        manifest::{Manifest, Os},
+        ResourceFile, SaveableResourceFile,
+    },
     scan::{
-        layout::BackupLayout, prepare_backup_target, scan_game_for_backup, BackupId, DuplicateDetector, Launchers,
-        OperationStepDecision, ScanKind, SteamShortcuts, TitleFinder, TitleQuery,
+        external, layout::BackupLayout, plan_game_for_backup, prepare_backup_target, registry, scan_game_for_backup,
+        with_final_restore_redirect, BackupId, BackupScanOptions, DuplicateDetector, Launchers, OperationStepDecision,
+        RunningProcesses, ScanKind, SteamShortcuts, TitleFinder, TitleQuery,
     },
     wrap,
 };
 
 const PROGRESS_BAR_REFRESH_INTERVAL: Duration = Duration::from_millis(50);
 
-pub fn show_error(error: &Error, gui: bool, force: bool) {
+pub fn show_error(error: &Error, gui: bool, force: bool, api: bool) {
     let message = TRANSLATOR.handle_error(error);
 
-    if gui {
+    if api {
+        eprintln!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "errors": report::ApiErrors::failure(error) })).unwrap()
+        );
+    } else if gui {
         let _ = ui::alert(gui, force, &message);
     } else {
         eprintln!("{}", message);
@@ -72,6 +88,26 @@ fn load_manifest(
     }
 }
 
+/// Combine the games from the command line (or stdin) with any from a `--games-from` file.
+fn parse_games_with_file(games: Vec<String>, games_from: Option<StrictPath>) -> Result<Vec<String>, Error> {
+    let Some(file) = games_from else {
+        return Ok(parse_games(games));
+    };
+
+    let Some(content) = file.read() else {
+        return Err(Error::UnableToReadFile(file));
+    };
+    let from_file = parse::parse_game_list(&content);
+    log::debug!("Games from file: {:?}", &from_file);
+
+    // An empty list would mean all games, which is surely not what the file intended.
+    if from_file.is_empty() {
+        return Err(Error::CliGamesFileEmpty { path: file });
+    }
+
+    Ok(games.into_iter().chain(from_file).collect())
+}
+
 fn parse_games(games: Vec<String>) -> Vec<String> {
     if !games.is_empty() {
         games
@@ -89,6 +125,40 @@ fn parse_games(games: Vec<String>) -> Vec<String> {
     }
 }
 
+/// Password for encrypted zip backups.
+/// If encryption is enabled, but the password isn't in the config file or environment,
+/// then ask for it, unless this is a non-interactive run.
+fn zip_password(config: &Config, force: bool, gui: bool, api: bool) -> Option<String> {
+    use std::io::IsTerminal;
+
+    config.zip_password().or_else(|| {
+        let interactive = !force && !gui && !api && std::io::stdin().is_terminal();
+        if config.backup.format.zip.encryption.is_some() && interactive {
+            ui::password(&TRANSLATOR.cli_enter_zip_password())
+        } else {
+            None
+        }
+    })
+}
+
+/// Where to save an exported file.
+/// If the output is an existing folder, then the file is named inside of it
+/// according to the `export.name` template.
+fn export_target(
+    config: &Config,
+    output: StrictPath,
+    game: Option<&str>,
+    count: usize,
+    extension: &str,
+) -> Result<StrictPath, Error> {
+    if output.is_dir() {
+        let name = config.export.render_name(game, &chrono::Utc::now(), Os::HOST, count)?;
+        Ok(output.joined(&format!("{name}.{extension}")))
+    } else {
+        Ok(output)
+    }
+}
+
 pub fn evaluate_games(
     default: BTreeSet<String>,
     requested: Vec<String>,
@@ -102,13 +172,35 @@ pub fn evaluate_games(
     let mut invalid = BTreeSet::new();
 
     for game in requested {
-        match title_finder.find_one_by_name(&game) {
-            Some(found) => {
-                valid.insert(found);
+        if let Some(found) = title_finder.find_one_by_name(&game) {
+            valid.insert(found);
+            continue;
+        }
+
+        match parse::match_game_glob(&game, &default) {
+            Some(matched) if !matched.is_empty() => {
+                log::debug!("Games matching {:?}: {:?}", &game, &matched);
+                valid.extend(matched);
             }
-            None => {
+            Some(_) => {
+                let closest = parse::closest_game_titles(&game, &default);
+                if !closest.is_empty() {
+                    eprintln!("{}", TRANSLATOR.cli_closest_games(&game, &closest));
+                }
                 invalid.insert(game);
             }
+            None => match title_finder.find_one_by_fuzzy_name(&game) {
+                Ok(found) => {
+                    eprintln!("{}", TRANSLATOR.cli_fuzzy_game_match(&game, &found));
+                    valid.insert(found);
+                }
+                Err(closest) => {
+                    if !closest.is_empty() {
+                        eprintln!("{}", TRANSLATOR.cli_closest_games(&game, &closest));
+                    }
+                    invalid.insert(game);
+                }
+            },
         }
     }
 
@@ -119,12 +211,73 @@ pub fn evaluate_games(
     Ok(valid.into_iter().collect())
 }
 
+/// Remove the games matched by `--exclude`, which supports the same names and globs as `evaluate_games`.
+/// Each exclusion must match some game, so that a typo doesn't go unnoticed.
+pub fn exclude_games(
+    games: Vec<String>,
+    default: BTreeSet<String>,
+    excluded: Vec<String>,
+    title_finder: &TitleFinder,
+) -> Result<Vec<String>, Vec<String>> {
+    if excluded.is_empty() {
+        return Ok(games);
+    }
+
+    let excluded: BTreeSet<_> = evaluate_games(default, excluded, title_finder)?.into_iter().collect();
+    Ok(games
+        .into_iter()
+        .filter(|name| {
+            let keep = !excluded.contains(name);
+            if !keep {
+                log::trace!("[{name}] excluded by --exclude");
+            }
+            keep
+        })
+        .collect())
+}
+
 pub fn parse() -> Result<Cli, clap::Error> {
     use clap::Parser;
     Cli::try_parse()
 }
 
-pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool) -> Result<(), Error> {
+/// Print the config file and/or backup folder locations, one per line.
+pub fn print_paths(config_file: bool, backup_dir: bool) -> Result<(), Error> {
+    let absolute = |path: StrictPath| path.interpreted().map(|x| x.render()).unwrap_or_else(|_| path.render());
+
+    if config_file {
+        println!("{}", absolute(Config::path()));
+    }
+    if backup_dir {
+        let config = Config::load()?;
+        println!("{}", absolute(config.backup.path));
+    }
+
+    Ok(())
+}
+
+/// An error that stopped a CLI command.
+pub struct Failure {
+    pub error: Error,
+    /// Whether the error was already included in a JSON report,
+    /// so that it should not be shown again.
+    pub reported: bool,
+}
+
+pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool) -> Result<(), Failure> {
+    let mut failure_reported = false;
+    run_subcommand(sub, no_manifest_update, try_manifest_update, &mut failure_reported).map_err(|error| Failure {
+        error,
+        reported: failure_reported,
+    })
+}
+
+fn run_subcommand(
+    sub: Subcommand,
+    no_manifest_update: bool,
+    try_manifest_update: bool,
+    failure_reported: &mut bool,
+) -> Result<(), Error> {
     let mut config = Config::load()?;
     if let Some(threads) = get_threads_from_env().or(config.runtime.threads) {
         initialize_rayon(threads);
@@ -143,6 +296,8 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             force,
             wine_prefix,
             api,
+            ndjson,
+            summary,
             gui,
             sort,
             format,
@@ -153,11 +308,26 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             cloud_sync,
             no_cloud_sync,
             dump_registry,
+            duplicates,
+            only_tag,
+            exclude_tag,
+            batch,
+            comment,
+            full_scan,
+            exclude,
+            games_from,
             games,
         } => {
-            let games = parse_games(games);
+            let games = parse_games_with_file(games, games_from)?;
 
-            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            let mut reporter = if api || ndjson {
+                Reporter::json()
+            } else {
+                Reporter::standard()
+            };
+            if summary {
+                reporter.summarize();
+            }
 
             let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
 
@@ -180,26 +350,69 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 prepare_backup_target(&backup_dir)?;
             }
 
-            let retention = config.backup.retention.with_limits(full_limit, differential_limit);
-
-            let layout = BackupLayout::new(backup_dir.clone());
+            let layout = BackupLayout::new(backup_dir.clone())
+                .with_folder_template(config.backup.folder_template.clone())
+                .with_filename_policy(config.filename_policy())
+                .with_hooks(config.backup.hooks())
+                .with_space_check(config.backup.space_check)
+                .with_mirrors(config.backup.mirrors.clone())
+                .with_zip_password(zip_password(&config, force, gui, api));
             let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
 
             let games_specified = !games.is_empty();
-            let games = match evaluate_games(manifest.primary_titles(), games, &title_finder) {
+            let excluding = !exclude.is_empty();
+            let games = match evaluate_games(manifest.primary_titles(), games, &title_finder)
+                .and_then(|games| exclude_games(games, manifest.primary_titles(), exclude, &title_finder))
+            {
                 Ok(games) => games,
                 Err(games) => {
                     reporter.trip_unknown_games(games.clone());
-                    reporter.print_failure();
-                    return Err(Error::CliUnrecognizedGames { games });
+                    let error = Error::CliUnrecognizedGames { games };
+                    *failure_reported = reporter.print_failure(&error);
+                    return Err(error);
                 }
             };
-
+            let games: Vec<_> = games
+                .into_iter()
+                .filter(|name| {
+                    let matched = manifest.0[name].matches_tags(&only_tag, &exclude_tag);
+                    if !matched {
+                        log::trace!("[{name}] excluded by tag filter");
+                    }
+                    matched
+                })
+                .collect();
+            let scanned_all =
+                !games_specified && !excluding && batch.is_none() && only_tag.is_empty() && exclude_tag.is_empty();
             let launchers = Launchers::scan(&roots, &manifest, &games, &title_finder, None);
+            let games = match batch {
+                Some(size) => {
+                    let games = layout.select_batch(&games, size, &cache.backup.batch_attempts, |name| {
+                        games_specified || launchers.contains_game(name)
+                    });
+                    if !preview {
+                        let now = chrono::Utc::now();
+                        for game in &games {
+                            cache.backup.batch_attempts.insert(game.clone(), now);
+                        }
+                        cache.save();
+                    }
+                    games
+                }
+                None => games,
+            };
+
+            let quick_scan = !full_scan
+                && config
+                    .backup
+                    .quick_scan
+                    .is_active(cache.backup.last_full_scan, chrono::Utc::now());
+
             let filter = config.backup.filter.clone();
             let toggled_paths = config.backup.toggled_paths.clone();
             let toggled_registry = config.backup.toggled_registry.clone();
             let steam_shortcuts = SteamShortcuts::scan();
+            let processes = RunningProcesses::scan();
 
             let cloud_sync = negatable_flag(
                 cloud_sync && !preview,
@@ -208,15 +421,17 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     && !preview
                     && crate::cloud::validate_cloud_config(&config, &config.cloud.path).is_ok(),
             );
+            let force_timestamped_name = cloud_sync && config.cloud.timestamped_backups;
             let mut should_sync_cloud_after = cloud_sync && !preview;
             if cloud_sync {
+                let sync_games = crate::cloud::backup_sync_games(&config, games_specified.then_some(&games[..]), None);
                 let changes = sync_cloud(
                     &config,
                     &backup_dir,
                     &config.cloud.path,
                     SyncDirection::Upload,
                     Finality::Preview,
-                    if games_specified { &games } else { &[] },
+                    sync_games.as_deref().unwrap_or_default(),
                 );
                 match changes {
                     Ok(changes) => {
@@ -270,9 +485,17 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         &config.redirects,
                         config.restore.reverse_redirects,
                         &steam_shortcuts,
-                        config.backup.only_constructive,
+                        &BackupScanOptions {
+                            processes: Some(&processes),
+                            quick_scan,
+                            ..BackupScanOptions::from_config(&config, name)
+                        },
                     );
-                    let ignored = !&config.is_game_enabled_for_backup(name) && !games_specified;
+                    let skipped_running = config.backup.skip_running_games && scan_info.game_running;
+                    if skipped_running {
+                        log::info!("[{name}] skipping backup because the game is running");
+                    }
+                    let ignored = (!&config.is_game_enabled_for_backup(name) && !games_specified) || skipped_running;
                     let decision = if ignored {
                         OperationStepDecision::Ignored
                     } else {
@@ -281,7 +504,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     let backup_info = if preview || ignored {
                         None
                     } else {
-                        let mut backup_format = config.backup.format.clone();
+                        let mut backup_format = config.backup.format_for(name);
                         if let Some(format) = format {
                             backup_format.chosen = format;
                         }
@@ -289,17 +512,28 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                             backup_format.zip.compression = compression;
                         }
                         if let Some(level) = compression_level {
-                            backup_format
-                                .compression
-                                .set_level(&backup_format.zip.compression, level);
+                            // Gzip uses the same levels as deflate.
+                            let method = match backup_format.chosen {
+                                BackupFormat::TarGz => ZipCompression::Deflate,
+                                _ => backup_format.zip.compression,
+                            };
+                            backup_format.compression.set_level(&method, level);
                         }
 
-                        layout.game_layout(name).back_up(
+                        let retention = config
+                            .backup
+                            .retention_for(name)
+                            .with_limits(full_limit, differential_limit)
+                            .with_force_timestamped_name(force_timestamped_name);
+
+                        layout.game_layout_for(name, Some(game)).back_up(
                             &scan_info,
                             &chrono::Utc::now(),
                             &backup_format,
                             retention,
                             config.backup.only_constructive,
+                            config.backup.checksum,
+                            comment.as_deref().or(config.backup.comment_template.as_deref()),
                         )
                     };
                     log::trace!("step {i} completed");
@@ -307,26 +541,51 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         None
                     } else {
                         let display_title = config.display_name(name);
+                        if ndjson {
+                            Reporter::stream_game(
+                                &display_title,
+                                &scan_info,
+                                backup_info.as_ref(),
+                                &decision,
+                                dump_registry,
+                            );
+                        }
                         Some((display_title, scan_info, backup_info, decision))
                     }
                 })
                 .collect();
             log::info!("completed backup");
 
+            if !preview {
+                layout.collect_garbage(false);
+
+                if scanned_all && !quick_scan {
+                    cache.backup.last_full_scan = Some(chrono::Utc::now());
+                    cache.save();
+                }
+            }
+
             if should_sync_cloud_after {
                 let changed_games: Vec<_> = info
                     .iter()
-                    .filter(|(_, scan_info, _, _)| scan_info.needs_cloud_sync())
+                    .filter(|(_, scan_info, _, _)| {
+                        scan_info.needs_cloud_sync() && config.is_game_enabled_for_cloud(&scan_info.game_name)
+                    })
                     .map(|(_, scan_info, _, _)| scan_info.game_name.clone())
                     .collect();
                 if !changed_games.is_empty() {
+                    let sync_games = crate::cloud::backup_sync_games(
+                        &config,
+                        games_specified.then_some(&games[..]),
+                        Some(&changed_games[..]),
+                    );
                     let sync_result = sync_cloud(
                         &config,
                         &backup_dir,
                         &config.cloud.path,
                         SyncDirection::Upload,
                         Finality::Final,
-                        &changed_games,
+                        sync_games.as_deref().unwrap_or_default(),
                     );
                     if sync_result.is_err() {
                         reporter.trip_cloud_sync_failed();
@@ -371,24 +630,47 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     failed = true;
                 }
             }
-            reporter.print(&backup_dir);
+            if duplicates {
+                reporter.add_duplicates(&duplicate_detector);
+            }
+            if ndjson {
+                reporter.print_stream_summary();
+            } else {
+                reporter.print(&backup_dir);
+            }
         }
         Subcommand::Restore {
             preview,
             path,
             force,
             api,
+            summary,
             gui,
             sort,
             backup,
+            before,
+            after,
             cloud_sync,
             no_cloud_sync,
             dump_registry,
+            duplicates,
+            constructive,
+            interactive,
+            yes,
+            file,
+            target_root,
+            force_redirect_fallback,
+            registry_hive,
+            exclude,
+            games_from,
             games,
         } => {
-            let games = parse_games(games);
+            let games = parse_games_with_file(games, games_from)?;
 
             let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            if summary {
+                reporter.summarize();
+            }
 
             let restore_dir = match path {
                 None => config.restore.path.clone(),
@@ -399,26 +681,97 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 return Ok(());
             }
 
-            let layout = BackupLayout::new(restore_dir.clone());
+            let layout = BackupLayout::new(restore_dir.clone())
+                .with_redirect_fallback(force_redirect_fallback || config.restore.redirect_fallback)
+                .with_wine_prefix(config.restore.wine_prefix.clone())
+                .with_conflict_policy(config.restore.conflict_policy)
+                .with_empty_dir_exclusion(config.backup.filter.exclude_empty_dirs)
+                .with_registry_filter(config.backup.filter.clone())
+                .with_zip_password(zip_password(&config, force, gui, api));
 
-            if backup.is_some() && games.len() != 1 {
+            if (backup.is_some() || interactive) && games.len() != 1 {
                 return Err(Error::CliBackupIdWithMultipleGames);
             }
+            if let (Some(after), Some(before)) = (&after, &before) {
+                if after >= before {
+                    return Err(Error::CliInvalidTimeWindow);
+                }
+            }
             let backup_id = backup.as_ref().map(|x| BackupId::Named(x.clone()));
+            let time_window = before.is_some() || after.is_some();
 
             let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
             let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
 
             let games_specified = !games.is_empty();
-            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
+            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder)
+                .and_then(|games| exclude_games(games, layout.restorable_game_set(), exclude, &title_finder))
+            {
                 Ok(games) => games,
                 Err(games) => {
                     reporter.trip_unknown_games(games.clone());
-                    reporter.print_failure();
-                    return Err(Error::CliUnrecognizedGames { games });
+                    let error = Error::CliUnrecognizedGames { games };
+                    *failure_reported = reporter.print_failure(&error);
+                    return Err(error);
+                }
+            };
+
+            let backup_id = match games.as_slice() {
+                [name] if interactive => {
+                    let backups = layout.game_layout(name).restorable_backups_flattened();
+                    let Some(latest) = backups.len().checked_sub(1) else {
+                        return Err(Error::CliInvalidBackupId);
+                    };
+                    let choice = if yes {
+                        latest
+                    } else {
+                        let options: Vec<_> = backups
+                            .iter()
+                            .map(|backup| match backup.comment() {
+                                Some(comment) => format!("{} - {comment}", backup.label()),
+                                None => backup.label(),
+                            })
+                            .collect();
+                        ui::select(&TRANSLATOR.cli_select_backup(name, backups.len()), &options)?
+                    };
+                    Some(backups[choice].id())
                 }
+                _ => backup_id,
             };
 
+            let unknown_target_roots: Vec<_> = target_root
+                .iter()
+                .filter(|x| !games.contains(&x.game))
+                .map(|x| x.game.clone())
+                .collect();
+            if !unknown_target_roots.is_empty() {
+                reporter.trip_unknown_games(unknown_target_roots.clone());
+                let error = Error::CliUnrecognizedGames {
+                    games: unknown_target_roots,
+                };
+                *failure_reported = reporter.print_failure(&error);
+                return Err(error);
+            }
+            let target_roots: HashMap<_, _> = target_root.into_iter().map(|x| (x.game, x.path)).collect();
+
+            let now = chrono::Utc::now();
+            let recently_restored: Vec<_> = games
+                .iter()
+                .filter(|x| games_specified || config.is_game_enabled_for_restore(x))
+                .filter(|x| cache.restore.recent_warning(x, &now).is_some())
+                .map(|x| config.display_name(x))
+                .collect();
+            if !recently_restored.is_empty()
+                && !ui::confirm(
+                    gui,
+                    force,
+                    preview,
+                    &TRANSLATOR.confirm_restore_recent(&recently_restored),
+                )?
+            {
+                return Ok(());
+            }
+
             let cloud_sync = negatable_flag(
                 cloud_sync && !preview,
                 no_cloud_sync,
@@ -447,6 +800,13 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
+            let mut toggled_registry = config.restore.toggled_registry.clone();
+            if !registry_hive.is_empty() {
+                for game in &games {
+                    toggled_registry.limit_hives(game, &registry_hive);
+                }
+            }
+
             log::info!("beginning restore with {} steps", games.len());
 
             let mut info: Vec<_> = games
@@ -456,14 +816,59 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 .filter_map(|(i, name)| {
                     log::trace!("step {i} / {}: {name}", games.len());
                     let mut layout = layout.game_layout(name);
-                    let scan_info = layout.scan_for_restoration(
+                    let backup_id = if time_window {
+                        match layout.latest_backup_between(after.as_ref(), before.as_ref()) {
+                            Some(backup) => Some(backup.id()),
+                            None => {
+                                log::info!("[{name}] no backups within the requested time window");
+                                return None;
+                            }
+                        }
+                    } else {
+                        backup_id.clone()
+                    };
+                    let mut scan_info = layout.scan_for_restoration(
                         name,
                         backup_id.as_ref().unwrap_or(&BackupId::Latest),
                         &config.redirects,
                         config.restore.reverse_redirects,
                         &config.restore.toggled_paths,
-                        &config.restore.toggled_registry,
+                        &toggled_registry,
                     );
+                    if let Some(target) = target_roots.get(name) {
+                        let Some(source) = scan_info.restore_root() else {
+                            log::trace!("step {i} completed (no target root)");
+                            let display_title = config.display_name(name);
+                            return Some((
+                                display_title,
+                                scan_info,
+                                Default::default(),
+                                OperationStepDecision::Processed,
+                                Some(Err(Error::CliTargetRootUnavailable { game: name.clone() })),
+                            ));
+                        };
+                        let redirects = with_final_restore_redirect(
+                            &config.redirects,
+                            source,
+                            target.clone(),
+                            config.restore.reverse_redirects,
+                        );
+                        scan_info = layout.scan_for_restoration(
+                            name,
+                            backup_id.as_ref().unwrap_or(&BackupId::Latest),
+                            &redirects,
+                            config.restore.reverse_redirects,
+                            &config.restore.toggled_paths,
+                            &toggled_registry,
+                        );
+                    }
+                    if constructive {
+                        scan_info.skip_destructive_restore();
+                    }
+                    let filter = file.as_ref().map(|glob| scan_info.restore_files_matching(glob));
+                    if let Some(filter) = &filter {
+                        scan_info.restrict_restore(filter);
+                    }
                     let ignored = !&config.is_game_enabled_for_restore(name) && !games_specified;
                     let decision = if ignored {
                         OperationStepDecision::Ignored
@@ -487,10 +892,16 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         }
                     }
 
-                    let restore_info = if scan_info.backup.is_none() || preview || ignored {
+                    let restore_info = if scan_info.backup.is_none() || ignored {
                         crate::scan::BackupInfo::default()
                     } else {
-                        layout.restore(&scan_info, &config.restore.toggled_registry)
+                        let mut restore_info = if preview {
+                            crate::scan::BackupInfo::default()
+                        } else {
+                            layout.restore(&scan_info, &toggled_registry, filter.as_deref())
+                        };
+                        restore_info.warnings.extend(cache.restore.recent_warning(name, &now));
+                        restore_info
                     };
                     log::trace!("step {i} completed");
                     if !scan_info.can_report_game() {
@@ -503,6 +914,21 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 .collect();
             log::info!("completed restore");
 
+            if !preview {
+                // Record the restore even if some items failed,
+                // since the rest of the live data was still replaced.
+                for (_, scan_info, restore_info, decision, failure) in info.iter() {
+                    if failure.is_none()
+                        && *decision == OperationStepDecision::Processed
+                        && scan_info.backup.is_some()
+                        && scan_info.restored_anything(restore_info)
+                    {
+                        cache.restore.last_restored.insert(scan_info.game_name.clone(), now);
+                    }
+                }
+                cache.save();
+            }
+
             for (_, scan_info, _, _, failure) in info.iter() {
                 if let Some(failure) = failure {
                     return failure.clone();
@@ -543,6 +969,9 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     failed = true;
                 }
             }
+            if duplicates {
+                reporter.add_duplicates(&duplicate_detector);
+            }
             reporter.print(&restore_dir);
         }
         Subcommand::Complete { shell } => {
@@ -560,7 +989,64 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 &mut std::io::stdout(),
             )
         }
-        Subcommand::Backups { path, api, games } => {
+        Subcommand::Backups {
+            sub: Some(BackupsSubcommand::FindFile { path, api, file }),
+            ..
+        } => {
+            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            reporter.suppress_overall();
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout = BackupLayout::new(restore_dir.clone());
+            let coverage = layout.find_file(&file);
+
+            for covered in &coverage {
+                reporter.add_file_coverage(config.display_name(&covered.game), covered);
+            }
+
+            if coverage.is_empty() {
+                let error = Error::CliFileNotBackedUp { path: file };
+                *failure_reported = reporter.print_failure(&error);
+                return Err(error);
+            }
+
+            reporter.print(&restore_dir);
+        }
+        Subcommand::Backups {
+            sub: Some(BackupsSubcommand::ClearRestored { path, games }),
+            ..
+        } => {
+            let games = parse_games(games);
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout = BackupLayout::new(restore_dir);
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => return Err(Error::CliUnrecognizedGames { games }),
+            };
+
+            for name in games {
+                cache.restore.last_restored.remove(&name);
+            }
+            cache.save();
+        }
+        Subcommand::Backups {
+            sub: None,
+            path,
+            api,
+            games,
+        } => {
             let games = parse_games(games);
 
             let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
@@ -575,12 +1061,14 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
             let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
 
+            let games_specified = !games.is_empty();
             let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
                 Ok(games) => games,
                 Err(games) => {
                     reporter.trip_unknown_games(games.clone());
-                    reporter.print_failure();
-                    return Err(Error::CliUnrecognizedGames { games });
+                    let error = Error::CliUnrecognizedGames { games };
+                    *failure_reported = reporter.print_failure(&error);
+                    return Err(error);
                 }
             };
 
@@ -599,8 +1087,210 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             for (name, display_title, backup_dir, backups) in info {
                 reporter.add_backups(name, display_title, backup_dir, &backups);
             }
+            if !games_specified {
+                reporter.add_total_size(layout.total_size());
+            }
             reporter.print(&restore_dir);
         }
+        Subcommand::Maintain {
+            preview,
+            path,
+            force,
+            api,
+            full_limit,
+            differential_limit,
+            games,
+        } => {
+            let games = parse_games(games);
+
+            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            reporter.suppress_overall();
+
+            let backup_dir = match path {
+                None => config.backup.path.clone(),
+                Some(p) => p,
+            };
+
+            if !ui::confirm(false, force, preview, &TRANSLATOR.confirm_maintenance(&backup_dir))? {
+                return Ok(());
+            }
+
+            let layout = BackupLayout::new(backup_dir.clone()).with_mirrors(config.backup.mirrors.clone());
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => {
+                    reporter.trip_unknown_games(games.clone());
+                    let error = Error::CliUnrecognizedGames { games };
+                    *failure_reported = reporter.print_failure(&error);
+                    return Err(error);
+                }
+            };
+
+            let now = chrono::Utc::now();
+            let info: Vec<_> = games
+                .par_iter()
+                .progress_count(games.len() as u64)
+                .filter_map(|name| {
+                    let mut layout = layout.try_game_layout(name)?;
+                    let retention = config
+                        .backup
+                        .retention_for(name)
+                        .with_limits(full_limit, differential_limit);
+                    let info = layout.maintain(retention, &now, preview);
+                    Some((name, config.display_name(name), info))
+                })
+                .collect();
+
+            let mut freed_bytes = layout.collect_garbage(preview);
+            for (name, display_title, info) in info {
+                freed_bytes += info.freed_bytes;
+                reporter.add_maintenance(name, display_title, &info);
+            }
+            reporter.add_maintenance_total(freed_bytes);
+            reporter.print(&backup_dir);
+        }
+        Subcommand::Scrub { path, api, games } => {
+            let games = parse_games(games);
+
+            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            reporter.suppress_overall();
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout =
+                BackupLayout::new(restore_dir.clone()).with_zip_password(zip_password(&config, false, false, api));
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => {
+                    reporter.trip_unknown_games(games.clone());
+                    let error = Error::CliUnrecognizedGames { games };
+                    *failure_reported = reporter.print_failure(&error);
+                    return Err(error);
+                }
+            };
+
+            let info: Vec<_> = games
+                .par_iter()
+                .progress_count(games.len() as u64)
+                .filter_map(|name| {
+                    let layout = layout.try_game_layout(name)?;
+                    let diverged = layout.scrub(BackupId::Latest);
+                    Some((name, config.display_name(name), diverged))
+                })
+                .collect();
+
+            for (name, display_title, diverged) in info {
+                if !reporter.add_scrub(name, display_title, &diverged) {
+                    failed = true;
+                }
+            }
+            reporter.print(&restore_dir);
+        }
+        Subcommand::Merge {
+            path,
+            force,
+            api,
+            backup,
+            keep,
+            games,
+        } => {
+            let games = parse_games(games);
+
+            let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
+            reporter.suppress_overall();
+
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            if !ui::confirm(false, force, false, &TRANSLATOR.confirm_merge(&restore_dir, !keep))? {
+                return Ok(());
+            }
+
+            let layout =
+                BackupLayout::new(restore_dir.clone()).with_zip_password(zip_password(&config, force, false, api));
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            if backup.is_some() && games.len() != 1 {
+                return Err(Error::CliBackupIdWithMultipleGames);
+            }
+            let backup_id = backup.map(BackupId::Named).unwrap_or(BackupId::Latest);
+
+            let games = match evaluate_games(layout.restorable_game_set(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => {
+                    reporter.trip_unknown_games(games.clone());
+                    let error = Error::CliUnrecognizedGames { games };
+                    *failure_reported = reporter.print_failure(&error);
+                    return Err(error);
+                }
+            };
+
+            let now = chrono::Utc::now();
+            let info: Vec<_> = games
+                .par_iter()
+                .progress_count(games.len() as u64)
+                .filter_map(|name| {
+                    let mut layout = layout.try_game_layout(name)?;
+                    if layout.verify_id(&backup_id) != backup_id {
+                        return Some((name, config.display_name(name), Err(Error::CliInvalidBackupId)));
+                    }
+                    match layout.merge(&backup_id, &now, &config.backup.format_for(name), !keep) {
+                        Err(Error::NoDifferentialBackupToMerge) => None,
+                        merged => Some((name, config.display_name(name), merged)),
+                    }
+                })
+                .collect();
+
+            for (name, display_title, merged) in info {
+                if !reporter.add_merge(name, display_title, &merged) {
+                    failed = true;
+                }
+            }
+            reporter.print(&restore_dir);
+        }
+        Subcommand::Delete { path, force, id, game } => {
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout = BackupLayout::new(restore_dir.clone()).with_mirrors(config.backup.mirrors_for(&restore_dir));
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let Some((name, mut game_layout)) = title_finder
+                .find_one_by_name(&game)
+                .and_then(|name| layout.try_game_layout(&name).map(|x| (name, x)))
+            else {
+                return Err(Error::CliUnrecognizedGames { games: vec![game] });
+            };
+
+            let backup_id = BackupId::Named(id.clone());
+            if game_layout.verify_id(&backup_id) != backup_id {
+                return Err(Error::CliInvalidBackupId);
+            }
+
+            let dependents = game_layout.dependent_backups(&backup_id);
+            let msg = TRANSLATOR.confirm_delete_backup(config.display_name(&name), &id, &dependents);
+            if !ui::confirm(false, force, false, &msg)? {
+                return Ok(());
+            }
+
+            game_layout.delete_backup(&backup_id)?;
+            layout.collect_garbage(false);
+        }
         Subcommand::Find {
             api,
             multiple,
@@ -610,6 +1300,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
             steam_id,
             gog_id,
             lutris_id,
+            save_dir,
             normalized,
             fuzzy,
             disabled,
@@ -636,6 +1327,7 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 steam_id,
                 gog_id,
                 lutris_id,
+                save_dir: save_dir.clone(),
                 normalized,
                 fuzzy,
                 backup,
@@ -653,21 +1345,331 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 if let Some(gog_id) = gog_id {
                     invalid.push(gog_id.to_string());
                 }
+                if let Some(save_dir) = save_dir {
+                    invalid.push(save_dir.render());
+                }
                 reporter.trip_unknown_games(invalid.clone());
-                reporter.print_failure();
-                return Err(Error::CliUnrecognizedGames { games: invalid });
+                let error = Error::CliUnrecognizedGames { games: invalid };
+                *failure_reported = reporter.print_failure(&error);
+                return Err(error);
             }
 
             reporter.print(&restore_dir);
         }
+        Subcommand::DumpRegistry { path, id, output, game } => {
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout = BackupLayout::new(restore_dir).with_zip_password(zip_password(&config, false, false, false));
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let Some((name, game_layout)) = title_finder
+                .find_one_by_name(&game)
+                .and_then(|name| Some((name.clone(), layout.try_game_layout(&name)?)))
+            else {
+                return Err(Error::CliUnrecognizedGames { games: vec![game] });
+            };
+
+            let backup_id = id.map(BackupId::Named).unwrap_or(BackupId::Latest);
+            if game_layout.verify_id(&backup_id) != backup_id {
+                return Err(Error::CliInvalidBackupId);
+            }
+
+            let Some(hives) = game_layout.registry_content(&backup_id) else {
+                return Err(Error::CliBackupHasNoRegistry);
+            };
+
+            match output {
+                Some(output) => {
+                    let output = export_target(&config, output, Some(&name), 1, "reg")?;
+                    if output
+                        .create_parent_dir()
+                        .and_then(|_| output.write_with_bytes(&hives.to_regedit_bytes()))
+                        .is_err()
+                    {
+                        return Err(Error::UnableToWriteFile(output));
+                    }
+                }
+                None => print!("{}", hives.to_regedit_string()),
+            }
+        }
+        Subcommand::Diff {
+            path,
+            api,
+            game,
+            from,
+            to,
+        } => {
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout = BackupLayout::new(restore_dir).with_zip_password(zip_password(&config, false, false, api));
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let Some(game_layout) = title_finder
+                .find_one_by_name(&game)
+                .and_then(|name| layout.try_game_layout(&name))
+            else {
+                return Err(Error::CliUnrecognizedGames { games: vec![game] });
+            };
+
+            let from = BackupId::Named(from);
+            let to = BackupId::Named(to);
+            if game_layout.verify_id(&from) != from || game_layout.verify_id(&to) != to {
+                return Err(Error::CliInvalidBackupId);
+            }
+
+            let diff = game_layout.diff(&from, &to);
+
+            if api {
+                println!("{}", serde_json::to_string_pretty(&diff).unwrap());
+            } else {
+                for file in &diff.added {
+                    println!("+ {file}");
+                }
+                for file in &diff.removed {
+                    println!("- {file}");
+                }
+                for file in &diff.changed {
+                    println!("~ {file}");
+                }
+                if diff.registry_changed {
+                    println!("~ {}", registry::Format::Reg.filename());
+                }
+            }
+        }
+        Subcommand::Locate { path, id, game } => {
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout = BackupLayout::new(restore_dir);
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let Some(game_layout) = title_finder
+                .find_one_by_name(&game)
+                .and_then(|name| layout.try_game_layout(&name))
+            else {
+                return Err(Error::CliUnrecognizedGames { games: vec![game] });
+            };
+
+            let backup_id = id.map(BackupId::Named).unwrap_or(BackupId::Latest);
+            if game_layout.verify_id(&backup_id) != backup_id {
+                return Err(Error::CliInvalidBackupId);
+            }
+
+            let files = game_layout.locate(&backup_id);
+            println!("{}", serde_json::to_string_pretty(&files).unwrap());
+        }
+        Subcommand::Export { path, out, game } => {
+            let restore_dir = match path {
+                None => config.restore.path.clone(),
+                Some(p) => p,
+            };
+
+            let layout = BackupLayout::new(restore_dir);
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let Some((name, game_layout)) = title_finder
+                .find_one_by_name(&game)
+                .and_then(|name| Some((name.clone(), layout.try_game_layout(&name)?)))
+            else {
+                return Err(Error::CliUnrecognizedGames { games: vec![game] });
+            };
+
+            let out = export_target(&config, out, Some(&name), 1, "zip")?;
+
+            if let Err(e) = game_layout.export_bundle(&out) {
+                log::error!("Unable to export bundle: {:?} | {e}", &out);
+                return Err(Error::UnableToWriteFile(out));
+            }
+        }
+        Subcommand::Import { path, merge, bundle } => {
+            let backup_dir = match path {
+                None => config.backup.path.clone(),
+                Some(p) => p,
+            };
+
+            prepare_backup_target(&backup_dir)?;
+
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let mut layout = BackupLayout::new(backup_dir.clone())
+                .with_folder_template(config.backup.folder_template.clone())
+                .with_filename_policy(config.filename_policy());
+
+            let game = layout.import_bundle(&bundle, &manifest, merge)?;
+            println!("{}", layout.game_folder(&game).render());
+        }
+        Subcommand::ImportExternal {
+            path,
+            format,
+            map,
+            source,
+        } => {
+            let backup_dir = match path {
+                None => config.backup.path.clone(),
+                Some(p) => p,
+            };
+
+            if !source.is_dir() {
+                return Err(Error::UnableToOpenDir(source));
+            }
+            prepare_backup_target(&backup_dir)?;
+
+            let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+            let layout = BackupLayout::new(backup_dir.clone())
+                .with_folder_template(config.backup.folder_template.clone())
+                .with_filename_policy(config.filename_policy());
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+
+            let mut unmatched = vec![];
+            let mut matched = vec![];
+            for game in format.importer().discover(&source) {
+                let name = match map.iter().find(|x| x.folder == game.label) {
+                    Some(mapping) => title_finder.find_one_by_name(&mapping.game),
+                    None => title_finder.find_one_by_normalized_name(&game.label),
+                };
+                match name {
+                    Some(name) => matched.push((game, name)),
+                    None => unmatched.push(game.label),
+                }
+            }
+
+            let roots = config.expanded_roots();
+            let subjects: Vec<_> = matched.iter().map(|(_, name)| name.clone()).collect();
+            let launchers = Launchers::scan(&roots, &manifest, &subjects, &title_finder, None);
+            let steam_shortcuts = SteamShortcuts::scan();
+
+            for (game, name) in matched {
+                let mut game_layout = layout.game_layout_for(&name, manifest.0.get(&name));
+                if game_layout.has_backups() {
+                    println!(
+                        "{}",
+                        TRANSLATOR.cli_external_game_already_backed_up(&game.source, &name)
+                    );
+                    continue;
+                }
+
+                let Some(target) = manifest
+                    .0
+                    .get(&name)
+                    .map(|data| {
+                        plan_game_for_backup(
+                            data,
+                            &name,
+                            &roots,
+                            &app_dir(),
+                            &launchers,
+                            &config.backup.filter,
+                            None,
+                            &steam_shortcuts,
+                        )
+                    })
+                    .and_then(|plan| external::restore_target(&plan))
+                else {
+                    println!(
+                        "{}",
+                        TRANSLATOR.cli_external_game_without_save_location(&game.source, &name)
+                    );
+                    continue;
+                };
+
+                let scan = match external::scan_external_game(&game, &name, &target, config.backup.hash) {
+                    Ok(scan) => scan,
+                    Err(file) => {
+                        println!("{}", TRANSLATOR.cli_external_game_failed(&game.source, &name));
+                        eprintln!("{}", TRANSLATOR.unable_to_read_file(&file));
+                        failed = true;
+                        continue;
+                    }
+                };
+                let when = external::last_modified(&game).unwrap_or_else(chrono::Utc::now);
+                let backup_info = game_layout.back_up(
+                    &scan,
+                    &when,
+                    &config.backup.format_for(&name),
+                    config.backup.retention_for(&name),
+                    false,
+                    config.backup.checksum,
+                    None,
+                );
+                match backup_info {
+                    Some(info) if info.successful() => {
+                        println!("{}", TRANSLATOR.cli_imported_external_game(&game.source, &name));
+                    }
+                    Some(_) => {
+                        println!("{}", TRANSLATOR.cli_external_game_failed(&game.source, &name));
+                        failed = true;
+                    }
+                    None => {}
+                }
+            }
+
+            if !unmatched.is_empty() {
+                println!("{}", TRANSLATOR.cli_unmatched_external_folders(&unmatched));
+            }
+        }
         Subcommand::Manifest { sub: manifest_sub } => match manifest_sub {
-            ManifestSubcommand::Show { api } => {
+            ManifestSubcommand::Show { api, output } => {
                 let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
 
+                let content = if api {
+                    serde_json::to_string(&manifest).unwrap()
+                } else {
+                    serde_yaml::to_string(&manifest).unwrap()
+                };
+
+                match output {
+                    Some(output) => {
+                        let extension = if api { "json" } else { "yaml" };
+                        let output = export_target(&config, output, None, manifest.0.len(), extension)?;
+                        if output
+                            .create_parent_dir()
+                            .and_then(|_| output.write_with_content(&content))
+                            .is_err()
+                        {
+                            return Err(Error::UnableToWriteFile(output));
+                        }
+                    }
+                    None => println!("{content}"),
+                }
+            }
+            ManifestSubcommand::List {
+                store,
+                tag,
+                with_notes,
+                api,
+            } => {
+                let manifest = load_manifest(&config, &mut cache, true, false).unwrap_or_default();
+
+                let mut games: Vec<&str> = match store {
+                    Some(store) => metadata::find_games_by_store(&manifest, store),
+                    None => manifest.0.keys().map(|x| x.as_str()).collect(),
+                };
+                if let Some(tag) = tag {
+                    let tagged = metadata::find_games_by_tag(&manifest, &tag);
+                    games.retain(|x| tagged.binary_search(x).is_ok());
+                }
+                if with_notes {
+                    let noted = metadata::find_games_with_notes(&manifest);
+                    games.retain(|x| noted.binary_search(x).is_ok());
+                }
+
                 if api {
-                    println!("{}", serde_json::to_string(&manifest).unwrap());
+                    println!("{}", serde_json::to_string(&games).unwrap());
                 } else {
-                    println!("{}", serde_yaml::to_string(&manifest).unwrap());
+                    for game in games {
+                        println!("{game}");
+                    }
                 }
             }
             ManifestSubcommand::Update { force } => {
@@ -686,6 +1688,20 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     println!("{}", serde_yaml::to_string(&config).unwrap());
                 }
             }
+            ConfigSubcommand::Get { api, key } => {
+                let value = config.get_by_key(&key)?;
+
+                if api {
+                    println!("{}", serde_json::to_string(&value).unwrap());
+                } else {
+                    let serialized = serde_yaml::to_string(&value).unwrap();
+                    println!("{}", serialized.trim_start_matches("---").trim());
+                }
+            }
+            ConfigSubcommand::Set { key, value } => {
+                config.set_by_key(&key, &value)?;
+                config.save();
+            }
         },
         Subcommand::Cloud { sub: cloud_sub } => match cloud_sub {
             parse::CloudSubcommand::Set { sub } => match sub {
@@ -748,12 +1764,31 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 parse::CloudSetSubcommand::Smb {
                     host,
                     port,
+                    share,
                     username,
                     password,
                 } => {
                     configure_cloud(
                         &mut config,
                         Remote::Smb {
+                            id: Remote::generate_id(),
+                            host,
+                            port,
+                            share,
+                            username,
+                            password,
+                        },
+                    )?;
+                }
+                parse::CloudSetSubcommand::Sftp {
+                    host,
+                    port,
+                    username,
+                    password,
+                } => {
+                    configure_cloud(
+                        &mut config,
+                        Remote::Sftp {
                             id: Remote::generate_id(),
                             host,
                             port,
@@ -779,6 +1814,46 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         },
                     )?;
                 }
+                parse::CloudSetSubcommand::S3 {
+                    bucket,
+                    region,
+                    endpoint,
+                    access_key_id,
+                    secret_access_key,
+                } => {
+                    let remote = Remote::S3 {
+                        id: Remote::generate_id(),
+                        bucket,
+                        region,
+                        endpoint,
+                        access_key_id,
+                        secret_access_key,
+                    };
+                    if !remote.is_complete() {
+                        return Err(Error::CloudNotConfigured);
+                    }
+                    configure_cloud(&mut config, remote)?;
+                }
+                parse::CloudSetSubcommand::B2 { bucket, account, key } => {
+                    configure_cloud(
+                        &mut config,
+                        Remote::B2 {
+                            id: Remote::generate_id(),
+                            bucket,
+                            account,
+                            key,
+                        },
+                    )?;
+                }
+                parse::CloudSetSubcommand::Local { path } => {
+                    configure_cloud(
+                        &mut config,
+                        Remote::Local {
+                            id: Remote::generate_id(),
+                            path,
+                        },
+                    )?;
+                }
             },
             parse::CloudSubcommand::Upload {
                 local,
@@ -806,8 +1881,9 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     Err(games) => {
                         let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
                         reporter.trip_unknown_games(games.clone());
-                        reporter.print_failure();
-                        return Err(Error::CliUnrecognizedGames { games });
+                        let error = Error::CliUnrecognizedGames { games };
+                        *failure_reported = reporter.print_failure(&error);
+                        return Err(error);
                     }
                 };
 
@@ -849,8 +1925,9 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     Err(games) => {
                         let mut reporter = if api { Reporter::json() } else { Reporter::standard() };
                         reporter.trip_unknown_games(games.clone());
-                        reporter.print_failure();
-                        return Err(Error::CliUnrecognizedGames { games });
+                        let error = Error::CliUnrecognizedGames { games };
+                        *failure_reported = reporter.print_failure(&error);
+                        return Err(error);
                     }
                 };
 
@@ -937,6 +2014,29 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
 
+            // Determine how to launch the game
+            let launch = if commands.is_empty() {
+                let inferred = game_name.as_ref().and_then(|game_name| {
+                    let roots = config.expanded_roots();
+                    let launchers = Launchers::scan(&roots, &manifest, &[game_name.clone()], &title_finder, None);
+                    wrap::infer_launch_command(game_name, &manifest, &roots, &launchers)
+                });
+                match inferred {
+                    Some(launch) => launch,
+                    None => {
+                        let error = Error::GameLaunchCommandUnknown;
+                        ui::alert_with_error(gui, force, &TRANSLATOR.game_did_not_launch(), &error)?;
+                        return Err(error);
+                    }
+                }
+            } else {
+                wrap::LaunchCommand {
+                    program: commands[0].clone(),
+                    args: commands[1..].to_vec(),
+                    working_dir: None,
+                }
+            };
+
             // Restore
             // TODO: Detect if saves are unchanged and skip the question?
             'restore: {
@@ -963,22 +2063,36 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     break 'restore;
                 }
 
-                if let Err(err) = run(
+                if let Err(err) = run_subcommand(
                     Subcommand::Restore {
+                        games_from: None,
                         games: vec![game_name.clone()],
                         force: true,
                         preview,
                         path: path.clone(),
                         api: Default::default(),
+                        summary: Default::default(),
                         gui: Default::default(),
                         sort: Default::default(),
                         backup: Default::default(),
+                        before: Default::default(),
+                        after: Default::default(),
                         cloud_sync,
                         no_cloud_sync,
                         dump_registry: Default::default(),
+                        duplicates: Default::default(),
+                        constructive: Default::default(),
+                        interactive: Default::default(),
+                        yes: Default::default(),
+                        file: None,
+                        target_root: vec![],
+                        force_redirect_fallback: false,
+                        registry_hive: vec![],
+                        exclude: vec![],
                     },
                     no_manifest_update,
                     try_manifest_update,
+                    failure_reported,
                 ) {
                     log::error!("Wrap failed to restore game {:?} with: {:?}", wrap_game_info, err);
                     ui::alert_with_error(gui, force, &TRANSLATOR.restore_one_game_failed(game_name), &err)?;
@@ -988,7 +2102,12 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 
             // Launch game
             // TODO: Legendary returns immediately. Can we handle that?
-            let result = Command::new(&commands[0]).args(&commands[1..]).status();
+            let mut command = Command::new(&launch.program);
+            command.args(&launch.args);
+            if let Some(working_dir) = launch.working_dir.as_ref().and_then(|x| x.as_std_path_buf().ok()) {
+                command.current_dir(working_dir);
+            }
+            let result = command.status();
             match result {
                 Ok(status) => {
                     log::debug!("Wrapped game command executed, returning status: {:#?}", status);
@@ -1010,14 +2129,17 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                     break 'backup;
                 }
 
-                if let Err(err) = run(
+                if let Err(err) = run_subcommand(
                     Subcommand::Backup {
+                        games_from: None,
                         games: vec![game_name.clone()],
                         force: true,
                         preview,
                         path,
                         wine_prefix: Default::default(),
                         api: Default::default(),
+                        ndjson: Default::default(),
+                        summary: Default::default(),
                         gui: Default::default(),
                         sort: Default::default(),
                         format,
@@ -1028,9 +2150,17 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                         cloud_sync,
                         no_cloud_sync,
                         dump_registry: Default::default(),
+                        duplicates: Default::default(),
+                        only_tag: Default::default(),
+                        exclude_tag: Default::default(),
+                        batch: Default::default(),
+                        comment: Default::default(),
+                        full_scan: Default::default(),
+                        exclude: vec![],
                     },
                     no_manifest_update,
                     try_manifest_update,
+                    failure_reported,
                 ) {
                     log::error!("Wrap failed to back up with: {:#?}", err);
                     ui::alert_with_error(gui, force, &TRANSLATOR.back_up_one_game_failed(game_name), &err)?;
@@ -1038,6 +2168,97 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 }
             }
         }
+        Subcommand::Schedule {
+            interval,
+            once_per_day,
+            games,
+        } => {
+            let games = parse_games(games);
+
+            let manifest = load_manifest(&config, &mut cache, no_manifest_update, try_manifest_update)?;
+            let layout = BackupLayout::new(config.backup.path.clone());
+            let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
+            let games = match evaluate_games(BTreeSet::new(), games, &title_finder) {
+                Ok(games) => games,
+                Err(games) => return Err(Error::CliUnrecognizedGames { games }),
+            };
+
+            log::info!("Starting scheduled backups every {:?}", interval);
+
+            // When backing up all games, checking each one's latest backup would mean
+            // scanning the whole backup folder, so we track our own runs instead.
+            let mut last_run = None;
+
+            loop {
+                match Config::load() {
+                    Ok(x) => config = x,
+                    Err(e) => log::warn!("Unable to reload config; using previous config: {e:?}"),
+                }
+                // Nobody is watching, so don't risk backing up saves that are being written.
+                config.backup.skip_running_games = true;
+                // The daemon shouldn't stop just because the manifest couldn't be updated.
+                let manifest = load_manifest(&config, &mut cache, no_manifest_update, true).unwrap_or_default();
+
+                let today = chrono::Local::now().date_naive();
+                let subjects = if games.is_empty() {
+                    None
+                } else if once_per_day {
+                    let layout = BackupLayout::new(config.backup.path.clone());
+                    Some(
+                        games
+                            .iter()
+                            .filter(|name| {
+                                layout
+                                    .last_backup_time(name)
+                                    .map(|when| when.with_timezone(&chrono::Local).date_naive())
+                                    != Some(today)
+                            })
+                            .cloned()
+                            .collect::<Vec<_>>(),
+                    )
+                } else {
+                    Some(games.clone())
+                };
+
+                if once_per_day && subjects.is_none() && last_run == Some(today) {
+                    log::info!("Scheduled backup skipped: already ran today");
+                } else if subjects.as_ref().is_some_and(|x| x.is_empty()) {
+                    log::info!("Scheduled backup skipped: nothing left to back up today");
+                } else {
+                    let progress_bar = scan_progress_bar(0);
+                    let results =
+                        api::back_up_with_progress(&config, &manifest, subjects.as_deref(), Finality::Final, |event| {
+                            progress_bar.set_length(event.total as u64);
+                            progress_bar.set_position(event.current as u64);
+                        });
+                    progress_bar.finish_and_clear();
+                    last_run = Some(today);
+
+                    let mut found = 0;
+                    let mut failed = vec![];
+                    for (scan_info, backup_info) in &results {
+                        if scan_info.found_anything() {
+                            found += 1;
+                        }
+                        if backup_info.as_ref().is_some_and(|x| !x.successful()) {
+                            failed.push(scan_info.game_name.clone());
+                        }
+                    }
+
+                    if failed.is_empty() {
+                        log::info!("Scheduled backup finished: {found} game(s) with data");
+                    } else {
+                        log::warn!(
+                            "Scheduled backup finished: {found} game(s) with data, {} failed: {:?}",
+                            failed.len(),
+                            failed
+                        );
+                    }
+                }
+
+                std::thread::sleep(interval);
+            }
+        }
         Subcommand::Api { input } => {
             TRANSLATOR.set_language(Language::English);
 
@@ -1064,7 +2285,8 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
                 parse::SchemaSubcommand::ApiInput => schemars::schema_for!(api::Input),
                 parse::SchemaSubcommand::ApiOutput => schemars::schema_for!(api::Output),
                 parse::SchemaSubcommand::GeneralOutput => schemars::schema_for!(report::JsonOutput),
-                parse::SchemaSubcommand::Config => schemars::schema_for!(Config),
+                parse::SchemaSubcommand::Config => api::config_schema(),
+                parse::SchemaSubcommand::Manifest => api::manifest_schema(),
             };
 
             let serialized = match format {
@@ -1082,13 +2304,33 @@ pub fn run(sub: Subcommand, no_manifest_update: bool, try_manifest_update: bool)
 }
 
 fn configure_cloud(config: &mut Config, remote: Remote) -> Result<(), Error> {
+    let rclone = Rclone::new(config.apps.rclone.clone(), remote.clone());
+
+    // Sign in first so that the old remote stays intact if the user gives up.
+    let token = if remote.uses_browser_authorization() {
+        eprintln!(
+            "{}",
+            TRANSLATOR.waiting_for_cloud_authorization(&RemoteChoice::from(Some(&remote)).to_string())
+        );
+
+        let interrupted = register_sigint();
+        let authorized = rclone.authorize().and_then(|mut authorization| loop {
+            if let Some(outcome) = authorization.poll(&interrupted) {
+                break outcome;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        });
+        unregister_sigint();
+        Some(authorized.map_err(Error::UnableToConfigureCloud)?)
+    } else {
+        None
+    };
+
     if let Some(old_remote) = config.cloud.remote.as_ref() {
         _ = Rclone::new(config.apps.rclone.clone(), old_remote.clone()).unconfigure_remote();
     }
 
-    Rclone::new(config.apps.rclone.clone(), remote.clone())
-        .configure_remote()
-        .map_err(Error::UnableToConfigureCloud)?;
+    rclone.configure_remote(token).map_err(Error::UnableToConfigureCloud)?;
 
     config.cloud.remote = Some(remote);
     config.save();
@@ -1137,16 +2379,25 @@ fn sync_cloud(
 
     let remote = crate::cloud::validate_cloud_config(config, cloud)?;
 
+    let layout = BackupLayout::new(local.clone())
+        .with_folder_template(config.backup.folder_template.clone())
+        .with_filename_policy(config.filename_policy());
+    let timestamp_solo = config.cloud.timestamped_backups && finality == Finality::Final;
+    if timestamp_solo && sync == SyncDirection::Upload {
+        layout.timestamp_solo_backups(games);
+    }
+    let subjects = games;
     let games = if !games.is_empty() {
-        let layout = BackupLayout::new(local.clone());
-        let games: Vec<_> = games.iter().filter_map(|x| layout.game_folder(x).leaf()).collect();
-        games
+        layout.cloud_folders(games)
     } else {
         vec![]
     };
+    let excluded = layout.cloud_excluded_paths(&config.cloud.excluded_games);
 
-    let rclone = Rclone::new(config.apps.rclone.clone(), remote);
-    let mut process = match rclone.sync(local, cloud, sync, finality, &games) {
+    let rclone = Rclone::new(config.apps.rclone.clone(), remote)
+        .with_transfers(config.cloud.transfers)
+        .with_bandwidth_limit(config.cloud.bandwidth_limit.clone());
+    let mut process = match rclone.sync(local, cloud, sync, finality, &games, &excluded) {
         Ok(p) => p,
         Err(e) => return Err(Error::UnableToSynchronizeCloud(e)),
     };
@@ -1179,6 +2430,12 @@ fn sync_cloud(
         match process.succeeded() {
             Some(Ok(_)) => {
                 unregister_sigint();
+                if timestamp_solo && sync == SyncDirection::Download {
+                    BackupLayout::new(local.clone())
+                        .with_folder_template(config.backup.folder_template.clone())
+                        .with_filename_policy(config.filename_policy())
+                        .timestamp_solo_backups(subjects);
+                }
                 return Ok(changes);
             }
             Some(Err(e)) => {
@@ -1,13 +1,18 @@
-use std::io::Read;
+use std::{collections::HashSet, io::Read, sync::Mutex};
 
 use itertools::Itertools;
+use rayon::prelude::*;
 
 use crate::{
     lang::TRANSLATOR,
     path::StrictPath,
-    prelude::Error,
+    prelude::{app_dir, Error, Finality},
     resource::{config::Config, manifest::Manifest},
-    scan::{compare_ranked_titles, layout::BackupLayout, TitleFinder, TitleQuery},
+    scan::{
+        compare_ranked_titles, layout::BackupLayout, plan_game_for_backup, scan_game_for_backup, BackupId, BackupInfo,
+        BackupScanOptions, Launchers, RunningProcesses, ScanInfo, ScanKind, ScanPlan, SteamShortcuts, TitleFinder,
+        TitleQuery,
+    },
 };
 
 /// The full input to the `api` command.
@@ -49,6 +54,7 @@ pub enum Output {
 pub enum Request {
     FindTitle(request::FindTitle),
     CheckAppUpdate(request::CheckAppUpdate),
+    PlanBackup(request::PlanBackup),
 }
 
 /// A response to an individual request.
@@ -58,6 +64,7 @@ pub enum Response {
     Error(response::Error),
     FindTitle(response::FindTitle),
     CheckAppUpdate(response::CheckAppUpdate),
+    PlanBackup(response::PlanBackup),
 }
 
 pub mod request {
@@ -108,6 +115,16 @@ pub mod request {
     #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
     #[serde(default, rename_all = "camelCase")]
     pub struct CheckAppUpdate {}
+
+    /// Show what a backup scan would check for a game,
+    /// without actually scanning for any saves.
+    /// This is useful for understanding why a path is or isn't included.
+    #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+    #[serde(default, rename_all = "camelCase")]
+    pub struct PlanBackup {
+        /// Exact title of the game.
+        pub game: String,
+    }
 }
 
 pub mod response {
@@ -132,6 +149,23 @@ pub mod response {
         pub update: Option<AppUpdate>,
     }
 
+    #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+    #[serde(default, rename_all = "camelCase")]
+    pub struct PlanBackup {
+        /// Roots that would be checked, including any extra Wine prefixes.
+        pub roots: Vec<String>,
+        /// Paths that would be checked. These may contain glob patterns.
+        pub paths: Vec<String>,
+        /// Registry keys that would be checked.
+        pub registry: Vec<String>,
+        /// Globally ignored paths.
+        pub ignored_paths: Vec<String>,
+        /// Globally ignored registry keys.
+        pub ignored_registry: Vec<String>,
+        /// Whether store screenshots would be excluded.
+        pub exclude_store_screenshots: bool,
+    }
+
     #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
     #[serde(default, rename_all = "camelCase")]
     pub struct AppUpdate {
@@ -142,6 +176,25 @@ pub mod response {
     }
 }
 
+/// Where the published schemas live.
+/// This is used for each schema's `$id`, so it should not change.
+const SCHEMA_BASE_URL: &str = "https://raw.githubusercontent.com/mtkennerly/ludusavi/master/docs/schema";
+
+fn schema_with_id(mut schema: schemars::schema::RootSchema, name: &str) -> schemars::schema::RootSchema {
+    schema.schema.metadata().id = Some(format!("{SCHEMA_BASE_URL}/{name}.yaml"));
+    schema
+}
+
+/// JSON Schema for `config.yaml`.
+pub fn config_schema() -> schemars::schema::RootSchema {
+    schema_with_id(schemars::schema_for!(Config), "config")
+}
+
+/// JSON Schema for the manifest format, including secondary manifests.
+pub fn manifest_schema() -> schemars::schema::RootSchema {
+    schema_with_id(schemars::schema_for!(Manifest), "manifest")
+}
+
 fn parse_input(input: Option<String>) -> Result<Input, String> {
     if let Some(input) = input {
         let input = serde_json::from_str::<Input>(&input).map_err(|e| e.to_string())?;
@@ -180,13 +233,275 @@ pub fn abort_message(message: String) -> ! {
     std::process::exit(1);
 }
 
+/// Compute what a backup scan would check for a game, without running it.
+pub fn plan(config: &Config, manifest: &Manifest, title_finder: &TitleFinder, game: &str) -> Option<ScanPlan> {
+    let data = manifest.0.get(game)?;
+    let roots = config.expanded_roots();
+    let subjects = vec![game.to_string()];
+    let launchers = Launchers::scan(&roots, manifest, &subjects, title_finder, None);
+    let steam_shortcuts = SteamShortcuts::scan();
+
+    Some(plan_game_for_backup(
+        data,
+        game,
+        &roots,
+        &app_dir(),
+        &launchers,
+        &config.backup.filter,
+        None,
+        &steam_shortcuts,
+    ))
+}
+
+/// Progress notification for one game during `back_up_with_progress` or `restore_with_progress`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressEvent {
+    /// The game that was just processed.
+    pub game: String,
+    /// How many games have been processed so far, including this one.
+    pub current: usize,
+    /// How many games will be processed in total.
+    pub total: usize,
+    /// Whether this is part of a backup or a restore.
+    pub phase: ScanKind,
+}
+
+/// Back up games synchronously, using the retention, redirects, and toggles from the config.
+/// When `games` is `None`, all processable games from the manifest are scanned.
+/// No files are written unless `finality` is final.
+#[allow(unused)]
+pub fn back_up(
+    config: &Config,
+    manifest: &Manifest,
+    games: Option<&[String]>,
+    finality: Finality,
+) -> Vec<(ScanInfo, Option<BackupInfo>)> {
+    back_up_with_progress(config, manifest, games, finality, |_| {})
+}
+
+/// Like `back_up`, but games are processed in parallel,
+/// and `on_event` is called as each game finishes.
+/// Events may arrive out of order, but `current` always increases by one.
+pub fn back_up_with_progress(
+    config: &Config,
+    manifest: &Manifest,
+    games: Option<&[String]>,
+    finality: Finality,
+    on_event: impl FnMut(ProgressEvent) + Send,
+) -> Vec<(ScanInfo, Option<BackupInfo>)> {
+    let mut manifest = manifest.clone();
+    manifest.incorporate_extensions(config);
+
+    let subjects: Vec<_> = match games {
+        Some(games) => {
+            let games: HashSet<_> = games.iter().collect();
+            manifest.0.keys().filter(|k| games.contains(k)).cloned().collect()
+        }
+        None => manifest.processable_titles().cloned().collect(),
+    };
+    let games_specified = games.is_some();
+    let single = subjects.len() == 1 && games_specified;
+
+    let roots = config.expanded_roots();
+    let layout = BackupLayout::new(config.backup.path.clone())
+        .with_folder_template(config.backup.folder_template.clone())
+        .with_filename_policy(config.filename_policy())
+        .with_hooks(config.backup.hooks())
+        .with_space_check(config.backup.space_check)
+        .with_mirrors(config.backup.mirrors.clone())
+        .with_zip_password(config.zip_password());
+    let title_finder = TitleFinder::new(config, &manifest, layout.restorable_game_set());
+    let steam_shortcuts = SteamShortcuts::scan();
+    let processes = RunningProcesses::scan();
+    let launchers = Launchers::scan(&roots, &manifest, &subjects, &title_finder, None);
+    let force_timestamped_name = config.cloud.synchronize && config.cloud.timestamped_backups;
+
+    let subjects: Vec<_> = subjects.into_iter().filter(|x| !x.trim().is_empty()).sorted().collect();
+    let progress = Progress::new(subjects.len(), ScanKind::Backup, on_event);
+
+    let results = subjects
+        .par_iter()
+        .filter_map(|key| {
+            let game = &manifest.0[key];
+
+            let previous = layout.latest_backup(
+                key,
+                ScanKind::Backup,
+                &config.redirects,
+                config.restore.reverse_redirects,
+                &config.restore.toggled_paths,
+                config.backup.only_constructive,
+            );
+
+            if config
+                .backup
+                .filter
+                .excludes(games_specified, previous.is_some(), &game.cloud)
+            {
+                log::trace!("[{key}] excluded by backup filter");
+                progress.step(key);
+                return None;
+            }
+
+            let scan_info = scan_game_for_backup(
+                game,
+                key,
+                &roots,
+                &app_dir(),
+                &launchers,
+                &config.backup.filter,
+                None,
+                &config.backup.toggled_paths,
+                &config.backup.toggled_registry,
+                previous,
+                &config.redirects,
+                config.restore.reverse_redirects,
+                &steam_shortcuts,
+                &BackupScanOptions {
+                    processes: Some(&processes),
+                    // Without the cache, we can't tell when the last full scan was.
+                    quick_scan: false,
+                    ..BackupScanOptions::from_config(&config, key)
+                },
+            );
+            if !config.is_game_enabled_for_backup(key) && !single {
+                progress.step(key);
+                return Some((scan_info, None));
+            }
+            if config.backup.skip_running_games && scan_info.game_running {
+                log::info!("[{key}] skipping backup because the game is running");
+                progress.step(key);
+                return Some((scan_info, None));
+            }
+
+            let backup_info = if finality.preview() {
+                None
+            } else {
+                let retention = config
+                    .backup
+                    .retention_for(key)
+                    .with_force_timestamped_name(force_timestamped_name);
+                layout.game_layout_for(key, Some(game)).back_up(
+                    &scan_info,
+                    &chrono::Utc::now(),
+                    &config.backup.format_for(key),
+                    retention,
+                    config.backup.only_constructive,
+                    config.backup.checksum,
+                    config.backup.comment_template.as_deref(),
+                )
+            };
+            progress.step(key);
+            Some((scan_info, backup_info))
+        })
+        .collect();
+
+    if !finality.preview() {
+        layout.collect_garbage(false);
+    }
+
+    results
+}
+
+/// Restore the latest backup of games synchronously, using the redirects and toggles from the config.
+/// When `games` is `None`, all games with backups in the restore path are processed.
+/// No files are written unless `finality` is final.
+#[allow(unused)]
+pub fn restore(config: &Config, games: Option<&[String]>, finality: Finality) -> Vec<(ScanInfo, Option<BackupInfo>)> {
+    restore_with_progress(config, games, finality, |_| {})
+}
+
+/// Like `restore`, but games are processed in parallel,
+/// and `on_event` is called as each game finishes.
+/// Events may arrive out of order, but `current` always increases by one.
+#[allow(unused)]
+pub fn restore_with_progress(
+    config: &Config,
+    games: Option<&[String]>,
+    finality: Finality,
+    on_event: impl FnMut(ProgressEvent) + Send,
+) -> Vec<(ScanInfo, Option<BackupInfo>)> {
+    let layout = BackupLayout::new(config.restore.path.clone())
+        .with_redirect_fallback(config.restore.redirect_fallback)
+        .with_wine_prefix(config.restore.wine_prefix.clone())
+        .with_conflict_policy(config.restore.conflict_policy)
+        .with_empty_dir_exclusion(config.backup.filter.exclude_empty_dirs)
+        .with_registry_filter(config.backup.filter.clone())
+        .with_zip_password(config.zip_password());
+
+    let mut restorables = layout.restorable_games();
+    if let Some(games) = games {
+        restorables.retain(|x| games.contains(x));
+    }
+    let single = restorables.len() == 1 && games.is_some();
+
+    let restorables: Vec<_> = restorables.into_iter().sorted().collect();
+    let progress = Progress::new(restorables.len(), ScanKind::Restore, on_event);
+
+    restorables
+        .par_iter()
+        .map(|name| {
+            let mut game_layout = layout.game_layout(name);
+            let scan_info = game_layout.scan_for_restoration(
+                name,
+                &BackupId::Latest,
+                &config.redirects,
+                config.restore.reverse_redirects,
+                &config.restore.toggled_paths,
+                &config.restore.toggled_registry,
+            );
+            if !config.is_game_enabled_for_restore(name) && !single {
+                progress.step(name);
+                return (scan_info, None);
+            }
+
+            let backup_info = if scan_info.backup.is_some() && !finality.preview() {
+                Some(game_layout.restore(&scan_info, &config.restore.toggled_registry, None))
+            } else {
+                None
+            };
+            progress.step(name);
+            (scan_info, backup_info)
+        })
+        .collect()
+}
+
+/// Shares the progress callback between worker threads.
+struct Progress<F> {
+    total: usize,
+    phase: ScanKind,
+    state: Mutex<(usize, F)>,
+}
+
+impl<F: FnMut(ProgressEvent)> Progress<F> {
+    fn new(total: usize, phase: ScanKind, on_event: F) -> Self {
+        Self {
+            total,
+            phase,
+            state: Mutex::new((0, on_event)),
+        }
+    }
+
+    fn step(&self, game: &str) {
+        let mut state = self.state.lock().unwrap();
+        let (current, on_event) = &mut *state;
+        *current += 1;
+        on_event(ProgressEvent {
+            game: game.to_string(),
+            current: *current,
+            total: self.total,
+            phase: self.phase,
+        });
+    }
+}
+
 pub fn process(input: Option<String>, config: &Config, manifest: &Manifest) -> Result<Output, String> {
     let input = parse_input(input)?;
     log::debug!("API input: {input:?}");
     let mut responses = vec![];
 
     let backup_path = input.config.backup_path.unwrap_or_else(|| config.restore.path.clone());
-    let layout = BackupLayout::new(backup_path);
+    let layout = BackupLayout::new(backup_path).with_zip_password(config.zip_password());
 
     let title_finder = TitleFinder::new(config, manifest, layout.restorable_game_set());
 
@@ -211,6 +526,7 @@ pub fn process(input: Option<String>, config: &Config, manifest: &Manifest) -> R
                     steam_id,
                     gog_id,
                     lutris_id,
+                    save_dir: None,
                     normalized,
                     fuzzy,
                     backup,
@@ -240,6 +556,23 @@ pub fn process(input: Option<String>, config: &Config, manifest: &Manifest) -> R
                     responses.push(Response::Error(response::Error { message: e.to_string() }));
                 }
             },
+            Request::PlanBackup(request::PlanBackup { game }) => match plan(config, manifest, &title_finder, &game) {
+                Some(plan) => {
+                    responses.push(Response::PlanBackup(response::PlanBackup {
+                        roots: plan.roots.iter().map(|x| x.path().render()).collect(),
+                        paths: plan.sorted_paths(),
+                        registry: plan.registry,
+                        ignored_paths: plan.ignored_paths.iter().map(|x| x.render()).collect(),
+                        ignored_registry: plan.ignored_registry.iter().map(|x| x.raw()).collect(),
+                        exclude_store_screenshots: plan.exclude_store_screenshots,
+                    }));
+                }
+                None => {
+                    responses.push(Response::Error(response::Error {
+                        message: TRANSLATOR.handle_error(&Error::CliUnrecognizedGames { games: vec![game] }),
+                    }));
+                }
+            },
         }
     }
 
@@ -249,7 +582,31 @@ pub fn process(input: Option<String>, config: &Config, manifest: &Manifest) -> R
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        resource::ResourceFile,
+        scan::ScannedFile,
+        testing::{repo, s},
+    };
     use pretty_assertions::assert_eq;
+    use velcro::hash_map;
+
+    #[test]
+    pub fn schemas_have_stable_ids() {
+        let id = |schema: schemars::schema::RootSchema| schema.schema.metadata.and_then(|x| x.id);
+
+        assert_eq!(
+            Some(s(
+                "https://raw.githubusercontent.com/mtkennerly/ludusavi/master/docs/schema/config.yaml"
+            )),
+            id(config_schema()),
+        );
+        assert_eq!(
+            Some(s(
+                "https://raw.githubusercontent.com/mtkennerly/ludusavi/master/docs/schema/manifest.yaml"
+            )),
+            id(manifest_schema()),
+        );
+    }
 
     #[test]
     pub fn deserialize_input() {
@@ -307,4 +664,125 @@ mod tests {
         .trim();
         assert_eq!(expected, serialized);
     }
+
+    #[test]
+    fn can_back_up_in_preview_mode() {
+        let config = Config::load_from_string(&format!(
+            r#"
+            manifest:
+              url: example.com
+              etag: null
+            roots:
+              - path: {0}/tests/root1
+                store: other
+              - path: {0}/tests/root2
+                store: other
+            backup:
+              path: {0}/tests/nonexistent
+            restore:
+              path: {0}/tests/nonexistent
+            "#,
+            repo()
+        ))
+        .unwrap();
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              files:
+                <base>/file1.txt: {}
+                <base>/subdir: {}
+            "#,
+        )
+        .unwrap();
+
+        let results = back_up(&config, &manifest, Some(&[s("game1")]), Finality::Preview);
+
+        assert_eq!(1, results.len());
+        let (scan_info, backup_info) = &results[0];
+        assert_eq!(
+            hash_map! {
+                format!("{}/tests/root1/game1/subdir/file2.txt", repo()).into(): ScannedFile::new(2, "9d891e731f75deae56884d79e9816736b7488080").change_new(),
+                format!("{}/tests/root2/game1/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+            },
+            scan_info.found_files
+        );
+        assert!(backup_info.is_none());
+    }
+
+    #[test]
+    fn reports_progress_for_each_game() {
+        let config = Config::load_from_string(&format!(
+            r#"
+            manifest:
+              url: example.com
+              etag: null
+            roots:
+              - path: {0}/tests/root1
+                store: other
+              - path: {0}/tests/root2
+                store: other
+            backup:
+              path: {0}/tests/nonexistent
+            restore:
+              path: {0}/tests/nonexistent
+            "#,
+            repo()
+        ))
+        .unwrap();
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+              files:
+                <base>/file1.txt: {}
+            game2:
+              files:
+                <root>/game2/*.txt: {}
+            "#,
+        )
+        .unwrap();
+
+        let mut events = vec![];
+        let results = back_up_with_progress(
+            &config,
+            &manifest,
+            Some(&[s("game1"), s("game2")]),
+            Finality::Preview,
+            |event| events.push(event),
+        );
+
+        assert_eq!(2, results.len());
+        assert_eq!(vec![1, 2], events.iter().map(|x| x.current).collect::<Vec<_>>());
+        assert!(events.iter().all(|x| x.total == 2 && x.phase == ScanKind::Backup));
+        assert_eq!(
+            vec![s("game1"), s("game2")],
+            events.iter().map(|x| x.game.clone()).sorted().collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn can_preview_restore_synchronously() {
+        let config = Config::load_from_string(&format!(
+            r#"
+            manifest:
+              url: example.com
+              etag: null
+            roots: []
+            backup:
+              path: {0}/tests/nonexistent
+            restore:
+              path: {0}/tests/backup
+            "#,
+            repo()
+        ))
+        .unwrap();
+
+        let results = restore(&config, Some(&[s("game1")]), Finality::Preview);
+
+        assert_eq!(1, results.len());
+        let (scan_info, backup_info) = &results[0];
+        assert_eq!("game1", scan_info.game_name);
+        assert!(scan_info.backup.is_some());
+        assert_eq!(2, scan_info.found_files.len());
+        assert!(backup_info.is_none());
+    }
 }
@@ -3,10 +3,15 @@ use std::path::PathBuf;
 use crate::{
     cloud::WebDavProvider,
     prelude::StrictPath,
-    resource::config::{BackupFormat, Sort, SortKey, ZipCompression},
+    resource::{
+        config::{BackupFormat, Sort, SortKey, ZipCompression},
+        manifest::{Os, Store, Tag},
+    },
+    scan::external::ExternalFormat,
 };
 
 use clap::{ArgGroup, Args, ValueEnum};
+use itertools::Itertools;
 
 macro_rules! possible_values {
     ($t: ty, $options: ident) => {{
@@ -27,6 +32,128 @@ fn parse_existing_strict_path(path: &str) -> Result<StrictPath, std::io::Error>
     Ok(sp)
 }
 
+fn parse_glob(glob: &str) -> Result<String, globset::Error> {
+    globset::Glob::new(glob)?;
+    Ok(glob.to_string())
+}
+
+fn parse_target_root(value: &str) -> Result<TargetRoot, String> {
+    let Some((game, path)) = value.rsplit_once('=') else {
+        return Err("expected a value like <GAME>=<PATH>".to_string());
+    };
+    if game.trim().is_empty() || path.trim().is_empty() {
+        return Err("both the game and the path must be set".to_string());
+    }
+
+    Ok(TargetRoot {
+        game: game.to_string(),
+        path: parse_strict_path(path).map_err(|e| e.to_string())?,
+    })
+}
+
+fn parse_external_mapping(value: &str) -> Result<ExternalMapping, String> {
+    let Some((folder, game)) = value.split_once('=') else {
+        return Err("expected a value like <FOLDER>=<GAME>".to_string());
+    };
+    if folder.trim().is_empty() || game.trim().is_empty() {
+        return Err("both the folder and the game must be set".to_string());
+    }
+
+    Ok(ExternalMapping {
+        folder: folder.to_string(),
+        game: game.to_string(),
+    })
+}
+
+fn parse_registry_hive(value: &str) -> Result<String, String> {
+    crate::scan::registry::normalize_hive(value)
+        .map(|x| x.to_string())
+        .ok_or_else(|| "expected one of: HKCU, HKLM, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE".to_string())
+}
+
+/// Read game names from a list with one name per line,
+/// skipping blank lines and comments.
+pub fn parse_game_list(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect()
+}
+
+const GAME_GLOB_CHARS: &[char] = &['*', '?', '[', '{'];
+
+/// Expand a game argument like `The Witcher*` against the known titles.
+/// Returns `None` if the argument is not a glob.
+/// Special characters can be matched literally with a backslash (`\*`) or brackets (`[*]`).
+pub fn match_game_glob<'a>(pattern: &str, titles: impl IntoIterator<Item = &'a String>) -> Option<Vec<String>> {
+    if !pattern.contains(GAME_GLOB_CHARS) {
+        return None;
+    }
+
+    let glob = globset::GlobBuilder::new(pattern)
+        .backslash_escape(true)
+        .case_insensitive(!Os::HOST.is_case_sensitive())
+        .build()
+        .ok()?
+        .compile_matcher();
+
+    Some(
+        titles
+            .into_iter()
+            .filter(|x| glob.is_match(x.as_str()))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Find the titles most similar to the literal portion of a glob,
+/// for suggesting alternatives when it doesn't match anything.
+pub fn closest_game_titles<'a>(pattern: &str, titles: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    const LIMIT: usize = 3;
+
+    let literal = pattern.replace(GAME_GLOB_CHARS, "").to_lowercase();
+
+    titles
+        .into_iter()
+        .map(|title| (strsim::jaro_winkler(&title.to_lowercase(), &literal), title))
+        .filter(|(score, _)| *score >= 0.75)
+        .sorted_by(|(x, _), (y, _)| y.total_cmp(x))
+        .take(LIMIT)
+        .map(|(_, title)| title.clone())
+        .collect()
+}
+
+fn parse_datetime(value: &str) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+    chrono::DateTime::parse_from_rfc3339(value).map(|x| x.with_timezone(&chrono::Utc))
+}
+
+fn parse_interval(value: &str) -> Result<std::time::Duration, String> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| "expected a value like 90s, 30m, 2h, or 1d".to_string())?;
+    let multiplier: u64 = match unit {
+        "s" => 1,
+        "m" | "" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        _ => return Err(format!("unknown unit: {unit}")),
+    };
+    let seconds = amount
+        .checked_mul(multiplier)
+        .ok_or_else(|| "the interval is too long".to_string())?;
+    if seconds == 0 {
+        return Err("the interval must be greater than zero".to_string());
+    }
+
+    Ok(std::time::Duration::from_secs(seconds))
+}
+
 fn styles() -> clap::builder::styling::Styles {
     use clap::builder::styling::{AnsiColor, Effects, Styles};
 
@@ -37,6 +164,20 @@ fn styles() -> clap::builder::styling::Styles {
         .placeholder(AnsiColor::Green.on_default())
 }
 
+/// A one-off restore location for a specific game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetRoot {
+    pub game: String,
+    pub path: StrictPath,
+}
+
+/// A manual choice of game for a folder being imported from another tool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalMapping {
+    pub folder: String,
+    pub game: String,
+}
+
 #[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
 pub enum CompletionShell {
     #[clap(about = "Completions for Bash")]
@@ -159,6 +300,20 @@ pub enum Subcommand {
         #[clap(long)]
         api: bool,
 
+        /// Print each game's result as a single line of JSON as soon as it finishes,
+        /// followed by a final line with the overall stats.
+        /// This is useful for processing large libraries incrementally.
+        /// Unlike `--api`, files are not marked as duplicates.
+        #[clap(long, conflicts_with("api"))]
+        ndjson: bool,
+
+        /// Only print aggregate stats instead of each game's details:
+        /// how many games have saves, their total size,
+        /// how many games changed, and how many games failed.
+        /// This can be combined with `--api` to print just the summary as JSON.
+        #[clap(long, conflicts_with("ndjson"))]
+        summary: bool,
+
         /// Use GUI dialogs for prompts and some information.
         #[clap(long)]
         gui: bool,
@@ -178,9 +333,9 @@ pub enum Subcommand {
         #[clap(long, value_parser = possible_values!(ZipCompression, ALL_NAMES))]
         compression: Option<ZipCompression>,
 
-        /// Compression level to use for new zip backups.
+        /// Compression level to use for new zip or tar.gz backups.
         /// When not specified, this defers to the config file.
-        /// Valid ranges: 1 to 9 for deflate/bzip2, -7 to 22 for zstd.
+        /// Valid ranges: 1 to 9 for deflate/bzip2/tar.gz, -7 to 22 for zstd.
         #[clap(long, allow_hyphen_values(true))]
         compression_level: Option<i32>,
 
@@ -206,7 +361,7 @@ pub enum Subcommand {
 
         /// Don't perform any cloud checks or synchronization.
         /// When not specified, this defers to the config file.
-        #[clap(long, conflicts_with("cloud_sync"))]
+        #[clap(long, visible_alias = "no-cloud", conflicts_with("cloud_sync"))]
         no_cloud_sync: bool,
 
         /// Include the serialized registry content in the output.
@@ -214,6 +369,51 @@ pub enum Subcommand {
         #[clap(long)]
         dump_registry: bool,
 
+        /// List any files and registry keys that are claimed by more than one game.
+        #[clap(long)]
+        duplicates: bool,
+
+        /// Only back up games that have at least one of these tags in the manifest.
+        /// This may be specified multiple times.
+        #[clap(long, value_parser = possible_values!(Tag, ALL_NAMES))]
+        only_tag: Vec<Tag>,
+
+        /// Don't back up games that have any of these tags in the manifest.
+        /// This may be specified multiple times.
+        #[clap(long, value_parser = possible_values!(Tag, ALL_NAMES))]
+        exclude_tag: Vec<Tag>,
+
+        /// Only back up this many games,
+        /// choosing the ones whose latest backup is oldest.
+        /// Unless you specify the games,
+        /// this only considers games that have backups or are installed in your roots.
+        /// Installed games without any backups are chosen first.
+        /// Running this repeatedly will cycle through the selected games.
+        #[clap(long)]
+        batch: Option<usize>,
+
+        /// Comment to set on each new backup.
+        /// Supports the same placeholders as the `backup.commentTemplate` config field.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        comment: Option<String>,
+
+        /// Hash every file, even if `backup.quickScan` is enabled in the config file.
+        #[clap(long)]
+        full_scan: bool,
+
+        /// Don't back up this game, even if it's otherwise selected.
+        /// This supports the same names and globs as the positional games, and it takes precedence over them.
+        /// This may be specified multiple times.
+        #[clap(long)]
+        exclude: Vec<String>,
+
+        /// Also process the games listed in this file, with one name per line.
+        /// Blank lines and lines starting with `#` are ignored.
+        /// If the file does not list any games, then Ludusavi will exit with an error.
+        #[clap(long, value_parser = parse_existing_strict_path)]
+        games_from: Option<StrictPath>,
+
         /// Only back up these specific games.
         /// Alternatively supports stdin (one value per line).
         #[clap()]
@@ -239,6 +439,13 @@ pub enum Subcommand {
         #[clap(long)]
         api: bool,
 
+        /// Only print aggregate stats instead of each game's details:
+        /// how many games have saves, their total size,
+        /// how many games changed, and how many games failed.
+        /// This can be combined with `--api` to print just the summary as JSON.
+        #[clap(long)]
+        summary: bool,
+
         /// Use GUI dialogs for prompts and some information.
         #[clap(long)]
         gui: bool,
@@ -253,6 +460,16 @@ pub enum Subcommand {
         #[clap(long)]
         backup: Option<String>,
 
+        /// Only consider backups made before this time, like `2024-01-31T12:00:00Z` (RFC 3339).
+        /// For each game, the latest backup within the time window is restored.
+        #[clap(long, value_parser = parse_datetime, conflicts_with("backup"))]
+        before: Option<chrono::DateTime<chrono::Utc>>,
+
+        /// Only consider backups made after this time, like `2024-01-31T12:00:00Z` (RFC 3339).
+        /// For each game, the latest backup within the time window is restored.
+        #[clap(long, value_parser = parse_datetime, conflicts_with("backup"))]
+        after: Option<chrono::DateTime<chrono::Utc>>,
+
         /// Warn if the local and cloud backups are out of sync.
         /// The restore will still proceed regardless.
         /// This has no effect on previews.
@@ -262,7 +479,7 @@ pub enum Subcommand {
 
         /// Don't perform any cloud checks or synchronization.
         /// When not specified, this defers to the config file.
-        #[clap(long, conflicts_with("cloud_sync"))]
+        #[clap(long, visible_alias = "no-cloud", conflicts_with("cloud_sync"))]
         no_cloud_sync: bool,
 
         /// Include the serialized registry content in the output.
@@ -270,6 +487,64 @@ pub enum Subcommand {
         #[clap(long)]
         dump_registry: bool,
 
+        /// List any files and registry keys that are claimed by more than one game.
+        #[clap(long)]
+        duplicates: bool,
+
+        /// Never remove live files or overwrite newer ones during the restore.
+        /// Files that would be removed or overwritten are skipped and reported instead.
+        #[clap(long)]
+        constructive: bool,
+
+        /// List the game's backups and ask which one to restore.
+        /// This is only valid when restoring a single game.
+        #[clap(long, conflicts_with_all(["backup", "before", "after", "api", "gui"]))]
+        interactive: bool,
+
+        /// With `--interactive`, pick the latest backup without asking.
+        #[clap(long, requires("interactive"))]
+        yes: bool,
+
+        /// Only restore files whose restored location matches this glob.
+        /// Matching a folder also includes everything inside of it.
+        /// A relative glob can match at any depth.
+        /// Redirects are applied before matching.
+        /// Registry content is not restored when this is set.
+        #[clap(long, value_parser = parse_glob)]
+        file: Option<String>,
+
+        /// Restore a game into a different folder for this run only, like `--target-root "Game=/new/location"`.
+        /// The deepest folder containing all of the game's files is redirected to the new path,
+        /// after any redirects from the config file.
+        /// If the game's backup has no files to restore, then the command fails.
+        /// This may be repeated for multiple games.
+        #[clap(long, value_name = "GAME=PATH", value_parser = parse_target_root)]
+        target_root: Vec<TargetRoot>,
+
+        /// If a redirected file's folder can't be created,
+        /// then restore the file to its original location instead.
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        force_redirect_fallback: bool,
+
+        /// Only restore registry content from this hive, like `--registry-hive HKCU`.
+        /// This may be repeated for multiple hives.
+        /// When not specified, all hives are restored.
+        #[clap(long, value_name = "HIVE", value_parser = parse_registry_hive)]
+        registry_hive: Vec<String>,
+
+        /// Don't restore this game, even if it's otherwise selected.
+        /// This supports the same names and globs as the positional games, and it takes precedence over them.
+        /// This may be specified multiple times.
+        #[clap(long)]
+        exclude: Vec<String>,
+
+        /// Also process the games listed in this file, with one name per line.
+        /// Blank lines and lines starting with `#` are ignored.
+        /// If the file does not list any games, then Ludusavi will exit with an error.
+        #[clap(long, value_parser = parse_existing_strict_path)]
+        games_from: Option<StrictPath>,
+
         /// Only restore these specific games.
         /// Alternatively supports stdin (one value per line).
         #[clap()]
@@ -281,7 +556,11 @@ pub enum Subcommand {
         shell: CompletionShell,
     },
     /// Show backups
+    #[clap(args_conflicts_with_subcommands = true)]
     Backups {
+        #[clap(subcommand)]
+        sub: Option<BackupsSubcommand>,
+
         /// Directory in which to find backups.
         /// When unset, this defaults to the restore path from the config file.
         #[clap(long, value_parser = parse_strict_path)]
@@ -297,6 +576,129 @@ pub enum Subcommand {
         #[clap()]
         games: Vec<String>,
     },
+    /// Apply retention limits to existing backups
+    ///
+    /// This forgets any backups beyond the retention limits from the config file
+    /// and removes their leftover files, without scanning for new saves.
+    /// Locked backups are never removed.
+    #[clap(visible_alias = "prune")]
+    Maintain {
+        /// List out what would be removed, but don't actually perform the operation.
+        #[clap(long)]
+        preview: bool,
+
+        /// Directory in which to find backups.
+        /// When not specified, this defers to the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Don't ask for confirmation.
+        #[clap(long)]
+        force: bool,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Maximum number of full backups to retain per game.
+        /// Must be between 1 and 255 (inclusive).
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        full_limit: Option<u8>,
+
+        /// Maximum number of differential backups to retain per full backup.
+        /// Must be between 0 and 255 (inclusive).
+        /// When not specified, this defers to the config file.
+        #[clap(long)]
+        differential_limit: Option<u8>,
+
+        /// Only maintain these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Check stored backup content against the recorded hashes
+    ///
+    /// This re-reads each file in the latest backup of each game
+    /// and reports any whose content no longer matches the hash
+    /// that was recorded when the backup was made.
+    /// Nothing is modified.
+    Scrub {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Only scrub these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Combine differential backups into a new full backup
+    ///
+    /// For each game, this takes the latest backup (or the one chosen with `--backup`),
+    /// combines it with the full backup that it depends on,
+    /// and stores the result as a new full backup in the current format from the config file.
+    /// Games whose selected backup is not differential are skipped.
+    /// Afterward, the merged backups are removed unless they are locked.
+    Merge {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Don't ask for confirmation.
+        #[clap(long)]
+        force: bool,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Merge a specific backup, using an ID returned by the `backups` command.
+        /// This is only valid when merging a single game.
+        #[clap(long)]
+        backup: Option<String>,
+
+        /// Keep the merged backups instead of removing them.
+        #[clap(long)]
+        keep: bool,
+
+        /// Only merge these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+    /// Delete a single backup of a game
+    ///
+    /// This removes the backup's files and forgets it in `mapping.yaml`.
+    /// Deleting a full backup also deletes any differential backups that depend on it.
+    /// Locked backups cannot be deleted.
+    Delete {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Don't ask for confirmation.
+        #[clap(long)]
+        force: bool,
+
+        /// Backup to delete, using an ID returned by the `backups` command.
+        #[clap(long)]
+        id: String,
+
+        /// Game whose backup should be deleted.
+        #[clap()]
+        game: String,
+    },
     /// Find game titles
     ///
     /// Precedence: Steam ID -> GOG ID -> Lutris ID -> exact names -> normalized names.
@@ -347,6 +749,13 @@ pub enum Subcommand {
         #[clap(long)]
         lutris_id: Option<String>,
 
+        /// Look up games whose save locations could be in this folder,
+        /// based on the paths in the manifest.
+        /// This is separate from `--path`, which is where to find backups.
+        /// All matching games are returned, ranked by how specific the match is.
+        #[clap(long, value_parser = parse_strict_path)]
+        save_dir: Option<StrictPath>,
+
         /// Look up game by an approximation of the title.
         /// Ignores capitalization, "edition" suffixes, year suffixes, and some special symbols.
         /// This may find multiple games for a single input.
@@ -372,6 +781,149 @@ pub enum Subcommand {
         #[clap()]
         names: Vec<String>,
     },
+    /// Export a game's backed up registry data as a `.reg` file
+    ///
+    /// The output can be inspected or imported manually with regedit.
+    /// If the backup does not contain any registry data, Ludusavi will exit with an error.
+    DumpRegistry {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Export a specific backup, using an ID returned by the `backups` command.
+        /// When not specified, this uses the latest backup.
+        #[clap(long)]
+        id: Option<String>,
+
+        /// Save the registry data to this file instead of printing it.
+        /// If this is an existing folder, then the file is saved inside of it,
+        /// named according to the `export.name` template from the config file.
+        /// The file is encoded as UTF-16, like regedit's own exports.
+        #[clap(long, value_parser = parse_strict_path)]
+        output: Option<StrictPath>,
+
+        /// Game whose registry data should be exported.
+        #[clap()]
+        game: String,
+    },
+    /// Show what changed between two backups of a game
+    ///
+    /// Files are listed by their original path,
+    /// prefixed with `+` if added, `-` if removed, or `~` if changed.
+    /// Registry data is compared by its hash.
+    Diff {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
+        #[clap(long)]
+        api: bool,
+
+        /// Game whose backups should be compared.
+        #[clap()]
+        game: String,
+
+        /// Older backup, using an ID returned by the `backups` command.
+        #[clap()]
+        from: String,
+
+        /// Newer backup, using an ID returned by the `backups` command.
+        #[clap()]
+        to: String,
+    },
+    /// Show where a game's backed up files are stored
+    ///
+    /// This prints a JSON object keyed by each file's original path.
+    /// Files in simple backups are listed with their path on disk,
+    /// while files in zip or tar backups are listed with their archive and the name within it.
+    Locate {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Locate files in a specific backup, using an ID returned by the `backups` command.
+        /// When not specified, this uses the latest backup.
+        #[clap(long)]
+        id: Option<String>,
+
+        /// Game whose files should be located.
+        #[clap()]
+        game: String,
+    },
+    /// Package a game's backups into a single file
+    ///
+    /// The bundle is a zip file containing the game's backup folder,
+    /// including `mapping.yaml` and all of its full and differential backups.
+    /// It can be added to another backup folder with the `import` command.
+    Export {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Save the bundle to this file.
+        /// If this is an existing folder, then the bundle is saved inside of it,
+        /// named according to the `export.name` template from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        out: StrictPath,
+
+        /// Game whose backups should be exported.
+        #[clap()]
+        game: String,
+    },
+    /// Add a game's backups from a bundle created by the `export` command
+    ///
+    /// The game folder is named the same way as for a new backup.
+    /// If the game already has backups in the target folder,
+    /// Ludusavi will exit with an error, unless you set `--merge`.
+    Import {
+        /// Directory in which to store the imported backups.
+        /// When unset, this defaults to the backup path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// If the game already has backups,
+        /// add any of the bundle's backups that aren't there yet.
+        /// Backups that conflict with existing ones will cause an error.
+        #[clap(long)]
+        merge: bool,
+
+        /// Bundle to import.
+        #[clap(value_parser = parse_strict_path)]
+        bundle: StrictPath,
+    },
+    /// Create initial backups from save data kept by another tool
+    ///
+    /// Each folder in the source is matched to a game by its name,
+    /// and its files are backed up as if they had been found in that game's save folder on this system,
+    /// which is where they will be restored.
+    /// Games that already have backups are skipped.
+    /// Folders that don't match any game are listed so that you can choose one with `--map`.
+    ImportExternal {
+        /// Directory in which to store the new backups.
+        /// When unset, this defaults to the backup path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// How the source data is organized.
+        /// `folders` expects one subfolder per game, containing that game's save files.
+        #[clap(long, value_parser = possible_values!(ExternalFormat, ALL_NAMES), default_value = "folders")]
+        format: ExternalFormat,
+
+        /// Import a folder as a specific game, like `--map "Old Name=Game Title"`.
+        /// This may be repeated for multiple folders.
+        #[clap(long, value_name = "FOLDER=GAME", value_parser = parse_external_mapping)]
+        map: Vec<ExternalMapping>,
+
+        /// Directory containing the other tool's save data.
+        #[clap(value_parser = parse_strict_path)]
+        source: StrictPath,
+    },
     /// Options for Ludusavi's data set.
     Manifest {
         #[clap(subcommand)]
@@ -416,9 +968,9 @@ pub enum Subcommand {
         #[clap(long, value_parser = possible_values!(ZipCompression, ALL_NAMES))]
         compression: Option<ZipCompression>,
 
-        /// Compression level to use for new zip backups.
+        /// Compression level to use for new zip or tar.gz backups.
         /// When not specified, this defers to the config file.
-        /// Valid ranges: 1 to 9 for deflate/bzip2, -7 to 22 for zstd.
+        /// Valid ranges: 1 to 9 for deflate/bzip2/tar.gz, -7 to 22 for zstd.
         #[clap(long, allow_hyphen_values(true))]
         compression_level: Option<i32>,
 
@@ -443,15 +995,41 @@ pub enum Subcommand {
 
         /// Don't perform any cloud checks or synchronization.
         /// When not specified, this defers to the config file.
-        #[clap(long, conflicts_with("cloud_sync"))]
+        #[clap(long, visible_alias = "no-cloud", conflicts_with("cloud_sync"))]
         no_cloud_sync: bool,
 
         /// Commands to launch the game.
         /// Use `--` first to separate these from the `wrap` options;
         /// e.g., `ludusavi wrap --name foo -- foo.exe --windowed`.
-        #[clap(required = true)]
+        /// If omitted, Ludusavi will try to launch the game
+        /// using the manifest's launch info for its install folder.
+        #[clap()]
         commands: Vec<String>,
     },
+    /// Back up data repeatedly in the background
+    ///
+    /// This keeps running until it is stopped,
+    /// backing up with the settings from the config file on each interval.
+    /// The config file and manifest are reloaded before each round.
+    /// Games that seem to be running are skipped until the next round.
+    /// Results are written to the log file.
+    Schedule {
+        /// How long to wait between rounds of backups,
+        /// like `90s`, `30m`, `2h`, or `1d`.
+        /// A number without a unit is treated as minutes.
+        #[clap(long, value_parser = parse_interval)]
+        interval: std::time::Duration,
+
+        /// Skip games that have already been backed up today.
+        /// When no games are specified, only run once per day.
+        #[clap(long)]
+        once_per_day: bool,
+
+        /// Only back up these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
     /// Execute bulk requests using JSON input.
     ///
     /// If there is a problem with the entire input
@@ -488,11 +1066,49 @@ impl Subcommand {
             Self::Restore { force, .. } => *force,
             Self::Complete { .. } => false,
             Self::Backups { .. } => false,
+            Self::Maintain { force, .. } => *force,
+            Self::Scrub { .. } => false,
+            Self::Merge { force, .. } => *force,
+            Self::Delete { force, .. } => *force,
             Self::Find { .. } => false,
+            Self::DumpRegistry { .. } => false,
+            Self::Diff { .. } => false,
+            Self::Locate { .. } => false,
+            Self::Export { .. } => false,
+            Self::Import { .. } => false,
+            Self::ImportExternal { .. } => false,
             Self::Manifest { .. } => false,
             Self::Config { .. } => false,
             Self::Cloud { sub } => sub.force(),
             Self::Wrap { force, .. } => *force,
+            Self::Schedule { .. } => false,
+            Self::Api { .. } => false,
+            Self::Schema { .. } => false,
+        }
+    }
+
+    pub fn api(&self) -> bool {
+        match self {
+            Self::Backup { api, .. } => *api,
+            Self::Restore { api, .. } => *api,
+            Self::Complete { .. } => false,
+            Self::Backups { api, .. } => *api,
+            Self::Maintain { api, .. } => *api,
+            Self::Scrub { api, .. } => *api,
+            Self::Merge { api, .. } => *api,
+            Self::Delete { .. } => false,
+            Self::Find { api, .. } => *api,
+            Self::DumpRegistry { .. } => false,
+            Self::Diff { api, .. } => *api,
+            Self::Locate { .. } => false,
+            Self::Export { .. } => false,
+            Self::Import { .. } => false,
+            Self::ImportExternal { .. } => false,
+            Self::Manifest { .. } => false,
+            Self::Config { .. } => false,
+            Self::Cloud { sub } => sub.api(),
+            Self::Wrap { .. } => false,
+            Self::Schedule { .. } => false,
             Self::Api { .. } => false,
             Self::Schema { .. } => false,
         }
@@ -504,11 +1120,22 @@ impl Subcommand {
             Self::Restore { gui, .. } => *gui,
             Self::Complete { .. } => false,
             Self::Backups { .. } => false,
+            Self::Maintain { .. } => false,
+            Self::Scrub { .. } => false,
+            Self::Merge { .. } => false,
+            Self::Delete { .. } => false,
             Self::Find { .. } => false,
+            Self::DumpRegistry { .. } => false,
+            Self::Diff { .. } => false,
+            Self::Locate { .. } => false,
+            Self::Export { .. } => false,
+            Self::Import { .. } => false,
+            Self::ImportExternal { .. } => false,
             Self::Manifest { .. } => false,
             Self::Config { .. } => false,
             Self::Cloud { sub } => sub.gui(),
             Self::Wrap { gui, .. } => *gui,
+            Self::Schedule { .. } => false,
             Self::Api { .. } => false,
             Self::Schema { .. } => false,
         }
@@ -522,6 +1149,31 @@ pub enum ManifestSubcommand {
         /// Print information to stdout in machine-readable JSON.
         #[clap(long)]
         api: bool,
+
+        /// Save the manifest to this file instead of printing it.
+        /// If this is an existing folder, then the manifest is saved inside of it,
+        /// named according to the `export.name` template from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        output: Option<StrictPath>,
+    },
+    /// List the names of games in the manifest, including any custom entries.
+    List {
+        /// Only list games associated with this store,
+        /// either by a store ID or by store-specific save locations.
+        #[clap(long, value_parser = possible_values!(Store, ALL_NAMES))]
+        store: Option<Store>,
+
+        /// Only list games that have save locations with this tag.
+        #[clap(long, value_parser = possible_values!(Tag, ALL_NAMES))]
+        tag: Option<Tag>,
+
+        /// Only list games that have notes about their save data.
+        #[clap(long)]
+        with_notes: bool,
+
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
     },
     /// Check for any manifest updates and download if available.
     /// By default, does nothing if the most recent check was within the last 24 hours.
@@ -533,17 +1185,78 @@ pub enum ManifestSubcommand {
 }
 
 #[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
-pub enum ConfigSubcommand {
-    /// Print the active configuration.
-    Show {
+pub enum BackupsSubcommand {
+    /// Check whether a file is included in any backup.
+    ///
+    /// This reports each game whose latest backup contains the file,
+    /// along with the backup that stores it.
+    /// A file that the latest backup recorded as deleted is not included.
+    /// If no backup contains the file, Ludusavi will exit with an error.
+    FindFile {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
         /// Print information to stdout in machine-readable JSON.
+        /// This replaces the default, human-readable output.
         #[clap(long)]
         api: bool,
 
-        /// Print the default configuration.
-        #[clap(long)]
+        /// Original location of the file.
+        #[clap(value_parser = parse_strict_path)]
+        file: StrictPath,
+    },
+    /// Forget when games were last restored.
+    ///
+    /// Ludusavi records when each game was last restored
+    /// and warns if you try to restore it again soon afterward.
+    /// This clears that record.
+    ClearRestored {
+        /// Directory in which to find backups.
+        /// When unset, this defaults to the restore path from the config file.
+        #[clap(long, value_parser = parse_strict_path)]
+        path: Option<StrictPath>,
+
+        /// Only clear the record for these specific games.
+        /// Alternatively supports stdin (one value per line).
+        #[clap()]
+        games: Vec<String>,
+    },
+}
+
+#[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigSubcommand {
+    /// Print the active configuration.
+    Show {
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+
+        /// Print the default configuration.
+        #[clap(long)]
         default: bool,
     },
+    /// Print a single config value.
+    Get {
+        /// Print information to stdout in machine-readable JSON.
+        #[clap(long)]
+        api: bool,
+
+        /// Dotted path to the value, as it appears in the config file
+        /// (e.g., `backup.format.chosen` or `roots.0.path`).
+        key: String,
+    },
+    /// Change a single config value and save the config file.
+    Set {
+        /// Dotted path to the value, as it appears in the config file
+        /// (e.g., `backup.format.chosen` or `roots.0.path`).
+        key: String,
+
+        /// New value, parsed as YAML.
+        /// It must fit the expected type for the key.
+        value: String,
+    },
 }
 
 #[derive(clap::Subcommand, Clone, Debug, PartialEq, Eq)]
@@ -632,6 +1345,14 @@ impl CloudSubcommand {
         }
     }
 
+    pub fn api(&self) -> bool {
+        match self {
+            Self::Set { .. } => false,
+            Self::Upload { api, .. } => *api,
+            Self::Download { api, .. } => *api,
+        }
+    }
+
     pub fn gui(&self) -> bool {
         match self {
             Self::Set { .. } => false,
@@ -683,6 +1404,25 @@ pub enum CloudSetSubcommand {
         /// Port number.
         #[clap(long, default_value_t = 445)]
         port: i32,
+        /// Name of the share.
+        /// If set, the cloud path is treated as a folder within this share.
+        #[clap(long, default_value = "")]
+        share: String,
+        /// Username for authentication.
+        #[clap(long)]
+        username: String,
+        /// Password for authentication.
+        #[clap(long, default_value = "")]
+        password: String,
+    },
+    /// Use an SFTP server.
+    Sftp {
+        /// Host URL.
+        #[clap(long)]
+        host: String,
+        /// Port number.
+        #[clap(long, default_value_t = 22)]
+        port: i32,
         /// Username for authentication.
         #[clap(long)]
         username: String,
@@ -706,6 +1446,42 @@ pub enum CloudSetSubcommand {
         #[clap(long, default_value = WebDavProvider::OTHER, value_parser = possible_values!(WebDavProvider, ALL_CLI))]
         provider: WebDavProvider,
     },
+    /// Use Amazon S3 or an S3-compatible service.
+    S3 {
+        /// Bucket name.
+        #[clap(long)]
+        bucket: String,
+        /// Region of the bucket.
+        #[clap(long, default_value = "")]
+        region: String,
+        /// Endpoint URL, for S3-compatible services other than AWS.
+        #[clap(long, default_value = "")]
+        endpoint: String,
+        /// Access key ID for authentication.
+        #[clap(long)]
+        access_key_id: String,
+        /// Secret access key for authentication.
+        #[clap(long)]
+        secret_access_key: String,
+    },
+    /// Use Backblaze B2.
+    B2 {
+        /// Bucket name.
+        #[clap(long)]
+        bucket: String,
+        /// Account ID or application key ID.
+        #[clap(long)]
+        account: String,
+        /// Application key.
+        #[clap(long)]
+        key: String,
+    },
+    /// Use a folder on this computer, such as a mounted network drive.
+    Local {
+        /// Folder in which to store the cloud path.
+        #[clap(long)]
+        path: String,
+    },
 }
 
 #[derive(Args, Clone, Debug, PartialEq, Eq)]
@@ -719,7 +1495,7 @@ pub struct WrapSubcommand {
     pub infer: Option<Launcher>,
 
     /// Directly set game name as known to Ludusavi
-    #[clap(long)]
+    #[clap(long, visible_alias = "game")]
     pub name: Option<String>,
 }
 
@@ -731,6 +1507,8 @@ pub enum SchemaSubcommand {
     ApiOutput,
     #[clap(about = "Schema for config.yaml")]
     Config,
+    #[clap(about = "Schema for the manifest format")]
+    Manifest,
     #[clap(
         about = "Schema for general command output in --api mode (`backup`, `restore`, `backups`, `find`, `cloud upload`, `cloud download`)"
     )]
@@ -753,6 +1531,15 @@ pub struct Cli {
     #[clap(long)]
     pub try_manifest_update: bool,
 
+    /// Print the absolute path of the config file and exit.
+    #[clap(long)]
+    pub print_config_path: bool,
+
+    /// Print the absolute path of the configured backup folder and exit.
+    /// When combined with `--print-config-path`, this is printed on the second line.
+    #[clap(long)]
+    pub print_backup_path: bool,
+
     #[clap(subcommand)]
     pub sub: Option<Subcommand>,
 }
@@ -782,6 +1569,29 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: None,
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_print_paths() {
+        check_args(
+            &[
+                "ludusavi",
+                "--config",
+                "tests/config",
+                "--print-config-path",
+                "--print-backup-path",
+            ],
+            Cli {
+                config: Some(PathBuf::from("tests/config")),
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: true,
+                print_backup_path: true,
                 sub: None,
             },
         );
@@ -795,12 +1605,16 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Backup {
                     preview: false,
                     path: None,
                     force: false,
                     wine_prefix: None,
                     api: false,
+                    ndjson: false,
+                    summary: false,
                     gui: false,
                     sort: None,
                     format: None,
@@ -811,6 +1625,56 @@ mod tests {
                     cloud_sync: false,
                     no_cloud_sync: false,
                     dump_registry: false,
+                    duplicates: false,
+                    only_tag: vec![],
+                    exclude_tag: vec![],
+                    batch: None,
+                    comment: None,
+                    full_scan: false,
+                    exclude: vec![],
+                    games_from: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backup_with_no_cloud_alias() {
+        check_args(
+            &["ludusavi", "backup", "--no-cloud"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Backup {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    wine_prefix: None,
+                    api: false,
+                    ndjson: false,
+                    summary: false,
+                    gui: false,
+                    sort: None,
+                    format: None,
+                    compression: None,
+                    compression_level: None,
+                    full_limit: None,
+                    differential_limit: None,
+                    cloud_sync: false,
+                    no_cloud_sync: true,
+                    dump_registry: false,
+                    duplicates: false,
+                    only_tag: vec![],
+                    exclude_tag: vec![],
+                    batch: None,
+                    comment: None,
+                    full_scan: false,
+                    exclude: vec![],
+                    games_from: None,
                     games: vec![],
                 }),
             },
@@ -845,6 +1709,24 @@ mod tests {
                 "2",
                 "--cloud-sync",
                 "--dump-registry",
+                "--duplicates",
+                "--only-tag",
+                "save",
+                "--only-tag",
+                "config",
+                "--exclude-tag",
+                "config",
+                "--batch",
+                "10",
+                "--comment",
+                "before {date}",
+                "--full-scan",
+                "--exclude",
+                "game2",
+                "--exclude",
+                "other*",
+                "--games-from",
+                "tests/games.txt",
                 "game1",
                 "game2",
             ],
@@ -852,12 +1734,16 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Backup {
                     preview: true,
                     path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
                     force: true,
                     wine_prefix: Some(StrictPath::relative(s("tests/wine-prefix"), Some(repo_raw()))),
                     api: true,
+                    ndjson: false,
+                    summary: false,
                     gui: true,
                     sort: Some(CliSort::Name),
                     format: Some(BackupFormat::Zip),
@@ -868,6 +1754,14 @@ mod tests {
                     cloud_sync: true,
                     no_cloud_sync: false,
                     dump_registry: true,
+                    duplicates: true,
+                    only_tag: vec![Tag::Save, Tag::Config],
+                    exclude_tag: vec![Tag::Config],
+                    batch: Some(10),
+                    comment: Some(s("before {date}")),
+                    full_scan: true,
+                    exclude: vec![s("game2"), s("other*")],
+                    games_from: Some(StrictPath::relative(s("tests/games.txt"), Some(repo_raw()))),
                     games: vec![s("game1"), s("game2")],
                 }),
             },
@@ -882,12 +1776,16 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Backup {
                     preview: false,
                     path: Some(StrictPath::relative(s("tests/fake"), Some(repo_raw()))),
                     force: false,
                     wine_prefix: None,
                     api: false,
+                    ndjson: false,
+                    summary: false,
                     gui: false,
                     sort: None,
                     format: None,
@@ -898,6 +1796,14 @@ mod tests {
                     cloud_sync: false,
                     no_cloud_sync: false,
                     dump_registry: false,
+                    duplicates: false,
+                    only_tag: vec![],
+                    exclude_tag: vec![],
+                    batch: None,
+                    comment: None,
+                    full_scan: false,
+                    exclude: vec![],
+                    games_from: None,
                     games: vec![],
                 }),
             },
@@ -920,12 +1826,16 @@ mod tests {
                     config: None,
                     no_manifest_update: false,
                     try_manifest_update: false,
+                    print_config_path: false,
+                    print_backup_path: false,
                     sub: Some(Subcommand::Backup {
                         preview: false,
                         path: None,
                         force: false,
                         wine_prefix: None,
                         api: false,
+                        ndjson: false,
+                        summary: false,
                         gui: false,
                         sort: Some(sort),
                         format: None,
@@ -936,6 +1846,14 @@ mod tests {
                         cloud_sync: false,
                         no_cloud_sync: false,
                         dump_registry: false,
+                        duplicates: false,
+                        only_tag: vec![],
+                        exclude_tag: vec![],
+                        batch: None,
+                        comment: None,
+                        full_scan: false,
+                        exclude: vec![],
+                        games_from: None,
                         games: vec![],
                     }),
                 },
@@ -951,12 +1869,16 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Backup {
                     preview: false,
                     path: None,
                     force: false,
                     wine_prefix: None,
                     api: false,
+                    ndjson: false,
+                    summary: false,
                     gui: false,
                     sort: None,
                     format: None,
@@ -967,12 +1889,78 @@ mod tests {
                     cloud_sync: false,
                     no_cloud_sync: false,
                     dump_registry: false,
+                    duplicates: false,
+                    only_tag: vec![],
+                    exclude_tag: vec![],
+                    batch: None,
+                    comment: None,
+                    full_scan: false,
+                    exclude: vec![],
+                    games_from: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backup_with_ndjson() {
+        check_args(
+            &["ludusavi", "backup", "--preview", "--ndjson"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Backup {
+                    preview: true,
+                    path: None,
+                    force: false,
+                    wine_prefix: None,
+                    api: false,
+                    ndjson: true,
+                    summary: false,
+                    gui: false,
+                    sort: None,
+                    format: None,
+                    compression: None,
+                    compression_level: None,
+                    full_limit: None,
+                    differential_limit: None,
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    dump_registry: false,
+                    duplicates: false,
+                    only_tag: vec![],
+                    exclude_tag: vec![],
+                    batch: None,
+                    comment: None,
+                    full_scan: false,
+                    exclude: vec![],
+                    games_from: None,
                     games: vec![],
                 }),
             },
         );
     }
 
+    #[test]
+    fn rejects_cli_backup_with_ndjson_and_api() {
+        check_args_err(
+            &["ludusavi", "backup", "--ndjson", "--api"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_backup_with_summary_and_ndjson() {
+        check_args_err(
+            &["ludusavi", "backup", "--summary", "--ndjson"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
     #[test]
     fn accepts_cli_restore_with_minimal_arguments() {
         check_args(
@@ -981,17 +1969,32 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Restore {
                     preview: false,
                     path: None,
                     force: false,
                     api: false,
+                    summary: false,
                     gui: false,
                     sort: None,
                     backup: None,
+                    before: None,
+                    after: None,
                     cloud_sync: false,
                     no_cloud_sync: false,
                     dump_registry: false,
+                    duplicates: false,
+                    constructive: false,
+                    interactive: false,
+                    yes: false,
+                    file: None,
+                    target_root: vec![],
+                    force_redirect_fallback: false,
+                    registry_hive: vec![],
+                    exclude: vec![],
+                    games_from: None,
                     games: vec![],
                 }),
             },
@@ -1015,6 +2018,19 @@ mod tests {
                 ".",
                 "--cloud-sync",
                 "--dump-registry",
+                "--duplicates",
+                "--constructive",
+                "--file",
+                "saves/*",
+                "--target-root",
+                "game1=/new/location",
+                "--force-redirect-fallback",
+                "--registry-hive",
+                "hkcu",
+                "--exclude",
+                "game2",
+                "--games-from",
+                "tests/games.txt",
                 "game1",
                 "game2",
             ],
@@ -1022,6 +2038,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Restore {
                     preview: true,
                     path: Some(StrictPath::relative(
@@ -1030,18 +2048,233 @@ mod tests {
                     )),
                     force: true,
                     api: true,
+                    summary: false,
                     gui: false,
                     sort: Some(CliSort::Name),
                     backup: Some(s(".")),
+                    before: None,
+                    after: None,
                     cloud_sync: true,
                     no_cloud_sync: false,
                     dump_registry: true,
+                    duplicates: true,
+                    constructive: true,
+                    interactive: false,
+                    yes: false,
+                    file: Some(s("saves/*")),
+                    target_root: vec![TargetRoot {
+                        game: s("game1"),
+                        path: StrictPath::relative(s("/new/location"), Some(StrictPath::cwd().raw())),
+                    }],
+                    force_redirect_fallback: true,
+                    registry_hive: vec![s("HKEY_CURRENT_USER")],
+                    exclude: vec![s("game2")],
+                    games_from: Some(StrictPath::relative(
+                        s("tests/games.txt"),
+                        Some(StrictPath::cwd().interpret().unwrap()),
+                    )),
                     games: vec![s("game1"), s("game2")],
                 }),
             },
         );
     }
 
+    #[test]
+    fn accepts_cli_restore_with_time_window() {
+        check_args(
+            &[
+                "ludusavi",
+                "restore",
+                "--after",
+                "2024-01-02T03:04:05Z",
+                "--before",
+                "2024-01-03T05:04:05+02:00",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Restore {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    api: false,
+                    summary: false,
+                    gui: false,
+                    sort: None,
+                    backup: None,
+                    before: Some(
+                        chrono::NaiveDate::from_ymd_opt(2024, 1, 3)
+                            .unwrap()
+                            .and_hms_opt(3, 4, 5)
+                            .unwrap()
+                            .and_utc(),
+                    ),
+                    after: Some(
+                        chrono::NaiveDate::from_ymd_opt(2024, 1, 2)
+                            .unwrap()
+                            .and_hms_opt(3, 4, 5)
+                            .unwrap()
+                            .and_utc(),
+                    ),
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    dump_registry: false,
+                    duplicates: false,
+                    constructive: false,
+                    interactive: false,
+                    yes: false,
+                    file: None,
+                    target_root: vec![],
+                    force_redirect_fallback: false,
+                    registry_hive: vec![],
+                    exclude: vec![],
+                    games_from: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_malformed_time() {
+        check_args_err(
+            &["ludusavi", "restore", "--before", "2024-01-02"],
+            clap::error::ErrorKind::ValueValidation,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_backup_and_time_window() {
+        check_args_err(
+            &[
+                "ludusavi",
+                "restore",
+                "--backup",
+                ".",
+                "--after",
+                "2024-01-02T03:04:05Z",
+            ],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_restore_with_interactive() {
+        check_args(
+            &["ludusavi", "restore", "--interactive", "--yes", "foo"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Restore {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    api: false,
+                    summary: false,
+                    gui: false,
+                    sort: None,
+                    backup: None,
+                    before: None,
+                    after: None,
+                    cloud_sync: false,
+                    no_cloud_sync: false,
+                    dump_registry: false,
+                    duplicates: false,
+                    constructive: false,
+                    interactive: true,
+                    yes: true,
+                    file: None,
+                    target_root: vec![],
+                    force_redirect_fallback: false,
+                    registry_hive: vec![],
+                    exclude: vec![],
+                    games_from: None,
+                    games: vec![s("foo")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_yes_without_interactive() {
+        check_args_err(
+            &["ludusavi", "restore", "--yes", "foo"],
+            clap::error::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_interactive_and_backup() {
+        check_args_err(
+            &["ludusavi", "restore", "--interactive", "--backup", ".", "foo"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn rejects_cli_restore_with_interactive_and_api_or_gui() {
+        check_args_err(
+            &["ludusavi", "restore", "--interactive", "--api", "foo"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+        check_args_err(
+            &["ludusavi", "restore", "--interactive", "--gui", "foo"],
+            clap::error::ErrorKind::ArgumentConflict,
+        );
+    }
+
+    #[test]
+    fn can_match_game_glob() {
+        let titles = [
+            s("The Witcher"),
+            s("The Witcher 3"),
+            s("Witcher Fan Game"),
+            s("Star*Game"),
+        ];
+
+        assert_eq!(None, match_game_glob("The Witcher", &titles));
+        assert_eq!(
+            Some(vec![s("The Witcher"), s("The Witcher 3")]),
+            match_game_glob("The Witcher*", &titles),
+        );
+        assert_eq!(
+            Some(vec![s("The Witcher 3")]),
+            match_game_glob("The Witcher ?", &titles)
+        );
+        assert_eq!(Some(vec![s("Star*Game")]), match_game_glob("Star[*]Game", &titles));
+        assert_eq!(Some(vec![]), match_game_glob("Witcher*", &titles[..2]));
+    }
+
+    #[test]
+    fn can_find_closest_game_titles() {
+        let titles = [s("The Witcher"), s("The Witcher 3"), s("Unrelated")];
+
+        assert_eq!(
+            vec![s("The Witcher"), s("The Witcher 3")],
+            closest_game_titles("The Witchr*", &titles),
+        );
+        assert_eq!(Vec::<String>::new(), closest_game_titles("Zzz*", &titles));
+    }
+
+    #[test]
+    fn can_parse_game_list() {
+        assert_eq!(
+            vec![s("game1"), s("game2")],
+            parse_game_list(
+                &StrictPath::new(format!("{}/tests/games.txt", repo_raw()))
+                    .read()
+                    .unwrap()
+            ),
+        );
+        assert_eq!(Vec::<String>::new(), parse_game_list(" \n# comment\n\n"));
+    }
+
     #[test]
     fn rejects_cli_restore_with_nonexistent_path() {
         check_args_err(
@@ -1050,6 +2283,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_cli_restore_with_malformed_target_root() {
+        check_args_err(
+            &["ludusavi", "restore", "--target-root", "/new/location"],
+            clap::error::ErrorKind::ValueValidation,
+        );
+        check_args_err(
+            &["ludusavi", "restore", "--target-root", "game1="],
+            clap::error::ErrorKind::ValueValidation,
+        );
+    }
+
     #[test]
     fn accepts_cli_restore_with_sort_variants() {
         let cases = [
@@ -1066,17 +2311,32 @@ mod tests {
                     config: None,
                     no_manifest_update: false,
                     try_manifest_update: false,
+                    print_config_path: false,
+                    print_backup_path: false,
                     sub: Some(Subcommand::Restore {
                         preview: false,
                         path: None,
                         force: false,
                         api: false,
+                        summary: false,
                         gui: false,
                         sort: Some(sort),
                         backup: None,
+                        before: None,
+                        after: None,
                         cloud_sync: false,
                         no_cloud_sync: false,
                         dump_registry: false,
+                        duplicates: false,
+                        constructive: false,
+                        interactive: false,
+                        yes: false,
+                        file: None,
+                        target_root: vec![],
+                        force_redirect_fallback: false,
+                        registry_hive: vec![],
+                        exclude: vec![],
+                        games_from: None,
                         games: vec![],
                     }),
                 },
@@ -1092,6 +2352,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Bash,
                 }),
@@ -1107,6 +2369,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Fish,
                 }),
@@ -1122,6 +2386,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Zsh,
                 }),
@@ -1137,6 +2403,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::PowerShell,
                 }),
@@ -1152,6 +2420,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Complete {
                     shell: CompletionShell::Elvish,
                 }),
@@ -1167,7 +2437,10 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Backups {
+                    sub: None,
                     path: None,
                     api: false,
                     games: vec![],
@@ -1192,7 +2465,10 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Backups {
+                    sub: None,
                     path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
                     api: true,
                     games: vec![s("game1"), s("game2")],
@@ -1201,6 +2477,623 @@ mod tests {
         );
     }
 
+    #[test]
+    fn accepts_cli_backups_find_file() {
+        check_args(
+            &[
+                "ludusavi",
+                "backups",
+                "find-file",
+                "--path",
+                "tests/backup",
+                "--api",
+                "tests/root/file.txt",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Backups {
+                    sub: Some(BackupsSubcommand::FindFile {
+                        path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                        api: true,
+                        file: StrictPath::relative(s("tests/root/file.txt"), Some(repo_raw())),
+                    }),
+                    path: None,
+                    api: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_backups_clear_restored() {
+        check_args(
+            &[
+                "ludusavi",
+                "backups",
+                "clear-restored",
+                "--path",
+                "tests/backup",
+                "game1",
+                "game2",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Backups {
+                    sub: Some(BackupsSubcommand::ClearRestored {
+                        path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                        games: vec![s("game1"), s("game2")],
+                    }),
+                    path: None,
+                    api: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_maintain_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "maintain"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Maintain {
+                    preview: false,
+                    path: None,
+                    force: false,
+                    api: false,
+                    full_limit: None,
+                    differential_limit: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_manifest_list_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "manifest",
+                "list",
+                "--store",
+                "gogGalaxy",
+                "--tag",
+                "save",
+                "--with-notes",
+                "--api",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::List {
+                        store: Some(Store::GogGalaxy),
+                        tag: Some(Tag::Save),
+                        with_notes: true,
+                        api: true,
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_manifest_show_with_all_arguments() {
+        check_args(
+            &["ludusavi", "manifest", "show", "--api", "--output", "tmp/manifest.json"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Manifest {
+                    sub: ManifestSubcommand::Show {
+                        api: true,
+                        output: Some(StrictPath::relative(s("tmp/manifest.json"), Some(repo_raw()))),
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_maintain_with_prune_alias() {
+        check_args(
+            &["ludusavi", "prune", "--preview"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Maintain {
+                    preview: true,
+                    path: None,
+                    force: false,
+                    api: false,
+                    full_limit: None,
+                    differential_limit: None,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_maintain_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "maintain",
+                "--preview",
+                "--path",
+                "tests/backup",
+                "--force",
+                "--api",
+                "--full-limit",
+                "1",
+                "--differential-limit",
+                "2",
+                "game1",
+                "game2",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Maintain {
+                    preview: true,
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    force: true,
+                    api: true,
+                    full_limit: Some(1),
+                    differential_limit: Some(2),
+                    games: vec![s("game1"), s("game2")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_scrub_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "scrub"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Scrub {
+                    path: None,
+                    api: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_scrub_with_all_arguments() {
+        check_args(
+            &["ludusavi", "scrub", "--path", "tests/backup", "--api", "game1", "game2"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Scrub {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    api: true,
+                    games: vec![s("game1"), s("game2")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_delete_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "delete", "--id", "bak", "game1"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Delete {
+                    path: None,
+                    force: false,
+                    id: s("bak"),
+                    game: s("game1"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_delete_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "delete",
+                "--path",
+                "tests/backup",
+                "--force",
+                "--id",
+                "bak",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Delete {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    force: true,
+                    id: s("bak"),
+                    game: s("game1"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_merge_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "merge"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Merge {
+                    path: None,
+                    force: false,
+                    api: false,
+                    backup: None,
+                    keep: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_merge_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "merge",
+                "--path",
+                "tests/backup",
+                "--force",
+                "--api",
+                "--backup",
+                "bak",
+                "--keep",
+                "game1",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Merge {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    force: true,
+                    api: true,
+                    backup: Some(s("bak")),
+                    keep: true,
+                    games: vec![s("game1")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_dump_registry_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "dump-registry", "foo"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::DumpRegistry {
+                    path: None,
+                    id: None,
+                    output: None,
+                    game: s("foo"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_diff_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "diff", "foo", "backup-1", "backup-2"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Diff {
+                    path: None,
+                    api: false,
+                    game: s("foo"),
+                    from: s("backup-1"),
+                    to: s("backup-2"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_diff_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "diff",
+                "--path",
+                "tests/backup",
+                "--api",
+                "foo",
+                "backup-1",
+                "backup-2",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Diff {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    api: true,
+                    game: s("foo"),
+                    from: s("backup-1"),
+                    to: s("backup-2"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_locate_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "locate",
+                "--path",
+                "tests/backup",
+                "--id",
+                "backup-1",
+                "foo",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Locate {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    id: Some(s("backup-1")),
+                    game: s("foo"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_dump_registry_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "dump-registry",
+                "--path",
+                "tests/backup",
+                "--id",
+                "backup-1",
+                "--output",
+                "tmp/foo.reg",
+                "foo",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::DumpRegistry {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    id: Some(s("backup-1")),
+                    output: Some(StrictPath::relative(s("tmp/foo.reg"), Some(repo_raw()))),
+                    game: s("foo"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_export_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "export",
+                "--path",
+                "tests/backup",
+                "--out",
+                "tmp/bundle.zip",
+                "foo",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Export {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    out: StrictPath::relative(s("tmp/bundle.zip"), Some(repo_raw())),
+                    game: s("foo"),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_export_without_output() {
+        check_args_err(
+            &["ludusavi", "export", "foo"],
+            clap::error::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn accepts_cli_import_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "import", "tmp/bundle.zip"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Import {
+                    path: None,
+                    merge: false,
+                    bundle: StrictPath::relative(s("tmp/bundle.zip"), Some(repo_raw())),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_import_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "import",
+                "--path",
+                "tests/backup",
+                "--merge",
+                "tmp/bundle.zip",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Import {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    merge: true,
+                    bundle: StrictPath::relative(s("tmp/bundle.zip"), Some(repo_raw())),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_import_external_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "import-external", "tmp/saves"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::ImportExternal {
+                    path: None,
+                    format: ExternalFormat::Folders,
+                    map: vec![],
+                    source: StrictPath::relative(s("tmp/saves"), Some(repo_raw())),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_import_external_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "import-external",
+                "--path",
+                "tests/backup",
+                "--format",
+                "folders",
+                "--map",
+                "Old Name=Game 1",
+                "--map",
+                "other=Game 2",
+                "tmp/saves",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::ImportExternal {
+                    path: Some(StrictPath::relative(s("tests/backup"), Some(repo_raw()))),
+                    format: ExternalFormat::Folders,
+                    map: vec![
+                        ExternalMapping {
+                            folder: s("Old Name"),
+                            game: s("Game 1"),
+                        },
+                        ExternalMapping {
+                            folder: s("other"),
+                            game: s("Game 2"),
+                        },
+                    ],
+                    source: StrictPath::relative(s("tmp/saves"), Some(repo_raw())),
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn rejects_cli_import_external_with_invalid_map() {
+        check_args_err(
+            &["ludusavi", "import-external", "--map", "no-game", "tmp/saves"],
+            clap::error::ErrorKind::ValueValidation,
+        );
+    }
+
     #[test]
     fn accepts_cli_find_with_minimal_arguments() {
         check_args(
@@ -1209,6 +3102,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Find {
                     api: false,
                     multiple: false,
@@ -1218,6 +3113,7 @@ mod tests {
                     steam_id: None,
                     gog_id: None,
                     lutris_id: None,
+                    save_dir: None,
                     normalized: false,
                     fuzzy: false,
                     disabled: false,
@@ -1246,6 +3142,8 @@ mod tests {
                 "102",
                 "--lutris-id",
                 "slug",
+                "--save-dir",
+                "tests/root1",
                 "--normalized",
                 "--fuzzy",
                 "--disabled",
@@ -1257,6 +3155,8 @@ mod tests {
                 config: None,
                 no_manifest_update: false,
                 try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
                 sub: Some(Subcommand::Find {
                     api: true,
                     multiple: true,
@@ -1266,6 +3166,7 @@ mod tests {
                     steam_id: Some(101),
                     gog_id: Some(102),
                     lutris_id: Some("slug".to_string()),
+                    save_dir: Some(StrictPath::relative(s("tests/root1"), Some(repo_raw()))),
                     normalized: true,
                     fuzzy: true,
                     disabled: true,
@@ -1275,4 +3176,113 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn accepts_cli_config_get() {
+        check_args(
+            &["ludusavi", "config", "get", "backup.format.chosen"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Config {
+                    sub: ConfigSubcommand::Get {
+                        api: false,
+                        key: s("backup.format.chosen"),
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_config_set() {
+        check_args(
+            &["ludusavi", "config", "set", "backup.retention.full", "3"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Config {
+                    sub: ConfigSubcommand::Set {
+                        key: s("backup.retention.full"),
+                        value: s("3"),
+                    },
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_schedule_with_minimal_arguments() {
+        check_args(
+            &["ludusavi", "schedule", "--interval", "30m"],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Schedule {
+                    interval: std::time::Duration::from_secs(30 * 60),
+                    once_per_day: false,
+                    games: vec![],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn accepts_cli_schedule_with_all_arguments() {
+        check_args(
+            &[
+                "ludusavi",
+                "schedule",
+                "--interval",
+                "2h",
+                "--once-per-day",
+                "game1",
+                "game2",
+            ],
+            Cli {
+                config: None,
+                no_manifest_update: false,
+                try_manifest_update: false,
+                print_config_path: false,
+                print_backup_path: false,
+                sub: Some(Subcommand::Schedule {
+                    interval: std::time::Duration::from_secs(2 * 60 * 60),
+                    once_per_day: true,
+                    games: vec![s("game1"), s("game2")],
+                }),
+            },
+        );
+    }
+
+    #[test]
+    fn can_parse_schedule_interval() {
+        use std::time::Duration;
+
+        assert_eq!(Ok(Duration::from_secs(90)), parse_interval("90s"));
+        assert_eq!(Ok(Duration::from_secs(5 * 60)), parse_interval("5m"));
+        assert_eq!(Ok(Duration::from_secs(5 * 60)), parse_interval("5"));
+        assert_eq!(Ok(Duration::from_secs(60 * 60)), parse_interval("1h"));
+        assert_eq!(Ok(Duration::from_secs(24 * 60 * 60)), parse_interval("1d"));
+        assert!(parse_interval("0m").is_err());
+        assert!(parse_interval("m").is_err());
+        assert!(parse_interval("5w").is_err());
+        assert!(parse_interval(&format!("{}d", u64::MAX / 60)).is_err());
+    }
+
+    #[test]
+    fn rejects_cli_schedule_without_interval() {
+        check_args_err(
+            &["ludusavi", "schedule"],
+            clap::error::ErrorKind::MissingRequiredArgument,
+        );
+    }
 }
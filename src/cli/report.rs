@@ -5,11 +5,13 @@ use itertools::Itertools;
 use crate::{
     cloud::CloudChange,
     lang::TRANSLATOR,
-    prelude::StrictPath,
+    prelude::{Error, StrictPath},
     resource::manifest::Os,
     scan::{
-        compare_ranked_titles_ref, layout::Backup, registry, BackupError, BackupInfo, DuplicateDetector,
-        OperationStatus, OperationStepDecision, ScanChange, ScanInfo, TitleMatch,
+        compare_ranked_titles_ref,
+        layout::{Backup, BackupKind, FileCoverage, FullBackup, MaintenanceInfo},
+        registry, BackupError, BackupInfo, ChangeSummary, DuplicateDetector, OperationStatus, OperationStepDecision,
+        OperationWarning, ScanChange, ScanInfo, TitleMatch,
     },
 };
 
@@ -30,9 +32,20 @@ pub struct ApiErrors {
     /// Ludusavi tried and failed to automatically synchronize with the cloud.
     #[serde(skip_serializing_if = "Option::is_none")]
     cloud_sync_failed: Option<concern::CloudSyncFailed>,
+    /// When this field is present,
+    /// the command stopped early because of this error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    failure: Option<concern::Failure>,
 }
 
 impl ApiErrors {
+    pub fn failure(error: &Error) -> Self {
+        Self {
+            failure: Some(concern::Failure::from(error)),
+            ..Default::default()
+        }
+    }
+
     /// This is used by the standard reporter.
     pub fn messages(&self) -> Vec<String> {
         let mut out = vec![];
@@ -55,6 +68,23 @@ pub mod concern {
 
     #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
     pub struct CloudSyncFailed {}
+
+    #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+    pub struct Failure {
+        /// Stable identifier for the kind of error.
+        pub code: String,
+        /// Human-readable explanation of the error.
+        pub message: String,
+    }
+
+    impl From<&crate::prelude::Error> for Failure {
+        fn from(error: &crate::prelude::Error) -> Self {
+            Self {
+                code: error.code().to_string(),
+                message: crate::lang::TRANSLATOR.handle_error(error),
+            }
+        }
+    }
 }
 
 #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
@@ -169,6 +199,9 @@ enum ApiGame {
         /// Raw data.
         #[serde(skip_serializing_if = "ApiDump::is_empty")]
         dump: ApiDump,
+        /// Non-fatal problems that may explain missing or unexpected results.
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<OperationWarning>,
     },
     /// Used by the `backups` command.
     Stored {
@@ -176,6 +209,37 @@ enum ApiGame {
         backup_path: String,
         backups: Vec<ApiBackup>,
     },
+    /// Used by the `backups find-file` command.
+    Covering {
+        /// The latest backup that stores the file.
+        backup: ApiBackup,
+        /// Hash of the file as stored in the backup.
+        hash: String,
+        /// Size of the file as stored in the backup.
+        bytes: u64,
+    },
+    /// Used by the `maintain` command.
+    Maintained {
+        /// Backups that exceeded the retention limits.
+        forgotten: Vec<String>,
+        /// Total size of the backup files that were (or would be) removed.
+        #[serde(rename = "freedBytes")]
+        freed_bytes: u64,
+    },
+    /// Used by the `scrub` command.
+    Scrubbed {
+        /// Original paths of files whose stored content no longer matches the recorded hash.
+        diverged: Vec<String>,
+    },
+    /// Used by the `merge` command.
+    Merged {
+        /// The new full backup, if the merge succeeded.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        backup: Option<ApiBackup>,
+        /// Why the merge failed, if it did.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
     /// Used by the `find` command.
     Found {
         /// How well the title matches the query.
@@ -188,12 +252,33 @@ enum ApiGame {
 #[serde(rename_all = "camelCase")]
 struct ApiBackup {
     name: String,
+    kind: BackupKind,
     when: chrono::DateTime<chrono::Utc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     os: Option<Os>,
     #[serde(skip_serializing_if = "Option::is_none")]
     comment: Option<String>,
     pub locked: bool,
+    /// Total size of the files that would be restored from this backup.
+    size: u64,
+    /// How long it took to create the backup, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<u64>,
+}
+
+impl From<&Backup> for ApiBackup {
+    fn from(backup: &Backup) -> Self {
+        Self {
+            name: backup.name().to_string(),
+            kind: backup.kind(),
+            when: *backup.when(),
+            os: backup.os(),
+            comment: backup.comment().cloned(),
+            locked: backup.locked(),
+            size: backup.total_size(),
+            duration_ms: backup.duration_ms(),
+        }
+    }
 }
 
 /// General output used by commands in `--api` mode
@@ -212,6 +297,82 @@ pub struct JsonOutput {
     /// Populated by the `cloud` commands.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     cloud: BTreeMap<String, CloudEntry>,
+    /// Items that are claimed by more than one game,
+    /// populated by the `backup` and `restore` commands when `--duplicates` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicates: Option<ApiDuplicates>,
+    /// Combined size of all backups for all games,
+    /// populated by the `backups` command when no games are specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_size: Option<u64>,
+}
+
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct ApiDuplicates {
+    /// Each key is a file path, and each value is the games that claim it.
+    files: BTreeMap<String, BTreeSet<String>>,
+    /// Each key is a registry path, and each value is the games that claim it.
+    registry: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// One line of `--ndjson` output for a game that finished processing.
+#[derive(Debug, serde::Serialize)]
+struct NdjsonGame {
+    name: String,
+    #[serde(flatten)]
+    game: ApiGame,
+}
+
+/// Final line of `--ndjson` output.
+#[derive(Debug, serde::Serialize)]
+struct NdjsonSummary<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<&'a ApiErrors>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overall: Option<&'a OperationStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicates: Option<&'a ApiDuplicates>,
+}
+
+/// Aggregate stats printed by `--summary` mode in place of each game's details.
+#[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiSummary {
+    /// How many games have saves.
+    games: usize,
+    /// How many bytes are used by those games' saves.
+    bytes: u64,
+    /// How many games are new or different since the last backup.
+    changed_games: usize,
+    /// How many games had at least one file or registry entry fail.
+    failed_games: usize,
+    /// How many files and registry entries were found with each kind of change.
+    changes: ChangeSummary,
+}
+
+impl ApiSummary {
+    fn add_game(&mut self, scan_info: &ScanInfo, backup_info: Option<&BackupInfo>, successful: bool) {
+        self.games += 1;
+        self.bytes += scan_info.sum_bytes(backup_info);
+
+        let changes = scan_info.count_changes();
+        if changes.brand_new() || changes.updated() {
+            self.changed_games += 1;
+        }
+        self.changes.merge(&scan_info.change_summary());
+        if !successful {
+            self.failed_games += 1;
+        }
+    }
+}
+
+/// Output of `--summary` mode when combined with `--api`.
+#[derive(Debug, serde::Serialize)]
+struct SummaryOutput<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<&'a ApiErrors>,
+    summary: &'a ApiSummary,
 }
 
 #[derive(Debug, Default, serde::Serialize, schemars::JsonSchema)]
@@ -228,9 +389,13 @@ pub enum Reporter {
         parts: Vec<String>,
         status: Option<OperationStatus>,
         errors: ApiErrors,
+        /// When set, only the aggregate stats are rendered.
+        summary: Option<ApiSummary>,
     },
     Json {
         output: JsonOutput,
+        /// When set, only the aggregate stats are rendered.
+        summary: Option<ApiSummary>,
     },
 }
 
@@ -240,6 +405,7 @@ impl Reporter {
             parts: vec![],
             status: Some(Default::default()),
             errors: Default::default(),
+            summary: None,
         }
     }
 
@@ -250,14 +416,26 @@ impl Reporter {
                 overall: Some(Default::default()),
                 games: Default::default(),
                 cloud: Default::default(),
+                duplicates: None,
+                total_size: None,
             },
+            summary: None,
+        }
+    }
+
+    /// Only render aggregate stats instead of each game's details.
+    pub fn summarize(&mut self) {
+        match self {
+            Self::Standard { summary, .. } | Self::Json { summary, .. } => {
+                *summary = Some(ApiSummary::default());
+            }
         }
     }
 
     fn set_errors(&mut self, f: impl FnOnce(&mut ApiErrors)) {
         match self {
             Reporter::Standard { errors, .. } => f(errors),
-            Reporter::Json { output } => {
+            Reporter::Json { output, .. } => {
                 if let Some(errors) = &mut output.errors.as_mut() {
                     f(errors)
                 } else {
@@ -293,6 +471,12 @@ impl Reporter {
         });
     }
 
+    fn trip_failure(&mut self, error: &Error) {
+        self.set_errors(|e| {
+            e.failure = Some(concern::Failure::from(error));
+        });
+    }
+
     pub fn suppress_overall(&mut self) {
         match self {
             Self::Standard { status, .. } => {
@@ -398,6 +582,10 @@ impl Reporter {
                     }
                 }
 
+                for warning in scan_info.all_warnings(backup_info) {
+                    parts.push(TRANSLATOR.cli_game_line_item_warning(warning));
+                }
+
                 if let Some(dumped_registry) = scan_info.dumped_registry.as_ref() {
                     let label = TRANSLATOR.custom_registry_label();
                     parts.push(format!("---------- {} ----------", &label));
@@ -412,7 +600,7 @@ impl Reporter {
                     status.add_game(scan_info, backup_info, decision == &OperationStepDecision::Processed);
                 }
             }
-            Self::Json { output } => {
+            Self::Json { output, .. } => {
                 let decision = decision.clone();
                 let mut files = BTreeMap::new();
                 let mut registry = BTreeMap::new();
@@ -529,11 +717,20 @@ impl Reporter {
                         files,
                         registry,
                         dump,
+                        warnings: scan_info.all_warnings(backup_info).into_iter().cloned().collect(),
                     },
                 );
             }
         }
 
+        match self {
+            Self::Standard { summary, .. } | Self::Json { summary, .. } => {
+                if let Some(summary) = summary.as_mut() {
+                    summary.add_game(scan_info, backup_info, successful);
+                }
+            }
+        }
+
         if !successful {
             self.trip_some_games_failed();
         }
@@ -557,10 +754,16 @@ impl Reporter {
                 parts.push(format!("  {} {}", TRANSLATOR.folder_label(), backup_dir.render()));
                 for backup in available_backups {
                     let mut line = format!(
-                        "  - \"{}\" ({})",
+                        "  - \"{}\" ({}, {}",
                         backup.name(),
                         backup.when_local().format("%Y-%m-%dT%H:%M:%S"),
+                        TRANSLATOR.adjusted_size(backup.total_size()),
                     );
+                    if let Some(duration_ms) = backup.duration_ms() {
+                        line += &format!(", {:.1}s", duration_ms as f64 / 1000.0);
+                    }
+                    line += ")";
+                    line += &format!(" [{:?}]", backup.kind());
                     if let Some(os) = backup.os() {
                         line += &format!(" [{os:?}]");
                     }
@@ -576,21 +779,12 @@ impl Reporter {
                 // Blank line between games.
                 parts.push("".to_string());
             }
-            Self::Json { output } => {
+            Self::Json { output, .. } => {
                 if available_backups.is_empty() {
                     return;
                 }
 
-                let mut backups = vec![];
-                for backup in available_backups {
-                    backups.push(ApiBackup {
-                        name: backup.name().to_string(),
-                        when: *backup.when(),
-                        os: backup.os(),
-                        comment: backup.comment().cloned(),
-                        locked: backup.locked(),
-                    });
-                }
+                let backups = available_backups.iter().map(ApiBackup::from).collect();
 
                 output.games.insert(
                     name.to_string(),
@@ -603,6 +797,171 @@ impl Reporter {
         }
     }
 
+    pub fn add_file_coverage(&mut self, display_title: &str, coverage: &FileCoverage) {
+        match self {
+            Self::Standard { parts, .. } => {
+                parts.push(format!("{}:", display_title));
+                parts.push(format!(
+                    "  - \"{}\" ({}) [{}]",
+                    coverage.backup.name(),
+                    coverage.backup.when_local().format("%Y-%m-%dT%H:%M:%S"),
+                    coverage.file.hash,
+                ));
+            }
+            Self::Json { output, .. } => {
+                output.games.insert(
+                    coverage.game.clone(),
+                    ApiGame::Covering {
+                        backup: ApiBackup::from(&coverage.backup),
+                        hash: coverage.file.hash.clone(),
+                        bytes: coverage.file.size,
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn add_maintenance(&mut self, name: &str, display_title: &str, info: &MaintenanceInfo) {
+        if info.is_empty() {
+            return;
+        }
+
+        match self {
+            Self::Standard { parts, .. } => {
+                parts.push(format!("{}:", display_title));
+                for backup in &info.forgotten {
+                    parts.push(format!("  - \"{}\"", backup));
+                }
+                parts.push(format!(
+                    "  {}: {}",
+                    TRANSLATOR.freed_space_label(),
+                    TRANSLATOR.adjusted_size(info.freed_bytes)
+                ));
+
+                // Blank line between games.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => {
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Maintained {
+                        forgotten: info.forgotten.clone(),
+                        freed_bytes: info.freed_bytes,
+                    },
+                );
+            }
+        }
+    }
+
+    pub fn add_maintenance_total(&mut self, freed_bytes: u64) {
+        if let Self::Standard { parts, .. } = self {
+            parts.push(TRANSLATOR.cli_maintenance_summary(freed_bytes));
+        }
+    }
+
+    pub fn add_scrub(&mut self, name: &str, display_title: &str, diverged: &[StrictPath]) -> bool {
+        if diverged.is_empty() {
+            return true;
+        }
+
+        match self {
+            Self::Standard { parts, .. } => {
+                parts.push(format!("{}:", display_title));
+                for file in diverged {
+                    parts.push(format!("  - {}", file.render()));
+                }
+
+                // Blank line between games.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => {
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Scrubbed {
+                        diverged: diverged.iter().map(|x| x.render()).collect(),
+                    },
+                );
+            }
+        }
+
+        self.trip_some_games_failed();
+        false
+    }
+
+    pub fn add_merge(&mut self, name: &str, display_title: &str, merged: &Result<FullBackup, Error>) -> bool {
+        match self {
+            Self::Standard { parts, .. } => {
+                parts.push(format!("{}:", display_title));
+                match merged {
+                    Ok(backup) => parts.push(format!("  - \"{}\"", backup.name)),
+                    Err(e) => parts.push(format!("  {}", TRANSLATOR.handle_error(e))),
+                }
+
+                // Blank line between games.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => {
+                output.games.insert(
+                    name.to_string(),
+                    ApiGame::Merged {
+                        backup: merged.as_ref().ok().map(|x| ApiBackup::from(&Backup::Full(x.clone()))),
+                        error: merged.as_ref().err().map(|e| TRANSLATOR.handle_error(e)),
+                    },
+                );
+            }
+        }
+
+        if merged.is_err() {
+            self.trip_some_games_failed();
+            return false;
+        }
+        true
+    }
+
+    /// List every file and registry key that multiple games claim.
+    /// This should be called after all games have been added to the detector.
+    pub fn add_duplicates(&mut self, duplicate_detector: &DuplicateDetector) {
+        let files = duplicate_detector.duplicated_files();
+        let registry = duplicate_detector.duplicated_registry();
+
+        match self {
+            Self::Standard { parts, .. } => {
+                if files.is_empty() && registry.is_empty() {
+                    return;
+                }
+
+                parts.push(TRANSLATOR.duplicates_label());
+                for (item, games) in files.iter().chain(registry.iter()) {
+                    parts.push(format!("  - {}", item));
+                    for game in games {
+                        parts.push(format!("    - {}", game));
+                    }
+                }
+
+                // Blank line before the summary.
+                parts.push("".to_string());
+            }
+            Self::Json { output, .. } => {
+                output.duplicates = Some(ApiDuplicates { files, registry });
+            }
+        }
+    }
+
+    pub fn add_total_size(&mut self, bytes: u64) {
+        match self {
+            Self::Standard { parts, .. } => {
+                parts.push(format!(
+                    "{} {}",
+                    TRANSLATOR.total_size_label(),
+                    TRANSLATOR.adjusted_size(bytes)
+                ));
+            }
+            Self::Json { output, .. } => {
+                output.total_size = Some(bytes);
+            }
+        }
+    }
+
     pub fn add_found_titles(&mut self, games: &BTreeMap<String, TitleMatch>) {
         match self {
             Self::Standard { parts, .. } => {
@@ -612,7 +971,7 @@ impl Reporter {
                     parts.push(name.to_owned());
                 }
             }
-            Self::Json { output } => {
+            Self::Json { output, .. } => {
                 for (name, info) in games {
                     output
                         .games
@@ -624,7 +983,33 @@ impl Reporter {
 
     fn render(&self, path: &StrictPath) -> String {
         match self {
-            Self::Standard { parts, status, errors } => match status {
+            Self::Standard {
+                summary: Some(summary),
+                errors,
+                ..
+            } => {
+                let mut out = TRANSLATOR.cli_aggregate_summary(
+                    summary.games,
+                    summary.bytes,
+                    summary.changed_games,
+                    summary.failed_games,
+                );
+                for message in errors.messages() {
+                    out += &format!("\n\n{message}");
+                }
+                out
+            }
+            Self::Json {
+                output,
+                summary: Some(summary),
+            } => serde_json::to_string_pretty(&SummaryOutput {
+                errors: output.errors.as_ref(),
+                summary,
+            })
+            .unwrap(),
+            Self::Standard {
+                parts, status, errors, ..
+            } => match status {
                 Some(status) => {
                     let mut out = parts.join("\n") + "\n" + &TRANSLATOR.cli_summary(status, path);
                     for message in errors.messages() {
@@ -634,15 +1019,74 @@ impl Reporter {
                 }
                 None => parts.join("\n"),
             },
-            Self::Json { output } => serde_json::to_string_pretty(&output).unwrap(),
+            Self::Json { output, .. } => serde_json::to_string_pretty(&output).unwrap(),
+        }
+    }
+
+    /// Print one game's result as its own line of JSON, without waiting for the other games.
+    /// Duplicates can't be known until every game is scanned, so they aren't flagged here.
+    pub fn stream_game(
+        name: &str,
+        scan_info: &ScanInfo,
+        backup_info: Option<&BackupInfo>,
+        decision: &OperationStepDecision,
+        dump_registry: bool,
+    ) {
+        for line in Self::render_stream_game(name, scan_info, backup_info, decision, dump_registry) {
+            println!("{line}");
+        }
+    }
+
+    fn render_stream_game(
+        name: &str,
+        scan_info: &ScanInfo,
+        backup_info: Option<&BackupInfo>,
+        decision: &OperationStepDecision,
+        dump_registry: bool,
+    ) -> Vec<String> {
+        let mut reporter = Self::json();
+        reporter.add_game(
+            name,
+            scan_info,
+            backup_info,
+            decision,
+            &DuplicateDetector::default(),
+            dump_registry,
+        );
+
+        match reporter {
+            Self::Json { output, .. } => output
+                .games
+                .into_iter()
+                .map(|(name, game)| serde_json::to_string(&NdjsonGame { name, game }).unwrap())
+                .collect(),
+            Self::Standard { .. } => vec![],
         }
     }
 
-    pub fn print_failure(&self) {
+    /// Print the final line after streaming games, with the overall stats and any errors.
+    pub fn print_stream_summary(&self) {
+        if let Self::Json { output, .. } = self {
+            let summary = NdjsonSummary {
+                errors: output.errors.as_ref(),
+                overall: output.overall.as_ref(),
+                duplicates: output.duplicates.as_ref(),
+            };
+            println!("{}", serde_json::to_string(&summary).unwrap());
+        }
+    }
+
+    /// Returns whether the failure was printed,
+    /// in which case it should not be printed again when the error reaches main.
+    pub fn print_failure(&mut self, error: &Error) -> bool {
         // The standard reporter doesn't need to print on failure because
         // that's handled generically in main.
         if let Self::Json { .. } = self {
+            self.trip_failure(error);
             self.print(&StrictPath::new("".to_string()));
+            true
+        } else {
+            false
         }
     }
 
@@ -658,6 +1102,8 @@ pub fn report_cloud_changes(changes: &[CloudChange], api: bool) {
             overall: None,
             games: Default::default(),
             cloud: Default::default(),
+            duplicates: None,
+            total_size: None,
         };
 
         output.cloud = changes
@@ -727,7 +1173,11 @@ Overall:
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                     "/file2".into(): ScannedFile {
                         size: 51_200,
@@ -736,7 +1186,11 @@ Overall:
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 },
                 found_registry_keys: hash_map! {
@@ -753,6 +1207,8 @@ Overall:
                 failed_registry: hash_map! {
                     "HKEY_CURRENT_USER/Key1".into(): BackupError::Test
                 },
+                warnings: vec![],
+                mirrors: Default::default(),
             }),
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
@@ -780,6 +1236,45 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_in_standard_mode_with_warnings() {
+        let mut reporter = Reporter::standard();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_map! {
+                    "/file1".into(): ScannedFile::new(102_400, "1"),
+                },
+                warnings: vec![OperationWarning::UnreadableFile { path: s("/file2") }],
+                ..Default::default()
+            },
+            Some(&BackupInfo {
+                warnings: vec![OperationWarning::UnremovableBackupFile { path: s("/old") }],
+                ..Default::default()
+            }),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+            false,
+        );
+        assert_eq!(
+            r#"
+foo [100.00 KiB]:
+  - /file1
+  - [WARNING] Unable to read file: /file2
+  - [WARNING] Unable to remove old backup file: /old
+
+Overall:
+  Games: 1
+  Size: 100.00 KiB
+  Location: /dev/null
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
     #[test]
     fn can_render_in_standard_mode_with_multiple_games_in_backup_mode() {
         let mut reporter = Reporter::standard();
@@ -796,7 +1291,11 @@ Overall:
                         ignored: false,
                         change: ScanChange::Same,
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 },
                 found_registry_keys: hash_map! {},
@@ -819,7 +1318,11 @@ Overall:
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 },
                 found_registry_keys: hash_map! {},
@@ -864,7 +1367,11 @@ Overall:
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                     "/backup/file2".into(): ScannedFile {
                         size: 51_200,
@@ -873,7 +1380,11 @@ Overall:
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 },
                 found_registry_keys: hash_map! {},
@@ -1012,6 +1523,111 @@ Overall:
         );
     }
 
+    fn add_summary_games(reporter: &mut Reporter) {
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_map! {
+                    "/new".into(): ScannedFile::new(1, "1".to_string()).change_as(ScanChange::New),
+                    "/failed".into(): ScannedFile::new(2, "2".to_string()).change_as(ScanChange::Different),
+                },
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            Some(&BackupInfo {
+                failed_files: hash_map! {
+                    "/failed".into(): BackupError::Test,
+                },
+                failed_registry: hash_map! {},
+                warnings: vec![],
+                mirrors: Default::default(),
+            }),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+            false,
+        );
+        reporter.add_game(
+            "bar",
+            &ScanInfo {
+                game_name: s("bar"),
+                found_files: hash_map! {
+                    "/same".into(): ScannedFile::new(4, "4".to_string()).change_as(ScanChange::Same),
+                },
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            None,
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+            false,
+        );
+    }
+
+    #[test]
+    fn can_render_in_standard_mode_with_summary() {
+        let mut reporter = Reporter::standard();
+        reporter.summarize();
+        add_summary_games(&mut reporter);
+
+        assert_eq!(
+            r#"
+Summary:
+  Games: 2
+  Size: 5 B
+  Changed: 1
+  Failed: 1
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_summary() {
+        let mut reporter = Reporter::json();
+        reporter.summarize();
+        add_summary_games(&mut reporter);
+
+        assert_eq!(
+            r#"
+{
+  "errors": {
+    "someGamesFailed": true
+  },
+  "summary": {
+    "games": 2,
+    "bytes": 5,
+    "changedGames": 1,
+    "failedGames": 1,
+    "changes": {
+      "files": {
+        "new": 1,
+        "different": 1,
+        "same": 1
+      },
+      "registryKeys": {
+        "new": 0,
+        "different": 0,
+        "same": 0
+      },
+      "registryValues": {
+        "new": 0,
+        "different": 0,
+        "same": 0
+      },
+      "ignoredFiles": 0,
+      "ignoredRegistryKeys": 0,
+      "ignoredRegistryValues": 0
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
     #[test]
     fn can_render_in_standard_mode_with_dumps() {
         let mut reporter = Reporter::standard();
@@ -1094,6 +1710,66 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_in_json_mode_with_failure() {
+        let mut reporter = Reporter::json();
+        let games = vec![s("foo")];
+
+        reporter.trip_unknown_games(games.clone());
+        reporter.trip_failure(&Error::CliUnrecognizedGames { games });
+        assert_eq!(
+            r#"
+{
+  "errors": {
+    "unknownGames": [
+      "foo"
+    ],
+    "failure": {
+      "code": "CliUnrecognizedGames",
+      "message": "No info for these games:\n  - foo"
+    }
+  },
+  "overall": {
+    "totalGames": 0,
+    "totalBytes": 0,
+    "processedGames": 0,
+    "processedBytes": 0,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 0
+    }
+  },
+  "games": {}
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_stream_line_for_one_game() {
+        assert_eq!(
+            vec![s(
+                r#"{"name":"foo","decision":"Processed","change":"Same","files":{"/file1":{"change":"Unknown","bytes":100}},"registry":{}}"#
+            )],
+            Reporter::render_stream_game(
+                "foo",
+                &ScanInfo {
+                    game_name: s("foo"),
+                    found_files: hash_map! {
+                        "/file1".into(): ScannedFile::new(100, "1"),
+                    },
+                    ..Default::default()
+                },
+                None,
+                &OperationStepDecision::Processed,
+                false,
+            ),
+        );
+    }
+
     #[test]
     fn can_render_in_json_mode_with_one_game_in_backup_mode() {
         let mut reporter = Reporter::json();
@@ -1120,6 +1796,8 @@ Overall:
                 failed_registry: hash_map! {
                     "HKEY_CURRENT_USER/Key1".into(): BackupError::Test
                 },
+                warnings: vec![],
+                mirrors: Default::default(),
             }),
             &OperationStepDecision::Processed,
             &DuplicateDetector::default(),
@@ -1205,7 +1883,11 @@ Overall:
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                     "/backup/file2".into(): ScannedFile {
                         size: 50,
@@ -1214,7 +1896,11 @@ Overall:
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 },
                 found_registry_keys: hash_map! {},
@@ -1263,6 +1949,70 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_in_json_mode_with_warnings() {
+        let mut reporter = Reporter::json();
+
+        reporter.add_game(
+            "foo",
+            &ScanInfo {
+                game_name: s("foo"),
+                found_files: hash_map! {
+                    "/file1".into(): ScannedFile::new(100, "1"),
+                },
+                warnings: vec![OperationWarning::OverlappingRoots {
+                    parent: s("/root"),
+                    child: s("/root/nested"),
+                }],
+                ..Default::default()
+            },
+            Some(&BackupInfo::default()),
+            &OperationStepDecision::Processed,
+            &DuplicateDetector::default(),
+            false,
+        );
+        assert_eq!(
+            r#"
+{
+  "overall": {
+    "totalGames": 1,
+    "totalBytes": 100,
+    "processedGames": 1,
+    "processedBytes": 100,
+    "changedGames": {
+      "new": 0,
+      "different": 0,
+      "same": 1
+    }
+  },
+  "games": {
+    "foo": {
+      "decision": "Processed",
+      "change": "Same",
+      "files": {
+        "/file1": {
+          "change": "Unknown",
+          "bytes": 100
+        }
+      },
+      "registry": {},
+      "warnings": [
+        {
+          "overlappingRoots": {
+            "parent": "/root",
+            "child": "/root/nested"
+          }
+        }
+      ]
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
     #[test]
     fn can_render_in_json_mode_with_duplicated_entries() {
         let mut reporter = Reporter::json();
@@ -1345,6 +2095,70 @@ Overall:
         );
     }
 
+    #[test]
+    fn can_render_duplicates_section() {
+        let mut duplicate_detector = DuplicateDetector::default();
+        for name in &["foo", "bar"] {
+            duplicate_detector.add_game(
+                &ScanInfo {
+                    game_name: s(name),
+                    found_files: hash_map! {
+                        "/file1".into(): ScannedFile::new(102_400, "1"),
+                    },
+                    found_registry_keys: hash_map! {
+                        "HKEY_CURRENT_USER/Key1".into(): ScannedRegistry::new(),
+                    },
+                    ..Default::default()
+                },
+                true,
+            );
+        }
+
+        let mut reporter = Reporter::standard();
+        reporter.suppress_overall();
+        reporter.add_duplicates(&duplicate_detector);
+        assert_eq!(
+            r#"
+Duplicates:
+  - /file1
+    - bar
+    - foo
+  - HKEY_CURRENT_USER/Key1
+    - bar
+    - foo
+"#
+            .trim_start(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+        reporter.add_duplicates(&duplicate_detector);
+        assert_eq!(
+            r#"
+{
+  "games": {},
+  "duplicates": {
+    "files": {
+      "/file1": [
+        "bar",
+        "foo"
+      ]
+    },
+    "registry": {
+      "HKEY_CURRENT_USER/Key1": [
+        "bar",
+        "foo"
+      ]
+    }
+  }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
     #[test]
     fn can_render_in_json_mode_with_different_file_changes() {
         let mut reporter = Reporter::json();
@@ -1472,6 +2286,122 @@ Overall:
       }
     }
   }
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_backups() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+
+        reporter.add_backups(
+            "foo",
+            "foo",
+            StrictPath::new(s("/backups/foo")),
+            &[
+                Backup::Full(FullBackup {
+                    name: s("."),
+                    when: chrono::DateTime::<chrono::Utc>::default(),
+                    files: btree_map! {
+                        s("drive-X/file1.txt"): crate::scan::layout::IndividualMappingFile {
+                            hash: s("abc"),
+                            size: 5,
+                            base: None,
+                            object: false,
+                            mtime: None,
+                        },
+                    },
+                    ..Default::default()
+                }),
+                Backup::Differential(crate::scan::layout::DifferentialBackup {
+                    name: s("backup-1"),
+                    when: chrono::DateTime::<chrono::Utc>::default(),
+                    comment: Some(s("note")),
+                    locked: true,
+                    duration_ms: Some(1500),
+                    inherited_size: 5,
+                    ..Default::default()
+                }),
+            ],
+        );
+        reporter.add_total_size(5);
+        assert_eq!(
+            r#"
+{
+  "games": {
+    "foo": {
+      "backupPath": "/backups/foo",
+      "backups": [
+        {
+          "name": ".",
+          "kind": "full",
+          "when": "1970-01-01T00:00:00Z",
+          "locked": false,
+          "size": 5
+        },
+        {
+          "name": "backup-1",
+          "kind": "differential",
+          "when": "1970-01-01T00:00:00Z",
+          "comment": "note",
+          "locked": true,
+          "size": 5,
+          "durationMs": 1500
+        }
+      ]
+    }
+  },
+  "totalSize": 5
+}
+            "#
+            .trim(),
+            reporter.render(&StrictPath::new(s("/dev/null")))
+        );
+    }
+
+    #[test]
+    fn can_render_in_json_mode_with_file_coverage() {
+        let mut reporter = Reporter::json();
+        reporter.suppress_overall();
+
+        reporter.add_file_coverage(
+            "foo",
+            &FileCoverage {
+                game: s("foo"),
+                backup: Backup::Full(crate::scan::layout::FullBackup {
+                    name: s("backup-1"),
+                    when: chrono::DateTime::<chrono::Utc>::default(),
+                    ..Default::default()
+                }),
+                file: crate::scan::layout::IndividualMappingFile {
+                    hash: s("abc"),
+                    size: 5,
+                    base: None,
+                    object: false,
+                    mtime: None,
+                },
+            },
+        );
+        assert_eq!(
+            r#"
+{
+  "games": {
+    "foo": {
+      "backup": {
+        "name": "backup-1",
+        "kind": "full",
+        "when": "1970-01-01T00:00:00Z",
+        "locked": false,
+        "size": 0
+      },
+      "hash": "abc",
+      "bytes": 5
+    }
+  }
 }
             "#
             .trim(),
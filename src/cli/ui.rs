@@ -26,6 +26,60 @@ fn pause() -> Result<(), Error> {
     Ok(())
 }
 
+/// Ask the user to choose one of several options by number,
+/// asking again until the input is valid.
+/// Returns the index of the chosen option.
+/// Like `confirm`, this writes to stderr so that it doesn't mix with the command's output.
+pub fn select(prompt: &str, options: &[String]) -> Result<usize, Error> {
+    use std::io::prelude::Write;
+
+    let stdin = std::io::stdin();
+    let mut stderr = std::io::stderr();
+
+    for (i, option) in options.iter().enumerate() {
+        eprintln!("  {}. {}", i + 1, option);
+    }
+
+    loop {
+        write!(stderr, "{} ", prompt).map_err(|_| Error::CliUnableToRequestConfirmation)?;
+        stderr.flush().map_err(|_| Error::CliUnableToRequestConfirmation)?;
+
+        let mut input = String::new();
+        let read = stdin
+            .read_line(&mut input)
+            .map_err(|_| Error::CliUnableToRequestConfirmation)?;
+        if read == 0 {
+            // Stdin was closed, so there's no point in asking again.
+            return Err(Error::CliUnableToRequestConfirmation);
+        }
+
+        match parse_selection(&input, options.len()) {
+            Some(index) => {
+                log::debug!("User selected: {}", index + 1);
+                return Ok(index);
+            }
+            None => eprintln!("{}", TRANSLATOR.cli_invalid_selection()),
+        }
+    }
+}
+
+/// Ask for a password without echoing it.
+/// Like `select`, this writes to stderr so that it doesn't mix with the command's output.
+pub fn password(prompt: &str) -> Option<String> {
+    match dialoguer::Password::new().with_prompt(prompt).interact() {
+        Ok(value) => Some(value).filter(|x| !x.is_empty()),
+        Err(err) => {
+            log::error!("Unable to request password: {:?}", err);
+            None
+        }
+    }
+}
+
+fn parse_selection(input: &str, total: usize) -> Option<usize> {
+    let choice: usize = input.trim().parse().ok()?;
+    (1..=total).contains(&choice).then(|| choice - 1)
+}
+
 pub fn alert_with_raw_error(gui: bool, force: bool, msg: &str, error: &str) -> Result<(), Error> {
     alert(
         gui,
@@ -119,3 +173,20 @@ pub fn confirm(gui: bool, force: bool, preview: bool, msg: &str) -> Result<bool,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn can_parse_selection() {
+        assert_eq!(Some(0), parse_selection("1", 3));
+        assert_eq!(Some(2), parse_selection(" 3\n", 3));
+        assert_eq!(None, parse_selection("0", 3));
+        assert_eq!(None, parse_selection("4", 3));
+        assert_eq!(None, parse_selection("-1", 3));
+        assert_eq!(None, parse_selection("", 3));
+        assert_eq!(None, parse_selection("abc", 3));
+    }
+}
@@ -1,4 +1,8 @@
-use std::io::{BufRead, BufReader};
+use std::{
+    io::{BufRead, BufReader, Read},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
 
 use crate::{
     lang::TRANSLATOR,
@@ -7,6 +11,9 @@ use crate::{
     scan::ScanChange,
 };
 
+/// How long to wait for the user to finish signing in through their browser.
+pub const AUTHORIZATION_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
 pub fn validate_cloud_config(config: &Config, cloud_path: &str) -> Result<Remote, Error> {
     if !config.apps.rclone.is_valid() {
         return Err(Error::RcloneUnavailable);
@@ -14,10 +21,37 @@ pub fn validate_cloud_config(config: &Config, cloud_path: &str) -> Result<Remote
     let Some(remote) = config.cloud.remote.clone() else {
         return Err(Error::CloudNotConfigured);
     };
+    if !remote.is_complete() {
+        return Err(Error::CloudNotConfigured);
+    }
+    if let Remote::Local { path, .. } = &remote {
+        let path = StrictPath::new(path.clone());
+        if !path.is_dir() {
+            return Err(Error::CloudLocalPathUnavailable { path });
+        }
+    }
     validate_cloud_path(cloud_path)?;
+    if let Some(limit) = &config.cloud.bandwidth_limit {
+        validate_bandwidth_limit(limit)?;
+    }
     Ok(remote)
 }
 
+/// Which games' folders to synchronize around a backup, where `None` means the whole backup folder.
+/// Before the backup, `changed` is `None`, and this covers every game that could be uploaded afterward,
+/// so that the conflict check and the upload agree.
+/// After the backup, this only covers the changed games if `cloud.scopeToChanged` is enabled.
+pub fn backup_sync_games(
+    config: &Config,
+    requested: Option<&[String]>,
+    changed: Option<&[String]>,
+) -> Option<Vec<String>> {
+    match changed {
+        Some(changed) if config.cloud.scope_to_changed => Some(changed.to_vec()),
+        _ => requested.map(|x| x.to_vec()),
+    }
+}
+
 pub fn validate_cloud_path(path: &str) -> Result<(), Error> {
     if path.is_empty() || path == "/" {
         Err(Error::CloudPathInvalid)
@@ -26,6 +60,25 @@ pub fn validate_cloud_path(path: &str) -> Result<(), Error> {
     }
 }
 
+/// Accepts the forms of `--bwlimit` that apply to the whole transfer:
+/// `off`, a single rate like `500k` or `1.5M`, or an upload:download pair like `1M:500k`.
+pub fn validate_bandwidth_limit(limit: &str) -> Result<(), Error> {
+    fn valid_rate(rate: &str) -> bool {
+        let number = match rate.char_indices().last() {
+            Some((i, unit)) if "bkmgtp".contains(unit.to_ascii_lowercase()) => &rate[..i],
+            _ => rate,
+        };
+        !number.is_empty() && number.chars().all(|c| c.is_ascii_digit() || c == '.') && number.parse::<f64>().is_ok()
+    }
+
+    let rates: Vec<_> = limit.split(':').collect();
+    if limit == "off" || (rates.len() <= 2 && rates.iter().all(|x| valid_rate(x))) {
+        Ok(())
+    } else {
+        Err(Error::CloudBandwidthLimitInvalid)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct CloudChange {
     pub path: String,
@@ -199,6 +252,18 @@ impl RcloneProcess {
         res
     }
 
+    /// Read everything that the process printed to stdout.
+    /// This should only be called after the process has exited.
+    pub fn stdout(&mut self) -> String {
+        let mut out = String::new();
+        if let Some(stdout) = self.child.stdout.as_mut() {
+            if let Err(e) = stdout.read_to_string(&mut out) {
+                log::error!("Unable to read stdout from Rclone: {e:?}");
+            }
+        }
+        out
+    }
+
     pub fn kill(&mut self) -> Result<(), std::io::Error> {
         let res = self.child.kill();
         if let Err(e) = &res {
@@ -218,7 +283,11 @@ pub enum RemoteChoice {
     GoogleDrive,
     OneDrive,
     Smb,
+    Sftp,
     WebDav,
+    S3,
+    B2,
+    Local,
 }
 
 impl RemoteChoice {
@@ -230,7 +299,11 @@ impl RemoteChoice {
         Self::OneDrive,
         Self::Ftp,
         Self::Smb,
+        Self::Sftp,
         Self::WebDav,
+        Self::S3,
+        Self::B2,
+        Self::Local,
         Self::Custom,
     ];
 }
@@ -246,7 +319,11 @@ impl ToString for RemoteChoice {
             Self::GoogleDrive => "Google Drive".to_string(),
             Self::OneDrive => "OneDrive".to_string(),
             Self::Smb => "SMB".to_string(),
+            Self::Sftp => "SFTP".to_string(),
             Self::WebDav => "WebDAV".to_string(),
+            Self::S3 => "S3".to_string(),
+            Self::B2 => "Backblaze B2".to_string(),
+            Self::Local => TRANSLATOR.local_folder_label(),
         }
     }
 }
@@ -278,6 +355,17 @@ pub enum Remote {
         password: String,
     },
     Smb {
+        id: String,
+        host: String,
+        port: i32,
+        /// Prepended to the cloud path, so that it can hold just the folder within the share.
+        #[serde(default)]
+        share: String,
+        username: String,
+        #[serde(skip, default)]
+        password: String,
+    },
+    Sftp {
         id: String,
         host: String,
         port: i32,
@@ -293,6 +381,29 @@ pub enum Remote {
         password: String,
         provider: WebDavProvider,
     },
+    S3 {
+        id: String,
+        bucket: String,
+        region: String,
+        /// Only needed for S3-compatible services other than AWS.
+        #[serde(default)]
+        endpoint: String,
+        access_key_id: String,
+        #[serde(skip, default)]
+        secret_access_key: String,
+    },
+    B2 {
+        id: String,
+        bucket: String,
+        account: String,
+        #[serde(skip, default)]
+        key: String,
+    },
+    /// A folder on this machine, such as a mounted network drive.
+    Local {
+        id: String,
+        path: String,
+    },
 }
 
 impl Remote {
@@ -305,7 +416,40 @@ impl Remote {
             Remote::OneDrive { id } => id,
             Remote::Ftp { id, .. } => id,
             Remote::Smb { id, .. } => id,
+            Remote::Sftp { id, .. } => id,
             Remote::WebDav { id, .. } => id,
+            Remote::S3 { id, .. } => id,
+            Remote::B2 { id, .. } => id,
+            Remote::Local { id, .. } => id,
+        }
+    }
+
+    /// For remotes that store files in a bucket, share, or fixed folder,
+    /// this is prepended to the cloud path.
+    pub fn bucket(&self) -> Option<&str> {
+        match self {
+            Remote::S3 { bucket, .. } => Some(bucket),
+            Remote::B2 { bucket, .. } => Some(bucket),
+            Remote::Smb { share, .. } if !share.is_empty() => Some(share),
+            Remote::Local { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Whether the fields needed to configure this remote are filled in.
+    /// Credentials are not checked because they are not kept in the config file.
+    pub fn is_complete(&self) -> bool {
+        match self {
+            Remote::S3 {
+                bucket,
+                region,
+                endpoint,
+                access_key_id,
+                ..
+            } => !bucket.is_empty() && !access_key_id.is_empty() && !(region.is_empty() && endpoint.is_empty()),
+            Remote::B2 { bucket, account, .. } => !bucket.is_empty() && !account.is_empty(),
+            Remote::Local { path, .. } => !path.is_empty(),
+            _ => true,
         }
     }
 
@@ -318,7 +462,11 @@ impl Remote {
             Self::GoogleDrive { .. } => "drive",
             Self::OneDrive { .. } => "onedrive",
             Self::Smb { .. } => "smb",
+            Self::Sftp { .. } => "sftp",
             Self::WebDav { .. } => "webdav",
+            Self::S3 { .. } => "s3",
+            Self::B2 { .. } => "b2",
+            Self::Local { .. } => "local",
         }
     }
 
@@ -345,12 +493,24 @@ impl Remote {
                 "access_scopes=Files.ReadWrite,offline_access".to_string(),
             ]),
             Self::Smb {
+                id: _,
+                host,
+                port,
+                share: _,
+                username,
+                password,
+            } => Some(vec![
+                format!("host={host}"),
+                format!("port={port}"),
+                format!("user={username}"),
+                format!("pass={password}"),
+            ]),
+            Self::Sftp {
                 id: _,
                 host,
                 port,
                 username,
                 password,
-                ..
             } => Some(vec![
                 format!("host={host}"),
                 format!("port={port}"),
@@ -369,6 +529,52 @@ impl Remote {
                 format!("pass={password}"),
                 format!("vendor={}", provider.slug()),
             ]),
+            Self::S3 {
+                id: _,
+                bucket: _,
+                region,
+                endpoint,
+                access_key_id,
+                secret_access_key,
+            } => {
+                let mut args = vec![
+                    format!("provider={}", if endpoint.is_empty() { "AWS" } else { "Other" }),
+                    "env_auth=false".to_string(),
+                    format!("access_key_id={access_key_id}"),
+                    format!("secret_access_key={secret_access_key}"),
+                ];
+                if !region.is_empty() {
+                    args.push(format!("region={region}"));
+                }
+                if !endpoint.is_empty() {
+                    args.push(format!("endpoint={endpoint}"));
+                }
+                Some(args)
+            }
+            Self::B2 {
+                id: _,
+                bucket: _,
+                account,
+                key,
+            } => Some(vec![format!("account={account}"), format!("key={key}")]),
+            Self::Local { .. } => None,
+        }
+    }
+
+    /// Whether Ludusavi guides the user through signing in with their browser for this remote.
+    pub fn uses_browser_authorization(&self) -> bool {
+        match self {
+            Self::Dropbox { .. } | Self::GoogleDrive { .. } => true,
+            Self::Custom { .. }
+            | Self::Box { .. }
+            | Self::Ftp { .. }
+            | Self::OneDrive { .. }
+            | Self::Smb { .. }
+            | Self::Sftp { .. }
+            | Self::WebDav { .. }
+            | Self::S3 { .. }
+            | Self::B2 { .. }
+            | Self::Local { .. } => false,
         }
     }
 
@@ -381,7 +587,11 @@ impl Remote {
             | Self::GoogleDrive { .. }
             | Self::OneDrive { .. }
             | Self::Smb { .. }
-            | Self::WebDav { .. } => true,
+            | Self::Sftp { .. }
+            | Self::WebDav { .. }
+            | Self::S3 { .. }
+            | Self::B2 { .. }
+            | Self::Local { .. } => true,
         }
     }
 
@@ -391,9 +601,32 @@ impl Remote {
                 host, port, username, ..
             } => Some(format!("{}@{}:{}", username, host, port)),
             Remote::Smb {
+                host,
+                port,
+                share,
+                username,
+                ..
+            } => Some(if share.is_empty() {
+                format!("{}@{}:{}", username, host, port)
+            } else {
+                format!("{}@{}:{}/{}", username, host, port, share)
+            }),
+            Remote::Sftp {
                 host, port, username, ..
             } => Some(format!("{}@{}:{}", username, host, port)),
             Remote::WebDav { url, provider, .. } => Some(format!("{} - {}", provider.to_string(), url)),
+            Remote::S3 {
+                bucket,
+                region,
+                endpoint,
+                ..
+            } => Some(format!(
+                "{} - {}",
+                bucket,
+                if endpoint.is_empty() { region } else { endpoint }
+            )),
+            Remote::B2 { bucket, account, .. } => Some(format!("{} - {}", bucket, account)),
+            Remote::Local { path, .. } => Some(path.clone()),
             _ => None,
         }
     }
@@ -414,7 +647,11 @@ impl From<Option<&Remote>> for RemoteChoice {
                 Remote::GoogleDrive { .. } => RemoteChoice::GoogleDrive,
                 Remote::OneDrive { .. } => RemoteChoice::OneDrive,
                 Remote::Smb { .. } => RemoteChoice::Smb,
+                Remote::Sftp { .. } => RemoteChoice::Sftp,
                 Remote::WebDav { .. } => RemoteChoice::WebDav,
+                Remote::S3 { .. } => RemoteChoice::S3,
+                Remote::B2 { .. } => RemoteChoice::B2,
+                Remote::Local { .. } => RemoteChoice::Local,
             }
         } else {
             RemoteChoice::None
@@ -454,6 +691,14 @@ impl TryFrom<RemoteChoice> for Remote {
                 id: Remote::generate_id(),
                 host: String::new(),
                 port: 445,
+                share: String::new(),
+                username: String::new(),
+                password: String::new(),
+            }),
+            RemoteChoice::Sftp => Ok(Remote::Sftp {
+                id: Remote::generate_id(),
+                host: String::new(),
+                port: 22,
                 username: String::new(),
                 password: String::new(),
             }),
@@ -464,6 +709,24 @@ impl TryFrom<RemoteChoice> for Remote {
                 password: String::new(),
                 provider: WebDavProvider::Other,
             }),
+            RemoteChoice::S3 => Ok(Remote::S3 {
+                id: Remote::generate_id(),
+                bucket: String::new(),
+                region: String::new(),
+                endpoint: String::new(),
+                access_key_id: String::new(),
+                secret_access_key: String::new(),
+            }),
+            RemoteChoice::B2 => Ok(Remote::B2 {
+                id: Remote::generate_id(),
+                bucket: String::new(),
+                account: String::new(),
+                key: String::new(),
+            }),
+            RemoteChoice::Local => Ok(Remote::Local {
+                id: Remote::generate_id(),
+                path: String::new(),
+            }),
         }
     }
 }
@@ -540,20 +803,97 @@ impl std::str::FromStr for WebDavProvider {
     }
 }
 
+/// Extract the token from the output of `rclone authorize`,
+/// which is printed between two markers.
+fn parse_authorization_token(stdout: &str) -> Option<String> {
+    let (_, rest) = stdout.split_once("--->")?;
+    let (token, _) = rest.split_once("<---End paste")?;
+    let token = token.trim();
+    (!token.is_empty()).then(|| token.to_string())
+}
+
+/// A browser sign-in flow that is waiting for the user.
+pub struct Authorization {
+    process: RcloneProcess,
+    started: Instant,
+}
+
+impl Authorization {
+    /// Check on the sign-in without blocking.
+    /// This returns `None` while the user is still signing in,
+    /// and stops the process if `cancel` is set or if the user doesn't finish within `AUTHORIZATION_TIMEOUT`.
+    pub fn poll(&mut self, cancel: &AtomicBool) -> Option<Result<String, CommandError>> {
+        let program = self.process.program.clone();
+        let args = self.process.args.clone();
+
+        if let Some(outcome) = self.process.succeeded() {
+            return Some(outcome.and_then(|_| {
+                parse_authorization_token(&self.process.stdout()).ok_or(CommandError::Exited {
+                    program,
+                    args,
+                    code: 0,
+                    stdout: None,
+                    stderr: Some("No token found in output".to_string()),
+                })
+            }));
+        }
+
+        if cancel.load(Ordering::Relaxed) {
+            log::info!("Rclone authorization cancelled");
+            let _ = self.process.kill();
+            return Some(Err(CommandError::Cancelled { program, args }));
+        }
+
+        if self.started.elapsed() > AUTHORIZATION_TIMEOUT {
+            log::warn!("Rclone authorization timed out");
+            let _ = self.process.kill();
+            return Some(Err(CommandError::TimedOut { program, args }));
+        }
+
+        None
+    }
+}
+
 pub struct Rclone {
     app: App,
     remote: Remote,
+    transfers: Option<usize>,
+    bandwidth_limit: Option<String>,
 }
 
 impl Rclone {
     pub fn new(app: App, remote: Remote) -> Self {
-        Self { app, remote }
+        Self {
+            app,
+            remote,
+            transfers: None,
+            bandwidth_limit: None,
+        }
+    }
+
+    pub fn with_transfers(mut self, transfers: Option<usize>) -> Self {
+        self.transfers = transfers;
+        self
+    }
+
+    pub fn with_bandwidth_limit(mut self, bandwidth_limit: Option<String>) -> Self {
+        self.bandwidth_limit = bandwidth_limit;
+        self
     }
 
     fn path(&self, path: &str) -> String {
         // Rclone doesn't allow backslashes in non-local paths:
         // https://rclone.org/docs/#syntax-of-remote-paths
-        format!("{}:{}", self.remote.id(), path.replace('\\', "/"))
+        let path = path.replace('\\', "/");
+        match self.remote.bucket() {
+            Some(bucket) => format!(
+                "{}:{}/{}",
+                self.remote.id(),
+                bucket.trim_end_matches(['/', '\\']),
+                path.trim_start_matches('/')
+            ),
+            None => format!("{}:{}", self.remote.id(), path),
+        }
     }
 
     fn args(&self, args: &[String]) -> Vec<String> {
@@ -580,12 +920,31 @@ impl Rclone {
         Ok(out.stdout)
     }
 
-    pub fn configure_remote(&self) -> Result<(), CommandError> {
+    /// Start Rclone's browser sign-in flow.
+    /// Use `Authorization::poll` to wait for the resulting token.
+    pub fn authorize(&self) -> Result<Authorization, CommandError> {
+        let program = self.app.path.raw();
+        let args = self.args(&["authorize".to_string(), self.remote.slug().to_string()]);
+        let process = RcloneProcess::launch(program, args)?;
+
+        Ok(Authorization {
+            process,
+            started: Instant::now(),
+        })
+    }
+
+    /// For remotes that use browser authorization,
+    /// pass the token from `authorize`.
+    pub fn configure_remote(&self, token: Option<String>) -> Result<(), CommandError> {
         if !self.remote.needs_configuration() {
             return Ok(());
         }
 
-        let mut privacy = Privacy::Public;
+        let mut privacy = if token.is_some() {
+            Privacy::Private
+        } else {
+            Privacy::Public
+        };
 
         let mut remote = self.remote.clone();
         match &mut remote {
@@ -593,7 +952,8 @@ impl Rclone {
             | Remote::Box { .. }
             | Remote::Dropbox { .. }
             | Remote::GoogleDrive { .. }
-            | Remote::OneDrive { .. } => {}
+            | Remote::OneDrive { .. }
+            | Remote::Local { .. } => {}
             Remote::Ftp { password, .. } => {
                 privacy = Privacy::Private;
                 *password = self.obscure(password)?;
@@ -602,10 +962,19 @@ impl Rclone {
                 privacy = Privacy::Private;
                 *password = self.obscure(password)?;
             }
+            Remote::Sftp { password, .. } => {
+                privacy = Privacy::Private;
+                *password = self.obscure(password)?;
+            }
             Remote::WebDav { password, .. } => {
                 privacy = Privacy::Private;
                 *password = self.obscure(password)?;
             }
+            // These backends read their keys as-is, so the keys can't be obscured,
+            // but they still only end up in Rclone's config and not ours.
+            Remote::S3 { .. } | Remote::B2 { .. } => {
+                privacy = Privacy::Private;
+            }
         }
 
         let mut args = vec![
@@ -619,6 +988,12 @@ impl Rclone {
             args.extend(config_args);
         }
 
+        if let Some(token) = token {
+            // With the token already in hand, Rclone doesn't need to open the browser again.
+            args.push(format!("token={token}"));
+            args.push("--non-interactive".to_string());
+        }
+
         self.run(&args, &[0], privacy)?;
         Ok(())
     }
@@ -641,6 +1016,7 @@ impl Rclone {
         direction: SyncDirection,
         finality: Finality,
         game_dirs: &[String],
+        excluded_paths: &[String],
     ) -> Result<RcloneProcess, CommandError> {
         if direction == SyncDirection::Upload && !local.exists() {
             // Rclone will fail with exit code 3 if the local folder does not exist.
@@ -658,11 +1034,16 @@ impl Rclone {
             args.push("--dry-run".to_string());
         }
 
-        for game_dir in game_dirs {
-            // Inclusion rules are file-based, so we have to add `**`.
-            args.push(format!("--include=/{game_dir}/**"));
+        if let Some(transfers) = self.transfers {
+            args.push(format!("--transfers={transfers}"));
         }
 
+        if let Some(limit) = &self.bandwidth_limit {
+            args.push(format!("--bwlimit={limit}"));
+        }
+
+        args.extend(sync_filters(game_dirs, excluded_paths));
+
         match direction {
             SyncDirection::Upload => {
                 args.push(local.render());
@@ -678,6 +1059,28 @@ impl Rclone {
     }
 }
 
+/// Rclone applies the first matching filter rule,
+/// so exclusions come first to take precedence over any inclusions.
+/// Excluded files are also left alone on the destination rather than deleted.
+/// Excluded paths may be folders or individual files (such as shared objects).
+fn sync_filters(game_dirs: &[String], excluded_paths: &[String]) -> Vec<String> {
+    let mut filters = vec![];
+
+    // Filter rules are file-based, so we have to add `**` to cover folders.
+    for excluded in excluded_paths {
+        filters.push(format!("--filter=- /{excluded}"));
+        filters.push(format!("--filter=- /{excluded}/**"));
+    }
+    for game_dir in game_dirs {
+        filters.push(format!("--filter=+ /{game_dir}/**"));
+    }
+    if !game_dirs.is_empty() {
+        filters.push("--filter=- **".to_string());
+    }
+
+    filters
+}
+
 pub mod rclone_monitor {
     use iced::{
         futures::{channel::mpsc, stream, StreamExt},
@@ -812,3 +1215,122 @@ pub mod rclone_monitor {
         Subscription::run(unfold)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_validate_bandwidth_limit() {
+        for valid in ["off", "500", "500k", "500K", "1M", "1.5M", "10G", "1M:500k"] {
+            assert!(validate_bandwidth_limit(valid).is_ok(), "{valid}");
+        }
+        for invalid in [
+            "",
+            "M",
+            "fast",
+            "1X",
+            "-1M",
+            "1M:",
+            "1M:500k:1k",
+            "08:00,512k",
+            "1.5.0M",
+        ] {
+            assert!(validate_bandwidth_limit(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn can_choose_games_to_sync_around_backup() {
+        let requested = vec!["a".to_string(), "b".to_string()];
+        let changed = vec!["a".to_string()];
+
+        let mut config = Config::default();
+        config.cloud.scope_to_changed = true;
+        assert_eq!(None, backup_sync_games(&config, None, None));
+        assert_eq!(
+            Some(requested.clone()),
+            backup_sync_games(&config, Some(&requested), None)
+        );
+        assert_eq!(Some(changed.clone()), backup_sync_games(&config, None, Some(&changed)));
+        assert_eq!(
+            Some(changed.clone()),
+            backup_sync_games(&config, Some(&requested), Some(&changed))
+        );
+
+        config.cloud.scope_to_changed = false;
+        assert_eq!(None, backup_sync_games(&config, None, Some(&changed)));
+        assert_eq!(
+            Some(requested.clone()),
+            backup_sync_games(&config, Some(&requested), Some(&changed))
+        );
+    }
+
+    #[test]
+    fn can_build_sync_filters() {
+        assert_eq!(Vec::<String>::new(), sync_filters(&[], &[]));
+        assert_eq!(
+            vec!["--filter=+ /a/**".to_string(), "--filter=- **".to_string()],
+            sync_filters(&["a".to_string()], &[]),
+        );
+        assert_eq!(
+            vec!["--filter=- /b".to_string(), "--filter=- /b/**".to_string()],
+            sync_filters(&[], &["b".to_string()]),
+        );
+        assert_eq!(
+            vec![
+                "--filter=- /b".to_string(),
+                "--filter=- /b/**".to_string(),
+                "--filter=+ /a/**".to_string(),
+                "--filter=+ /b/**".to_string(),
+                "--filter=- **".to_string(),
+            ],
+            sync_filters(&["a".to_string(), "b".to_string()], &["b".to_string()]),
+        );
+    }
+
+    #[test]
+    fn can_parse_authorization_token() {
+        let stdout = r#"
+Paste the following into your remote machine --->
+{"access_token":"abc","token_type":"bearer","expiry":"2024-01-01T00:00:00Z"}
+<---End paste
+"#;
+        assert_eq!(
+            Some(r#"{"access_token":"abc","token_type":"bearer","expiry":"2024-01-01T00:00:00Z"}"#.to_string()),
+            parse_authorization_token(stdout)
+        );
+        assert_eq!(None, parse_authorization_token(""));
+        assert_eq!(None, parse_authorization_token("--->\n<---End paste"));
+    }
+
+    #[test]
+    fn can_prepend_fixed_folder_to_cloud_path() {
+        let rclone = |remote| Rclone::new(App::default(), remote);
+
+        let local = Remote::Local {
+            id: "local".to_string(),
+            path: "/mnt/nas/".to_string(),
+        };
+        assert_eq!("local:/mnt/nas/ludusavi-backup", rclone(local).path("ludusavi-backup"));
+
+        let smb = Remote::Smb {
+            id: "smb".to_string(),
+            host: "nas".to_string(),
+            port: 445,
+            share: "games".to_string(),
+            username: "user".to_string(),
+            password: String::new(),
+        };
+        assert_eq!("smb:games/ludusavi-backup", rclone(smb).path("/ludusavi-backup"));
+
+        let sftp = Remote::Sftp {
+            id: "sftp".to_string(),
+            host: "nas".to_string(),
+            port: 22,
+            username: "user".to_string(),
+            password: String::new(),
+        };
+        assert_eq!("sftp:ludusavi-backup", rclone(sftp).path("ludusavi-backup"));
+    }
+}
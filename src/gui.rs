@@ -14,6 +14,7 @@ mod screen;
 mod search;
 mod shortcuts;
 mod style;
+mod tray;
 mod undoable;
 mod widget;
 
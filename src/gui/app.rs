@@ -6,24 +6,25 @@ use std::{
 use iced::{keyboard, widget::scrollable, Alignment, Length, Subscription, Task};
 
 use crate::{
-    cloud::{rclone_monitor, Rclone, Remote},
+    cloud::{rclone_monitor, Rclone, Remote, RemoteChoice},
     gui::{
         button,
         common::{
             BackupPhase, BrowseFileSubject, BrowseSubject, Flags, GameAction, GameSelection, Message, Operation,
             RestorePhase, Screen, ScrollSubject, UndoSubject, ValidatePhase,
         },
-        modal::{CloudModalState, Modal, ModalField, ModalInputKind},
+        modal::{CloudModalState, Modal, ModalField, ModalInputKind, PrunedGame},
         notification::Notification,
         screen,
         shortcuts::{RootHistory, Shortcut, TextHistories, TextHistory},
         style,
+        tray::{self, Tray},
         widget::{id, Column, Container, Element, IcedParentExt, Progress, Row, Stack},
     },
     lang::TRANSLATOR,
     prelude::{
-        app_dir, get_threads_from_env, initialize_rayon, EditAction, Error, Finality, RedirectEditActionField,
-        StrictPath, SyncDirection,
+        app_dir, get_threads_from_env, initialize_rayon, CommandError, EditAction, Error, Finality,
+        RedirectEditActionField, StrictPath, SyncDirection,
     },
     resource::{
         cache::{self, Cache},
@@ -33,7 +34,7 @@ use crate::{
     },
     scan::{
         game_filter, layout::BackupLayout, prepare_backup_target, registry::RegistryItem, scan_game_for_backup,
-        BackupId, Launchers, ScanKind, SteamShortcuts, TitleFinder,
+        BackupId, BackupScanOptions, Launchers, RunningProcesses, ScanKind, SteamShortcuts, TitleFinder,
     },
 };
 
@@ -70,6 +71,32 @@ pub enum SaveKind {
     Backup(String),
 }
 
+/// Wait this long after the latest change before saving,
+/// so that a burst of edits only results in one write.
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(2);
+/// But don't hold off longer than this after the first unsaved change,
+/// so that continuous editing still gets saved periodically.
+const SAVE_MAX_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PendingSave {
+    first: Instant,
+    latest: Instant,
+}
+
+impl PendingSave {
+    fn new(now: Instant) -> Self {
+        Self {
+            first: now,
+            latest: now,
+        }
+    }
+
+    fn is_due(&self, now: Instant) -> bool {
+        now.duration_since(self.latest) >= SAVE_DEBOUNCE || now.duration_since(self.first) >= SAVE_MAX_DELAY
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct LoadedManifest {
     pub primary: Manifest,
@@ -89,6 +116,7 @@ pub struct App {
     restore_screen: screen::Restore,
     custom_games_screen: screen::CustomGames,
     operation_should_cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    cloud_authorization_should_cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
     operation_steps: Vec<Task<Message>>,
     operation_steps_active: usize,
     progress: Progress,
@@ -101,15 +129,17 @@ pub struct App {
     text_histories: TextHistories,
     rclone_monitor_sender: Option<iced::futures::channel::mpsc::Sender<rclone_monitor::Input>>,
     exiting: bool,
-    pending_save: HashMap<SaveKind, Instant>,
+    pending_save: HashMap<SaveKind, PendingSave>,
     modifiers: keyboard::Modifiers,
     jump_to_game_after_scan: Option<String>,
+    tray: Option<Tray>,
+    notify_on_backup_finished: bool,
 }
 
 impl App {
     fn go_idle(&mut self) {
         if self.exiting {
-            self.save();
+            self.save(true);
             std::process::exit(0);
         }
 
@@ -159,12 +189,11 @@ impl App {
         self.show_modal(Modal::Error { variant: error })
     }
 
-    fn save(&mut self) {
-        let threshold = Duration::from_secs(1);
+    fn save(&mut self, force: bool) {
         let now = Instant::now();
 
-        self.pending_save.retain(|item, then| {
-            if (now - *then) < threshold {
+        self.pending_save.retain(|item, pending| {
+            if !force && !pending.is_due(now) {
                 return true;
             }
 
@@ -178,17 +207,24 @@ impl App {
         });
     }
 
+    fn queue_save(&mut self, kind: SaveKind) {
+        let now = Instant::now();
+        self.pending_save
+            .entry(kind)
+            .and_modify(|pending| pending.latest = now)
+            .or_insert_with(|| PendingSave::new(now));
+    }
+
     fn save_config(&mut self) {
-        self.pending_save.insert(SaveKind::Config, Instant::now());
+        self.queue_save(SaveKind::Config);
     }
 
     fn save_cache(&mut self) {
-        self.pending_save.insert(SaveKind::Cache, Instant::now());
+        self.queue_save(SaveKind::Cache);
     }
 
     fn save_backup(&mut self, game: &str) {
-        self.pending_save
-            .insert(SaveKind::Backup(game.to_string()), Instant::now());
+        self.queue_save(SaveKind::Backup(game.to_string()));
     }
 
     fn invalidate_path_caches(&self) {
@@ -241,17 +277,23 @@ impl App {
     ) -> Result<(), Error> {
         let remote = crate::cloud::validate_cloud_config(&self.config, &self.config.cloud.path)?;
 
+        let layout = BackupLayout::new(local.clone())
+            .with_folder_template(self.config.backup.folder_template.clone())
+            .with_filename_policy(self.config.filename_policy());
+        if self.config.cloud.timestamped_backups && direction == SyncDirection::Upload && finality == Finality::Final {
+            let subjects: Vec<_> = games.iter().flat_map(|x| x.iter().cloned()).collect();
+            layout.timestamp_solo_backups(&subjects);
+        }
         let games = match games {
-            Some(games) => {
-                let layout = BackupLayout::new(local.clone());
-                let games: Vec<_> = games.iter().filter_map(|x| layout.game_folder(x).leaf()).collect();
-                games
-            }
+            Some(games) => layout.cloud_folders(games.iter()),
             None => vec![],
         };
+        let excluded = layout.cloud_excluded_paths(&self.config.cloud.excluded_games);
 
-        let rclone = Rclone::new(self.config.apps.rclone.clone(), remote);
-        match rclone.sync(local, &self.config.cloud.path, direction, finality, &games) {
+        let rclone = Rclone::new(self.config.apps.rclone.clone(), remote)
+            .with_transfers(self.config.cloud.transfers)
+            .with_bandwidth_limit(self.config.cloud.bandwidth_limit.clone());
+        match rclone.sync(local, &self.config.cloud.path, direction, finality, &games, &excluded) {
             Ok(process) => {
                 if let Some(sender) = self.rclone_monitor_sender.as_mut() {
                     if standalone {
@@ -312,6 +354,14 @@ impl App {
                 self.operation =
                     Operation::new_backup(if preview { Finality::Preview } else { Finality::Final }, games);
                 self.operation.set_force_new_full_backups(repair);
+                self.operation.set_quick_scan(
+                    !repair
+                        && self
+                            .config
+                            .backup
+                            .quick_scan
+                            .is_active(self.cache.backup.last_full_scan, chrono::Utc::now()),
+                );
 
                 if !preview {
                     if let Err(e) = prepare_backup_target(&self.config.backup.path) {
@@ -340,9 +390,11 @@ impl App {
                 }
 
                 let local = self.config.backup.path.clone();
-                let games = self.operation.games();
+                let requested: Option<Vec<_>> = self.operation.games().map(|x| x.iter().cloned().collect());
+                let sync_games = crate::cloud::backup_sync_games(&self.config, requested.as_deref(), None)
+                    .map(|x| GameSelection::group(x.into_iter().collect()));
 
-                match self.start_sync_cloud(&local, SyncDirection::Upload, Finality::Preview, games.cloned(), false) {
+                match self.start_sync_cloud(&local, SyncDirection::Upload, Finality::Preview, sync_games, false) {
                     Ok(_) => {
                         // deferring to `transition_from_cloud_step`
                         Task::none()
@@ -374,6 +426,7 @@ impl App {
                 let mut manifest = self.manifest.primary.clone();
                 let config = self.config.clone();
                 let previewed_games = self.backup_screen.previewed_games.clone();
+                let cancel_flag = self.operation_should_cancel.clone();
 
                 Task::perform(
                     async move {
@@ -395,20 +448,38 @@ impl App {
                         let subjects: Vec<_> = subjects.into_iter().collect();
 
                         let roots = config.expanded_roots();
-                        let layout = BackupLayout::new(config.backup.path.clone());
+                        let layout = BackupLayout::new(config.backup.path.clone())
+                            .with_folder_template(config.backup.folder_template.clone())
+                            .with_filename_policy(config.filename_policy())
+                            .with_hooks(config.backup.hooks())
+                            .with_space_check(config.backup.space_check)
+                            .with_mirrors(config.backup.mirrors.clone())
+                            .with_zip_password(config.zip_password())
+                            .with_cancel_flag(cancel_flag);
                         let title_finder = TitleFinder::new(&config, &manifest, layout.restorable_game_set());
                         let steam = SteamShortcuts::scan();
+                        let processes = RunningProcesses::scan();
                         let launchers = Launchers::scan(&roots, &manifest, &subjects, &title_finder, None);
+                        let history: HashMap<_, _> = if preview {
+                            HashMap::new()
+                        } else {
+                            subjects
+                                .iter()
+                                .filter_map(|name| Some((name.clone(), layout.backup_history(name)?)))
+                                .collect()
+                        };
 
-                        (subjects, manifest, layout, steam, launchers)
+                        (subjects, manifest, layout, steam, processes, launchers, history)
                     },
-                    move |(subjects, manifest, layout, steam, heroic)| {
+                    move |(subjects, manifest, layout, steam, processes, heroic, history)| {
                         Message::Backup(BackupPhase::RegisterCommands {
                             subjects,
                             manifest,
                             layout: Box::new(layout),
                             steam,
+                            processes,
                             launchers: heroic,
+                            history,
                         })
                     },
                 )
@@ -418,7 +489,9 @@ impl App {
                 manifest,
                 layout,
                 steam,
+                processes,
                 launchers,
+                history,
             } => {
                 log::info!("beginning backup with {} steps", subjects.len());
                 let preview = self.operation.preview();
@@ -449,6 +522,7 @@ impl App {
                 }
 
                 self.progress.set_max(subjects.len() as f32);
+                self.progress.set_history(&subjects, &history);
                 self.register_notify_on_single_game_scanned();
 
                 let config = std::sync::Arc::new(self.config.clone());
@@ -457,11 +531,11 @@ impl App {
                 let launchers = std::sync::Arc::new(launchers);
                 let filter = std::sync::Arc::new(self.config.backup.filter.clone());
                 let steam_shortcuts = std::sync::Arc::new(steam);
+                let processes = std::sync::Arc::new(processes);
                 let games_specified = self.operation.games_specified();
-                let retention = config
-                    .backup
-                    .retention
-                    .with_force_new_full(self.operation.should_force_new_full_backups());
+                let force_new_full = self.operation.should_force_new_full_backups();
+                let quick_scan = self.operation.quick_scan();
+                let force_timestamped_name = config.cloud.synchronize && config.cloud.timestamped_backups;
 
                 for key in subjects {
                     let game = manifest.0[&key].clone();
@@ -471,6 +545,7 @@ impl App {
                     let layout = layout.clone();
                     let filter = filter.clone();
                     let steam_shortcuts = steam_shortcuts.clone();
+                    let processes = processes.clone();
                     let cancel_flag = self.operation_should_cancel.clone();
                     self.operation_steps.push(Task::perform(
                         async move {
@@ -509,19 +584,34 @@ impl App {
                                 &config.redirects,
                                 config.restore.reverse_redirects,
                                 &steam_shortcuts,
-                                config.backup.only_constructive,
+                                &BackupScanOptions {
+                                    processes: Some(&processes),
+                                    quick_scan,
+                                    ..BackupScanOptions::from_config(&config, &key)
+                                },
                             );
                             if !config.is_game_enabled_for_backup(&key) && !single {
                                 return (Some(scan_info), None);
                             }
+                            if config.backup.skip_running_games && scan_info.game_running {
+                                log::info!("[{key}] skipping backup because the game is running");
+                                return (Some(scan_info), None);
+                            }
 
                             let backup_info = if !preview {
-                                layout.game_layout(&key).back_up(
+                                let retention = config
+                                    .backup
+                                    .retention_for(&key)
+                                    .with_force_new_full(force_new_full)
+                                    .with_force_timestamped_name(force_timestamped_name);
+                                layout.game_layout_for(&key, Some(&game)).back_up(
                                     &scan_info,
                                     &chrono::Utc::now(),
-                                    &config.backup.format,
+                                    &config.backup.format_for(&key),
                                     retention,
                                     config.backup.only_constructive,
+                                    config.backup.checksum,
+                                    config.backup.comment_template.as_deref(),
                                 )
                             } else {
                                 None
@@ -538,7 +628,10 @@ impl App {
                 Task::batch(self.operation_steps.drain(..self.operation_steps_active))
             }
             BackupPhase::GameScanned { scan_info, backup_info } => {
-                self.progress.step();
+                match scan_info.as_ref() {
+                    Some(scan_info) => self.progress.step_game(&scan_info.game_name),
+                    None => self.progress.step(),
+                }
                 let full = self.operation.full();
 
                 if let Some(mut scan_info) = scan_info {
@@ -609,33 +702,33 @@ impl App {
                 let local = self.config.backup.path.clone();
                 let games = self.operation.games();
 
-                let changed_games = GameSelection::group(
-                    self.backup_screen
-                        .log
-                        .entries
-                        .iter()
-                        .filter(|x| {
-                            let relevant = games
-                                .as_ref()
-                                .map(|games| games.contains(&x.scan_info.game_name))
-                                .unwrap_or(true);
-                            relevant && x.scan_info.needs_cloud_sync()
-                        })
-                        .map(|x| x.scan_info.game_name.clone())
-                        .collect(),
-                );
+                let changed_games: Vec<_> = self
+                    .backup_screen
+                    .log
+                    .entries
+                    .iter()
+                    .filter(|x| {
+                        let relevant = games
+                            .as_ref()
+                            .map(|games| games.contains(&x.scan_info.game_name))
+                            .unwrap_or(true);
+                        relevant
+                            && x.scan_info.needs_cloud_sync()
+                            && self.config.is_game_enabled_for_cloud(&x.scan_info.game_name)
+                    })
+                    .map(|x| x.scan_info.game_name.clone())
+                    .collect();
 
                 if changed_games.is_empty() {
                     return self.handle_backup(BackupPhase::Done);
                 }
 
-                match self.start_sync_cloud(
-                    &local,
-                    SyncDirection::Upload,
-                    Finality::Final,
-                    Some(changed_games),
-                    false,
-                ) {
+                let requested: Option<Vec<_>> = games.map(|x| x.iter().cloned().collect());
+                let sync_games =
+                    crate::cloud::backup_sync_games(&self.config, requested.as_deref(), Some(&changed_games[..]))
+                        .map(|x| GameSelection::group(x.into_iter().collect()));
+
+                match self.start_sync_cloud(&local, SyncDirection::Upload, Finality::Final, sync_games, false) {
                     Ok(_) => {
                         // deferring to `transition_from_cloud_step`
                         Task::none()
@@ -658,6 +751,14 @@ impl App {
                     self.cache.backup.recent_games.clear();
                 }
 
+                if !preview
+                    && full
+                    && !self.operation.quick_scan()
+                    && !self.operation_should_cancel.load(std::sync::atomic::Ordering::Relaxed)
+                {
+                    self.cache.backup.last_full_scan = Some(chrono::Utc::now());
+                }
+
                 for entry in &self.backup_screen.log.entries {
                     self.cache.backup.recent_games.insert(entry.scan_info.game_name.clone());
                     if let Some(backup_info) = &entry.backup_info {
@@ -671,12 +772,22 @@ impl App {
                     self.backup_screen.previewed_games.clear();
                 }
 
+                if !preview {
+                    BackupLayout::new(self.config.backup.path.clone())
+                        .with_mirrors(self.config.backup.mirrors.clone())
+                        .collect_garbage(false);
+                }
+
                 self.save_cache();
 
                 if failed {
                     self.operation.push_error(Error::SomeEntriesFailed);
                 }
 
+                if std::mem::take(&mut self.notify_on_backup_finished) {
+                    self.timed_notification = Some(Notification::new(TRANSLATOR.tray_backup_finished()).expires(5));
+                }
+
                 let errors = self.operation.errors().cloned();
                 self.go_idle();
 
@@ -719,8 +830,27 @@ impl App {
         const SCAN_KIND: ScanKind = ScanKind::Restore;
 
         match phase {
-            RestorePhase::Confirm { games } => self.show_modal(Modal::ConfirmRestore { games }),
-            RestorePhase::Start { preview, mut games } => {
+            RestorePhase::Confirm { games, only } => {
+                let now = chrono::Utc::now();
+                let recent = self
+                    .cache
+                    .restore
+                    .last_restored
+                    .keys()
+                    .filter(|x| match &games {
+                        Some(games) => games.contains(x),
+                        None => self.config.is_game_enabled_for_restore(x),
+                    })
+                    .filter(|x| self.cache.restore.recent_warning(x, &now).is_some())
+                    .map(|x| self.config.display_name(x).to_string())
+                    .collect();
+                self.show_modal(Modal::ConfirmRestore { games, only, recent })
+            }
+            RestorePhase::Start {
+                preview,
+                mut games,
+                only,
+            } => {
                 if !self.operation.idle() {
                     return Task::none();
                 }
@@ -750,7 +880,7 @@ impl App {
                 }
 
                 self.operation =
-                    Operation::new_restore(if preview { Finality::Preview } else { Finality::Final }, games);
+                    Operation::new_restore(if preview { Finality::Preview } else { Finality::Final }, games, only);
 
                 self.invalidate_path_caches();
                 self.timed_notification = None;
@@ -785,12 +915,26 @@ impl App {
             }
             RestorePhase::Load => {
                 let restore_path = self.config.restore.path.clone();
+                let redirect_fallback = self.config.restore.redirect_fallback;
+                let wine_prefix = self.config.restore.wine_prefix.clone();
+                let conflict_policy = self.config.restore.conflict_policy;
+                let exclude_empty_dirs = self.config.backup.filter.exclude_empty_dirs;
+                let registry_filter = self.config.backup.filter.clone();
+                let mirrors = self.config.backup.mirrors_for(&restore_path);
+                let zip_password = self.config.zip_password();
 
                 self.progress.start();
 
                 Task::perform(
                     async move {
-                        let layout = BackupLayout::new(restore_path);
+                        let layout = BackupLayout::new(restore_path)
+                            .with_redirect_fallback(redirect_fallback)
+                            .with_wine_prefix(wine_prefix)
+                            .with_conflict_policy(conflict_policy)
+                            .with_empty_dir_exclusion(exclude_empty_dirs)
+                            .with_registry_filter(registry_filter)
+                            .with_mirrors(mirrors)
+                            .with_zip_password(zip_password);
                         let restorables = layout.restorable_games();
                         (layout, restorables)
                     },
@@ -843,10 +987,12 @@ impl App {
 
                 let config = std::sync::Arc::new(self.config.clone());
                 let layout = std::sync::Arc::new(layout);
+                let only = self.operation.restore_only().cloned();
 
                 for name in restorables {
                     let config = config.clone();
                     let layout = layout.clone();
+                    let only = only.clone();
                     let cancel_flag = self.operation_should_cancel.clone();
                     let backup_id = self.backups_to_restore.get(&name).cloned().unwrap_or(BackupId::Latest);
                     self.operation_steps.push(Task::perform(
@@ -857,7 +1003,7 @@ impl App {
                                 return (None, None, layout);
                             }
 
-                            let scan_info = layout.scan_for_restoration(
+                            let mut scan_info = layout.scan_for_restoration(
                                 &name,
                                 &backup_id,
                                 &config.redirects,
@@ -869,8 +1015,13 @@ impl App {
                                 return (Some(scan_info), None, layout);
                             }
 
+                            let filter = only.as_ref().map(|path| scan_info.restore_files_within(path));
+                            if let Some(filter) = &filter {
+                                scan_info.restrict_restore(filter);
+                            }
+
                             let backup_info = if scan_info.backup.is_some() && !preview {
-                                Some(layout.restore(&scan_info, &config.restore.toggled_registry))
+                                Some(layout.restore(&scan_info, &config.restore.toggled_registry, filter.as_deref()))
                             } else {
                                 None
                             };
@@ -906,6 +1057,15 @@ impl App {
                     );
                     if scan_info.can_report_game() {
                         if let Some(backup_info) = backup_info.as_ref() {
+                            // Record the restore even if some items failed,
+                            // since the rest of the live data was still replaced.
+                            // This is saved to the cache once the whole restore is done.
+                            if scan_info.restored_anything(backup_info) {
+                                self.cache
+                                    .restore
+                                    .last_restored
+                                    .insert(scan_info.game_name.clone(), chrono::Utc::now());
+                            }
                             scan_info.clear_processed_changes(backup_info, SCAN_KIND);
                         }
 
@@ -1007,7 +1167,7 @@ impl App {
 
     fn handle_validation(&mut self, phase: ValidatePhase) -> Task<Message> {
         match phase {
-            ValidatePhase::Start => {
+            ValidatePhase::Start { scrub } => {
                 if !self.operation.idle() {
                     return Task::none();
                 }
@@ -1019,7 +1179,7 @@ impl App {
                     });
                 }
 
-                self.operation = Operation::new_validate_backups();
+                self.operation = Operation::new_validate_backups(scrub);
 
                 self.invalidate_path_caches();
                 self.timed_notification = None;
@@ -1028,12 +1188,13 @@ impl App {
             }
             ValidatePhase::Load => {
                 let restore_path = self.config.restore.path.clone();
+                let zip_password = self.config.zip_password();
 
                 self.progress.start();
 
                 Task::perform(
                     async move {
-                        let layout = BackupLayout::new(restore_path);
+                        let layout = BackupLayout::new(restore_path).with_zip_password(zip_password);
                         let subjects = layout.restorable_games();
                         (layout, subjects)
                     },
@@ -1058,6 +1219,7 @@ impl App {
                 self.progress.set_max(subjects.len() as f32);
 
                 let layout = std::sync::Arc::new(layout);
+                let scrub = matches!(self.operation, Operation::ValidateBackups { scrub: true, .. });
 
                 for name in subjects {
                     let layout = layout.clone();
@@ -1074,7 +1236,8 @@ impl App {
                             };
 
                             // TODO: Add an option to validate all backups at once.
-                            let valid = layout.validate(backup_id);
+                            let valid =
+                                layout.validate(backup_id.clone()) && (!scrub || layout.scrub(backup_id).is_empty());
                             (name, valid)
                         },
                         move |(game, valid)| Message::ValidateBackups(ValidatePhase::GameScanned { game, valid }),
@@ -1168,6 +1331,8 @@ impl App {
                 files: standard.files.keys().cloned().collect(),
                 registry: standard.registry.keys().cloned().collect(),
                 install_dir: standard.install_dir.keys().filter(|x| *x != &name).cloned().collect(),
+                include: vec![],
+                store: standard.store,
                 expanded: true,
             }
         } else {
@@ -1180,11 +1345,14 @@ impl App {
                 files: vec![],
                 registry: vec![],
                 install_dir: vec![],
+                include: vec![],
+                store: None,
                 expanded: true,
             }
         };
 
-        self.text_histories.add_custom_game(&game);
+        self.text_histories
+            .add_custom_game(&game, self.config.backup.exclusions_for(&name));
         self.config.custom_games.push(game);
         self.save_config();
 
@@ -1195,6 +1363,18 @@ impl App {
         self.switch_screen(Screen::CustomGames)
     }
 
+    /// Subpath exclusions are keyed by game name, so renaming a custom game switches to that name's list.
+    fn reload_custom_game_exclusions(&mut self, index: usize) {
+        let name = &self.config.custom_games[index].name;
+        self.text_histories.custom_games[index].exclusions = self
+            .config
+            .backup
+            .exclusions_for(name)
+            .iter()
+            .map(|x| TextHistory::raw(x))
+            .collect();
+    }
+
     fn customize_game_as_alias(&mut self, name: String) -> Task<Message> {
         let game = CustomGame {
             name: "".to_string(),
@@ -1205,10 +1385,12 @@ impl App {
             files: vec![],
             registry: vec![],
             install_dir: vec![],
+            include: vec![],
+            store: None,
             expanded: true,
         };
 
-        self.text_histories.add_custom_game(&game);
+        self.text_histories.add_custom_game(&game, &[]);
         self.config.custom_games.push(game);
         self.save_config();
 
@@ -1229,6 +1411,70 @@ impl App {
         )
     }
 
+    /// Check only the games that were just added to the manifest for any saves on this system.
+    fn find_newly_supported_games(config: Config, manifest: Manifest, added: Vec<String>) -> Task<Message> {
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let manifest = Manifest(
+                        manifest
+                            .0
+                            .into_iter()
+                            .filter(|(name, _)| added.contains(name))
+                            .collect(),
+                    );
+                    let subjects: Vec<_> = manifest.processable_titles().cloned().collect();
+
+                    let roots = config.expanded_roots();
+                    // Leave out existing backups so that launcher scans only pick up the new games.
+                    let title_finder = TitleFinder::new(&config, &manifest, Default::default());
+                    let launchers = Launchers::scan(&roots, &manifest, &subjects, &title_finder, None);
+                    let steam_shortcuts = SteamShortcuts::scan();
+                    let processes = RunningProcesses::scan();
+
+                    let mut found: Vec<_> = subjects
+                        .into_iter()
+                        .filter(|name| {
+                            let Some(game) = manifest.0.get(name) else {
+                                return false;
+                            };
+                            scan_game_for_backup(
+                                game,
+                                name,
+                                &roots,
+                                &app_dir(),
+                                &launchers,
+                                &config.backup.filter,
+                                None,
+                                &config.backup.toggled_paths,
+                                &config.backup.toggled_registry,
+                                None,
+                                &config.redirects,
+                                config.restore.reverse_redirects,
+                                &steam_shortcuts,
+                                &BackupScanOptions {
+                                    processes: Some(&processes),
+                                    ..BackupScanOptions::from_config(&config, name)
+                                },
+                            )
+                            .found_anything()
+                        })
+                        .collect();
+                    found.sort();
+                    found
+                })
+                .await
+            },
+            |join| match join {
+                Ok(games) => Message::NewlySupportedGamesFound(games),
+                Err(e) => {
+                    log::error!("Unable to check for newly supported games: {e:?}");
+                    Message::Ignore
+                }
+            },
+        )
+    }
+
     fn open_url(url: String) -> Task<Message> {
         let url2 = url.clone();
         Task::future(async move {
@@ -1287,24 +1533,59 @@ impl App {
             .insert(subject, scrollable::AbsoluteOffset::default());
     }
 
-    fn configure_remote(&self, remote: Remote) -> Task<Message> {
+    fn configure_remote(&mut self, remote: Remote) -> Task<Message> {
         let rclone = self.config.apps.rclone.clone();
         let old_remote = self.config.cloud.remote.clone();
         let new_remote = remote.clone();
-        Task::future(async move {
-            let result = async {
+
+        let cancel_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        self.cloud_authorization_should_cancel = cancel_flag.clone();
+
+        let show_progress = if remote.uses_browser_authorization() {
+            self.show_modal(Modal::AuthorizingCloud {
+                remote: RemoteChoice::from(Some(&remote)),
+            })
+        } else {
+            Task::none()
+        };
+
+        Task::batch([
+            show_progress,
+            Task::future(async move {
+                let new_rclone = Rclone::new(rclone.clone(), new_remote);
+
+                // Sign in first so that the old remote stays intact if the user gives up.
+                let token = if remote.uses_browser_authorization() {
+                    let authorized = match new_rclone.authorize() {
+                        Ok(mut authorization) => {
+                            let mut interval = tokio::time::interval(std::time::Duration::from_millis(100));
+                            loop {
+                                interval.tick().await;
+                                if let Some(outcome) = authorization.poll(&cancel_flag) {
+                                    break outcome;
+                                }
+                            }
+                        }
+                        Err(e) => Err(e),
+                    };
+                    match authorized {
+                        Ok(token) => Some(token),
+                        Err(e) => return Message::AuthorizeCloudFailure(e),
+                    }
+                } else {
+                    None
+                };
+
                 if let Some(old_remote) = old_remote {
-                    _ = Rclone::new(rclone.clone(), old_remote).unconfigure_remote();
+                    _ = Rclone::new(rclone, old_remote).unconfigure_remote();
                 }
-                Rclone::new(rclone, new_remote).configure_remote()
-            }
-            .await;
 
-            match result {
-                Ok(_) => Message::ConfigureCloudSuccess(remote),
-                Err(e) => Message::ConfigureCloudFailure(e),
-            }
-        })
+                match new_rclone.configure_remote(token) {
+                    Ok(_) => Message::ConfigureCloudSuccess(remote),
+                    Err(e) => Message::ConfigureCloudFailure(e),
+                }
+            }),
+        ])
     }
 
     pub fn new(flags: Flags) -> (Self, Task<Message>) {
@@ -1384,6 +1665,7 @@ impl App {
             Self {
                 backup_screen: screen::Backup::new(&config, &cache),
                 restore_screen: screen::Restore::new(&config, &cache),
+                tray: if config.tray.enabled { Tray::new() } else { None },
                 config,
                 manifest,
                 cache,
@@ -1411,7 +1693,7 @@ impl App {
             Message::CloseModal => self.close_modal(),
             Message::Exit { user } => {
                 if self.operation.idle() || (user && self.exiting) {
-                    self.save();
+                    self.save(true);
                     std::process::exit(0)
                 } else {
                     self.exiting = true;
@@ -1419,7 +1701,7 @@ impl App {
                 }
             }
             Message::Save => {
-                self.save();
+                self.save(false);
                 Task::none()
             }
             Message::UpdateTime => {
@@ -1434,6 +1716,30 @@ impl App {
                 }
                 Task::none()
             }
+            Message::PollTray => {
+                let mut tasks = vec![];
+                while let Some(event) = tray::poll() {
+                    match event {
+                        tray::Event::BackupAll => {
+                            if self.operation.idle() {
+                                self.notify_on_backup_finished = true;
+                                tasks.push(self.handle_backup(BackupPhase::Start {
+                                    preview: false,
+                                    repair: false,
+                                    jump: false,
+                                    games: None,
+                                }));
+                            }
+                        }
+                        tray::Event::Open => {
+                            tasks.push(iced::window::get_latest().and_then(|id| {
+                                Task::batch([iced::window::minimize(id, false), iced::window::gain_focus(id)])
+                            }));
+                        }
+                    }
+                }
+                Task::batch(tasks)
+            }
             Message::Config { event } => {
                 let mut task = None;
 
@@ -1448,6 +1754,17 @@ impl App {
                     config::Event::CheckRelease(enabled) => {
                         self.config.release.check = enabled;
                     }
+                    config::Event::ShowTray(enabled) => {
+                        self.config.tray.enabled = enabled;
+                        match &self.tray {
+                            Some(tray) => tray.set_visible(enabled),
+                            None => {
+                                if enabled {
+                                    self.tray = Tray::new();
+                                }
+                            }
+                        }
+                    }
                     config::Event::BackupTarget(text) => {
                         self.text_histories.backup_target.push(&text);
                         self.config.backup.path.reset(text);
@@ -1522,6 +1839,13 @@ impl App {
                     config::Event::CustomGameIntegration(index, integration) => {
                         self.config.custom_games[index].integration = integration;
                     }
+                    config::Event::CustomGameStore(index, store) => {
+                        self.config.custom_games[index].store = store;
+                    }
+                    config::Event::CustomGameFormat(index, format) => {
+                        let name = self.config.custom_games[index].name.clone();
+                        self.config.backup.set_format_for(&name, format);
+                    }
                     config::Event::Redirect(action, field) => {
                         // TODO: Automatically refresh redirected paths in the game list.
                         match action {
@@ -1554,6 +1878,12 @@ impl App {
                     config::Event::ReverseRedirectsOnRestore(enabled) => {
                         self.config.restore.reverse_redirects = enabled;
                     }
+                    config::Event::RedirectFallbackOnRestore(enabled) => {
+                        self.config.restore.redirect_fallback = enabled;
+                    }
+                    config::Event::ConflictPolicy(value) => {
+                        self.config.restore.conflict_policy = value;
+                    }
                     config::Event::CustomGame(action) => {
                         let mut snap = false;
                         match action {
@@ -1565,6 +1895,7 @@ impl App {
                             EditAction::Change(index, value) => {
                                 self.text_histories.custom_games[index].name.push(&value);
                                 self.config.custom_games[index].name = value;
+                                self.reload_custom_game_exclusions(index);
                             }
                             EditAction::Remove(index) => {
                                 self.text_histories.custom_games.remove(index);
@@ -1658,9 +1989,69 @@ impl App {
                             self.config.custom_games[game_index].install_dir.swap(index, offset);
                         }
                     },
+                    config::Event::CustomGameInclude(game_index, action) => match action {
+                        EditAction::Add => {
+                            self.text_histories.custom_games[game_index]
+                                .include
+                                .push(Default::default());
+                            self.config.custom_games[game_index].include.push("".to_string());
+                        }
+                        EditAction::Change(index, value) => {
+                            self.text_histories.custom_games[game_index].include[index].push(&value);
+                            self.config.custom_games[game_index].include[index] = value;
+                        }
+                        EditAction::Remove(index) => {
+                            self.text_histories.custom_games[game_index].include.remove(index);
+                            self.config.custom_games[game_index].include.remove(index);
+                        }
+                        EditAction::Move(index, direction) => {
+                            let offset = direction.shift(index);
+                            self.text_histories.custom_games[game_index].include.swap(index, offset);
+                            self.config.custom_games[game_index].include.swap(index, offset);
+                        }
+                    },
+                    config::Event::CustomGameExclusion(game_index, action) => {
+                        let name = self.config.custom_games[game_index].name.clone();
+                        let histories = &mut self.text_histories.custom_games[game_index].exclusions;
+                        let exclusions = self.config.backup.game_exclusions.entry(name.clone()).or_default();
+                        match action {
+                            EditAction::Add => {
+                                histories.push(Default::default());
+                                exclusions.push("".to_string());
+                            }
+                            EditAction::Change(index, value) => {
+                                histories[index].push(&value);
+                                exclusions[index] = value;
+                            }
+                            EditAction::Remove(index) => {
+                                histories.remove(index);
+                                exclusions.remove(index);
+                            }
+                            EditAction::Move(index, direction) => {
+                                let offset = direction.shift(index);
+                                histories.swap(index, offset);
+                                exclusions.swap(index, offset);
+                            }
+                        }
+                        if exclusions.is_empty() {
+                            self.config.backup.game_exclusions.remove(&name);
+                        }
+                    }
                     config::Event::ExcludeStoreScreenshots(enabled) => {
                         self.config.backup.filter.exclude_store_screenshots = enabled;
                     }
+                    config::Event::MaxFileSize(size) => {
+                        self.config.backup.filter.max_file_size = size;
+                    }
+                    config::Event::MinFileSize(size) => {
+                        self.config.backup.filter.min_file_size = size;
+                    }
+                    config::Event::ExcludeEmptyFiles(enabled) => {
+                        self.config.backup.filter.exclude_empty_files = enabled;
+                    }
+                    config::Event::ExcludeEmptyDirs(enabled) => {
+                        self.config.backup.filter.exclude_empty_dirs = enabled;
+                    }
                     config::Event::CloudFilter(filter) => {
                         self.config.backup.filter.cloud = filter;
                     }
@@ -1690,31 +2081,34 @@ impl App {
                         }
                         self.config.backup.filter.build_globs();
                     }
-                    config::Event::BackupFilterIgnoredRegistry(action) => match action {
-                        EditAction::Add => {
-                            self.text_histories
-                                .backup_filter_ignored_registry
-                                .push(Default::default());
-                            self.config
-                                .backup
-                                .filter
-                                .ignored_registry
-                                .push(RegistryItem::new("".to_string()));
-                        }
-                        EditAction::Change(index, value) => {
-                            self.text_histories.backup_filter_ignored_registry[index].push(&value);
-                            self.config.backup.filter.ignored_registry[index] = RegistryItem::new(value);
-                        }
-                        EditAction::Remove(index) => {
-                            self.text_histories.backup_filter_ignored_registry.remove(index);
-                            self.config.backup.filter.ignored_registry.remove(index);
-                        }
-                        EditAction::Move(index, direction) => {
-                            let offset = direction.shift(index);
-                            self.text_histories.backup_filter_ignored_registry.swap(index, offset);
-                            self.config.backup.filter.ignored_registry.swap(index, offset);
+                    config::Event::BackupFilterIgnoredRegistry(action) => {
+                        match action {
+                            EditAction::Add => {
+                                self.text_histories
+                                    .backup_filter_ignored_registry
+                                    .push(Default::default());
+                                self.config
+                                    .backup
+                                    .filter
+                                    .ignored_registry
+                                    .push(RegistryItem::new("".to_string()));
+                            }
+                            EditAction::Change(index, value) => {
+                                self.text_histories.backup_filter_ignored_registry[index].push(&value);
+                                self.config.backup.filter.ignored_registry[index] = RegistryItem::new(value);
+                            }
+                            EditAction::Remove(index) => {
+                                self.text_histories.backup_filter_ignored_registry.remove(index);
+                                self.config.backup.filter.ignored_registry.remove(index);
+                            }
+                            EditAction::Move(index, direction) => {
+                                let offset = direction.shift(index);
+                                self.text_histories.backup_filter_ignored_registry.swap(index, offset);
+                                self.config.backup.filter.ignored_registry.swap(index, offset);
+                            }
                         }
-                    },
+                        self.config.backup.filter.build_globs();
+                    }
                     config::Event::GameListEntryEnabled {
                         name,
                         enabled,
@@ -1877,6 +2271,13 @@ impl App {
                         self.text_histories.cloud_path.push(&text);
                         self.config.cloud.path = text;
                     }
+                    config::Event::CloudTransfers(transfers) => {
+                        self.config.cloud.transfers = transfers;
+                    }
+                    config::Event::CloudBandwidthLimit(text) => {
+                        self.text_histories.cloud_bandwidth_limit.push(&text);
+                        self.config.cloud.bandwidth_limit = (!text.trim().is_empty()).then_some(text);
+                    }
                     config::Event::SortCustomGames => {
                         self.config.custom_games.sort_by(|x, y| x.name.cmp(&y.name));
                         self.text_histories
@@ -1889,6 +2290,9 @@ impl App {
                             entry.scan_info.only_constructive_backups = value;
                         }
                     }
+                    config::Event::FollowSymlinks(value) => {
+                        self.config.backup.follow_symlinks = value;
+                    }
                 }
 
                 self.save_config();
@@ -1941,10 +2345,12 @@ impl App {
                 self.updating_manifest = false;
                 self.manifest_notification = None;
                 let mut errors = vec![];
+                let mut modified = false;
 
                 for update in updates {
                     match update {
                         Ok(Some(update)) => {
+                            modified |= update.modified;
                             self.cache.update_manifest(update);
                         }
                         Ok(None) => {}
@@ -1956,8 +2362,18 @@ impl App {
 
                 self.save_cache();
 
+                let mut added = vec![];
                 match Manifest::load() {
                     Ok(x) => {
+                        // On the very first download, every game would look new.
+                        if modified && !self.manifest.primary.0.is_empty() {
+                            added =
+                                x.0.keys()
+                                    .filter(|name| !self.manifest.primary.0.contains_key(*name))
+                                    .cloned()
+                                    .collect();
+                        }
+
                         self.manifest = LoadedManifest {
                             primary: x.clone(),
                             extended: x.with_extensions(&self.config),
@@ -1968,17 +2384,64 @@ impl App {
                     }
                 }
 
-                if errors.is_empty() {
+                let task = if errors.is_empty() {
                     self.close_specific_modal(Modal::UpdatingManifest)
                 } else {
                     self.show_modal(Modal::Errors { errors })
+                };
+
+                if added.is_empty() {
+                    task
+                } else {
+                    Task::batch([
+                        task,
+                        Self::find_newly_supported_games(self.config.clone(), self.manifest.extended.clone(), added),
+                    ])
                 }
             }
+            Message::NewlySupportedGamesFound(games) => {
+                if !games.is_empty() {
+                    log::info!("Newly supported games with saves: {games:?}");
+                    self.timed_notification =
+                        Some(Notification::new(TRANSLATOR.newly_supported_games_found(games.len())).expires(5));
+                }
+                Task::none()
+            }
             Message::Backup(phase) => self.handle_backup(phase),
             Message::Restore(phase) => self.handle_restore(phase),
             Message::ValidateBackups(phase) => self.handle_validation(phase),
             Message::CancelOperation => self.cancel_operation(),
             Message::ShowGameNotes { game, notes } => self.show_modal(Modal::GameNotes { game, notes }),
+            Message::PreviewPruning => {
+                let layout = BackupLayout::new(self.config.backup.path.clone());
+                let now = chrono::Utc::now();
+                let mut games = vec![];
+
+                let mut names = layout.restorable_games();
+                names.sort();
+
+                for name in names {
+                    let Some(game_layout) = layout.try_game_layout(&name) else {
+                        continue;
+                    };
+                    let pruned: Vec<_> = game_layout
+                        .plan_pruning(self.config.backup.retention_for(&name), &now)
+                        .iter()
+                        .filter_map(|id| game_layout.find_by_id_flattened(id))
+                        .collect();
+                    if pruned.is_empty() {
+                        continue;
+                    }
+                    let locked = game_layout
+                        .restorable_backups_flattened()
+                        .into_iter()
+                        .filter(|x| x.locked())
+                        .collect();
+                    games.push(PrunedGame { name, pruned, locked });
+                }
+
+                self.show_modal(Modal::PruningPreview { games })
+            }
             Message::FindRoots => {
                 let missing = self.config.find_missing_roots();
                 if missing.is_empty() {
@@ -2012,6 +2475,18 @@ impl App {
                 Task::none()
             }
             Message::SwitchScreen(screen) => self.switch_screen(screen),
+            Message::ShowChangedGame { name } => {
+                self.backup_screen.log.search.show = true;
+                self.text_histories.backup_search_game_name.push(&name);
+                self.backup_screen.log.search.game_name = name.clone();
+                self.backup_screen.log.expand_game(
+                    &name,
+                    &self.backup_screen.duplicate_detector,
+                    &self.config,
+                    ScanKind::Backup,
+                );
+                self.switch_screen(Screen::Backup)
+            }
             Message::ToggleGameListEntryExpanded { name } => {
                 match self.screen {
                     Screen::Backup => {
@@ -2080,7 +2555,7 @@ impl App {
                             self.custom_games_screen.filter.enabled = !self.custom_games_screen.filter.enabled;
                             task = Some(iced::widget::text_input::focus(id::custom_games_search()));
                         }
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::ToggledFilter { filter, enabled } => match self.screen {
                         Screen::Backup => {
@@ -2090,7 +2565,7 @@ impl App {
                             self.restore_screen.log.search.toggle_filter(filter, enabled);
                         }
                         Screen::CustomGames => {}
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::EditedGameName(value) => match self.screen {
                         Screen::Backup => {
@@ -2105,7 +2580,7 @@ impl App {
                             self.text_histories.custom_games_search_game_name.push(&value);
                             self.custom_games_screen.filter.name = value;
                         }
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::Reset => match self.screen {
                         Screen::Backup => {
@@ -2120,7 +2595,7 @@ impl App {
                             self.custom_games_screen.filter.reset();
                             self.text_histories.custom_games_search_game_name.push("");
                         }
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::EditedFilterUniqueness(value) => match self.screen {
                         Screen::Backup => {
@@ -2130,7 +2605,7 @@ impl App {
                             self.restore_screen.log.search.uniqueness.choice = value;
                         }
                         Screen::CustomGames => {}
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::EditedFilterCompleteness(value) => match self.screen {
                         Screen::Backup => {
@@ -2140,7 +2615,7 @@ impl App {
                             self.restore_screen.log.search.completeness.choice = value;
                         }
                         Screen::CustomGames => {}
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::EditedFilterEnablement(value) => match self.screen {
                         Screen::Backup => {
@@ -2150,7 +2625,7 @@ impl App {
                             self.restore_screen.log.search.enablement.choice = value;
                         }
                         Screen::CustomGames => {}
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::EditedFilterChange(value) => match self.screen {
                         Screen::Backup => {
@@ -2160,7 +2635,7 @@ impl App {
                             self.restore_screen.log.search.change.choice = value;
                         }
                         Screen::CustomGames => {}
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                     game_filter::Event::EditedFilterManifest(value) => match self.screen {
                         Screen::Backup => {
@@ -2170,7 +2645,17 @@ impl App {
                             self.restore_screen.log.search.manifest.choice = value;
                         }
                         Screen::CustomGames => {}
-                        Screen::Other => {}
+                        Screen::Changes | Screen::Other => {}
+                    },
+                    game_filter::Event::EditedFilterFailure(value) => match self.screen {
+                        Screen::Backup => {
+                            self.backup_screen.log.search.failure.choice = value;
+                        }
+                        Screen::Restore => {
+                            self.restore_screen.log.search.failure.choice = value;
+                        }
+                        Screen::CustomGames => {}
+                        Screen::Changes | Screen::Other => {}
                     },
                 }
 
@@ -2406,10 +2891,13 @@ impl App {
                         &mut self.config.redirects[i].target,
                         &mut self.text_histories.redirects[i].target,
                     ),
-                    UndoSubject::CustomGameName(i) => shortcut.apply_to_string_field(
-                        &mut self.config.custom_games[i].name,
-                        &mut self.text_histories.custom_games[i].name,
-                    ),
+                    UndoSubject::CustomGameName(i) => {
+                        shortcut.apply_to_string_field(
+                            &mut self.config.custom_games[i].name,
+                            &mut self.text_histories.custom_games[i].name,
+                        );
+                        self.reload_custom_game_exclusions(i);
+                    }
                     UndoSubject::CustomGameAlias(i) => {
                         if let Some(alias) = self.config.custom_games[i].alias.as_mut() {
                             shortcut.apply_to_string_field(alias, &mut self.text_histories.custom_games[i].alias)
@@ -2427,6 +2915,25 @@ impl App {
                         &mut self.config.custom_games[i].install_dir[j],
                         &mut self.text_histories.custom_games[i].install_dir[j],
                     ),
+                    UndoSubject::CustomGameInclude(i, j) => shortcut.apply_to_string_field(
+                        &mut self.config.custom_games[i].include[j],
+                        &mut self.text_histories.custom_games[i].include[j],
+                    ),
+                    UndoSubject::CustomGameExclusion(i, j) => {
+                        let name = self.config.custom_games[i].name.clone();
+                        if let Some(exclusion) = self
+                            .config
+                            .backup
+                            .game_exclusions
+                            .get_mut(&name)
+                            .and_then(|x| x.get_mut(j))
+                        {
+                            shortcut.apply_to_string_field(
+                                exclusion,
+                                &mut self.text_histories.custom_games[i].exclusions[j],
+                            );
+                        }
+                    }
                     UndoSubject::BackupFilterIgnoredPath(i) => shortcut.apply_to_strict_path_field(
                         &mut self.config.backup.filter.ignored_paths[i],
                         &mut self.text_histories.backup_filter_ignored_paths[i],
@@ -2451,6 +2958,10 @@ impl App {
                     UndoSubject::CloudPath => {
                         shortcut.apply_to_string_field(&mut self.config.cloud.path, &mut self.text_histories.cloud_path)
                     }
+                    UndoSubject::CloudBandwidthLimit => shortcut.apply_to_option_string_field(
+                        &mut self.config.cloud.bandwidth_limit,
+                        &mut self.text_histories.cloud_bandwidth_limit,
+                    ),
                     UndoSubject::ModalField(field) => {
                         match field {
                             ModalInputKind::Url => self.text_histories.modal.url.apply(shortcut),
@@ -2458,6 +2969,10 @@ impl App {
                             ModalInputKind::Port => self.text_histories.modal.port.apply(shortcut),
                             ModalInputKind::Username => self.text_histories.modal.username.apply(shortcut),
                             ModalInputKind::Password => self.text_histories.modal.password.apply(shortcut),
+                            ModalInputKind::Bucket => self.text_histories.modal.bucket.apply(shortcut),
+                            ModalInputKind::Region => self.text_histories.modal.region.apply(shortcut),
+                            ModalInputKind::Share => self.text_histories.modal.share.apply(shortcut),
+                            ModalInputKind::Folder => self.text_histories.modal.folder.apply(shortcut),
                         }
                         return Task::none();
                     }
@@ -2483,8 +2998,41 @@ impl App {
                 self.handle_restore(RestorePhase::Start {
                     preview: true,
                     games: Some(GameSelection::single(game)),
+                    only: None,
+                })
+            }
+            Message::ToggleBackupLocked { game, id } => {
+                let updated = self.restore_screen.log.toggle_backup_locked(&game, &id);
+                if updated {
+                    self.save_backup(&game);
+                }
+                Task::none()
+            }
+            Message::ConfirmDeleteBackup { game, backup } => {
+                let dependents = self.restore_screen.log.dependent_backups(&game, &backup.id());
+                self.show_modal(Modal::ConfirmDeleteBackup {
+                    game,
+                    backup,
+                    dependents,
                 })
             }
+            Message::DeleteBackup { game, id } => match self.restore_screen.log.delete_backup(&game, &id) {
+                Ok(()) => {
+                    self.backups_to_restore.remove(&game);
+                    BackupLayout::new(self.config.restore.path.clone())
+                        .with_mirrors(self.config.backup.mirrors_for(&self.config.restore.path))
+                        .collect_garbage(false);
+                    Task::batch([
+                        self.close_modal(),
+                        self.handle_restore(RestorePhase::Start {
+                            preview: true,
+                            games: Some(GameSelection::single(game)),
+                            only: None,
+                        }),
+                    ])
+                }
+                Err(e) => self.show_error(e),
+            },
             Message::GameAction { action, game } => match action {
                 GameAction::PreviewBackup => self.handle_backup(BackupPhase::Start {
                     preview: true,
@@ -2509,16 +3057,19 @@ impl App {
                 GameAction::PreviewRestore => self.handle_restore(RestorePhase::Start {
                     preview: true,
                     games: Some(GameSelection::single(game)),
+                    only: None,
                 }),
                 GameAction::Restore { confirm } => {
                     if confirm {
                         self.handle_restore(RestorePhase::Confirm {
                             games: Some(GameSelection::single(game)),
+                            only: None,
                         })
                     } else {
                         self.handle_restore(RestorePhase::Start {
                             preview: false,
                             games: Some(GameSelection::single(game)),
+                            only: None,
                         })
                     }
                 }
@@ -2532,7 +3083,33 @@ impl App {
                     }
                     Task::none()
                 }
+                GameAction::Merge => match self
+                    .restore_screen
+                    .log
+                    .merge(&game, &self.config.backup.format_for(&game))
+                {
+                    Ok(merged) => {
+                        self.backups_to_restore
+                            .insert(game.clone(), BackupId::Named(merged.name));
+                        self.handle_restore(RestorePhase::Start {
+                            preview: true,
+                            games: Some(GameSelection::single(game)),
+                            only: None,
+                        })
+                    }
+                    Err(e) => self.show_error(e),
+                },
                 GameAction::MakeAlias => self.customize_game_as_alias(game),
+                GameAction::ExcludeFromCloud => {
+                    self.config.disable_game_for_cloud(&game);
+                    self.save_config();
+                    Task::none()
+                }
+                GameAction::IncludeInCloud => {
+                    self.config.enable_game_for_cloud(&game);
+                    self.save_config();
+                    Task::none()
+                }
             },
             Message::Scrolled { subject, position } => {
                 self.scroll_offsets.insert(subject, position);
@@ -2591,16 +3168,32 @@ impl App {
                             id: _,
                             host,
                             port,
+                            share,
                             username,
                             password,
                         } => {
                             self.text_histories.modal.host.initialize(host.clone());
                             self.text_histories.modal.port.initialize(port.to_string());
+                            self.text_histories.modal.share.initialize(share.clone());
                             self.text_histories.modal.username.initialize(username.clone());
                             self.text_histories.modal.password.initialize(password.clone());
 
                             self.show_modal(Modal::ConfigureSmbRemote)
                         }
+                        Remote::Sftp {
+                            id: _,
+                            host,
+                            port,
+                            username,
+                            password,
+                        } => {
+                            self.text_histories.modal.host.initialize(host.clone());
+                            self.text_histories.modal.port.initialize(port.to_string());
+                            self.text_histories.modal.username.initialize(username.clone());
+                            self.text_histories.modal.password.initialize(password.clone());
+
+                            self.show_modal(Modal::ConfigureSftpRemote)
+                        }
                         Remote::WebDav {
                             id: _,
                             url,
@@ -2614,6 +3207,39 @@ impl App {
 
                             self.show_modal(Modal::ConfigureWebDavRemote { provider: *provider })
                         }
+                        Remote::S3 {
+                            id: _,
+                            bucket,
+                            region,
+                            endpoint,
+                            access_key_id,
+                            secret_access_key,
+                        } => {
+                            self.text_histories.modal.bucket.initialize(bucket.clone());
+                            self.text_histories.modal.region.initialize(region.clone());
+                            self.text_histories.modal.url.initialize(endpoint.clone());
+                            self.text_histories.modal.username.initialize(access_key_id.clone());
+                            self.text_histories.modal.password.initialize(secret_access_key.clone());
+
+                            self.show_modal(Modal::ConfigureS3Remote)
+                        }
+                        Remote::B2 {
+                            id: _,
+                            bucket,
+                            account,
+                            key,
+                        } => {
+                            self.text_histories.modal.bucket.initialize(bucket.clone());
+                            self.text_histories.modal.username.initialize(account.clone());
+                            self.text_histories.modal.password.initialize(key.clone());
+
+                            self.show_modal(Modal::ConfigureB2Remote)
+                        }
+                        Remote::Local { id: _, path } => {
+                            self.text_histories.modal.folder.initialize(path.clone());
+
+                            self.show_modal(Modal::ConfigureLocalRemote)
+                        }
                         Remote::Box { .. }
                         | Remote::Dropbox { .. }
                         | Remote::GoogleDrive { .. }
@@ -2639,6 +3265,21 @@ impl App {
                 self.save_config();
                 self.show_error(Error::UnableToConfigureCloud(error))
             }
+            Message::AuthorizeCloudFailure(error) => {
+                // The old remote is left as-is until the user finishes signing in.
+                self.text_histories.clear_modal_fields();
+
+                if let CommandError::Cancelled { .. } = error {
+                    self.close_modal()
+                } else {
+                    self.show_error(Error::UnableToConfigureCloud(error))
+                }
+            }
+            Message::CancelCloudAuthorization => {
+                self.cloud_authorization_should_cancel
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+                Task::none()
+            }
             Message::ConfirmSynchronizeCloud { direction } => {
                 let local = self.config.backup.path.clone();
 
@@ -2695,6 +3336,22 @@ impl App {
                             return cmd;
                         }
 
+                        if self.config.cloud.timestamped_backups
+                            && matches!(
+                                self.operation,
+                                Operation::Cloud {
+                                    direction: SyncDirection::Download,
+                                    finality: Finality::Final,
+                                    ..
+                                }
+                            )
+                        {
+                            BackupLayout::new(self.config.backup.path.clone())
+                                .with_folder_template(self.config.backup.folder_template.clone())
+                                .with_filename_policy(self.config.filename_policy())
+                                .timestamp_solo_backups(&[]);
+                        }
+
                         if let Some(modal) = self.modal.as_mut() {
                             self.operation = Operation::Idle;
                             self.progress.reset();
@@ -2735,6 +3392,18 @@ impl App {
                     ModalField::Password(new) => {
                         self.text_histories.modal.password.push(&new);
                     }
+                    ModalField::Bucket(new) => {
+                        self.text_histories.modal.bucket.push(&new);
+                    }
+                    ModalField::Region(new) => {
+                        self.text_histories.modal.region.push(&new);
+                    }
+                    ModalField::Share(new) => {
+                        self.text_histories.modal.share.push(&new);
+                    }
+                    ModalField::Folder(new) => {
+                        self.text_histories.modal.folder.push(&new);
+                    }
                     ModalField::WebDavProvider(new) => {
                         if let Some(Modal::ConfigureWebDavRemote { provider }) = self.modal.as_mut() {
                             *provider = new;
@@ -2781,6 +3450,10 @@ impl App {
             subscriptions.push(iced::time::every(Duration::from_millis(250)).map(|_| Message::PruneNotifications));
         }
 
+        if self.tray.is_some() && self.config.tray.enabled {
+            subscriptions.push(iced::time::every(Duration::from_millis(200)).map(|_| Message::PollTray));
+        }
+
         if self.progress.visible() {
             subscriptions.push(iced::time::every(Duration::from_millis(100)).map(|_| Message::UpdateTime));
         }
@@ -2814,6 +3487,7 @@ impl App {
                     .padding([10, 20])
                     .spacing(20)
                     .push(button::nav(Screen::Backup, self.screen))
+                    .push(button::nav(Screen::Changes, self.screen))
                     .push(button::nav(Screen::Restore, self.screen))
                     .push(button::nav(Screen::CustomGames, self.screen))
                     .push(button::nav(Screen::Other, self.screen)),
@@ -2821,13 +3495,16 @@ impl App {
             .push(match self.screen {
                 Screen::Backup => self.backup_screen.view(
                     &self.config,
+                    &self.cache,
                     &self.manifest.extended,
                     &self.operation,
                     &self.text_histories,
                     &self.modifiers,
                 ),
+                Screen::Changes => screen::changes(&self.config, &self.backup_screen.log, &self.operation),
                 Screen::Restore => self.restore_screen.view(
                     &self.config,
+                    &self.cache,
                     &self.manifest.extended,
                     &self.operation,
                     &self.text_histories,
@@ -2868,3 +3545,35 @@ impl App {
             .into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_save_waits_for_edits_to_settle() {
+        let start = Instant::now();
+        let mut pending = PendingSave::new(start);
+
+        assert!(!pending.is_due(start + Duration::from_secs(1)));
+        assert!(pending.is_due(start + SAVE_DEBOUNCE));
+
+        pending.latest = start + Duration::from_secs(1);
+        assert!(!pending.is_due(start + SAVE_DEBOUNCE));
+        assert!(pending.is_due(start + Duration::from_secs(1) + SAVE_DEBOUNCE));
+    }
+
+    #[test]
+    fn pending_save_is_due_after_max_delay_despite_continuous_edits() {
+        let start = Instant::now();
+        let mut pending = PendingSave::new(start);
+
+        for millis in (0..5000).step_by(500) {
+            pending.latest = start + Duration::from_millis(millis);
+            assert!(!pending.is_due(pending.latest));
+        }
+
+        pending.latest = start + SAVE_MAX_DELAY;
+        assert!(pending.is_due(pending.latest));
+    }
+}
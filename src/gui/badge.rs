@@ -92,6 +92,15 @@ impl Badge {
         }
     }
 
+    pub fn removed_entry_with_count(count: usize) -> Self {
+        Self {
+            text: format!("{}{}", crate::lang::REMOVAL_SYMBOL, count),
+            change: Some(ScanChange::Removed),
+            tooltip: Some(TRANSLATOR.removed_tooltip()),
+            ..Default::default()
+        }
+    }
+
     pub fn on_press(mut self, message: Message) -> Self {
         self.on_press = Some(message);
         self
@@ -1,18 +1,20 @@
 use iced::{alignment, keyboard, Length};
+use itertools::Itertools;
 
 use crate::{
     gui::{
         common::{
-            BackupPhase, BrowseFileSubject, BrowseSubject, Message, Operation, RestorePhase, Screen, ValidatePhase,
+            BackupPhase, BrowseFileSubject, BrowseSubject, GameSelection, Message, Operation, RestorePhase, Screen,
+            ValidatePhase,
         },
         icon::Icon,
         style,
         widget::{text, Button, Container, Element, Row, Text, Tooltip},
     },
     lang::TRANSLATOR,
-    prelude::{EditAction, Finality, SyncDirection},
+    prelude::{EditAction, Finality, StrictPath, SyncDirection},
     resource::{config, manifest},
-    scan::game_filter,
+    scan::{game_filter, layout::Backup},
 };
 
 const WIDTH: u16 = 125;
@@ -322,6 +324,7 @@ pub fn open_url_icon<'a>(url: String) -> Element<'a> {
 pub fn nav<'a>(screen: Screen, current_screen: Screen) -> Button<'a> {
     let label = match screen {
         Screen::Backup => TRANSLATOR.nav_backup_button(),
+        Screen::Changes => TRANSLATOR.nav_changes_button(),
         Screen::Restore => TRANSLATOR.nav_restore_button(),
         Screen::CustomGames => TRANSLATOR.nav_custom_games_button(),
         Screen::Other => TRANSLATOR.nav_other_button(),
@@ -486,7 +489,10 @@ pub fn restore<'a>(ongoing: &Operation, filtered: bool) -> Element<'a> {
         .width(WIDTH)
         .align_x(alignment::Horizontal::Center),
         match ongoing {
-            Operation::Idle => Some(Message::Restore(RestorePhase::Confirm { games: None })),
+            Operation::Idle => Some(Message::Restore(RestorePhase::Confirm {
+                games: None,
+                only: None,
+            })),
             Operation::Restore {
                 finality: Finality::Final,
                 cancelling: false,
@@ -528,6 +534,7 @@ pub fn restore_preview<'a>(ongoing: &Operation, filtered: bool) -> Element<'a> {
             Operation::Idle => Some(Message::Restore(RestorePhase::Start {
                 preview: true,
                 games: None,
+                only: None,
             })),
             Operation::Restore {
                 finality: Finality::Preview,
@@ -552,27 +559,133 @@ pub fn restore_preview<'a>(ongoing: &Operation, filtered: bool) -> Element<'a> {
 pub fn validate_backups<'a>(ongoing: &Operation) -> Element<'a> {
     template(
         text(match ongoing {
-            Operation::ValidateBackups { cancelling: false, .. } => TRANSLATOR.cancel_button(),
-            Operation::ValidateBackups { cancelling: true, .. } => TRANSLATOR.cancelling_button(),
+            Operation::ValidateBackups {
+                cancelling: false,
+                scrub: false,
+                ..
+            } => TRANSLATOR.cancel_button(),
+            Operation::ValidateBackups {
+                cancelling: true,
+                scrub: false,
+                ..
+            } => TRANSLATOR.cancelling_button(),
             _ => TRANSLATOR.validate_button(),
         })
         .width(WIDTH)
         .align_x(alignment::Horizontal::Center),
         match ongoing {
-            Operation::Idle => Some(Message::ValidateBackups(ValidatePhase::Start)),
-            Operation::ValidateBackups { cancelling: false, .. } => Some(Message::CancelOperation),
+            Operation::Idle => Some(Message::ValidateBackups(ValidatePhase::Start { scrub: false })),
+            Operation::ValidateBackups {
+                cancelling: false,
+                scrub: false,
+                ..
+            } => Some(Message::CancelOperation),
+            _ => None,
+        },
+        matches!(ongoing, Operation::ValidateBackups { scrub: false, .. }).then_some(style::Button::Negative),
+    )
+}
+
+pub fn scrub_backups<'a>(ongoing: &Operation) -> Element<'a> {
+    template(
+        text(match ongoing {
+            Operation::ValidateBackups {
+                cancelling: false,
+                scrub: true,
+                ..
+            } => TRANSLATOR.cancel_button(),
+            Operation::ValidateBackups {
+                cancelling: true,
+                scrub: true,
+                ..
+            } => TRANSLATOR.cancelling_button(),
+            _ => TRANSLATOR.scrub_button(),
+        })
+        .width(WIDTH)
+        .align_x(alignment::Horizontal::Center),
+        match ongoing {
+            Operation::Idle => Some(Message::ValidateBackups(ValidatePhase::Start { scrub: true })),
+            Operation::ValidateBackups {
+                cancelling: false,
+                scrub: true,
+                ..
+            } => Some(Message::CancelOperation),
             _ => None,
         },
-        matches!(ongoing, Operation::ValidateBackups { .. }).then_some(style::Button::Negative),
+        matches!(ongoing, Operation::ValidateBackups { scrub: true, .. }).then_some(style::Button::Negative),
     )
 }
 
 pub fn show_game_notes<'a>(game: String, notes: Vec<manifest::Note>) -> Element<'a> {
-    template_bare(
+    let summary = notes.iter().map(|note| note.message.as_str()).join("\n\n");
+    let button = template_bare(
         Icon::Info.text_narrow(),
         Some(Message::ShowGameNotes { game, notes }),
         Some(style::Button::Bare),
+    );
+
+    Tooltip::new(button, text(summary), iced::widget::tooltip::Position::Top)
+        .class(style::Container::Tooltip)
+        .into()
+}
+
+pub fn toggle_backup_locked<'a>(game: String, backup: &Backup, enabled: bool) -> Element<'a> {
+    let locked = backup.locked();
+    let button = template_bare(
+        (if locked { Icon::Lock } else { Icon::LockOpen }).text_narrow(),
+        enabled.then(|| Message::ToggleBackupLocked { game, id: backup.id() }),
+        Some(style::Button::Bare),
+    );
+
+    Tooltip::new(
+        button,
+        text(if locked {
+            TRANSLATOR.unlock_button()
+        } else {
+            TRANSLATOR.lock_button()
+        }),
+        iced::widget::tooltip::Position::Top,
     )
+    .class(style::Container::Tooltip)
+    .into()
+}
+
+pub fn delete_backup<'a>(game: String, backup: &Backup, enabled: bool) -> Element<'a> {
+    let button = template_bare(
+        Icon::Delete.text_narrow(),
+        (enabled && !backup.locked()).then(|| Message::ConfirmDeleteBackup {
+            game,
+            backup: backup.clone(),
+        }),
+        Some(style::Button::Bare),
+    );
+
+    Tooltip::new(
+        button,
+        text(TRANSLATOR.delete_backup_button()),
+        iced::widget::tooltip::Position::Top,
+    )
+    .class(style::Container::Tooltip)
+    .into()
+}
+
+pub fn restore_only<'a>(game: String, path: StrictPath) -> Element<'a> {
+    let button = Button::new(Icon::Download.text_small())
+        .on_press(Message::Restore(RestorePhase::Confirm {
+            games: Some(GameSelection::single(game)),
+            only: Some(path),
+        }))
+        .class(style::Button::Primary)
+        .padding(5)
+        .height(25);
+
+    Tooltip::new(
+        button,
+        text(TRANSLATOR.restore_only_this_button()),
+        iced::widget::tooltip::Position::Top,
+    )
+    .class(style::Container::Tooltip)
+    .into()
 }
 
 pub fn expand<'a>(expanded: bool, on_press: Message) -> Element<'a> {
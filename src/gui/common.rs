@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 use iced::{widget::text_input, Length};
 
@@ -16,8 +16,8 @@ use crate::{
     },
     scan::{
         game_filter,
-        layout::{Backup, BackupLayout, GameLayout},
-        BackupInfo, Launchers, ScanInfo, ScanKind, SteamShortcuts,
+        layout::{Backup, BackupHistory, BackupLayout, GameLayout},
+        BackupId, BackupInfo, Launchers, RunningProcesses, ScanInfo, ScanKind, SteamShortcuts,
     },
 };
 
@@ -54,7 +54,9 @@ pub enum BackupPhase {
         manifest: Manifest,
         layout: Box<BackupLayout>,
         steam: SteamShortcuts,
+        processes: RunningProcesses,
         launchers: Launchers,
+        history: HashMap<String, BackupHistory>,
     },
     GameScanned {
         scan_info: Option<ScanInfo>,
@@ -68,10 +70,13 @@ pub enum BackupPhase {
 pub enum RestorePhase {
     Confirm {
         games: Option<GameSelection>,
+        /// Only restore the files at or inside of this location.
+        only: Option<StrictPath>,
     },
     Start {
         preview: bool,
         games: Option<GameSelection>,
+        only: Option<StrictPath>,
     },
     CloudCheck,
     Load,
@@ -89,7 +94,9 @@ pub enum RestorePhase {
 
 #[derive(Debug, Clone)]
 pub enum ValidatePhase {
-    Start,
+    Start {
+        scrub: bool,
+    },
     Load,
     RegisterCommands {
         subjects: Vec<String>,
@@ -112,6 +119,7 @@ pub enum Message {
     CloseModal,
     UpdateTime,
     PruneNotifications,
+    PollTray,
     Config {
         event: config::Event,
     },
@@ -121,6 +129,7 @@ pub enum Message {
         force: bool,
     },
     ManifestUpdated(Vec<Result<Option<ManifestUpdate>, Error>>),
+    NewlySupportedGamesFound(Vec<String>),
     Backup(BackupPhase),
     Restore(RestorePhase),
     ValidateBackups(ValidatePhase),
@@ -128,6 +137,9 @@ pub enum Message {
     FindRoots,
     ConfirmAddMissingRoots(Vec<Root>),
     SwitchScreen(Screen),
+    ShowChangedGame {
+        name: String,
+    },
     ToggleGameListEntryExpanded {
         name: String,
     },
@@ -163,6 +175,18 @@ pub enum Message {
         game: String,
         backup: Backup,
     },
+    ToggleBackupLocked {
+        game: String,
+        id: BackupId,
+    },
+    ConfirmDeleteBackup {
+        game: String,
+        backup: Backup,
+    },
+    DeleteBackup {
+        game: String,
+        id: BackupId,
+    },
     GameAction {
         action: GameAction,
         game: String,
@@ -180,6 +204,7 @@ pub enum Message {
         game: String,
         notes: Vec<manifest::Note>,
     },
+    PreviewPruning,
     EditedBackupComment {
         game: String,
         comment: String,
@@ -193,6 +218,8 @@ pub enum Message {
     EditedCloudRemote(RemoteChoice),
     ConfigureCloudSuccess(Remote),
     ConfigureCloudFailure(CommandError),
+    AuthorizeCloudFailure(CommandError),
+    CancelCloudAuthorization,
     ConfirmSynchronizeCloud {
         direction: SyncDirection,
     },
@@ -317,17 +344,20 @@ pub enum Operation {
         errors: Vec<Error>,
         cloud_changes: i64,
         force_new_full_backup: bool,
+        quick_scan: bool,
     },
     Restore {
         finality: Finality,
         cancelling: bool,
         checking_cloud: bool,
         games: Option<GameSelection>,
+        only: Option<StrictPath>,
         errors: Vec<Error>,
         cloud_changes: i64,
     },
     ValidateBackups {
         cancelling: bool,
+        scrub: bool,
         faulty_games: BTreeSet<String>,
     },
     Cloud {
@@ -355,23 +385,26 @@ impl Operation {
             errors: vec![],
             cloud_changes: 0,
             force_new_full_backup: false,
+            quick_scan: false,
         }
     }
 
-    pub fn new_restore(finality: Finality, games: Option<GameSelection>) -> Self {
+    pub fn new_restore(finality: Finality, games: Option<GameSelection>, only: Option<StrictPath>) -> Self {
         Self::Restore {
             finality,
             cancelling: false,
             checking_cloud: false,
             games,
+            only,
             errors: vec![],
             cloud_changes: 0,
         }
     }
 
-    pub fn new_validate_backups() -> Self {
+    pub fn new_validate_backups(scrub: bool) -> Self {
         Self::ValidateBackups {
             cancelling: false,
+            scrub,
             faulty_games: Default::default(),
         }
     }
@@ -416,6 +449,13 @@ impl Operation {
         }
     }
 
+    pub fn restore_only(&self) -> Option<&StrictPath> {
+        match self {
+            Operation::Restore { only, .. } => only.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn games_specified(&self) -> bool {
         match self {
             Operation::Idle => false,
@@ -597,12 +637,33 @@ impl Operation {
             Operation::Cloud { .. } => (),
         }
     }
+
+    pub fn quick_scan(&self) -> bool {
+        match self {
+            Operation::Idle => false,
+            Operation::Backup { quick_scan, .. } => *quick_scan,
+            Operation::Restore { .. } => false,
+            Operation::ValidateBackups { .. } => false,
+            Operation::Cloud { .. } => false,
+        }
+    }
+
+    pub fn set_quick_scan(&mut self, value: bool) {
+        match self {
+            Operation::Idle => (),
+            Operation::Backup { quick_scan, .. } => *quick_scan = value,
+            Operation::Restore { .. } => (),
+            Operation::ValidateBackups { .. } => (),
+            Operation::Cloud { .. } => (),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub enum Screen {
     #[default]
     Backup,
+    Changes,
     Restore,
     CustomGames,
     Other,
@@ -643,12 +704,15 @@ pub enum UndoSubject {
     CustomGameFile(usize, usize),
     CustomGameRegistry(usize, usize),
     CustomGameInstallDir(usize, usize),
+    CustomGameInclude(usize, usize),
+    CustomGameExclusion(usize, usize),
     BackupFilterIgnoredPath(usize),
     BackupFilterIgnoredRegistry(usize),
     RcloneExecutable,
     RcloneArguments,
     CloudRemoteId,
     CloudPath,
+    CloudBandwidthLimit,
     ModalField(ModalInputKind),
     BackupComment(String),
 }
@@ -671,17 +735,25 @@ impl UndoSubject {
             | UndoSubject::CustomGameFile(_, _)
             | UndoSubject::CustomGameRegistry(_, _)
             | UndoSubject::CustomGameInstallDir(_, _)
+            | UndoSubject::CustomGameInclude(_, _)
+            | UndoSubject::CustomGameExclusion(_, _)
             | UndoSubject::BackupFilterIgnoredPath(_)
             | UndoSubject::BackupFilterIgnoredRegistry(_)
             | UndoSubject::RcloneExecutable
             | UndoSubject::RcloneArguments
             | UndoSubject::CloudRemoteId
             | UndoSubject::CloudPath
+            | UndoSubject::CloudBandwidthLimit
             | UndoSubject::BackupComment(_) => Privacy::Public,
             UndoSubject::ModalField(field) => match field {
-                ModalInputKind::Url | ModalInputKind::Host | ModalInputKind::Port | ModalInputKind::Username => {
-                    Privacy::Public
-                }
+                ModalInputKind::Url
+                | ModalInputKind::Host
+                | ModalInputKind::Port
+                | ModalInputKind::Username
+                | ModalInputKind::Bucket
+                | ModalInputKind::Region
+                | ModalInputKind::Share
+                | ModalInputKind::Folder => Privacy::Public,
                 ModalInputKind::Password => Privacy::Private,
             },
         }
@@ -691,6 +763,7 @@ impl UndoSubject {
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum ScrollSubject {
     Backup,
+    Changes,
     Restore,
     CustomGames,
     Other,
@@ -708,6 +781,7 @@ impl ScrollSubject {
     pub fn id(&self) -> iced::widget::scrollable::Id {
         match self {
             Self::Backup => crate::gui::widget::id::backup_scroll(),
+            Self::Changes => crate::gui::widget::id::changes_scroll(),
             Self::Restore => crate::gui::widget::id::restore_scroll(),
             Self::CustomGames => crate::gui::widget::id::custom_games_scroll(),
             Self::Other => crate::gui::widget::id::other_scroll(),
@@ -734,6 +808,7 @@ impl From<Screen> for ScrollSubject {
     fn from(value: Screen) -> Self {
         match value {
             Screen::Backup => Self::Backup,
+            Screen::Changes => Self::Changes,
             Screen::Restore => Self::Restore,
             Screen::CustomGames => Self::CustomGames,
             Screen::Other => Self::Other,
@@ -752,7 +827,10 @@ pub enum GameAction {
     Comment,
     Lock,
     Unlock,
+    Merge,
     MakeAlias,
+    ExcludeFromCloud,
+    IncludeInCloud,
 }
 
 impl GameAction {
@@ -763,6 +841,8 @@ impl GameAction {
         invented: bool,
         has_backups: bool,
         locked: bool,
+        differential: bool,
+        cloud_enabled: bool,
     ) -> Vec<Self> {
         let mut options = vec![];
 
@@ -785,6 +865,14 @@ impl GameAction {
 
         options.push(Self::MakeAlias);
 
+        if scan_kind.is_backup() {
+            if cloud_enabled {
+                options.push(Self::ExcludeFromCloud);
+            } else {
+                options.push(Self::IncludeInCloud);
+            }
+        }
+
         if scan_kind.is_restore() && has_backups {
             options.push(Self::Comment);
 
@@ -793,6 +881,10 @@ impl GameAction {
             } else {
                 options.push(Self::Lock);
             }
+
+            if differential && !operating {
+                options.push(Self::Merge);
+            }
         }
 
         if !invented {
@@ -817,7 +909,10 @@ impl GameAction {
             GameAction::Comment => Icon::Comment,
             GameAction::Lock => Icon::Lock,
             GameAction::Unlock => Icon::LockOpen,
+            GameAction::Merge => Icon::CallMerge,
             GameAction::MakeAlias => Icon::Edit,
+            GameAction::ExcludeFromCloud => Icon::VisibilityOff,
+            GameAction::IncludeInCloud => Icon::Upload,
         }
     }
 }
@@ -845,7 +940,10 @@ impl ToString for GameAction {
             Self::Comment => TRANSLATOR.comment_button(),
             Self::Lock => TRANSLATOR.lock_button(),
             Self::Unlock => TRANSLATOR.unlock_button(),
+            Self::Merge => TRANSLATOR.merge_button(),
             Self::MakeAlias => TRANSLATOR.alias_label(),
+            Self::ExcludeFromCloud => TRANSLATOR.exclude_from_cloud_button(),
+            Self::IncludeInCloud => TRANSLATOR.include_in_cloud_button(),
         }
     }
 }
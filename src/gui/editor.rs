@@ -18,7 +18,10 @@ use crate::{
     lang::TRANSLATOR,
     resource::{
         cache::Cache,
-        config::{self, Config, CustomGameKind, Integration, RedirectKind, SecondaryManifestConfigKind},
+        config::{
+            self, Config, CustomGameKind, CustomGameStore, GameBackupFormat, Integration, RedirectKind,
+            SecondaryManifestConfigKind,
+        },
         manifest::{Manifest, Store},
     },
 };
@@ -225,11 +228,19 @@ pub fn redirect<'a>(config: &Config, histories: &TextHistories, modifiers: &keyb
     let redirects = config.get_redirects();
 
     let wrapper = Container::new({
-        let mut content = Column::new().padding(5).spacing(4).push(checkbox(
-            TRANSLATOR.reverse_redirects_when_restoring(),
-            config.restore.reverse_redirects,
-            Message::config(config::Event::ReverseRedirectsOnRestore),
-        ));
+        let mut content = Column::new()
+            .padding(5)
+            .spacing(4)
+            .push(checkbox(
+                TRANSLATOR.reverse_redirects_when_restoring(),
+                config.restore.reverse_redirects,
+                Message::config(config::Event::ReverseRedirectsOnRestore),
+            ))
+            .push(checkbox(
+                TRANSLATOR.fall_back_when_redirect_fails(),
+                config.restore.redirect_fallback,
+                Message::config(config::Event::RedirectFallbackOnRestore),
+            ));
 
         content = config.redirects.iter().enumerate().fold(content, |parent, (i, _)| {
             parent.push(
@@ -422,6 +433,48 @@ pub fn custom_games<'a>(
                                     .class(style::PickList::Primary),
                                 )
                         })
+                        .push_if(config.custom_games[i].kind() == CustomGameKind::Game, || {
+                            Row::new()
+                                .spacing(10)
+                                .push(
+                                    Column::new()
+                                        .width(left_side)
+                                        .padding(padding::top(top_side))
+                                        .push(text(TRANSLATOR.field(&TRANSLATOR.store_label()))),
+                                )
+                                .push(
+                                    pick_list(
+                                        CustomGameStore::all(),
+                                        Some(CustomGameStore(config.custom_games[i].store)),
+                                        Message::config(move |v: CustomGameStore| {
+                                            config::Event::CustomGameStore(i, v.0)
+                                        }),
+                                    )
+                                    .class(style::PickList::Primary),
+                                )
+                        })
+                        .push_if(config.custom_games[i].kind() == CustomGameKind::Game, || {
+                            Row::new()
+                                .spacing(10)
+                                .push(
+                                    Column::new()
+                                        .width(left_side)
+                                        .padding(padding::top(top_side))
+                                        .push(text(TRANSLATOR.field(&TRANSLATOR.backup_format_label()))),
+                                )
+                                .push(
+                                    pick_list(
+                                        GameBackupFormat::all(),
+                                        Some(GameBackupFormat(
+                                            config.backup.game_format.get(&config.custom_games[i].name).copied(),
+                                        )),
+                                        Message::config(move |v: GameBackupFormat| {
+                                            config::Event::CustomGameFormat(i, v.0)
+                                        }),
+                                    )
+                                    .class(style::PickList::Primary),
+                                )
+                        })
                         .push_if(config.custom_games[i].kind() == CustomGameKind::Game, || {
                             Row::new()
                                 .spacing(10)
@@ -551,6 +604,93 @@ pub fn custom_games<'a>(
                                             i,
                                         )),
                                 )
+                        })
+                        .push_if(config.custom_games[i].kind() == CustomGameKind::Game, || {
+                            Row::new()
+                                .spacing(10)
+                                .push(
+                                    Column::new()
+                                        .width(left_side)
+                                        .padding(padding::top(top_side))
+                                        .push(text(TRANSLATOR.field(&TRANSLATOR.custom_include_label()))),
+                                )
+                                .push(
+                                    x.include
+                                        .iter()
+                                        .enumerate()
+                                        .fold(Column::new().spacing(4), |column, (ii, _)| {
+                                            column.push(
+                                                Row::new()
+                                                    .align_y(Alignment::Center)
+                                                    .spacing(20)
+                                                    .push(button::move_up_nested(
+                                                        Message::config2(config::Event::CustomGameInclude),
+                                                        i,
+                                                        ii,
+                                                    ))
+                                                    .push(button::move_down_nested(
+                                                        Message::config2(config::Event::CustomGameInclude),
+                                                        i,
+                                                        ii,
+                                                        x.include.len(),
+                                                    ))
+                                                    .push(histories.input(UndoSubject::CustomGameInclude(i, ii)))
+                                                    .push(button::remove_nested(
+                                                        Message::config2(config::Event::CustomGameInclude),
+                                                        i,
+                                                        ii,
+                                                    )),
+                                            )
+                                        })
+                                        .push(button::add_nested(
+                                            Message::config2(config::Event::CustomGameInclude),
+                                            i,
+                                        )),
+                                )
+                        })
+                        .push_if(config.custom_games[i].kind() == CustomGameKind::Game, || {
+                            let exclusions = config.backup.exclusions_for(&config.custom_games[i].name);
+                            Row::new()
+                                .spacing(10)
+                                .push(
+                                    Column::new()
+                                        .width(left_side)
+                                        .padding(padding::top(top_side))
+                                        .push(text(TRANSLATOR.field(&TRANSLATOR.custom_excluded_subpaths_label()))),
+                                )
+                                .push(
+                                    exclusions
+                                        .iter()
+                                        .enumerate()
+                                        .fold(Column::new().spacing(4), |column, (ii, _)| {
+                                            column.push(
+                                                Row::new()
+                                                    .align_y(Alignment::Center)
+                                                    .spacing(20)
+                                                    .push(button::move_up_nested(
+                                                        Message::config2(config::Event::CustomGameExclusion),
+                                                        i,
+                                                        ii,
+                                                    ))
+                                                    .push(button::move_down_nested(
+                                                        Message::config2(config::Event::CustomGameExclusion),
+                                                        i,
+                                                        ii,
+                                                        exclusions.len(),
+                                                    ))
+                                                    .push(histories.input(UndoSubject::CustomGameExclusion(i, ii)))
+                                                    .push(button::remove_nested(
+                                                        Message::config2(config::Event::CustomGameExclusion),
+                                                        i,
+                                                        ii,
+                                                    )),
+                                            )
+                                        })
+                                        .push(button::add_nested(
+                                            Message::config2(config::Event::CustomGameExclusion),
+                                            i,
+                                        )),
+                                )
                         });
                 }
 
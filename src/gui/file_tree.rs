@@ -97,6 +97,11 @@ impl FileTreeNode {
         false
     }
 
+    /// Hives are never collapsed into their children so that they can be toggled as a whole.
+    fn is_registry_hive(&self) -> bool {
+        self.node_type == FileTreeNodeType::RegistryKey && self.keys.len() == 1
+    }
+
     pub fn view(
         &self,
         level: u16,
@@ -108,6 +113,13 @@ impl FileTreeNode {
     ) -> Container {
         let expanded = expansion.expanded(&self.keys);
 
+        let make_restore_only = || match (&self.path, scan_kind) {
+            (FileTreeNodePath::File(path), ScanKind::Restore) => {
+                Some(button::restore_only(game_name.to_string(), path.clone()))
+            }
+            _ => None,
+        };
+
         let make_enabler = || {
             let game_name = game_name.to_string();
             let path = self.path.clone();
@@ -147,6 +159,8 @@ impl FileTreeNode {
         };
 
         if self.nodes.is_empty() {
+            let excluded = self.scanned_file.as_ref().and_then(|(_, f)| f.excluded);
+
             return Container::new(
                 Row::new()
                     .align_y(Alignment::Center)
@@ -164,7 +178,7 @@ impl FileTreeNode {
                                 .width(25),
                         ),
                     })
-                    .push_maybe(make_enabler())
+                    .push_maybe(if excluded.is_some() { None } else { make_enabler() })
                     .push(text(label))
                     .push_maybe({
                         match self.change {
@@ -184,6 +198,16 @@ impl FileTreeNode {
                             .as_ref()
                             .map(|x| Badge::new(&TRANSLATOR.badge_failed()).tooltip(x.clone()).view()),
                     )
+                    .push_if(self.scanned_file.as_ref().is_some_and(|(_, f)| f.conflict), || {
+                        Badge::new(&TRANSLATOR.badge_conflict())
+                            .tooltip(TRANSLATOR.conflict_tooltip())
+                            .view()
+                    })
+                    .push_maybe(excluded.map(|x| {
+                        Badge::new(&TRANSLATOR.badge_excluded())
+                            .tooltip(TRANSLATOR.file_exclusion(&x))
+                            .view()
+                    }))
                     .push_maybe({
                         self.scanned_file.as_ref().and_then(|(scan_key, scanned)| {
                             let scan_kind = scanned.scan_kind();
@@ -196,6 +220,7 @@ impl FileTreeNode {
                             })
                         })
                     })
+                    .push_maybe(make_restore_only())
                     .push_maybe({
                         self.scanned_file.as_ref().map(|(_, f)| {
                             let size = TRANSLATOR.adjusted_size(f.size);
@@ -203,7 +228,7 @@ impl FileTreeNode {
                         })
                     }),
             );
-        } else if self.nodes.len() == 1 {
+        } else if self.nodes.len() == 1 && !self.is_registry_hive() {
             let keys: Vec<_> = self.nodes.keys().cloned().collect();
             let key = &keys[0];
             if !self.nodes.get(key).unwrap().nodes.is_empty() {
@@ -253,6 +278,7 @@ impl FileTreeNode {
                             ),
                             FileTreeNodePath::RegistryKey(..) | FileTreeNodePath::RegistryValue(..) => None,
                         })
+                        .push_maybe(make_restore_only())
                         .push_maybe({
                             let total_bytes = self.calculate_directory_size(true);
                             let total_size = total_bytes.map(|bytes| TRANSLATOR.adjusted_size(bytes));
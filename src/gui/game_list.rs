@@ -24,13 +24,16 @@ use crate::{
         widget::{checkbox, pick_list, text, Button, Column, Container, IcedButtonExt, IcedParentExt, Row, Tooltip},
     },
     lang::TRANSLATOR,
+    prelude::Error,
     resource::{
         cache::Cache,
-        config::{self, Config, Sort},
+        config::{self, BackupFormats, Config, Sort},
         manifest::{self, Manifest, Os},
     },
     scan::{
-        game_filter, layout::GameLayout, BackupInfo, DuplicateDetector, OperationStatus, ScanChange, ScanInfo, ScanKind,
+        game_filter,
+        layout::{BackupKind, FullBackup, GameLayout},
+        BackupId, BackupInfo, DuplicateDetector, OperationStatus, ScanChange, ScanChangeCount, ScanInfo, ScanKind,
     },
 };
 
@@ -51,6 +54,7 @@ impl GameListEntry {
         &self,
         scan_kind: ScanKind,
         config: &Config,
+        cache: &Cache,
         manifest: &Manifest,
         duplicate_detector: &DuplicateDetector,
         operation: &Operation,
@@ -63,6 +67,14 @@ impl GameListEntry {
             Some(x) => x.successful(),
             _ => true,
         };
+        let recently_restored = match &self.backup_info {
+            None if scan_kind.is_restore() => cache
+                .restore
+                .recent_warning(&self.scan_info.game_name, &chrono::Utc::now()),
+            _ => None,
+        };
+        let mut warnings = self.scan_info.all_warnings(self.backup_info.as_ref());
+        warnings.extend(recently_restored.as_ref());
 
         let enabled = config.is_game_enabled_for_operation(&self.scan_info.game_name, scan_kind);
         let all_items_ignored = self.scan_info.all_ignored();
@@ -114,6 +126,7 @@ impl GameListEntry {
                                         ScanKind::Restore => Some(Message::Restore(RestorePhase::Start {
                                             preview: true,
                                             games: Some(GameSelection::single(self.scan_info.game_name.clone())),
+                                            only: None,
                                         })),
                                     }
                                 } else {
@@ -167,6 +180,17 @@ impl GameListEntry {
                                 .view()
                         })
                         .push_if(!successful, || Badge::new(&TRANSLATOR.badge_failed()).view())
+                        .push_if(!warnings.is_empty(), || {
+                            Badge::new(&TRANSLATOR.badge_warning())
+                                .tooltip(
+                                    warnings
+                                        .iter()
+                                        .map(|x| TRANSLATOR.operation_warning(x))
+                                        .collect::<Vec<_>>()
+                                        .join("\n"),
+                                )
+                                .view()
+                        })
                         .push_maybe({
                             self.scan_info
                                 .backup
@@ -183,10 +207,9 @@ impl GameListEntry {
                                 })
                         })
                         .push_maybe({
-                            manifest.0.get(&name).and_then(|data| {
-                                (scan_kind.is_backup() && !data.notes.is_empty())
-                                    .then(|| button::show_game_notes(name.clone(), data.notes.clone()))
-                            })
+                            let notes = crate::metadata::game_notes(manifest, &name);
+                            (scan_kind.is_backup() && !notes.is_empty())
+                                .then(|| button::show_game_notes(name.clone(), notes.to_vec()))
                         })
                         .push_maybe({
                             self.scan_info
@@ -194,14 +217,26 @@ impl GameListEntry {
                                 .as_ref()
                                 .and_then(|backup| backup.os())
                                 .and_then(|os| {
-                                    (os != Os::HOST && os != Os::Other).then(|| Badge::new(&format!("{os:?}")).view())
+                                    (os != Os::HOST && os != Os::Other).then(|| {
+                                        Badge::new(&format!("{os:?}"))
+                                            .tooltip(TRANSLATOR.backup_from_other_os_tooltip(os))
+                                            .view()
+                                    })
                                 })
                         })
                         .push_maybe({
-                            self.scan_info
-                                .backup
-                                .as_ref()
-                                .and_then(|backup| backup.locked().then_some(Icon::Lock.text().width(Length::Shrink)))
+                            self.scan_info.backup.as_ref().and_then(|backup| {
+                                scan_kind.is_restore().then(|| {
+                                    button::toggle_backup_locked(self.scan_info.game_name.clone(), backup, !operating)
+                                })
+                            })
+                        })
+                        .push_maybe({
+                            self.scan_info.backup.as_ref().and_then(|backup| {
+                                scan_kind.is_restore().then(|| {
+                                    button::delete_backup(self.scan_info.game_name.clone(), backup, !operating)
+                                })
+                            })
                         })
                         .push(
                             Row::new()
@@ -302,6 +337,12 @@ impl GameListEntry {
                                                 .as_ref()
                                                 .map(|backup| backup.locked())
                                                 .unwrap_or_default(),
+                                            self.scan_info
+                                                .backup
+                                                .as_ref()
+                                                .map(|backup| backup.kind() == BackupKind::Differential)
+                                                .unwrap_or_default(),
+                                            config.is_game_enabled_for_cloud(&self.scan_info.game_name),
                                         );
                                         let game_name = self.scan_info.game_name.clone();
 
@@ -390,6 +431,8 @@ pub struct GameList {
     pub search: FilterComponent,
     expanded_games: HashSet<String>,
     pub filter_duplicates_of: Option<String>,
+    /// Kept up to date as games are scanned, rather than recomputed on each render.
+    changed_games: Vec<(String, ScanChangeCount)>,
 }
 
 impl GameList {
@@ -409,6 +452,7 @@ impl GameList {
         &self,
         scan_kind: ScanKind,
         config: &Config,
+        cache: &Cache,
         manifest: &Manifest,
         duplicate_detector: &DuplicateDetector,
         duplicatees: Option<&HashSet<String>>,
@@ -428,6 +472,7 @@ impl GameList {
                         histories,
                         config.scan.show_deselected_games,
                         self.manifests(manifest),
+                        self.failed_games(),
                     )
                 })
                 .push({
@@ -446,6 +491,7 @@ impl GameList {
                                 parent.push(x.view(
                                     scan_kind,
                                     config,
+                                    cache,
                                     manifest,
                                     duplicate_detector,
                                     operation,
@@ -461,6 +507,59 @@ impl GameList {
         )
     }
 
+    /// Scanned games with new, updated, or removed data that haven't been backed up yet,
+    /// with the most changes first.
+    pub fn changed_games(&self) -> &[(String, ScanChangeCount)] {
+        &self.changed_games
+    }
+
+    fn entry_changes(entry: &GameListEntry) -> Option<ScanChangeCount> {
+        if !entry.scanned || entry.backup_info.is_some() {
+            return None;
+        }
+
+        let summary = entry.scan_info.change_summary();
+        let mut changes = summary.files;
+        changes.merge(&summary.registry_keys);
+        changes.merge(&summary.registry_values);
+        (changes.changed() > 0).then_some(changes)
+    }
+
+    fn sort_changed_games(&mut self) {
+        self.changed_games.sort_by(|(name1, changes1), (name2, changes2)| {
+            changes2
+                .changed()
+                .cmp(&changes1.changed())
+                .then_with(|| name1.to_lowercase().cmp(&name2.to_lowercase()))
+        });
+    }
+
+    /// Update the changed games for a single game after it was scanned or modified.
+    fn refresh_changed_game(&mut self, game: &str) {
+        self.changed_games.retain(|(name, _)| name != game);
+        let changes = self.find_game(game).and_then(|i| Self::entry_changes(&self.entries[i]));
+        if let Some(changes) = changes {
+            self.changed_games.push((game.to_string(), changes));
+            self.sort_changed_games();
+        }
+    }
+
+    fn refresh_changed_games(&mut self) {
+        self.changed_games = self
+            .entries
+            .iter()
+            .filter_map(|entry| Self::entry_changes(entry).map(|changes| (entry.scan_info.game_name.clone(), changes)))
+            .collect();
+        self.sort_changed_games();
+    }
+
+    fn failed_games(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|x| x.backup_info.as_ref().is_some_and(|x| !x.successful()))
+            .count()
+    }
+
     pub fn all_visible_entries_selected(
         &self,
         config: &Config,
@@ -493,6 +592,7 @@ impl GameList {
 
         let qualifies = self.search.qualifies(
             &entry.scan_info,
+            entry.backup_info.as_ref(),
             manifest,
             config.is_game_enabled_for_operation(&entry.scan_info.game_name, scan_kind),
             config.is_game_customized(&entry.scan_info.game_name),
@@ -641,6 +741,7 @@ impl GameList {
     pub fn clear(&mut self) {
         self.entries.clear();
         self.expanded_games.clear();
+        self.changed_games.clear();
     }
 
     pub fn with_recent_games(scan_kind: ScanKind, config: &Config, cache: &Cache) -> Self {
@@ -739,6 +840,8 @@ impl GameList {
                 }
             }
         }
+
+        self.refresh_changed_game(&game_name);
     }
 
     pub fn refresh_game_tree(
@@ -774,6 +877,8 @@ impl GameList {
                     entry.refresh_tree(duplicate_detector, config, scan_kind);
                 }
             }
+
+            self.refresh_changed_game(game);
         }
     }
 
@@ -791,6 +896,7 @@ impl GameList {
                 entry.refresh_tree(duplicate_detector, config, scan_kind);
             }
         }
+        self.changed_games.retain(|(name, _)| name != game);
     }
 
     pub fn unscan_games(&mut self, games: &GameSelection) {
@@ -805,6 +911,7 @@ impl GameList {
                 }
             }
         }
+        self.refresh_changed_games();
     }
 
     pub fn contains_unscanned_games(&self) -> bool {
@@ -841,22 +948,73 @@ impl GameList {
         let Some(index) = self.find_game(game) else {
             return false;
         };
-        let entry = &mut self.entries[index];
-        let Some(backup) = &mut entry.scan_info.backup else {
+        let Some(id) = self.entries[index].scan_info.backup.as_ref().map(|x| x.id()) else {
+            return false;
+        };
+
+        self.toggle_backup_locked(game, &id)
+    }
+
+    pub fn toggle_backup_locked(&mut self, game: &str, id: &BackupId) -> bool {
+        let Some(index) = self.find_game(game) else {
             return false;
         };
+        let entry = &mut self.entries[index];
         let Some(layout) = &mut entry.game_layout else {
             return false;
         };
+        let Some(backup) = entry.scan_info.available_backups.iter_mut().find(|x| &x.id() == id) else {
+            return false;
+        };
 
         let new = !backup.locked();
 
         layout.set_backup_locked(backup.name(), new);
         backup.set_locked(new);
+        if let Some(selected) = entry.scan_info.backup.as_mut().filter(|x| &x.id() == id) {
+            selected.set_locked(new);
+        }
 
         true
     }
 
+    pub fn merge(&mut self, game: &str, format: &BackupFormats) -> Result<FullBackup, Error> {
+        let Some(index) = self.find_game(game) else {
+            return Err(Error::NoDifferentialBackupToMerge);
+        };
+        let entry = &mut self.entries[index];
+        let Some(backup) = &entry.scan_info.backup else {
+            return Err(Error::NoDifferentialBackupToMerge);
+        };
+        let Some(layout) = &mut entry.game_layout else {
+            return Err(Error::NoDifferentialBackupToMerge);
+        };
+
+        layout.merge(&backup.id(), &chrono::Utc::now(), format, false)
+    }
+
+    pub fn dependent_backups(&self, game: &str, id: &BackupId) -> Vec<String> {
+        let Some(index) = self.find_game(game) else {
+            return vec![];
+        };
+        let Some(layout) = &self.entries[index].game_layout else {
+            return vec![];
+        };
+
+        layout.dependent_backups(id)
+    }
+
+    pub fn delete_backup(&mut self, game: &str, id: &BackupId) -> Result<(), Error> {
+        let Some(index) = self.find_game(game) else {
+            return Err(Error::CliInvalidBackupId);
+        };
+        let Some(layout) = &mut self.entries[index].game_layout else {
+            return Err(Error::CliInvalidBackupId);
+        };
+
+        layout.delete_backup(id)
+    }
+
     pub fn save_layout(&mut self, game: &str) {
         let Some(index) = self.find_game(game) else { return };
         let entry = &mut self.entries[index];
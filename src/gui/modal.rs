@@ -19,11 +19,12 @@ use crate::{
         widget::{pick_list, text, Column, Container, Element, IcedParentExt, Row, Space},
     },
     lang::TRANSLATOR,
-    prelude::{Error, Finality, SyncDirection},
+    prelude::{Error, Finality, StrictPath, SyncDirection},
     resource::{
         config::{Config, Root},
         manifest,
     },
+    scan::layout::Backup,
 };
 
 const CHANGES_PER_PAGE: usize = 500;
@@ -41,6 +42,10 @@ pub enum ModalInputKind {
     Port,
     Username,
     Password,
+    Bucket,
+    Region,
+    Share,
+    Folder,
 }
 
 #[derive(Debug, Clone)]
@@ -50,6 +55,10 @@ pub enum ModalField {
     Port(String),
     Username(String),
     Password(String),
+    Bucket(String),
+    Region(String),
+    Share(String),
+    Folder(String),
     WebDavProvider(WebDavProvider),
 }
 
@@ -61,6 +70,10 @@ impl ModalField {
             ModalInputKind::Port => TRANSLATOR.port_label(),
             ModalInputKind::Username => TRANSLATOR.username_label(),
             ModalInputKind::Password => TRANSLATOR.password_label(),
+            ModalInputKind::Bucket => TRANSLATOR.bucket_label(),
+            ModalInputKind::Region => TRANSLATOR.region_label(),
+            ModalInputKind::Share => TRANSLATOR.share_label(),
+            ModalInputKind::Folder => TRANSLATOR.folder_label(),
         };
 
         Row::new()
@@ -116,6 +129,15 @@ impl CloudModalState {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrunedGame {
+    pub name: String,
+    /// Backups beyond the retention limits.
+    pub pruned: Vec<Backup>,
+    /// Locked backups, which are retained regardless of the limits.
+    pub locked: Vec<Backup>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Modal {
     Error {
@@ -130,6 +152,9 @@ pub enum Modal {
     },
     ConfirmRestore {
         games: Option<GameSelection>,
+        only: Option<StrictPath>,
+        /// Display names of games that were already restored a short time ago.
+        recent: Vec<String>,
     },
     NoMissingRoots,
     ConfirmAddMissingRoots(Vec<Root>),
@@ -151,28 +176,51 @@ pub enum Modal {
     },
     ConfigureFtpRemote,
     ConfigureSmbRemote,
+    ConfigureSftpRemote,
     ConfigureWebDavRemote {
         provider: WebDavProvider,
     },
+    ConfigureS3Remote,
+    ConfigureB2Remote,
+    ConfigureLocalRemote,
+    AuthorizingCloud {
+        remote: RemoteChoice,
+    },
     GameNotes {
         game: String,
         notes: Vec<manifest::Note>,
     },
+    PruningPreview {
+        games: Vec<PrunedGame>,
+    },
+    ConfirmDeleteBackup {
+        game: String,
+        backup: Backup,
+        /// Differential backups that will be deleted along with a full backup.
+        dependents: Vec<String>,
+    },
 }
 
 impl Modal {
     pub fn variant(&self) -> ModalVariant {
         match self {
-            Self::Exiting | Self::UpdatingManifest => ModalVariant::Loading,
-            Self::Error { .. } | Self::Errors { .. } | Self::NoMissingRoots | Self::GameNotes { .. } => {
-                ModalVariant::Info
-            }
+            Self::Exiting | Self::UpdatingManifest | Self::AuthorizingCloud { .. } => ModalVariant::Loading,
+            Self::Error { .. }
+            | Self::Errors { .. }
+            | Self::NoMissingRoots
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. } => ModalVariant::Info,
             Self::ConfirmBackup { .. }
             | Self::ConfirmRestore { .. }
             | Self::ConfirmAddMissingRoots(..)
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
+            | Self::ConfigureSftpRemote
             | Self::ConfigureWebDavRemote { .. }
+            | Self::ConfigureS3Remote
+            | Self::ConfigureB2Remote
+            | Self::ConfigureLocalRemote
+            | Self::ConfirmDeleteBackup { .. }
             | Self::AppUpdate { .. } => ModalVariant::Confirm,
             Self::BackupValidation { games } => {
                 if games.is_empty() {
@@ -199,7 +247,15 @@ impl Modal {
             Self::ConfirmBackup { .. } => {
                 TRANSLATOR.confirm_backup(&config.backup.path, config.backup.path.exists(), true)
             }
-            Self::ConfirmRestore { .. } => TRANSLATOR.confirm_restore(&config.restore.path, true),
+            Self::ConfirmRestore { recent, .. } => {
+                let primary = TRANSLATOR.confirm_restore(&config.restore.path, true);
+                if recent.is_empty() {
+                    primary
+                } else {
+                    let recent: Vec<_> = recent.iter().map(|x| x.as_str()).collect();
+                    format!("{}\n\n{}", primary, TRANSLATOR.confirm_restore_recent(&recent))
+                }
+            }
             Self::NoMissingRoots => TRANSLATOR.no_missing_roots(),
             Self::ConfirmAddMissingRoots(missing) => TRANSLATOR.confirm_add_missing_roots(missing),
             Self::AppUpdate { release } => TRANSLATOR.new_version_available(release.version.to_string().as_str()),
@@ -229,8 +285,25 @@ impl Modal {
             }
             Self::ConfigureFtpRemote { .. } => RemoteChoice::Ftp.to_string(),
             Self::ConfigureSmbRemote { .. } => RemoteChoice::Smb.to_string(),
+            Self::ConfigureSftpRemote => RemoteChoice::Sftp.to_string(),
             Self::ConfigureWebDavRemote { .. } => RemoteChoice::WebDav.to_string(),
+            Self::ConfigureS3Remote => RemoteChoice::S3.to_string(),
+            Self::ConfigureB2Remote => RemoteChoice::B2.to_string(),
+            Self::ConfigureLocalRemote => RemoteChoice::Local.to_string(),
+            Self::AuthorizingCloud { remote } => TRANSLATOR.waiting_for_cloud_authorization(&remote.to_string()),
             Self::GameNotes { game, .. } => game.clone(),
+            Self::PruningPreview { games } => {
+                if games.is_empty() {
+                    TRANSLATOR.no_backups_to_prune()
+                } else {
+                    TRANSLATOR.backups_to_prune()
+                }
+            }
+            Self::ConfirmDeleteBackup {
+                game,
+                backup,
+                dependents,
+            } => TRANSLATOR.confirm_delete_backup(config.display_name(game), &backup.label(), dependents),
         }
     }
 
@@ -240,19 +313,25 @@ impl Modal {
             | Self::Errors { .. }
             | Self::NoMissingRoots
             | Self::BackupValidation { .. }
-            | Self::GameNotes { .. } => Some(Message::CloseModal),
-            Self::Exiting => None,
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. } => Some(Message::CloseModal),
+            Self::Exiting | Self::AuthorizingCloud { .. } => None,
             Self::ConfirmBackup { games } => Some(Message::Backup(BackupPhase::Start {
                 preview: false,
                 repair: false,
                 jump: false,
                 games: games.clone(),
             })),
-            Self::ConfirmRestore { games } => Some(Message::Restore(RestorePhase::Start {
+            Self::ConfirmRestore { games, only, .. } => Some(Message::Restore(RestorePhase::Start {
                 preview: false,
                 games: games.clone(),
+                only: only.clone(),
             })),
             Self::ConfirmAddMissingRoots(missing) => Some(Message::ConfirmAddMissingRoots(missing.clone())),
+            Self::ConfirmDeleteBackup { game, backup, .. } => Some(Message::DeleteBackup {
+                game: game.clone(),
+                id: backup.id(),
+            }),
             Self::AppUpdate { release } => Some(Message::OpenUrlAndCloseModal(release.url.clone())),
             Self::UpdatingManifest => None,
             Self::ConfirmCloudSync { direction, state, .. } => {
@@ -287,6 +366,7 @@ impl Modal {
             Self::ConfigureSmbRemote => {
                 let host = histories.modal.host.current();
                 let port = histories.modal.port.current();
+                let share = histories.modal.share.current();
                 let username = histories.modal.username.current();
                 let password = histories.modal.password.current();
 
@@ -295,6 +375,26 @@ impl Modal {
                     None
                 } else {
                     Some(Message::FinalizeRemote(Remote::Smb {
+                        id: Remote::generate_id(),
+                        host,
+                        port,
+                        share,
+                        username,
+                        password,
+                    }))
+                }
+            }
+            Self::ConfigureSftpRemote => {
+                let host = histories.modal.host.current();
+                let port = histories.modal.port.current();
+                let username = histories.modal.username.current();
+                let password = histories.modal.password.current();
+
+                let Ok(port) = port.parse::<i32>() else { return None };
+                if host.is_empty() || username.is_empty() {
+                    None
+                } else {
+                    Some(Message::FinalizeRemote(Remote::Sftp {
                         id: Remote::generate_id(),
                         host,
                         port,
@@ -320,6 +420,33 @@ impl Modal {
                     }))
                 }
             }
+            Self::ConfigureS3Remote => {
+                let remote = Remote::S3 {
+                    id: Remote::generate_id(),
+                    bucket: histories.modal.bucket.current(),
+                    region: histories.modal.region.current(),
+                    endpoint: histories.modal.url.current(),
+                    access_key_id: histories.modal.username.current(),
+                    secret_access_key: histories.modal.password.current(),
+                };
+                remote.is_complete().then_some(Message::FinalizeRemote(remote))
+            }
+            Self::ConfigureB2Remote => {
+                let remote = Remote::B2 {
+                    id: Remote::generate_id(),
+                    bucket: histories.modal.bucket.current(),
+                    account: histories.modal.username.current(),
+                    key: histories.modal.password.current(),
+                };
+                remote.is_complete().then_some(Message::FinalizeRemote(remote))
+            }
+            Self::ConfigureLocalRemote => {
+                let remote = Remote::Local {
+                    id: Remote::generate_id(),
+                    path: histories.modal.folder.current(),
+                };
+                remote.is_complete().then_some(Message::FinalizeRemote(remote))
+            }
         }
     }
 
@@ -353,6 +480,10 @@ impl Modal {
                     )]
                 }
             }
+            Self::AuthorizingCloud { .. } => vec![button::negative(
+                TRANSLATOR.cancel_button(),
+                Some(Message::CancelCloudAuthorization),
+            )],
             Self::Error { .. }
             | Self::Errors { .. }
             | Self::Exiting
@@ -364,8 +495,14 @@ impl Modal {
             | Self::AppUpdate { .. }
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
+            | Self::ConfigureSftpRemote
             | Self::ConfigureWebDavRemote { .. }
-            | Self::GameNotes { .. } => vec![],
+            | Self::ConfigureS3Remote
+            | Self::ConfigureB2Remote
+            | Self::ConfigureLocalRemote
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. }
+            | Self::ConfirmDeleteBackup { .. } => vec![],
         }
     }
 
@@ -386,7 +523,9 @@ impl Modal {
             | Self::NoMissingRoots
             | Self::ConfirmAddMissingRoots(_)
             | Self::AppUpdate { .. }
-            | Self::UpdatingManifest => (),
+            | Self::UpdatingManifest
+            | Self::AuthorizingCloud { .. }
+            | Self::ConfirmDeleteBackup { .. } => (),
             Self::BackupValidation { games } => {
                 for game in games.iter().sorted() {
                     col = col.push(text(game))
@@ -431,11 +570,20 @@ impl Modal {
                         );
                 }
             }
-            Self::ConfigureFtpRemote { .. } | Self::ConfigureSmbRemote { .. } => {
+            Self::ConfigureFtpRemote { .. } | Self::ConfigureSftpRemote => {
+                col = col
+                    .width(500)
+                    .push(ModalField::view(ModalInputKind::Host, histories))
+                    .push(ModalField::view(ModalInputKind::Port, histories))
+                    .push(ModalField::view(ModalInputKind::Username, histories))
+                    .push(ModalField::view(ModalInputKind::Password, histories));
+            }
+            Self::ConfigureSmbRemote { .. } => {
                 col = col
                     .width(500)
                     .push(ModalField::view(ModalInputKind::Host, histories))
                     .push(ModalField::view(ModalInputKind::Port, histories))
+                    .push(ModalField::view(ModalInputKind::Share, histories))
                     .push(ModalField::view(ModalInputKind::Username, histories))
                     .push(ModalField::view(ModalInputKind::Password, histories));
             }
@@ -452,6 +600,25 @@ impl Modal {
                         ModalField::WebDavProvider,
                     ));
             }
+            Self::ConfigureS3Remote => {
+                col = col
+                    .width(500)
+                    .push(ModalField::view(ModalInputKind::Bucket, histories))
+                    .push(ModalField::view(ModalInputKind::Region, histories))
+                    .push(ModalField::view(ModalInputKind::Url, histories))
+                    .push(ModalField::view(ModalInputKind::Username, histories))
+                    .push(ModalField::view(ModalInputKind::Password, histories));
+            }
+            Self::ConfigureB2Remote => {
+                col = col
+                    .width(500)
+                    .push(ModalField::view(ModalInputKind::Bucket, histories))
+                    .push(ModalField::view(ModalInputKind::Username, histories))
+                    .push(ModalField::view(ModalInputKind::Password, histories));
+            }
+            Self::ConfigureLocalRemote => {
+                col = col.width(500).push(ModalField::view(ModalInputKind::Folder, histories));
+            }
             Self::GameNotes { notes, .. } => {
                 col = notes.iter().fold(col, |parent, note| {
                     parent.push(
@@ -466,6 +633,33 @@ impl Modal {
                     )
                 });
             }
+            Self::PruningPreview { games } => {
+                col = games.iter().fold(col, |parent, game| {
+                    parent.push(
+                        Column::new()
+                            .width(Length::Fill)
+                            .spacing(5)
+                            .push(text(config.display_name(&game.name)).size(16))
+                            .push(game.pruned.iter().fold(Column::new().spacing(5), |parent, backup| {
+                                parent.push(
+                                    Row::new()
+                                        .spacing(10)
+                                        .push(Icon::Delete.text_small())
+                                        .push(text(backup.label())),
+                                )
+                            }))
+                            .push(game.locked.iter().fold(Column::new().spacing(5), |parent, backup| {
+                                parent.push(
+                                    Row::new()
+                                        .spacing(10)
+                                        .push(Icon::Lock.text_small())
+                                        .push(text(backup.label()))
+                                        .push(text(format!("({})", TRANSLATOR.retained_label()))),
+                                )
+                            })),
+                    )
+                });
+            }
         }
 
         col
@@ -496,7 +690,7 @@ impl Modal {
                 .push(
                     Container::new(
                         match self.variant() {
-                            ModalVariant::Loading => Row::new(),
+                            ModalVariant::Loading => Row::with_children(self.extra_controls()),
                             ModalVariant::Info => Row::with_children(self.extra_controls()).push(positive_button),
                             ModalVariant::Confirm => Row::with_children(self.extra_controls())
                                 .push_if(!matches!(self, Modal::BackupValidation { .. }), || positive_button)
@@ -533,8 +727,15 @@ impl Modal {
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
+            | Self::ConfigureSftpRemote
             | Self::ConfigureWebDavRemote { .. }
-            | Self::GameNotes { .. } => (),
+            | Self::ConfigureS3Remote
+            | Self::ConfigureB2Remote
+            | Self::ConfigureLocalRemote
+            | Self::AuthorizingCloud { .. }
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. }
+            | Self::ConfirmDeleteBackup { .. } => (),
         }
     }
 
@@ -571,8 +772,15 @@ impl Modal {
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
+            | Self::ConfigureSftpRemote
             | Self::ConfigureWebDavRemote { .. }
-            | Self::GameNotes { .. } => (),
+            | Self::ConfigureS3Remote
+            | Self::ConfigureB2Remote
+            | Self::ConfigureLocalRemote
+            | Self::AuthorizingCloud { .. }
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. }
+            | Self::ConfirmDeleteBackup { .. } => (),
         }
     }
 
@@ -593,8 +801,15 @@ impl Modal {
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
+            | Self::ConfigureSftpRemote
             | Self::ConfigureWebDavRemote { .. }
-            | Self::GameNotes { .. } => (),
+            | Self::ConfigureS3Remote
+            | Self::ConfigureB2Remote
+            | Self::ConfigureLocalRemote
+            | Self::AuthorizingCloud { .. }
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. }
+            | Self::ConfirmDeleteBackup { .. } => (),
         }
     }
 
@@ -613,8 +828,15 @@ impl Modal {
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
+            | Self::ConfigureSftpRemote
             | Self::ConfigureWebDavRemote { .. }
-            | Self::GameNotes { .. } => false,
+            | Self::ConfigureS3Remote
+            | Self::ConfigureB2Remote
+            | Self::ConfigureLocalRemote
+            | Self::AuthorizingCloud { .. }
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. }
+            | Self::ConfirmDeleteBackup { .. } => false,
         }
     }
 
@@ -633,8 +855,15 @@ impl Modal {
             | Self::UpdatingManifest
             | Self::ConfigureFtpRemote { .. }
             | Self::ConfigureSmbRemote { .. }
+            | Self::ConfigureSftpRemote
             | Self::ConfigureWebDavRemote { .. }
-            | Self::GameNotes { .. } => 2,
+            | Self::ConfigureS3Remote
+            | Self::ConfigureB2Remote
+            | Self::ConfigureLocalRemote
+            | Self::AuthorizingCloud { .. }
+            | Self::GameNotes { .. }
+            | Self::PruningPreview { .. }
+            | Self::ConfirmDeleteBackup { .. } => 2,
         }
     }
 
@@ -20,7 +20,7 @@ use crate::{
     prelude::{AVAILABLE_PARALELLISM, STEAM_DECK},
     resource::{
         cache::Cache,
-        config::{self, BackupFormat, CloudFilter, Config, SortKey, Theme, ZipCompression},
+        config::{self, BackupFormat, CloudFilter, Config, ConflictPolicy, SortKey, Theme, ZipCompression},
         manifest::{Manifest, Store},
     },
     scan::{DuplicateDetector, Duplication, OperationStatus, ScanKind},
@@ -28,6 +28,8 @@ use crate::{
 
 const RCLONE_URL: &str = "https://rclone.org/downloads";
 const RELEASE_URL: &str = "https://github.com/mtkennerly/ludusavi/releases";
+/// File size limits are edited in mebibytes.
+const MIB: u64 = 1024 * 1024;
 
 fn template(content: Column) -> Element {
     Container::new(content.spacing(15).align_x(Alignment::Center))
@@ -78,6 +80,7 @@ impl Backup {
     pub fn view(
         &self,
         config: &Config,
+        cache: &Cache,
         manifest: &Manifest,
         operation: &Operation,
         histories: &TextHistories,
@@ -136,6 +139,7 @@ impl Backup {
             .push(self.log.view(
                 Self::SCAN_KIND,
                 config,
+                cache,
                 manifest,
                 &self.duplicate_detector,
                 duplicatees.as_ref(),
@@ -148,6 +152,59 @@ impl Backup {
     }
 }
 
+/// A compact list of the games whose saves changed since their last backup,
+/// based on the backup screen's latest scan.
+pub fn changes<'a>(config: &Config, log: &GameList, operation: &Operation) -> Element<'a> {
+    let games = log.changed_games();
+
+    let content = Column::new()
+        .push(
+            Row::new()
+                .padding([0, 20])
+                .spacing(20)
+                .align_y(Alignment::Center)
+                .push(button::backup_preview(operation, false))
+                .push(text(TRANSLATOR.changed_games_label(games.len()))),
+        )
+        .push({
+            let content = games.iter().fold(
+                Column::new()
+                    .width(Length::Fill)
+                    .padding(padding::bottom(5).left(15).right(15))
+                    .spacing(5),
+                |parent, (name, changes)| {
+                    parent.push(
+                        Container::new(
+                            Row::new()
+                                .padding(padding::left(5).right(5))
+                                .spacing(15)
+                                .align_y(Alignment::Center)
+                                .push(
+                                    Button::new(text(config.display_name(name).to_string()))
+                                        .on_press(Message::ShowChangedGame { name: name.clone() })
+                                        .class(style::Button::GameListEntryTitle)
+                                        .padding(2)
+                                        .width(Length::Fill),
+                                )
+                                .push_if(changes.new > 0, || Badge::new_entry_with_count(changes.new).view())
+                                .push_if(changes.different > 0, || {
+                                    Badge::changed_entry_with_count(changes.different).view()
+                                })
+                                .push_if(changes.removed > 0, || {
+                                    Badge::removed_entry_with_count(changes.removed).view()
+                                }),
+                        )
+                        .padding(5)
+                        .class(style::Container::GameListEntry),
+                    )
+                },
+            );
+            ScrollSubject::Changes.into_widget(content)
+        });
+
+    template(content)
+}
+
 #[derive(Default)]
 pub struct Restore {
     pub log: GameList,
@@ -167,6 +224,7 @@ impl Restore {
     pub fn view(
         &self,
         config: &Config,
+        cache: &Cache,
         manifest: &Manifest,
         operation: &Operation,
         histories: &TextHistories,
@@ -195,6 +253,7 @@ impl Restore {
                         self.log.is_filtered(),
                     ))
                     .push(button::validate_backups(operation))
+                    .push(button::scrub_backups(operation))
                     .push(button::filter(self.log.search.show)),
             )
             .push(make_status_row(
@@ -221,11 +280,22 @@ impl Restore {
                         pick_list(SortKey::ALL, Some(sort.key), Message::config(config::Event::SortKey))
                             .class(style::PickList::Primary),
                     )
-                    .push(button::sort_order(sort.reversed)),
+                    .push(button::sort_order(sort.reversed))
+                    .push("|")
+                    .push(text(TRANSLATOR.conflict_policy_label()))
+                    .push(
+                        pick_list(
+                            ConflictPolicy::ALL,
+                            Some(config.restore.conflict_policy),
+                            Message::config(config::Event::ConflictPolicy),
+                        )
+                        .class(style::PickList::Primary),
+                    ),
             )
             .push(self.log.view(
                 Self::SCAN_KIND,
                 config,
+                cache,
                 manifest,
                 &self.duplicate_detector,
                 duplicatees.as_ref(),
@@ -312,6 +382,10 @@ pub fn other<'a>(
     let is_rclone_valid = config.apps.rclone.is_valid();
     let is_cloud_configured = config.cloud.remote.is_some();
     let is_cloud_path_valid = crate::cloud::validate_cloud_path(&config.cloud.path).is_ok();
+    let is_cloud_bandwidth_limit_valid = match &config.cloud.bandwidth_limit {
+        Some(limit) => crate::cloud::validate_bandwidth_limit(limit).is_ok(),
+        None => true,
+    };
 
     let content = Column::new()
         .push_if(*STEAM_DECK, || {
@@ -367,6 +441,11 @@ pub fn other<'a>(
                         ))
                         .push(button::open_url_icon(RELEASE_URL.to_string())),
                 )
+                .push(checkbox(
+                    TRANSLATOR.show_tray_icon(),
+                    config.tray.enabled,
+                    Message::config(config::Event::ShowTray),
+                ))
                 .push(
                     Column::new().spacing(5).push(text(TRANSLATOR.scan_field())).push(
                         Container::new(
@@ -403,6 +482,56 @@ pub fn other<'a>(
                                     )
                                     .class(style::Checkbox),
                                 )
+                                .push(
+                                    Column::new()
+                                        .spacing(5)
+                                        .push(checkbox(
+                                            TRANSLATOR.explanation_for_max_file_size(),
+                                            config.backup.filter.max_file_size.is_some(),
+                                            Message::config(|enabled| {
+                                                config::Event::MaxFileSize(enabled.then_some(100 * MIB))
+                                            }),
+                                        ))
+                                        .push_maybe(config.backup.filter.max_file_size.map(|size| {
+                                            Container::new(number_input(
+                                                (size / MIB) as i32,
+                                                TRANSLATOR.file_size_limit_label(),
+                                                1..=i32::MAX,
+                                                Message::config(|x| config::Event::MaxFileSize(Some(x as u64 * MIB))),
+                                            ))
+                                            .padding(padding::left(35))
+                                        })),
+                                )
+                                .push(
+                                    Column::new()
+                                        .spacing(5)
+                                        .push(checkbox(
+                                            TRANSLATOR.explanation_for_min_file_size(),
+                                            config.backup.filter.min_file_size.is_some(),
+                                            Message::config(|enabled| {
+                                                config::Event::MinFileSize(enabled.then_some(MIB))
+                                            }),
+                                        ))
+                                        .push_maybe(config.backup.filter.min_file_size.map(|size| {
+                                            Container::new(number_input(
+                                                (size / MIB) as i32,
+                                                TRANSLATOR.file_size_limit_label(),
+                                                1..=i32::MAX,
+                                                Message::config(|x| config::Event::MinFileSize(Some(x as u64 * MIB))),
+                                            ))
+                                            .padding(padding::left(35))
+                                        })),
+                                )
+                                .push(checkbox(
+                                    TRANSLATOR.explanation_for_exclude_empty_files(),
+                                    config.backup.filter.exclude_empty_files,
+                                    Message::config(config::Event::ExcludeEmptyFiles),
+                                ))
+                                .push(checkbox(
+                                    TRANSLATOR.explanation_for_exclude_empty_dirs(),
+                                    config.backup.filter.exclude_empty_dirs,
+                                    Message::config(config::Event::ExcludeEmptyDirs),
+                                ))
                                 .push(checkbox(
                                     TRANSLATOR.show_disabled_games(),
                                     config.scan.show_deselected_games,
@@ -532,7 +661,11 @@ pub fn other<'a>(
                                                 0..=255,
                                                 Message::config(|x| config::Event::DiffRetention(x as u8)),
                                             )
-                                        }),
+                                        })
+                                        .push(button::primary(
+                                            TRANSLATOR.preview_pruning_button(),
+                                            operation.idle().then_some(Message::PreviewPruning),
+                                        )),
                                 )
                                 .push(
                                     Row::new()
@@ -582,6 +715,11 @@ pub fn other<'a>(
                                     TRANSLATOR.skip_unconstructive_backups(),
                                     config.backup.only_constructive,
                                     Message::config(config::Event::OnlyConstructiveBackups),
+                                )))
+                                .push(Row::new().spacing(5).align_y(Alignment::Center).push(checkbox(
+                                    TRANSLATOR.follow_symlinks(),
+                                    config.backup.follow_symlinks,
+                                    Message::config(config::Event::FollowSymlinks),
                                 ))),
                         )
                         .class(style::Container::GameListEntry),
@@ -669,22 +807,55 @@ pub fn other<'a>(
                                                     Icon::Error.text().width(Length::Shrink).class(style::Text::Failure)
                                                 })
                                         })
-                                        .push_if(is_cloud_configured && is_cloud_path_valid, || {
+                                        .push_if(choice != RemoteChoice::None, || {
                                             Row::new()
                                                 .spacing(20)
                                                 .align_y(Alignment::Center)
-                                                .push(button::upload(operation))
-                                                .push(button::download(operation))
                                                 .push(checkbox(
-                                                    TRANSLATOR.synchronize_automatically(),
-                                                    config.cloud.synchronize,
-                                                    Message::config(|_| config::Event::ToggleCloudSynchronize),
+                                                    TRANSLATOR.limit_cloud_transfers(),
+                                                    config.cloud.transfers.is_some(),
+                                                    Message::config(|enabled| {
+                                                        config::Event::CloudTransfers(enabled.then_some(4))
+                                                    }),
                                                 ))
+                                                .push_maybe(config.cloud.transfers.map(|transfers| {
+                                                    number_input(
+                                                        transfers as i32,
+                                                        TRANSLATOR.transfers_label(),
+                                                        1..=64,
+                                                        Message::config(|x| {
+                                                            config::Event::CloudTransfers(Some(x as usize))
+                                                        }),
+                                                    )
+                                                }))
+                                                .push(text(TRANSLATOR.bandwidth_limit_label()))
+                                                .push(histories.input(UndoSubject::CloudBandwidthLimit))
                                         })
+                                        .push_if(
+                                            is_cloud_configured
+                                                && is_cloud_path_valid
+                                                && is_cloud_bandwidth_limit_valid,
+                                            || {
+                                                Row::new()
+                                                    .spacing(20)
+                                                    .align_y(Alignment::Center)
+                                                    .push(button::upload(operation))
+                                                    .push(button::download(operation))
+                                                    .push(checkbox(
+                                                        TRANSLATOR.synchronize_automatically(),
+                                                        config.cloud.synchronize,
+                                                        Message::config(|_| config::Event::ToggleCloudSynchronize),
+                                                    ))
+                                            },
+                                        )
                                         .push_if(!is_cloud_configured, || text(TRANSLATOR.cloud_not_configured()))
                                         .push_if(!is_cloud_path_valid, || {
                                             text(TRANSLATOR.prefix_warning(&TRANSLATOR.cloud_path_invalid()))
                                                 .class(style::Text::Failure)
+                                        })
+                                        .push_if(!is_cloud_bandwidth_limit_valid, || {
+                                            text(TRANSLATOR.prefix_warning(&TRANSLATOR.cloud_bandwidth_limit_invalid()))
+                                                .class(style::Text::Failure)
                                         });
                                 } else {
                                     column = column
@@ -3,6 +3,7 @@ use iced::{padding, Alignment};
 
 use crate::{
     gui::{
+        badge::Badge,
         button,
         common::{Message, Screen, UndoSubject},
         shortcuts::TextHistories,
@@ -13,7 +14,7 @@ use crate::{
     resource::{config::CustomGame, manifest::Manifest},
     scan::{
         game_filter::{self, FilterKind},
-        Duplication, ScanInfo,
+        BackupInfo, Duplication, ScanInfo,
     },
 };
 
@@ -32,6 +33,7 @@ pub struct FilterComponent {
     pub enablement: Filter<game_filter::Enablement>,
     pub change: Filter<game_filter::Change>,
     pub manifest: Filter<game_filter::Manifest>,
+    pub failure: Filter<game_filter::Failure>,
 }
 
 fn template<'a, T: 'static + Default + Copy + Eq + PartialEq + ToString>(
@@ -97,6 +99,7 @@ impl FilterComponent {
         self.enablement.active = false;
         self.change.active = false;
         self.manifest.active = false;
+        self.failure.active = false;
     }
 
     pub fn is_dirty(&self) -> bool {
@@ -106,11 +109,13 @@ impl FilterComponent {
             || self.enablement.active
             || self.change.active
             || self.manifest.active
+            || self.failure.active
     }
 
     pub fn qualifies(
         &self,
         scan: &ScanInfo,
+        backup_info: Option<&BackupInfo>,
         manifest: &Manifest,
         enabled: bool,
         customized: bool,
@@ -134,8 +139,9 @@ impl FilterComponent {
                 .manifest
                 .choice
                 .qualifies(manifest.0.get(&scan.game_name), customized);
+        let failure = !self.failure.active || self.failure.choice.qualifies(backup_info);
 
-        fuzzy && unique && complete && changed && enable && manifest
+        fuzzy && unique && complete && changed && enable && manifest && failure
     }
 
     pub fn toggle_filter(&mut self, filter: FilterKind, enabled: bool) {
@@ -145,6 +151,7 @@ impl FilterComponent {
             FilterKind::Enablement => self.enablement.active = enabled,
             FilterKind::Change => self.change.active = enabled,
             FilterKind::Manifest => self.manifest.active = enabled,
+            FilterKind::Failure => self.failure.active = enabled,
         }
     }
 
@@ -154,6 +161,7 @@ impl FilterComponent {
         histories: &TextHistories,
         show_deselected_games: bool,
         manifests: Vec<game_filter::Manifest>,
+        failed_games: usize,
     ) -> Option<Element> {
         if !self.show {
             return None;
@@ -201,6 +209,22 @@ impl FilterComponent {
                             event: game_filter::Event::EditedFilterChange(value),
                         },
                     ))
+                    .push(
+                        Row::new()
+                            .spacing(10)
+                            .align_y(Alignment::Center)
+                            .push(template(
+                                &self.failure,
+                                FilterKind::Failure,
+                                game_filter::Failure::ALL,
+                                move |value| Message::Filter {
+                                    event: game_filter::Event::EditedFilterFailure(value),
+                                },
+                            ))
+                            .push_if(failed_games > 0, || {
+                                Badge::new(&TRANSLATOR.failed_games_badge(failed_games)).view()
+                            }),
+                    )
                     .push_if(show_deselected_games, || {
                         template(
                             &self.enablement,
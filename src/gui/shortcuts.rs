@@ -77,6 +77,15 @@ impl Shortcut {
             }
         }
     }
+
+    pub fn apply_to_option_string_field(&self, config: &mut Option<String>, history: &mut TextHistory) {
+        let value = match self {
+            Shortcut::Undo => history.undo(),
+            Shortcut::Redo => history.redo(),
+        };
+
+        *config = (!value.trim().is_empty()).then_some(value);
+    }
 }
 
 impl From<crate::gui::undoable::Action> for Shortcut {
@@ -209,6 +218,8 @@ pub struct CustomGameHistory {
     pub files: Vec<TextHistory>,
     pub registry: Vec<TextHistory>,
     pub install_dir: Vec<TextHistory>,
+    pub include: Vec<TextHistory>,
+    pub exclusions: Vec<TextHistory>,
 }
 
 #[derive(Default)]
@@ -218,6 +229,10 @@ pub struct ModalHistory {
     pub port: TextHistory,
     pub username: TextHistory,
     pub password: TextHistory,
+    pub bucket: TextHistory,
+    pub region: TextHistory,
+    pub share: TextHistory,
+    pub folder: TextHistory,
 }
 
 #[derive(Default)]
@@ -237,6 +252,7 @@ pub struct TextHistories {
     pub rclone_arguments: TextHistory,
     pub cloud_remote_id: TextHistory,
     pub cloud_path: TextHistory,
+    pub cloud_bandwidth_limit: TextHistory,
     pub modal: ModalHistory,
     pub backup_comments: HashMap<String, TextHistory>,
 }
@@ -251,6 +267,7 @@ impl TextHistories {
             rclone_executable: TextHistory::path(&config.apps.rclone.path),
             rclone_arguments: TextHistory::raw(&config.apps.rclone.arguments),
             cloud_path: TextHistory::raw(&config.cloud.path),
+            cloud_bandwidth_limit: TextHistory::raw(config.cloud.bandwidth_limit.as_deref().unwrap_or_default()),
             ..Default::default()
         };
 
@@ -273,7 +290,7 @@ impl TextHistories {
         }
 
         for x in &config.custom_games {
-            histories.add_custom_game(x);
+            histories.add_custom_game(x, config.backup.exclusions_for(&x.name));
         }
 
         for x in &config.backup.filter.ignored_paths {
@@ -292,13 +309,15 @@ impl TextHistories {
         histories
     }
 
-    pub fn add_custom_game(&mut self, game: &CustomGame) {
+    pub fn add_custom_game(&mut self, game: &CustomGame, exclusions: &[String]) {
         let history = CustomGameHistory {
             name: TextHistory::raw(&game.name),
             alias: TextHistory::raw(&game.alias.clone().unwrap_or_default()),
             files: game.files.iter().map(|x| TextHistory::raw(x)).collect(),
             registry: game.registry.iter().map(|x| TextHistory::raw(x)).collect(),
             install_dir: game.install_dir.iter().map(|x| TextHistory::raw(x)).collect(),
+            include: game.include.iter().map(|x| TextHistory::raw(x)).collect(),
+            exclusions: exclusions.iter().map(|x| TextHistory::raw(x)).collect(),
         };
         self.custom_games.push(history);
     }
@@ -309,6 +328,10 @@ impl TextHistories {
         self.modal.port.clear();
         self.modal.username.clear();
         self.modal.password.clear();
+        self.modal.bucket.clear();
+        self.modal.region.clear();
+        self.modal.share.clear();
+        self.modal.folder.clear();
     }
 
     pub fn input<'a>(&self, subject: UndoSubject) -> Element<'a> {
@@ -348,6 +371,16 @@ impl TextHistories {
                 .get(*i)
                 .and_then(|x| x.install_dir.get(*j).map(|y| y.current()))
                 .unwrap_or_default(),
+            UndoSubject::CustomGameInclude(i, j) => self
+                .custom_games
+                .get(*i)
+                .and_then(|x| x.include.get(*j).map(|y| y.current()))
+                .unwrap_or_default(),
+            UndoSubject::CustomGameExclusion(i, j) => self
+                .custom_games
+                .get(*i)
+                .and_then(|x| x.exclusions.get(*j).map(|y| y.current()))
+                .unwrap_or_default(),
             UndoSubject::BackupFilterIgnoredPath(i) => self
                 .backup_filter_ignored_paths
                 .get(*i)
@@ -362,12 +395,17 @@ impl TextHistories {
             UndoSubject::RcloneArguments => self.rclone_arguments.current(),
             UndoSubject::CloudRemoteId => self.cloud_remote_id.current(),
             UndoSubject::CloudPath => self.cloud_path.current(),
+            UndoSubject::CloudBandwidthLimit => self.cloud_bandwidth_limit.current(),
             UndoSubject::ModalField(field) => match field {
                 ModalInputKind::Url => self.modal.url.current(),
                 ModalInputKind::Host => self.modal.host.current(),
                 ModalInputKind::Port => self.modal.port.current(),
                 ModalInputKind::Username => self.modal.username.current(),
                 ModalInputKind::Password => self.modal.password.current(),
+                ModalInputKind::Bucket => self.modal.bucket.current(),
+                ModalInputKind::Region => self.modal.region.current(),
+                ModalInputKind::Share => self.modal.share.current(),
+                ModalInputKind::Folder => self.modal.folder.current(),
             },
             UndoSubject::BackupComment(game) => self.backup_comments.get(game).map(|x| x.current()).unwrap_or_default(),
         };
@@ -414,6 +452,12 @@ impl TextHistories {
             UndoSubject::CustomGameInstallDir(i, j) => Box::new(Message::config(move |value| {
                 config::Event::CustomGameInstallDir(i, EditAction::Change(j, value))
             })),
+            UndoSubject::CustomGameInclude(i, j) => Box::new(Message::config(move |value| {
+                config::Event::CustomGameInclude(i, EditAction::Change(j, value))
+            })),
+            UndoSubject::CustomGameExclusion(i, j) => Box::new(Message::config(move |value| {
+                config::Event::CustomGameExclusion(i, EditAction::Change(j, value))
+            })),
             UndoSubject::BackupFilterIgnoredPath(i) => Box::new(Message::config(move |value| {
                 config::Event::BackupFilterIgnoredPath(EditAction::Change(i, value))
             })),
@@ -424,6 +468,7 @@ impl TextHistories {
             UndoSubject::RcloneArguments => Box::new(Message::config(config::Event::RcloneArguments)),
             UndoSubject::CloudRemoteId => Box::new(Message::config(config::Event::CloudRemoteId)),
             UndoSubject::CloudPath => Box::new(Message::config(config::Event::CloudPath)),
+            UndoSubject::CloudBandwidthLimit => Box::new(Message::config(config::Event::CloudBandwidthLimit)),
             UndoSubject::ModalField(field) => Box::new(move |value| {
                 Message::EditedModalField(match field {
                     ModalInputKind::Url => ModalField::Url(value),
@@ -431,6 +476,10 @@ impl TextHistories {
                     ModalInputKind::Port => ModalField::Port(value),
                     ModalInputKind::Username => ModalField::Username(value),
                     ModalInputKind::Password => ModalField::Password(value),
+                    ModalInputKind::Bucket => ModalField::Bucket(value),
+                    ModalInputKind::Region => ModalField::Region(value),
+                    ModalInputKind::Share => ModalField::Share(value),
+                    ModalInputKind::Folder => ModalField::Folder(value),
                 })
             }),
             UndoSubject::BackupComment(game) => Box::new(move |comment| Message::EditedBackupComment {
@@ -455,12 +504,14 @@ impl TextHistories {
             UndoSubject::CustomGameFile(_, _) => "".to_string(),
             UndoSubject::CustomGameRegistry(_, _) => "".to_string(),
             UndoSubject::CustomGameInstallDir(_, _) => "".to_string(),
+            UndoSubject::CustomGameInclude(_, _) => TRANSLATOR.custom_game_name_placeholder(),
             UndoSubject::BackupFilterIgnoredPath(_) => "".to_string(),
             UndoSubject::BackupFilterIgnoredRegistry(_) => "".to_string(),
             UndoSubject::RcloneExecutable => TRANSLATOR.executable_label(),
             UndoSubject::RcloneArguments => TRANSLATOR.arguments_label(),
             UndoSubject::CloudRemoteId => "".to_string(),
             UndoSubject::CloudPath => "".to_string(),
+            UndoSubject::CloudBandwidthLimit => "1M".to_string(),
             UndoSubject::ModalField(_) => "".to_string(),
             UndoSubject::BackupComment(_) => TRANSLATOR.comment_label(),
         };
@@ -476,9 +527,12 @@ impl TextHistories {
             | UndoSubject::CustomGameInstallDir(_, _)
             | UndoSubject::BackupFilterIgnoredPath(_)
             | UndoSubject::RcloneExecutable => (!path_appears_valid(&current)).then_some(ERROR_ICON),
-            UndoSubject::CustomGameName(_) | UndoSubject::CustomGameAlias(_) => {
+            UndoSubject::CustomGameName(_) | UndoSubject::CustomGameAlias(_) | UndoSubject::CustomGameInclude(_, _) => {
                 (current.trim() != current).then_some(ERROR_ICON)
             }
+            UndoSubject::CloudBandwidthLimit => (!current.trim().is_empty()
+                && crate::cloud::validate_bandwidth_limit(&current).is_err())
+            .then_some(ERROR_ICON),
             UndoSubject::SecondaryManifest(_)
             | UndoSubject::BackupSearchGameName
             | UndoSubject::RestoreSearchGameName
@@ -0,0 +1,82 @@
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuItem},
+    TrayIcon, TrayIconBuilder,
+};
+
+use crate::lang::TRANSLATOR;
+
+const BACKUP_ALL: &str = "backup-all";
+const OPEN: &str = "open";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    BackupAll,
+    Open,
+}
+
+/// System tray icon with a small menu for common actions.
+/// On Linux, this uses the StatusNotifierItem D-Bus backend,
+/// which runs on its own worker thread and doesn't need a GTK event loop.
+pub struct Tray {
+    icon: TrayIcon,
+}
+
+impl Tray {
+    pub fn new() -> Option<Self> {
+        build().map(|icon| Self { icon })
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        if let Err(e) = self.icon.set_visible(visible) {
+            log::error!("Unable to change tray icon visibility: {e:?}");
+        }
+    }
+}
+
+fn build() -> Option<TrayIcon> {
+    let menu = Menu::new();
+    let items = [
+        MenuItem::with_id(BACKUP_ALL, TRANSLATOR.tray_backup_all(), true, None),
+        MenuItem::with_id(OPEN, TRANSLATOR.tray_open(), true, None),
+    ];
+    for item in &items {
+        if let Err(e) = menu.append(item) {
+            log::error!("Unable to add tray menu item: {e:?}");
+            return None;
+        }
+    }
+
+    let mut builder = TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_tooltip(TRANSLATOR.app_name());
+    if let Some(icon) = icon() {
+        builder = builder.with_icon(icon);
+    }
+
+    match builder.build() {
+        Ok(icon) => Some(icon),
+        Err(e) => {
+            log::error!("Unable to create tray icon: {e:?}");
+            None
+        }
+    }
+}
+
+fn icon() -> Option<tray_icon::Icon> {
+    let buffer = image::load_from_memory(include_bytes!("../../assets/icon.png"))
+        .ok()?
+        .to_rgba8();
+    let width = buffer.width();
+    let height = buffer.height();
+    tray_icon::Icon::from_rgba(buffer.into_raw(), width, height).ok()
+}
+
+/// Get the next action chosen from the tray menu, if any.
+pub fn poll() -> Option<Event> {
+    let event = MenuEvent::receiver().try_recv().ok()?;
+    match event.id.0.as_str() {
+        BACKUP_ALL => Some(Event::BackupAll),
+        OPEN => Some(Event::Open),
+        _ => None,
+    }
+}
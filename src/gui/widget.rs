@@ -1,4 +1,4 @@
-use std::ops::RangeInclusive;
+use std::{collections::HashMap, ops::RangeInclusive};
 
 use iced::{widget as w, Alignment, Length};
 
@@ -9,6 +9,7 @@ use crate::{
         style::{self, Theme},
     },
     lang::TRANSLATOR,
+    scan::layout::BackupHistory,
 };
 
 pub type Renderer = iced::Renderer;
@@ -64,6 +65,8 @@ pub mod id {
 
     pub static BACKUP_SCROLL: LazyLock<iced::widget::scrollable::Id> =
         LazyLock::new(iced::widget::scrollable::Id::unique);
+    pub static CHANGES_SCROLL: LazyLock<iced::widget::scrollable::Id> =
+        LazyLock::new(iced::widget::scrollable::Id::unique);
     pub static RESTORE_SCROLL: LazyLock<iced::widget::scrollable::Id> =
         LazyLock::new(iced::widget::scrollable::Id::unique);
     pub static CUSTOM_GAMES_SCROLL: LazyLock<iced::widget::scrollable::Id> =
@@ -84,6 +87,10 @@ pub mod id {
         (*BACKUP_SCROLL).clone()
     }
 
+    pub fn changes_scroll() -> iced::widget::scrollable::Id {
+        (*CHANGES_SCROLL).clone()
+    }
+
     pub fn restore_scroll() -> iced::widget::scrollable::Id {
         (*RESTORE_SCROLL).clone()
     }
@@ -148,6 +155,10 @@ pub struct Progress {
     prepared: bool,
     start_time: Option<chrono::DateTime<chrono::Utc>>,
     current_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Estimated cost of each game that has not finished yet.
+    pending_weights: HashMap<String, f64>,
+    /// Estimated cost of the games that have already finished.
+    done_weight: f64,
 }
 
 impl Progress {
@@ -161,6 +172,8 @@ impl Progress {
         self.prepared = false;
         self.start_time = None;
         self.current_time = None;
+        self.pending_weights.clear();
+        self.done_weight = 0.0;
     }
 
     pub fn start(&mut self) {
@@ -168,12 +181,86 @@ impl Progress {
         self.current = 0.0;
         self.prepared = false;
         self.start_time = Some(chrono::Utc::now());
+        self.pending_weights.clear();
+        self.done_weight = 0.0;
     }
 
     pub fn step(&mut self) {
         self.current += 1.0;
     }
 
+    pub fn step_game(&mut self, game: &str) {
+        self.step();
+        if let Some(weight) = self.pending_weights.remove(game) {
+            self.done_weight += weight;
+        }
+    }
+
+    /// Weight each game by how long its previous backup took,
+    /// so that the ETA accounts for known-slow games.
+    /// Games without a recorded duration are estimated from their previous size,
+    /// or from the average duration if they have never been backed up.
+    /// If no game has a recorded duration, the ETA falls back to assuming uniform steps.
+    pub fn set_history(&mut self, games: &[String], history: &HashMap<String, BackupHistory>) {
+        self.pending_weights.clear();
+        self.done_weight = 0.0;
+
+        let timed: Vec<_> = history
+            .values()
+            .filter_map(|x| x.duration_ms.map(|duration| (duration as f64, x.size as f64)))
+            .collect();
+        if timed.is_empty() {
+            return;
+        }
+
+        let average_duration = timed.iter().map(|(duration, _)| duration).sum::<f64>() / timed.len() as f64;
+        let timed_size: f64 = timed.iter().map(|(_, size)| size).sum();
+        let ms_per_byte =
+            (timed_size > 0.0).then(|| timed.iter().map(|(duration, _)| duration).sum::<f64>() / timed_size);
+
+        for game in games {
+            let weight = match history.get(game) {
+                Some(BackupHistory {
+                    duration_ms: Some(duration),
+                    ..
+                }) => *duration as f64,
+                Some(BackupHistory {
+                    size,
+                    duration_ms: None,
+                }) => match ms_per_byte {
+                    Some(rate) => *size as f64 * rate,
+                    None => average_duration,
+                },
+                None => average_duration,
+            };
+            // Even an instant backup still takes a step.
+            self.pending_weights.insert(game.clone(), weight.max(1.0));
+        }
+    }
+
+    /// Estimated time until the operation finishes.
+    fn remaining(&self) -> Option<chrono::Duration> {
+        let start = self.start_time.as_ref()?;
+        let current = self.current_time.as_ref()?;
+        if !self.prepared || self.current <= 0.0 || self.current >= self.max {
+            return None;
+        }
+        let elapsed = (*current - *start).num_milliseconds() as f64;
+
+        let remaining_steps = (self.max - self.current) as f64;
+        let fraction = if self.done_weight > 0.0 && !self.pending_weights.is_empty() {
+            // Some steps finish without naming their game (e.g., when filtered out),
+            // so extrapolate from the average pending weight rather than the sum.
+            let pending_weight: f64 = self.pending_weights.values().sum();
+            let average_pending = pending_weight / self.pending_weights.len() as f64;
+            average_pending * remaining_steps / self.done_weight
+        } else {
+            remaining_steps / self.current as f64
+        };
+
+        Some(chrono::Duration::milliseconds((elapsed * fraction) as i64))
+    }
+
     pub fn set(&mut self, current: f32, max: f32) {
         self.current = current;
         self.max = max;
@@ -214,7 +301,8 @@ impl Progress {
             } else {
                 TRANSLATOR.scan_label()
             }),
-            Operation::ValidateBackups { .. } => Some(TRANSLATOR.validate_button()),
+            Operation::ValidateBackups { scrub: false, .. } => Some(TRANSLATOR.validate_button()),
+            Operation::ValidateBackups { scrub: true, .. } => Some(TRANSLATOR.scrub_button()),
             Operation::Cloud { .. } => Some(TRANSLATOR.cloud_label()),
         };
 
@@ -229,6 +317,15 @@ impl Progress {
             )
         });
 
+        let remaining = self.remaining().map(|remaining| {
+            TRANSLATOR.time_remaining(&format!(
+                "{:0>2}:{:0>2}:{:0>2}",
+                remaining.num_hours(),
+                remaining.num_minutes() % 60,
+                remaining.num_seconds() % 60,
+            ))
+        });
+
         let count = if !self.prepared {
             None
         } else {
@@ -264,7 +361,8 @@ impl Progress {
                 .push_maybe(label.map(|x| text(x).size(text_size)))
                 .push_maybe(elapsed.map(|x| text(x).size(text_size)))
                 .push(ProgressBar::new(0.0..=self.max, self.current).height(8))
-                .push_maybe(count.map(|x| text(x).size(text_size))),
+                .push_maybe(count.map(|x| text(x).size(text_size)))
+                .push_maybe(remaining.map(|x| text(x).size(text_size))),
         )
         .height(16)
         .class(style::Container::ModalBackground)
@@ -377,3 +475,87 @@ pub mod operation {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::hash_map;
+
+    use super::*;
+    use crate::testing::s;
+
+    fn history() -> HashMap<String, BackupHistory> {
+        hash_map! {
+            s("timed"): BackupHistory { size: 10, duration_ms: Some(1000) },
+            s("untimed"): BackupHistory { size: 20, duration_ms: None },
+        }
+    }
+
+    fn progress(max: f32, elapsed_secs: i64) -> Progress {
+        let start = chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap()
+            .and_utc();
+        let mut progress = Progress::default();
+        progress.set_max(max);
+        progress.start_time = Some(start);
+        progress.current_time = Some(start + chrono::Duration::seconds(elapsed_secs));
+        progress
+    }
+
+    #[test]
+    fn can_weight_games_by_history() {
+        let mut progress = Progress::default();
+        progress.set_history(&[s("timed"), s("untimed"), s("new")], &history());
+        assert_eq!(
+            hash_map! {
+                // Recorded duration.
+                s("timed"): 1000.0,
+                // Size times the recorded rate of 100 ms per byte.
+                s("untimed"): 2000.0,
+                // Average recorded duration.
+                s("new"): 1000.0,
+            },
+            progress.pending_weights,
+        );
+    }
+
+    #[test]
+    fn can_skip_weights_without_any_recorded_duration() {
+        let mut progress = Progress::default();
+        progress.set_history(
+            &[s("untimed"), s("new")],
+            &hash_map! { s("untimed"): BackupHistory { size: 20, duration_ms: None } },
+        );
+        assert!(progress.pending_weights.is_empty());
+    }
+
+    #[test]
+    fn can_estimate_remaining_time_from_weights() {
+        let mut progress = progress(3.0, 10);
+        progress.set_history(&[s("timed"), s("untimed"), s("new")], &history());
+        progress.step_game("timed");
+
+        // The remaining games are expected to take 3 times as long as the finished one.
+        assert_eq!(Some(chrono::Duration::seconds(30)), progress.remaining());
+    }
+
+    #[test]
+    fn can_estimate_remaining_time_from_uniform_steps() {
+        let mut progress = progress(4.0, 10);
+        progress.step_game("new");
+
+        assert_eq!(Some(chrono::Duration::seconds(30)), progress.remaining());
+    }
+
+    #[test]
+    fn cannot_estimate_remaining_time_before_first_step_or_after_last() {
+        let mut progress = progress(2.0, 10);
+        assert_eq!(None, progress.remaining());
+
+        progress.step();
+        progress.step();
+        assert_eq!(None, progress.remaining());
+    }
+}
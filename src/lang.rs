@@ -7,12 +7,14 @@ use regex::Regex;
 use unic_langid::LanguageIdentifier;
 
 use crate::{
-    prelude::{CommandError, Error, StrictPath, VARIANT},
+    prelude::{CommandError, Error, StrictPath, ENV_ZIP_PASSWORD, VARIANT},
     resource::{
-        config::{BackupFormat, CustomGameKind, RedirectKind, Root, SortKey, Theme, ZipCompression},
-        manifest::Store,
+        config::{BackupFormat, ConflictPolicy, CustomGameKind, RedirectKind, Root, SortKey, Theme, ZipCompression},
+        manifest::{Os, Store},
+    },
+    scan::{
+        game_filter, BackupError, FileExclusion, OperationStatus, OperationStepDecision, OperationWarning, ScanChange,
     },
-    scan::{game_filter, BackupError, OperationStatus, OperationStepDecision, ScanChange},
 };
 
 const PATH: &str = "path";
@@ -30,6 +32,20 @@ const MESSAGE: &str = "message";
 const APP: &str = "app";
 const GAME: &str = "game";
 const VERSION: &str = "version";
+const NAME: &str = "name";
+const KEY: &str = "key";
+const PARENT: &str = "parent";
+const CHILD: &str = "child";
+const SIZE: &str = "size";
+const REDIRECT: &str = "redirect";
+const PATTERN: &str = "pattern";
+const REQUIRED: &str = "required";
+const AVAILABLE: &str = "available";
+const SHORTFALL: &str = "shortfall";
+const MINUTES: &str = "minutes";
+const TIME: &str = "time";
+const OS: &str = "os";
+const VARIABLE: &str = "variable";
 
 pub const TRANSLATOR: Translator = Translator {};
 pub const ADD_SYMBOL: &str = "+";
@@ -368,6 +384,26 @@ impl Translator {
         translate("button-comment")
     }
 
+    pub fn merge_button(&self) -> String {
+        translate("button-merge")
+    }
+
+    pub fn exclude_from_cloud_button(&self) -> String {
+        translate("button-exclude-from-cloud")
+    }
+
+    pub fn include_in_cloud_button(&self) -> String {
+        translate("button-include-in-cloud")
+    }
+
+    pub fn delete_backup_button(&self) -> String {
+        translate("button-delete-backup")
+    }
+
+    pub fn restore_only_this_button(&self) -> String {
+        translate("button-restore-only-this")
+    }
+
     pub fn lock_button(&self) -> String {
         translate("button-lock")
     }
@@ -379,21 +415,37 @@ impl Translator {
     pub fn handle_error(&self, error: &Error) -> String {
         match error {
             Error::ConfigInvalid { why } => self.config_is_invalid(why),
+            Error::ConfigKeyUnrecognized { key, nearest } => self.config_key_unrecognized(key, nearest),
+            Error::ConfigValueInvalid { key, why } => self.config_value_invalid(key, why),
             Error::ManifestInvalid { why, identifier } => self.manifest_is_invalid(why, identifier.as_deref()),
             Error::ManifestCannotBeUpdated { identifier } => self.manifest_cannot_be_updated(identifier.as_deref()),
             Error::CliUnrecognizedGames { games } => self.cli_unrecognized_games(games),
             Error::CliUnableToRequestConfirmation => self.cli_unable_to_request_confirmation(),
             Error::CliBackupIdWithMultipleGames => self.cli_backup_id_with_multiple_games(),
+            Error::CliInvalidTimeWindow => self.cli_invalid_time_window(),
             Error::CliInvalidBackupId => self.cli_invalid_backup_id(),
+            Error::CliFileNotBackedUp { path } => self.cli_file_not_backed_up(path),
+            Error::CliBackupHasNoRegistry => self.cli_backup_has_no_registry(),
+            Error::CliTargetRootUnavailable { game } => self.cli_target_root_unavailable(game),
+            Error::CliGamesFileEmpty { path } => self.cli_games_file_empty(path),
             Error::SomeEntriesFailed => self.some_entries_failed(),
             Error::CannotPrepareBackupTarget { path } => self.cannot_prepare_backup_target(path),
+            Error::InsufficientSpace {
+                path,
+                required,
+                available,
+            } => self.insufficient_space(path, *required, *available),
             Error::RestorationSourceInvalid { path } => self.restoration_source_is_invalid(path),
             Error::RegistryIssue => self.registry_issue(),
             Error::UnableToOpenDir(path) => self.unable_to_open_dir(path),
+            Error::UnableToReadFile(path) => self.unable_to_read_file(path),
+            Error::UnableToWriteFile(path) => self.unable_to_write_file(path),
             Error::UnableToOpenUrl(url) => self.unable_to_open_url(url),
             Error::RcloneUnavailable => self.rclone_unavailable(),
             Error::CloudNotConfigured => self.cloud_not_configured(),
             Error::CloudPathInvalid => self.cloud_path_invalid(),
+            Error::CloudLocalPathUnavailable { path } => self.cloud_local_path_unavailable(path),
+            Error::CloudBandwidthLimitInvalid => self.cloud_bandwidth_limit_invalid(),
             Error::UnableToConfigureCloud(error) => {
                 format!(
                     "{}\n\n{}",
@@ -410,6 +462,20 @@ impl Translator {
             }
             Error::CloudConflict => TRANSLATOR.prefix_error(&TRANSLATOR.cloud_synchronize_conflict()),
             Error::GameDidNotLaunch { why } => format!("{}\n\n{}", self.game_did_not_launch(), self.prefix_error(why)),
+            Error::GameLaunchCommandUnknown => self.game_launch_command_unknown(),
+            Error::ExportNameInvalid { name } => self.export_name_invalid(name),
+            Error::NoDifferentialBackupToMerge => self.no_differential_backup_to_merge(),
+            Error::BackupLocked { backup } => self.backup_locked(backup),
+            Error::BackupMergeFailed { why } => format!("{}\n\n{}", self.backup_merge_failed(), self.prefix_error(why)),
+            Error::BackupPasswordMissing => self.backup_password_missing(),
+            Error::BackupPasswordIncorrect { backup } => self.backup_password_incorrect(backup),
+            Error::BundleInvalid { path, why } => {
+                format!("{}\n\n{}", self.bundle_invalid(path), self.prefix_error(why))
+            }
+            Error::BundleGameAlreadyBackedUp { game } => self.bundle_game_already_backed_up(game),
+            Error::BundleImportFailed { why } => {
+                format!("{}\n\n{}", self.bundle_import_failed(), self.prefix_error(why))
+            }
         }
     }
 
@@ -421,6 +487,8 @@ impl Translator {
                 format!("{}\n\n{}", translate_args("command-unlaunched", &args), raw)
             }
             CommandError::Terminated { .. } => translate_args("command-terminated", &args),
+            CommandError::Cancelled { .. } => translate_args("command-cancelled", &args),
+            CommandError::TimedOut { .. } => translate_args("command-timed-out", &args),
             CommandError::Exited {
                 code, stdout, stderr, ..
             } => {
@@ -448,6 +516,21 @@ impl Translator {
         format!("{}\n{}", prefix, lines)
     }
 
+    pub fn cli_closest_games(&self, pattern: &str, games: &[String]) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATTERN, pattern);
+        let prefix = translate_args("cli-closest-games", &args);
+        let lines = games.iter().map(|x| format!("  - {}", x)).join("\n");
+        format!("{}\n{}", prefix, lines)
+    }
+
+    pub fn cli_fuzzy_game_match(&self, pattern: &str, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATTERN, pattern);
+        args.set(GAME, game);
+        translate_args("cli-fuzzy-game-match", &args)
+    }
+
     pub fn cli_unable_to_request_confirmation(&self) -> String {
         #[cfg(target_os = "windows")]
         let extra_note = translate("cli-unable-to-request-confirmation.winpty-workaround");
@@ -462,10 +545,80 @@ impl Translator {
         translate("cli-backup-id-with-multiple-games")
     }
 
+    pub fn cli_invalid_time_window(&self) -> String {
+        translate("cli-invalid-time-window")
+    }
+
     pub fn cli_invalid_backup_id(&self) -> String {
         translate("cli-invalid-backup-id")
     }
 
+    pub fn cli_select_backup(&self, game: &str, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        args.set(TOTAL, total);
+        translate_args("cli-select-backup", &args)
+    }
+
+    pub fn cli_invalid_selection(&self) -> String {
+        translate("cli-invalid-selection")
+    }
+
+    pub fn cli_enter_zip_password(&self) -> String {
+        translate("cli-enter-zip-password")
+    }
+
+    pub fn cli_file_not_backed_up(&self, path: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, path.render());
+        translate_args("cli-file-not-backed-up", &args)
+    }
+
+    pub fn cli_backup_has_no_registry(&self) -> String {
+        translate("cli-backup-has-no-registry")
+    }
+
+    pub fn cli_target_root_unavailable(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        translate_args("cli-target-root-unavailable", &args)
+    }
+
+    pub fn cli_games_file_empty(&self, path: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, path.render());
+        translate_args("cli-games-file-empty", &args)
+    }
+
+    fn external_game_message(&self, id: &str, path: &StrictPath, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, path.render());
+        args.set(GAME, game);
+        translate_args(id, &args)
+    }
+
+    pub fn cli_imported_external_game(&self, path: &StrictPath, game: &str) -> String {
+        self.external_game_message("cli-imported-external-game", path, game)
+    }
+
+    pub fn cli_external_game_already_backed_up(&self, path: &StrictPath, game: &str) -> String {
+        self.external_game_message("cli-external-game-already-backed-up", path, game)
+    }
+
+    pub fn cli_external_game_without_save_location(&self, path: &StrictPath, game: &str) -> String {
+        self.external_game_message("cli-external-game-without-save-location", path, game)
+    }
+
+    pub fn cli_external_game_failed(&self, path: &StrictPath, game: &str) -> String {
+        self.external_game_message("cli-external-game-failed", path, game)
+    }
+
+    pub fn cli_unmatched_external_folders(&self, folders: &[String]) -> String {
+        let prefix = translate("cli-unmatched-external-folders");
+        let lines = folders.iter().map(|x| format!("  - {}", x)).join("\n");
+        format!("{}\n{}", prefix, lines)
+    }
+
     pub fn cloud_not_configured(&self) -> String {
         translate("cloud-not-configured")
     }
@@ -474,6 +627,64 @@ impl Translator {
         translate("cloud-path-invalid")
     }
 
+    pub fn cloud_local_path_unavailable(&self, path: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, path.render());
+        translate_args("cloud-local-path-unavailable", &args)
+    }
+
+    pub fn cloud_bandwidth_limit_invalid(&self) -> String {
+        translate("cloud-bandwidth-limit-invalid")
+    }
+
+    pub fn export_name_invalid(&self, name: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, name);
+        translate_args("export-name-invalid", &args)
+    }
+
+    pub fn no_differential_backup_to_merge(&self) -> String {
+        translate("no-differential-backup-to-merge")
+    }
+
+    pub fn backup_merge_failed(&self) -> String {
+        translate("backup-merge-failed")
+    }
+
+    pub fn backup_locked(&self, backup: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, backup);
+        translate_args("backup-locked", &args)
+    }
+
+    pub fn backup_password_missing(&self) -> String {
+        let mut args = FluentArgs::new();
+        args.set(VARIABLE, ENV_ZIP_PASSWORD);
+        translate_args("backup-password-missing", &args)
+    }
+
+    pub fn backup_password_incorrect(&self, backup: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(NAME, backup);
+        translate_args("backup-password-incorrect", &args)
+    }
+
+    pub fn bundle_invalid(&self, path: &StrictPath) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, path.render());
+        translate_args("bundle-invalid", &args)
+    }
+
+    pub fn bundle_game_already_backed_up(&self, game: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        translate_args("bundle-game-already-backed-up", &args)
+    }
+
+    pub fn bundle_import_failed(&self) -> String {
+        translate("bundle-import-failed")
+    }
+
     pub fn some_entries_failed(&self) -> String {
         translate("some-entries-failed")
     }
@@ -518,6 +729,12 @@ impl Translator {
         translate("badge-failed")
     }
 
+    pub fn failed_games_badge(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        translate_args("badge-failed-count", &args)
+    }
+
     pub fn badge_duplicates(&self) -> String {
         translate("badge-duplicates")
     }
@@ -530,6 +747,37 @@ impl Translator {
         translate("badge-ignored")
     }
 
+    pub fn badge_excluded(&self) -> String {
+        translate("badge-excluded")
+    }
+
+    pub fn badge_conflict(&self) -> String {
+        translate("badge-conflict")
+    }
+
+    pub fn conflict_tooltip(&self) -> String {
+        translate("conflict-tooltip")
+    }
+
+    pub fn badge_warning(&self) -> String {
+        translate("badge-warning")
+    }
+
+    pub fn file_exclusion(&self, exclusion: &FileExclusion) -> String {
+        let mut args = FluentArgs::new();
+        match exclusion {
+            FileExclusion::TooLarge { limit } => {
+                args.set(SIZE, self.adjusted_size(*limit));
+                translate_args("file-exceeds-max-size", &args)
+            }
+            FileExclusion::TooSmall { limit } => {
+                args.set(SIZE, self.adjusted_size(*limit));
+                translate_args("file-below-min-size", &args)
+            }
+            FileExclusion::Empty => translate("file-is-empty"),
+        }
+    }
+
     pub fn badge_redirected_from(&self, original: &StrictPath) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, original.render());
@@ -624,6 +872,74 @@ impl Translator {
         format!("    - {}", error.message())
     }
 
+    pub fn cli_game_line_item_warning(&self, warning: &OperationWarning) -> String {
+        format!(
+            "  - {} {}",
+            self.label(&self.badge_warning()),
+            self.operation_warning(warning)
+        )
+    }
+
+    pub fn operation_warning(&self, warning: &OperationWarning) -> String {
+        let mut args = FluentArgs::new();
+        match warning {
+            OperationWarning::SkippedFile { path } => {
+                args.set(PATH, path);
+                translate_args("warning-skipped-file", &args)
+            }
+            OperationWarning::UnreadableFile { path } => {
+                args.set(PATH, path);
+                translate_args("warning-unreadable-file", &args)
+            }
+            OperationWarning::FutureModifiedTime { path } => {
+                args.set(PATH, path);
+                translate_args("warning-future-modified-time", &args)
+            }
+            OperationWarning::InaccessibleRegistry { key } => {
+                args.set(KEY, key);
+                translate_args("warning-inaccessible-registry", &args)
+            }
+            OperationWarning::OverlappingRoots { parent, child } => {
+                args.set(PARENT, parent);
+                args.set(CHILD, child);
+                translate_args("warning-overlapping-roots", &args)
+            }
+            OperationWarning::UnremovableBackupFile { path } => {
+                args.set(PATH, path);
+                translate_args("warning-unremovable-backup-file", &args)
+            }
+            OperationWarning::SkippedDestructiveRestore { path } => {
+                args.set(PATH, path);
+                translate_args("warning-skipped-destructive-restore", &args)
+            }
+            OperationWarning::GameRunning => translate("warning-game-running"),
+            OperationWarning::HookFailed { command, message } => {
+                args.set(COMMAND, command);
+                args.set(MESSAGE, message);
+                translate_args("warning-hook-failed", &args)
+            }
+            OperationWarning::InsufficientSpace { required, available } => {
+                args.set(REQUIRED, self.adjusted_size(*required));
+                args.set(AVAILABLE, self.adjusted_size(*available));
+                translate_args("warning-insufficient-space", &args)
+            }
+            OperationWarning::RedirectFallback { path, redirect } => {
+                args.set(PATH, path);
+                args.set(REDIRECT, redirect);
+                translate_args("warning-redirect-fallback", &args)
+            }
+            OperationWarning::RecentlyRestored { minutes } => {
+                args.set(MINUTES, *minutes);
+                translate_args("warning-recently-restored", &args)
+            }
+            OperationWarning::MirrorFailed { path, message } => {
+                args.set(PATH, path);
+                args.set(MESSAGE, message);
+                translate_args("warning-mirror-failed", &args)
+            }
+        }
+    }
+
     pub fn cli_summary(&self, status: &OperationStatus, location: &StrictPath) -> String {
         let new_games = if status.changed_games.new > 0 {
             format!(" [{}{}]", crate::lang::ADD_SYMBOL, status.changed_games.new)
@@ -662,6 +978,21 @@ impl Translator {
         )
     }
 
+    pub fn cli_aggregate_summary(&self, games: usize, bytes: u64, changed_games: usize, failed_games: usize) -> String {
+        format!(
+            "{}:\n  {}: {}\n  {}: {}\n  {}: {}\n  {}: {}",
+            translate("summary"),
+            translate("total-games"),
+            games,
+            translate("file-size"),
+            self.adjusted_size(bytes),
+            translate("changed-games"),
+            changed_games,
+            translate("failed-games"),
+            failed_games,
+        )
+    }
+
     pub fn backup_button(&self) -> String {
         translate("button-backup")
     }
@@ -706,6 +1037,16 @@ impl Translator {
         translate("button-nav-other")
     }
 
+    pub fn nav_changes_button(&self) -> String {
+        translate("button-nav-changes")
+    }
+
+    pub fn changed_games_label(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        translate_args("label-changed-games", &args)
+    }
+
     pub fn customize_button(&self) -> String {
         translate("button-customize")
     }
@@ -726,6 +1067,18 @@ impl Translator {
         translate("backups-are-valid")
     }
 
+    pub fn no_backups_to_prune(&self) -> String {
+        translate("no-backups-to-prune")
+    }
+
+    pub fn backups_to_prune(&self) -> String {
+        translate("backups-to-prune")
+    }
+
+    pub fn retained_label(&self) -> String {
+        translate("label-retained")
+    }
+
     pub fn backups_are_invalid(&self) -> String {
         translate("backups-are-invalid")
     }
@@ -807,6 +1160,14 @@ impl Translator {
         translate("button-validate")
     }
 
+    pub fn preview_pruning_button(&self) -> String {
+        translate("button-preview-pruning")
+    }
+
+    pub fn scrub_button(&self) -> String {
+        translate("button-scrub")
+    }
+
     pub fn override_manifest_button(&self) -> String {
         translate("button-override-manifest")
     }
@@ -827,6 +1188,25 @@ impl Translator {
         format!("{}\n{}", translate("config-is-invalid"), why)
     }
 
+    pub fn config_key_unrecognized(&self, key: &str, nearest: &[String]) -> String {
+        let mut args = FluentArgs::new();
+        args.set(KEY, key);
+        let message = translate_args("config-key-unrecognized", &args);
+
+        if nearest.is_empty() {
+            return message;
+        }
+
+        let lines = nearest.iter().map(|x| format!("  - {}", x)).join("\n");
+        format!("{}\n{}\n{}", message, translate("config-key-suggestions"), lines)
+    }
+
+    pub fn config_value_invalid(&self, key: &str, why: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(KEY, key);
+        format!("{}\n{}", translate_args("config-value-invalid", &args), why)
+    }
+
     pub fn manifest_is_invalid(&self, why: &str, identifier: Option<&str>) -> String {
         let message = translate("manifest-is-invalid");
         let identifier = identifier.map(|x| format!(" ({})", x)).unwrap_or("".to_string());
@@ -845,6 +1225,15 @@ impl Translator {
         translate_args("cannot-prepare-backup-target", &args)
     }
 
+    pub fn insufficient_space(&self, target: &StrictPath, required: u64, available: u64) -> String {
+        let mut args = FluentArgs::new();
+        args.set(PATH, target.render());
+        args.set(REQUIRED, self.adjusted_size(required));
+        args.set(AVAILABLE, self.adjusted_size(available));
+        args.set(SHORTFALL, self.adjusted_size(required.saturating_sub(available)));
+        translate_args("insufficient-space", &args)
+    }
+
     pub fn restoration_source_is_invalid(&self, source: &StrictPath) -> String {
         let mut args = FluentArgs::new();
         args.set(PATH, source.render());
@@ -864,6 +1253,14 @@ impl Translator {
         format!("{}\n\n{}", translate("unable-to-open-directory"), path.resolve())
     }
 
+    pub fn unable_to_read_file(&self, path: &StrictPath) -> String {
+        format!("{}\n\n{}", translate("unable-to-read-file"), path.resolve())
+    }
+
+    pub fn unable_to_write_file(&self, path: &StrictPath) -> String {
+        format!("{}\n\n{}", translate("unable-to-write-file"), path.resolve())
+    }
+
     pub fn unable_to_open_url(&self, url: &str) -> String {
         format!("{}\n\n{}", translate("unable-to-open-url"), url)
     }
@@ -880,6 +1277,26 @@ impl Translator {
         translate("cloud-synchronize-conflict")
     }
 
+    pub fn waiting_for_cloud_authorization(&self, provider: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(APP, provider);
+        args.set(MINUTES, crate::cloud::AUTHORIZATION_TIMEOUT.as_secs() / 60);
+        translate_args("waiting-for-cloud-authorization", &args)
+    }
+
+    pub fn cli_maintenance_summary(&self, freed_bytes: u64) -> String {
+        format!(
+            "{}:\n  {}: {}",
+            translate("overall"),
+            translate("freed-space"),
+            self.adjusted_size(freed_bytes)
+        )
+    }
+
+    pub fn freed_space_label(&self) -> String {
+        translate("freed-space")
+    }
+
     pub fn adjusted_size(&self, bytes: u64) -> String {
         let byte = byte_unit::Byte::from(bytes);
         let adjusted_byte = byte.get_appropriate_unit(byte_unit::UnitType::Binary);
@@ -936,6 +1353,14 @@ impl Translator {
         translate("label-installed-name")
     }
 
+    pub fn custom_include_label(&self) -> String {
+        translate("label-include-games")
+    }
+
+    pub fn custom_excluded_subpaths_label(&self) -> String {
+        translate("label-excluded-subpaths")
+    }
+
     pub fn sort_label(&self) -> String {
         translate("field-sort")
     }
@@ -963,6 +1388,19 @@ impl Translator {
         })
     }
 
+    pub fn conflict_policy_label(&self) -> String {
+        translate("field-conflict-policy")
+    }
+
+    pub fn conflict_policy(&self, policy: &ConflictPolicy) -> String {
+        translate(match policy {
+            ConflictPolicy::Overwrite => "conflict-policy-overwrite",
+            ConflictPolicy::Skip => "conflict-policy-skip",
+            ConflictPolicy::KeepNewer => "conflict-policy-keep-newer",
+            ConflictPolicy::Backup => "conflict-policy-backup",
+        })
+    }
+
     pub fn sort_key(&self, key: &SortKey) -> String {
         translate(match key {
             SortKey::Name => "game-name",
@@ -1001,10 +1439,19 @@ impl Translator {
         })
     }
 
+    pub fn filter_failure(&self, filter: game_filter::Failure) -> String {
+        translate(match filter {
+            game_filter::Failure::Failed => "label-has-failures",
+            game_filter::Failure::Succeeded => "label-no-failures",
+        })
+    }
+
     pub fn backup_format(&self, key: &BackupFormat) -> String {
         translate(match key {
             BackupFormat::Simple => "backup-format-simple",
             BackupFormat::Zip => "backup-format-zip",
+            BackupFormat::Tar => "backup-format-tar",
+            BackupFormat::TarGz => "backup-format-tar-gz",
         })
     }
 
@@ -1029,6 +1476,7 @@ impl Translator {
             RedirectKind::Backup => self.backup_button(),
             RedirectKind::Restore => self.restore_button(),
             RedirectKind::Bidirectional => translate("redirect-bidirectional"),
+            RedirectKind::Regex => translate("redirect-regex"),
         }
     }
 
@@ -1036,6 +1484,10 @@ impl Translator {
         translate("reverse-redirects-when-restoring")
     }
 
+    pub fn fall_back_when_redirect_fails(&self) -> String {
+        translate("fall-back-when-redirect-fails")
+    }
+
     pub fn game_label(&self) -> String {
         translate("label-game")
     }
@@ -1068,6 +1520,14 @@ impl Translator {
         translate("label-integration")
     }
 
+    pub fn store_label(&self) -> String {
+        translate("label-store")
+    }
+
+    pub fn backup_format_label(&self) -> String {
+        translate("label-backup-format")
+    }
+
     pub fn custom_game_kind(&self, kind: &CustomGameKind) -> String {
         match kind {
             CustomGameKind::Game => self.game_label(),
@@ -1115,6 +1575,26 @@ impl Translator {
         translate("explanation-for-exclude-store-screenshots")
     }
 
+    pub fn explanation_for_max_file_size(&self) -> String {
+        translate("explanation-for-max-file-size")
+    }
+
+    pub fn explanation_for_min_file_size(&self) -> String {
+        translate("explanation-for-min-file-size")
+    }
+
+    pub fn explanation_for_exclude_empty_files(&self) -> String {
+        translate("explanation-for-exclude-empty-files")
+    }
+
+    pub fn explanation_for_exclude_empty_dirs(&self) -> String {
+        translate("explanation-for-exclude-empty-dirs")
+    }
+
+    pub fn file_size_limit_label(&self) -> String {
+        self.field(&translate("label-size-limit"))
+    }
+
     pub fn explanation_for_exclude_cloud_games(&self) -> String {
         translate("explanation-for-exclude-cloud-games")
     }
@@ -1215,6 +1695,26 @@ impl Translator {
         self.field(&translate("label-folder"))
     }
 
+    pub fn bandwidth_limit_label(&self) -> String {
+        self.field(&translate("label-bandwidth-limit"))
+    }
+
+    pub fn transfers_label(&self) -> String {
+        self.field(&translate("label-transfers"))
+    }
+
+    pub fn limit_cloud_transfers(&self) -> String {
+        translate("limit-cloud-transfers")
+    }
+
+    pub fn duplicates_label(&self) -> String {
+        self.field(&translate("label-duplicates"))
+    }
+
+    pub fn total_size_label(&self) -> String {
+        self.field(&translate("label-total-size"))
+    }
+
     pub fn executable_label(&self) -> String {
         translate("label-executable")
     }
@@ -1255,10 +1755,30 @@ impl Translator {
         self.field(&translate("label-provider"))
     }
 
+    pub fn bucket_label(&self) -> String {
+        self.field(&translate("label-bucket"))
+    }
+
+    pub fn region_label(&self) -> String {
+        self.field(&translate("label-region"))
+    }
+
+    pub fn share_label(&self) -> String {
+        self.field(&translate("label-share"))
+    }
+
+    pub fn local_folder_label(&self) -> String {
+        translate("label-local-folder")
+    }
+
     pub fn none_label(&self) -> String {
         translate("label-none")
     }
 
+    pub fn default_label(&self) -> String {
+        translate("label-default")
+    }
+
     pub fn custom_label(&self) -> String {
         translate("label-custom")
     }
@@ -1281,10 +1801,20 @@ impl Translator {
         translate("skip-unconstructive-backups")
     }
 
+    pub fn follow_symlinks(&self) -> String {
+        translate("follow-symlinks")
+    }
+
     pub fn total_games(&self) -> String {
         translate("total-games")
     }
 
+    pub fn time_remaining(&self, time: &str) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TIME, time);
+        translate_args("time-remaining", &args)
+    }
+
     pub fn new_tooltip(&self) -> String {
         translate("label-new")
     }
@@ -1297,6 +1827,12 @@ impl Translator {
         translate("label-removed")
     }
 
+    pub fn backup_from_other_os_tooltip(&self, os: Os) -> String {
+        let mut args = FluentArgs::new();
+        args.set(OS, format!("{os:?}"));
+        translate_args("backup-from-other-os", &args)
+    }
+
     fn consider_doing_a_preview(&self) -> String {
         translate("consider-doing-a-preview")
     }
@@ -1318,6 +1854,37 @@ impl Translator {
         }
     }
 
+    pub fn confirm_maintenance(&self, target: &StrictPath) -> String {
+        format!("{}\n\n{}", translate("confirm-maintenance"), target.render())
+    }
+
+    pub fn confirm_delete_backup(&self, game: &str, backup: &str, dependents: &[String]) -> String {
+        let mut args = FluentArgs::new();
+        args.set(GAME, game);
+        args.set(NAME, backup);
+        let primary = translate_args("confirm-delete-backup", &args);
+
+        if dependents.is_empty() {
+            primary
+        } else {
+            format!(
+                "{}\n\n{}\n{}",
+                primary,
+                translate("confirm-delete-backup.dependents"),
+                dependents.iter().map(|x| format!("  - {x}")).join("\n")
+            )
+        }
+    }
+
+    pub fn confirm_merge(&self, target: &StrictPath, prune: bool) -> String {
+        let primary = if prune {
+            translate("confirm-merge-and-prune")
+        } else {
+            translate("confirm-merge")
+        };
+        format!("{}\n\n{}", primary, target.render())
+    }
+
     pub fn confirm_restore(&self, source: &StrictPath, suggest: bool) -> String {
         let primary = translate("confirm-restore");
 
@@ -1333,6 +1900,10 @@ impl Translator {
         }
     }
 
+    pub fn confirm_restore_recent(&self, games: &[&str]) -> String {
+        format!("{}\n\n{}", translate("confirm-restore-recent"), games.join("\n"))
+    }
+
     pub fn confirm_cloud_upload(&self, local: &str, cloud: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(LOCAL_PATH, local);
@@ -1409,6 +1980,10 @@ impl Translator {
         translate("game-did-not-launch")
     }
 
+    pub fn game_launch_command_unknown(&self) -> String {
+        translate("game-launch-command-unknown")
+    }
+
     pub fn back_up_one_game_confirm(&self, game: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(GAME, game);
@@ -1437,6 +2012,28 @@ impl Translator {
         translate("new-version-check")
     }
 
+    pub fn show_tray_icon(&self) -> String {
+        translate("show-tray-icon")
+    }
+
+    pub fn tray_backup_all(&self) -> String {
+        translate("tray-backup-all")
+    }
+
+    pub fn tray_open(&self) -> String {
+        translate("tray-open")
+    }
+
+    pub fn tray_backup_finished(&self) -> String {
+        translate("tray-backup-finished")
+    }
+
+    pub fn newly_supported_games_found(&self, total: usize) -> String {
+        let mut args = FluentArgs::new();
+        args.set(TOTAL, total);
+        translate_args("newly-supported-games-found", &args)
+    }
+
     pub fn new_version_available(&self, version: &str) -> String {
         let mut args = FluentArgs::new();
         args.set(VERSION, version);
@@ -185,6 +185,18 @@ fn main() {
         }
     };
 
+    if args.print_config_path || args.print_backup_path {
+        if let Err(e) = cli::print_paths(args.print_config_path, args.print_backup_path) {
+            failed = true;
+            cli::show_error(&e, false, false, false);
+        }
+        flush_logger();
+        if failed {
+            std::process::exit(1);
+        }
+        return;
+    }
+
     match args.sub {
         None => {
             #[cfg(target_os = "windows")]
@@ -202,10 +214,13 @@ fn main() {
         Some(sub) => {
             let gui = sub.gui();
             let force = sub.force();
+            let api = sub.api();
 
-            if let Err(e) = cli::run(sub, args.no_manifest_update, args.try_manifest_update) {
+            if let Err(failure) = cli::run(sub, args.no_manifest_update, args.try_manifest_update) {
                 failed = true;
-                cli::show_error(&e, gui, force);
+                if !failure.reported {
+                    cli::show_error(&failure.error, gui, force, api);
+                }
             }
         }
     };
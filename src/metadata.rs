@@ -1,3 +1,5 @@
+use crate::resource::manifest::{Game, Manifest, Note, Store, Tag};
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
 pub struct Release {
     pub version: semver::Version,
@@ -69,3 +71,109 @@ impl Release {
         }
     }
 }
+
+/// Names of the manifest's games that are associated with a store.
+/// The manifest is keyed by name, so the results are already sorted and unique.
+pub fn find_games_by_store(manifest: &Manifest, store: Store) -> Vec<&str> {
+    find_games(manifest, |game| game.has_store(store))
+}
+
+/// Names of the manifest's games that have any file or registry entry with a tag.
+/// The manifest is keyed by name, so the results are already sorted and unique.
+pub fn find_games_by_tag<'a>(manifest: &'a Manifest, tag: &Tag) -> Vec<&'a str> {
+    find_games(manifest, |game| game.has_tag(tag))
+}
+
+/// Names of the manifest's games that have notes about their save data.
+/// The manifest is keyed by name, so the results are already sorted and unique.
+pub fn find_games_with_notes(manifest: &Manifest) -> Vec<&str> {
+    find_games(manifest, |game| !game.notes.is_empty())
+}
+
+/// Notes about a game's save data, such as when the game actually writes its saves.
+pub fn game_notes<'a>(manifest: &'a Manifest, game: &str) -> &'a [Note] {
+    manifest.0.get(game).map(|x| x.notes.as_slice()).unwrap_or_default()
+}
+
+fn find_games(manifest: &Manifest, predicate: impl Fn(&Game) -> bool) -> Vec<&str> {
+    manifest
+        .0
+        .iter()
+        .filter(|(_, game)| predicate(game))
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{resource::ResourceFile, testing::s};
+
+    fn manifest() -> Manifest {
+        Manifest::load_from_string(
+            r#"
+            game-b:
+              steam:
+                id: 1
+              files:
+                foo:
+                  tags: [save]
+            game-a:
+              id:
+                steamExtra: [2]
+              registry:
+                bar:
+                  tags: [config]
+            game-c:
+              files:
+                baz:
+                  when:
+                    - store: gog
+                  tags: [save, config]
+              notes:
+                - message: Saves only after quitting.
+            game-d: {}
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn can_find_games_by_store() {
+        let manifest = manifest();
+        assert_eq!(vec!["game-a", "game-b"], find_games_by_store(&manifest, Store::Steam));
+        assert_eq!(vec!["game-c"], find_games_by_store(&manifest, Store::Gog));
+        assert_eq!(Vec::<&str>::new(), find_games_by_store(&manifest, Store::Epic));
+    }
+
+    #[test]
+    fn can_find_games_by_tag() {
+        let manifest = manifest();
+        assert_eq!(vec!["game-b", "game-c"], find_games_by_tag(&manifest, &Tag::Save));
+        assert_eq!(vec!["game-a", "game-c"], find_games_by_tag(&manifest, &Tag::Config));
+    }
+
+    #[test]
+    fn can_find_game_notes() {
+        let manifest = manifest();
+        assert_eq!(vec!["game-c"], find_games_with_notes(&manifest));
+        assert_eq!(
+            vec![s("Saves only after quitting.")],
+            game_notes(&manifest, "game-c")
+                .iter()
+                .map(|x| x.message.clone())
+                .collect::<Vec<_>>(),
+        );
+        assert!(game_notes(&manifest, "game-d").is_empty());
+        assert!(game_notes(&manifest, "unknown").is_empty());
+    }
+
+    #[test]
+    fn notes_round_trip_through_serialization() {
+        let manifest = manifest();
+        let reloaded = Manifest::load_from_string(&serde_yaml::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(game_notes(&manifest, "game-c"), game_notes(&reloaded, "game-c"));
+    }
+}
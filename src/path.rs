@@ -1,13 +1,30 @@
-use std::sync::{Arc, LazyLock, Mutex};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, LazyLock, Mutex,
+};
 
 use filetime::FileTime;
 use itertools::Itertools;
 
 use crate::{
-    prelude::{AnyError, SKIP},
-    resource::manifest::{placeholder, Os},
+    prelude::{AnyError, Hasher, SKIP},
+    resource::{
+        config::HashAlgorithm,
+        manifest::{placeholder, Os},
+    },
 };
 
+/// Below this size, mapping and splitting a file across threads costs more than it saves.
+const BLAKE3_MAPPED_HASH_THRESHOLD: u64 = 1024 * 1024;
+
+/// How much to copy between checks for cancellation.
+const CANCELLABLE_COPY_CHUNK: usize = 64 * 1024;
+
+/// The error for a copy that was stopped by its cancellation flag.
+pub fn cancelled_error() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::Interrupted, "Cancelled")
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Drive {
     Root,
@@ -79,6 +96,14 @@ impl CommonPath {
     }
 }
 
+/// This is looked up every time rather than cached,
+/// so that paths follow the executable if it gets moved.
+fn current_exe_dir() -> Option<String> {
+    let mut exe = std::env::current_exe().ok()?;
+    exe.pop();
+    Some(exe.to_string_lossy().to_string())
+}
+
 #[derive(Debug)]
 pub enum SetFileTimeError {
     Write(std::io::Error),
@@ -97,6 +122,13 @@ pub enum StrictPathError {
     Unsupported,
 }
 
+/// Where a file's content is stored on disk: the device (or volume) and the file's index on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FileIdentity {
+    device: u64,
+    index: u64,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct Analysis {
     drive: Option<Drive>,
@@ -275,8 +307,13 @@ impl StrictPath {
                 }
                 Component::Unix(UComponent::Normal(part)) | Component::Windows(WComponent::Normal(part)) => {
                     if i == 0 {
+                        let exe_dir;
                         let mapped = match part {
                             "~" | placeholder::HOME => CommonPath::Home.get(),
+                            placeholder::EXE => {
+                                exe_dir = current_exe_dir();
+                                exe_dir.as_deref()
+                            }
                             placeholder::XDG_CONFIG => CommonPath::Config.get(),
                             placeholder::XDG_DATA | placeholder::WIN_APP_DATA => CommonPath::Data.get(),
                             placeholder::WIN_LOCAL_APP_DATA => CommonPath::DataLocal.get(),
@@ -488,6 +525,86 @@ impl StrictPath {
         self.is_file() || self.is_dir()
     }
 
+    pub fn is_symlink(&self) -> bool {
+        self.as_std_path_buf().map(|x| x.is_symlink()).unwrap_or_default()
+    }
+
+    /// Where this path points, if it is a symlink.
+    pub fn read_link(&self) -> Option<String> {
+        std::fs::read_link(self.as_std_path_buf().ok()?)
+            .ok()
+            .map(|x| x.to_string_lossy().to_string())
+    }
+
+    /// Like `interpreted`, but without resolving the final component if it is a symlink.
+    pub fn interpreted_link(&self) -> Result<Self, StrictPathError> {
+        match (self.is_symlink(), self.parent_raw(), self.leaf()) {
+            (true, Some(parent), Some(leaf)) => Ok(parent.interpreted()?.joined(&leaf)),
+            _ => self.interpreted(),
+        }
+    }
+
+    /// Replace this path with a symlink to `target`,
+    /// which may be relative to this path's parent folder.
+    pub fn create_symlink(&self, target: &str) -> std::io::Result<()> {
+        let link = self.as_std_path_buf()?;
+        if link.is_symlink() || link.is_file() {
+            std::fs::remove_file(&link)?;
+        }
+
+        #[cfg(target_os = "windows")]
+        std::os::windows::fs::symlink_file(target, &link)?;
+        #[cfg(not(target_os = "windows"))]
+        std::os::unix::fs::symlink(target, &link)?;
+
+        Ok(())
+    }
+
+    /// Replace this path with a hardlink to `original`.
+    /// This fails if the two paths are on different file systems.
+    pub fn create_hardlink(&self, original: &StrictPath) -> std::io::Result<()> {
+        let link = self.as_std_path_buf()?;
+        if link.is_symlink() || link.is_file() {
+            std::fs::remove_file(&link)?;
+        }
+
+        std::fs::hard_link(original.as_std_path_buf()?, &link)
+    }
+
+    /// Identify the file's content on disk, if it has more than one hardlink.
+    /// All of the hardlinks to the same content share the same identity.
+    pub fn hardlink_identity(&self) -> Option<FileIdentity> {
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::io::AsRawHandle;
+            use windows::Win32::{
+                Foundation::HANDLE,
+                Storage::FileSystem::{GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION},
+            };
+
+            let file = std::fs::File::open(self.as_std_path_buf().ok()?).ok()?;
+            let mut info = BY_HANDLE_FILE_INFORMATION::default();
+            unsafe { GetFileInformationByHandle(HANDLE(file.as_raw_handle()), &mut info) }.ok()?;
+
+            (info.nNumberOfLinks > 1).then(|| FileIdentity {
+                device: info.dwVolumeSerialNumber as u64,
+                index: ((info.nFileIndexHigh as u64) << 32) | info.nFileIndexLow as u64,
+            })
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::os::unix::fs::MetadataExt;
+
+            let metadata = std::fs::metadata(self.as_std_path_buf().ok()?).ok()?;
+
+            (metadata.is_file() && metadata.nlink() > 1).then(|| FileIdentity {
+                device: metadata.dev(),
+                index: metadata.ino(),
+            })
+        }
+    }
+
     pub fn metadata(&self) -> std::io::Result<std::fs::Metadata> {
         self.as_std_path_buf()?.metadata()
     }
@@ -634,6 +751,10 @@ impl StrictPath {
         std::fs::write(self.as_std_path_buf()?, content.as_bytes())
     }
 
+    pub fn write_with_bytes(&self, content: &[u8]) -> std::io::Result<()> {
+        std::fs::write(self.as_std_path_buf()?, content)
+    }
+
     pub fn move_to(&self, new_path: &StrictPath) -> std::io::Result<()> {
         std::fs::rename(self.as_std_path_buf()?, new_path.as_std_path_buf()?)
     }
@@ -642,6 +763,37 @@ impl StrictPath {
         std::fs::copy(self.as_std_path_buf()?, target.as_std_path_buf()?)
     }
 
+    /// Like `copy_to`, but checks the flag after each chunk so that a large file can be interrupted.
+    /// If cancelled, the partially written target is removed.
+    pub fn copy_to_cancellable(&self, target: &StrictPath, cancel: &AtomicBool) -> std::io::Result<u64> {
+        use std::io::{Read, Write};
+
+        let mut reader = self.open()?;
+        let mut writer = target.create()?;
+        let mut buffer = vec![0; CANCELLABLE_COPY_CHUNK];
+        let mut copied = 0;
+
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(x) => x,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            writer.write_all(&buffer[..read])?;
+            copied += read as u64;
+
+            if cancel.load(Ordering::Relaxed) {
+                drop(writer);
+                let _ = target.remove();
+                return Err(cancelled_error());
+            }
+        }
+
+        writer.flush()?;
+        Ok(copied)
+    }
+
     pub fn create_dirs(&self) -> std::io::Result<()> {
         std::fs::create_dir_all(self.as_std_path_buf()?)?;
         Ok(())
@@ -712,7 +864,12 @@ impl StrictPath {
         false
     }
 
-    pub fn copy_to_path(&self, context: &str, target_file: &StrictPath) -> Result<(), std::io::Error> {
+    pub fn copy_to_path(
+        &self,
+        context: &str,
+        target_file: &StrictPath,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<(), std::io::Error> {
         log::trace!("[{context}] copy {:?} -> {:?}", &self, &target_file);
 
         if let Err(e) = target_file.create_parent_dir() {
@@ -735,8 +892,16 @@ impl StrictPath {
             ));
         }
 
-        if let Err(e) = self.copy_to(target_file) {
-            log::error!("[{context}] unable to copy: {:?} -> {:?} | {e}", &self, &target_file);
+        let copied = match cancel {
+            Some(cancel) => self.copy_to_cancellable(target_file, cancel),
+            None => self.copy_to(target_file),
+        };
+        if let Err(e) = copied {
+            if e.kind() == std::io::ErrorKind::Interrupted {
+                log::info!("[{context}] cancelled copy: {:?} -> {:?}", &self, &target_file);
+            } else {
+                log::error!("[{context}] unable to copy: {:?} -> {:?} | {e}", &self, &target_file);
+            }
             return Err(e);
         }
 
@@ -935,16 +1100,70 @@ impl StrictPath {
         }
     }
 
+    /// Free space on the disk that contains this path, if it can be determined.
+    /// If the path does not exist yet, this checks its nearest existing parent.
+    pub fn available_space(&self, disks: &sysinfo::Disks) -> Option<u64> {
+        let mut existing = self.interpreted().ok()?;
+        while !existing.exists() {
+            existing = existing.parent()?;
+        }
+
+        let mounts: Vec<_> = disks.list().iter().map(|x| StrictPath::from(x.mount_point())).collect();
+        let mount = match mounts.iter().find(|x| x.equivalent(&existing)) {
+            Some(mount) => mount.clone(),
+            None => existing.nearest_prefix(mounts.clone())?,
+        };
+
+        disks
+            .list()
+            .iter()
+            .zip(&mounts)
+            .find(|(_, x)| x.equivalent(&mount))
+            .map(|(disk, _)| disk.available_space())
+    }
+
+    /// For a folder, this is the total size of all files inside of it.
+    pub fn size_recursive(&self) -> u64 {
+        if !self.is_dir() {
+            return self.size();
+        }
+
+        let Ok(path) = self.as_std_path_buf() else {
+            return 0;
+        };
+
+        walkdir::WalkDir::new(path)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|x| x.ok())
+            .filter(|x| x.file_type().is_file())
+            .filter_map(|x| x.metadata().ok())
+            .map(|x| x.len())
+            .sum()
+    }
+
     pub fn sha1(&self) -> String {
-        self.try_sha1().unwrap_or_default()
+        self.hash(HashAlgorithm::Sha1)
     }
 
     pub fn try_sha1(&self) -> Result<String, Box<dyn std::error::Error>> {
+        self.try_hash(HashAlgorithm::Sha1)
+    }
+
+    pub fn hash(&self, algorithm: HashAlgorithm) -> String {
+        self.try_hash(algorithm).unwrap_or_default()
+    }
+
+    pub fn try_hash(&self, algorithm: HashAlgorithm) -> Result<String, Box<dyn std::error::Error>> {
         use std::io::Read;
 
-        use sha1::Digest;
+        if algorithm == HashAlgorithm::Blake3 {
+            if let Some(hash) = self.try_hash_blake3_mapped() {
+                return Ok(hash);
+            }
+        }
 
-        let mut hasher = sha1::Sha1::new();
+        let mut hasher = Hasher::new(algorithm);
 
         let file = self.open()?;
         let mut reader = std::io::BufReader::new(file);
@@ -958,7 +1177,26 @@ impl StrictPath {
             hasher.update(&buffer[..read]);
         }
 
-        Ok(format!("{:x}", hasher.finalize()))
+        Ok(hasher.finalize())
+    }
+
+    /// Hash a large file by memory-mapping it and splitting the work across threads.
+    /// Returns `None` for small files or if the file can't be mapped,
+    /// in which case the caller should stream the content instead.
+    fn try_hash_blake3_mapped(&self) -> Option<String> {
+        let path = self.as_std_path_buf().ok()?;
+        if std::fs::metadata(&path).ok()?.len() < BLAKE3_MAPPED_HASH_THRESHOLD {
+            return None;
+        }
+
+        let mut hasher = blake3::Hasher::new();
+        match hasher.update_mmap_rayon(&path) {
+            Ok(_) => Some(hasher.finalize().to_hex().to_string()),
+            Err(e) => {
+                log::debug!("Unable to hash mapped file, will stream instead: {:?} | {e}", self);
+                None
+            }
+        }
     }
 }
 
@@ -1095,6 +1333,42 @@ mod tests {
             assert!(!StrictPath::new(format!("{}/fake", repo())).exists());
         }
 
+        #[test]
+        fn can_hash_with_each_algorithm() {
+            let path = StrictPath::new(format!("{}/tests/root3/game5/data/file1.txt", repo()));
+            assert_eq!(
+                s("3a52ce780950d4d969792a2559cd519d7ee8c727"),
+                path.hash(HashAlgorithm::Sha1)
+            );
+            assert_eq!(
+                s("cdb4ee2aea69cc6a83331bbe96dc2caa9a299d21329efb0336fc02a82e1839a8"),
+                path.hash(HashAlgorithm::Sha256)
+            );
+            assert_eq!(
+                s("a2b910880c859d384db4e83c839bb0442c90f331bd609c832349ebd04bbda043"),
+                path.hash(HashAlgorithm::Blake3)
+            );
+        }
+
+        #[test]
+        fn can_hash_large_file_with_blake3() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-blake3-mapped"));
+            let _ = path.remove();
+            let file = path.joined("large.bin");
+            file.create_parent_dir().unwrap();
+
+            let content: Vec<u8> = (0..BLAKE3_MAPPED_HASH_THRESHOLD * 2).map(|x| (x % 251) as u8).collect();
+            std::fs::write(file.as_std_path_buf().unwrap(), &content).unwrap();
+
+            let mut streamed = Hasher::new(HashAlgorithm::Blake3);
+            streamed.update(&content);
+
+            assert!(file.try_hash_blake3_mapped().is_some());
+            assert_eq!(streamed.finalize(), file.hash(HashAlgorithm::Blake3));
+
+            let _ = path.remove();
+        }
+
         #[test]
         fn can_split_drive_for_windows_path() {
             assert_eq!((s("C:"), s("foo/bar")), StrictPath::new(s("C:/foo/bar")).split_drive());
@@ -1177,6 +1451,17 @@ mod tests {
                 .is_err());
         }
 
+        #[test]
+        fn checks_available_space_of_nearest_existing_parent() {
+            let disks = sysinfo::Disks::new_with_refreshed_list();
+            let existing = StrictPath::new(format!("{}/tests", repo()));
+            let missing = StrictPath::new(format!("{}/tests/nonexistent/subdir", repo()));
+
+            let space = existing.available_space(&disks);
+            assert!(space.is_some());
+            assert_eq!(space, missing.available_space(&disks));
+        }
+
         #[test]
         fn can_replace() {
             // Identical
@@ -1227,6 +1512,52 @@ mod tests {
                 StrictPath::new("/foo").replace(&StrictPath::new("/foo"), &StrictPath::new("")),
             );
         }
+
+        #[test]
+        fn can_cancel_copy_partway() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-cancel-copy"));
+            let _ = path.remove();
+            let source = path.joined("source.bin");
+            let target = path.joined("nested/target.bin");
+            source.create_parent_dir().unwrap();
+            source.write_with_bytes(&vec![1; CANCELLABLE_COPY_CHUNK * 3]).unwrap();
+
+            // The flag is only checked after a chunk has been written,
+            // so this stops partway through the file.
+            let cancel = AtomicBool::new(true);
+            let error = source.copy_to_path("test", &target, Some(&cancel)).unwrap_err();
+            assert_eq!(std::io::ErrorKind::Interrupted, error.kind());
+            assert!(!target.exists());
+
+            cancel.store(false, Ordering::Relaxed);
+            source.copy_to_path("test", &target, Some(&cancel)).unwrap();
+            assert_eq!(source.sha1(), target.sha1());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_identify_hardlinks() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-hardlink-identity"));
+            let _ = path.remove();
+            let original = path.joined("original.txt");
+            let link = path.joined("link.txt");
+            let separate = path.joined("separate.txt");
+            original.create_parent_dir().unwrap();
+            original.write_with_content("foo").unwrap();
+            separate.write_with_content("foo").unwrap();
+
+            // A file with a single link has nothing to share an identity with.
+            assert_eq!(None, original.hardlink_identity());
+
+            link.create_hardlink(&original).unwrap();
+            assert!(original.hardlink_identity().is_some());
+            assert_eq!(original.hardlink_identity(), link.hardlink_identity());
+            assert_eq!(None, separate.hardlink_identity());
+            assert_eq!(None, path.joined("missing.txt").hardlink_identity());
+
+            let _ = path.remove();
+        }
     }
 
     mod strict_path_display_and_access {
@@ -1486,6 +1817,13 @@ mod tests {
             assert_eq!(Ok(home()), path.access());
         }
 
+        #[test]
+        fn exe_placeholder() {
+            let exe_dir = current_exe_dir().unwrap();
+            let path = StrictPath::new("<exe>/backups".to_owned());
+            assert_eq!(StrictPath::new(format!("{exe_dir}/backups")).access(), path.access());
+        }
+
         #[test]
         fn empty() {
             let path = StrictPath::from("");
@@ -7,7 +7,10 @@ use std::{
 use itertools::Itertools;
 
 pub use crate::path::StrictPath;
-use crate::{path::CommonPath, resource::manifest::Os};
+use crate::{
+    path::CommonPath,
+    resource::{config::HashAlgorithm, manifest::Os},
+};
 
 pub static VERSION: LazyLock<&'static str> =
     LazyLock::new(|| option_env!("LUDUSAVI_VERSION").unwrap_or(env!("CARGO_PKG_VERSION")));
@@ -48,6 +51,7 @@ pub const ENV_DEBUG: &str = "LUDUSAVI_DEBUG";
 const ENV_THREADS: &str = "LUDUSAVI_THREADS";
 #[allow(unused)]
 pub const ENV_LINUX_APP_ID: &str = "LUDUSAVI_LINUX_APP_ID";
+pub const ENV_ZIP_PASSWORD: &str = "LUDUSAVI_ZIP_PASSWORD";
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Finality {
@@ -92,33 +96,140 @@ pub enum Error {
     ConfigInvalid {
         why: String,
     },
+    ConfigKeyUnrecognized {
+        key: String,
+        nearest: Vec<String>,
+    },
+    ConfigValueInvalid {
+        key: String,
+        why: String,
+    },
     CliUnrecognizedGames {
         games: Vec<String>,
     },
     CliUnableToRequestConfirmation,
     CliBackupIdWithMultipleGames,
+
+    CliInvalidTimeWindow,
     CliInvalidBackupId,
+    CliFileNotBackedUp {
+        path: StrictPath,
+    },
+    CliBackupHasNoRegistry,
+    CliTargetRootUnavailable {
+        game: String,
+    },
+    CliGamesFileEmpty {
+        path: StrictPath,
+    },
     SomeEntriesFailed,
     CannotPrepareBackupTarget {
         path: StrictPath,
     },
+    InsufficientSpace {
+        path: StrictPath,
+        required: u64,
+        available: u64,
+    },
     RestorationSourceInvalid {
         path: StrictPath,
     },
     #[cfg_attr(not(target_os = "windows"), allow(unused))]
     RegistryIssue,
     UnableToOpenDir(StrictPath),
+    UnableToReadFile(StrictPath),
+    UnableToWriteFile(StrictPath),
     UnableToOpenUrl(String),
     RcloneUnavailable,
     CloudNotConfigured,
     CloudPathInvalid,
+    CloudLocalPathUnavailable {
+        path: StrictPath,
+    },
+    CloudBandwidthLimitInvalid,
     UnableToConfigureCloud(CommandError),
     UnableToSynchronizeCloud(CommandError),
     CloudConflict,
     GameDidNotLaunch {
         why: String,
     },
+    GameLaunchCommandUnknown,
+    ExportNameInvalid {
+        name: String,
+    },
+    NoDifferentialBackupToMerge,
+    BackupLocked {
+        backup: String,
+    },
+    BackupMergeFailed {
+        why: String,
+    },
+    BackupPasswordMissing,
+    BackupPasswordIncorrect {
+        backup: String,
+    },
+    BundleInvalid {
+        path: StrictPath,
+        why: String,
+    },
+    BundleGameAlreadyBackedUp {
+        game: String,
+    },
+    BundleImportFailed {
+        why: String,
+    },
 }
+
+impl Error {
+    /// Stable, machine-readable identifier for this kind of error.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ManifestInvalid { .. } => "ManifestInvalid",
+            Self::ManifestCannotBeUpdated { .. } => "ManifestCannotBeUpdated",
+            Self::ConfigInvalid { .. } => "ConfigInvalid",
+            Self::ConfigKeyUnrecognized { .. } => "ConfigKeyUnrecognized",
+            Self::ConfigValueInvalid { .. } => "ConfigValueInvalid",
+            Self::CliUnrecognizedGames { .. } => "CliUnrecognizedGames",
+            Self::CliUnableToRequestConfirmation => "CliUnableToRequestConfirmation",
+            Self::CliBackupIdWithMultipleGames => "CliBackupIdWithMultipleGames",
+            Self::CliInvalidTimeWindow => "CliInvalidTimeWindow",
+            Self::CliInvalidBackupId => "CliInvalidBackupId",
+            Self::CliFileNotBackedUp { .. } => "CliFileNotBackedUp",
+            Self::CliBackupHasNoRegistry => "CliBackupHasNoRegistry",
+            Self::CliTargetRootUnavailable { .. } => "CliTargetRootUnavailable",
+            Self::CliGamesFileEmpty { .. } => "CliGamesFileEmpty",
+            Self::SomeEntriesFailed => "SomeEntriesFailed",
+            Self::CannotPrepareBackupTarget { .. } => "CannotPrepareBackupTarget",
+            Self::InsufficientSpace { .. } => "InsufficientSpace",
+            Self::RestorationSourceInvalid { .. } => "RestorationSourceInvalid",
+            Self::RegistryIssue => "RegistryIssue",
+            Self::UnableToOpenDir(_) => "UnableToOpenDir",
+            Self::UnableToReadFile(_) => "UnableToReadFile",
+            Self::UnableToWriteFile(_) => "UnableToWriteFile",
+            Self::UnableToOpenUrl(_) => "UnableToOpenUrl",
+            Self::RcloneUnavailable => "RcloneUnavailable",
+            Self::CloudNotConfigured => "CloudNotConfigured",
+            Self::CloudPathInvalid => "CloudPathInvalid",
+            Self::CloudLocalPathUnavailable { .. } => "CloudLocalPathUnavailable",
+            Self::CloudBandwidthLimitInvalid => "CloudBandwidthLimitInvalid",
+            Self::UnableToConfigureCloud(_) => "UnableToConfigureCloud",
+            Self::UnableToSynchronizeCloud(_) => "UnableToSynchronizeCloud",
+            Self::CloudConflict => "CloudConflict",
+            Self::GameDidNotLaunch { .. } => "GameDidNotLaunch",
+            Self::GameLaunchCommandUnknown => "GameLaunchCommandUnknown",
+            Self::ExportNameInvalid { .. } => "ExportNameInvalid",
+            Self::NoDifferentialBackupToMerge => "NoDifferentialBackupToMerge",
+            Self::BackupLocked { .. } => "BackupLocked",
+            Self::BackupMergeFailed { .. } => "BackupMergeFailed",
+            Self::BackupPasswordMissing => "BackupPasswordMissing",
+            Self::BackupPasswordIncorrect { .. } => "BackupPasswordIncorrect",
+            Self::BundleInvalid { .. } => "BundleInvalid",
+            Self::BundleGameAlreadyBackedUp { .. } => "BundleGameAlreadyBackedUp",
+            Self::BundleImportFailed { .. } => "BundleImportFailed",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CommandError {
     Launched {
@@ -137,6 +248,14 @@ pub enum CommandError {
         stdout: Option<String>,
         stderr: Option<String>,
     },
+    Cancelled {
+        program: String,
+        args: Vec<String>,
+    },
+    TimedOut {
+        program: String,
+        args: Vec<String>,
+    },
 }
 
 impl CommandError {
@@ -145,6 +264,8 @@ impl CommandError {
             Self::Launched { program, args, .. } => format!("{} {}", program, args.join(" ")),
             Self::Terminated { program, args } => format!("{} {}", program, args.join(" ")),
             Self::Exited { program, args, .. } => format!("{} {}", program, args.join(" ")),
+            Self::Cancelled { program, args } => format!("{} {}", program, args.join(" ")),
+            Self::TimedOut { program, args } => format!("{} {}", program, args.join(" ")),
         }
     }
 }
@@ -175,10 +296,65 @@ pub fn filter_map_walkdir(context: &str, e: Result<walkdir::DirEntry, walkdir::E
 
 #[cfg_attr(not(target_os = "windows"), allow(unused))]
 pub fn sha1(content: String) -> String {
-    use sha1::Digest;
-    let mut hasher = sha1::Sha1::new();
-    hasher.update(content);
-    format!("{:x}", hasher.finalize())
+    hash(content, HashAlgorithm::Sha1)
+}
+
+pub fn hash(content: String, algorithm: HashAlgorithm) -> String {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(content.as_bytes());
+    hasher.finalize()
+}
+
+/// Incremental hashing with any of the supported algorithms.
+pub enum Hasher {
+    Sha1(sha1::Sha1),
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        use sha1::Digest;
+
+        match algorithm {
+            HashAlgorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        use sha1::Digest;
+
+        match self {
+            Self::Sha1(hasher) => hasher.update(data),
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    pub fn finalize(self) -> String {
+        use sha1::Digest;
+
+        match self {
+            Self::Sha1(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 pub fn get_threads_from_env() -> Option<NonZeroUsize> {
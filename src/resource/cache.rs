@@ -8,8 +8,12 @@ use crate::{
         manifest::ManifestUpdate,
         ResourceFile, SaveableResourceFile,
     },
+    scan::OperationWarning,
 };
 
+/// How soon after a restore to warn about restoring the same game again.
+const RECENT_RESTORE_MINUTES: i64 = 60;
+
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct Cache {
@@ -43,6 +47,8 @@ pub type Manifests = BTreeMap<String, Manifest>;
 #[serde(default)]
 pub struct Manifest {
     pub etag: Option<String>,
+    /// Value of the `Last-Modified` header, for servers that don't send an ETag.
+    pub last_modified: Option<String>,
     pub checked: Option<chrono::DateTime<chrono::Utc>>,
     pub updated: Option<chrono::DateTime<chrono::Utc>>,
 }
@@ -51,12 +57,33 @@ pub struct Manifest {
 #[serde(default)]
 pub struct Backup {
     pub recent_games: BTreeSet<String>,
+    /// When a complete backup last hashed every file, for `backup.quickScan`.
+    pub last_full_scan: Option<chrono::DateTime<chrono::Utc>>,
+    /// When each game was last picked for a `backup --batch` run.
+    /// Games without any saves never get a backup,
+    /// so this lets the batch rotate past them.
+    pub batch_attempts: BTreeMap<String, chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct Restore {
     pub recent_games: BTreeSet<String>,
+    /// When each game was last restored on this system.
+    /// This is only informational, to help avoid restoring the same backup twice.
+    pub last_restored: BTreeMap<String, chrono::DateTime<chrono::Utc>>,
+}
+
+impl Restore {
+    /// If the game was restored within the last `RECENT_RESTORE_MINUTES`,
+    /// then return a warning about restoring it again.
+    pub fn recent_warning(&self, game: &str, now: &chrono::DateTime<chrono::Utc>) -> Option<OperationWarning> {
+        let last = self.last_restored.get(game)?;
+        let minutes = (*now - *last).num_minutes();
+        (0..RECENT_RESTORE_MINUTES)
+            .contains(&minutes)
+            .then_some(OperationWarning::RecentlyRestored { minutes })
+    }
 }
 
 impl ResourceFile for Cache {
@@ -117,6 +144,7 @@ impl Cache {
     pub fn update_manifest(&mut self, update: ManifestUpdate) {
         let cached = self.manifests.entry(update.url).or_default();
         cached.etag = update.etag;
+        cached.last_modified = update.last_modified;
         cached.checked = Some(update.timestamp);
         if update.modified {
             cached.updated = Some(update.timestamp);
@@ -148,3 +176,37 @@ impl Cache {
         now.signed_duration_since(self.release.checked).num_hours() >= 24
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use velcro::btree_map;
+
+    use super::*;
+    use crate::testing::s;
+
+    #[test]
+    fn can_warn_about_recent_restore() {
+        let now = chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap()
+            .and_utc();
+        let restore = Restore {
+            last_restored: btree_map! {
+                s("recent"): now - chrono::Duration::minutes(5),
+                s("old"): now - chrono::Duration::minutes(RECENT_RESTORE_MINUTES),
+                s("future"): now + chrono::Duration::minutes(5),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Some(OperationWarning::RecentlyRestored { minutes: 5 }),
+            restore.recent_warning("recent", &now)
+        );
+        assert_eq!(None, restore.recent_warning("old", &now));
+        assert_eq!(None, restore.recent_warning("future", &now));
+        assert_eq!(None, restore.recent_warning("unknown", &now));
+    }
+}
@@ -6,16 +6,26 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use itertools::Itertools;
+use schemars::schema::{Schema, SchemaObject, SingleOrVec};
+
 use crate::{
     cloud::Remote,
     lang::{Language, TRANSLATOR},
     path::CommonPath,
-    prelude::{app_dir, EditAction, Error, RedirectEditActionField, StrictPath, AVAILABLE_PARALELLISM},
+    prelude::{
+        app_dir, EditAction, Error, RedirectEditActionField, StrictPath, AVAILABLE_PARALELLISM, ENV_ZIP_PASSWORD,
+        INVALID_FILE_CHARS,
+    },
     resource::{
-        manifest::{self, CloudMetadata, Manifest, Store},
+        manifest::{self, CloudMetadata, Manifest, Os, Store},
         ResourceFile, SaveableResourceFile,
     },
-    scan::{registry::RegistryItem, ScanKind},
+    scan::{
+        layout::escape_folder_name,
+        registry::{self, RegistryItem},
+        BackupHooks, FileExclusion, ScanKind,
+    },
 };
 
 pub const MANIFEST_URL: &str =
@@ -30,6 +40,7 @@ pub enum Event {
     Theme(Theme),
     Language(Language),
     CheckRelease(bool),
+    ShowTray(bool),
     BackupTarget(String),
     RestoreSource(String),
     Root(EditAction),
@@ -40,15 +51,25 @@ pub enum Event {
     SecondaryManifestKind(usize, SecondaryManifestConfigKind),
     CustomGameKind(usize, CustomGameKind),
     CustomGameIntegration(usize, Integration),
+    CustomGameStore(usize, Option<Store>),
+    CustomGameFormat(usize, Option<BackupFormat>),
     Redirect(EditAction, Option<RedirectEditActionField>),
     ReverseRedirectsOnRestore(bool),
+    RedirectFallbackOnRestore(bool),
+    ConflictPolicy(ConflictPolicy),
     CustomGame(EditAction),
     CustomGameAlias(usize, String),
     CustomGaleAliasDisplay(usize, bool),
     CustomGameFile(usize, EditAction),
     CustomGameRegistry(usize, EditAction),
     CustomGameInstallDir(usize, EditAction),
+    CustomGameInclude(usize, EditAction),
+    CustomGameExclusion(usize, EditAction),
     ExcludeStoreScreenshots(bool),
+    MaxFileSize(Option<u64>),
+    MinFileSize(Option<u64>),
+    ExcludeEmptyFiles(bool),
+    ExcludeEmptyDirs(bool),
     CloudFilter(CloudFilter),
     BackupFilterIgnoredPath(EditAction),
     BackupFilterIgnoredRegistry(EditAction),
@@ -96,8 +117,11 @@ pub enum Event {
     RcloneArguments(String),
     CloudRemoteId(String),
     CloudPath(String),
+    CloudTransfers(Option<usize>),
+    CloudBandwidthLimit(String),
     SortCustomGames,
     OnlyConstructiveBackups(bool),
+    FollowSymlinks(bool),
 }
 
 /// Settings for `config.yaml`
@@ -109,10 +133,12 @@ pub struct Config {
     pub manifest: ManifestConfig,
     pub language: Language,
     pub theme: Theme,
+    pub tray: Tray,
     pub roots: Vec<Root>,
     pub redirects: Vec<RedirectConfig>,
     pub backup: BackupConfig,
     pub restore: RestoreConfig,
+    pub export: ExportConfig,
     pub scan: Scan,
     pub cloud: Cloud,
     pub apps: Apps,
@@ -140,6 +166,14 @@ impl Default for Release {
     }
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Tray {
+    /// Whether to show an icon in the system tray while the GUI is open.
+    /// The icon's menu lets you back up all games or bring the window to the front.
+    pub enabled: bool,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct ManifestConfig {
@@ -538,10 +572,13 @@ pub enum RedirectKind {
     #[default]
     Restore,
     Bidirectional,
+    /// The source is a regular expression, and the target may refer to its capture groups.
+    /// This applies in both backup and restore mode.
+    Regex,
 }
 
 impl RedirectKind {
-    pub const ALL: &'static [Self] = &[Self::Backup, Self::Restore, Self::Bidirectional];
+    pub const ALL: &'static [Self] = &[Self::Backup, Self::Restore, Self::Bidirectional, Self::Regex];
 }
 
 impl ToString for RedirectKind {
@@ -600,9 +637,25 @@ pub struct BackupFilter {
     /// Globally ignored paths.
     pub ignored_paths: Vec<StrictPath>,
     /// Globally ignored registry keys.
+    /// Entries may be glob patterns, which can also match individual values,
+    /// such as `HKEY_CURRENT_USER/Software/Game/*/lastPlayed`.
     pub ignored_registry: Vec<RegistryItem>,
+    /// Exclude files larger than this many bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_file_size: Option<u64>,
+    /// Exclude files smaller than this many bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_file_size: Option<u64>,
+    /// Exclude files that are completely empty.
+    /// Some games rely on empty marker files, so use this with care.
+    pub exclude_empty_files: bool,
+    /// When restoring, don't leave behind any new folders that end up empty,
+    /// such as when all of their files fail to restore.
+    pub exclude_empty_dirs: bool,
     #[serde(skip)]
     pub path_globs: Arc<Mutex<Option<globset::GlobSet>>>,
+    #[serde(skip)]
+    pub registry_globs: Arc<Mutex<Option<globset::GlobSet>>>,
 }
 
 impl std::fmt::Debug for BackupFilter {
@@ -612,6 +665,10 @@ impl std::fmt::Debug for BackupFilter {
             .field("cloud", &self.cloud)
             .field("ignored_paths", &self.ignored_paths)
             .field("ignored_registry", &self.ignored_registry)
+            .field("max_file_size", &self.max_file_size)
+            .field("min_file_size", &self.min_file_size)
+            .field("exclude_empty_files", &self.exclude_empty_files)
+            .field("exclude_empty_dirs", &self.exclude_empty_dirs)
             .finish()
     }
 }
@@ -623,11 +680,17 @@ impl PartialEq for BackupFilter {
         self.exclude_store_screenshots == other.exclude_store_screenshots
             && self.ignored_paths == other.ignored_paths
             && self.ignored_registry == other.ignored_registry
+            && self.max_file_size == other.max_file_size
+            && self.min_file_size == other.min_file_size
+            && self.exclude_empty_files == other.exclude_empty_files
+            && self.exclude_empty_dirs == other.exclude_empty_dirs
     }
 }
 
 impl BackupFilter {
     pub fn build_globs(&mut self) {
+        self.build_registry_globs();
+
         let mut path_globs = self.path_globs.lock().unwrap();
         if self.ignored_paths.is_empty() {
             *path_globs = None;
@@ -659,6 +722,51 @@ impl BackupFilter {
         *path_globs = builder.build().ok();
     }
 
+    fn build_registry_globs(&mut self) {
+        let mut registry_globs = self.registry_globs.lock().unwrap();
+
+        let patterns: Vec<_> = self
+            .ignored_registry
+            .iter()
+            .map(|x| x.render())
+            .filter(|x| x.contains(['*', '?', '[', '{']))
+            .collect();
+        if patterns.is_empty() {
+            *registry_globs = None;
+            return;
+        }
+
+        let mut builder = globset::GlobSetBuilder::new();
+        for normalized in patterns {
+            let variants = vec![
+                normalized.to_string(),
+                // If the pattern matches a key, we also want to include its subkeys and values.
+                format!("{}/**", &normalized),
+            ];
+
+            for variant in variants {
+                if let Ok(glob) = globset::GlobBuilder::new(&variant)
+                    .literal_separator(true)
+                    .backslash_escape(false)
+                    .case_insensitive(true)
+                    .build()
+                {
+                    builder.add(glob);
+                }
+            }
+        }
+
+        *registry_globs = builder.build().ok();
+    }
+
+    fn is_registry_glob_match(&self, rendered: &str) -> bool {
+        let registry_globs = self.registry_globs.lock().unwrap();
+        registry_globs
+            .as_ref()
+            .map(|set| set.is_match(rendered))
+            .unwrap_or(false)
+    }
+
     pub fn is_path_ignored(&self, item: &StrictPath) -> bool {
         if self.ignored_paths.is_empty() {
             return false;
@@ -680,6 +788,28 @@ impl BackupFilter {
         self.ignored_registry
             .iter()
             .any(|x| x.is_prefix_of(item) || x.interpret() == interpreted)
+            || self.is_registry_glob_match(&item.render())
+    }
+
+    #[cfg_attr(not(target_os = "windows"), allow(unused))]
+    pub fn is_registry_value_ignored(&self, key: &RegistryItem, value: &str) -> bool {
+        if self.ignored_registry.is_empty() {
+            return false;
+        }
+        self.is_registry_ignored(key) || self.is_registry_glob_match(&format!("{}/{}", key.render(), value))
+    }
+
+    /// Files exactly at a limit are still included.
+    pub fn size_exclusion(&self, size: u64) -> Option<FileExclusion> {
+        if self.exclude_empty_files && size == 0 {
+            return Some(FileExclusion::Empty);
+        }
+
+        match (self.max_file_size, self.min_file_size) {
+            (Some(limit), _) if size > limit => Some(FileExclusion::TooLarge { limit }),
+            (_, Some(limit)) if size < limit => Some(FileExclusion::TooSmall { limit }),
+            _ => None,
+        }
     }
 
     pub fn excludes(&self, explicit: bool, has_backup: bool, info: &CloudMetadata) -> bool {
@@ -841,15 +971,64 @@ pub struct Sort {
     pub reversed: bool,
 }
 
+#[derive(Clone, Debug, Copy, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum RetentionPolicy {
+    /// Keep a certain number of full and differential backups.
+    #[default]
+    Count,
+    /// Keep backups based on how old they are, according to `age`.
+    Age,
+}
+
+/// Time-based retention, where older backups are thinned out gradually.
+/// Each window is measured back from the current time,
+/// and a backup is kept if any of the windows keeps it.
+/// Backups older than all of the windows are forgotten,
+/// except for the newest full backup and its newest differential backup.
+#[derive(Clone, Debug, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AgeRetention {
+    /// Keep every backup from this many hours.
+    pub within_hours: u32,
+    /// Keep the newest backup from each hour for this many hours.
+    pub hourly: u32,
+    /// Keep the newest backup from each day for this many days.
+    pub daily: u32,
+    /// Keep the newest backup from each week for this many weeks.
+    pub weekly: u32,
+}
+
+impl Default for AgeRetention {
+    fn default() -> Self {
+        Self {
+            within_hours: 0,
+            hourly: 24,
+            daily: 7,
+            weekly: 4,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Retention {
     /// Full backups to keep. Range: 1-255.
+    /// With the `age` policy, this only decides when to start a new full backup.
+    #[serde(deserialize_with = "deserialize_full_retention")]
     pub full: u8,
     /// Differential backups to keep. Range: 0-255.
+    /// With the `age` policy, this only decides when to start a new full backup.
     pub differential: u8,
+    /// How to decide which backups to forget.
+    pub policy: RetentionPolicy,
+    /// Limits for the `age` policy.
+    pub age: AgeRetention,
     #[serde(skip)]
     pub force_new_full: bool,
+    /// Don't store a single full backup directly in the game folder.
+    #[serde(skip)]
+    pub force_timestamped_name: bool,
 }
 
 impl Retention {
@@ -876,6 +1055,104 @@ impl Retention {
             ..self
         }
     }
+
+    pub fn with_force_timestamped_name(self, force: bool) -> Self {
+        Self {
+            force_timestamped_name: force,
+            ..self
+        }
+    }
+}
+
+fn deserialize_full_retention<'de, D>(deserializer: D) -> Result<u8, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize;
+
+    let full = u8::deserialize(deserializer)?;
+    if full == 0 {
+        return Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Unsigned(0),
+            &"a full retention between 1 and 255",
+        ));
+    }
+    Ok(full)
+}
+
+fn deserialize_optional_full_retention<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_full_retention(deserializer).map(Some)
+}
+
+/// Retention settings for a specific game.
+/// Any fields that are not set here are taken from the global retention.
+#[derive(Clone, Debug, Copy, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RetentionOverride {
+    /// Full backups to keep. Range: 1-255.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_optional_full_retention"
+    )]
+    pub full: Option<u8>,
+    /// Differential backups to keep. Range: 0-255.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub differential: Option<u8>,
+    /// How to decide which backups to forget.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<RetentionPolicy>,
+    /// Limits for the `age` policy.
+    #[serde(skip_serializing_if = "AgeRetentionOverride::is_empty")]
+    pub age: AgeRetentionOverride,
+}
+
+impl RetentionOverride {
+    pub fn apply(&self, global: Retention) -> Retention {
+        Retention {
+            full: self.full.unwrap_or(global.full),
+            differential: self.differential.unwrap_or(global.differential),
+            policy: self.policy.unwrap_or(global.policy),
+            age: self.age.apply(global.age),
+            ..global
+        }
+    }
+}
+
+/// Time-based retention settings for a specific game.
+/// Any windows that are not set here are taken from the global retention.
+#[derive(Clone, Debug, Copy, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AgeRetentionOverride {
+    /// Keep every backup from this many hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_hours: Option<u32>,
+    /// Keep the newest backup from each hour for this many hours.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hourly: Option<u32>,
+    /// Keep the newest backup from each day for this many days.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily: Option<u32>,
+    /// Keep the newest backup from each week for this many weeks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weekly: Option<u32>,
+}
+
+impl AgeRetentionOverride {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn apply(&self, global: AgeRetention) -> AgeRetention {
+        AgeRetention {
+            within_hours: self.within_hours.unwrap_or(global.within_hours),
+            hourly: self.hourly.unwrap_or(global.hourly),
+            daily: self.daily.unwrap_or(global.daily),
+            weekly: self.weekly.unwrap_or(global.weekly),
+        }
+    }
 }
 
 impl Default for Retention {
@@ -883,7 +1160,10 @@ impl Default for Retention {
         Self {
             full: 1,
             differential: 0,
+            policy: RetentionPolicy::Count,
+            age: AgeRetention::default(),
             force_new_full: false,
+            force_timestamped_name: false,
         }
     }
 }
@@ -894,11 +1174,40 @@ pub enum BackupFormat {
     #[default]
     Simple,
     Zip,
+    Tar,
+    TarGz,
 }
 
 impl BackupFormat {
-    pub const ALL: &'static [Self] = &[Self::Simple, Self::Zip];
-    pub const ALL_NAMES: &'static [&'static str] = &["simple", "zip"];
+    pub const ALL: &'static [Self] = &[Self::Simple, Self::Zip, Self::Tar, Self::TarGz];
+    pub const ALL_NAMES: &'static [&'static str] = &["simple", "zip", "tar", "tarGz"];
+
+    /// File extension of the archive, if this is an archive format.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::Simple => None,
+            Self::Zip => Some("zip"),
+            Self::Tar => Some("tar"),
+            Self::TarGz => Some("tar.gz"),
+        }
+    }
+
+    /// Detect the format of an existing backup by its file name.
+    pub fn from_name(name: &str) -> Self {
+        if name.ends_with(".zip") {
+            Self::Zip
+        } else if name.ends_with(".tar.gz") {
+            Self::TarGz
+        } else if name.ends_with(".tar") {
+            Self::Tar
+        } else {
+            Self::Simple
+        }
+    }
+
+    pub fn is_tar(&self) -> bool {
+        matches!(self, Self::Tar | Self::TarGz)
+    }
 }
 
 impl std::str::FromStr for BackupFormat {
@@ -908,6 +1217,8 @@ impl std::str::FromStr for BackupFormat {
         match s {
             "simple" => Ok(Self::Simple),
             "zip" => Ok(Self::Zip),
+            "tar" => Ok(Self::Tar),
+            "tarGz" => Ok(Self::TarGz),
             _ => Err(format!("invalid backup format: {}", s)),
         }
     }
@@ -929,49 +1240,81 @@ pub struct BackupFormats {
     /// Settings for specific compression methods.
     /// In compression levels, higher numbers are slower, but save more space.
     pub compression: Compression,
+    /// Store changed files in differential backups as binary deltas
+    /// against the full backup, rather than copying the whole file.
+    /// This saves space when large files only change slightly,
+    /// but those files can then only be restored through Ludusavi.
+    /// Only applies to the simple format.
+    pub delta: bool,
+    /// Store each distinct file content only once in a shared folder,
+    /// so that identical files across games and backups don't take up extra space.
+    /// Unreferenced content is cleaned up after backups and maintenance.
+    /// Only applies to the simple format.
+    pub dedupe: bool,
+    /// Store files directly under each backup folder by their path without the drive,
+    /// rather than in a `drive-*` folder, so that backups are easier to browse.
+    /// Backups with files from more than one drive still use `drive-*` folders.
+    /// Only applies to the simple format.
+    pub flat: bool,
 }
 
 impl BackupFormats {
-    pub fn level(&self) -> Option<i32> {
+    fn method(&self) -> ZipCompression {
         match self.chosen {
-            BackupFormat::Simple => None,
-            BackupFormat::Zip => match self.zip.compression {
-                ZipCompression::None => None,
-                ZipCompression::Deflate => Some(self.compression.deflate.level),
-                ZipCompression::Bzip2 => Some(self.compression.bzip2.level),
-                ZipCompression::Zstd => Some(self.compression.zstd.level),
-            },
+            BackupFormat::Simple | BackupFormat::Tar => ZipCompression::None,
+            BackupFormat::TarGz => ZipCompression::Deflate,
+            BackupFormat::Zip => self.zip.compression,
         }
     }
 
-    pub fn set_level(&mut self, value: i32) {
-        match self.chosen {
-            BackupFormat::Simple => {}
-            BackupFormat::Zip => match self.zip.compression {
-                ZipCompression::None => {}
-                ZipCompression::Deflate => {
-                    self.compression.deflate.level = value;
-                }
-                ZipCompression::Bzip2 => {
-                    self.compression.bzip2.level = value;
-                }
-                ZipCompression::Zstd => {
-                    self.compression.zstd.level = value;
-                }
-            },
+    fn configured_level(&self) -> Option<i32> {
+        match self.method() {
+            ZipCompression::None => None,
+            ZipCompression::Deflate => Some(self.compression.deflate.level),
+            ZipCompression::Bzip2 => Some(self.compression.bzip2.level),
+            ZipCompression::Zstd => Some(self.compression.zstd.level),
         }
     }
 
-    pub fn range(&self) -> Option<std::ops::RangeInclusive<i32>> {
-        match self.chosen {
-            BackupFormat::Simple => None,
-            BackupFormat::Zip => match self.zip.compression {
-                ZipCompression::None => None,
-                ZipCompression::Deflate => Some(DeflateCompression::RANGE),
-                ZipCompression::Bzip2 => Some(Bzip2Compression::RANGE),
-                ZipCompression::Zstd => Some(ZstdCompression::RANGE),
-            },
+    /// Compression level for the active format,
+    /// clamped to the range supported by its compression method.
+    pub fn level(&self) -> Option<i32> {
+        let level = self.configured_level()?;
+        let range = self.method().level_range()?;
+        Some(level.clamp(*range.start(), *range.end()))
+    }
+
+    /// Encryption for the active format, if it's zip and encryption is enabled.
+    pub fn zip_encryption(&self) -> Option<&ZipEncryption> {
+        self.zip
+            .encryption
+            .as_ref()
+            .filter(|_| self.chosen == BackupFormat::Zip)
+    }
+
+    /// Like `level`, but logs a warning if the configured level had to be clamped.
+    /// This should be used when actually performing a backup.
+    pub fn validated_level(&self) -> Option<i32> {
+        let configured = self.configured_level();
+        let level = self.level();
+        if configured != level {
+            log::warn!(
+                "Compression level {:?} is out of range for {:?}; using {:?} instead",
+                configured,
+                self.method(),
+                level
+            );
         }
+        level
+    }
+
+    pub fn set_level(&mut self, value: i32) {
+        let method = self.method();
+        self.compression.set_level(&method, value);
+    }
+
+    pub fn range(&self) -> Option<std::ops::RangeInclusive<i32>> {
+        self.method().level_range()
     }
 }
 
@@ -980,6 +1323,44 @@ impl BackupFormats {
 pub struct ZipConfig {
     /// Preferred compression method.
     pub compression: ZipCompression,
+    /// Encrypt new zip backups with AES.
+    /// Existing backups keep whatever encryption they were made with.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<ZipEncryption>,
+}
+
+#[derive(Clone, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ZipEncryption {
+    /// Size of the AES key that is derived from the password.
+    pub key_derivation: ZipKeyDerivation,
+    /// Password for new backups and for restoring encrypted ones.
+    /// If this is empty, then the `LUDUSAVI_ZIP_PASSWORD` environment variable is used instead,
+    /// so that the password doesn't need to be saved in this file.
+    pub password: String,
+}
+
+impl std::fmt::Debug for ZipEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The config is logged on startup, so keep the password out of it.
+        f.debug_struct("ZipEncryption")
+            .field("key_derivation", &self.key_derivation)
+            .field("password", &(!self.password.is_empty()).then_some("<redacted>"))
+            .finish()
+    }
+}
+
+/// The key is derived from the password with PBKDF2, as in the WinZip AES format.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ZipKeyDerivation {
+    /// 128-bit key.
+    Aes128,
+    /// 192-bit key.
+    Aes192,
+    /// 256-bit key.
+    #[default]
+    Aes256,
 }
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
@@ -995,6 +1376,16 @@ pub enum ZipCompression {
 impl ZipCompression {
     pub const ALL: &'static [Self] = &[Self::None, Self::Deflate, Self::Bzip2, Self::Zstd];
     pub const ALL_NAMES: &'static [&'static str] = &["none", "deflate", "bzip2", "zstd"];
+
+    /// Compression levels supported by this method, if any.
+    pub fn level_range(&self) -> Option<std::ops::RangeInclusive<i32>> {
+        match self {
+            Self::None => None,
+            Self::Deflate => Some(DeflateCompression::RANGE),
+            Self::Bzip2 => Some(Bzip2Compression::RANGE),
+            Self::Zstd => Some(ZstdCompression::RANGE),
+        }
+    }
 }
 
 impl std::str::FromStr for ZipCompression {
@@ -1017,6 +1408,122 @@ impl ToString for ZipCompression {
     }
 }
 
+#[derive(
+    Copy, Clone, Debug, Default, Eq, PartialEq, Hash, serde::Serialize, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SpaceCheck {
+    /// Skip the backup and report an error.
+    #[default]
+    Abort,
+    /// Make the backup anyway, but report a warning.
+    Warn,
+    /// Don't check the free space.
+    Off,
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum FilenamePolicy {
+    /// Escape anything that would be a problem on any OS,
+    /// including leading dots, which hide folders on Linux and Mac.
+    #[default]
+    Portable,
+    /// Escape characters that Windows doesn't allow and trailing dots.
+    Windows,
+    /// Only escape slashes and null characters.
+    Posix,
+}
+
+impl FilenamePolicy {
+    /// Windows can't create the names that `posix` allows, so use `windows` there instead.
+    pub fn for_host(self, host: Os) -> Self {
+        match (self, host) {
+            (Self::Posix, Os::Windows) => Self::Windows,
+            _ => self,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct QuickScan {
+    /// Skip scanning a game's files again if none of them or their folders
+    /// have been modified since the game's last backup.
+    pub enabled: bool,
+    /// Do a full scan anyway when the last one was at least this many days ago.
+    /// Set to 0 to never force a full scan.
+    pub full_scan_days: u32,
+}
+
+impl Default for QuickScan {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            full_scan_days: 7,
+        }
+    }
+}
+
+impl QuickScan {
+    /// Whether a backup scan right now may skip hashing unmodified files.
+    pub fn is_active(
+        &self,
+        last_full_scan: Option<chrono::DateTime<chrono::Utc>>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if self.full_scan_days == 0 {
+            return true;
+        }
+        match last_full_scan {
+            Some(last) => now - last < chrono::Duration::days(self.full_scan_days as i64),
+            None => false,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictPolicy {
+    /// Replace the existing file.
+    #[default]
+    Overwrite,
+    /// Keep the existing file.
+    Skip,
+    /// Keep the existing file if it was modified after the backup was made.
+    KeepNewer,
+    /// Rename the existing file before replacing it.
+    Backup,
+}
+
+impl ConflictPolicy {
+    pub const ALL: &'static [Self] = &[Self::Overwrite, Self::Skip, Self::KeepNewer, Self::Backup];
+}
+
+impl ToString for ConflictPolicy {
+    fn to_string(&self) -> String {
+        TRANSLATOR.conflict_policy(self)
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Compression {
@@ -1101,6 +1608,11 @@ impl ZstdCompression {
 pub struct BackupConfig {
     /// Full path to a directory in which to save backups.
     pub path: StrictPath,
+    /// Additional directories that receive a copy of each game's backups
+    /// after the backup to `path` succeeds.
+    /// A failure to copy to one of these does not fail the backup itself.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<StrictPath>,
     /// Names of games to skip when backing up.
     pub ignored_games: BTreeSet<String>,
     pub filter: BackupFilter,
@@ -1108,9 +1620,77 @@ pub struct BackupConfig {
     pub toggled_registry: ToggledRegistry,
     pub sort: Sort,
     pub retention: Retention,
+    /// Retention limits for specific games.
+    /// Any settings that a game does not override are taken from `retention`.
+    pub game_retention: BTreeMap<String, RetentionOverride>,
     pub format: BackupFormats,
+    /// Formats for new backups of specific games, used instead of `format.chosen`.
+    /// Existing backups in other formats can still be restored.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub game_format: BTreeMap<String, BackupFormat>,
+    /// Glob patterns to exclude for specific games,
+    /// relative to each folder matched by the game's save paths,
+    /// such as `logs` or `**/*.log`.
+    /// Unlike toggled paths, these still apply if the game's folders move.
+    /// Patterns follow the host's case sensitivity.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub game_exclusions: BTreeMap<String, Vec<String>>,
     /// Don't create a new backup if there are only removed saves and no new/edited ones.
     pub only_constructive: bool,
+    /// Back up symlinked files as regular files, without recording the links.
+    /// When unset, symlinks in simple backups are recreated on restore.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub follow_symlinks: bool,
+    /// Store a checksum of each new backup so that validation can detect later modifications.
+    pub checksum: bool,
+    /// Algorithm used to hash files in new full backups.
+    /// Existing backups keep using the algorithm they were created with.
+    pub hash: HashAlgorithm,
+    /// Template for the comment on each new backup.
+    /// Supported tokens:
+    /// `{game}` (game name),
+    /// `{date}` (UTC timestamp),
+    /// `{os}` (current operating system),
+    /// `{count}` (number of files in the backup).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_template: Option<String>,
+    /// Template for the folder of each game that has not been backed up yet,
+    /// relative to the backup path. Use `/` to create nested folders.
+    /// Supported tokens:
+    /// `{game}` (game name),
+    /// `{store}` (`Steam`, `GOG`, or `Other`, based on the manifest),
+    /// `{id}` (Steam or GOG ID, if known).
+    /// The template must include `{game}`, or else it is ignored.
+    /// Existing backups are not moved when this changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder_template: Option<String>,
+    /// Which characters to escape when naming the folder of a game that has not been backed up yet.
+    /// Existing backups are still found by their mapping files when this changes.
+    pub filename_policy: FilenamePolicy,
+    /// Don't back up games that seem to be running,
+    /// since their saves may be in the middle of being written.
+    /// Running games are still reported with a warning either way.
+    pub skip_running_games: bool,
+    /// What to do when a new backup might not fit in the free space of the backup drive.
+    pub space_check: SpaceCheck,
+    /// Skip hashing files that have not changed since the last backup.
+    pub quick_scan: QuickScan,
+    /// Command to run before backing up each game,
+    /// such as `notify-send "Backing up {game}"`.
+    /// Supported tokens:
+    /// `{game}` (game name),
+    /// `{path}` (game's backup folder).
+    /// Failures are reported as warnings and do not stop the backup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_hook: Option<String>,
+    /// Command to run after backing up each game.
+    /// Supported tokens:
+    /// `{game}` (game name),
+    /// `{path}` (game's backup folder),
+    /// `{status}` (`success` or `failure`).
+    /// Failures are reported as warnings.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_hook: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
@@ -1124,6 +1704,61 @@ pub struct RestoreConfig {
     pub toggled_registry: ToggledRegistry,
     pub sort: Sort,
     pub reverse_redirects: bool,
+    /// If a redirected file's folder can't be created,
+    /// then restore the file to its original location instead.
+    pub redirect_fallback: bool,
+    /// When restoring a backup from Windows on another OS,
+    /// translate its Windows paths (like `C:/Users/...`) into this Wine prefix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wine_prefix: Option<StrictPath>,
+    /// What to do when a file being restored already exists with different content.
+    pub conflict_policy: ConflictPolicy,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ExportConfig {
+    /// Template for naming exported files (without the file extension).
+    /// Supported tokens:
+    /// `{game}` (game name, if exporting a single game),
+    /// `{date}` (UTC timestamp),
+    /// `{os}` (current operating system),
+    /// `{count}` (number of games exported).
+    pub name: String,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            name: "ludusavi-export-{date}".to_string(),
+        }
+    }
+}
+
+impl ExportConfig {
+    pub fn render_name(
+        &self,
+        game: Option<&str>,
+        now: &chrono::DateTime<chrono::Utc>,
+        os: Os,
+        count: usize,
+    ) -> Result<String, Error> {
+        let rendered = self
+            .name
+            .replace("{game}", &game.map(escape_folder_name).unwrap_or_default())
+            .replace("{date}", &now.format("%Y%m%dT%H%M%SZ").to_string())
+            .replace("{os}", os.slug())
+            .replace("{count}", &count.to_string());
+        let rendered = rendered.trim();
+
+        if rendered.is_empty() || rendered == "." || rendered == ".." || rendered.contains(INVALID_FILE_CHARS) {
+            return Err(Error::ExportNameInvalid {
+                name: rendered.to_string(),
+            });
+        }
+
+        Ok(rendered.to_string())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
@@ -1135,6 +1770,11 @@ pub struct Scan {
     pub show_unchanged_games: bool,
     /// In the GUI, show recent games that have not been scanned yet.
     pub show_unscanned_games: bool,
+    /// In the CLI, when a game name doesn't match any known title,
+    /// automatically use the closest title if it is at least this similar.
+    /// Range: 0-100, where 100 disables this.
+    /// Less similar titles are only listed as suggestions.
+    pub fuzzy_match_threshold: u8,
 }
 
 impl Default for Scan {
@@ -1143,6 +1783,7 @@ impl Default for Scan {
             show_deselected_games: true,
             show_unchanged_games: true,
             show_unscanned_games: true,
+            fuzzy_match_threshold: 90,
         }
     }
 }
@@ -1159,6 +1800,31 @@ pub struct Cloud {
     /// If true, upload changes automatically after backing up,
     /// as long as there aren't any conflicts.
     pub synchronize: bool,
+    /// If true, while synchronization is enabled,
+    /// always give new full backups a timestamped name
+    /// instead of storing a single full backup directly in the game folder
+    /// (listed as `.` in the mapping file),
+    /// which some cloud tools may not handle well.
+    pub timestamped_backups: bool,
+    /// If true, when uploading after a backup,
+    /// only synchronize the folders of games whose backups changed.
+    /// If false, synchronize everything covered by the backup operation,
+    /// the same as the conflict check before the backup.
+    pub scope_to_changed: bool,
+    /// Maximum number of files that Rclone will transfer in parallel.
+    /// When unset, Rclone's default is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfers: Option<usize>,
+    /// Bandwidth limit for Rclone, such as `500k` or `1M` (bytes per second).
+    /// Separate upload and download limits can be set like `1M:500k`.
+    /// When unset, there is no limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bandwidth_limit: Option<String>,
+    /// Names of games whose backups are kept locally, but never synchronized with the cloud.
+    /// Their folders in the cloud are also left as-is.
+    /// With deduplication, shared content that only these games use is skipped too.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub excluded_games: BTreeSet<String>,
 }
 
 impl Default for Cloud {
@@ -1167,6 +1833,11 @@ impl Default for Cloud {
             remote: Default::default(),
             path: "ludusavi-backup".to_string(),
             synchronize: true,
+            timestamped_backups: false,
+            scope_to_changed: true,
+            transfers: None,
+            bandwidth_limit: None,
+            excluded_games: BTreeSet::new(),
         }
     }
 }
@@ -1232,6 +1903,15 @@ pub struct CustomGame {
     pub registry: Vec<String>,
     /// Bare folder names where the game has been installed.
     pub install_dir: Vec<String>,
+    /// Names of other games whose file, registry, and installation folder entries
+    /// should be merged into this game.
+    /// For example, this can combine the save locations of a game's demo and full versions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// If set, then the game's paths are always expanded as if it came from this store,
+    /// regardless of which store its roots are configured as.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<Store>,
     #[serde(skip)]
     pub expanded: bool,
 }
@@ -1281,6 +1961,50 @@ impl ToString for CustomGameKind {
     }
 }
 
+/// Choice of a custom game's pinned store, where `None` means to use each root's own store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CustomGameStore(pub Option<Store>);
+
+impl CustomGameStore {
+    pub fn all() -> Vec<Self> {
+        std::iter::once(None)
+            .chain(Store::ALL.iter().copied().map(Some))
+            .map(Self)
+            .collect()
+    }
+}
+
+impl ToString for CustomGameStore {
+    fn to_string(&self) -> String {
+        match self.0 {
+            Some(store) => TRANSLATOR.store(&store),
+            None => TRANSLATOR.none_label(),
+        }
+    }
+}
+
+/// Choice of a game's backup format, where `None` means to use the global format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameBackupFormat(pub Option<BackupFormat>);
+
+impl GameBackupFormat {
+    pub fn all() -> Vec<Self> {
+        std::iter::once(None)
+            .chain(BackupFormat::ALL.iter().copied().map(Some))
+            .map(Self)
+            .collect()
+    }
+}
+
+impl ToString for GameBackupFormat {
+    fn to_string(&self) -> String {
+        match self.0 {
+            Some(format) => TRANSLATOR.backup_format(&format),
+            None => TRANSLATOR.default_label(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub enum Integration {
@@ -1312,18 +2036,81 @@ impl Default for ManifestConfig {
     }
 }
 
+impl BackupConfig {
+    /// Retention limits for a game, falling back to the global limits when there is no override.
+    pub fn retention_for(&self, game: &str) -> Retention {
+        match self.game_retention.get(game) {
+            Some(custom) => custom.apply(self.retention),
+            None => self.retention,
+        }
+    }
+
+    pub fn format_for(&self, game: &str) -> BackupFormats {
+        let mut format = self.format.clone();
+        if let Some(chosen) = self.game_format.get(game) {
+            format.chosen = *chosen;
+        }
+        format
+    }
+
+    /// Subpath exclusions for a game, relative to each folder matched by its save paths.
+    pub fn exclusions_for(&self, game: &str) -> &[String] {
+        self.game_exclusions.get(game).map(|x| x.as_slice()).unwrap_or_default()
+    }
+
+    pub fn set_format_for(&mut self, game: &str, format: Option<BackupFormat>) {
+        match format {
+            Some(format) => {
+                self.game_format.insert(game.to_string(), format);
+            }
+            None => {
+                self.game_format.remove(game);
+            }
+        }
+    }
+
+    pub fn hooks(&self) -> BackupHooks {
+        BackupHooks::new(self.pre_hook.clone(), self.post_hook.clone())
+    }
+
+    /// Mirrors only copy the configured backup folder,
+    /// so they don't apply when working with some other folder (like a separate restore path).
+    pub fn mirrors_for(&self, path: &StrictPath) -> Vec<StrictPath> {
+        if path.equivalent(&self.path) {
+            self.mirrors.clone()
+        } else {
+            vec![]
+        }
+    }
+}
+
 impl Default for BackupConfig {
     fn default() -> Self {
         Self {
             path: default_backup_dir(),
+            mirrors: vec![],
             ignored_games: BTreeSet::new(),
             filter: BackupFilter::default(),
             toggled_paths: Default::default(),
             toggled_registry: Default::default(),
             sort: Default::default(),
             retention: Retention::default(),
+            game_retention: Default::default(),
             format: Default::default(),
+            game_format: Default::default(),
+            game_exclusions: Default::default(),
             only_constructive: Default::default(),
+            follow_symlinks: Default::default(),
+            checksum: Default::default(),
+            hash: Default::default(),
+            comment_template: None,
+            folder_template: None,
+            filename_policy: Default::default(),
+            skip_running_games: false,
+            space_check: Default::default(),
+            quick_scan: Default::default(),
+            pre_hook: None,
+            post_hook: None,
         }
     }
 }
@@ -1337,7 +2124,148 @@ impl Default for RestoreConfig {
             toggled_registry: Default::default(),
             sort: Default::default(),
             reverse_redirects: false,
+            redirect_fallback: false,
+            wine_prefix: None,
+            conflict_policy: Default::default(),
+        }
+    }
+}
+
+fn lookup_key<'a>(root: &'a mut serde_yaml::Value, key: &str) -> Option<&'a mut serde_yaml::Value> {
+    key.split('.').try_fold(root, |value, part| match value {
+        serde_yaml::Value::Mapping(mapping) => mapping.get_mut(&serde_yaml::Value::String(part.to_string())),
+        serde_yaml::Value::Sequence(sequence) => part.parse::<usize>().ok().and_then(|i| sequence.get_mut(i)),
+        _ => None,
+    })
+}
+
+/// Like `lookup_key`, but fills in any unset fields along the way.
+fn insert_key<'a>(root: &'a mut serde_yaml::Value, key: &str) -> Option<&'a mut serde_yaml::Value> {
+    key.split('.').try_fold(root, |value, part| {
+        if value.is_null() {
+            *value = serde_yaml::Value::Mapping(Default::default());
+        }
+        match value {
+            serde_yaml::Value::Mapping(mapping) => {
+                let part = serde_yaml::Value::String(part.to_string());
+                if !mapping.contains_key(&part) {
+                    mapping.insert(part.clone(), serde_yaml::Value::Null);
+                }
+                mapping.get_mut(&part)
+            }
+            serde_yaml::Value::Sequence(sequence) => part.parse::<usize>().ok().and_then(|i| sequence.get_mut(i)),
+            _ => None,
+        }
+    })
+}
+
+/// Find the schema of a field within an object or an item within an array,
+/// looking through references and wrappers like the ones for defaults and optional fields.
+fn schema_child<'a>(
+    definitions: &'a schemars::Map<String, Schema>,
+    schema: &'a SchemaObject,
+    part: &str,
+) -> Option<&'a SchemaObject> {
+    if let Some(reference) = &schema.reference {
+        return match definitions.get(reference.strip_prefix("#/definitions/")?)? {
+            Schema::Object(definition) => schema_child(definitions, definition, part),
+            Schema::Bool(_) => None,
+        };
+    }
+
+    if let Some(subschemas) = &schema.subschemas {
+        let found = subschemas
+            .all_of
+            .iter()
+            .chain(subschemas.any_of.iter())
+            .chain(subschemas.one_of.iter())
+            .flatten()
+            .find_map(|sub| match sub {
+                Schema::Object(sub) => schema_child(definitions, sub, part),
+                Schema::Bool(_) => None,
+            });
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    if let Some(object) = &schema.object {
+        if let Some(Schema::Object(property)) = object.properties.get(part) {
+            return Some(property);
+        }
+        if let Some(Schema::Object(property)) = object.additional_properties.as_deref() {
+            return Some(property);
+        }
+    }
+
+    if let Some(array) = &schema.array {
+        if let (Ok(_), Some(SingleOrVec::Single(item))) = (part.parse::<usize>(), &array.items) {
+            if let Schema::Object(item) = item.as_ref() {
+                return Some(item);
+            }
+        }
+    }
+
+    None
+}
+
+/// Whether the config format has a field at this dotted path,
+/// even if the field is currently unset and so left out of the serialized config.
+fn schema_has_key(key: &str) -> bool {
+    let root = schemars::schema_for!(Config);
+    let mut schema = &root.schema;
+    for part in key.split('.') {
+        match schema_child(&root.definitions, schema, part) {
+            Some(child) => schema = child,
+            None => return false,
+        }
+    }
+    true
+}
+
+fn collect_keys(value: &serde_yaml::Value, prefix: Option<&str>, keys: &mut Vec<String>) {
+    let join = |part: String| match prefix {
+        Some(prefix) => format!("{prefix}.{part}"),
+        None => part,
+    };
+
+    match value {
+        serde_yaml::Value::Mapping(mapping) => {
+            for (part, child) in mapping {
+                if let Some(part) = part.as_str() {
+                    let key = join(part.to_string());
+                    collect_keys(child, Some(&key), keys);
+                    keys.push(key);
+                }
+            }
         }
+        serde_yaml::Value::Sequence(sequence) => {
+            for (i, child) in sequence.iter().enumerate() {
+                let key = join(i.to_string());
+                collect_keys(child, Some(&key), keys);
+                keys.push(key);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn unrecognized_key(root: &serde_yaml::Value, key: &str) -> Error {
+    let mut keys = vec![];
+    collect_keys(root, None, &mut keys);
+
+    let nearest = keys
+        .into_iter()
+        .map(|x| (strsim::jaro_winkler(key, &x), x))
+        .filter(|(score, _)| *score >= 0.7)
+        .sorted_by(|(score1, key1), (score2, key2)| score2.total_cmp(score1).then_with(|| key1.cmp(key2)))
+        .take(5)
+        .map(|(_, x)| x)
+        .collect();
+
+    Error::ConfigKeyUnrecognized {
+        key: key.to_string(),
+        nearest,
     }
 }
 
@@ -1389,9 +2317,62 @@ impl Config {
         ResourceFile::load().map_err(|e| Error::ConfigInvalid { why: format!("{}", e) })
     }
 
-    pub fn archive_invalid() -> Result<(), Box<dyn std::error::Error>> {
-        Self::path().move_to(&Self::file_archived_invalid())?;
-        Ok(())
+    pub fn archive_invalid() -> Result<(), Box<dyn std::error::Error>> {
+        Self::path().move_to(&Self::file_archived_invalid())?;
+        Ok(())
+    }
+
+    /// Look up a value by its dotted path in the serialized config,
+    /// such as `backup.format.chosen` or `roots.0.path`.
+    /// Fields that are currently unset are reported as null.
+    pub fn get_by_key(&self, key: &str) -> Result<serde_yaml::Value, Error> {
+        let mut root = serde_yaml::to_value(self).unwrap();
+        match lookup_key(&mut root, key) {
+            Some(value) => Ok(value.clone()),
+            None if schema_has_key(key) => Ok(serde_yaml::Value::Null),
+            None => Err(unrecognized_key(&root, key)),
+        }
+    }
+
+    /// Replace a value by its dotted path in the serialized config.
+    /// The new value is parsed as YAML and must still fit the config format.
+    /// If it does not, then it is tried again as a plain string.
+    /// Fields that are currently unset can be set as well.
+    pub fn set_by_key(&mut self, key: &str, value: &str) -> Result<(), Error> {
+        let mut root = serde_yaml::to_value(&*self).unwrap();
+        if lookup_key(&mut root, key).is_none() && !schema_has_key(key) {
+            return Err(unrecognized_key(&root, key));
+        }
+
+        let mut expanded = root.clone();
+        if insert_key(&mut expanded, key).is_none() {
+            return Err(unrecognized_key(&root, key));
+        }
+
+        let parsed = serde_yaml::from_str(value).unwrap_or_else(|_| serde_yaml::Value::String(value.to_string()));
+        let mut why = String::new();
+        for candidate in [parsed, serde_yaml::Value::String(value.to_string())] {
+            let mut root = expanded.clone();
+            if let Some(target) = insert_key(&mut root, key) {
+                *target = candidate;
+            }
+            match serde_yaml::from_value::<Self>(root) {
+                Ok(updated) => {
+                    *self = ResourceFile::migrate(updated);
+                    return Ok(());
+                }
+                Err(e) => {
+                    if why.is_empty() {
+                        why = e.to_string();
+                    }
+                }
+            }
+        }
+
+        Err(Error::ConfigValueInvalid {
+            key: key.to_string(),
+            why,
+        })
     }
 
     pub fn find_missing_roots(&self) -> Vec<Root> {
@@ -1599,6 +2580,18 @@ impl Config {
         self.backup.ignored_games.insert(name.to_owned());
     }
 
+    pub fn is_game_enabled_for_cloud(&self, name: &str) -> bool {
+        !self.cloud.excluded_games.contains(name)
+    }
+
+    pub fn enable_game_for_cloud(&mut self, name: &str) {
+        self.cloud.excluded_games.remove(name);
+    }
+
+    pub fn disable_game_for_cloud(&mut self, name: &str) {
+        self.cloud.excluded_games.insert(name.to_owned());
+    }
+
     pub fn is_game_enabled_for_restore(&self, name: &str) -> bool {
         !self.restore.ignored_games.contains(name)
     }
@@ -1661,6 +2654,7 @@ impl Config {
 
     pub fn add_custom_game(&mut self) {
         self.custom_games.push(CustomGame {
+            store: None,
             expanded: true,
             ..Default::default()
         });
@@ -1688,6 +2682,43 @@ impl Config {
             && !self.custom_games[index].name.trim().is_empty()
     }
 
+    /// The filename policy that new backups should actually use on this system.
+    /// This logs a warning if the configured policy can't be used as-is
+    /// or if it may produce names that a cloud provider rejects.
+    pub fn filename_policy(&self) -> FilenamePolicy {
+        let configured = self.backup.filename_policy;
+        let policy = configured.for_host(Os::HOST);
+        if policy != configured {
+            log::warn!(
+                "Filename policy {:?} is not supported on {:?}; using {:?} instead",
+                configured,
+                Os::HOST,
+                policy
+            );
+        }
+        if policy == FilenamePolicy::Posix && self.cloud.synchronize {
+            log::warn!(
+                "Filename policy {:?} may produce folder names that cloud providers reject",
+                policy
+            );
+        }
+        policy
+    }
+
+    /// Password for encrypted zip backups,
+    /// either from the config file or from the environment.
+    pub fn zip_password(&self) -> Option<String> {
+        self.backup
+            .format
+            .zip
+            .encryption
+            .as_ref()
+            .map(|x| x.password.clone())
+            .filter(|x| !x.is_empty())
+            .or_else(|| std::env::var(ENV_ZIP_PASSWORD).ok())
+            .filter(|x| !x.is_empty())
+    }
+
     pub fn expanded_roots(&self) -> Vec<Root> {
         for root in &self.roots {
             log::trace!(
@@ -1760,6 +2791,9 @@ impl Config {
     fn rebase_paths(&mut self) {
         let cwd = StrictPath::cwd();
         self.backup.path.rebase(&cwd);
+        for mirror in &mut self.backup.mirrors {
+            mirror.rebase(&cwd);
+        }
         self.restore.path.rebase(&cwd);
     }
 }
@@ -1779,8 +2813,11 @@ impl ToggledPaths {
     }
 
     pub fn is_ignored(&self, game: &str, path: &StrictPath) -> bool {
-        let transitive = self.is_enabled_transitively(game, path);
-        let specific = self.is_enabled_specifically(game, path);
+        self.is_ignored_case_sensitive(game, path, Os::HOST.is_case_sensitive())
+    }
+
+    pub fn is_ignored_case_sensitive(&self, game: &str, path: &StrictPath, case_sensitive: bool) -> bool {
+        let (transitive, specific) = self.is_enabled(game, path, case_sensitive);
         match (transitive, specific) {
             (_, Some(x)) => !x,
             (Some(x), _) => !x,
@@ -1788,70 +2825,96 @@ impl ToggledPaths {
         }
     }
 
-    fn is_enabled_transitively(&self, game: &str, path: &StrictPath) -> Option<bool> {
-        self.0.get(game).and_then(|x| {
-            path.nearest_prefix(x.keys().cloned().collect())
-                .as_ref()
-                .map(|prefix| x[prefix])
-        })
+    /// On case-insensitive hosts, paths that only differ by casing refer to the same file,
+    /// so we compare them in lowercase.
+    fn fold_case(path: &StrictPath, case_sensitive: bool) -> StrictPath {
+        if case_sensitive {
+            path.clone()
+        } else {
+            StrictPath::new(path.raw().to_lowercase())
+        }
     }
 
-    fn is_enabled_specifically(&self, game: &str, path: &StrictPath) -> Option<bool> {
-        self.0.get(game).and_then(|x| match x.get(path) {
+    /// Returns whether the path is enabled transitively (via its nearest toggled parent)
+    /// and specifically (via its own entry).
+    fn is_enabled(&self, game: &str, path: &StrictPath, case_sensitive: bool) -> (Option<bool>, Option<bool>) {
+        let Some(toggled) = self.0.get(game) else {
+            return (None, None);
+        };
+
+        let folded: BTreeMap<_, _> = toggled
+            .iter()
+            .map(|(k, v)| (Self::fold_case(k, case_sensitive), *v))
+            .collect();
+        let folded_path = Self::fold_case(path, case_sensitive);
+
+        let transitive = folded_path
+            .nearest_prefix(folded.keys().cloned().collect())
+            .as_ref()
+            .map(|prefix| folded[prefix]);
+
+        let specific = match toggled.get(path) {
             Some(enabled) => Some(*enabled),
-            None => x
+            None => folded
                 .iter()
-                .find(|(k, _)| path.interpret() == k.interpret())
+                .find(|(k, _)| folded_path.interpret() == k.interpret())
                 .map(|(_, v)| *v),
-        })
+        };
+
+        (transitive, specific)
     }
 
-    fn set_enabled(&mut self, game: &str, path: &StrictPath, enabled: bool) {
-        self.remove_with_children(game, path);
+    fn set_enabled(&mut self, game: &str, path: &StrictPath, enabled: bool, case_sensitive: bool) {
+        self.remove_with_children(game, path, case_sensitive);
         self.0
             .entry(game.to_string())
             .or_default()
             .insert(path.clone(), enabled);
     }
 
-    fn remove(&mut self, game: &str, path: &StrictPath) {
-        self.remove_with_children(game, path);
+    fn remove(&mut self, game: &str, path: &StrictPath, case_sensitive: bool) {
+        self.remove_with_children(game, path, case_sensitive);
         if self.0[game].is_empty() {
             self.0.remove(game);
         }
     }
 
-    fn remove_with_children(&mut self, game: &str, path: &StrictPath) {
+    fn remove_with_children(&mut self, game: &str, path: &StrictPath, case_sensitive: bool) {
+        let folded = Self::fold_case(path, case_sensitive);
         let keys: Vec<_> = self
             .0
             .get(game)
             .map(|x| x.keys().cloned().collect())
             .unwrap_or_default();
         for key in keys {
-            if path.is_prefix_of(&key) || key.interpret() == path.interpret() {
+            let folded_key = Self::fold_case(&key, case_sensitive);
+            if folded.is_prefix_of(&folded_key) || folded_key.interpret() == folded.interpret() {
                 self.0.get_mut(game).map(|entry| entry.remove(&key));
             }
         }
     }
 
     pub fn toggle(&mut self, game: &str, path: &StrictPath) {
-        let transitive = self.is_enabled_transitively(game, path);
-        let specific = self.is_enabled_specifically(game, path);
+        self.toggle_case_sensitive(game, path, Os::HOST.is_case_sensitive());
+    }
+
+    pub fn toggle_case_sensitive(&mut self, game: &str, path: &StrictPath, case_sensitive: bool) {
+        let (transitive, specific) = self.is_enabled(game, path, case_sensitive);
         match (transitive, specific) {
             (None, None | Some(true)) => {
-                self.set_enabled(game, path, false);
+                self.set_enabled(game, path, false, case_sensitive);
             }
             (None, Some(false)) => {
-                self.remove(game, path);
+                self.remove(game, path, case_sensitive);
             }
             (Some(x), None) => {
-                self.set_enabled(game, path, !x);
+                self.set_enabled(game, path, !x, case_sensitive);
             }
             (Some(x), Some(y)) if x == y => {
-                self.set_enabled(game, path, !x);
+                self.set_enabled(game, path, !x, case_sensitive);
             }
             (Some(_), Some(_)) => {
-                self.remove(game, path);
+                self.remove(game, path, case_sensitive);
             }
         }
     }
@@ -2020,6 +3083,16 @@ impl ToggledRegistry {
 
         self.prune(game, path);
     }
+
+    /// Disable every hive except for the specified ones,
+    /// overriding any settings on keys within the disabled hives.
+    pub fn limit_hives(&mut self, game: &str, hives: &[String]) {
+        for hive in registry::HIVES {
+            if !hives.iter().any(|x| x == hive) {
+                self.set_enabled(game, &RegistryItem::new(hive.to_string()), None, false);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -2063,6 +3136,7 @@ mod tests {
                 redirects: vec![],
                 backup: BackupConfig {
                     path: StrictPath::relative(s("~/backup"), Some(StrictPath::cwd().render())),
+                    mirrors: vec![],
                     ignored_games: BTreeSet::new(),
                     filter: BackupFilter {
                         exclude_store_screenshots: false,
@@ -2072,8 +3146,22 @@ mod tests {
                     toggled_registry: Default::default(),
                     sort: Default::default(),
                     retention: Retention::default(),
+                    game_retention: Default::default(),
                     format: Default::default(),
+                    game_format: Default::default(),
+                    game_exclusions: Default::default(),
                     only_constructive: false,
+                    follow_symlinks: false,
+                    checksum: false,
+                    hash: HashAlgorithm::Sha1,
+                    comment_template: None,
+                    folder_template: None,
+                    filename_policy: FilenamePolicy::Portable,
+                    skip_running_games: false,
+                    space_check: SpaceCheck::Abort,
+                    quick_scan: QuickScan::default(),
+                    pre_hook: None,
+                    post_hook: None,
                 },
                 restore: RestoreConfig {
                     path: StrictPath::relative(s("~/restore"), Some(StrictPath::cwd().render())),
@@ -2082,6 +3170,9 @@ mod tests {
                     toggled_registry: Default::default(),
                     sort: Default::default(),
                     reverse_redirects: false,
+                    redirect_fallback: false,
+                    wine_prefix: None,
+                    conflict_policy: ConflictPolicy::Overwrite,
                 },
                 scan: Default::default(),
                 apps: Apps {
@@ -2097,6 +3188,175 @@ mod tests {
         );
     }
 
+    #[test]
+    fn can_resolve_game_retention() {
+        let config = Config::load_from_string(
+            r#"
+            manifest:
+              url: example.com
+            roots: []
+            backup:
+              path: ~/backup
+              retention:
+                full: 2
+                differential: 3
+                policy: age
+                age:
+                  daily: 3
+              gameRetention:
+                foo:
+                  full: 5
+                  age:
+                    weekly: 1
+            restore:
+              path: ~/restore
+            "#,
+        )
+        .unwrap();
+
+        let global = Retention {
+            policy: RetentionPolicy::Age,
+            age: AgeRetention {
+                daily: 3,
+                ..Default::default()
+            },
+            ..Retention::new(2, 3)
+        };
+        assert_eq!(
+            Retention {
+                full: 5,
+                age: AgeRetention {
+                    weekly: 1,
+                    ..global.age
+                },
+                ..global
+            },
+            config.backup.retention_for("foo")
+        );
+        assert_eq!(global, config.backup.retention_for("bar"));
+    }
+
+    #[test]
+    fn can_resolve_game_format() {
+        let config = Config::load_from_string(
+            r#"
+            manifest:
+              url: example.com
+            roots: []
+            backup:
+              path: ~/backup
+              format:
+                chosen: zip
+              gameFormat:
+                foo: simple
+            restore:
+              path: ~/restore
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(BackupFormat::Simple, config.backup.format_for("foo").chosen);
+        assert_eq!(BackupFormat::Zip, config.backup.format_for("bar").chosen);
+    }
+
+    #[test]
+    fn rejects_zero_full_retention() {
+        for retention in ["retention: { full: 0 }", "gameRetention: { foo: { full: 0 } }"] {
+            let config = Config::load_from_string(&format!(
+                r#"
+                manifest:
+                  url: example.com
+                roots: []
+                backup:
+                  path: ~/backup
+                  {retention}
+                restore:
+                  path: ~/restore
+                "#,
+            ));
+            assert!(config.is_err());
+        }
+    }
+
+    #[test]
+    fn can_get_and_set_values_by_key() {
+        let mut config = Config::default();
+
+        assert_eq!(
+            serde_yaml::Value::String(s("simple")),
+            config.get_by_key("backup.format.chosen").unwrap()
+        );
+
+        config.set_by_key("backup.format.chosen", "zip").unwrap();
+        assert_eq!(BackupFormat::Zip, config.backup.format.chosen);
+
+        config.set_by_key("backup.retention.full", "3").unwrap();
+        assert_eq!(3, config.backup.retention.full);
+
+        config.set_by_key("backup.path", "123").unwrap();
+        assert_eq!("123", config.backup.path.raw());
+    }
+
+    #[test]
+    fn can_get_and_set_unset_optional_values_by_key() {
+        let mut config = Config::default();
+
+        assert_eq!(
+            serde_yaml::Value::Null,
+            config.get_by_key("backup.commentTemplate").unwrap()
+        );
+        config.set_by_key("backup.commentTemplate", "{game}").unwrap();
+        assert_eq!(Some(s("{game}")), config.backup.comment_template);
+
+        config
+            .set_by_key("backup.format.zip.encryption.password", "secret")
+            .unwrap();
+        assert_eq!(
+            Some(ZipEncryption {
+                password: s("secret"),
+                ..Default::default()
+            }),
+            config.backup.format.zip.encryption
+        );
+
+        assert!(matches!(
+            config.set_by_key("backup.commentTemplat", "{game}"),
+            Err(Error::ConfigKeyUnrecognized { .. })
+        ));
+        assert!(matches!(
+            config.set_by_key("roots.99.path", "/foo"),
+            Err(Error::ConfigKeyUnrecognized { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_values_by_key() {
+        let mut config = Config::default();
+
+        assert!(matches!(
+            config.set_by_key("backup.retention.full", "many"),
+            Err(Error::ConfigValueInvalid { .. })
+        ));
+        assert!(matches!(
+            config.set_by_key("backup.retention.full", "0"),
+            Err(Error::ConfigValueInvalid { .. })
+        ));
+        assert_eq!(Config::default().backup.retention, config.backup.retention);
+    }
+
+    #[test]
+    fn suggests_nearest_keys_when_unrecognized() {
+        let config = Config::default();
+
+        match config.get_by_key("backup.format.chosn") {
+            Err(Error::ConfigKeyUnrecognized { key, nearest }) => {
+                assert_eq!("backup.format.chosn", key);
+                assert_eq!(Some(&s("backup.format.chosen")), nearest.first());
+            }
+            x => panic!("unexpected result: {x:?}"),
+        }
+    }
+
     #[test]
     fn can_parse_optional_fields_when_present_in_config() {
         let config = Config::load_from_string(
@@ -2119,6 +3379,8 @@ mod tests {
                 target: ~/new
             backup:
               path: ~/backup
+              mirrors:
+                - ~/mirror
               ignoredGames:
                 - Backup Game 1
                 - Backup Game 2
@@ -2179,6 +3441,7 @@ mod tests {
                 },
                 language: Language::English,
                 theme: Theme::Light,
+                tray: Default::default(),
                 roots: vec![Root::new("~/steam", Store::Steam), Root::new("~/other", Store::Other),],
                 redirects: vec![RedirectConfig {
                     kind: RedirectKind::Restore,
@@ -2187,6 +3450,7 @@ mod tests {
                 }],
                 backup: BackupConfig {
                     path: StrictPath::relative(s("~/backup"), Some(StrictPath::cwd().render())),
+                    mirrors: vec![StrictPath::relative(s("~/mirror"), Some(StrictPath::cwd().render()))],
                     ignored_games: btree_set! {
                         s("Backup Game 1"),
                         s("Backup Game 2"),
@@ -2199,8 +3463,22 @@ mod tests {
                     toggled_registry: Default::default(),
                     sort: Default::default(),
                     retention: Retention::default(),
+                    game_retention: Default::default(),
                     format: Default::default(),
+                    game_format: Default::default(),
+                    game_exclusions: Default::default(),
                     only_constructive: true,
+                    follow_symlinks: false,
+                    checksum: false,
+                    hash: HashAlgorithm::Sha1,
+                    comment_template: None,
+                    folder_template: None,
+                    filename_policy: FilenamePolicy::Portable,
+                    skip_running_games: false,
+                    space_check: SpaceCheck::Abort,
+                    quick_scan: QuickScan::default(),
+                    pre_hook: None,
+                    post_hook: None,
                 },
                 restore: RestoreConfig {
                     path: StrictPath::relative(s("~/restore"), Some(StrictPath::cwd().render())),
@@ -2212,11 +3490,16 @@ mod tests {
                     toggled_registry: Default::default(),
                     sort: Default::default(),
                     reverse_redirects: false,
+                    redirect_fallback: false,
+                    wine_prefix: None,
+                    conflict_policy: ConflictPolicy::Overwrite,
                 },
+                export: Default::default(),
                 scan: Scan {
                     show_deselected_games: false,
                     show_unchanged_games: false,
                     show_unscanned_games: false,
+                    fuzzy_match_threshold: 90,
                 },
                 cloud: Cloud {
                     remote: Some(Remote::GoogleDrive {
@@ -2224,6 +3507,11 @@ mod tests {
                     }),
                     path: "ludusavi-backup".to_string(),
                     synchronize: false,
+                    timestamped_backups: false,
+                    scope_to_changed: true,
+                    transfers: None,
+                    bandwidth_limit: None,
+                    excluded_games: BTreeSet::new(),
                 },
                 apps: Apps {
                     rclone: App {
@@ -2241,6 +3529,8 @@ mod tests {
                         files: vec![],
                         registry: vec![],
                         install_dir: vec![],
+                        include: vec![],
+                        store: None,
                         expanded: false,
                     },
                     CustomGame {
@@ -2256,6 +3546,8 @@ mod tests {
                             s("Custom Install Dir 2"),
                             s("Custom Install Dir 2")
                         ],
+                        include: vec![],
+                        store: None,
                         expanded: false,
                     },
                 ],
@@ -2278,6 +3570,8 @@ manifest:
   enable: true
 language: en-US
 theme: light
+tray:
+  enabled: false
 roots:
   - store: steam
     path: ~/steam
@@ -2312,6 +3606,13 @@ backup:
   retention:
     full: 1
     differential: 0
+    policy: count
+    age:
+      withinHours: 0
+      hourly: 24
+      daily: 7
+      weekly: 4
+  gameRetention: {}
   format:
     chosen: simple
     zip:
@@ -2323,7 +3624,18 @@ backup:
         level: 6
       zstd:
         level: 10
+    delta: false
+    dedupe: false
+    flat: false
   onlyConstructive: false
+  checksum: false
+  hash: sha1
+  filenamePolicy: portable
+  skipRunningGames: false
+  spaceCheck: abort
+  quickScan:
+    enabled: false
+    fullScanDays: 7
 restore:
   path: ~/restore
   ignoredGames:
@@ -2336,6 +3648,10 @@ restore:
     key: status
     reversed: false
   reverseRedirects: false
+  redirectFallback: false
+  conflictPolicy: overwrite
+export:
+  name: "ludusavi-export-{date}"
 scan:
   showDeselectedGames: false
   showUnchangedGames: false
@@ -2346,6 +3662,8 @@ cloud:
       id: remote-id
   path: ludusavi-backup
   synchronize: true
+  timestampedBackups: false
+  scopeToChanged: true
 apps:
   rclone:
     path: rclone.exe
@@ -2388,6 +3706,7 @@ customGames:
                 },
                 language: Language::English,
                 theme: Theme::Light,
+                tray: Default::default(),
                 roots: vec![Root::new("~/steam", Store::Steam), Root::new("~/other", Store::Other),],
                 redirects: vec![RedirectConfig {
                     kind: RedirectKind::Restore,
@@ -2396,6 +3715,7 @@ customGames:
                 }],
                 backup: BackupConfig {
                     path: StrictPath::new(s("~/backup")),
+                    mirrors: vec![],
                     ignored_games: btree_set! {
                         s("Backup Game 3"),
                         s("Backup Game 1"),
@@ -2409,8 +3729,22 @@ customGames:
                     toggled_registry: Default::default(),
                     sort: Default::default(),
                     retention: Retention::default(),
+                    game_retention: Default::default(),
                     format: Default::default(),
+                    game_format: Default::default(),
+                    game_exclusions: Default::default(),
                     only_constructive: false,
+                    follow_symlinks: false,
+                    checksum: false,
+                    hash: HashAlgorithm::Sha1,
+                    comment_template: None,
+                    folder_template: None,
+                    filename_policy: FilenamePolicy::Portable,
+                    skip_running_games: false,
+                    space_check: SpaceCheck::Abort,
+                    quick_scan: QuickScan::default(),
+                    pre_hook: None,
+                    post_hook: None,
                 },
                 restore: RestoreConfig {
                     path: StrictPath::new(s("~/restore")),
@@ -2423,11 +3757,16 @@ customGames:
                     toggled_registry: Default::default(),
                     sort: Default::default(),
                     reverse_redirects: false,
+                    redirect_fallback: false,
+                    wine_prefix: None,
+                    conflict_policy: ConflictPolicy::Overwrite,
                 },
+                export: Default::default(),
                 scan: Scan {
                     show_deselected_games: false,
                     show_unchanged_games: false,
                     show_unscanned_games: false,
+                    fuzzy_match_threshold: 90,
                 },
                 cloud: Cloud {
                     remote: Some(Remote::GoogleDrive {
@@ -2435,6 +3774,11 @@ customGames:
                     }),
                     path: "ludusavi-backup".to_string(),
                     synchronize: true,
+                    timestamped_backups: false,
+                    scope_to_changed: true,
+                    transfers: None,
+                    bandwidth_limit: None,
+                    excluded_games: BTreeSet::new(),
                 },
                 apps: Apps {
                     rclone: App {
@@ -2452,6 +3796,8 @@ customGames:
                         files: vec![],
                         registry: vec![],
                         install_dir: vec![],
+                        include: vec![],
+                        store: None,
                         expanded: false,
                     },
                     CustomGame {
@@ -2467,6 +3813,8 @@ customGames:
                             s("Custom Install Dir 2"),
                             s("Custom Install Dir 2")
                         ],
+                        include: vec![],
+                        store: None,
                         expanded: false,
                     },
                     CustomGame {
@@ -2478,6 +3826,8 @@ customGames:
                         files: vec![],
                         registry: vec![],
                         install_dir: vec![],
+                        include: vec![],
+                        store: None,
                         expanded: false,
                     },
                 ],
@@ -2487,6 +3837,265 @@ customGames:
         );
     }
 
+    mod export_name {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        fn now() -> chrono::DateTime<chrono::Utc> {
+            chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+                .unwrap()
+                .and_hms_opt(3, 4, 5)
+                .unwrap()
+                .and_utc()
+        }
+
+        fn export(name: &str) -> ExportConfig {
+            ExportConfig { name: s(name) }
+        }
+
+        #[test]
+        fn default_template() {
+            assert_eq!(
+                Ok(s("ludusavi-export-20000102T030405Z")),
+                ExportConfig::default().render_name(None, &now(), Os::Windows, 2),
+            );
+        }
+
+        #[test]
+        fn expands_all_tokens() {
+            assert_eq!(
+                Ok(s("foo-20000102T030405Z-linux-3")),
+                export("{game}-{date}-{os}-{count}").render_name(Some("foo"), &now(), Os::Linux, 3),
+            );
+        }
+
+        #[test]
+        fn expands_repeated_and_unknown_tokens() {
+            assert_eq!(
+                Ok(s("mac-mac-{other}")),
+                export("{os}-{os}-{other}").render_name(None, &now(), Os::Mac, 0),
+            );
+        }
+
+        #[test]
+        fn escapes_game_name() {
+            assert_eq!(
+                Ok(s("foo_ bar_")),
+                export("{game}").render_name(Some("foo: bar?"), &now(), Os::Windows, 1),
+            );
+        }
+
+        #[test]
+        fn rejects_invalid_characters_in_template() {
+            assert_eq!(
+                Err(Error::ExportNameInvalid { name: s("exports/foo") }),
+                export("exports/{game}").render_name(Some("foo"), &now(), Os::Windows, 1),
+            );
+            assert_eq!(
+                Err(Error::ExportNameInvalid { name: s("foo:1") }),
+                export("{game}:{count}").render_name(Some("foo"), &now(), Os::Windows, 1),
+            );
+        }
+
+        #[test]
+        fn rejects_empty_result() {
+            assert_eq!(
+                Err(Error::ExportNameInvalid { name: s("") }),
+                export("{game}").render_name(None, &now(), Os::Windows, 0),
+            );
+            assert_eq!(
+                Err(Error::ExportNameInvalid { name: s("..") }),
+                export("..").render_name(None, &now(), Os::Windows, 0),
+            );
+        }
+    }
+
+    #[test]
+    fn can_adjust_filename_policy_for_host() {
+        assert_eq!(FilenamePolicy::Windows, FilenamePolicy::Posix.for_host(Os::Windows));
+        assert_eq!(FilenamePolicy::Posix, FilenamePolicy::Posix.for_host(Os::Linux));
+        assert_eq!(FilenamePolicy::Posix, FilenamePolicy::Posix.for_host(Os::Mac));
+        assert_eq!(FilenamePolicy::Portable, FilenamePolicy::Portable.for_host(Os::Windows));
+        assert_eq!(FilenamePolicy::Windows, FilenamePolicy::Windows.for_host(Os::Linux));
+    }
+
+    #[test]
+    fn can_decide_when_quick_scan_is_active() {
+        let now = chrono::NaiveDate::from_ymd_opt(2000, 1, 8)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+        let enabled = QuickScan {
+            enabled: true,
+            full_scan_days: 7,
+        };
+
+        assert!(!QuickScan::default().is_active(Some(now), now));
+        assert!(!enabled.is_active(None, now));
+        assert!(enabled.is_active(Some(now - chrono::Duration::days(6)), now));
+        assert!(!enabled.is_active(Some(now - chrono::Duration::days(7)), now));
+        assert!(QuickScan {
+            full_scan_days: 0,
+            ..enabled
+        }
+        .is_active(None, now));
+    }
+
+    #[test]
+    fn can_exclude_files_by_size() {
+        let filter = BackupFilter {
+            max_file_size: Some(100),
+            min_file_size: Some(10),
+            ..Default::default()
+        };
+
+        assert_eq!(None, filter.size_exclusion(10));
+        assert_eq!(None, filter.size_exclusion(100));
+        assert_eq!(Some(FileExclusion::TooSmall { limit: 10 }), filter.size_exclusion(9));
+        assert_eq!(Some(FileExclusion::TooLarge { limit: 100 }), filter.size_exclusion(101));
+        assert_eq!(None, BackupFilter::default().size_exclusion(u64::MAX));
+        assert_eq!(None, BackupFilter::default().size_exclusion(0));
+    }
+
+    #[test]
+    fn can_exclude_empty_files() {
+        let filter = BackupFilter {
+            exclude_empty_files: true,
+            min_file_size: Some(10),
+            ..Default::default()
+        };
+
+        assert_eq!(Some(FileExclusion::Empty), filter.size_exclusion(0));
+        assert_eq!(Some(FileExclusion::TooSmall { limit: 10 }), filter.size_exclusion(1));
+        assert_eq!(None, filter.size_exclusion(10));
+    }
+
+    #[test]
+    fn can_ignore_registry_values_by_glob() {
+        let mut filter = BackupFilter {
+            ignored_registry: vec![
+                RegistryItem::new(s(r"HKEY_CURRENT_USER\Software\Game\*\lastPlayed")),
+                RegistryItem::new(s("HKEY_CURRENT_USER/Software/Other/*/Window*")),
+                RegistryItem::new(s("HKEY_CURRENT_USER/Software/Plain")),
+            ],
+            ..Default::default()
+        };
+        filter.build_globs();
+
+        let key = |raw: &str| RegistryItem::new(raw.to_string());
+
+        assert!(filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Game/Profile1"), "lastPlayed"));
+        assert!(filter.is_registry_value_ignored(&key(r"HKEY_CURRENT_USER\Software\Game\Profile2"), "LASTPLAYED"));
+        assert!(!filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Game/Profile1"), "score"));
+        assert!(!filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Game"), "lastPlayed"));
+        assert!(!filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Game/A/B"), "lastPlayed"));
+        assert!(!filter.is_registry_ignored(&key("HKEY_CURRENT_USER/Software/Game/Profile1")));
+
+        assert!(filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Other/Display"), "WindowX"));
+        assert!(filter.is_registry_ignored(&key("HKEY_CURRENT_USER/Software/Other/Display/WindowState")));
+        assert!(!filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Other/Display"), "volume"));
+
+        assert!(filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Plain/Sub"), "anything"));
+        assert!(!filter.is_registry_value_ignored(&key("HKEY_CURRENT_USER/Software/Plainer"), "anything"));
+    }
+
+    mod compression_level {
+        use super::*;
+
+        fn formats(chosen: BackupFormat, method: ZipCompression, level: i32) -> BackupFormats {
+            let mut formats = BackupFormats {
+                chosen,
+                zip: ZipConfig {
+                    compression: method,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            formats.compression.deflate.level = level;
+            formats.compression.bzip2.level = level;
+            formats.compression.zstd.level = level;
+            formats
+        }
+
+        #[test]
+        fn is_clamped_for_deflate() {
+            assert_eq!(
+                Some(1),
+                formats(BackupFormat::Zip, ZipCompression::Deflate, -5).validated_level()
+            );
+            assert_eq!(
+                Some(5),
+                formats(BackupFormat::Zip, ZipCompression::Deflate, 5).validated_level()
+            );
+            assert_eq!(
+                Some(9),
+                formats(BackupFormat::Zip, ZipCompression::Deflate, 20).validated_level()
+            );
+            assert_eq!(
+                Some(9),
+                formats(BackupFormat::TarGz, ZipCompression::Zstd, 20).validated_level()
+            );
+        }
+
+        #[test]
+        fn is_clamped_for_bzip2() {
+            assert_eq!(
+                Some(1),
+                formats(BackupFormat::Zip, ZipCompression::Bzip2, 0).validated_level()
+            );
+            assert_eq!(
+                Some(5),
+                formats(BackupFormat::Zip, ZipCompression::Bzip2, 5).validated_level()
+            );
+            assert_eq!(
+                Some(9),
+                formats(BackupFormat::Zip, ZipCompression::Bzip2, 20).validated_level()
+            );
+        }
+
+        #[test]
+        fn is_clamped_for_zstd() {
+            assert_eq!(
+                Some(-7),
+                formats(BackupFormat::Zip, ZipCompression::Zstd, -10).validated_level()
+            );
+            assert_eq!(
+                Some(20),
+                formats(BackupFormat::Zip, ZipCompression::Zstd, 20).validated_level()
+            );
+            assert_eq!(
+                Some(22),
+                formats(BackupFormat::Zip, ZipCompression::Zstd, 30).validated_level()
+            );
+        }
+
+        #[test]
+        fn is_not_applicable_without_compression() {
+            assert_eq!(
+                None,
+                formats(BackupFormat::Zip, ZipCompression::None, 30).validated_level()
+            );
+            assert_eq!(
+                None,
+                formats(BackupFormat::Simple, ZipCompression::Zstd, 30).validated_level()
+            );
+            assert_eq!(
+                None,
+                formats(BackupFormat::Tar, ZipCompression::Zstd, 30).validated_level()
+            );
+        }
+
+        #[test]
+        fn is_clamped_when_set() {
+            let mut formats = formats(BackupFormat::Zip, ZipCompression::Bzip2, 5);
+            formats.set_level(15);
+            assert_eq!(Some(9), formats.level());
+            assert_eq!(9, formats.compression.bzip2.level);
+        }
+    }
+
     mod ignored_paths {
         use pretty_assertions::assert_eq;
 
@@ -2610,6 +4219,44 @@ customGames:
                 ],
             );
         }
+
+        #[test]
+        fn ignores_path_with_different_casing_when_case_insensitive() {
+            let mut toggled = ToggledPaths::default();
+            let upper = StrictPath::new(repo_path("tests/root1/Game1/SubDir/file2.txt"));
+            let lower = StrictPath::new(repo_path("tests/root1/game1/subdir/file2.txt"));
+
+            toggled.toggle_case_sensitive("game", &upper, false);
+            assert!(toggled.is_ignored_case_sensitive("game", &lower, false));
+            assert!(!toggled.is_ignored_case_sensitive("game", &lower, true));
+
+            toggled.toggle_case_sensitive("game", &lower, false);
+            assert!(!toggled.is_ignored_case_sensitive("game", &upper, false));
+            assert_eq!(ToggledPaths::default(), toggled);
+        }
+
+        #[test]
+        fn ignores_child_of_path_with_different_casing_when_case_insensitive() {
+            let mut toggled = ToggledPaths::default();
+            let parent = StrictPath::new(repo_path("tests/root1/Game1/SubDir"));
+            let child = StrictPath::new(repo_path("tests/root1/game1/subdir/file2.txt"));
+
+            toggled.toggle_case_sensitive("game", &parent, false);
+            assert!(toggled.is_ignored_case_sensitive("game", &child, false));
+            assert!(!toggled.is_ignored_case_sensitive("game", &child, true));
+
+            toggled.toggle_case_sensitive("game", &child, false);
+            assert!(!toggled.is_ignored_case_sensitive("game", &child, false));
+            assert_eq!(
+                ToggledPaths(btree_map! {
+                    s("game"): btree_map! {
+                        parent.clone(): false,
+                        child.clone(): true,
+                    }
+                }),
+                toggled
+            );
+        }
     }
 
     mod ignored_registry {
@@ -2900,5 +4547,35 @@ customGames:
                 ],
             );
         }
+
+        #[test]
+        fn can_limit_hives() {
+            let mut toggled = ToggledRegistry(btree_map! {
+                s("game"): btree_map! {
+                    RegistryItem::new(s("HKEY_LOCAL_MACHINE/Software/Ludusavi")): ToggledRegistryEntry::Key(true),
+                }
+            });
+
+            toggled.limit_hives("game", &[s("HKEY_CURRENT_USER")]);
+
+            assert_eq!(
+                ToggledRegistry(btree_map! {
+                    s("game"): btree_map! {
+                        RegistryItem::new(s("HKEY_LOCAL_MACHINE")): ToggledRegistryEntry::Key(false),
+                    }
+                }),
+                toggled
+            );
+            assert!(!toggled.is_ignored(
+                "game",
+                &RegistryItem::new(s("HKEY_CURRENT_USER/Software/Ludusavi")),
+                None
+            ));
+            assert!(toggled.is_ignored(
+                "game",
+                &RegistryItem::new(s("HKEY_LOCAL_MACHINE/Software/Ludusavi")),
+                None
+            ));
+        }
     }
 }
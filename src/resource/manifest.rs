@@ -28,6 +28,8 @@ pub mod placeholder {
     pub const WIN_DIR: &str = "<winDir>";
     pub const XDG_DATA: &str = "<xdgData>";
     pub const XDG_CONFIG: &str = "<xdgConfig>";
+    /// Only used in Ludusavi's own config, not in the manifest.
+    pub const EXE: &str = "<exe>";
 }
 
 #[derive(
@@ -68,6 +70,16 @@ impl Os {
         Self::Other
     };
 
+    /// Lowercase name for use in rendered templates.
+    pub fn slug(&self) -> &'static str {
+        match self {
+            Self::Windows => "windows",
+            Self::Linux => "linux",
+            Self::Mac => "mac",
+            Self::Other => "other",
+        }
+    }
+
     pub fn is_case_sensitive(&self) -> bool {
         match self {
             Self::Windows | Self::Mac => false,
@@ -146,6 +158,38 @@ impl Store {
         Store::OtherMac,
         Store::Other,
     ];
+
+    pub const ALL_NAMES: &'static [&'static str] = &[
+        "ea",
+        "epic",
+        "gog",
+        "gogGalaxy",
+        "heroic",
+        "legendary",
+        "lutris",
+        "microsoft",
+        "origin",
+        "prime",
+        "steam",
+        "uplay",
+        "otherHome",
+        "otherWine",
+        "otherWindows",
+        "otherLinux",
+        "otherMac",
+        "other",
+    ];
+}
+
+impl std::str::FromStr for Store {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::ALL_NAMES.iter().position(|x| *x == s) {
+            Some(i) => Ok(Self::ALL[i]),
+            None => Err(format!("invalid store: {}", s)),
+        }
+    }
 }
 
 impl ToString for Store {
@@ -154,7 +198,19 @@ impl ToString for Store {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum Tag {
     Save,
@@ -164,6 +220,35 @@ pub enum Tag {
     Other,
 }
 
+impl Tag {
+    pub const ALL_NAMES: &'static [&'static str] = &["save", "config"];
+}
+
+impl std::str::FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "save" => Ok(Self::Save),
+            "config" => Ok(Self::Config),
+            _ => Err(format!("invalid tag: {}", s)),
+        }
+    }
+}
+
+/// How a secondary manifest's entry combines with an existing entry for the same game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Override {
+    /// Add to the existing entry.
+    #[default]
+    Merge,
+    /// Use this entry instead of the existing one.
+    Replace,
+    /// Drop the existing entry.
+    Remove,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Secondary {
     pub id: String,
@@ -179,10 +264,10 @@ pub enum Source {
     Secondary(String),
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 pub struct Manifest(pub BTreeMap<String, Game>);
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Game {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -192,6 +277,8 @@ pub struct Game {
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub install_dir: BTreeMap<String, GameInstallDirEntry>,
     #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub launch: BTreeMap<String, Vec<GameLaunchEntry>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub registry: BTreeMap<String, GameRegistryEntry>,
     #[serde(skip_serializing_if = "SteamMetadata::is_empty")]
     pub steam: SteamMetadata,
@@ -203,6 +290,13 @@ pub struct Game {
     pub cloud: CloudMetadata,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub notes: Vec<Note>,
+    /// Only used by secondary manifests to control how this entry
+    /// combines with an existing entry for the same game.
+    #[serde(rename = "override", skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<Override>,
+    /// Set by custom games to pin the store used when expanding paths.
+    #[serde(skip)]
+    pub store: Option<Store>,
     #[serde(skip)]
     pub sources: BTreeSet<Source>,
 }
@@ -242,6 +336,56 @@ impl Game {
         }
     }
 
+    /// All tags used by any of the game's file or registry entries.
+    pub fn tags(&self) -> BTreeSet<Tag> {
+        self.files
+            .values()
+            .flat_map(|x| x.tags.iter())
+            .chain(self.registry.values().flat_map(|x| x.tags.iter()))
+            .cloned()
+            .collect()
+    }
+
+    /// If `only` is not empty, then the game must have at least one of those tags.
+    /// The game must not have any of the `exclude` tags.
+    pub fn matches_tags(&self, only: &[Tag], exclude: &[Tag]) -> bool {
+        if only.is_empty() && exclude.is_empty() {
+            return true;
+        }
+
+        let tags = self.tags();
+        (only.is_empty() || only.iter().any(|x| tags.contains(x))) && !exclude.iter().any(|x| tags.contains(x))
+    }
+
+    /// Whether any of the game's file or registry entries use this tag.
+    pub fn has_tag(&self, tag: &Tag) -> bool {
+        self.files.values().any(|x| x.tags.contains(tag)) || self.registry.values().any(|x| x.tags.contains(tag))
+    }
+
+    /// Whether the game is associated with this store,
+    /// either by a store-specific ID or by a file or registry entry for that store.
+    pub fn has_store(&self, store: Store) -> bool {
+        let by_id = match store {
+            Store::Steam => self.steam.id.is_some() || !self.id.steam_extra.is_empty(),
+            Store::Gog => self.gog.id.is_some() || !self.id.gog_extra.is_empty(),
+            Store::Lutris => self.id.lutris.is_some(),
+            _ => false,
+        };
+
+        by_id
+            || self.store == Some(store)
+            || self
+                .files
+                .values()
+                .flat_map(|x| x.when.iter())
+                .any(|x| x.store == Some(store))
+            || self
+                .registry
+                .values()
+                .flat_map(|x| x.when.iter())
+                .any(|x| x.store == Some(store))
+    }
+
     pub fn is_from_manifest(&self) -> bool {
         self.sources.iter().any(|source| match source {
             Source::Primary => true,
@@ -251,7 +395,7 @@ impl Game {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct GameFileEntry {
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
@@ -260,10 +404,34 @@ pub struct GameFileEntry {
     pub when: BTreeSet<GameFileConstraint>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 pub struct GameInstallDirEntry {}
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameLaunchEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub when: BTreeSet<GameLaunchConstraint>,
+}
+
+impl GameLaunchEntry {
+    /// Whether this entry is usable on the given OS and store.
+    /// Entries without any constraints apply everywhere.
+    pub fn applies_to(&self, os: Os, store: Store) -> bool {
+        self.when.is_empty()
+            || self.when.iter().any(|x| {
+                x.os.map(|x| x == os).unwrap_or(true)
+                    && x.store.map(|x| x == store).unwrap_or(true)
+                    && x.bit.map(|x| x == usize::BITS as u8).unwrap_or(true)
+            })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct GameRegistryEntry {
     #[serde(skip_serializing_if = "BTreeSet::is_empty")]
@@ -272,7 +440,19 @@ pub struct GameRegistryEntry {
     pub when: BTreeSet<GameRegistryConstraint>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
 #[serde(default, rename_all = "camelCase")]
 pub struct GameFileConstraint {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -281,14 +461,49 @@ pub struct GameFileConstraint {
     pub store: Option<Store>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameLaunchConstraint {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<Os>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<Store>,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    schemars::JsonSchema,
+)]
 #[serde(default, rename_all = "camelCase")]
 pub struct GameRegistryConstraint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub store: Option<Store>,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct SteamMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -301,7 +516,7 @@ impl SteamMetadata {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct GogMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -314,7 +529,7 @@ impl GogMetadata {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct IdMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -367,7 +582,7 @@ impl<'a> IdSet<'a> {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct CloudMetadata {
     #[serde(skip_serializing_if = "std::ops::Not::not")]
@@ -396,7 +611,7 @@ impl CloudMetadata {
     }
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(default, rename_all = "camelCase")]
 pub struct Note {
     pub message: String,
@@ -408,6 +623,7 @@ pub struct Note {
 pub struct ManifestUpdate {
     pub url: String,
     pub etag: Option<String>,
+    pub last_modified: Option<String>,
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub modified: bool,
 }
@@ -501,16 +717,43 @@ impl Manifest {
 
         let path = Self::path_for(url, primary);
 
-        let mut req = reqwest::blocking::Client::new()
-            .get(url)
-            .header(reqwest::header::USER_AGENT, &*crate::prelude::USER_AGENT);
-        let old_etag = cache.get(url).and_then(|x| x.etag.clone());
-        if let Some(etag) = old_etag.as_ref() {
-            if path.exists() {
-                req = req.header(reqwest::header::IF_NONE_MATCH, etag);
+        // We can only skip the download if we still have our copy.
+        let cached = cache.get(url).filter(|_| path.exists());
+        let conditions = Self::conditional_headers(cached);
+
+        let send = |conditions: &[(reqwest::header::HeaderName, String)]| {
+            let mut req = reqwest::blocking::Client::new()
+                .get(url)
+                .header(reqwest::header::USER_AGENT, &*crate::prelude::USER_AGENT);
+            for (header, value) in conditions {
+                req = req.header(header, value);
             }
-        }
-        let mut res = req.send().map_err(|_e| cannot_update())?;
+            req.send()
+        };
+
+        let res = match send(conditions.as_slice()) {
+            Ok(res) if conditions.is_empty() || !Self::should_retry(res.status()) => res,
+            outcome => {
+                if conditions.is_empty() {
+                    return Err(cannot_update());
+                }
+                // Some servers choke on conditional requests instead of ignoring them.
+                match &outcome {
+                    Ok(res) => log::warn!(
+                        "Conditional manifest request failed with {}, retrying without conditions: {}",
+                        res.status(),
+                        url
+                    ),
+                    Err(e) => log::warn!(
+                        "Conditional manifest request failed ({:?}), retrying without conditions: {}",
+                        e,
+                        url
+                    ),
+                }
+                send(&[]).map_err(|_e| cannot_update())?
+            }
+        };
+
         match res.status() {
             reqwest::StatusCode::OK => {
                 app_dir().create_dirs().map_err(|_| cannot_update())?;
@@ -528,21 +771,24 @@ impl Manifest {
 
                 path.write_with_content(&manifest_string).map_err(|_| cannot_update())?;
 
-                let new_etag = res
-                    .headers()
-                    .get(reqwest::header::ETAG)
-                    .map(|etag| String::from_utf8_lossy(etag.as_bytes()).to_string());
+                let header = |name| {
+                    res.headers()
+                        .get(name)
+                        .map(|value| String::from_utf8_lossy(value.as_bytes()).to_string())
+                };
 
                 Ok(Some(ManifestUpdate {
                     url: url.to_string(),
-                    etag: new_etag,
+                    etag: header(reqwest::header::ETAG),
+                    last_modified: header(reqwest::header::LAST_MODIFIED),
                     timestamp: chrono::offset::Utc::now(),
                     modified: true,
                 }))
             }
             reqwest::StatusCode::NOT_MODIFIED => Ok(Some(ManifestUpdate {
                 url: url.to_string(),
-                etag: old_etag,
+                etag: cached.and_then(|x| x.etag.clone()),
+                last_modified: cached.and_then(|x| x.last_modified.clone()),
                 timestamp: chrono::offset::Utc::now(),
                 modified: false,
             })),
@@ -550,6 +796,28 @@ impl Manifest {
         }
     }
 
+    /// Whether a failed conditional request might succeed without the conditions.
+    /// Client errors like 404 would fail either way, so we don't bother retrying those.
+    fn should_retry(status: reqwest::StatusCode) -> bool {
+        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    }
+
+    /// Headers to only download the manifest if it changed since we last saw it.
+    fn conditional_headers(cached: Option<&cache::Manifest>) -> Vec<(reqwest::header::HeaderName, String)> {
+        let mut headers = vec![];
+
+        if let Some(cached) = cached {
+            if let Some(etag) = &cached.etag {
+                headers.push((reqwest::header::IF_NONE_MATCH, etag.clone()));
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                headers.push((reqwest::header::IF_MODIFIED_SINCE, last_modified.clone()));
+            }
+        }
+
+        headers
+    }
+
     pub fn update_mut(config: &Config, cache: &mut Cache, force: bool) -> Result<(), Error> {
         let mut error = None;
 
@@ -644,6 +912,65 @@ impl Manifest {
 
             self.add_custom_game(custom_game.clone());
         }
+
+        self.merge_custom_includes(config);
+    }
+
+    /// Merge the entries of any included games into their custom games.
+    /// Includes are followed transitively, and each game is only visited once,
+    /// so cycles between custom games are harmless.
+    fn merge_custom_includes(&mut self, config: &Config) {
+        let includes: HashMap<&str, &[String]> = config
+            .custom_games
+            .iter()
+            .filter(|x| !x.ignore && !x.include.is_empty())
+            .map(|x| (x.name.as_str(), x.include.as_slice()))
+            .collect();
+
+        for (name, direct) in &includes {
+            if !self.0.contains_key(*name) {
+                continue;
+            }
+
+            let mut merged = Game::default();
+            let mut visited = BTreeSet::from([name.to_string()]);
+            let mut pending: Vec<&String> = direct.iter().collect();
+            while let Some(included) = pending.pop() {
+                let included = included.trim();
+                if included.is_empty() || !visited.insert(included.to_string()) {
+                    continue;
+                }
+
+                let Some(game) = self.0.get(included) else {
+                    log::warn!("custom game '{name}' includes unknown game: '{included}'");
+                    continue;
+                };
+                for (k, v) in &game.files {
+                    merged.files.entry(k.clone()).or_insert_with(|| v.clone());
+                }
+                for (k, v) in &game.registry {
+                    merged.registry.entry(k.clone()).or_insert_with(|| v.clone());
+                }
+                for k in game.install_dir.keys() {
+                    merged.install_dir.entry(k.clone()).or_default();
+                }
+                if let Some(nested) = includes.get(included) {
+                    pending.extend(nested.iter());
+                }
+            }
+
+            if let Some(stored) = self.0.get_mut(*name) {
+                for (k, v) in merged.files {
+                    stored.files.entry(k).or_insert(v);
+                }
+                for (k, v) in merged.registry {
+                    stored.registry.entry(k).or_insert(v);
+                }
+                for (k, v) in merged.install_dir {
+                    stored.install_dir.entry(k).or_insert(v);
+                }
+            }
+        }
     }
 
     fn add_custom_game(&mut self, custom: CustomGame) {
@@ -672,6 +999,7 @@ impl Manifest {
                     // If you choose not to back up games with cloud support,
                     // you probably still want to back up your customized versions of such games.
                     stored.cloud = CloudMetadata::default();
+                    stored.store = custom.store;
                     stored.sources.insert(Source::Custom);
                 }
                 Integration::Extend => {
@@ -686,6 +1014,9 @@ impl Manifest {
                         stored.install_dir.entry(item).or_default();
                     }
                     stored.cloud = CloudMetadata::default();
+                    if custom.store.is_some() {
+                        stored.store = custom.store;
+                    }
                     stored.sources.insert(Source::Custom);
                 }
             }
@@ -707,6 +1038,7 @@ impl Manifest {
                     .into_iter()
                     .map(|x| (x, GameInstallDirEntry::default()))
                     .collect(),
+                store: custom.store,
                 sources: BTreeSet::from_iter([Source::Custom]),
                 ..Default::default()
             };
@@ -715,25 +1047,44 @@ impl Manifest {
         }
     }
 
+    /// Secondary manifests are applied in order:
+    /// first the ones from the config, then the ones found in roots.
+    /// Entries that merge (the default) are applied from all secondary manifests first,
+    /// and then any entries that replace or remove a game are applied in the same order,
+    /// so an override always takes precedence over additions,
+    /// and a later override takes precedence over an earlier one.
     fn load_secondary_manifests(&mut self, config: &Config) {
-        for secondary in config.manifest.load_secondary_manifests() {
-            self.incorporate_secondary_manifest(secondary);
-        }
-
+        let mut secondaries = config.manifest.load_secondary_manifests();
         for root in &config.roots {
             for (path, secondary) in root.find_secondary_manifests() {
-                self.incorporate_secondary_manifest(Secondary {
+                secondaries.push(Secondary {
                     id: path.render(),
                     path,
                     data: secondary,
                 });
             }
         }
+
+        self.incorporate_secondary_manifests(secondaries);
+    }
+
+    fn incorporate_secondary_manifests(&mut self, secondaries: Vec<Secondary>) {
+        let overrides: Vec<_> = secondaries
+            .into_iter()
+            .map(|secondary| self.incorporate_secondary_manifest(secondary))
+            .collect();
+
+        for secondary in overrides {
+            self.apply_secondary_overrides(secondary);
+        }
     }
 
-    fn incorporate_secondary_manifest(&mut self, secondary: Secondary) {
+    /// Returns the entries that replace or remove a game,
+    /// which must be applied after all secondary manifests have been incorporated.
+    fn incorporate_secondary_manifest(&mut self, secondary: Secondary) -> Secondary {
         log::debug!("incorporating secondary manifest: {}", &secondary.id);
         let manifest = secondary.data.0;
+        let mut overrides = Manifest::default();
 
         for (name, mut game) in manifest {
             if name.is_empty() {
@@ -747,6 +1098,11 @@ impl Manifest {
 
             game.normalize_relative_paths();
 
+            if matches!(game.overrides, Some(Override::Replace | Override::Remove)) {
+                overrides.0.insert(name, game);
+                continue;
+            }
+
             if let Some(standard) = self.0.get_mut(&name) {
                 log::debug!("overriding game from secondary manifest: {name}");
 
@@ -794,6 +1150,38 @@ impl Manifest {
                 self.0.insert(name, game);
             }
         }
+
+        Secondary {
+            data: overrides,
+            ..secondary
+        }
+    }
+
+    fn apply_secondary_overrides(&mut self, secondary: Secondary) {
+        for (name, mut game) in secondary.data.0 {
+            match game.overrides {
+                Some(Override::Remove) => {
+                    log::debug!("removing game from secondary manifest: {name}");
+                    self.0.remove(&name);
+                }
+                Some(Override::Replace) => {
+                    log::debug!("replacing game from secondary manifest: {name}");
+
+                    if let Some(folder) = secondary.path.parent().and_then(|x| x.leaf()) {
+                        game.install_dir.insert(folder, GameInstallDirEntry {});
+                    }
+
+                    for note in &mut game.notes {
+                        note.source = Some(secondary.id.clone());
+                    }
+
+                    game.sources.insert(Source::Secondary(secondary.id.clone()));
+
+                    self.0.insert(name, game);
+                }
+                Some(Override::Merge) | None => {}
+            }
+        }
     }
 
     pub fn processable_titles(&self) -> impl Iterator<Item = &String> {
@@ -806,12 +1194,15 @@ impl Manifest {
                 alias,
                 files,
                 install_dir: _,
+                launch: _,
                 registry,
                 steam,
                 gog,
                 id,
                 cloud: _,
                 notes: _,
+                overrides: _,
+                store: _,
                 sources: _,
             } = &v;
             alias.is_none()
@@ -867,6 +1258,14 @@ mod tests {
     use super::*;
     use crate::testing::s;
 
+    #[test]
+    fn os_slug_matches_serialized_name() {
+        for os in [Os::Windows, Os::Linux, Os::Mac, Os::Other] {
+            assert_eq!(os, Os::from(os.slug()));
+            assert_eq!(format!("\"{}\"", os.slug()), serde_json::to_string(&os).unwrap());
+        }
+    }
+
     #[test]
     fn can_parse_game_with_no_fields() {
         let manifest = Manifest::load_from_string(
@@ -881,12 +1280,15 @@ mod tests {
                 alias: None,
                 files: Default::default(),
                 install_dir: Default::default(),
+                launch: Default::default(),
                 registry: Default::default(),
                 steam: Default::default(),
                 gog: Default::default(),
                 id: Default::default(),
                 cloud: Default::default(),
                 notes: Default::default(),
+                overrides: None,
+                store: None,
                 sources: Default::default(),
             },
             manifest.0["game"],
@@ -908,6 +1310,14 @@ mod tests {
                     - save
               installDir:
                 ExampleGame: {}
+              launch:
+                <base>/game.exe:
+                  - arguments: --windowed
+                    workingDir: <base>
+                    when:
+                      - bit: 64
+                        os: windows
+                        store: steam
               registry:
                 bar:
                   when:
@@ -950,6 +1360,17 @@ mod tests {
                 install_dir: btree_map! {
                     s("ExampleGame"): GameInstallDirEntry {}
                 },
+                launch: btree_map! {
+                    s("<base>/game.exe"): vec![GameLaunchEntry {
+                        arguments: Some(s("--windowed")),
+                        working_dir: Some(s("<base>")),
+                        when: btree_set![GameLaunchConstraint {
+                            bit: Some(64),
+                            os: Some(Os::Windows),
+                            store: Some(Store::Steam),
+                        }],
+                    }]
+                },
                 registry: btree_map! {
                     s("bar"): GameRegistryEntry {
                         when: btree_set![
@@ -976,12 +1397,137 @@ mod tests {
                     uplay: true
                 },
                 notes: Default::default(),
+                overrides: None,
+                store: None,
                 sources: Default::default(),
             },
             manifest.0["game"],
         );
     }
 
+    #[test]
+    fn can_build_conditional_headers_for_manifest_update() {
+        use reqwest::header::{IF_MODIFIED_SINCE, IF_NONE_MATCH};
+
+        assert_eq!(
+            Vec::<(reqwest::header::HeaderName, String)>::new(),
+            Manifest::conditional_headers(None)
+        );
+        assert_eq!(
+            vec![(IF_NONE_MATCH, s("abc"))],
+            Manifest::conditional_headers(Some(&cache::Manifest {
+                etag: Some(s("abc")),
+                ..Default::default()
+            }))
+        );
+        assert_eq!(
+            vec![
+                (IF_NONE_MATCH, s("abc")),
+                (IF_MODIFIED_SINCE, s("Wed, 21 Oct 2015 07:28:00 GMT"))
+            ],
+            Manifest::conditional_headers(Some(&cache::Manifest {
+                etag: Some(s("abc")),
+                last_modified: Some(s("Wed, 21 Oct 2015 07:28:00 GMT")),
+                ..Default::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn can_decide_whether_to_retry_manifest_update_without_conditions() {
+        use reqwest::StatusCode;
+
+        assert!(Manifest::should_retry(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(Manifest::should_retry(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(Manifest::should_retry(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!Manifest::should_retry(StatusCode::NOT_FOUND));
+        assert!(!Manifest::should_retry(StatusCode::FORBIDDEN));
+        assert!(!Manifest::should_retry(StatusCode::PRECONDITION_FAILED));
+    }
+
+    #[test]
+    fn can_check_if_launch_entry_applies() {
+        let entry = GameLaunchEntry {
+            when: btree_set![GameLaunchConstraint {
+                os: Some(Os::Windows),
+                store: Some(Store::Steam),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert!(GameLaunchEntry::default().applies_to(Os::Linux, Store::Other));
+        assert!(entry.applies_to(Os::Windows, Store::Steam));
+        assert!(!entry.applies_to(Os::Linux, Store::Steam));
+        assert!(!entry.applies_to(Os::Windows, Store::Gog));
+    }
+
+    #[test]
+    fn can_override_games_from_secondary_manifests() {
+        let mut manifest = Manifest::load_from_string(
+            r#"
+            merged:
+              files:
+                primary: {}
+            replaced:
+              files:
+                primary: {}
+            removed:
+              files:
+                primary: {}
+            "#,
+        )
+        .unwrap();
+
+        let secondary = |id: &str, content: &str| Secondary {
+            id: id.to_string(),
+            path: StrictPath::new(format!("/{id}.yaml")),
+            data: Manifest::load_from_string(content).unwrap(),
+        };
+
+        manifest.incorporate_secondary_manifests(vec![
+            secondary(
+                "first",
+                r#"
+                merged:
+                  files:
+                    first: {}
+                replaced:
+                  override: replace
+                  files:
+                    first: {}
+                removed:
+                  override: remove
+                "#,
+            ),
+            secondary(
+                "second",
+                r#"
+                replaced:
+                  files:
+                    second: {}
+                "#,
+            ),
+        ]);
+
+        assert_eq!(
+            btree_set![s("merged"), s("replaced")],
+            manifest.0.keys().cloned().collect::<BTreeSet<_>>()
+        );
+        assert_eq!(
+            btree_set![s("first"), s("primary")],
+            manifest.0["merged"].files.keys().cloned().collect::<BTreeSet<_>>()
+        );
+        assert_eq!(
+            btree_set![s("first")],
+            manifest.0["replaced"].files.keys().cloned().collect::<BTreeSet<_>>()
+        );
+        assert_eq!(
+            btree_set![Source::Secondary(s("first"))],
+            manifest.0["replaced"].sources
+        );
+    }
+
     #[test]
     fn can_parse_game_with_minimal_files() {
         let manifest = Manifest::load_from_string(
@@ -1044,6 +1590,64 @@ mod tests {
         assert!(manifest.0["game"].files["foo"].tags.is_empty());
     }
 
+    #[test]
+    fn can_match_game_by_tags() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            untagged:
+              files:
+                foo: {}
+            save:
+              files:
+                foo:
+                  tags: [save]
+            config:
+              registry:
+                foo:
+                  tags: [config]
+            both:
+              files:
+                foo:
+                  tags: [save]
+              registry:
+                bar:
+                  tags: [config]
+            "#,
+        )
+        .unwrap();
+
+        let untagged = &manifest.0["untagged"];
+        let save = &manifest.0["save"];
+        let config = &manifest.0["config"];
+        let both = &manifest.0["both"];
+
+        assert!(untagged.tags().is_empty());
+        assert_eq!(btree_set![Tag::Save, Tag::Config], both.tags());
+
+        // No filter.
+        assert!(untagged.matches_tags(&[], &[]));
+        assert!(both.matches_tags(&[], &[]));
+
+        // Only.
+        assert!(!untagged.matches_tags(&[Tag::Save], &[]));
+        assert!(save.matches_tags(&[Tag::Save], &[]));
+        assert!(!config.matches_tags(&[Tag::Save], &[]));
+        assert!(both.matches_tags(&[Tag::Save], &[]));
+        assert!(config.matches_tags(&[Tag::Save, Tag::Config], &[]));
+
+        // Exclude.
+        assert!(untagged.matches_tags(&[], &[Tag::Config]));
+        assert!(save.matches_tags(&[], &[Tag::Config]));
+        assert!(!config.matches_tags(&[], &[Tag::Config]));
+        assert!(!both.matches_tags(&[], &[Tag::Config]));
+
+        // Combined.
+        assert!(!untagged.matches_tags(&[Tag::Save], &[Tag::Config]));
+        assert!(save.matches_tags(&[Tag::Save], &[Tag::Config]));
+        assert!(!config.matches_tags(&[Tag::Save], &[Tag::Config]));
+        assert!(!both.matches_tags(&[Tag::Save], &[Tag::Config]));
+    }
+
     #[test]
     fn can_parse_game_with_minimal_install_dir() {
         let manifest = Manifest::load_from_string(
@@ -1166,4 +1770,64 @@ mod tests {
             manifest.aliases(),
         );
     }
+
+    #[test]
+    fn can_merge_included_games_into_custom_games() {
+        let mut manifest = Manifest::load_from_string(
+            r#"
+            demo:
+              files:
+                <base>/demo.sav: {}
+              installDir:
+                Demo: {}
+              registry:
+                HKEY_CURRENT_USER/Software/Demo: {}
+            full:
+              files:
+                <base>/full.sav: {}
+            "#,
+        )
+        .unwrap();
+
+        let config = Config {
+            custom_games: vec![
+                CustomGame {
+                    name: s("combined"),
+                    files: vec![s("<base>/custom.sav")],
+                    include: vec![s("demo"), s("full"), s("missing")],
+                    ..Default::default()
+                },
+                CustomGame {
+                    name: s("loop1"),
+                    include: vec![s("loop2")],
+                    ..Default::default()
+                },
+                CustomGame {
+                    name: s("loop2"),
+                    include: vec![s("loop1"), s("full")],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        manifest.add_custom_games(&config);
+
+        let combined = &manifest.0["combined"];
+        assert_eq!(
+            btree_set! { s("<base>/custom.sav"), s("<base>/demo.sav"), s("<base>/full.sav") },
+            combined.files.keys().cloned().collect::<BTreeSet<_>>(),
+        );
+        assert_eq!(
+            btree_set! { s("HKEY_CURRENT_USER/Software/Demo") },
+            combined.registry.keys().cloned().collect::<BTreeSet<_>>(),
+        );
+        assert!(combined.install_dir.contains_key("Demo"));
+
+        for name in ["loop1", "loop2"] {
+            assert_eq!(
+                btree_set! { s("<base>/full.sav") },
+                manifest.0[name].files.keys().cloned().collect::<BTreeSet<_>>(),
+            );
+        }
+    }
 }
@@ -1,18 +1,24 @@
 mod backup;
 mod change;
+mod delta;
 mod duplicate;
+pub mod external;
 pub mod game_filter;
+mod hook;
 pub mod launchers;
 pub mod layout;
+mod plan;
 mod preview;
+mod process;
 pub mod registry;
 mod saves;
 mod steam;
 mod title;
+mod warning;
 
 use std::{
     collections::{HashMap, HashSet},
-    sync::LazyLock,
+    sync::{LazyLock, RwLock},
 };
 
 use regex::Regex;
@@ -20,21 +26,31 @@ use regex::Regex;
 #[allow(unused)]
 pub use self::{
     backup::{BackupError, BackupId, BackupInfo, OperationStatus, OperationStepDecision},
-    change::{ScanChange, ScanChangeCount},
+    change::{ChangeSummary, ScanChange, ScanChangeCount},
     duplicate::{DuplicateDetector, Duplication},
+    hook::BackupHooks,
     launchers::{LauncherGame, Launchers},
+    plan::ScanPlan,
     preview::ScanInfo,
-    saves::{ScannedFile, ScannedRegistry, ScannedRegistryValue, ScannedRegistryValues},
+    process::RunningProcesses,
+    saves::{
+        FileExclusion, HardlinkTarget, ScannedFile, ScannedRegistry, ScannedRegistryValue, ScannedRegistryValues,
+        SymlinkTarget,
+    },
     steam::{SteamShortcut, SteamShortcuts},
     title::{compare_ranked_titles, compare_ranked_titles_ref, TitleFinder, TitleMatch, TitleQuery},
+    warning::OperationWarning,
 };
 
 use crate::{
-    path::{CommonPath, StrictPath},
+    path::{CommonPath, FileIdentity, StrictPath},
     prelude::{filter_map_walkdir, Error, SKIP},
     resource::{
-        config::{BackupFilter, RedirectConfig, RedirectKind, Root, SortKey, ToggledPaths, ToggledRegistry},
-        manifest::{Game, GameFileEntry, IdSet, Os, Store},
+        config::{
+            BackupFilter, Config, HashAlgorithm, RedirectConfig, RedirectKind, Root, SortKey, ToggledPaths,
+            ToggledRegistry,
+        },
+        manifest::{Game, GameFileEntry, IdSet, Os, Store, Tag},
     },
     scan::layout::LatestBackup,
 };
@@ -86,11 +102,19 @@ pub fn game_file_target(
             ScanKind::Backup => match redirect.kind {
                 RedirectKind::Backup | RedirectKind::Bidirectional => (&redirect.source, &redirect.target),
                 RedirectKind::Restore => continue,
+                RedirectKind::Regex => {
+                    redirected = redirect_by_regex(&redirected, &redirect.source, &redirect.target);
+                    continue;
+                }
             },
             ScanKind::Restore => match redirect.kind {
                 RedirectKind::Backup => continue,
                 RedirectKind::Restore => (&redirect.source, &redirect.target),
                 RedirectKind::Bidirectional => (&redirect.target, &redirect.source),
+                RedirectKind::Regex => {
+                    redirected = redirect_by_regex(&redirected, &redirect.source, &redirect.target);
+                    continue;
+                }
             },
         };
         redirected = redirected.replace(source, target);
@@ -99,6 +123,112 @@ pub fn game_file_target(
     (original != &redirected).then_some(redirected)
 }
 
+/// Add a one-off restore redirect that takes effect after all of the configured ones.
+/// When redirects are reversed on restore, it goes at the front so that it's still applied last.
+pub fn with_final_restore_redirect(
+    redirects: &[RedirectConfig],
+    source: StrictPath,
+    target: StrictPath,
+    reverse_redirects_on_restore: bool,
+) -> Vec<RedirectConfig> {
+    let extra = RedirectConfig {
+        kind: RedirectKind::Restore,
+        source,
+        target,
+    };
+
+    let mut redirects = redirects.to_vec();
+    if reverse_redirects_on_restore {
+        redirects.insert(0, extra);
+    } else {
+        redirects.push(extra);
+    }
+    redirects
+}
+
+/// Add restore redirects that translate Windows paths into a Wine prefix,
+/// so that a backup from Windows can be restored on another OS.
+/// Like `with_final_restore_redirect`, these take effect after all of the configured ones.
+pub fn with_wine_prefix_redirects(
+    redirects: &[RedirectConfig],
+    prefix: &StrictPath,
+    reverse_redirects_on_restore: bool,
+) -> Vec<RedirectConfig> {
+    let Ok(prefix) = prefix.interpret() else {
+        log::warn!("Unable to use Wine prefix for restore: {:?}", prefix);
+        return redirects.to_vec();
+    };
+
+    // Proton always uses this user name, while plain Wine uses the OS user name.
+    let users = StrictPath::new(format!("{prefix}/drive_c/users"));
+    let user = if users.joined("steamuser").is_dir() {
+        "steamuser".to_string()
+    } else {
+        crate::prelude::OS_USERNAME.to_string()
+    };
+
+    let drive_c = format!("{prefix}/drive_c").replace('$', "$$");
+    let user = user.replace('$', "$$");
+    let extras = [
+        ("(?i)^C:/Users/Public/", format!("{drive_c}/users/Public/")),
+        ("(?i)^C:/Users/[^/]+/", format!("{drive_c}/users/{user}/")),
+        ("(?i)^C:/", format!("{drive_c}/")),
+    ]
+    .into_iter()
+    .map(|(source, target)| RedirectConfig {
+        kind: RedirectKind::Regex,
+        source: StrictPath::new(source.to_string()),
+        target: StrictPath::new(target),
+    });
+
+    let mut redirects = redirects.to_vec();
+    if reverse_redirects_on_restore {
+        let extras: Vec<_> = extras.rev().collect();
+        redirects.splice(0..0, extras);
+    } else {
+        redirects.extend(extras);
+    }
+    redirects
+}
+
+/// The pattern is matched against the rendered path, which always uses forward slashes.
+/// The replacement may refer to capture groups like `$1` or `${name}`.
+fn redirect_by_regex(original: &StrictPath, pattern: &StrictPath, replacement: &StrictPath) -> StrictPath {
+    let Some(regex) = redirect_regex(pattern.raw()) else {
+        return original.clone();
+    };
+
+    let rendered = original.render();
+    if !regex.is_match(&rendered) {
+        return original.clone();
+    }
+
+    StrictPath::new(regex.replace(&rendered, replacement.raw()).to_string())
+}
+
+/// Redirects are checked for every scanned file,
+/// so each pattern is only compiled (and reported if invalid) once per run.
+fn redirect_regex(pattern: &str) -> Option<Regex> {
+    static CACHE: LazyLock<RwLock<HashMap<String, Option<Regex>>>> = LazyLock::new(Default::default);
+
+    if let Some(regex) = CACHE.read().unwrap().get(pattern) {
+        return regex.clone();
+    }
+
+    CACHE
+        .write()
+        .unwrap()
+        .entry(pattern.to_string())
+        .or_insert_with(|| match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                log::warn!("Invalid redirect regex: {:?} | {e}", pattern);
+                None
+            }
+        })
+        .clone()
+}
+
 fn check_windows_path(path: &str) -> &str {
     match Os::HOST {
         Os::Windows => path,
@@ -498,7 +628,8 @@ pub fn parse_paths(
         .collect()
 }
 
-pub fn scan_game_for_backup(
+/// Determine what a backup scan would check, without reading any file content.
+pub fn plan_game_for_backup(
     game: &Game,
     name: &str,
     roots: &[Root],
@@ -506,24 +637,12 @@ pub fn scan_game_for_backup(
     launchers: &Launchers,
     filter: &BackupFilter,
     wine_prefix: Option<&StrictPath>,
-    ignored_paths: &ToggledPaths,
-    #[cfg_attr(not(target_os = "windows"), allow(unused))] ignored_registry: &ToggledRegistry,
-    previous: Option<LatestBackup>,
-    redirects: &[RedirectConfig],
-    reverse_redirects_on_restore: bool,
     steam_shortcuts: &SteamShortcuts,
-    only_constructive_backups: bool,
-) -> ScanInfo {
-    log::trace!("[{name}] beginning scan for backup");
-
-    let mut found_files = HashMap::new();
-    #[cfg_attr(not(target_os = "windows"), allow(unused))]
-    let mut found_registry_keys = HashMap::new();
-    #[allow(unused)]
-    let mut dumped_registry = None;
-    let has_backups = previous.is_some();
+) -> ScanPlan {
+    log::trace!("[{name}] planning scan for backup");
 
     let mut paths_to_check = HashSet::<(StrictPath, Option<bool>)>::new();
+    let mut save_paths = HashSet::<StrictPath>::new();
 
     // Add a dummy root for checking paths without `<root>`.
     let mut roots_to_check: Vec<Root> = vec![Root::new(SKIP, Store::Other)];
@@ -554,6 +673,12 @@ pub fn scan_game_for_backup(
         }
     }
 
+    let planned_roots: Vec<_> = roots_to_check
+        .iter()
+        .filter(|root| root.path().raw() != SKIP)
+        .cloned()
+        .collect();
+
     for root in roots_to_check {
         log::trace!("[{name}] adding candidates from root: {:?}", &root,);
         if root.path().raw().trim().is_empty() {
@@ -564,6 +689,18 @@ pub fn scan_game_for_backup(
             continue;
         };
 
+        // A custom game may pin its store, in which case we expand its paths as if each configured root
+        // belonged to that store. Launcher lookups still use the original root, since that's how they're keyed.
+        let expansion_root = match game.store {
+            Some(store) if roots.contains(&root) => {
+                let mut pinned = root.clone();
+                pinned.set_store(store);
+                log::trace!("[{name}] expanding paths with pinned store: {:?}", &pinned);
+                pinned
+            }
+            _ => root.clone(),
+        };
+
         for (raw_path, path_data) in &game.files {
             log::trace!("[{name}] parsing candidates from: {}", raw_path);
             if raw_path.trim().is_empty() {
@@ -581,7 +718,7 @@ pub fn scan_game_for_backup(
                 candidates.extend(parse_paths(
                     raw_path,
                     path_data,
-                    &root,
+                    &expansion_root,
                     install_dir,
                     full_install_dir,
                     &all_ids,
@@ -599,7 +736,7 @@ pub fn scan_game_for_backup(
                     candidates.extend(parse_paths(
                         raw_path,
                         path_data,
-                        &root,
+                        &expansion_root,
                         install_dir.as_ref(),
                         full_install_dir,
                         &all_ids,
@@ -612,10 +749,13 @@ pub fn scan_game_for_backup(
 
             for (candidate, case_sensitive) in candidates {
                 log::trace!("[{name}] parsed candidate: {candidate:?}");
+                if path_data.tags.contains(&Tag::Save) {
+                    save_paths.insert(candidate.clone());
+                }
                 paths_to_check.insert((candidate, Some(case_sensitive)));
             }
         }
-        if root.store() == Store::Steam {
+        if expansion_root.store() == Store::Steam {
             for id in all_ids.steam(steam_shortcut.map(|x| x.id)) {
                 // Cloud saves:
                 paths_to_check.insert((
@@ -649,6 +789,123 @@ pub fn scan_game_for_backup(
         }
     }
 
+    let mut registry_to_check = vec![];
+    for key in game.registry.keys() {
+        if key.trim().is_empty() {
+            continue;
+        }
+
+        log::trace!("[{name}] computing candidates for registry: {key}");
+        registry_to_check.push(key.clone());
+        let normalized = key.replace('\\', "/").to_lowercase();
+        if normalized.starts_with("hkey_local_machine/software/") && !normalized.contains("/wow6432node/") {
+            let tail = &key[28..];
+            registry_to_check.push(format!("HKEY_LOCAL_MACHINE/SOFTWARE/Wow6432Node/{}", tail));
+            registry_to_check.push(format!(
+                "HKEY_CURRENT_USER/Software/Classes/VirtualStore/MACHINE/SOFTWARE/{}",
+                tail
+            ));
+            registry_to_check.push(format!(
+                "HKEY_CURRENT_USER/Software/Classes/VirtualStore/MACHINE/SOFTWARE/Wow6432Node/{}",
+                tail
+            ));
+        }
+    }
+
+    log::trace!("[{name}] completed plan for backup");
+
+    ScanPlan {
+        game_name: name.to_string(),
+        roots: planned_roots,
+        paths: paths_to_check,
+        save_paths,
+        registry: registry_to_check,
+        ignored_paths: filter.ignored_paths.clone(),
+        ignored_registry: filter.ignored_registry.clone(),
+        exclude_store_screenshots: filter.exclude_store_screenshots,
+    }
+}
+
+/// Settings for `scan_game_for_backup` beyond what identifies the game and where to look.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BackupScanOptions<'a> {
+    /// Used to tell if the game is running. If unset, it is assumed not to be.
+    pub processes: Option<&'a RunningProcesses>,
+    pub only_constructive_backups: bool,
+    pub follow_symlinks: bool,
+    pub hash_algorithm: HashAlgorithm,
+    /// Skip walking and hashing the game's files
+    /// if none of them or their folders have been modified since the previous backup.
+    pub quick_scan: bool,
+    /// Globs to exclude within the game's matched folders.
+    pub subpath_exclusions: &'a [String],
+}
+
+impl<'a> BackupScanOptions<'a> {
+    /// Options from the config for a particular game.
+    pub fn from_config(config: &'a Config, game: &str) -> Self {
+        Self {
+            only_constructive_backups: config.backup.only_constructive,
+            follow_symlinks: config.backup.follow_symlinks,
+            hash_algorithm: config.backup.hash,
+            subpath_exclusions: config.backup.exclusions_for(game),
+            ..Default::default()
+        }
+    }
+}
+
+pub fn scan_game_for_backup(
+    game: &Game,
+    name: &str,
+    roots: &[Root],
+    manifest_dir: &StrictPath,
+    launchers: &Launchers,
+    filter: &BackupFilter,
+    wine_prefix: Option<&StrictPath>,
+    ignored_paths: &ToggledPaths,
+    #[cfg_attr(not(target_os = "windows"), allow(unused))] ignored_registry: &ToggledRegistry,
+    previous: Option<LatestBackup>,
+    redirects: &[RedirectConfig],
+    reverse_redirects_on_restore: bool,
+    steam_shortcuts: &SteamShortcuts,
+    options: &BackupScanOptions,
+) -> ScanInfo {
+    log::trace!("[{name}] beginning scan for backup");
+
+    let BackupScanOptions {
+        processes,
+        only_constructive_backups,
+        follow_symlinks,
+        hash_algorithm,
+        quick_scan,
+        subpath_exclusions,
+    } = *options;
+
+    let mut found_files = HashMap::new();
+    #[cfg_attr(not(target_os = "windows"), allow(unused))]
+    let mut found_registry_keys = HashMap::new();
+    #[allow(unused)]
+    let mut dumped_registry = None;
+    let has_backups = previous.is_some();
+    let mut warnings = vec![];
+    // Allow some leeway for files that are being written during the scan.
+    let clock_limit = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+
+    let plan = plan_game_for_backup(
+        game,
+        name,
+        roots,
+        manifest_dir,
+        launchers,
+        filter,
+        wine_prefix,
+        steam_shortcuts,
+    );
+
+    let previous_hash_algorithm = previous
+        .as_ref()
+        .map(|previous| previous.scan.hash_algorithm)
+        .unwrap_or(hash_algorithm);
     let previous_files: HashMap<&StrictPath, &String> = previous
         .as_ref()
         .map(|previous| {
@@ -661,7 +918,30 @@ pub fn scan_game_for_backup(
         })
         .unwrap_or_default();
 
-    for (path, case_sensitive) in paths_to_check {
+    // If nothing has been touched since the last backup,
+    // then we can skip walking the save folders and reuse that backup's hashes,
+    // as long as that backup used the same algorithm.
+    // Redirects and subpath exclusions change how the found files map to the backup,
+    // so we don't try to account for them here.
+    let quick_scan_files = previous
+        .as_ref()
+        .filter(|_| {
+            quick_scan
+                && previous_hash_algorithm == hash_algorithm
+                && redirects.is_empty()
+                && subpath_exclusions.is_empty()
+        })
+        .and_then(|previous| find_unchanged_files(name, previous, &plan.paths, filter, ignored_paths, follow_symlinks));
+    let paths = match quick_scan_files {
+        Some(files) => {
+            log::debug!("[{name}] unchanged since last backup, skipping full scan");
+            found_files = files;
+            Default::default()
+        }
+        None => plan.paths,
+    };
+
+    for (path, case_sensitive) in paths {
         log::trace!("[{name}] checking: {path:?}");
         if filter.is_path_ignored(&path) {
             log::debug!("[{name}] excluded: {path:?}");
@@ -673,20 +953,39 @@ pub fn scan_game_for_backup(
         };
         for p in paths {
             if p.is_file() {
-                let Ok(scan_key) = p.interpreted().map(|x| x.rendered()) else {
+                let symlink = if follow_symlinks {
+                    None
+                } else {
+                    p.read_link().map(SymlinkTarget)
+                };
+                let scan_key = match symlink {
+                    None => p.interpreted(),
+                    Some(_) => p.interpreted_link(),
+                };
+                let Ok(scan_key) = scan_key.map(|x| x.rendered()) else {
+                    warnings.push(OperationWarning::SkippedFile { path: p.render() });
                     continue;
                 };
                 if filter.is_path_ignored(&scan_key) {
                     log::debug!("[{name}] excluded: {scan_key:?}");
                     continue;
                 }
-                let ignored = ignored_paths.is_ignored(name, &scan_key);
                 log::debug!("[{name}] found: {scan_key:?}");
                 let size = scan_key.size();
-                let hash = scan_key.sha1();
+                let excluded = filter.size_exclusion(size);
+                if let Some(excluded) = excluded {
+                    log::debug!("[{name}] excluded by size: {scan_key:?}, {excluded:?}");
+                }
+                let ignored = excluded.is_some() || ignored_paths.is_ignored(name, &scan_key);
                 let redirected = game_file_target(&scan_key, redirects, reverse_redirects_on_restore, ScanKind::Backup);
-                let change =
-                    ScanChange::evaluate_backup(&hash, previous_files.get(redirected.as_ref().unwrap_or(&scan_key)));
+                let hash = inspect_found_file(name, &scan_key, hash_algorithm, clock_limit, &mut warnings);
+                let change = evaluate_backup_change(
+                    &scan_key,
+                    &hash,
+                    hash_algorithm,
+                    previous_files.get(redirected.as_ref().unwrap_or(&scan_key)),
+                    previous_hash_algorithm,
+                );
                 found_files.insert(
                     scan_key,
                     ScannedFile {
@@ -697,10 +996,18 @@ pub fn scan_game_for_backup(
                         original_path: None,
                         ignored,
                         container: None,
+                        excluded,
+                        symlink,
+                        hardlink: None,
+                        conflict: false,
                     },
                 );
             } else if p.is_dir() {
                 log::trace!("[{name}] looking for files in: {p:?}");
+                let excluded_subpaths = build_subpath_exclusions(&p, subpath_exclusions);
+                // Linked folders are always traversed.
+                // WalkDir detects any loops that this creates and reports them as errors,
+                // which we log and skip.
                 for child in walkdir::WalkDir::new(p.as_std_path_buf().unwrap())
                     .max_depth(100)
                     .follow_links(true)
@@ -710,11 +1017,26 @@ pub fn scan_game_for_backup(
                     #[cfg(not(target_os = "windows"))]
                     if child.path().to_string_lossy().contains('\\') {
                         // TODO: Support names containing a slash.
+                        warnings.push(OperationWarning::SkippedFile {
+                            path: StrictPath::from(&child).render(),
+                        });
                         continue;
                     }
 
                     if child.file_type().is_file() {
-                        let Ok(scan_key) = StrictPath::from(&child).interpreted().map(|x| x.rendered()) else {
+                        let symlink = if !follow_symlinks && child.path_is_symlink() {
+                            StrictPath::from(&child).read_link().map(SymlinkTarget)
+                        } else {
+                            None
+                        };
+                        let scan_key = match symlink {
+                            None => StrictPath::from(&child).interpreted(),
+                            Some(_) => StrictPath::from(&child).interpreted_link(),
+                        };
+                        let Ok(scan_key) = scan_key.map(|x| x.rendered()) else {
+                            warnings.push(OperationWarning::SkippedFile {
+                                path: StrictPath::from(&child).render(),
+                            });
                             continue;
                         };
 
@@ -722,15 +1044,29 @@ pub fn scan_game_for_backup(
                             log::debug!("[{name}] excluded: {scan_key:?}");
                             continue;
                         }
-                        let ignored = ignored_paths.is_ignored(name, &scan_key);
+                        if excluded_subpaths
+                            .as_ref()
+                            .is_some_and(|x| x.is_match(scan_key.render()))
+                        {
+                            log::debug!("[{name}] excluded by game subpath: {scan_key:?}");
+                            continue;
+                        }
                         log::debug!("[{name}] found: {scan_key:?}");
                         let size = scan_key.size();
-                        let hash = scan_key.sha1();
+                        let excluded = filter.size_exclusion(size);
+                        if let Some(excluded) = excluded {
+                            log::debug!("[{name}] excluded by size: {scan_key:?}, {excluded:?}");
+                        }
+                        let ignored = excluded.is_some() || ignored_paths.is_ignored(name, &scan_key);
                         let redirected =
                             game_file_target(&scan_key, redirects, reverse_redirects_on_restore, ScanKind::Backup);
-                        let change = ScanChange::evaluate_backup(
+                        let hash = inspect_found_file(name, &scan_key, hash_algorithm, clock_limit, &mut warnings);
+                        let change = evaluate_backup_change(
+                            &scan_key,
                             &hash,
+                            hash_algorithm,
                             previous_files.get(redirected.as_ref().unwrap_or(&scan_key)),
+                            previous_hash_algorithm,
                         );
                         found_files.insert(
                             scan_key,
@@ -742,6 +1078,10 @@ pub fn scan_game_for_backup(
                                 original_path: None,
                                 ignored,
                                 container: None,
+                                excluded,
+                                symlink,
+                                hardlink: None,
+                                conflict: false,
                             },
                         );
                     }
@@ -750,6 +1090,8 @@ pub fn scan_game_for_backup(
         }
     }
 
+    link_hardlinked_files(name, &mut found_files);
+
     // Mark removed files.
     let current_files: Vec<_> = found_files
         .iter()
@@ -778,6 +1120,10 @@ pub fn scan_game_for_backup(
                     original_path: None,
                     ignored: ignored_paths.is_ignored(name, previous_file),
                     container: None,
+                    excluded: None,
+                    symlink: None,
+                    hardlink: None,
+                    conflict: false,
                 },
             );
         }
@@ -788,58 +1134,47 @@ pub fn scan_game_for_backup(
         let previous_registry = previous.and_then(|x| x.registry_content);
         let mut current_registry = registry::Hives::default();
 
-        for key in game.registry.keys() {
-            if key.trim().is_empty() {
-                continue;
-            }
-
-            log::trace!("[{name}] computing candidates for registry: {key}");
-            let mut candidates = vec![key.clone()];
-            let normalized = key.replace('\\', "/").to_lowercase();
-            if normalized.starts_with("hkey_local_machine/software/") && !normalized.contains("/wow6432node/") {
-                let tail = &key[28..];
-                candidates.push(format!("HKEY_LOCAL_MACHINE/SOFTWARE/Wow6432Node/{}", tail));
-                candidates.push(format!(
-                    "HKEY_CURRENT_USER/Software/Classes/VirtualStore/MACHINE/SOFTWARE/{}",
-                    tail
-                ));
-                candidates.push(format!(
-                    "HKEY_CURRENT_USER/Software/Classes/VirtualStore/MACHINE/SOFTWARE/Wow6432Node/{}",
-                    tail
-                ));
-            }
-
-            for candidate in &candidates {
-                log::trace!("[{name}] checking registry: {candidate}");
-                for (scan_key, mut scanned) in
-                    registry::win::scan_registry(name, candidate, filter, ignored_registry, previous_registry.as_ref())
-                        .unwrap_or_default()
-                {
-                    log::debug!("[{name}] found registry: {}", scan_key.raw());
-
-                    // Mark removed registry values.
-                    let previous_values = previous_registry
-                        .as_ref()
-                        .and_then(|x| x.get_path(&scan_key).map(|y| y.0.keys().cloned().collect::<Vec<_>>()))
-                        .unwrap_or_default();
-                    for previous_value in previous_values {
-                        #[allow(clippy::map_entry)]
-                        if !scanned.values.contains_key(&previous_value) {
-                            let ignored = ignored_registry.is_ignored(name, &scan_key, Some(&previous_value));
-                            scanned.values.insert(
-                                previous_value,
-                                ScannedRegistryValue {
-                                    ignored,
-                                    change: ScanChange::Removed,
-                                },
-                            );
-                        }
+        for candidate in &plan.registry {
+            log::trace!("[{name}] checking registry: {candidate}");
+            let scanned_keys = match registry::win::scan_registry(
+                name,
+                candidate,
+                filter,
+                ignored_registry,
+                previous_registry.as_ref(),
+            ) {
+                Ok(x) => x,
+                Err(_) => {
+                    warnings.push(OperationWarning::InaccessibleRegistry { key: candidate.clone() });
+                    continue;
+                }
+            };
+            for (scan_key, mut scanned) in scanned_keys {
+                log::debug!("[{name}] found registry: {}", scan_key.raw());
+
+                // Mark removed registry values.
+                let previous_values = previous_registry
+                    .as_ref()
+                    .and_then(|x| x.get_path(&scan_key).map(|y| y.0.keys().cloned().collect::<Vec<_>>()))
+                    .unwrap_or_default();
+                for previous_value in previous_values {
+                    #[allow(clippy::map_entry)]
+                    if !scanned.values.contains_key(&previous_value) {
+                        let ignored = ignored_registry.is_ignored(name, &scan_key, Some(&previous_value))
+                            || filter.is_registry_value_ignored(&scan_key, &previous_value);
+                        scanned.values.insert(
+                            previous_value,
+                            ScannedRegistryValue {
+                                ignored,
+                                change: ScanChange::Removed,
+                            },
+                        );
                     }
+                }
 
-                    let _ = current_registry.back_up_key(name, &scan_key, &scanned);
+                let _ = current_registry.back_up_key(name, &scan_key, &scanned);
 
-                    found_registry_keys.insert(scan_key, scanned);
-                }
+                found_registry_keys.insert(scan_key, scanned);
             }
         }
 
@@ -867,17 +1202,258 @@ pub fn scan_game_for_backup(
         dumped_registry = (!current_registry.is_empty()).then_some(current_registry);
     }
 
+    warnings.extend(find_overlapping_roots(roots, &found_files));
+
+    let install_dirs: Vec<_> = roots
+        .iter()
+        .flat_map(|root| launchers.get_game(root, name))
+        .filter_map(|x| x.install_dir.clone())
+        .collect();
+    let game_running = processes.is_some_and(|processes| is_game_running(game, &install_dirs, processes));
+    if game_running {
+        log::warn!("[{name}] game appears to be running");
+        warnings.push(OperationWarning::GameRunning);
+    }
+
+    warnings.sort();
+    warnings.dedup();
+
     log::trace!("[{name}] completed scan for backup");
 
     ScanInfo {
         game_name: name.to_string(),
         found_files,
         found_registry_keys,
+        hash_algorithm,
         available_backups: vec![],
         backup: None,
         has_backups,
         dumped_registry,
         only_constructive_backups,
+        warnings,
+        game_running,
+    }
+}
+
+/// Best-effort check for whether a game is currently running,
+/// based on whether any process was launched from the game's install folder.
+/// The manifest doesn't list executables, so this relies on the `installDir` names
+/// and on any install folders that were found through launchers.
+pub fn is_game_running(game: &Game, install_dirs: &[StrictPath], processes: &RunningProcesses) -> bool {
+    let names: Vec<_> = game
+        .install_dir
+        .keys()
+        .map(|x| x.as_str())
+        .filter(|x| !x.trim().is_empty())
+        .collect();
+
+    processes.any_inside(install_dirs, &names)
+}
+
+/// Compare against the previous backup, rehashing if it used a different algorithm.
+fn evaluate_backup_change(
+    scan_key: &StrictPath,
+    hash: &str,
+    hash_algorithm: HashAlgorithm,
+    previous_hash: Option<&&String>,
+    previous_hash_algorithm: HashAlgorithm,
+) -> ScanChange {
+    if previous_hash.is_some() && hash_algorithm != previous_hash_algorithm {
+        ScanChange::evaluate_backup(&scan_key.hash(previous_hash_algorithm), previous_hash)
+    } else {
+        ScanChange::evaluate_backup(hash, previous_hash)
+    }
+}
+
+/// Per-game exclusions are relative to each folder that the game's save paths matched.
+/// Like globally ignored paths, a pattern that matches a folder also excludes its children.
+/// Patterns follow the host's case sensitivity.
+fn build_subpath_exclusions(folder: &StrictPath, patterns: &[String]) -> Option<globset::GlobSet> {
+    if patterns.is_empty() {
+        return None;
+    }
+
+    let base = globset::escape(&folder.interpreted().ok()?.globbable());
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.trim().trim_matches(['/', '\\']).replace('\\', "/");
+        if pattern.is_empty() {
+            continue;
+        }
+
+        for variant in [format!("{base}/{pattern}"), format!("{base}/{pattern}/**")] {
+            if let Ok(glob) = globset::GlobBuilder::new(&variant)
+                .literal_separator(true)
+                .backslash_escape(false)
+                .case_insensitive(!Os::HOST.is_case_sensitive())
+                .build()
+            {
+                builder.add(glob);
+            }
+        }
+    }
+
+    builder.build().ok()
+}
+
+/// For quick scans, check whether the files from the last backup are all still in place,
+/// with the same size, and whether neither they nor their folders have been modified since then.
+/// Adding, removing, or renaming a file updates its folder's modification time,
+/// so this catches new files as long as they're next to the old ones.
+/// A new file could also be in a new folder, like another save slot,
+/// so we check each folder up to the save path that the old files were found in,
+/// along with the save paths themselves.
+fn find_unchanged_files(
+    name: &str,
+    previous: &LatestBackup,
+    save_paths: &HashSet<(StrictPath, Option<bool>)>,
+    filter: &BackupFilter,
+    ignored_paths: &ToggledPaths,
+    follow_symlinks: bool,
+) -> Option<HashMap<StrictPath, ScannedFile>> {
+    let unmodified = |path: &StrictPath| {
+        path.get_mtime()
+            .is_ok_and(|mtime| chrono::DateTime::<chrono::Utc>::from(mtime) < previous.when)
+    };
+
+    let save_folders: Vec<_> = save_paths
+        .iter()
+        .filter_map(|(path, _)| external::glob_free_prefix(&path.render()))
+        .map(StrictPath::new)
+        .collect();
+
+    let mut found_files = HashMap::new();
+    let mut folders: HashSet<_> = save_folders.iter().filter(|x| x.is_dir()).cloned().collect();
+
+    for (scan_key, previous_file) in &previous.scan.found_files {
+        let path = previous_file.original_path(scan_key);
+        if previous_file.hash.is_empty() || filter.is_path_ignored(path) || !path.is_file() || !unmodified(path) {
+            return None;
+        }
+
+        let size = path.size();
+        if size != previous_file.size {
+            return None;
+        }
+
+        let mut folder = path.parent();
+        while let Some(current) = folder {
+            folder = save_folders
+                .iter()
+                .any(|x| x.is_prefix_of(&current))
+                .then(|| current.parent())
+                .flatten();
+            folders.insert(current);
+        }
+
+        let symlink = if follow_symlinks {
+            None
+        } else {
+            path.read_link().map(SymlinkTarget)
+        };
+        let excluded = filter.size_exclusion(size);
+        let ignored = excluded.is_some() || ignored_paths.is_ignored(name, path);
+
+        found_files.insert(
+            path.clone(),
+            ScannedFile {
+                change: ScanChange::Same,
+                size,
+                hash: previous_file.hash.clone(),
+                redirected: None,
+                original_path: None,
+                ignored,
+                container: None,
+                excluded,
+                symlink,
+                hardlink: None,
+                conflict: false,
+            },
+        );
+    }
+
+    // With no files to go by, we can't tell if any were added.
+    if found_files.is_empty() || !folders.iter().all(unmodified) {
+        return None;
+    }
+
+    Some(found_files)
+}
+
+/// Hash a found file, noting any problems that don't prevent it from being backed up.
+fn inspect_found_file(
+    name: &str,
+    scan_key: &StrictPath,
+    hash_algorithm: HashAlgorithm,
+    clock_limit: std::time::SystemTime,
+    warnings: &mut Vec<OperationWarning>,
+) -> String {
+    if scan_key.get_mtime().is_ok_and(|x| x > clock_limit) {
+        log::warn!("[{name}] file modified in the future: {scan_key:?}");
+        warnings.push(OperationWarning::FutureModifiedTime {
+            path: scan_key.render(),
+        });
+    }
+
+    match scan_key.try_hash(hash_algorithm) {
+        Ok(hash) => hash,
+        Err(e) => {
+            log::warn!("[{name}] unable to read file: {scan_key:?} | {e:?}");
+            warnings.push(OperationWarning::UnreadableFile {
+                path: scan_key.render(),
+            });
+            "".to_string()
+        }
+    }
+}
+
+/// Only reports nested roots that actually contain some of the found files.
+fn find_overlapping_roots(roots: &[Root], found_files: &HashMap<StrictPath, ScannedFile>) -> Vec<OperationWarning> {
+    let mut warnings = vec![];
+
+    for parent in roots {
+        for child in roots {
+            if !parent.path().is_prefix_of(child.path()) {
+                continue;
+            }
+            if found_files.keys().any(|file| child.path().is_prefix_of(file)) {
+                warnings.push(OperationWarning::OverlappingRoots {
+                    parent: parent.path().render(),
+                    child: child.path().render(),
+                });
+            }
+        }
+    }
+
+    warnings
+}
+
+/// When several found files are hardlinks to the same content,
+/// the first one by path is kept as-is and the others are marked as links to it,
+/// so that restoring can recreate the links instead of separate copies.
+fn link_hardlinked_files(name: &str, found_files: &mut HashMap<StrictPath, ScannedFile>) {
+    let mut groups = HashMap::<FileIdentity, Vec<(String, StrictPath)>>::new();
+    for (scan_key, file) in found_files.iter() {
+        if file.symlink.is_some() {
+            continue;
+        }
+        if let Some(identity) = scan_key.hardlink_identity() {
+            groups
+                .entry(identity)
+                .or_default()
+                .push((file.mapping_key(scan_key), scan_key.clone()));
+        }
+    }
+
+    for mut group in groups.into_values().filter(|x| x.len() > 1) {
+        group.sort_by(|x, y| x.0.cmp(&y.0));
+        let primary = group[0].0.clone();
+        for (mapping_key, scan_key) in group.into_iter().skip(1) {
+            log::debug!("[{name}] found hardlink: {mapping_key} -> {primary}");
+            if let Some(file) = found_files.get_mut(&scan_key) {
+                file.hardlink = Some(HardlinkTarget(primary.clone()));
+            }
+        }
     }
 }
 
@@ -959,8 +1535,6 @@ mod tests {
         testing::{repo, s, EMPTY_HASH},
     };
 
-    const ONLY_CONSTRUCTIVE: bool = false;
-
     fn config() -> Config {
         Config::load_from_string(&format!(
             r#"
@@ -1126,40 +1700,456 @@ mod tests {
     }
 
     #[test]
-    fn can_scan_game_for_backup_with_file_matches() {
-        assert_eq!(
-            ScanInfo {
-                game_name: s("game1"),
-                found_files: hash_map! {
-                    format!("{}/tests/root1/game1/subdir/file2.txt", repo()).into(): ScannedFile::new(2, "9d891e731f75deae56884d79e9816736b7488080").change_new(),
-                    format!("{}/tests/root2/game1/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
-                },
-                found_registry_keys: hash_map! {},
-                ..Default::default()
-            },
-            scan_game_for_backup(
-                &manifest().0["game1"],
-                "game1",
-                &config().roots,
-                &StrictPath::new(repo()),
-                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
-                &BackupFilter::default(),
-                None,
-                &ToggledPaths::default(),
-                &ToggledRegistry::default(),
-                None,
-                &[],
-                false,
-                &Default::default(),
-                ONLY_CONSTRUCTIVE,
-            ),
-        );
+    fn can_compute_game_file_target_with_final_restore_redirect() {
+        let redirects = [RedirectConfig {
+            kind: RedirectKind::Restore,
+            source: StrictPath::new("/old"),
+            target: StrictPath::new("/mid"),
+        }];
+
+        for reverse in [false, true] {
+            let redirects = with_final_restore_redirect(
+                &redirects,
+                StrictPath::new("/mid/game"),
+                StrictPath::new("/new/location"),
+                reverse,
+            );
+            assert_eq!(
+                Some(StrictPath::new("/new/location/save.dat")),
+                game_file_target(
+                    &StrictPath::new("/old/game/save.dat"),
+                    &redirects,
+                    reverse,
+                    ScanKind::Restore,
+                ),
+            );
+        }
+    }
 
-        assert_eq!(
-            ScanInfo {
-                game_name: s("game 2"),
-                found_files: hash_map! {
-                    format!("{}/tests/root2/game2/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+    #[test]
+    fn can_compute_game_file_target_with_wine_prefix() {
+        let prefix = StrictPath::new(format!("{}/tests/proton-prefix", repo()));
+        let drive_c = prefix.joined("drive_c").render();
+
+        for reverse in [false, true] {
+            let redirects = with_wine_prefix_redirects(
+                &[RedirectConfig {
+                    kind: RedirectKind::Restore,
+                    source: StrictPath::new("D:/Old"),
+                    target: StrictPath::new("C:/Games"),
+                }],
+                &prefix,
+                reverse,
+            );
+
+            assert_eq!(
+                Some(StrictPath::new(format!("{drive_c}/users/steamuser/AppData/save.dat"))),
+                game_file_target(
+                    &StrictPath::new("C:/Users/Alice/AppData/save.dat"),
+                    &redirects,
+                    reverse,
+                    ScanKind::Restore,
+                ),
+            );
+            assert_eq!(
+                Some(StrictPath::new(format!("{drive_c}/users/Public/Documents/save.dat"))),
+                game_file_target(
+                    &StrictPath::new("C:/Users/Public/Documents/save.dat"),
+                    &redirects,
+                    reverse,
+                    ScanKind::Restore,
+                ),
+            );
+            // The configured redirects still apply first.
+            assert_eq!(
+                Some(StrictPath::new(format!("{drive_c}/Games/save.dat"))),
+                game_file_target(
+                    &StrictPath::new("D:/Old/save.dat"),
+                    &redirects,
+                    reverse,
+                    ScanKind::Restore
+                ),
+            );
+        }
+    }
+
+    #[test]
+    fn can_compute_game_file_target_with_regex() {
+        let redirects = [RedirectConfig {
+            kind: RedirectKind::Regex,
+            source: StrictPath::new("^C:/Users/([^/]+)/Saved Games/(.+)$"),
+            target: StrictPath::new("D:/Saves/$1/$2"),
+        }];
+
+        // Match - backup, with a Windows drive letter and backslashes
+        assert_eq!(
+            Some(StrictPath::new("D:/Saves/Alice/Game/save.dat")),
+            game_file_target(
+                &StrictPath::new("C:\\Users\\Alice\\Saved Games\\Game\\save.dat"),
+                &redirects,
+                false,
+                ScanKind::Backup,
+            ),
+        );
+
+        // Match - restore
+        assert_eq!(
+            Some(StrictPath::new("D:/Saves/Bob/Game/save.dat")),
+            game_file_target(
+                &StrictPath::new("C:/Users/Bob/Saved Games/Game/save.dat"),
+                &redirects,
+                false,
+                ScanKind::Restore,
+            ),
+        );
+
+        // Mismatch - different drive
+        assert_eq!(
+            None,
+            game_file_target(
+                &StrictPath::new("E:/Users/Alice/Saved Games/Game/save.dat"),
+                &redirects,
+                false,
+                ScanKind::Backup,
+            ),
+        );
+
+        // Middle of the path, chained with a plain redirect
+        assert_eq!(
+            Some(StrictPath::new("D:/Users/main/AppData/save.dat")),
+            game_file_target(
+                &StrictPath::new("C:/Users/Alice/AppData/save.dat"),
+                &[
+                    RedirectConfig {
+                        kind: RedirectKind::Regex,
+                        source: StrictPath::new("^([A-Z]):/Users/[^/]+/"),
+                        target: StrictPath::new("${1}:/Users/main/"),
+                    },
+                    RedirectConfig {
+                        kind: RedirectKind::Backup,
+                        source: StrictPath::new("C:/Users"),
+                        target: StrictPath::new("D:/Users"),
+                    },
+                ],
+                false,
+                ScanKind::Backup,
+            ),
+        );
+
+        // Reversed order on restore
+        assert_eq!(
+            Some(StrictPath::new("C:/Users/main/AppData/save.dat")),
+            game_file_target(
+                &StrictPath::new("D:/Users/Alice/AppData/save.dat"),
+                &[
+                    RedirectConfig {
+                        kind: RedirectKind::Regex,
+                        source: StrictPath::new("^C:/Users/[^/]+/"),
+                        target: StrictPath::new("C:/Users/main/"),
+                    },
+                    RedirectConfig {
+                        kind: RedirectKind::Restore,
+                        source: StrictPath::new("D:/Users"),
+                        target: StrictPath::new("C:/Users"),
+                    },
+                ],
+                true,
+                ScanKind::Restore,
+            ),
+        );
+
+        // Invalid pattern
+        assert_eq!(
+            None,
+            game_file_target(
+                &StrictPath::new("C:/foo"),
+                &[RedirectConfig {
+                    kind: RedirectKind::Regex,
+                    source: StrictPath::new("C:/(foo"),
+                    target: StrictPath::new("C:/bar"),
+                }],
+                false,
+                ScanKind::Backup,
+            ),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_pinned_store() {
+        // Heroic roots need launcher info to resolve `<base>`,
+        // so pinning the store means that the plain folders in the roots no longer match.
+        let game = Game {
+            store: Some(Store::Heroic),
+            ..manifest().0["game1"].clone()
+        };
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {},
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &game,
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+                &BackupFilter::default(),
+                None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                false,
+                &Default::default(),
+                &Default::default(),
+            ),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_store_specific_path_when_store_is_pinned() {
+        // `<storeGameId>` only expands for certain stores,
+        // so the `other` roots can only find this file when the store is pinned to GOG.
+        let game = Game {
+            files: btree_map! { s("<root>/game<storeGameId>/file1.txt"): GameFileEntry::default() },
+            gog: crate::resource::manifest::GogMetadata { id: Some(1) },
+            ..Default::default()
+        };
+        let scan = |game: &Game| {
+            scan_game_for_backup(
+                game,
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::default(),
+                &BackupFilter::default(),
+                None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                false,
+                &Default::default(),
+                &Default::default(),
+            )
+        };
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {},
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            scan(&game),
+        );
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    format!("{}/tests/root2/game1/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            scan(&Game {
+                store: Some(Store::Gog),
+                ..game.clone()
+            }),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_subpath_exclusions() {
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    format!("{}/tests/root2/game1/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+                &BackupFilter::default(),
+                None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                false,
+                &Default::default(),
+                &BackupScanOptions {
+                    // Only applies inside of `<base>/subdir`, not to `<base>/file1.txt`.
+                    subpath_exclusions: &[s("*.txt")],
+                    ..Default::default()
+                },
+            ),
+        );
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_quick_scan() {
+        let file1: StrictPath = format!("{}/tests/root2/game1/file1.txt", repo()).into();
+        let file2: StrictPath = format!("{}/tests/root1/game1/subdir/file2.txt", repo()).into();
+        let previous = |when, file2_size| LatestBackup {
+            scan: ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    file1.clone(): ScannedFile::new(1, "old"),
+                    file2.clone(): ScannedFile::new(file2_size, "old"),
+                },
+                ..Default::default()
+            },
+            when,
+            registry_content: None,
+        };
+        let scan = |previous, quick_scan| {
+            scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+                &BackupFilter::default(),
+                None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                Some(previous),
+                &[],
+                false,
+                &Default::default(),
+                &BackupScanOptions {
+                    quick_scan,
+                    ..Default::default()
+                },
+            )
+        };
+        let later = chrono::Utc::now() + chrono::Duration::days(1);
+        let earlier = chrono::DateTime::UNIX_EPOCH;
+
+        // Nothing was modified since the last backup, so the whole game reuses the old hashes.
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    file1.clone(): ScannedFile::new(1, "old").change_as(ScanChange::Same),
+                    file2.clone(): ScannedFile::new(2, "old").change_as(ScanChange::Same),
+                },
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            scan(previous(later, 2), true),
+        );
+
+        let full = ScanInfo {
+            game_name: s("game1"),
+            found_files: hash_map! {
+                file1.clone(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_as(ScanChange::Different),
+                file2.clone(): ScannedFile::new(2, "9d891e731f75deae56884d79e9816736b7488080").change_as(ScanChange::Different),
+            },
+            found_registry_keys: hash_map! {},
+            ..Default::default()
+        };
+        // One file's size changed, so every file gets hashed.
+        assert_eq!(full, scan(previous(later, 3), true));
+        // Modified since the last backup.
+        assert_eq!(full, scan(previous(earlier, 2), true));
+        // Quick scan not active.
+        assert_eq!(full, scan(previous(later, 2), false));
+    }
+
+    #[test]
+    fn quick_scan_notices_new_save_folders() {
+        let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-quick-scan-folders"));
+        let _ = path.remove();
+        let saves = path.joined("saves");
+        let slot1 = saves.joined("slot1");
+        let file = slot1.joined("a.sav");
+        file.create_parent_dir().unwrap();
+        file.write_with_content("a").unwrap();
+
+        let past = std::time::SystemTime::now() - std::time::Duration::from_secs(60 * 60);
+        for x in [&file, &slot1, &saves] {
+            x.set_mtime(past).unwrap();
+        }
+
+        let previous = LatestBackup {
+            scan: ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    file.clone(): ScannedFile::new(1, "old"),
+                },
+                ..Default::default()
+            },
+            when: chrono::Utc::now() - chrono::Duration::minutes(1),
+            registry_content: None,
+        };
+        let save_paths: HashSet<_> = [(saves.joined("*"), None)].into_iter().collect();
+        let unchanged = || {
+            find_unchanged_files(
+                "game1",
+                &previous,
+                &save_paths,
+                &BackupFilter::default(),
+                &ToggledPaths::default(),
+                false,
+            )
+        };
+
+        assert!(unchanged().is_some());
+
+        // This only updates the modification time of the `saves` folder, not `slot1`.
+        saves.joined("slot2/b.sav").create_parent_dir().unwrap();
+        assert!(unchanged().is_none());
+
+        let _ = path.remove();
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_file_matches() {
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    format!("{}/tests/root1/game1/subdir/file2.txt", repo()).into(): ScannedFile::new(2, "9d891e731f75deae56884d79e9816736b7488080").change_new(),
+                    format!("{}/tests/root2/game1/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+                &BackupFilter::default(),
+                None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                false,
+                &Default::default(),
+                &Default::default(),
+            ),
+        );
+
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game 2"),
+                found_files: hash_map! {
+                    format!("{}/tests/root2/game2/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
                 },
                 found_registry_keys: hash_map! {},
                 ..Default::default()
@@ -1178,7 +2168,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1209,11 +2199,31 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
 
+    #[test]
+    fn can_find_overlapping_roots() {
+        let roots = &[
+            Root::new(format!("{}/tests/root3", repo()), Store::Other),
+            Root::new(format!("{}/tests/root3/game5", repo()), Store::Other),
+            Root::new(format!("{}/tests/root3/game_2", repo()), Store::Other),
+        ];
+        let found_files = hash_map! {
+            format!("{}/tests/root3/game5/data/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727"),
+        };
+
+        assert_eq!(
+            vec![OperationWarning::OverlappingRoots {
+                parent: format!("{}/tests/root3", repo()),
+                child: format!("{}/tests/root3/game5", repo()),
+            }],
+            find_overlapping_roots(roots, &found_files),
+        );
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_redirect_to_symlink() {
         let roots = &[Root::new(format!("{}/tests/root3", repo()), Store::Other)];
@@ -1228,7 +2238,11 @@ mod tests {
                         ignored: false,
                         change: ScanChange::New,
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: Some(StrictPath::new(format!("{}/tests/root3/game5/data-symlink/file1.txt", repo()))),
+                        conflict: false,
                     },
                 },
                 found_registry_keys: hash_map! {},
@@ -1252,11 +2266,127 @@ mod tests {
                 }],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
 
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn can_scan_game_for_backup_with_symlinks() {
+        let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-scan-symlinks"));
+        let _ = path.remove();
+        let saves = path.joined("saves");
+        let link = saves.joined("slot.sav");
+        let shared = path.joined("shared/slot.sav");
+        saves.create_dirs().unwrap();
+        shared.create_parent_dir().unwrap();
+        shared.write_with_content("x").unwrap();
+        std::os::unix::fs::symlink(shared.interpret().unwrap(), link.as_std_path_buf().unwrap()).unwrap();
+        // This folder links back to its parent, so it must not be traversed forever.
+        std::os::unix::fs::symlink(
+            saves.interpret().unwrap(),
+            saves.joined("loop").as_std_path_buf().unwrap(),
+        )
+        .unwrap();
+
+        let game = Game {
+            files: btree_map! { saves.render(): GameFileEntry::default() },
+            ..Default::default()
+        };
+        let scan = |follow_symlinks| {
+            scan_game_for_backup(
+                &game,
+                "game",
+                &[],
+                &StrictPath::new(repo()),
+                &Launchers::default(),
+                &BackupFilter::default(),
+                None,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                false,
+                &Default::default(),
+                &BackupScanOptions {
+                    follow_symlinks,
+                    ..Default::default()
+                },
+            )
+            .found_files
+            .into_iter()
+            .map(|(scan_key, file)| (scan_key.render(), file.symlink))
+            .collect::<HashMap<_, _>>()
+        };
+
+        assert_eq!(
+            hash_map! {
+                link.interpreted_link().unwrap().render(): Some(SymlinkTarget(shared.interpret().unwrap())),
+            },
+            scan(false),
+        );
+        assert_eq!(
+            hash_map! {
+                shared.interpreted().unwrap().render(): None,
+            },
+            scan(true),
+        );
+
+        let _ = path.remove();
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn can_scan_game_for_backup_with_hardlinks() {
+        let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-scan-hardlinks"));
+        let _ = path.remove();
+        let saves = path.joined("saves");
+        let primary = saves.joined("a.sav");
+        let link = saves.joined("b.sav");
+        let separate = saves.joined("c.sav");
+        saves.create_dirs().unwrap();
+        primary.write_with_content("x").unwrap();
+        link.create_hardlink(&primary).unwrap();
+        separate.write_with_content("x").unwrap();
+
+        let game = Game {
+            files: btree_map! { saves.render(): GameFileEntry::default() },
+            ..Default::default()
+        };
+        let found = scan_game_for_backup(
+            &game,
+            "game",
+            &[],
+            &StrictPath::new(repo()),
+            &Launchers::default(),
+            &BackupFilter::default(),
+            None,
+            &ToggledPaths::default(),
+            &ToggledRegistry::default(),
+            None,
+            &[],
+            false,
+            &Default::default(),
+            &Default::default(),
+        )
+        .found_files
+        .into_iter()
+        .map(|(scan_key, file)| (scan_key.render(), file.hardlink))
+        .collect::<HashMap<_, _>>();
+
+        assert_eq!(
+            hash_map! {
+                primary.interpreted().unwrap().render(): None,
+                link.interpreted().unwrap().render(): Some(HardlinkTarget(primary.interpreted().unwrap().render())),
+                separate.interpreted().unwrap().render(): None,
+            },
+            found,
+        );
+
+        let _ = path.remove();
+    }
+
     #[test]
     fn can_scan_game_for_backup_with_fuzzy_matched_install_dir() {
         let roots = &[Root::new(format!("{}/tests/root3", repo()), Store::Other)];
@@ -1283,11 +2413,74 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
 
+    #[test]
+    fn can_plan_game_for_backup_with_files() {
+        let roots = &[Root::new(format!("{}/tests/root2", repo()), Store::Other)];
+        let plan = plan_game_for_backup(
+            &manifest().0["game 2"],
+            "game 2",
+            roots,
+            &StrictPath::new(repo()),
+            &Launchers::scan_dirs(roots, &manifest(), &["game 2".to_string()]),
+            &BackupFilter::default(),
+            None,
+            &Default::default(),
+        );
+
+        assert_eq!(s("game 2"), plan.game_name);
+        assert_eq!(roots.to_vec(), plan.roots);
+        assert!(plan.sorted_paths().contains(&format!("{}/tests/root2/game2", repo())));
+        assert!(plan.registry.is_empty());
+    }
+
+    #[test]
+    fn can_plan_game_for_backup_with_registry() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game:
+              registry:
+                HKEY_LOCAL_MACHINE/SOFTWARE/Ludusavi: {}
+                HKEY_CURRENT_USER/Software/Ludusavi: {}
+            "#,
+        )
+        .unwrap();
+        let filter = BackupFilter {
+            ignored_registry: vec![registry::RegistryItem::new(s(
+                "HKEY_CURRENT_USER/Software/Ludusavi/ignored",
+            ))],
+            ..Default::default()
+        };
+
+        let plan = plan_game_for_backup(
+            &manifest.0["game"],
+            "game",
+            &[],
+            &StrictPath::new(repo()),
+            &Launchers::default(),
+            &filter,
+            None,
+            &Default::default(),
+        );
+
+        assert_eq!(
+            vec![
+                s("HKEY_CURRENT_USER/Software/Ludusavi"),
+                s("HKEY_LOCAL_MACHINE/SOFTWARE/Ludusavi"),
+                s("HKEY_LOCAL_MACHINE/SOFTWARE/Wow6432Node/Ludusavi"),
+                s("HKEY_CURRENT_USER/Software/Classes/VirtualStore/MACHINE/SOFTWARE/Ludusavi"),
+                s("HKEY_CURRENT_USER/Software/Classes/VirtualStore/MACHINE/SOFTWARE/Wow6432Node/Ludusavi"),
+            ],
+            plan.registry,
+        );
+        assert_eq!(filter.ignored_registry, plan.ignored_registry);
+        assert!(plan.roots.is_empty());
+    }
+
     #[test]
     #[cfg(target_os = "windows")]
     fn can_scan_game_for_backup_with_file_matches_in_custom_home_folder() {
@@ -1318,7 +2511,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1352,7 +2545,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1382,7 +2575,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1412,7 +2605,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1450,7 +2643,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1490,7 +2683,76 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
+            ),
+        );
+    }
+
+    #[test]
+    fn can_detect_running_game() {
+        use crate::resource::manifest::GameInstallDirEntry;
+        use std::path::PathBuf;
+
+        let game = Game {
+            install_dir: btree_map! {
+                s("FakeGame"): GameInstallDirEntry {},
+            },
+            ..Default::default()
+        };
+        let processes = RunningProcesses::new(vec![
+            PathBuf::from("/games/fakegame/bin/game.exe"),
+            PathBuf::from(format!("{}/tests/launchers/other.exe", repo())),
+        ]);
+
+        assert!(is_game_running(&game, &[], &processes));
+        assert!(is_game_running(
+            &Game::default(),
+            &[StrictPath::new(format!("{}/tests/launchers", repo()))],
+            &processes
+        ));
+        assert!(!is_game_running(
+            &Game::default(),
+            &[StrictPath::new(format!("{}/tests/missing", repo()))],
+            &processes
+        ));
+        assert!(!is_game_running(&game, &[], &RunningProcesses::default()));
+    }
+
+    #[test]
+    fn can_scan_game_for_backup_with_file_matches_and_size_limit() {
+        let mut filter = BackupFilter {
+            max_file_size: Some(1),
+            ..Default::default()
+        };
+        let ignored = ToggledPaths::default();
+        let found = hash_map! {
+            format!("{}/tests/root1/game1/subdir/file2.txt", repo()).into(): ScannedFile::new(2, "9d891e731f75deae56884d79e9816736b7488080").change_new().excluded(FileExclusion::TooLarge { limit: 1 }),
+            format!("{}/tests/root2/game1/file1.txt", repo()).into(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+        };
+
+        filter.build_globs();
+        assert_eq!(
+            ScanInfo {
+                game_name: s("game1"),
+                found_files: found,
+                found_registry_keys: hash_map! {},
+                ..Default::default()
+            },
+            scan_game_for_backup(
+                &manifest().0["game1"],
+                "game1",
+                &config().roots,
+                &StrictPath::new(repo()),
+                &Launchers::scan_dirs(&config().roots, &manifest(), &["game1".to_string()]),
+                &filter,
+                None,
+                &ignored,
+                &ToggledRegistry::default(),
+                None,
+                &[],
+                false,
+                &Default::default(),
+                &Default::default(),
             ),
         );
     }
@@ -1530,7 +2792,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1579,7 +2841,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1637,7 +2899,7 @@ mod tests {
                 &[],
                 false,
                 &Default::default(),
-                ONLY_CONSTRUCTIVE,
+                &Default::default(),
             ),
         );
     }
@@ -1768,7 +3030,7 @@ mod tests {
                     &[],
                     false,
                     &Default::default(),
-                    ONLY_CONSTRUCTIVE,
+                    &Default::default(),
                 ),
             );
         }
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use crate::{
     lang::TRANSLATOR,
     path::StrictPath,
-    scan::{registry::RegistryItem, ScanChangeCount, ScanInfo},
+    scan::{registry::RegistryItem, OperationWarning, ScanChangeCount, ScanInfo},
 };
 
 #[derive(Clone, Debug)]
@@ -29,6 +29,10 @@ impl BackupError {
 pub struct BackupInfo {
     pub failed_files: HashMap<StrictPath, BackupError>,
     pub failed_registry: HashMap<RegistryItem, BackupError>,
+    /// Non-fatal problems encountered during the backup.
+    pub warnings: Vec<OperationWarning>,
+    /// Outcome of copying the backup to each mirror folder.
+    pub mirrors: HashMap<StrictPath, Result<(), BackupError>>,
 }
 
 impl BackupInfo {
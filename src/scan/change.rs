@@ -1,6 +1,7 @@
 use crate::{
     lang::{ADD_SYMBOL, CHANGE_SYMBOL, REMOVAL_SYMBOL},
     prelude::StrictPath,
+    resource::config::HashAlgorithm,
     scan::ScanKind,
 };
 
@@ -100,6 +101,18 @@ impl ScanChangeCount {
         }
     }
 
+    pub fn merge(&mut self, other: &Self) {
+        self.new += other.new;
+        self.different += other.different;
+        self.removed += other.removed;
+        self.same += other.same;
+    }
+
+    /// How many entries are new, updated, or removed.
+    pub fn changed(&self) -> usize {
+        self.new + self.different + self.removed
+    }
+
     pub fn brand_new(&self) -> bool {
         self.only(ScanChange::New)
     }
@@ -139,6 +152,30 @@ impl ScanChangeCount {
     }
 }
 
+/// Counts of the files and registry entries in one or more scans, by `ScanChange`.
+/// Ignored entries are only counted in the `ignored_*` fields, regardless of their change.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSummary {
+    pub files: ScanChangeCount,
+    pub registry_keys: ScanChangeCount,
+    pub registry_values: ScanChangeCount,
+    pub ignored_files: usize,
+    pub ignored_registry_keys: usize,
+    pub ignored_registry_values: usize,
+}
+
+impl ChangeSummary {
+    pub fn merge(&mut self, other: &Self) {
+        self.files.merge(&other.files);
+        self.registry_keys.merge(&other.registry_keys);
+        self.registry_values.merge(&other.registry_values);
+        self.ignored_files += other.ignored_files;
+        self.ignored_registry_keys += other.ignored_registry_keys;
+        self.ignored_registry_values += other.ignored_registry_values;
+    }
+}
+
 impl ScanChange {
     pub fn evaluate_backup(current_hash: &str, previous_hash: Option<&&String>) -> Self {
         match previous_hash {
@@ -153,8 +190,8 @@ impl ScanChange {
         }
     }
 
-    pub fn evaluate_restore(original_path: &StrictPath, previous_hash: &str) -> Self {
-        match original_path.try_sha1() {
+    pub fn evaluate_restore(original_path: &StrictPath, previous_hash: &str, algorithm: HashAlgorithm) -> Self {
+        match original_path.try_hash(algorithm) {
             Err(_) => Self::New,
             Ok(current_hash) => {
                 if current_hash == previous_hash {
@@ -0,0 +1,291 @@
+//! Binary deltas between two versions of a file.
+//!
+//! The base file is split into fixed-size blocks, and the new version is scanned
+//! with a rolling checksum to find regions that can be copied from the base.
+//! Anything else is stored literally.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+};
+
+/// Identifies a delta file and the version of its encoding.
+const MAGIC: &[u8; 8] = b"LSVDLT01";
+const BLOCK_SIZE: usize = 4096;
+/// Literal runs are split at this size to keep memory use bounded.
+const MAX_LITERAL: usize = 1024 * 1024;
+
+const OP_COPY: u8 = 1;
+const OP_LITERAL: u8 = 2;
+
+/// Adler-style checksum that can be updated one byte at a time.
+#[derive(Default)]
+struct RollingChecksum {
+    a: u32,
+    b: u32,
+    len: u32,
+}
+
+impl RollingChecksum {
+    fn new(data: &[u8]) -> Self {
+        let mut checksum = Self::default();
+        for byte in data {
+            checksum.push(*byte);
+        }
+        checksum
+    }
+
+    fn push(&mut self, byte: u8) {
+        self.a = self.a.wrapping_add(byte as u32);
+        self.b = self.b.wrapping_add(self.a);
+        self.len += 1;
+    }
+
+    fn pop(&mut self, byte: u8) {
+        self.a = self.a.wrapping_sub(byte as u32);
+        self.b = self.b.wrapping_sub(self.len.wrapping_mul(byte as u32));
+        self.len -= 1;
+    }
+
+    fn value(&self) -> u32 {
+        (self.b << 16) | (self.a & 0xffff)
+    }
+}
+
+struct Block {
+    offset: u64,
+    len: usize,
+    strong: [u8; 32],
+}
+
+fn read_full(source: &mut impl Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match source.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+fn index_blocks(mut base: impl Read) -> io::Result<HashMap<u32, Vec<Block>>> {
+    let mut blocks: HashMap<u32, Vec<Block>> = HashMap::new();
+    let mut buffer = vec![0; BLOCK_SIZE];
+    let mut offset = 0;
+
+    loop {
+        let len = read_full(&mut base, &mut buffer)?;
+        if len == 0 {
+            break;
+        }
+
+        let data = &buffer[..len];
+        blocks
+            .entry(RollingChecksum::new(data).value())
+            .or_default()
+            .push(Block {
+                offset,
+                len,
+                strong: *blake3::hash(data).as_bytes(),
+            });
+        offset += len as u64;
+
+        if len < BLOCK_SIZE {
+            break;
+        }
+    }
+
+    Ok(blocks)
+}
+
+fn write_copy(output: &mut impl Write, offset: u64, len: u64) -> io::Result<()> {
+    output.write_all(&[OP_COPY])?;
+    output.write_all(&offset.to_le_bytes())?;
+    output.write_all(&len.to_le_bytes())
+}
+
+fn write_literal(output: &mut impl Write, literal: &mut Vec<u8>) -> io::Result<()> {
+    if literal.is_empty() {
+        return Ok(());
+    }
+    output.write_all(&[OP_LITERAL])?;
+    output.write_all(&(literal.len() as u64).to_le_bytes())?;
+    output.write_all(literal)?;
+    literal.clear();
+    Ok(())
+}
+
+fn read_u64(source: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    source.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Write a delta that can turn `base` into `target`.
+pub fn encode(base: impl Read, target: impl Read, mut output: impl Write) -> io::Result<()> {
+    let blocks = index_blocks(base)?;
+    output.write_all(MAGIC)?;
+
+    let mut target = BufReader::new(target).bytes();
+    let mut window = VecDeque::with_capacity(BLOCK_SIZE);
+    let mut checksum = RollingChecksum::default();
+    let mut literal = vec![];
+    let mut pending_copy: Option<(u64, u64)> = None;
+
+    loop {
+        while window.len() < BLOCK_SIZE {
+            match target.next().transpose()? {
+                Some(byte) => {
+                    window.push_back(byte);
+                    checksum.push(byte);
+                }
+                None => break,
+            }
+        }
+        if window.is_empty() {
+            break;
+        }
+
+        let matched = blocks.get(&checksum.value()).and_then(|candidates| {
+            let data = window.make_contiguous();
+            let strong = blake3::hash(data);
+            candidates
+                .iter()
+                .find(|block| block.len == data.len() && &block.strong == strong.as_bytes())
+        });
+
+        if let Some(block) = matched {
+            write_literal(&mut output, &mut literal)?;
+            match pending_copy.as_mut() {
+                Some((offset, len)) if *offset + *len == block.offset => {
+                    *len += block.len as u64;
+                }
+                _ => {
+                    if let Some((offset, len)) = pending_copy.take() {
+                        write_copy(&mut output, offset, len)?;
+                    }
+                    pending_copy = Some((block.offset, block.len as u64));
+                }
+            }
+            window.clear();
+            checksum = RollingChecksum::default();
+            continue;
+        }
+
+        if let Some((offset, len)) = pending_copy.take() {
+            write_copy(&mut output, offset, len)?;
+        }
+        if let Some(byte) = window.pop_front() {
+            checksum.pop(byte);
+            literal.push(byte);
+        }
+        if literal.len() >= MAX_LITERAL {
+            write_literal(&mut output, &mut literal)?;
+        }
+    }
+
+    if let Some((offset, len)) = pending_copy.take() {
+        write_copy(&mut output, offset, len)?;
+    }
+    write_literal(&mut output, &mut literal)?;
+    output.flush()
+}
+
+/// Rebuild the target by applying a delta from `encode` to the same `base`.
+pub fn decode(mut base: impl Read + Seek, delta: impl Read, mut output: impl Write) -> io::Result<()> {
+    let mut delta = BufReader::new(delta);
+
+    let mut magic = [0; 8];
+    delta.read_exact(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(invalid_data("unrecognized delta format"));
+    }
+
+    let mut op = [0; 1];
+    while read_full(&mut delta, &mut op)? > 0 {
+        match op[0] {
+            OP_COPY => {
+                let offset = read_u64(&mut delta)?;
+                let len = read_u64(&mut delta)?;
+                base.seek(SeekFrom::Start(offset))?;
+                if io::copy(&mut (&mut base).take(len), &mut output)? != len {
+                    return Err(invalid_data("delta refers past the end of the base file"));
+                }
+            }
+            OP_LITERAL => {
+                let len = read_u64(&mut delta)?;
+                if io::copy(&mut (&mut delta).take(len), &mut output)? != len {
+                    return Err(invalid_data("delta is truncated"));
+                }
+            }
+            _ => return Err(invalid_data("unrecognized delta operation")),
+        }
+    }
+
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+
+    fn round_trip(base: &[u8], target: &[u8]) -> usize {
+        let mut delta = vec![];
+        encode(base, target, &mut delta).unwrap();
+
+        let mut rebuilt = vec![];
+        decode(Cursor::new(base), delta.as_slice(), &mut rebuilt).unwrap();
+        assert_eq!(target, rebuilt.as_slice());
+
+        delta.len()
+    }
+
+    fn sample(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn can_round_trip_empty_files() {
+        round_trip(b"", b"");
+        round_trip(b"", b"new");
+        round_trip(b"old", b"");
+    }
+
+    #[test]
+    fn can_round_trip_identical_files() {
+        let base = sample(BLOCK_SIZE * 10 + 123);
+        let size = round_trip(&base, &base);
+        assert!(size < 100);
+    }
+
+    #[test]
+    fn can_round_trip_small_change() {
+        let base = sample(BLOCK_SIZE * 10 + 123);
+        let mut target = base.clone();
+        target[BLOCK_SIZE * 5 + 7] ^= 0xff;
+        target.splice(BLOCK_SIZE * 2..BLOCK_SIZE * 2, b"inserted".iter().copied());
+
+        let size = round_trip(&base, &target);
+        assert!(size < BLOCK_SIZE * 3);
+    }
+
+    #[test]
+    fn can_round_trip_unrelated_files() {
+        round_trip(&sample(BLOCK_SIZE + 1), b"completely different");
+    }
+
+    #[test]
+    fn rejects_unrecognized_delta() {
+        let mut rebuilt = vec![];
+        assert!(decode(Cursor::new(b"base"), b"not a delta".as_slice(), &mut rebuilt).is_err());
+    }
+}
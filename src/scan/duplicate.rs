@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use crate::{
     prelude::StrictPath,
@@ -372,6 +372,24 @@ impl DuplicateDetector {
         duplicates.remove(game);
         duplicates
     }
+
+    /// Each file path that more than one game claims, along with those games.
+    pub fn duplicated_files(&self) -> BTreeMap<String, BTreeSet<String>> {
+        self.files
+            .iter()
+            .filter(|(_, games)| games.len() > 1)
+            .map(|(path, games)| (path.render(), games.keys().cloned().collect()))
+            .collect()
+    }
+
+    /// Each registry key that more than one game claims, along with those games.
+    pub fn duplicated_registry(&self) -> BTreeMap<String, BTreeSet<String>> {
+        self.registry
+            .iter()
+            .filter(|(_, games)| games.len() > 1)
+            .map(|(path, games)| (path.render(), games.keys().cloned().collect()))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -463,7 +481,11 @@ mod tests {
             ignored: false,
             change: Default::default(),
             container: None,
+            excluded: None,
+            symlink: None,
+            hardlink: None,
             redirected: None,
+            conflict: false,
         };
         let scan_key_1b = StrictPath::from("file1b.txt");
         let file1b = ScannedFile {
@@ -473,7 +495,11 @@ mod tests {
             ignored: false,
             change: Default::default(),
             container: None,
+            excluded: None,
+            symlink: None,
+            hardlink: None,
             redirected: None,
+            conflict: false,
         };
 
         detector.add_game(
@@ -515,7 +541,11 @@ mod tests {
                     ignored: false,
                     change: Default::default(),
                     container: None,
+                    excluded: None,
+                    symlink: None,
+                    hardlink: None,
                     redirected: None,
+                    conflict: false,
                 }
             )
         );
@@ -542,7 +572,11 @@ mod tests {
                     ignored: false,
                     change: Default::default(),
                     container: None,
+                    excluded: None,
+                    symlink: None,
+                    hardlink: None,
                     redirected: None,
+                    conflict: false,
                 }
             )
         );
@@ -0,0 +1,279 @@
+use crate::{
+    path::StrictPath,
+    prelude::filter_map_walkdir,
+    resource::config::HashAlgorithm,
+    scan::{ScanChange, ScanInfo, ScanPlan, ScannedFile},
+};
+
+/// Layout of save data kept by another backup tool,
+/// which can be imported to seed a game's first backup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExternalFormat {
+    /// One subfolder per game, each containing that game's save files.
+    #[default]
+    Folders,
+}
+
+impl ExternalFormat {
+    pub const ALL_NAMES: &'static [&'static str] = &["folders"];
+
+    pub fn importer(&self) -> &'static dyn ExternalImporter {
+        match self {
+            Self::Folders => &FoldersImporter,
+        }
+    }
+}
+
+impl std::str::FromStr for ExternalFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "folders" => Ok(Self::Folders),
+            _ => Err(format!("invalid import format: {}", s)),
+        }
+    }
+}
+
+/// A game's saves as found in another tool's backup.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExternalGame {
+    /// How the other tool identified the game.
+    /// This is matched against the manifest's titles.
+    pub label: String,
+    /// Where the other tool kept this game's saves.
+    pub source: StrictPath,
+    /// Each save file, paired with its location relative to the game's save folder.
+    pub files: Vec<(StrictPath, String)>,
+}
+
+/// Each supported format only needs to find the games and their files;
+/// matching them to titles and creating the backups is shared.
+pub trait ExternalImporter {
+    fn discover(&self, source: &StrictPath) -> Vec<ExternalGame>;
+}
+
+pub struct FoldersImporter;
+
+impl ExternalImporter for FoldersImporter {
+    fn discover(&self, source: &StrictPath) -> Vec<ExternalGame> {
+        let Ok(base) = source.interpret() else {
+            log::error!("Invalid import source: {:?}", source);
+            return vec![];
+        };
+
+        let mut games = vec![];
+        for folder in walkdir::WalkDir::new(&base)
+            .min_depth(1)
+            .max_depth(1)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|x| filter_map_walkdir("ludusavi::FoldersImporter", x))
+            .filter(|x| x.file_type().is_dir() && !x.file_name().to_string_lossy().starts_with('.'))
+        {
+            let label = folder.file_name().to_string_lossy().to_string();
+
+            let mut files = vec![];
+            for entry in walkdir::WalkDir::new(folder.path())
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|x| filter_map_walkdir(&label, x))
+                .filter(|x| x.file_type().is_file())
+            {
+                let Ok(relative) = entry.path().strip_prefix(folder.path()) else {
+                    continue;
+                };
+                let relative = relative
+                    .components()
+                    .map(|x| x.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((StrictPath::from(&entry), relative));
+            }
+            files.sort_by(|x, y| x.1.cmp(&y.1));
+
+            games.push(ExternalGame {
+                label,
+                source: StrictPath::from(&folder),
+                files,
+            });
+        }
+
+        games.sort_by(|x, y| x.label.cmp(&y.label));
+        games
+    }
+}
+
+/// Choose where a game's imported saves should be restored,
+/// based on the paths that a backup scan would check.
+/// Each path is cut off before its first wildcard, since a glob can't be restored into.
+/// Paths that point to a specific file are skipped, since the imported files would end up beside it
+/// rather than where the game expects them.
+/// Paths tagged as saves are preferred, then existing folders, and then the deepest one,
+/// since it's the most specific.
+pub fn restore_target(plan: &ScanPlan) -> Option<StrictPath> {
+    plan.paths
+        .iter()
+        .filter_map(|(path, _)| {
+            let rendered = path.render();
+            let prefix = glob_free_prefix(&rendered)?;
+            let globbed = prefix.len() < rendered.trim_end_matches('/').len();
+            let candidate = StrictPath::new(prefix);
+            let folder = globbed || candidate.is_dir() || (!candidate.exists() && !looks_like_file(&candidate));
+            folder.then(|| (plan.save_paths.contains(path), candidate))
+        })
+        .max_by_key(|(save, path)| {
+            (
+                *save,
+                path.is_dir(),
+                path.raw().matches('/').count(),
+                path.raw().to_string(),
+            )
+        })
+        .map(|(_, path)| path)
+}
+
+/// For paths that don't exist yet, guess based on whether there's a file extension.
+fn looks_like_file(path: &StrictPath) -> bool {
+    std::path::Path::new(path.raw()).extension().is_some()
+}
+
+/// The part of a path before any wildcards, as long as it's more specific than a drive or top-level folder.
+pub fn glob_free_prefix(path: &str) -> Option<String> {
+    let prefix = path
+        .trim_end_matches('/')
+        .split('/')
+        .take_while(|part| !part.contains(['*', '?', '[', ']']))
+        .collect::<Vec<_>>()
+        .join("/");
+
+    (prefix.matches('/').count() > 1).then_some(prefix)
+}
+
+/// Treat the imported files like a backup scan that found them,
+/// with each one set to be restored at its relative location under the target folder.
+/// If any file can't be read, then that file is returned instead,
+/// since a partial import would look like a complete backup.
+pub fn scan_external_game(
+    game: &ExternalGame,
+    name: &str,
+    target: &StrictPath,
+    hash_algorithm: HashAlgorithm,
+) -> Result<ScanInfo, StrictPath> {
+    let found_files = game
+        .files
+        .iter()
+        .map(|(file, relative)| {
+            let hash = match file.try_hash(hash_algorithm) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    log::error!("[{name}] unable to read imported file: {:?} | {e}", file);
+                    return Err(file.clone());
+                }
+            };
+            let scanned = ScannedFile {
+                size: file.size(),
+                hash,
+                original_path: None,
+                ignored: false,
+                change: ScanChange::New,
+                container: None,
+                redirected: Some(target.joined(relative)),
+                excluded: None,
+                symlink: None,
+                hardlink: None,
+                conflict: false,
+            };
+            Ok((file.clone(), scanned))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(ScanInfo {
+        game_name: name.to_string(),
+        found_files,
+        hash_algorithm,
+        ..Default::default()
+    })
+}
+
+/// When the imported saves were last changed, so that the backup reflects their age.
+pub fn last_modified(game: &ExternalGame) -> Option<chrono::DateTime<chrono::Utc>> {
+    game.files
+        .iter()
+        .filter_map(|(file, _)| file.get_mtime().ok())
+        .max()
+        .map(chrono::DateTime::<chrono::Utc>::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::{repo_path, s};
+
+    #[test]
+    fn can_discover_games_in_folders() {
+        let path = repo_path("tests/external/folders");
+
+        let games = FoldersImporter.discover(&path);
+        assert_eq!(
+            vec![
+                (s("Game A"), vec![s("save.dat")]),
+                (s("Game B"), vec![s("nested/save.dat")])
+            ],
+            games
+                .iter()
+                .map(|x| (
+                    x.label.clone(),
+                    x.files.iter().map(|(_, relative)| relative.clone()).collect()
+                ))
+                .collect::<Vec<(String, Vec<String>)>>(),
+        );
+
+        let target = StrictPath::new("/restore/here");
+        let scan = scan_external_game(&games[1], "game b", &target, HashAlgorithm::Sha1).unwrap();
+        let (source, file) = scan.found_files.iter().next().unwrap();
+        assert_eq!(Some(s("b")), source.read());
+        assert_eq!(s("/restore/here/nested/save.dat"), file.mapping_key(source));
+        assert_eq!(ScanChange::New, file.change);
+    }
+
+    #[test]
+    fn can_choose_restore_target() {
+        let path = repo_path("tests/external/target");
+
+        let file = path.joined("config.ini");
+        let missing_file = path.joined("settings.json");
+        let folder = path.joined("saves");
+        let glob = path.joined("profiles/*.sav");
+        let mut plan = ScanPlan {
+            paths: [&file, &missing_file, &folder, &glob]
+                .into_iter()
+                .map(|x| (x.clone(), None))
+                .collect(),
+            ..Default::default()
+        };
+
+        // Existing folders win when nothing is tagged as a save.
+        assert_eq!(Some(folder.render()), restore_target(&plan).map(|x| x.render()));
+
+        plan.save_paths.insert(glob.clone());
+        assert_eq!(
+            Some(path.joined("profiles").render()),
+            restore_target(&plan).map(|x| x.render())
+        );
+
+        plan.paths = [&file, &missing_file].into_iter().map(|x| (x.clone(), None)).collect();
+        plan.save_paths = [file.clone(), missing_file.clone()].into_iter().collect();
+        assert_eq!(None, restore_target(&plan));
+    }
+
+    #[test]
+    fn can_cut_paths_before_wildcards() {
+        assert_eq!(Some(s("/home/user/game")), glob_free_prefix("/home/user/game/*.sav"));
+        assert_eq!(Some(s("/home/user/game")), glob_free_prefix("/home/user/game/"));
+        assert_eq!(Some(s("C:/Users/user")), glob_free_prefix("C:/Users/user/*/game"));
+        assert_eq!(None, glob_free_prefix("/home/*/game"));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     lang::TRANSLATOR,
     resource::manifest,
-    scan::{Duplication, ScanInfo},
+    scan::{BackupInfo, Duplication, ScanInfo},
 };
 
 use super::ScanChange;
@@ -17,6 +17,7 @@ pub enum Event {
     EditedFilterEnablement(Enablement),
     EditedFilterChange(Change),
     EditedFilterManifest(Manifest),
+    EditedFilterFailure(Failure),
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -26,6 +27,7 @@ pub enum FilterKind {
     Enablement,
     Change,
     Manifest,
+    Failure,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -155,3 +157,28 @@ impl Manifest {
             || (self.source == manifest::Source::Custom && customized)
     }
 }
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Failure {
+    #[default]
+    Failed,
+    Succeeded,
+}
+
+impl Failure {
+    pub const ALL: &'static [Self] = &[Self::Failed, Self::Succeeded];
+
+    /// Games that haven't been processed yet don't qualify either way.
+    pub fn qualifies(&self, backup_info: Option<&BackupInfo>) -> bool {
+        match self {
+            Self::Failed => backup_info.is_some_and(|x| !x.successful()),
+            Self::Succeeded => backup_info.is_some_and(|x| x.successful()),
+        }
+    }
+}
+
+impl ToString for Failure {
+    fn to_string(&self) -> String {
+        TRANSLATOR.filter_failure(*self)
+    }
+}
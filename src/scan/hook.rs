@@ -0,0 +1,213 @@
+use std::{
+    io::Read,
+    time::{Duration, Instant},
+};
+
+use crate::{path::StrictPath, scan::OperationWarning};
+
+/// Commands to run before and after backing up each game.
+#[derive(Clone, Debug)]
+pub struct BackupHooks {
+    pub pre: Option<String>,
+    pub post: Option<String>,
+    /// Hooks that take longer than this are stopped and reported as failures.
+    pub timeout: Duration,
+}
+
+impl Default for BackupHooks {
+    fn default() -> Self {
+        Self {
+            pre: None,
+            post: None,
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl BackupHooks {
+    pub fn new(pre: Option<String>, post: Option<String>) -> Self {
+        Self {
+            pre: pre.filter(|x| !x.trim().is_empty()),
+            post: post.filter(|x| !x.trim().is_empty()),
+            ..Default::default()
+        }
+    }
+
+    pub fn run_pre(&self, game: &str, path: &StrictPath) -> Option<OperationWarning> {
+        let template = self.pre.as_ref()?;
+        self.run(template, game, path, None).err()
+    }
+
+    pub fn run_post(&self, game: &str, path: &StrictPath, successful: bool) -> Option<OperationWarning> {
+        let template = self.post.as_ref()?;
+        let status = if successful { "success" } else { "failure" };
+        self.run(template, game, path, Some(status)).err()
+    }
+
+    fn run(&self, template: &str, game: &str, path: &StrictPath, status: Option<&str>) -> Result<(), OperationWarning> {
+        let fail = |message: String| {
+            log::warn!("[{game}] hook failed: {template} | {message}");
+            OperationWarning::HookFailed {
+                command: template.to_string(),
+                message,
+            }
+        };
+
+        let Some(args) = render_hook(template, game, path, status) else {
+            return Err(fail("unable to parse command".to_string()));
+        };
+
+        let mut command = std::process::Command::new(&args[0]);
+        command.args(&args[1..]);
+        command.stdin(std::process::Stdio::null());
+        command.stdout(std::process::Stdio::piped());
+        command.stderr(std::process::Stdio::piped());
+
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            command.creation_flags(windows::Win32::System::Threading::CREATE_NO_WINDOW.0);
+        }
+
+        log::info!("[{game}] running hook: {args:?}");
+        let mut child = command.spawn().map_err(|e| fail(e.to_string()))?;
+
+        // Read the output in the background so that a chatty hook can't fill the pipe and stall.
+        let drain = |stream: Option<Box<dyn Read + Send>>| {
+            std::thread::spawn(move || {
+                let mut content = String::new();
+                if let Some(mut stream) = stream {
+                    let _ = stream.read_to_string(&mut content);
+                }
+                content.trim().to_string()
+            })
+        };
+        let stdout = drain(child.stdout.take().map(|x| Box::new(x) as Box<dyn Read + Send>));
+        let stderr = drain(child.stderr.take().map(|x| Box::new(x) as Box<dyn Read + Send>));
+
+        let started = Instant::now();
+        let exit = loop {
+            match child.try_wait() {
+                Ok(Some(exit)) => break exit,
+                Ok(None) if started.elapsed() >= self.timeout => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(fail(format!("timed out after {:?}", self.timeout)));
+                }
+                Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+                Err(e) => return Err(fail(e.to_string())),
+            }
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        if !stdout.is_empty() {
+            log::info!("[{game}] hook stdout: {stdout}");
+        }
+        if !stderr.is_empty() {
+            log::info!("[{game}] hook stderr: {stderr}");
+        }
+
+        if exit.success() {
+            Ok(())
+        } else {
+            match exit.code() {
+                Some(code) => Err(fail(format!("exit code {code}"))),
+                None => Err(fail("terminated".to_string())),
+            }
+        }
+    }
+}
+
+/// The tokens are filled in after splitting the command,
+/// so that game names with spaces or quotes stay as single arguments.
+fn render_hook(template: &str, game: &str, path: &StrictPath, status: Option<&str>) -> Option<Vec<String>> {
+    let args: Vec<_> = shlex::split(template)?
+        .into_iter()
+        .map(|arg| {
+            arg.replace("{game}", game)
+                .replace("{path}", &path.render())
+                .replace("{status}", status.unwrap_or_default())
+        })
+        .collect();
+
+    (!args.is_empty()).then_some(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::testing::s;
+
+    #[test]
+    fn can_render_hook() {
+        assert_eq!(
+            Some(vec![s("notify"), s("it's done"), s("/backups/game"), s("success")]),
+            render_hook(
+                "notify '{game}' {path} {status}",
+                "it's done",
+                &StrictPath::new(s("/backups/game")),
+                Some("success"),
+            ),
+        );
+        assert_eq!(
+            Some(vec![s("notify"), s("")]),
+            render_hook("notify {status}", "game", &StrictPath::new(s("/backups/game")), None),
+        );
+        assert_eq!(
+            None,
+            render_hook("", "game", &StrictPath::new(s("/backups/game")), None)
+        );
+        assert_eq!(
+            None,
+            render_hook("notify 'unclosed", "game", &StrictPath::new(s("/backups/game")), None)
+        );
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn can_run_hooks() {
+        let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-hooks"));
+        let _ = path.remove();
+        path.create_dirs().unwrap();
+
+        let hooks = BackupHooks::new(
+            Some(s("sh -c 'echo {game} > {path}/pre.txt'")),
+            Some(s("sh -c 'echo {status} > {path}/post.txt'")),
+        );
+        assert_eq!(None, hooks.run_pre("game1", &path));
+        assert_eq!(None, hooks.run_post("game1", &path, false));
+        assert_eq!("game1\n", path.joined("pre.txt").read().unwrap());
+        assert_eq!("failure\n", path.joined("post.txt").read().unwrap());
+
+        let _ = path.remove();
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn reports_hook_failures() {
+        let path = StrictPath::new(s("/tmp"));
+
+        let hooks = BackupHooks::new(Some(s("sh -c 'exit 3'")), None);
+        assert_eq!(
+            Some(OperationWarning::HookFailed {
+                command: s("sh -c 'exit 3'"),
+                message: s("exit code 3"),
+            }),
+            hooks.run_pre("game1", &path),
+        );
+
+        let hooks = BackupHooks {
+            timeout: Duration::from_millis(100),
+            ..BackupHooks::new(None, Some(s("sleep 5")))
+        };
+        assert!(matches!(
+            hooks.run_post("game1", &path, true),
+            Some(OperationWarning::HookFailed { .. })
+        ));
+
+        assert_eq!(None, BackupHooks::default().run_pre("game1", &path));
+    }
+}
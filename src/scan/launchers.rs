@@ -42,6 +42,11 @@ impl Launchers {
             .iter()
     }
 
+    /// Whether the game was found in any root.
+    pub fn contains_game(&self, game: &str) -> bool {
+        self.games.values().any(|games| games.contains_key(game))
+    }
+
     pub fn scan(
         roots: &[Root],
         manifest: &Manifest,
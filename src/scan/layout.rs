@@ -1,22 +1,30 @@
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     io::Write,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
 };
 
 use chrono::{Datelike, Timelike};
 
 use crate::{
-    path::StrictPath,
-    prelude::{AnyError, INVALID_FILE_CHARS},
+    lang::TRANSLATOR,
+    path::{cancelled_error, StrictPath},
+    prelude::{sha1, AnyError, Error, Hasher, INVALID_FILE_CHARS},
     resource::{
         config::{
-            BackupFormat, BackupFormats, RedirectConfig, Retention, ToggledPaths, ToggledRegistry, ZipCompression,
+            AgeRetention, BackupFilter, BackupFormat, BackupFormats, ConflictPolicy, FilenamePolicy, HashAlgorithm,
+            RedirectConfig, Retention, RetentionPolicy, SpaceCheck, ToggledPaths, ToggledRegistry, ZipCompression,
+            ZipKeyDerivation,
         },
-        manifest::Os,
+        manifest::{Game, Manifest, Os},
     },
     scan::{
-        game_file_target, prepare_backup_target, registry, BackupError, BackupId, BackupInfo, ScanChange, ScanInfo,
-        ScanKind, ScannedFile,
+        delta, game_file_target, prepare_backup_target, registry, with_wine_prefix_redirects, BackupError, BackupHooks,
+        BackupId, BackupInfo, HardlinkTarget, OperationWarning, ScanChange, ScanInfo, ScanKind, ScannedFile,
+        SymlinkTarget,
     },
 };
 
@@ -25,6 +33,15 @@ use crate::scan::ScannedRegistry;
 
 const SAFE: &str = "_";
 const SOLO: &str = ".";
+/// Temporary folder within a game's backups where merged content is assembled.
+const MERGE_STAGING: &str = ".merge";
+/// Sidecar file with the Unix permissions of files in a Simple backup.
+const PERMISSIONS_FILE: &str = "permissions.yaml";
+/// Folder in the backup root where deduplicated file content is stored, keyed by hash.
+/// Game folders never start with a dot, so this can't collide with one.
+const OBJECTS_FOLDER: &str = ".objects";
+/// How deeply to look for game folders, to support nesting from a folder template.
+const MAX_GAME_FOLDER_DEPTH: usize = 4;
 
 macro_rules! some_or_continue {
     ($maybe:expr) => {
@@ -35,6 +52,10 @@ macro_rules! some_or_continue {
     };
 }
 
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|x| format!("{x:02x}")).collect()
+}
+
 fn encode_base64_for_folder(name: &str) -> String {
     use base64::prelude::*;
 
@@ -42,6 +63,17 @@ fn encode_base64_for_folder(name: &str) -> String {
 }
 
 pub fn escape_folder_name(name: &str) -> String {
+    escape_folder_name_for(name, FilenamePolicy::Portable)
+}
+
+pub fn escape_folder_name_for(name: &str, policy: FilenamePolicy) -> String {
+    let (leading_dot, trailing_dot, invalid): (bool, bool, &[char]) = match policy {
+        FilenamePolicy::Portable => (true, true, INVALID_FILE_CHARS),
+        FilenamePolicy::Windows => (false, true, INVALID_FILE_CHARS),
+        // Backslashes are allowed on Linux and Mac, but we treat them as separators.
+        FilenamePolicy::Posix => (false, false, &['/', '\\', '\0']),
+    };
+
     let mut escaped = String::from(name);
 
     // Technically, dots should be fine as long as the folder name isn't
@@ -52,22 +84,193 @@ pub fn escape_folder_name(name: &str) -> String {
     // the folder no longer exists at that location, so we also escape dots
     // at the end of the name. The combination of these two rules also
     // happens to cover the `.` and `..` cases.
-    if escaped.starts_with('.') {
+    if leading_dot && escaped.starts_with('.') {
         escaped.replace_range(..1, SAFE);
     }
-    if escaped.ends_with('.') {
+    if trailing_dot && escaped.ends_with('.') {
         escaped.replace_range(escaped.len() - 1.., SAFE);
     }
+    if escaped == "." || escaped == ".." {
+        escaped = SAFE.repeat(escaped.len());
+    }
+
+    escaped.replace(invalid, SAFE)
+}
+
+/// Expand the placeholders in a backup comment template.
+/// Returns `None` if the result would be blank.
+fn render_backup_comment(
+    template: &str,
+    game: &str,
+    now: &chrono::DateTime<chrono::Utc>,
+    count: usize,
+) -> Option<String> {
+    let rendered = template
+        .replace("{game}", game)
+        .replace("{date}", &now.format("%Y%m%dT%H%M%SZ").to_string())
+        .replace("{os}", Os::HOST.slug())
+        .replace("{count}", &count.to_string());
+    let rendered = rendered.trim();
+
+    (!rendered.is_empty()).then(|| rendered.to_string())
+}
+
+/// Decide which backups to keep under time-based retention.
+/// Within each window, only the newest backup from each hour/day/week is kept.
+/// A backup exactly as old as a window falls outside of it.
+fn retained_by_age<K: Copy + Eq + std::hash::Hash>(
+    backups: &[(K, chrono::DateTime<chrono::Utc>)],
+    age: AgeRetention,
+    now: &chrono::DateTime<chrono::Utc>,
+) -> HashSet<K> {
+    const HOUR: i64 = 60 * 60;
+    const DAY: i64 = 24 * HOUR;
+    const WEEK: i64 = 7 * DAY;
+    // The Unix epoch was a Thursday, so this makes each week start on Monday.
+    const WEEK_OFFSET: i64 = 3 * DAY;
+
+    let elapsed = |when: &chrono::DateTime<chrono::Utc>| (*now - *when).num_seconds();
+
+    let mut kept: HashSet<_> = backups
+        .iter()
+        .filter(|(_, when)| elapsed(when) < age.within_hours as i64 * HOUR)
+        .map(|(key, _)| *key)
+        .collect();
+
+    for (window, period, offset) in [
+        (age.hourly as i64 * HOUR, HOUR, 0),
+        (age.daily as i64 * DAY, DAY, 0),
+        (age.weekly as i64 * WEEK, WEEK, WEEK_OFFSET),
+    ] {
+        let mut newest = HashMap::<i64, (K, chrono::DateTime<chrono::Utc>)>::new();
+        for (key, when) in backups {
+            if elapsed(when) >= window {
+                continue;
+            }
+            let slot = (when.timestamp() + offset).div_euclid(period);
+            match newest.get(&slot) {
+                Some((_, existing)) if existing >= when => {}
+                _ => {
+                    newest.insert(slot, (*key, *when));
+                }
+            }
+        }
+        kept.extend(newest.into_values().map(|(key, _)| key));
+    }
+
+    kept
+}
+
+/// Tar archives can only be read sequentially, so each use needs a fresh handle.
+fn open_tar(path: &StrictPath, format: BackupFormat) -> std::io::Result<tar::Archive<Box<dyn std::io::Read>>> {
+    let handle = path.open()?;
+    let reader: Box<dyn std::io::Read> = match format {
+        BackupFormat::TarGz => Box::new(flate2::read::GzDecoder::new(handle)),
+        _ => Box::new(handle),
+    };
+    Ok(tar::Archive::new(reader))
+}
+
+fn tar_entry_names(path: &StrictPath, format: BackupFormat) -> std::io::Result<BTreeSet<String>> {
+    let mut archive = open_tar(path, format)?;
+    let mut names = BTreeSet::new();
+    for entry in archive.entries()? {
+        names.insert(String::from_utf8_lossy(&entry?.path_bytes()).to_string());
+    }
+    Ok(names)
+}
+
+/// In simple backups, a file that is hardlinked to another one with the same stored copy
+/// is only recorded in the mapping, and this returns the other file in that case.
+fn shared_hardlink<'a>(
+    format: BackupFormat,
+    file: &str,
+    hardlinks: &'a BTreeMap<String, String>,
+    info: impl Fn(&str) -> Option<&'a IndividualMappingFile>,
+) -> Option<&'a str> {
+    if format != BackupFormat::Simple {
+        return None;
+    }
+
+    let primary = hardlinks.get(file).filter(|x| !hardlinks.contains_key(*x))?;
+    let plain = |x: &IndividualMappingFile| !x.object && x.base.is_none();
+    let (linked, original) = (info(file)?, info(primary)?);
 
-    escaped.replace(INVALID_FILE_CHARS, SAFE)
+    (plain(linked) && plain(original) && linked.hash == original.hash).then_some(primary.as_str())
 }
 
 pub struct LatestBackup {
     pub scan: ScanInfo,
+    /// When the latest backup was made.
+    pub when: chrono::DateTime<chrono::Utc>,
     #[cfg_attr(not(target_os = "windows"), allow(unused))]
     pub registry_content: Option<registry::Hives>,
 }
 
+/// Outcome of applying retention limits outside of a backup.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MaintenanceInfo {
+    /// Names of backups that exceeded the retention limits.
+    pub forgotten: Vec<String>,
+    /// Total size of the backup files that were (or would be) removed.
+    pub freed_bytes: u64,
+}
+
+impl MaintenanceInfo {
+    pub fn is_empty(&self) -> bool {
+        self.forgotten.is_empty() && self.freed_bytes == 0
+    }
+}
+
+/// Size and timing of a game's most recent backup.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BackupHistory {
+    /// Total size of the files in the backup.
+    pub size: u64,
+    /// How long it took to create the backup, if recorded.
+    pub duration_ms: Option<u64>,
+}
+
+/// The latest backup of a game that contains a given file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FileCoverage {
+    pub game: String,
+    pub backup: Backup,
+    pub file: IndividualMappingFile,
+}
+
+/// Differences between two backups of the same game.
+/// Files are identified by their original path.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupDiff {
+    /// Files that are only in the newer backup.
+    pub added: BTreeSet<String>,
+    /// Files that are only in the older backup.
+    pub removed: BTreeSet<String>,
+    /// Files that are in both backups, but with different content.
+    pub changed: BTreeSet<String>,
+    /// Whether the registry content differs, based on its hash.
+    pub registry_changed: bool,
+}
+
+impl BackupDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty() && !self.registry_changed
+    }
+}
+
+/// Where a backed up file is stored on disk.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredFile {
+    /// The file is stored directly in the backup folder.
+    /// For deduplicated content, this is the shared object file.
+    Simple { path: String },
+    /// The file is stored inside of an archive.
+    Archive { container: String, entry: String },
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Backup {
     Full(FullBackup),
@@ -130,11 +333,85 @@ impl Backup {
         }
     }
 
-    pub fn label(&self) -> String {
+    pub fn checksum(&self) -> Option<&String> {
+        match self {
+            Self::Full(x) => x.checksum.as_ref(),
+            Self::Differential(x) => x.checksum.as_ref(),
+        }
+    }
+
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        match self {
+            Self::Full(x) => x.hash_algorithm,
+            Self::Differential(x) => x.hash_algorithm,
+        }
+    }
+
+    pub fn set_checksum(&mut self, checksum: Option<String>) {
+        match self {
+            Self::Full(x) => x.checksum = checksum,
+            Self::Differential(x) => x.checksum = checksum,
+        }
+    }
+
+    pub fn encryption(&self) -> Option<&BackupEncryption> {
         match self {
+            Self::Full(x) => x.encryption.as_ref(),
+            Self::Differential(x) => x.encryption.as_ref(),
+        }
+    }
+
+    pub fn set_encryption(&mut self, encryption: Option<BackupEncryption>) {
+        match self {
+            Self::Full(x) => x.encryption = encryption,
+            Self::Differential(x) => x.encryption = encryption,
+        }
+    }
+
+    pub fn duration_ms(&self) -> Option<u64> {
+        match self {
+            Self::Full(x) => x.duration_ms,
+            Self::Differential(x) => x.duration_ms,
+        }
+    }
+
+    pub fn set_duration(&mut self, duration: std::time::Duration) {
+        let duration_ms = Some(duration.as_millis() as u64);
+        match self {
+            Self::Full(x) => x.duration_ms = duration_ms,
+            Self::Differential(x) => x.duration_ms = duration_ms,
+        }
+    }
+
+    pub fn format(&self) -> BackupFormat {
+        match self {
+            Self::Full(x) => x.format(),
+            Self::Differential(x) => x.format(),
+        }
+    }
+
+    pub fn flat(&self) -> bool {
+        match self {
+            Self::Full(x) => x.flat,
+            Self::Differential(x) => x.flat,
+        }
+    }
+
+    /// Total size of the files that would be restored from this backup.
+    /// For differential backups, this includes the files inherited from the full backup.
+    pub fn total_size(&self) -> u64 {
+        match self {
+            Self::Full(x) => x.total_size(),
+            Self::Differential(x) => x.inherited_size + x.stored_size(),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        let label = match self {
             Self::Full(x) => x.label(),
             Self::Differential(x) => x.label(),
-        }
+        };
+        format!("{label} ({})", TRANSLATOR.adjusted_size(self.total_size()))
     }
 
     pub fn id(&self) -> BackupId {
@@ -163,6 +440,34 @@ impl Backup {
         }
     }
 
+    /// Name of the full backup against which the file is stored as a delta, if any.
+    /// File path must be in rendered form.
+    pub fn delta_base(&self, file: &str) -> Option<&str> {
+        match self {
+            Self::Full(_) => None,
+            Self::Differential(backup) => backup.files.get(file)?.as_ref()?.base.as_deref(),
+        }
+    }
+
+    /// Hash under which the file is stored in the shared object folder, if it is.
+    /// File path must be in rendered form.
+    pub fn object_hash(&self, file: &str) -> Option<&str> {
+        let file = match self {
+            Self::Full(backup) => backup.files.get(file)?,
+            Self::Differential(backup) => backup.files.get(file)?.as_ref()?,
+        };
+        file.object.then_some(file.hash.as_str())
+    }
+
+    /// The file whose stored copy this one shares, if it's only recorded as a hardlink.
+    /// File path must be in rendered form.
+    pub fn shared_hardlink(&self, file: &str) -> Option<&str> {
+        match self {
+            Self::Full(backup) => backup.shared_hardlink(file),
+            Self::Differential(backup) => backup.shared_hardlink(file),
+        }
+    }
+
     #[cfg_attr(not(target_os = "windows"), allow(unused))]
     pub fn includes_registry(&self) -> bool {
         match self {
@@ -192,6 +497,10 @@ impl Backup {
                 for file in failed {
                     backup.files.remove(&file);
                 }
+                let files = &backup.files;
+                backup
+                    .hardlinks
+                    .retain(|file, primary| files.contains_key(file) && files.contains_key(primary));
 
                 // TODO: Registry failures are currently ignored during backup.
                 // If that changes, then make sure this logic is still appropriate.
@@ -209,6 +518,8 @@ impl Backup {
                 for file in failed {
                     backup.files.remove(&file);
                 }
+                let files = &backup.files;
+                backup.hardlinks.retain(|file, _| files.contains_key(file));
 
                 if !backup_info.failed_registry.is_empty() {
                     backup.registry = None;
@@ -244,12 +555,50 @@ pub struct FullBackup {
     /// Locked backups do not count toward retention limits and are never deleted.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub locked: bool,
+    /// Hash of the stored backup content, used to detect tampering.
+    /// For archives, this is the hash of the archive file.
+    /// For simple backups, this is a composite hash of the stored files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// For encrypted zip backups, how to check the password before decrypting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<BackupEncryption>,
+    /// Algorithm that produced the file and registry hashes.
+    /// Backups from before this was recorded always used SHA-1.
+    #[serde(skip_serializing_if = "HashAlgorithm::is_default")]
+    pub hash_algorithm: HashAlgorithm,
+    /// How long it took to create the backup, in milliseconds.
+    /// Backups from before this was recorded do not have it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Files are stored by their path without the drive,
+    /// rather than in a `drive-*` folder.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub flat: bool,
     pub files: BTreeMap<String, IndividualMappingFile>,
+    /// Files that were symlinks, mapped to their link targets.
+    /// This is only recorded for simple backups.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub symlinks: BTreeMap<String, String>,
+    /// Files that were hardlinked to another file in the backup,
+    /// mapped to the file that they should be linked to on restore.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub hardlinks: BTreeMap<String, String>,
     pub registry: IndividualMappingRegistry,
     pub children: VecDeque<DifferentialBackup>,
 }
 
 impl FullBackup {
+    pub fn total_size(&self) -> u64 {
+        self.files.values().map(|x| x.size).sum()
+    }
+
+    /// The file whose stored copy this one shares, if it's only recorded as a hardlink.
+    /// File path must be in rendered form.
+    pub fn shared_hardlink(&self, file: &str) -> Option<&str> {
+        shared_hardlink(self.format(), file, &self.hardlinks, |x| self.files.get(x))
+    }
+
     pub fn label(&self) -> String {
         chrono::DateTime::<chrono::Local>::from(self.when)
             .format("%Y-%m-%dT%H:%M:%S")
@@ -257,11 +606,7 @@ impl FullBackup {
     }
 
     pub fn format(&self) -> BackupFormat {
-        if self.name.ends_with(".zip") {
-            BackupFormat::Zip
-        } else {
-            BackupFormat::Simple
-        }
+        BackupFormat::from_name(&self.name)
     }
 }
 
@@ -284,8 +629,40 @@ pub struct DifferentialBackup {
     /// Locked backups do not count toward retention limits and are never deleted.
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     pub locked: bool,
+    /// Hash of the stored backup content, used to detect tampering.
+    /// For archives, this is the hash of the archive file.
+    /// For simple backups, this is a composite hash of the stored files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+    /// For encrypted zip backups, how to check the password before decrypting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<BackupEncryption>,
+    /// Algorithm that produced the file and registry hashes.
+    /// Backups from before this was recorded always used SHA-1.
+    #[serde(skip_serializing_if = "HashAlgorithm::is_default")]
+    pub hash_algorithm: HashAlgorithm,
+    /// How long it took to create the backup, in milliseconds.
+    /// Backups from before this was recorded do not have it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Files are stored by their path without the drive,
+    /// rather than in a `drive-*` folder.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub flat: bool,
     pub files: BTreeMap<String, Option<IndividualMappingFile>>,
+    /// Files stored in this backup that were symlinks, mapped to their link targets.
+    /// This is only recorded for simple backups.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub symlinks: BTreeMap<String, String>,
+    /// Files stored in this backup that were hardlinked to another file,
+    /// mapped to the file that they should be linked to on restore.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub hardlinks: BTreeMap<String, String>,
     pub registry: Option<IndividualMappingRegistry>,
+    /// Total size of the files inherited from the parent full backup.
+    /// This is not stored in the mapping file, but filled in when the backup is looked up.
+    #[serde(skip)]
+    pub inherited_size: u64,
 }
 
 impl DifferentialBackup {
@@ -304,6 +681,29 @@ impl DifferentialBackup {
         self.registry.as_ref().map(|x| x.hash.is_none()).unwrap_or_default()
     }
 
+    /// The file whose stored copy this one shares, if it's only recorded as a hardlink.
+    /// File path must be in rendered form.
+    pub fn shared_hardlink(&self, file: &str) -> Option<&str> {
+        shared_hardlink(self.format(), file, &self.hardlinks, |x| self.files.get(x)?.as_ref())
+    }
+
+    /// Size of the files stored in this backup, not counting inherited ones.
+    pub fn stored_size(&self) -> u64 {
+        self.files.values().flatten().map(|x| x.size).sum()
+    }
+
+    /// Record the size of the files inherited from the parent,
+    /// skipping any that this backup replaces or removes.
+    pub fn with_inherited_size(mut self, parent: &FullBackup) -> Self {
+        self.inherited_size = parent
+            .files
+            .iter()
+            .filter(|(k, _)| !self.files.contains_key(*k))
+            .map(|(_, v)| v.size)
+            .sum();
+        self
+    }
+
     pub fn label(&self) -> String {
         chrono::DateTime::<chrono::Local>::from(self.when)
             .format("%Y-%m-%dT%H:%M:%S")
@@ -311,11 +711,7 @@ impl DifferentialBackup {
     }
 
     pub fn format(&self) -> BackupFormat {
-        if self.name.ends_with(".zip") {
-            BackupFormat::Zip
-        } else {
-            BackupFormat::Simple
-        }
+        BackupFormat::from_name(&self.name)
     }
 }
 
@@ -324,6 +720,65 @@ impl DifferentialBackup {
 pub struct IndividualMappingFile {
     pub hash: String,
     pub size: u64,
+    /// When set, this file is stored as a binary delta
+    /// against its copy in the named full backup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// When set, this file's content is stored once in the shared object folder,
+    /// under its hash, rather than in the backup itself.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub object: bool,
+    /// The file's modification time when it was backed up.
+    /// This is only recorded for objects, since the stored copy is shared with other games.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtime: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// How to check the password of an encrypted zip backup.
+/// The password itself is never stored.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BackupEncryption {
+    pub key_derivation: ZipKeyDerivation,
+    /// Random salt for the verifier.
+    pub salt: String,
+    /// Key derived from the password and salt.
+    /// A password that doesn't produce the same key is wrong.
+    pub verifier: String,
+}
+
+impl BackupEncryption {
+    const VERIFIER_ROUNDS: u32 = 100_000;
+
+    fn new(key_derivation: ZipKeyDerivation, password: &str) -> Result<Self, getrandom::Error> {
+        let mut salt = [0; 16];
+        getrandom::fill(&mut salt)?;
+        let salt = encode_hex(&salt);
+
+        Ok(Self {
+            key_derivation,
+            verifier: Self::derive(password, &salt),
+            salt,
+        })
+    }
+
+    fn derive(password: &str, salt: &str) -> String {
+        let mut key = [0; 32];
+        pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password.as_bytes(), salt.as_bytes(), Self::VERIFIER_ROUNDS, &mut key);
+        encode_hex(&key)
+    }
+
+    pub fn accepts(&self, password: &str) -> bool {
+        Self::derive(password, &self.salt) == self.verifier
+    }
+
+    fn aes_mode(&self) -> zip::AesMode {
+        match self.key_derivation {
+            ZipKeyDerivation::Aes128 => zip::AesMode::Aes128,
+            ZipKeyDerivation::Aes192 => zip::AesMode::Aes192,
+            ZipKeyDerivation::Aes256 => zip::AesMode::Aes256,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -332,6 +787,54 @@ pub struct IndividualMappingRegistry {
     pub hash: Option<String>,
 }
 
+/// Unix file modes for a Simple backup, keyed by original path.
+/// Archive formats store these in the archive itself.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct FilePermissions(BTreeMap<String, u32>);
+
+impl FilePermissions {
+    #[cfg_attr(target_os = "windows", allow(unused))]
+    fn record(&mut self, original_path: String, source: &StrictPath) {
+        #[cfg(not(target_os = "windows"))]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            if let Ok(metadata) = source.metadata() {
+                self.0.insert(original_path, metadata.permissions().mode());
+            }
+        }
+    }
+
+    #[cfg_attr(target_os = "windows", allow(unused))]
+    fn mode(&self, original_path: &str) -> Option<u32> {
+        self.0.get(original_path).copied()
+    }
+
+    #[cfg_attr(target_os = "windows", allow(unused))]
+    fn load(file: &StrictPath) -> Self {
+        if !file.is_file() {
+            return Self::default();
+        }
+        match file.try_read().map(|content| serde_yaml::from_str(&content)) {
+            Ok(Ok(x)) => x,
+            Ok(Err(e)) => {
+                log::warn!("Unable to parse permissions: {:?} | {:?}", &file, e);
+                Self::default()
+            }
+            Err(e) => {
+                log::warn!("Unable to read permissions: {:?} | {:?}", &file, e);
+                Self::default()
+            }
+        }
+    }
+
+    fn save(&self, file: &StrictPath) -> Result<(), AnyError> {
+        file.create_parent_dir()?;
+        file.write_with_content(&serde_yaml::to_string(&self)?)?;
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct IndividualMapping {
@@ -348,6 +851,31 @@ impl IndividualMapping {
         }
     }
 
+    /// Combined size of all backups for this game.
+    /// Files in the shared object folder are only counted once.
+    pub fn stored_size(&self) -> u64 {
+        let mut objects = HashMap::new();
+        self.stored_size_without_objects(&mut objects) + objects.values().sum::<u64>()
+    }
+
+    /// Size of the files stored in this game's own backup folders.
+    /// Files in the shared object folder are collected by hash instead,
+    /// so that the caller can count each one once, even across games.
+    fn stored_size_without_objects(&self, objects: &mut HashMap<String, u64>) -> u64 {
+        let mut total = 0;
+        for full in &self.backups {
+            let children = full.children.iter().flat_map(|diff| diff.files.values().flatten());
+            for file in full.files.values().chain(children) {
+                if file.object {
+                    objects.insert(file.hash.clone(), file.size);
+                } else {
+                    total += file.size;
+                }
+            }
+        }
+        total
+    }
+
     fn reversed_drives(&self) -> HashMap<String, String> {
         self.drives.iter().map(|(k, v)| (v.to_owned(), k.to_owned())).collect()
     }
@@ -381,7 +909,36 @@ impl IndividualMapping {
         }
     }
 
+    /// Whether the named backup stores its files without `drive-*` folders.
+    fn is_flat(&self, backup: &str) -> bool {
+        self.backups.iter().any(|full| {
+            (full.name == backup && full.flat) || full.children.iter().any(|diff| diff.name == backup && diff.flat)
+        })
+    }
+
+    /// Flat backups can only be used when all of the files are on the same drive,
+    /// since otherwise the drive would be needed to tell them apart.
+    fn can_store_flat<'a>(format: &BackupFormats, backup: &str, files: impl Iterator<Item = &'a String>) -> bool {
+        if !format.flat || format.chosen != BackupFormat::Simple || backup == SOLO {
+            return false;
+        }
+
+        let drives: HashSet<_> = files
+            .map(|file| StrictPath::new(file.clone()).split_drive().0)
+            .collect();
+        drives.len() <= 1
+    }
+
+    fn flat_game_file(base: &StrictPath, original_file: &StrictPath, backup: &str) -> StrictPath {
+        let (_, plain_path) = original_file.split_drive();
+        StrictPath::relative(format!("{}/{}", backup, plain_path), base.interpret().ok())
+    }
+
     pub fn game_file(&mut self, base: &StrictPath, original_file: &StrictPath, backup: &str) -> StrictPath {
+        if self.is_flat(backup) {
+            return Self::flat_game_file(base, original_file, backup);
+        }
+
         let (drive, plain_path) = original_file.split_drive();
         let drive_folder = self.drive_folder_name(&drive);
         StrictPath::relative(
@@ -391,6 +948,10 @@ impl IndividualMapping {
     }
 
     pub fn game_file_immutable(&self, base: &StrictPath, original_file: &StrictPath, backup: &str) -> StrictPath {
+        if self.is_flat(backup) {
+            return Self::flat_game_file(base, original_file, backup);
+        }
+
         let (drive, plain_path) = original_file.split_drive();
         let drive_folder = self.drive_folder_name_immutable(&drive);
         StrictPath::relative(
@@ -478,6 +1039,21 @@ impl IndividualMapping {
             .any(|full| full.name == name || full.children.iter().any(|diff| diff.name == name))
     }
 
+    /// Hashes of all content that this game keeps in the shared object folder.
+    pub fn object_hashes(&self) -> impl Iterator<Item = &str> {
+        self.backups
+            .iter()
+            .flat_map(|full| {
+                full.files.values().chain(
+                    full.children
+                        .iter()
+                        .flat_map(|diff| diff.files.values().filter_map(|x| x.as_ref())),
+                )
+            })
+            .filter(|x| x.object)
+            .map(|x| x.hash.as_str())
+    }
+
     pub fn irrelevant_parents(&self, base: &StrictPath) -> Vec<StrictPath> {
         let mut irrelevant = vec![];
         let relevant = self.backups.iter().map(|x| x.name.clone()).chain(
@@ -516,16 +1092,61 @@ impl IndividualMapping {
     }
 }
 
+/// Outcome of linking a restored file to another one, since they were hardlinked when backed up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HardlinkRestore {
+    /// A new link was created.
+    Created,
+    /// The files were already linked, so restoring the other one also updated this one.
+    Existing,
+    /// The files could not be linked, so this one needs to be restored separately.
+    Failed,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct GameLayout {
     pub path: StrictPath,
     mapping: IndividualMapping,
+    /// Root of the overall backup folder.
+    /// When unset, this is assumed to be the game folder's parent.
+    root: Option<StrictPath>,
+    hooks: BackupHooks,
+    redirect_fallback: bool,
+    wine_prefix: Option<StrictPath>,
+    space_check: SpaceCheck,
+    /// Disk list for the space check, shared by all games in a run and only built when first needed.
+    disks: Arc<OnceLock<Mutex<sysinfo::Disks>>>,
+    conflict_policy: ConflictPolicy,
+    mirrors: Vec<StrictPath>,
+    exclude_empty_dirs: bool,
+    /// When set, stop an in-progress backup as soon as possible.
+    cancel: Option<Arc<AtomicBool>>,
+    /// Registry exclusions from the backup filter, which also apply when restoring.
+    #[cfg_attr(not(target_os = "windows"), allow(unused))]
+    registry_filter: BackupFilter,
+    /// For creating and reading encrypted zip backups.
+    zip_password: Option<String>,
 }
 
 impl GameLayout {
     #[cfg(test)]
     pub fn new(path: StrictPath, mapping: IndividualMapping) -> Self {
-        Self { path, mapping }
+        Self {
+            path,
+            mapping,
+            root: None,
+            hooks: Default::default(),
+            redirect_fallback: false,
+            wine_prefix: None,
+            space_check: Default::default(),
+            disks: Default::default(),
+            conflict_policy: Default::default(),
+            mirrors: vec![],
+            exclude_empty_dirs: false,
+            cancel: None,
+            registry_filter: Default::default(),
+            zip_password: None,
+        }
     }
 
     pub fn load(path: StrictPath) -> Result<Self, AnyError> {
@@ -533,6 +1154,18 @@ impl GameLayout {
         Ok(Self {
             path,
             mapping: IndividualMapping::load(&mapping)?,
+            root: None,
+            hooks: Default::default(),
+            redirect_fallback: false,
+            wine_prefix: None,
+            space_check: Default::default(),
+            disks: Default::default(),
+            conflict_policy: Default::default(),
+            mirrors: vec![],
+            exclude_empty_dirs: false,
+            cancel: None,
+            registry_filter: Default::default(),
+            zip_password: None,
         })
     }
 
@@ -559,6 +1192,45 @@ impl GameLayout {
         }
     }
 
+    fn new_encryption(&self, format: &BackupFormats) -> Result<Option<BackupEncryption>, BackupError> {
+        let Some(encryption) = format.zip_encryption() else {
+            return Ok(None);
+        };
+        let Some(password) = self.zip_password.as_deref() else {
+            return Err(BackupError::App(Error::BackupPasswordMissing));
+        };
+
+        BackupEncryption::new(encryption.key_derivation, password)
+            .map(Some)
+            .map_err(|e| BackupError::Raw(e.to_string()))
+    }
+
+    /// The password to read the named backup's archive, if it is encrypted.
+    fn zip_password_for(&self, backup: &str) -> Result<Option<&str>, Error> {
+        let encryption = self.mapping.backups.iter().find_map(|full| {
+            if full.name == backup {
+                return Some(full.encryption.as_ref());
+            }
+            full.children
+                .iter()
+                .find(|diff| diff.name == backup)
+                .map(|diff| diff.encryption.as_ref())
+        });
+        let Some(encryption) = encryption.flatten() else {
+            return Ok(None);
+        };
+        let Some(password) = self.zip_password.as_deref() else {
+            return Err(Error::BackupPasswordMissing);
+        };
+        if !encryption.accepts(password) {
+            return Err(Error::BackupPasswordIncorrect {
+                backup: backup.to_string(),
+            });
+        }
+
+        Ok(Some(password))
+    }
+
     pub fn find_by_id(&self, id: &BackupId) -> Option<(&FullBackup, Option<&DifferentialBackup>)> {
         match id {
             BackupId::Latest => self.mapping.latest_backup(),
@@ -593,7 +1265,7 @@ impl GameLayout {
         match self.find_by_id(id) {
             None => None,
             Some((full, None)) => Some(Backup::Full(full.clone())),
-            Some((_, Some(diff))) => Some(Backup::Differential(diff.clone())),
+            Some((full, Some(diff))) => Some(Backup::Differential(diff.clone().with_inherited_size(full))),
         }
     }
 
@@ -621,53 +1293,178 @@ impl GameLayout {
                 ),
                 // Registry is handled separately.
                 found_registry_keys: Default::default(),
+                hash_algorithm: self
+                    .mapping
+                    .latest_backup()
+                    .map(|(full, _)| full.hash_algorithm)
+                    .unwrap_or_default(),
                 available_backups: vec![],
                 backup: None,
                 has_backups: true,
                 // Registry is handled separately.
                 dumped_registry: None,
                 only_constructive_backups,
+                warnings: vec![],
+                game_running: false,
             })
         }
     }
 
+    /// Find which backup stores the file as of the latest backup.
+    /// If the latest backup is differential and doesn't mention the file,
+    /// then the file is inherited from its full backup.
+    /// If the latest backup recorded the file as deleted, then it is not covered.
+    pub fn latest_backup_with_file(&self, file: &StrictPath) -> Option<(Backup, IndividualMappingFile)> {
+        let matches = |key: &String| StrictPath::new(key.to_string()).equivalent(file);
+
+        let (full, diff) = self.mapping.latest_backup()?;
+
+        if let Some(diff) = diff {
+            if let Some((_, stored)) = diff.files.iter().find(|(k, _)| matches(k)) {
+                let backup = Backup::Differential(diff.clone().with_inherited_size(full));
+                return stored.clone().map(|stored| (backup, stored));
+            }
+        }
+
+        full.files
+            .iter()
+            .find(|(k, _)| matches(k))
+            .map(|(_, stored)| (Backup::Full(full.clone()), stored.clone()))
+    }
+
     pub fn restorable_backups_flattened(&self) -> Vec<Backup> {
         let mut backups = vec![];
 
         for full in &self.mapping.backups {
             backups.push(Backup::Full(full.clone()));
             for diff in &full.children {
-                backups.push(Backup::Differential(diff.clone()));
+                backups.push(Backup::Differential(diff.clone().with_inherited_size(full)));
             }
         }
 
         backups
     }
 
-    pub fn restorable_files(
+    /// Find the latest backup made within the time window, where both bounds are exclusive.
+    pub fn latest_backup_between(
         &self,
-        id: &BackupId,
-        scan_kind: ScanKind,
-        redirects: &[RedirectConfig],
-        reverse_redirects_on_restore: bool,
-        toggled_paths: &ToggledPaths,
-    ) -> HashMap<StrictPath, ScannedFile> {
-        let mut files = HashMap::new();
+        after: Option<&chrono::DateTime<chrono::Utc>>,
+        before: Option<&chrono::DateTime<chrono::Utc>>,
+    ) -> Option<Backup> {
+        self.restorable_backups_flattened()
+            .into_iter()
+            .filter(|x| after.map(|after| x.when() > after).unwrap_or(true))
+            .filter(|x| before.map(|before| x.when() < before).unwrap_or(true))
+            .max_by_key(|x| *x.when())
+    }
 
-        match self.find_by_id(id) {
-            None => {}
-            Some((full, None)) => {
-                files.extend(self.restorable_files_from_full_backup(
-                    full,
-                    scan_kind,
-                    redirects,
-                    reverse_redirects_on_restore,
-                    toggled_paths,
-                ));
-            }
-            Some((full, Some(diff))) => {
-                files.extend(self.restorable_files_from_diff_backup(
-                    diff,
+    /// Compare the composite content of two backups.
+    /// Hashes from different algorithms can't be compared,
+    /// so every common file is considered changed when the algorithms differ.
+    pub fn diff(&self, a: &BackupId, b: &BackupId) -> BackupDiff {
+        let algorithm = |id: &BackupId| match self.find_by_id(id) {
+            None => None,
+            Some((full, None)) => Some(full.hash_algorithm),
+            Some((_, Some(diff))) => Some(diff.hash_algorithm),
+        };
+        let files = |id: &BackupId| -> BTreeMap<String, String> {
+            self.restorable_files(id, ScanKind::Backup, &[], false, &ToggledPaths::default())
+                .into_values()
+                .filter_map(|x| x.original_path.map(|path| (path.render(), x.hash)))
+                .collect()
+        };
+
+        let comparable = algorithm(a) == algorithm(b);
+        let old = files(a);
+        let new = files(b);
+
+        let mut diff = BackupDiff::default();
+        for (file, old_hash) in &old {
+            match new.get(file) {
+                None => {
+                    diff.removed.insert(file.clone());
+                }
+                Some(new_hash) => {
+                    if !comparable || old_hash != new_hash {
+                        diff.changed.insert(file.clone());
+                    }
+                }
+            }
+        }
+        for file in new.keys() {
+            if !old.contains_key(file) {
+                diff.added.insert(file.clone());
+            }
+        }
+
+        diff.registry_changed = self.registry_hash(a) != self.registry_hash(b);
+
+        diff
+    }
+
+    /// Find where each file in a backup is stored, keyed by the file's original path.
+    pub fn locate(&self, id: &BackupId) -> BTreeMap<String, StoredFile> {
+        let backup = self.find_by_id_flattened(id);
+
+        self.restorable_files(id, ScanKind::Backup, &[], false, &ToggledPaths::default())
+            .into_iter()
+            .map(|(scan_key, file)| {
+                let original_path = file.original_path(&scan_key);
+                let stored = match &file.container {
+                    None => {
+                        let stored = backup.as_ref().and_then(|backup| {
+                            self.stored_object(backup, original_path.raw())
+                                .or_else(|| self.stored_hardlink(backup, original_path.raw()))
+                        });
+                        StoredFile::Simple {
+                            path: stored.unwrap_or_else(|| scan_key.clone()).render(),
+                        }
+                    }
+                    Some(container) => StoredFile::Archive {
+                        container: container.render(),
+                        entry: scan_key.raw().to_string(),
+                    },
+                };
+                (original_path.render(), stored)
+            })
+            .collect()
+    }
+
+    fn registry_hash(&self, id: &BackupId) -> Option<&String> {
+        match self.find_by_id(id) {
+            None => None,
+            Some((full, None)) => full.registry.hash.as_ref(),
+            Some((full, Some(diff))) => match &diff.registry {
+                Some(registry) => registry.hash.as_ref(),
+                None => full.registry.hash.as_ref(),
+            },
+        }
+    }
+
+    pub fn restorable_files(
+        &self,
+        id: &BackupId,
+        scan_kind: ScanKind,
+        redirects: &[RedirectConfig],
+        reverse_redirects_on_restore: bool,
+        toggled_paths: &ToggledPaths,
+    ) -> HashMap<StrictPath, ScannedFile> {
+        let mut files = HashMap::new();
+
+        match self.find_by_id(id) {
+            None => {}
+            Some((full, None)) => {
+                files.extend(self.restorable_files_from_full_backup(
+                    full,
+                    scan_kind,
+                    redirects,
+                    reverse_redirects_on_restore,
+                    toggled_paths,
+                ));
+            }
+            Some((full, Some(diff))) => {
+                files.extend(self.restorable_files_from_diff_backup(
+                    diff,
                     scan_kind,
                     redirects,
                     reverse_redirects_on_restore,
@@ -682,8 +1479,24 @@ impl GameLayout {
                     toggled_paths,
                 ) {
                     let original_path = full_file.original_path.as_ref().unwrap().render();
-                    if diff.file(original_path) == BackupInclusion::Inherited {
-                        files.insert(scan_key, full_file);
+                    match diff.file(original_path) {
+                        BackupInclusion::Inherited => {
+                            files.insert(scan_key, full_file);
+                        }
+                        BackupInclusion::Excluded if scan_kind.is_restore() => {
+                            // The file was deleted by the time of this backup,
+                            // so restoring it means removing the live copy.
+                            if full_file.effective(&scan_key).is_file() {
+                                files.insert(
+                                    scan_key,
+                                    ScannedFile {
+                                        change: ScanChange::Removed,
+                                        ..full_file
+                                    },
+                                );
+                            }
+                        }
+                        BackupInclusion::Included | BackupInclusion::Excluded => {}
                     }
                 }
             }
@@ -722,9 +1535,11 @@ impl GameLayout {
                         ScannedFile {
                             change: match scan_kind {
                                 ScanKind::Backup => ScanChange::Unknown,
-                                ScanKind::Restore => {
-                                    ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
-                                }
+                                ScanKind::Restore => ScanChange::evaluate_restore(
+                                    redirected.as_ref().unwrap_or(&original_path),
+                                    &v.hash,
+                                    backup.hash_algorithm,
+                                ),
                             },
                             size: v.size,
                             hash: v.hash.clone(),
@@ -732,10 +1547,14 @@ impl GameLayout {
                             redirected,
                             original_path: Some(original_path),
                             container: None,
+                            excluded: None,
+                            symlink: backup.symlinks.get(mapping_key).cloned().map(SymlinkTarget),
+                            hardlink: backup.hardlinks.get(mapping_key).cloned().map(HardlinkTarget),
+                            conflict: false,
                         },
                     );
                 }
-                BackupFormat::Zip => {
+                BackupFormat::Zip | BackupFormat::Tar | BackupFormat::TarGz => {
                     let scan_key = StrictPath::new(self.mapping.game_file_for_zip_immutable(&original_path));
 
                     restorables.insert(
@@ -743,9 +1562,11 @@ impl GameLayout {
                         ScannedFile {
                             change: match scan_kind {
                                 ScanKind::Backup => ScanChange::Unknown,
-                                ScanKind::Restore => {
-                                    ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
-                                }
+                                ScanKind::Restore => ScanChange::evaluate_restore(
+                                    redirected.as_ref().unwrap_or(&original_path),
+                                    &v.hash,
+                                    backup.hash_algorithm,
+                                ),
                             },
                             size: v.size,
                             hash: v.hash.clone(),
@@ -753,6 +1574,10 @@ impl GameLayout {
                             redirected,
                             original_path: Some(original_path),
                             container: Some(self.path.joined(&backup.name)),
+                            excluded: None,
+                            symlink: None,
+                            hardlink: backup.hardlinks.get(mapping_key).cloned().map(HardlinkTarget),
+                            conflict: false,
                         },
                     );
                 }
@@ -793,9 +1618,11 @@ impl GameLayout {
                         ScannedFile {
                             change: match scan_kind {
                                 ScanKind::Backup => ScanChange::Unknown,
-                                ScanKind::Restore => {
-                                    ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
-                                }
+                                ScanKind::Restore => ScanChange::evaluate_restore(
+                                    redirected.as_ref().unwrap_or(&original_path),
+                                    &v.hash,
+                                    backup.hash_algorithm,
+                                ),
                             },
                             size: v.size,
                             hash: v.hash.clone(),
@@ -803,10 +1630,14 @@ impl GameLayout {
                             redirected,
                             original_path: Some(original_path),
                             container: None,
+                            excluded: None,
+                            symlink: backup.symlinks.get(mapping_key).cloned().map(SymlinkTarget),
+                            hardlink: backup.hardlinks.get(mapping_key).cloned().map(HardlinkTarget),
+                            conflict: false,
                         },
                     );
                 }
-                BackupFormat::Zip => {
+                BackupFormat::Zip | BackupFormat::Tar | BackupFormat::TarGz => {
                     let scan_key = StrictPath::new(self.mapping.game_file_for_zip_immutable(&original_path));
 
                     restorables.insert(
@@ -814,9 +1645,11 @@ impl GameLayout {
                         ScannedFile {
                             change: match scan_kind {
                                 ScanKind::Backup => ScanChange::Unknown,
-                                ScanKind::Restore => {
-                                    ScanChange::evaluate_restore(redirected.as_ref().unwrap_or(&original_path), &v.hash)
-                                }
+                                ScanKind::Restore => ScanChange::evaluate_restore(
+                                    redirected.as_ref().unwrap_or(&original_path),
+                                    &v.hash,
+                                    backup.hash_algorithm,
+                                ),
                             },
                             size: v.size,
                             hash: v.hash.clone(),
@@ -824,6 +1657,10 @@ impl GameLayout {
                             redirected,
                             original_path: Some(original_path),
                             container: Some(self.path.joined(&backup.name)),
+                            excluded: None,
+                            symlink: None,
+                            hardlink: backup.hardlinks.get(mapping_key).cloned().map(HardlinkTarget),
+                            conflict: false,
                         },
                     );
                 }
@@ -872,7 +1709,11 @@ impl GameLayout {
                         original_path,
                         ignored: false,
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 );
             }
@@ -880,7 +1721,6 @@ impl GameLayout {
         files
     }
 
-    #[cfg_attr(not(target_os = "windows"), allow(unused))]
     pub fn registry_content(&self, id: &BackupId) -> Option<registry::Hives> {
         match self.find_by_id(id) {
             None => None,
@@ -912,11 +1752,16 @@ impl GameLayout {
                 None
             }
             BackupFormat::Zip => {
+                let password = self.zip_password_for(backup).ok()?;
                 let handle = self.path.joined(backup).open().ok()?;
                 let mut archive = zip::ZipArchive::new(handle).ok()?;
 
                 for format in registry::Format::ALL {
-                    if let Ok(mut file) = archive.by_name(format.filename()) {
+                    let file = match password {
+                        Some(password) => archive.by_name_decrypt(format.filename(), password.as_bytes()),
+                        None => archive.by_name(format.filename()),
+                    };
+                    if let Ok(mut file) = file {
                         let mut buffer = vec![];
                         std::io::copy(&mut file, &mut buffer).ok()?;
                         let content = String::from_utf8(buffer).ok()?;
@@ -925,6 +1770,24 @@ impl GameLayout {
                     }
                 }
 
+                None
+            }
+            BackupFormat::Tar | BackupFormat::TarGz => {
+                use std::io::Read;
+
+                let mut archive = open_tar(&self.path.joined(backup), *format).ok()?;
+
+                for entry in archive.entries().ok()? {
+                    let mut entry = entry.ok()?;
+                    let name = String::from_utf8_lossy(&entry.path_bytes()).to_string();
+                    if let Some(format) = registry::Format::ALL.iter().find(|x| x.filename() == name) {
+                        let mut content = String::new();
+                        entry.read_to_string(&mut content).ok()?;
+
+                        return registry::Hives::deserialize(&content, *format);
+                    }
+                }
+
                 None
             }
         }
@@ -951,7 +1814,9 @@ impl GameLayout {
     ) -> String {
         if *kind == BackupKind::Full
             && retention.full == 1
+            && !retention.force_timestamped_name
             && format.chosen == BackupFormat::Simple
+            && !format.flat
             && self.mapping.backups.iter().all(|x| !x.locked)
         {
             SOLO.to_string()
@@ -961,9 +1826,9 @@ impl GameLayout {
                 BackupKind::Full => format!("backup-{}", timestamp),
                 BackupKind::Differential => format!("backup-{}-diff", timestamp),
             };
-            match format.chosen {
-                BackupFormat::Simple => name,
-                BackupFormat::Zip => format!("{name}.zip"),
+            match format.chosen.extension() {
+                None => name,
+                Some(extension) => format!("{name}.{extension}"),
             }
         }
     }
@@ -974,17 +1839,28 @@ impl GameLayout {
         now: &chrono::DateTime<chrono::Utc>,
         format: &BackupFormats,
         retention: Retention,
+        comment: Option<&str>,
     ) -> Option<Backup> {
         if !scan.found_anything_processable() && !retention.force_new_full {
             return None;
         }
 
-        let kind = self.plan_backup_kind(retention);
+        // Hashes from different algorithms can't be compared,
+        // so switching algorithms requires a new full backup.
+        let kind = if self
+            .mapping
+            .latest_backup()
+            .is_some_and(|(full, _)| full.hash_algorithm != scan.hash_algorithm)
+        {
+            BackupKind::Full
+        } else {
+            self.plan_backup_kind(retention)
+        };
 
         let backup = match kind {
-            BackupKind::Full => Backup::Full(self.plan_full_backup(scan, now, format, retention)),
+            BackupKind::Full => Backup::Full(self.plan_full_backup(scan, now, format, retention, comment)),
             BackupKind::Differential => {
-                Backup::Differential(self.plan_differential_backup(scan, now, format, retention))
+                Backup::Differential(self.plan_differential_backup(scan, now, format, retention, comment))
             }
         };
 
@@ -1017,41 +1893,66 @@ impl GameLayout {
         now: &chrono::DateTime<chrono::Utc>,
         format: &BackupFormats,
         retention: Retention,
+        comment: Option<&str>,
     ) -> FullBackup {
         let mut files = BTreeMap::new();
+        let mut symlinks = BTreeMap::new();
+        let mut hardlinks = BTreeMap::new();
         #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
         let mut registry = IndividualMappingRegistry::default();
 
         for (scan_key, file) in scan.found_files.iter().filter(|(_, x)| !x.ignored) {
             match file.change() {
                 ScanChange::New | ScanChange::Different | ScanChange::Same => {
+                    if let Some(symlink) = file.symlink.as_ref().filter(|_| format.chosen == BackupFormat::Simple) {
+                        symlinks.insert(file.mapping_key(scan_key), symlink.0.clone());
+                    }
+                    if let Some(hardlink) = file.hardlink.as_ref() {
+                        hardlinks.insert(file.mapping_key(scan_key), hardlink.0.clone());
+                    }
+                    let object = format.dedupe && format.chosen == BackupFormat::Simple;
                     files.insert(
                         file.mapping_key(scan_key),
                         IndividualMappingFile {
                             hash: file.hash.clone(),
                             size: file.size,
+                            base: None,
+                            object,
+                            mtime: Self::object_mtime(object, scan_key),
                         },
                     );
                 }
                 ScanChange::Removed | ScanChange::Unknown => (),
             }
         }
+        // A link is only useful if the file it points to was backed up too.
+        hardlinks.retain(|_, primary| files.contains_key(primary));
 
         #[cfg(target_os = "windows")]
         {
             use crate::scan::registry::Hives;
             let mut hives = Hives::default();
             let _ = hives.back_up(&scan.game_name, &scan.found_registry_keys);
-            registry.hash = hives.sha1(registry::Format::Reg);
+            registry.hash = hives.hash(registry::Format::Reg, scan.hash_algorithm);
         }
 
+        let name = self.generate_backup_name(&BackupKind::Full, now, format, retention);
+        let flat = IndividualMapping::can_store_flat(format, &name, files.keys());
+
         FullBackup {
-            name: self.generate_backup_name(&BackupKind::Full, now, format, retention),
+            name,
             when: *now,
             os: Some(Os::HOST),
-            comment: None,
+            comment: comment.and_then(|x| render_backup_comment(x, &scan.game_name, now, files.len())),
             locked: false,
+            checksum: None,
+            encryption: None,
+            hash_algorithm: scan.hash_algorithm,
+            duration_ms: None,
+            flat,
             files,
+            symlinks,
+            hardlinks,
             registry,
             children: VecDeque::new(),
         }
@@ -1063,6 +1964,7 @@ impl GameLayout {
         now: &chrono::DateTime<chrono::Utc>,
         format: &BackupFormats,
         retention: Retention,
+        comment: Option<&str>,
     ) -> DifferentialBackup {
         let mut files = BTreeMap::new();
         #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
@@ -1071,11 +1973,15 @@ impl GameLayout {
         for (scan_key, file) in &scan.found_files {
             match file.change() {
                 ScanChange::New | ScanChange::Different | ScanChange::Same => {
+                    let object = format.dedupe && format.chosen == BackupFormat::Simple;
                     files.insert(
                         file.mapping_key(scan_key),
                         (!file.ignored).then(|| IndividualMappingFile {
                             hash: file.hash.clone(),
                             size: file.size,
+                            base: None,
+                            object,
+                            mtime: Self::object_mtime(object, scan_key),
                         }),
                     );
                 }
@@ -1093,7 +1999,7 @@ impl GameLayout {
             let _ = hives.back_up(&scan.game_name, &scan.found_registry_keys);
             if !hives.is_empty() {
                 registry = Some(IndividualMappingRegistry {
-                    hash: hives.sha1(registry::Format::Reg),
+                    hash: hives.hash(registry::Format::Reg, scan.hash_algorithm),
                 });
             }
         }
@@ -1101,7 +2007,12 @@ impl GameLayout {
         // Individual saves' ScanChange are relative to the latest full + differential composite.
         // If the latest full backup has file 1 version 1, the latest diff has file 1 version 2,
         // and our new scan is back to version 1, then we don't want to duplicate the file content.
-        if let Some((full, _)) = self.mapping.latest_backup() {
+        // This only works when both sides were hashed with the same algorithm.
+        if let Some((full, _)) = self
+            .mapping
+            .latest_backup()
+            .filter(|(full, _)| full.hash_algorithm == scan.hash_algorithm)
+        {
             for (file, prior) in &full.files {
                 if let Some(current) = files.get(file) {
                     if Some(&prior.hash) == current.as_ref().map(|x| &x.hash) {
@@ -1118,14 +2029,73 @@ impl GameLayout {
             }
         }
 
+        // Changed files that also exist in the full backup can be stored as deltas against it.
+        if format.delta && format.chosen == BackupFormat::Simple {
+            if let Some((full, _)) = self
+                .mapping
+                .latest_backup()
+                .filter(|(full, _)| full.format() == BackupFormat::Simple)
+            {
+                for (file, current) in &mut files {
+                    if let Some(current) = current {
+                        // Deltas need a copy in the full backup's own folder.
+                        if full.files.get(file).is_some_and(|x| !x.object) {
+                            current.base = Some(full.name.clone());
+                            current.object = false;
+                            current.mtime = None;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Only files stored in this backup need their links recorded here.
+        // Inherited files use the full backup's record.
+        let symlinks = if format.chosen == BackupFormat::Simple {
+            scan.found_files
+                .iter()
+                .filter_map(|(scan_key, file)| {
+                    let key = file.mapping_key(scan_key);
+                    let symlink = file.symlink.as_ref()?;
+                    matches!(files.get(&key), Some(Some(_))).then(|| (key, symlink.0.clone()))
+                })
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        let hardlinks = scan
+            .found_files
+            .iter()
+            .filter_map(|(scan_key, file)| {
+                let key = file.mapping_key(scan_key);
+                let hardlink = file.hardlink.as_ref()?;
+                (matches!(files.get(&key), Some(Some(_))) && !matches!(files.get(&hardlink.0), Some(None)))
+                    .then(|| (key, hardlink.0.clone()))
+            })
+            .collect();
+
+        let count = files.values().filter(|x| x.is_some()).count();
+
+        let name = self.generate_backup_name(&BackupKind::Differential, now, format, retention);
+        let flat =
+            IndividualMapping::can_store_flat(format, &name, files.iter().filter(|(_, x)| x.is_some()).map(|(k, _)| k));
+
         DifferentialBackup {
-            name: self.generate_backup_name(&BackupKind::Differential, now, format, retention),
+            name,
             when: *now,
             os: Some(Os::HOST),
-            comment: None,
+            comment: comment.and_then(|x| render_backup_comment(x, &scan.game_name, now, count)),
             locked: false,
+            checksum: None,
+            encryption: None,
+            hash_algorithm: scan.hash_algorithm,
+            duration_ms: None,
+            flat,
             files,
+            symlinks,
+            hardlinks,
             registry,
+            inherited_size: 0,
         }
     }
 
@@ -1133,15 +2103,81 @@ impl GameLayout {
         let mut backup_info = BackupInfo::default();
 
         let mut relevant_files = vec![];
-        for (scan_key, file) in &scan.found_files {
+        let mut permissions = FilePermissions::default();
+
+        // Hardlinked files are handled after the files they point to,
+        // so that they're only recorded in the mapping if the other file was stored.
+        let mut found_files: Vec<_> = scan.found_files.iter().collect();
+        found_files.sort_by_key(|(_, file)| file.hardlink.is_some());
+
+        for (scan_key, file) in found_files {
+            if self.cancelled_for(backup) {
+                return backup_info;
+            }
             if !backup.includes_file(file.mapping_key(scan_key)) {
                 log::debug!("[{}] skipped: {}", self.mapping.name, scan_key.raw());
                 continue;
             }
 
-            let target_file = self
-                .mapping
-                .game_file(&self.path, file.effective(scan_key), backup.name());
+            if let Some(primary) = backup
+                .shared_hardlink(&file.mapping_key(scan_key))
+                .filter(|primary| !backup_info.failed_files.keys().any(|x| x.raw() == *primary))
+            {
+                log::info!(
+                    "[{}] recorded as hardlink: {:?} -> {}",
+                    self.mapping.name,
+                    scan_key,
+                    primary
+                );
+                permissions.record(file.mapping_key(scan_key), scan_key);
+                continue;
+            }
+
+            if let Some(hash) = backup.object_hash(&file.mapping_key(scan_key)) {
+                let object_file = self.object_file(hash);
+                if let Err(e) = self.back_up_file_as_object(scan_key, &object_file, self.cancel_flag(backup)) {
+                    backup_info
+                        .failed_files
+                        .insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                    continue;
+                }
+                log::info!(
+                    "[{}] backed up as object: {:?} -> {:?}",
+                    self.mapping.name,
+                    scan_key,
+                    object_file
+                );
+                permissions.record(file.mapping_key(scan_key), scan_key);
+                continue;
+            }
+
+            // The new backup isn't in the mapping yet, so we check its layout directly.
+            let target_file = if backup.flat() {
+                IndividualMapping::flat_game_file(&self.path, file.effective(scan_key), backup.name())
+            } else {
+                self.mapping
+                    .game_file(&self.path, file.effective(scan_key), backup.name())
+            };
+
+            if let Some(base) = backup.delta_base(&file.mapping_key(scan_key)) {
+                let base_file = self.mapping.game_file(&self.path, file.effective(scan_key), base);
+                if let Err(e) = self.back_up_file_as_delta(scan_key, &base_file, &target_file) {
+                    backup_info
+                        .failed_files
+                        .insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                    continue;
+                }
+                log::info!(
+                    "[{}] backed up as delta: {:?} -> {:?}",
+                    self.mapping.name,
+                    scan_key,
+                    target_file
+                );
+                permissions.record(file.mapping_key(scan_key), scan_key);
+                relevant_files.push(target_file);
+                continue;
+            }
+
             if scan_key.same_content(&target_file) {
                 log::info!(
                     "[{}] already matches: {:?} -> {:?}",
@@ -1149,40 +2185,212 @@ impl GameLayout {
                     &scan_key,
                     &target_file
                 );
+                // The content may match even though the live file was touched since.
+                if let Err(e) = scan_key.get_mtime().and_then(|mtime| target_file.set_mtime(mtime)) {
+                    log::warn!(
+                        "[{}] unable to update modification time: {:?} -> {:?} | {e}",
+                        self.mapping.name,
+                        &scan_key,
+                        &target_file
+                    );
+                }
+                permissions.record(file.mapping_key(scan_key), scan_key);
                 relevant_files.push(target_file);
                 continue;
             }
-            if let Err(e) = scan_key.copy_to_path(&self.mapping.name, &target_file) {
+            if let Err(e) = scan_key.copy_to_path(&self.mapping.name, &target_file, self.cancel_flag(backup)) {
                 backup_info
                     .failed_files
                     .insert(scan_key.clone(), BackupError::Raw(e.to_string()));
                 continue;
             }
             log::info!("[{}] backed up: {:?} -> {:?}", self.mapping.name, scan_key, target_file);
+            permissions.record(file.mapping_key(scan_key), scan_key);
             relevant_files.push(target_file);
         }
 
-        #[cfg(target_os = "windows")]
-        {
-            if backup.includes_registry() {
+        if !permissions.0.is_empty() {
+            let target_permissions_file = self.path.joined(backup.name()).joined(PERMISSIONS_FILE);
+            match permissions.save(&target_permissions_file) {
+                Ok(_) => relevant_files.push(target_permissions_file),
+                Err(e) => log::warn!(
+                    "[{}] unable to save permissions: {:?} | {e}",
+                    self.mapping.name,
+                    &target_permissions_file
+                ),
+            }
+        }
+
+        if backup.includes_registry() {
+            if let Some(hives) = Self::registry_for_backup(scan, &mut backup_info) {
                 let target_registry_file = self.path.joined(backup.name()).joined(registry::Format::Reg.filename());
-                let mut hives = registry::Hives::default();
-                if let Err(failed) = hives.back_up(&scan.game_name, &scan.found_registry_keys) {
-                    backup_info.failed_registry.extend(failed);
-                }
                 hives.save(&target_registry_file);
                 relevant_files.push(target_registry_file);
             }
         }
 
         if backup.full() && backup.name() == SOLO {
-            self.remove_irrelevant_backup_files(backup.name(), &relevant_files);
+            for file in self.remove_irrelevant_backup_files(backup.name(), &relevant_files) {
+                backup_info
+                    .warnings
+                    .push(OperationWarning::UnremovableBackupFile { path: file.render() });
+            }
             self.remove_empty_backup_subdirs(backup.name());
         }
 
         backup_info
     }
 
+    /// Registry content to store in a new backup.
+    /// When merging, the scan comes from an existing backup,
+    /// so we reuse its content instead of reading the live registry.
+    fn registry_for_backup(
+        scan: &ScanInfo,
+        #[cfg_attr(not(target_os = "windows"), allow(unused))] backup_info: &mut BackupInfo,
+    ) -> Option<registry::Hives> {
+        if scan.backup.is_some() {
+            return scan.dumped_registry.clone();
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            let mut hives = registry::Hives::default();
+            if let Err(failed) = hives.back_up(&scan.game_name, &scan.found_registry_keys) {
+                backup_info.failed_registry.extend(failed);
+            }
+            Some(hives)
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        None
+    }
+
+    fn back_up_file_as_delta(
+        &self,
+        source: &StrictPath,
+        base: &StrictPath,
+        target: &StrictPath,
+    ) -> Result<(), AnyError> {
+        log::trace!(
+            "[{}] about to back up as delta: {:?} -> {:?} (base: {:?})",
+            self.mapping.name,
+            source,
+            target,
+            base
+        );
+
+        target.create_parent_dir()?;
+        target.unset_readonly()?;
+        delta::encode(
+            std::io::BufReader::new(base.open()?),
+            source.open()?,
+            std::io::BufWriter::new(target.create()?),
+        )?;
+        target.set_mtime(source.get_mtime()?)?;
+
+        Ok(())
+    }
+
+    fn back_up_file_as_object(
+        &self,
+        source: &StrictPath,
+        target: &StrictPath,
+        cancel: Option<&AtomicBool>,
+    ) -> Result<(), AnyError> {
+        if target.is_file() {
+            log::trace!("[{}] object already stored: {:?}", self.mapping.name, target);
+            return Ok(());
+        }
+
+        // Other games may be storing the same content at the same time,
+        // so we copy to a name that's unique to this game and then move it into place.
+        let staging = target.popped().joined(&format!(
+            "{}.{}.tmp",
+            target.leaf().unwrap_or_default(),
+            self.path.leaf().unwrap_or_default()
+        ));
+        source.copy_to_path(&self.mapping.name, &staging, cancel)?;
+        if let Err(e) = staging.move_to(target) {
+            let _ = staging.remove();
+            if !target.is_file() {
+                return Err(Box::new(e));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shared folder for deduplicated file content across all games.
+    fn object_store(&self) -> StrictPath {
+        match &self.root {
+            Some(root) => root.joined(OBJECTS_FOLDER),
+            None => self.path.popped().joined(OBJECTS_FOLDER),
+        }
+    }
+
+    fn object_file(&self, hash: &str) -> StrictPath {
+        self.object_store().joined(hash)
+    }
+
+    /// Objects are shared, so their own modification time may belong to another game's copy.
+    fn object_mtime(object: bool, source: &StrictPath) -> Option<chrono::DateTime<chrono::Utc>> {
+        if !object {
+            return None;
+        }
+        source.get_mtime().ok().map(chrono::DateTime::<chrono::Utc>::from)
+    }
+
+    /// Where a file's content is kept in a simple backup.
+    /// `shared` is the file whose stored copy this one shares as a hardlink, if any.
+    fn stored_file(
+        &self,
+        backup: &str,
+        original_path: &StrictPath,
+        info: &IndividualMappingFile,
+        shared: Option<&str>,
+    ) -> StrictPath {
+        if info.object {
+            self.object_file(&info.hash)
+        } else if let Some(shared) = shared {
+            self.mapping
+                .game_file_immutable(&self.path, &StrictPath::new(shared.to_string()), backup)
+        } else {
+            self.mapping.game_file_immutable(&self.path, original_path, backup)
+        }
+    }
+
+    /// Where a file's content is kept in the shared object folder, if it is.
+    /// For differential backups, this accounts for files inherited from the full backup.
+    fn stored_object(&self, backup: &Backup, original_path: &str) -> Option<StrictPath> {
+        let info = self.stored_info(backup, original_path)?;
+        info.object.then(|| self.object_file(&info.hash))
+    }
+
+    /// Where a file's content is kept if it shares the stored copy of another file as a hardlink.
+    /// For differential backups, this accounts for files inherited from the full backup.
+    fn stored_hardlink(&self, backup: &Backup, original_path: &str) -> Option<StrictPath> {
+        let (full, diff) = self.find_by_id(&backup.id())?;
+        let (name, shared) = match diff.filter(|diff| diff.files.contains_key(original_path)) {
+            Some(diff) => (&diff.name, diff.shared_hardlink(original_path)?),
+            None => (&full.name, full.shared_hardlink(original_path)?),
+        };
+        Some(
+            self.mapping
+                .game_file_immutable(&self.path, &StrictPath::new(shared.to_string()), name),
+        )
+    }
+
+    /// A file's entry in the mapping.
+    /// For differential backups, this accounts for files inherited from the full backup.
+    fn stored_info(&self, backup: &Backup, original_path: &str) -> Option<&IndividualMappingFile> {
+        let (full, diff) = self.find_by_id(&backup.id())?;
+        match diff.and_then(|diff| diff.files.get(original_path)) {
+            Some(Some(info)) => Some(info),
+            Some(None) => None,
+            None => full.files.get(original_path),
+        }
+    }
+
     fn execute_backup_as_zip(&mut self, backup: &Backup, scan: &ScanInfo, format: &BackupFormats) -> BackupInfo {
         let mut backup_info = BackupInfo::default();
 
@@ -1211,17 +2419,26 @@ impl GameLayout {
             }
         };
         let mut zip = zip::ZipWriter::new(archive_file);
-        let options = zip::write::FileOptions::default()
+        let password = self.zip_password.clone().unwrap_or_default();
+        let options = zip::write::SimpleFileOptions::default()
             .compression_method(match format.zip.compression {
                 ZipCompression::None => zip::CompressionMethod::Stored,
                 ZipCompression::Deflate => zip::CompressionMethod::Deflated,
                 ZipCompression::Bzip2 => zip::CompressionMethod::Bzip2,
                 ZipCompression::Zstd => zip::CompressionMethod::Zstd,
             })
-            .compression_level(format.level())
+            .compression_level(format.validated_level().map(i64::from))
             .large_file(true);
+        let options = match backup.encryption() {
+            Some(encryption) => options.with_aes_encryption(encryption.aes_mode(), &password),
+            None => options,
+        };
 
         'item: for (scan_key, file) in &scan.found_files {
+            if self.cancelled() {
+                fail_all(&mut backup_info, cancelled_error().to_string());
+                return backup_info;
+            }
             if !backup.includes_file(file.mapping_key(scan_key)) {
                 log::debug!("[{}] skipped: {:?}", self.mapping.name, &scan_key);
                 continue;
@@ -1307,18 +2524,21 @@ impl GameLayout {
                     fail_file(scan_key, &mut backup_info, e.to_string());
                     continue 'item;
                 }
+                if self.cancelled() {
+                    log::info!(
+                        "[{}] cancelled backup: {:?} -> {}",
+                        self.mapping.name,
+                        &scan_key,
+                        &target_file_id
+                    );
+                    fail_all(&mut backup_info, cancelled_error().to_string());
+                    return backup_info;
+                }
             }
         }
 
-        #[cfg(target_os = "windows")]
-        {
-            use crate::scan::registry::Hives;
-
-            if backup.includes_registry() {
-                let mut hives = Hives::default();
-                if let Err(failed) = hives.back_up(&scan.game_name, &scan.found_registry_keys) {
-                    backup_info.failed_registry.extend(failed);
-                }
+        if backup.includes_registry() {
+            if let Some(hives) = Self::registry_for_backup(scan, &mut backup_info) {
                 let format = registry::Format::Reg;
                 if zip.start_file(format.filename(), options).is_ok() {
                     let _ = zip.write_all(hives.serialize(format).as_bytes());
@@ -1333,23 +2553,192 @@ impl GameLayout {
         backup_info
     }
 
-    fn insert_backup(&mut self, backup: Backup) {
-        match backup {
-            Backup::Full(backup) => {
-                self.mapping.backups.push_back(backup);
+    fn execute_backup_as_tar(&mut self, backup: &Backup, scan: &ScanInfo, format: &BackupFormats) -> BackupInfo {
+        let mut backup_info = BackupInfo::default();
+
+        let fail_all = |backup_info: &mut BackupInfo, error: String| {
+            for file in scan.found_files.keys() {
+                backup_info
+                    .failed_files
+                    .insert(file.clone(), BackupError::Raw(error.clone()));
             }
-            Backup::Differential(backup) => {
-                if let Some(parent) = self.mapping.backups.back_mut() {
-                    parent.children.push_back(backup);
-                }
+        };
+
+        let archive_path = self.path.joined(backup.name());
+        let archive_file = match archive_path.create() {
+            Ok(x) => x,
+            Err(e) => {
+                log::error!(
+                    "[{}] unable to create tar file: {:?} | {e}",
+                    self.mapping.name,
+                    &archive_path
+                );
+                fail_all(&mut backup_info, e.to_string());
+                return backup_info;
             }
-        }
-    }
+        };
 
-    fn forget_excess_backups(&mut self, retention: Retention) {
-        // We need to track by index rather than by ID.
-        // If we're merging into a single existing backup (like the special ID `.`),
-        // then we may have two of them before pruning the older one.
+        let finished = match format.chosen {
+            BackupFormat::TarGz => {
+                let level = format.validated_level().unwrap_or(6) as u32;
+                let encoder = flate2::write::GzEncoder::new(archive_file, flate2::Compression::new(level));
+                self.fill_tar(backup, scan, encoder, &mut backup_info)
+                    .and_then(|encoder| encoder.finish())
+                    .map(|_| ())
+            }
+            _ => self.fill_tar(backup, scan, archive_file, &mut backup_info).map(|_| ()),
+        };
+
+        if let Err(e) = finished {
+            log::error!(
+                "[{}] unable to finish tar file: {:?} | {e}",
+                self.mapping.name,
+                &archive_path
+            );
+            fail_all(&mut backup_info, e.to_string());
+        }
+
+        backup_info
+    }
+
+    fn fill_tar<W: Write>(
+        &mut self,
+        backup: &Backup,
+        scan: &ScanInfo,
+        writer: W,
+        backup_info: &mut BackupInfo,
+    ) -> std::io::Result<W> {
+        let mut tar = tar::Builder::new(writer);
+
+        for (scan_key, file) in &scan.found_files {
+            if self.cancelled() {
+                return Err(cancelled_error());
+            }
+            if !backup.includes_file(file.mapping_key(scan_key)) {
+                log::debug!("[{}] skipped: {:?}", self.mapping.name, &scan_key);
+                continue;
+            }
+
+            let target_file_id = self.mapping.game_file_for_zip(file.effective(scan_key));
+
+            let handle = match scan_key.open() {
+                Ok(x) => x,
+                Err(e) => {
+                    log::error!("[{}] unable to open source: {:?} | {e}", self.mapping.name, &scan_key);
+                    backup_info
+                        .failed_files
+                        .insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                    continue;
+                }
+            };
+            let metadata = match handle.metadata() {
+                Ok(x) => x,
+                Err(e) => {
+                    log::error!("[{}] unable to read metadata: {:?} | {e}", self.mapping.name, &scan_key);
+                    backup_info
+                        .failed_files
+                        .insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                    continue;
+                }
+            };
+
+            let mut header = tar::Header::new_gnu();
+            header.set_size(metadata.len());
+            header.set_mtime(
+                metadata
+                    .modified()
+                    .ok()
+                    .and_then(|x| x.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|x| x.as_secs())
+                    .unwrap_or_default(),
+            );
+            #[cfg(target_os = "windows")]
+            header.set_mode(0o644);
+            #[cfg(not(target_os = "windows"))]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                header.set_mode(metadata.permissions().mode());
+            }
+
+            if let Err(e) = tar.append_data(&mut header, &target_file_id, std::io::BufReader::new(handle)) {
+                log::error!(
+                    "[{}] unable to write target: {:?} -> {} | {e}",
+                    self.mapping.name,
+                    &scan_key,
+                    &target_file_id
+                );
+                backup_info
+                    .failed_files
+                    .insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                continue;
+            }
+
+            log::info!(
+                "[{}] backed up: {:?} -> {}",
+                self.mapping.name,
+                &scan_key,
+                &target_file_id
+            );
+        }
+
+        if backup.includes_registry() {
+            if let Some(hives) = Self::registry_for_backup(scan, backup_info) {
+                let format = registry::Format::Reg;
+                let content = hives.serialize(format);
+                let mut header = tar::Header::new_gnu();
+                header.set_size(content.len() as u64);
+                header.set_mode(0o644);
+                let _ = tar.append_data(&mut header, format.filename(), content.as_bytes());
+            }
+        }
+
+        tar.into_inner()
+    }
+
+    fn insert_backup(&mut self, backup: Backup) {
+        match backup {
+            Backup::Full(backup) => {
+                self.mapping.backups.push_back(backup);
+            }
+            Backup::Differential(backup) => {
+                if let Some(parent) = self.mapping.backups.back_mut() {
+                    parent.children.push_back(backup);
+                }
+            }
+        }
+    }
+
+    /// Find which backups would be forgotten by the retention limits, without changing anything.
+    /// Forgetting a full backup also forgets its differential backups.
+    pub fn plan_pruning(&self, retention: Retention, now: &chrono::DateTime<chrono::Utc>) -> Vec<BackupId> {
+        let excess = self.excess_backups(retention, now);
+        let mut ids = vec![];
+
+        for (i, full) in self.mapping.backups.iter().enumerate() {
+            let full_excess = excess.contains(&(i, None));
+            if full_excess {
+                ids.push(BackupId::Named(full.name.clone()));
+            }
+            for (j, diff) in full.children.iter().enumerate() {
+                if full_excess || excess.contains(&(i, Some(j))) {
+                    ids.push(BackupId::Named(diff.name.clone()));
+                }
+            }
+        }
+
+        ids
+    }
+
+    /// Indices of backups beyond the retention limits,
+    /// as `(full, None)` for a full backup or `(full, Some(diff))` for a differential one.
+    fn excess_backups(&self, retention: Retention, now: &chrono::DateTime<chrono::Utc>) -> Vec<(usize, Option<usize>)> {
+        match retention.policy {
+            RetentionPolicy::Count => self.excess_backups_by_count(retention),
+            RetentionPolicy::Age => self.excess_backups_by_age(retention.age, now),
+        }
+    }
+
+    fn excess_backups_by_count(&self, retention: Retention) -> Vec<(usize, Option<usize>)> {
         let mut excess = vec![];
 
         let unlocked_fulls = self
@@ -1360,7 +2749,7 @@ impl GameLayout {
             .count();
         let mut excess_fulls = unlocked_fulls.saturating_sub(retention.full as usize);
 
-        for (i, full) in self.mapping.backups.iter_mut().enumerate() {
+        for (i, full) in self.mapping.backups.iter().enumerate() {
             let locked = full.locked || full.children.iter().any(|diff| diff.locked);
             if !locked && excess_fulls > 0 {
                 excess.push((i, None));
@@ -1370,7 +2759,7 @@ impl GameLayout {
             let unlocked_diffs = full.children.iter().filter(|diff| !diff.locked).count();
             let mut excess_diffs = unlocked_diffs.saturating_sub(retention.differential as usize);
 
-            for (j, diff) in full.children.iter_mut().enumerate() {
+            for (j, diff) in full.children.iter().enumerate() {
                 let locked = diff.locked;
                 if !locked && excess_diffs > 0 {
                     excess.push((i, Some(j)));
@@ -1379,6 +2768,61 @@ impl GameLayout {
             }
         }
 
+        excess
+    }
+
+    /// Full and differential backups are judged alike by their age,
+    /// but a full backup is kept as long as any of its differential backups are kept.
+    /// The newest backup is always kept, even if it is older than all of the windows.
+    fn excess_backups_by_age(
+        &self,
+        age: AgeRetention,
+        now: &chrono::DateTime<chrono::Utc>,
+    ) -> Vec<(usize, Option<usize>)> {
+        let mut times = vec![];
+        for (i, full) in self.mapping.backups.iter().enumerate() {
+            times.push(((i, None), full.when));
+            for (j, diff) in full.children.iter().enumerate() {
+                times.push(((i, Some(j)), diff.when));
+            }
+        }
+        let mut kept = retained_by_age(&times, age, now);
+
+        // Otherwise, a game that hasn't been played in a while would lose all of its backups.
+        if let Some(i) = self.mapping.backups.len().checked_sub(1) {
+            kept.insert((i, None));
+            if let Some(j) = self.mapping.backups[i].children.len().checked_sub(1) {
+                kept.insert((i, Some(j)));
+            }
+        }
+
+        let mut excess = vec![];
+        for (i, full) in self.mapping.backups.iter().enumerate() {
+            let diff_needed = |j: usize, diff: &DifferentialBackup| diff.locked || kept.contains(&(i, Some(j)));
+
+            let full_needed = full.locked
+                || kept.contains(&(i, None))
+                || full.children.iter().enumerate().any(|(j, diff)| diff_needed(j, diff));
+            if !full_needed {
+                excess.push((i, None));
+            }
+
+            for (j, diff) in full.children.iter().enumerate() {
+                if !diff_needed(j, diff) {
+                    excess.push((i, Some(j)));
+                }
+            }
+        }
+
+        excess
+    }
+
+    fn forget_excess_backups(&mut self, retention: Retention, now: &chrono::DateTime<chrono::Utc>) {
+        // We need to track by index rather than by ID.
+        // If we're merging into a single existing backup (like the special ID `.`),
+        // then we may have two of them before pruning the older one.
+        let mut excess = self.excess_backups(retention, now);
+
         log::debug!("[{}] Excess backups: {:?}", &self.mapping.name, excess);
 
         if !excess.is_empty() {
@@ -1403,10 +2847,40 @@ impl GameLayout {
             match format.chosen {
                 BackupFormat::Simple => self.execute_backup_as_simple(backup, scan),
                 BackupFormat::Zip => self.execute_backup_as_zip(backup, scan, format),
+                BackupFormat::Tar | BackupFormat::TarGz => self.execute_backup_as_tar(backup, scan, format),
             }
         }
     }
 
+    fn cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|x| x.load(Ordering::Relaxed))
+    }
+
+    /// The cancellation flag that applies while writing this backup.
+    /// A single simple backup is updated in place, so stopping partway would leave
+    /// a mix of old and new files that doesn't match the mapping.
+    /// Once started, it always runs to completion, and the cancellation applies to the next game.
+    fn cancel_flag(&self, backup: &Backup) -> Option<&AtomicBool> {
+        self.cancel.as_deref().filter(|_| backup.name() != SOLO)
+    }
+
+    fn cancelled_for(&self, backup: &Backup) -> bool {
+        self.cancel_flag(backup).is_some_and(|x| x.load(Ordering::Relaxed))
+    }
+
+    /// Remove whatever a cancelled backup managed to write, so it isn't mistaken for a valid one.
+    /// The backup is never added to the mapping.
+    fn discard_cancelled_backup(&self, backup: &Backup) {
+        let target = self.path.joined(backup.name());
+        if let Err(e) = target.remove() {
+            log::warn!(
+                "[{}] unable to remove cancelled backup: {:?} | {e}",
+                &self.mapping.name,
+                &target
+            );
+        }
+    }
+
     fn prune_irrelevant_parents(&self) {
         for irrelevant_parent in self.mapping.irrelevant_parents(&self.path) {
             log::debug!(
@@ -1445,6 +2919,9 @@ impl GameLayout {
                 IndividualMappingFile {
                     hash: scan_key.sha1(),
                     size: scan_key.size(),
+                    base: None,
+                    object: false,
+                    mtime: None,
                 },
             );
         }
@@ -1487,8 +2964,16 @@ impl GameLayout {
             os,
             comment,
             locked,
+            checksum,
+            encryption,
+            hash_algorithm,
+            duration_ms,
+            flat,
             files,
+            symlinks,
+            hardlinks,
             registry,
+            inherited_size: _,
         } = initial.children.pop_front()?;
 
         initial.name = name;
@@ -1496,7 +2981,14 @@ impl GameLayout {
         initial.os = os;
         initial.comment = comment;
         initial.locked = initial.locked || locked;
+        initial.checksum = checksum;
+        initial.encryption = encryption;
+        initial.hash_algorithm = hash_algorithm;
+        initial.duration_ms = duration_ms;
+        initial.flat = flat;
         initial.files = files.into_iter().filter_map(|(k, v)| Some((k, v?))).collect();
+        initial.symlinks = symlinks;
+        initial.hardlinks = hardlinks;
         if let Some(registry) = registry {
             initial.registry = registry;
         }
@@ -1508,6 +3000,66 @@ impl GameLayout {
         Some(())
     }
 
+    /// Move a single full backup out of the game folder and into its own timestamped folder,
+    /// so that the `.` name from the mapping file is never synchronized to the cloud.
+    /// Afterward, it is restored the same way as any other full backup.
+    pub fn timestamp_solo_backup(&mut self) -> std::io::Result<bool> {
+        let Some(index) = self.mapping.backups.iter().position(|x| x.name == SOLO) else {
+            return Ok(false);
+        };
+
+        let name = format!(
+            "backup-{}",
+            Self::generate_file_friendly_timestamp(&self.mapping.backups[index].when)
+        );
+        let target = self.path.joined(&name);
+        log::info!(
+            "[{}] moving single full backup for cloud sync: {:?}",
+            &self.mapping.name,
+            &target
+        );
+        target.create_dirs()?;
+
+        // A single full backup stores its registry, permissions, and `drive-*` folders
+        // directly in the game folder, alongside the mapping file.
+        let mut stored: Vec<_> = registry::Format::ALL
+            .iter()
+            .map(|format| format.filename())
+            .chain([PERMISSIONS_FILE])
+            .map(|file| self.path.joined(file))
+            .filter(|file| file.is_file())
+            .collect();
+        for child in walkdir::WalkDir::new(self.path.as_std_path_buf()?)
+            .min_depth(1)
+            .max_depth(1)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|x| crate::scan::filter_map_walkdir(&self.mapping.name, x))
+            .filter(|x| x.file_name().to_string_lossy().starts_with("drive-"))
+        {
+            stored.push(StrictPath::from(&child));
+        }
+
+        for source in stored {
+            if let Some(leaf) = source.leaf() {
+                source.move_to(&target.joined(&leaf))?;
+            }
+        }
+
+        let full = &mut self.mapping.backups[index];
+        full.name = name.clone();
+        for diff in &mut full.children {
+            for file in diff.files.values_mut().flatten() {
+                if file.base.as_deref() == Some(SOLO) {
+                    file.base = Some(name.clone());
+                }
+            }
+        }
+        self.save();
+
+        Ok(true)
+    }
+
     pub fn back_up(
         &mut self,
         scan: &ScanInfo,
@@ -1515,6 +3067,8 @@ impl GameLayout {
         format: &BackupFormats,
         retention: Retention,
         only_constructive: bool,
+        checksum: bool,
+        comment: Option<&str>,
     ) -> Option<BackupInfo> {
         if !scan.found_anything() {
             log::trace!("[{}] nothing to back up", &scan.game_name);
@@ -1537,9 +3091,13 @@ impl GameLayout {
         }
 
         self.migrate_backups(true);
-        match self.plan_backup(scan, now, format, retention) {
+        match self.plan_backup(scan, now, format, retention, comment) {
             None => {
                 log::info!("[{}] no need for new backup", &scan.game_name);
+                // A mirror may have been added since the last backup.
+                if self.has_backups() && !self.mirrors.is_empty() {
+                    self.sync_mirrors();
+                }
                 None
             }
             Some(mut backup) => {
@@ -1549,85 +3107,583 @@ impl GameLayout {
                     backup.kind(),
                     backup.name()
                 );
-                let backup_info = self.execute_backup(&backup, scan, format);
+                if !backup.only_inherits_and_overrides() {
+                    match self.new_encryption(format) {
+                        Ok(encryption) => backup.set_encryption(encryption),
+                        Err(e) => return Some(BackupInfo::total_failure(scan, e)),
+                    }
+                }
+                let mut space_warning = None;
+                if let Some((required, available)) = self.insufficient_space(&backup, format) {
+                    log::warn!(
+                        "[{}] backup may not fit: {required} bytes needed, {available} bytes available",
+                        &scan.game_name
+                    );
+                    match self.space_check {
+                        SpaceCheck::Abort => {
+                            return Some(BackupInfo::total_failure(
+                                scan,
+                                BackupError::App(Error::InsufficientSpace {
+                                    path: self.path.clone(),
+                                    required,
+                                    available,
+                                }),
+                            ));
+                        }
+                        SpaceCheck::Warn | SpaceCheck::Off => {
+                            space_warning = Some(OperationWarning::InsufficientSpace { required, available });
+                        }
+                    }
+                }
+                let pre_hook_warning = self.hooks.run_pre(&scan.game_name, &self.path);
+                let started = std::time::Instant::now();
+                let mut backup_info = self.execute_backup(&backup, scan, format);
+                if self.cancelled_for(&backup) {
+                    log::info!("[{}] backup cancelled: {}", &scan.game_name, backup.name());
+                    self.discard_cancelled_backup(&backup);
+                    let _ = self.hooks.run_post(&scan.game_name, &self.path, false);
+                    return None;
+                }
+                backup.set_duration(started.elapsed());
+                backup_info.warnings.extend(space_warning);
+                backup_info.warnings.extend(pre_hook_warning);
                 backup.prune_failures(&backup_info);
                 if backup.needed() {
+                    if checksum {
+                        backup.set_checksum(self.compute_checksum(&backup));
+                    }
                     self.insert_backup(backup.clone());
-                    self.forget_excess_backups(retention);
+                    self.forget_excess_backups(retention, now);
                     self.save();
                 }
                 self.prune_irrelevant_parents();
+                if backup.needed() && backup_info.successful() && !self.mirrors.is_empty() {
+                    backup_info.mirrors = self.sync_mirrors();
+                    for (mirror, outcome) in &backup_info.mirrors {
+                        if let Err(e) = outcome {
+                            backup_info.warnings.push(OperationWarning::MirrorFailed {
+                                path: mirror.render(),
+                                message: e.message(),
+                            });
+                        }
+                    }
+                }
+                let post_hook_warning = self
+                    .hooks
+                    .run_post(&scan.game_name, &self.path, backup_info.successful());
+                backup_info.warnings.extend(post_hook_warning);
                 Some(backup_info)
             }
         }
     }
 
-    pub fn get_backups(&mut self) -> Vec<Backup> {
-        let mut available_backups = vec![];
+    /// Copy this game's backup folder, and any shared objects that it refers to,
+    /// to the same relative location under each mirror.
+    /// Anything that was removed from the backup folder is removed from the mirrors too.
+    fn sync_mirrors(&self) -> HashMap<StrictPath, Result<(), BackupError>> {
+        self.mirrors
+            .iter()
+            .map(|mirror| {
+                log::debug!("[{}] mirroring backup to: {:?}", &self.mapping.name, mirror);
+                let outcome = self.mirror_to(mirror).map_err(|e| {
+                    log::error!("[{}] failed to mirror backup: {:?} | {e}", &self.mapping.name, mirror);
+                    BackupError::Raw(e.to_string())
+                });
+                (mirror.clone(), outcome)
+            })
+            .collect()
+    }
 
-        if self.path.is_dir() {
-            self.migrate_backups(true);
-            available_backups = self.restorable_backups_flattened();
+    fn mirror_to(&self, mirror: &StrictPath) -> Result<(), AnyError> {
+        let root = self.root.clone().unwrap_or_else(|| self.path.popped());
+        let folder = self.path.interpret()?;
+        let relative = std::path::Path::new(&folder).strip_prefix(root.interpret()?)?;
+        let target = StrictPath::from(mirror.as_std_path_buf()?.join(relative));
+
+        Self::sync_folder(&self.path, &target)?;
+
+        let objects = mirror.joined(OBJECTS_FOLDER);
+        for hash in self.mapping.object_hashes() {
+            let object = objects.joined(hash);
+            if !object.is_file() {
+                object.create_parent_dir()?;
+                self.object_file(hash).copy_to(&object)?;
+            }
         }
 
-        available_backups
+        Ok(())
     }
 
-    pub fn has_backups(&self) -> bool {
-        !self.mapping.backups.is_empty()
+    /// Make `target` match `source` by copying any new or modified files
+    /// and removing anything that no longer exists in `source`.
+    fn sync_folder(source: &StrictPath, target: &StrictPath) -> Result<(), AnyError> {
+        let source = source.as_std_path_buf()?;
+        let target = target.as_std_path_buf()?;
+
+        for entry in walkdir::WalkDir::new(&source).follow_links(false) {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let from = StrictPath::from(&entry);
+            let to = StrictPath::from(target.join(entry.path().strip_prefix(&source)?));
+            let modified = match (from.get_mtime(), to.get_mtime()) {
+                (Ok(from_mtime), Ok(to_mtime)) => from_mtime > to_mtime || from.size() != to.size(),
+                _ => true,
+            };
+            if modified {
+                to.create_parent_dir()?;
+                from.copy_to(&to)?;
+                to.set_mtime(from.get_mtime()?)?;
+            }
+        }
+
+        if !target.exists() {
+            return Ok(());
+        }
+
+        for entry in walkdir::WalkDir::new(&target).follow_links(false).contents_first(true) {
+            let entry = entry?;
+            if source.join(entry.path().strip_prefix(&target)?).exists() {
+                continue;
+            }
+            if entry.file_type().is_dir() {
+                std::fs::remove_dir_all(entry.path())?;
+            } else {
+                std::fs::remove_file(entry.path())?;
+            }
+        }
+
+        Ok(())
     }
 
-    pub fn scan_for_restoration(
-        &mut self,
-        name: &str,
-        id: &BackupId,
-        redirects: &[RedirectConfig],
-        reverse_redirects_on_restore: bool,
-        toggled_paths: &ToggledPaths,
-        #[cfg_attr(not(target_os = "windows"), allow(unused))] toggled_registry: &ToggledRegistry,
-    ) -> ScanInfo {
-        log::trace!("[{name}] beginning scan for restore");
+    /// Rough estimate of the space that a planned backup will take up.
+    /// Compressed archives are assumed to shrink somewhat.
+    fn estimate_backup_size(backup: &Backup, format: &BackupFormats) -> u64 {
+        const COMPRESSED_SIZE_RATIO: f64 = 0.8;
 
-        let mut found_files = HashMap::new();
-        #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
-        let mut found_registry_keys = HashMap::new();
-        let mut available_backups = vec![];
-        let mut backup = None;
-        #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
-        let mut dumped_registry = None;
+        let size = match backup {
+            Backup::Full(x) => x.total_size(),
+            Backup::Differential(x) => x.stored_size(),
+        };
 
-        let id = self.verify_id(id);
+        let compressed = match format.chosen {
+            BackupFormat::Simple | BackupFormat::Tar => false,
+            BackupFormat::Zip => format.zip.compression != ZipCompression::None,
+            BackupFormat::TarGz => true,
+        };
 
-        if self.path.is_dir() {
-            self.migrate_backups(true);
-            found_files = self.restorable_files(
-                &id,
-                ScanKind::Restore,
-                redirects,
-                reverse_redirects_on_restore,
-                toggled_paths,
-            );
-            available_backups = self.restorable_backups_flattened();
-            backup = self.find_by_id_flattened(&id);
+        if compressed {
+            (size as f64 * COMPRESSED_SIZE_RATIO) as u64
+        } else {
+            size
         }
+    }
 
-        #[cfg(target_os = "windows")]
-        {
-            use crate::scan::{registry, RegistryItem, ScannedRegistryValue, ScannedRegistryValues};
+    /// Returns the required and available space if the backup may not fit.
+    fn insufficient_space(&self, backup: &Backup, format: &BackupFormats) -> Option<(u64, u64)> {
+        if self.space_check == SpaceCheck::Off {
+            return None;
+        }
 
-            if let Some(hives) = self.registry_content(&id) {
-                for (hive_name, keys) in hives.0.iter() {
-                    for (key_name, entries) in keys.0.iter() {
-                        let live_entries = registry::win::try_read_registry_key(hive_name, key_name);
-                        let mut live_values = ScannedRegistryValues::new();
+        // A single full backup is updated in place, so the files that it replaces will free up their space.
+        let replaced: u64 = if backup.name() == SOLO {
+            self.mapping
+                .backups
+                .iter()
+                .filter(|x| x.name == SOLO)
+                .flat_map(|x| x.files.values())
+                .filter(|x| !x.object)
+                .map(|x| x.size)
+                .sum()
+        } else {
+            0
+        };
+        let required = Self::estimate_backup_size(backup, format).saturating_sub(replaced);
+
+        let available = {
+            let mut disks = self
+                .disks
+                .get_or_init(|| Mutex::new(sysinfo::Disks::new_with_refreshed_list()))
+                .lock()
+                .ok()?;
+            disks.refresh();
+            self.path.available_space(&disks)?
+        };
+        (required > available).then_some((required, available))
+    }
 
-                        let path = RegistryItem::from_hive_and_key(hive_name, key_name);
+    /// Apply the retention limits and clean up leftover files,
+    /// without scanning for or creating a new backup.
+    pub fn maintain(
+        &mut self,
+        retention: Retention,
+        now: &chrono::DateTime<chrono::Utc>,
+        preview: bool,
+    ) -> MaintenanceInfo {
+        self.migrate_backups(!preview);
 
-                        for (entry_name, entry) in entries.0.iter() {
+        let before: Vec<_> = self
+            .restorable_backups_flattened()
+            .iter()
+            .map(|x| x.name().to_string())
+            .collect();
+        self.forget_excess_backups(retention, now);
+        let after: Vec<_> = self
+            .restorable_backups_flattened()
+            .iter()
+            .map(|x| x.name().to_string())
+            .collect();
+        let forgotten: Vec<_> = before.into_iter().filter(|x| !after.contains(x)).collect();
+
+        let freed_bytes = self
+            .mapping
+            .irrelevant_parents(&self.path)
+            .iter()
+            .map(|x| x.size_recursive())
+            .sum();
+
+        if !preview {
+            if !forgotten.is_empty() {
+                log::info!("[{}] forgetting excess backups: {:?}", &self.mapping.name, &forgotten);
+                self.save();
+            }
+            self.prune_irrelevant_parents();
+            for backup in &after {
+                self.remove_empty_backup_subdirs(backup);
+            }
+            if !self.mirrors.is_empty() {
+                self.sync_mirrors();
+            }
+        }
+
+        MaintenanceInfo { forgotten, freed_bytes }
+    }
+
+    /// Combine a differential backup and the full backup it depends on
+    /// into a new full backup, so that restoring no longer needs the whole chain.
+    /// When `prune` is set, the merged backups are forgotten afterward,
+    /// except for locked ones and any full backup that other differentials still depend on.
+    pub fn merge(
+        &mut self,
+        id: &BackupId,
+        now: &chrono::DateTime<chrono::Utc>,
+        format: &BackupFormats,
+        prune: bool,
+    ) -> Result<FullBackup, Error> {
+        self.migrate_backups(true);
+
+        let Some((full, Some(diff))) = self.find_by_id(id) else {
+            return Err(Error::NoDifferentialBackupToMerge);
+        };
+        let full_name = full.name.clone();
+        let diff_name = diff.name.clone();
+        let comment = diff.comment.clone();
+        let hash_algorithm = full.hash_algorithm;
+        let id = BackupId::Named(diff_name.clone());
+        let encryption = self
+            .new_encryption(format)
+            .map_err(|e| Error::BackupMergeFailed { why: e.message() })?;
+
+        // Extract the composite content first,
+        // since it may be spread across archives and deltas.
+        let staging = self.path.joined(MERGE_STAGING);
+        let _ = staging.remove();
+
+        let mut found_files = self.restorable_files(&id, ScanKind::Backup, &[], false, &ToggledPaths::default());
+        // Links are only recreated for the real restore, not in the staging area.
+        let mut symlinks = HashMap::new();
+        let mut hardlinks = HashMap::new();
+        for (scan_key, file) in &mut found_files {
+            let original_path = file.original_path(scan_key);
+            file.redirected = Some(staging.joined(&self.mapping.game_file_for_zip_immutable(original_path)));
+            file.change = ScanChange::New;
+            if let Some(symlink) = file.symlink.take() {
+                symlinks.insert(scan_key.clone(), symlink);
+            }
+            if let Some(hardlink) = file.hardlink.take() {
+                hardlinks.insert(scan_key.clone(), hardlink);
+            }
+        }
+        let source = ScanInfo {
+            game_name: self.mapping.name.clone(),
+            found_files,
+            hash_algorithm,
+            backup: self.find_by_id_flattened(&id),
+            ..Default::default()
+        };
+
+        let (failed, _) = self.restore_files(&source, None);
+        if let Some((file, e)) = failed.into_iter().next() {
+            let _ = staging.remove();
+            return Err(Error::BackupMergeFailed {
+                why: format!("{}: {}", file.render(), e.message()),
+            });
+        }
+
+        let hives = self.registry_content(&id).filter(|x| !x.is_empty());
+        let scan = ScanInfo {
+            game_name: self.mapping.name.clone(),
+            found_files: source
+                .found_files
+                .iter()
+                .map(|(scan_key, file)| {
+                    (
+                        file.effective(scan_key).clone(),
+                        ScannedFile {
+                            size: file.size,
+                            hash: file.hash.clone(),
+                            change: ScanChange::New,
+                            redirected: Some(file.original_path(scan_key).clone()),
+                            symlink: symlinks.get(scan_key).cloned(),
+                            hardlink: hardlinks.get(scan_key).cloned(),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            hash_algorithm,
+            backup: source.backup,
+            dumped_registry: hives.clone(),
+            ..Default::default()
+        };
+
+        let mut merged = FullBackup {
+            name: self.generate_backup_name(
+                &BackupKind::Full,
+                now,
+                format,
+                Retention::default().with_force_timestamped_name(true),
+            ),
+            when: *now,
+            os: Some(Os::HOST),
+            comment,
+            locked: false,
+            checksum: None,
+            encryption,
+            hash_algorithm,
+            duration_ms: None,
+            flat: false,
+            files: scan
+                .found_files
+                .iter()
+                .map(|(scan_key, file)| {
+                    (
+                        file.mapping_key(scan_key),
+                        IndividualMappingFile {
+                            hash: file.hash.clone(),
+                            size: file.size,
+                            base: None,
+                            object: false,
+                            mtime: None,
+                        },
+                    )
+                })
+                .collect(),
+            symlinks: if format.chosen == BackupFormat::Simple {
+                scan.found_files
+                    .iter()
+                    .filter_map(|(scan_key, file)| Some((file.mapping_key(scan_key), file.symlink.clone()?.0)))
+                    .collect()
+            } else {
+                BTreeMap::new()
+            },
+            hardlinks: scan
+                .found_files
+                .iter()
+                .filter_map(|(scan_key, file)| Some((file.mapping_key(scan_key), file.hardlink.clone()?.0)))
+                .collect(),
+            registry: IndividualMappingRegistry {
+                hash: hives.and_then(|x| x.hash(registry::Format::Reg, hash_algorithm)),
+            },
+            children: VecDeque::new(),
+        };
+        merged.flat = IndividualMapping::can_store_flat(format, &merged.name, merged.files.keys());
+
+        log::info!(
+            "[{}] merging {} into a new full backup: {}",
+            &self.mapping.name,
+            &diff_name,
+            &merged.name
+        );
+        let backup_info = self.execute_backup(&Backup::Full(merged.clone()), &scan, format);
+        let _ = staging.remove();
+        if let Some((file, e)) = backup_info.failed_files.into_iter().next() {
+            self.prune_irrelevant_parents();
+            return Err(Error::BackupMergeFailed {
+                why: format!("{}: {}", file.render(), e.message()),
+            });
+        }
+
+        if prune {
+            if let Some(full) = self.mapping.backups.iter_mut().find(|x| x.name == full_name) {
+                // Later differentials only depend on the full backup, so we can leave them alone.
+                let merged_diffs = full
+                    .children
+                    .iter()
+                    .position(|x| x.name == diff_name)
+                    .unwrap_or_default()
+                    + 1;
+                let mut i = 0;
+                full.children.retain(|diff| {
+                    i += 1;
+                    i > merged_diffs || diff.locked
+                });
+            }
+            self.mapping
+                .backups
+                .retain(|full| full.name != full_name || full.locked || !full.children.is_empty());
+        }
+
+        self.insert_backup(Backup::Full(merged.clone()));
+        self.save();
+        self.prune_irrelevant_parents();
+
+        Ok(merged)
+    }
+
+    /// Other backups that depend on the given one,
+    /// which would be deleted along with it.
+    pub fn dependent_backups(&self, id: &BackupId) -> Vec<String> {
+        match self.find_by_id(id) {
+            Some((full, None)) => full.children.iter().map(|x| x.name.clone()).collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Delete a single backup, including its files.
+    /// Deleting a full backup also deletes the differential backups that depend on it.
+    /// This is refused if any of the affected backups are locked.
+    pub fn delete_backup(&mut self, id: &BackupId) -> Result<(), Error> {
+        self.migrate_backups(true);
+
+        let Some((full, diff)) = self.find_by_id(id) else {
+            return Err(Error::CliInvalidBackupId);
+        };
+        let full_name = full.name.clone();
+
+        match diff {
+            Some(diff) => {
+                if diff.locked {
+                    return Err(Error::BackupLocked {
+                        backup: diff.name.clone(),
+                    });
+                }
+                let diff_name = diff.name.clone();
+
+                log::info!("[{}] deleting backup: {}", &self.mapping.name, &diff_name);
+                if let Some(full) = self.mapping.backups.iter_mut().find(|x| x.name == full_name) {
+                    full.children.retain(|x| x.name != diff_name);
+                }
+            }
+            None => {
+                if let Some(locked) = std::iter::once((&full.name, full.locked))
+                    .chain(full.children.iter().map(|x| (&x.name, x.locked)))
+                    .find_map(|(name, locked)| locked.then_some(name))
+                {
+                    return Err(Error::BackupLocked { backup: locked.clone() });
+                }
+
+                log::info!(
+                    "[{}] deleting backup: {} (with dependents: {:?})",
+                    &self.mapping.name,
+                    &full_name,
+                    full.children.iter().map(|x| &x.name).collect::<Vec<_>>()
+                );
+                self.mapping.backups.retain(|x| x.name != full_name);
+            }
+        }
+
+        self.save();
+        self.prune_irrelevant_parents();
+        if !self.mirrors.is_empty() {
+            self.sync_mirrors();
+        }
+
+        Ok(())
+    }
+
+    pub fn get_backups(&mut self) -> Vec<Backup> {
+        let mut available_backups = vec![];
+
+        if self.path.is_dir() {
+            self.migrate_backups(true);
+            available_backups = self.restorable_backups_flattened();
+        }
+
+        available_backups
+    }
+
+    pub fn has_backups(&self) -> bool {
+        !self.mapping.backups.is_empty()
+    }
+
+    pub fn scan_for_restoration(
+        &mut self,
+        name: &str,
+        id: &BackupId,
+        redirects: &[RedirectConfig],
+        reverse_redirects_on_restore: bool,
+        toggled_paths: &ToggledPaths,
+        #[cfg_attr(not(target_os = "windows"), allow(unused))] toggled_registry: &ToggledRegistry,
+    ) -> ScanInfo {
+        log::trace!("[{name}] beginning scan for restore");
+
+        let mut found_files = HashMap::new();
+        #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
+        let mut found_registry_keys = HashMap::new();
+        let mut available_backups = vec![];
+        let mut backup = None;
+        #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
+        let mut dumped_registry = None;
+
+        let id = self.verify_id(id);
+
+        if self.path.is_dir() {
+            self.migrate_backups(true);
+            backup = self.find_by_id_flattened(&id);
+            let redirects = match (&self.wine_prefix, backup.as_ref().and_then(|x| x.os())) {
+                (Some(prefix), Some(Os::Windows)) if Os::HOST != Os::Windows => {
+                    log::debug!("[{name}] translating Windows paths into Wine prefix: {:?}", prefix);
+                    with_wine_prefix_redirects(redirects, prefix, reverse_redirects_on_restore)
+                }
+                _ => redirects.to_vec(),
+            };
+            found_files = self.restorable_files(
+                &id,
+                ScanKind::Restore,
+                &redirects,
+                reverse_redirects_on_restore,
+                toggled_paths,
+            );
+            if let Some(backup) = backup.as_ref() {
+                Self::flag_conflicts(&mut found_files, backup.when());
+            }
+            available_backups = self.restorable_backups_flattened();
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            use crate::scan::{registry, RegistryItem, ScannedRegistryValue, ScannedRegistryValues};
+
+            if let Some(hives) = self.registry_content(&id) {
+                for (hive_name, keys) in hives.0.iter() {
+                    for (key_name, entries) in keys.0.iter() {
+                        let live_entries = registry::win::try_read_registry_key(hive_name, key_name);
+                        let mut live_values = ScannedRegistryValues::new();
+
+                        let path = RegistryItem::from_hive_and_key(hive_name, key_name);
+                        let value_ignored = |entry_name: &str| {
+                            toggled_registry.is_ignored(name, &path, Some(entry_name))
+                                || self.registry_filter.is_registry_value_ignored(&path, entry_name)
+                        };
+
+                        for (entry_name, entry) in entries.0.iter() {
                             live_values.insert(
                                 entry_name.clone(),
                                 ScannedRegistryValue {
-                                    ignored: toggled_registry.is_ignored(name, &path, Some(entry_name)),
+                                    ignored: value_ignored(entry_name),
                                     change: live_entries
                                         .as_ref()
                                         .and_then(|x| x.0.get(entry_name))
@@ -1643,11 +3699,9 @@ impl GameLayout {
                             );
                         }
 
-                        let ignored = toggled_registry.is_ignored(name, &path, None)
-                            && entries
-                                .0
-                                .keys()
-                                .all(|x| toggled_registry.is_ignored(name, &path, Some(x)));
+                        let ignored = (toggled_registry.is_ignored(name, &path, None)
+                            || self.registry_filter.is_registry_ignored(&path))
+                            && entries.0.keys().all(|x| value_ignored(x));
 
                         found_registry_keys.insert(
                             path,
@@ -1675,31 +3729,109 @@ impl GameLayout {
             game_name: name.to_string(),
             found_files,
             found_registry_keys,
+            hash_algorithm: backup.as_ref().map(|x| x.hash_algorithm()).unwrap_or_default(),
             available_backups,
             backup,
             has_backups,
             dumped_registry,
             only_constructive_backups: false,
+            warnings: vec![],
+            game_running: false,
         }
     }
 
+    /// When `filter` is set, only files whose original path is listed in it
+    /// (or is inside of a listed folder) will be restored,
+    /// and registry content will be left alone.
     pub fn restore(
         &self,
         scan: &ScanInfo,
         #[cfg_attr(not(target_os = "windows"), allow(unused))] toggled: &ToggledRegistry,
+        filter: Option<&[StrictPath]>,
     ) -> BackupInfo {
         log::trace!("[{}] beginning restore", &scan.game_name);
 
-        let mut failed_files = HashMap::new();
+        let (failed_files, warnings) = self.restore_files(scan, filter);
         #[cfg_attr(not(target_os = "windows"), allow(unused_mut))]
         let mut failed_registry = HashMap::new();
 
-        let mut containers: HashMap<StrictPath, zip::ZipArchive<std::fs::File>> = HashMap::new();
+        #[cfg(target_os = "windows")]
+        if filter.is_none() {
+            if let Some(backup) = scan.backup.as_ref() {
+                if let Some(hives) = self.registry_content(&backup.id()) {
+                    if let Err(failed) = hives.restore(&scan.game_name, toggled, &self.registry_filter) {
+                        failed_registry.extend(failed);
+                    }
+                }
+            }
+        }
+
+        log::trace!("[{}] completed restore", &scan.game_name);
+
+        BackupInfo {
+            failed_files,
+            failed_registry,
+            warnings,
+            mirrors: HashMap::new(),
+        }
+    }
+
+    /// Mark files whose live copy has different content and was modified after the backup.
+    fn flag_conflicts(files: &mut HashMap<StrictPath, ScannedFile>, backup_time: &chrono::DateTime<chrono::Utc>) {
+        for (scan_key, file) in files.iter_mut() {
+            if file.change != ScanChange::Different {
+                continue;
+            }
+
+            let modified = file.effective(scan_key).get_mtime().ok();
+            file.conflict = modified.is_some_and(|x| chrono::DateTime::<chrono::Utc>::from(x) > *backup_time);
+        }
+    }
+
+    /// Rename an existing file so that it isn't lost when the restored file replaces it.
+    fn move_conflicting_file(target: &StrictPath) -> Result<StrictPath, AnyError> {
+        let name = target.leaf().ok_or("file has no name")?;
+        let date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+        let moved = target.popped().joined(&format!("{name}.{date}.bak"));
+        target.move_to(&moved)?;
+        Ok(moved)
+    }
+
+    /// Write each changed, non-ignored file to its `effective` target,
+    /// returning any files that could not be restored
+    /// and any files that fell back to their original location.
+    fn restore_files(
+        &self,
+        scan: &ScanInfo,
+        filter: Option<&[StrictPath]>,
+    ) -> (HashMap<StrictPath, BackupError>, Vec<OperationWarning>) {
+        let mut failed_files = HashMap::new();
+        let mut warnings = vec![];
+        let mut created_dirs = BTreeSet::new();
+
+        let mut containers: HashMap<StrictPath, (zip::ZipArchive<std::fs::File>, Option<&str>)> = HashMap::new();
+        let mut tar_requests: HashMap<StrictPath, Vec<(&StrictPath, &ScannedFile, StrictPath)>> = HashMap::new();
         let mut failed_containers: HashMap<StrictPath, BackupError> = HashMap::new();
 
-        for (scan_key, file) in &scan.found_files {
+        #[cfg(not(target_os = "windows"))]
+        let permissions = self.permissions_for_restore(scan);
+
+        // Hardlinks are restored after the files they point to,
+        // which are tracked here by mapping key along with their content hash.
+        let mut linkable = HashMap::<String, (StrictPath, String)>::new();
+        let mut found_files: Vec<_> = scan.found_files.iter().collect();
+        found_files.sort_by_key(|(_, file)| file.hardlink.is_some());
+
+        for (scan_key, file) in found_files {
             let target = file.effective(scan_key);
 
+            if file.change() == ScanChange::Same && !file.ignored {
+                linkable.insert(
+                    file.original_path(scan_key).raw().to_string(),
+                    (target.clone(), file.hash.clone()),
+                );
+            }
+
             if !file.change().is_changed() || file.ignored {
                 log::info!(
                     "[{}] skipping file; change={:?}, ignored={}: {:?} -> {:?}",
@@ -1712,6 +3844,44 @@ impl GameLayout {
                 continue;
             }
 
+            if let Some(filter) = filter {
+                let original_path = file.original_path(scan_key);
+                if !filter
+                    .iter()
+                    .any(|x| x.equivalent(original_path) || x.is_prefix_of(original_path))
+                {
+                    log::info!(
+                        "[{}] skipping file outside of filter: {:?} -> {:?}",
+                        self.mapping.name,
+                        scan_key,
+                        &target
+                    );
+                    continue;
+                }
+            }
+
+            if file.change() == ScanChange::Removed {
+                match target.remove() {
+                    Ok(_) => {
+                        log::info!(
+                            "[{}] removed file deleted in backup: {:?} -> {:?}",
+                            &self.mapping.name,
+                            scan_key,
+                            &target
+                        );
+                    }
+                    Err(e) => {
+                        log::error!(
+                            "[{}] failed to remove file deleted in backup: {:?} | {e}",
+                            self.mapping.name,
+                            &target
+                        );
+                        failed_files.insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                    }
+                }
+                continue;
+            }
+
             if let Some(container) = file.container.as_ref() {
                 if let Some(e) = failed_containers.get(container) {
                     log::warn!(
@@ -1725,8 +3895,21 @@ impl GameLayout {
                     continue;
                 }
 
-                if !containers.contains_key(container) {
+                if !BackupFormat::from_name(container.raw()).is_tar() && !containers.contains_key(container) {
                     log::debug!("[{}] loading zip archive: {:?}", &self.mapping.name, &container);
+                    let password = match self.zip_password_for(&container.leaf().unwrap_or_default()) {
+                        Ok(password) => password,
+                        Err(e) => {
+                            log::error!(
+                                "[{}] unable to decrypt zip archive: {:?} | {e:?}",
+                                &self.mapping.name,
+                                &container
+                            );
+                            failed_containers.insert(container.clone(), BackupError::App(e.clone()));
+                            failed_files.insert(scan_key.clone(), BackupError::App(e));
+                            continue;
+                        }
+                    };
                     let handle = match container.open() {
                         Ok(handle) => handle,
                         Err(e) => {
@@ -1754,52 +3937,424 @@ impl GameLayout {
                         }
                     };
                     log::debug!("[{}] loaded zip archive: {:?}", &self.mapping.name, &container);
-                    containers.insert(container.clone(), archive);
+                    containers.insert(container.clone(), (archive, password));
                 }
             }
 
-            let outcome = match &file.container {
-                None => self.restore_file_from_simple(target, scan_key),
-                Some(container) => {
-                    let Some(archive) = containers.get_mut(container) else {
-                        continue;
-                    };
-                    self.restore_file_from_zip(target, scan_key, archive)
+            let original_path = file.original_path(scan_key);
+            let target = match self.redirect_fallback_target(file, original_path) {
+                Some(fallback) => {
+                    warnings.push(OperationWarning::RedirectFallback {
+                        path: fallback.render(),
+                        redirect: target.render(),
+                    });
+                    fallback
                 }
+                None => target,
             };
 
-            match outcome {
-                Ok(_) => {
-                    log::info!("[{}] restored: {:?} -> {:?}", &self.mapping.name, scan_key, &target);
-                }
-                Err(e) => {
-                    log::error!(
-                        "[{}] failed to restore: {:?} -> {:?} | {e}",
-                        self.mapping.name,
+            if file.change() == ScanChange::Different {
+                let keep = match self.conflict_policy {
+                    ConflictPolicy::Overwrite | ConflictPolicy::Backup => false,
+                    ConflictPolicy::Skip => true,
+                    ConflictPolicy::KeepNewer => file.conflict,
+                };
+                if keep {
+                    log::info!(
+                        "[{}] keeping existing file due to conflict policy {:?}: {:?} -> {:?}",
+                        &self.mapping.name,
+                        self.conflict_policy,
                         scan_key,
                         &target
                     );
-                    failed_files.insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                    continue;
                 }
-            }
-        }
 
-        #[cfg(target_os = "windows")]
-        {
-            if let Some(backup) = scan.backup.as_ref() {
-                if let Some(hives) = self.registry_content(&backup.id()) {
-                    if let Err(failed) = hives.restore(&scan.game_name, toggled) {
-                        failed_registry.extend(failed);
+                if self.conflict_policy == ConflictPolicy::Backup {
+                    match Self::move_conflicting_file(target) {
+                        Ok(moved) => {
+                            log::info!(
+                                "[{}] moved existing file aside: {:?} -> {:?}",
+                                &self.mapping.name,
+                                &target,
+                                &moved
+                            );
+                        }
+                        Err(e) => {
+                            log::error!(
+                                "[{}] unable to move existing file aside: {:?} | {e:?}",
+                                &self.mapping.name,
+                                &target
+                            );
+                            failed_files.insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                            continue;
+                        }
                     }
                 }
             }
-        }
 
-        log::trace!("[{}] completed restore", &scan.game_name);
+            let delta_base = scan
+                .backup
+                .as_ref()
+                .and_then(|backup| backup.delta_base(original_path.raw()));
 
-        BackupInfo {
-            failed_files,
-            failed_registry,
+            if self.exclude_empty_dirs {
+                created_dirs.extend(Self::first_missing_ancestor(target));
+            }
+
+            if let (None, Some(symlink)) = (&file.container, &file.symlink) {
+                self.restore_symlink(target, symlink);
+            }
+
+            if let Some((original, _)) = file
+                .hardlink
+                .as_ref()
+                .and_then(|x| linkable.get(&x.0))
+                .filter(|(_, hash)| hash == &file.hash)
+            {
+                match self.restore_hardlink(target, original) {
+                    HardlinkRestore::Created => {
+                        log::info!(
+                            "[{}] restored as hardlink: {:?} -> {:?}",
+                            &self.mapping.name,
+                            scan_key,
+                            &target
+                        );
+                        continue;
+                    }
+                    HardlinkRestore::Existing => {
+                        log::info!(
+                            "[{}] already hardlinked to restored file: {:?} -> {:?}",
+                            &self.mapping.name,
+                            scan_key,
+                            &target
+                        );
+                        continue;
+                    }
+                    HardlinkRestore::Failed => {}
+                }
+            }
+
+            let object = scan
+                .backup
+                .as_ref()
+                .and_then(|backup| self.stored_object(backup, original_path.raw()));
+
+            let outcome = match &file.container {
+                None => match (delta_base, &object) {
+                    (Some(base), _) => self.restore_file_from_delta(target, scan_key, original_path, base),
+                    (None, Some(object)) => self.restore_file_from_object(
+                        target,
+                        object,
+                        scan.backup
+                            .as_ref()
+                            .and_then(|backup| self.stored_info(backup, original_path.raw()))
+                            .and_then(|info| info.mtime),
+                    ),
+                    (None, None) => {
+                        let shared = scan
+                            .backup
+                            .as_ref()
+                            .and_then(|backup| self.stored_hardlink(backup, original_path.raw()));
+                        self.restore_file_from_simple(target, shared.as_ref().unwrap_or(scan_key))
+                    }
+                },
+                Some(container) => match BackupFormat::from_name(container.raw()) {
+                    BackupFormat::Tar | BackupFormat::TarGz => {
+                        tar_requests
+                            .entry(container.clone())
+                            .or_default()
+                            .push((scan_key, file, target.clone()));
+                        continue;
+                    }
+                    _ => {
+                        let Some((archive, password)) = containers.get_mut(container) else {
+                            continue;
+                        };
+                        self.restore_file_from_zip(target, scan_key, archive, *password)
+                    }
+                },
+            };
+
+            match outcome {
+                Ok(_) => {
+                    log::info!("[{}] restored: {:?} -> {:?}", &self.mapping.name, scan_key, &target);
+                    linkable.insert(original_path.raw().to_string(), (target.clone(), file.hash.clone()));
+
+                    #[cfg(not(target_os = "windows"))]
+                    {
+                        let mode = match file.container {
+                            None => permissions.mode(original_path.raw()),
+                            Some(_) => None,
+                        };
+                        if let Some(mode) = mode {
+                            if let Err(e) = Self::set_mode(target, mode) {
+                                log::warn!(
+                                    "[{}] unable to restore permissions: {:?} ({:o}) | {e}",
+                                    self.mapping.name,
+                                    &target,
+                                    mode
+                                );
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    log::error!(
+                        "[{}] failed to restore: {:?} -> {:?} | {e}",
+                        self.mapping.name,
+                        scan_key,
+                        &target
+                    );
+                    failed_files.insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                }
+            }
+        }
+
+        for (container, requests) in tar_requests {
+            let format = BackupFormat::from_name(container.raw());
+
+            // Files hardlinked to another one from the same archive are linked once that one is restored.
+            let queued: HashSet<_> = requests
+                .iter()
+                .map(|(scan_key, file, _)| file.original_path(scan_key).raw().to_string())
+                .collect();
+            let (mut links, mut batch): (Vec<_>, Vec<_>) = requests
+                .into_iter()
+                .partition(|(_, file, _)| file.hardlink.as_ref().is_some_and(|x| queued.contains(&x.0)));
+
+            while !batch.is_empty() {
+                let mut outcomes = self.restore_files_from_tar(
+                    &container,
+                    format,
+                    &batch
+                        .iter()
+                        .map(|(scan_key, _, target)| (*scan_key, target))
+                        .collect::<Vec<_>>(),
+                );
+
+                for (scan_key, file, target) in batch.drain(..) {
+                    match outcomes
+                        .remove(scan_key)
+                        .unwrap_or_else(|| Err("file not restored".into()))
+                    {
+                        Ok(_) => {
+                            log::info!("[{}] restored: {:?} -> {:?}", &self.mapping.name, scan_key, &target);
+                            linkable.insert(
+                                file.original_path(scan_key).raw().to_string(),
+                                (target.clone(), file.hash.clone()),
+                            );
+                        }
+                        Err(e) => {
+                            log::error!(
+                                "[{}] failed to restore: {:?} -> {:?} | {e}",
+                                self.mapping.name,
+                                scan_key,
+                                &target
+                            );
+                            failed_files.insert(scan_key.clone(), BackupError::Raw(e.to_string()));
+                        }
+                    }
+                }
+
+                // Anything that can't be linked is read from the archive in one more pass.
+                for (scan_key, file, target) in links.drain(..) {
+                    if let Some((original, _)) = file
+                        .hardlink
+                        .as_ref()
+                        .and_then(|x| linkable.get(&x.0))
+                        .filter(|(_, hash)| hash == &file.hash)
+                    {
+                        match self.restore_hardlink(&target, original) {
+                            HardlinkRestore::Created => {
+                                log::info!(
+                                    "[{}] restored as hardlink: {:?} -> {:?}",
+                                    &self.mapping.name,
+                                    scan_key,
+                                    &target
+                                );
+                                continue;
+                            }
+                            HardlinkRestore::Existing => {
+                                log::info!(
+                                    "[{}] already hardlinked to restored file: {:?} -> {:?}",
+                                    &self.mapping.name,
+                                    scan_key,
+                                    &target
+                                );
+                                continue;
+                            }
+                            HardlinkRestore::Failed => {}
+                        }
+                    }
+                    batch.push((scan_key, file, target));
+                }
+            }
+        }
+
+        for dir in created_dirs {
+            Self::remove_empty_dirs(&dir);
+        }
+
+        (failed_files, warnings)
+    }
+
+    /// The outermost folder that would need to be created for this file.
+    fn first_missing_ancestor(file: &StrictPath) -> Option<StrictPath> {
+        let mut missing = None;
+        let mut dir = file.popped();
+        while !dir.exists() {
+            let parent = dir.popped();
+            if parent == dir {
+                break;
+            }
+            missing = Some(dir);
+            dir = parent;
+        }
+        missing
+    }
+
+    /// Remove this folder and any of its subfolders that don't contain any files.
+    fn remove_empty_dirs(dir: &StrictPath) {
+        let Ok(dir) = dir.as_std_path_buf() else {
+            return;
+        };
+
+        for entry in walkdir::WalkDir::new(dir)
+            .follow_links(false)
+            .contents_first(true)
+            .into_iter()
+            .filter_map(|x| x.ok())
+            .filter(|x| x.file_type().is_dir())
+        {
+            // This only succeeds if the folder is empty.
+            if std::fs::remove_dir(entry.path()).is_ok() {
+                log::debug!("removed empty folder: {:?}", entry.path());
+            }
+        }
+    }
+
+    /// If the file is redirected and the redirected folder can't be created,
+    /// then this returns the original path to use instead.
+    fn redirect_fallback_target<'a>(
+        &self,
+        file: &ScannedFile,
+        original_path: &'a StrictPath,
+    ) -> Option<&'a StrictPath> {
+        if !self.redirect_fallback {
+            return None;
+        }
+        let redirected = file.redirected.as_ref()?;
+        if redirected == original_path {
+            return None;
+        }
+
+        match redirected.create_parent_dir() {
+            Ok(_) => None,
+            Err(e) => {
+                log::warn!(
+                    "[{}] unable to create redirected parent directories, falling back to original path: {:?} -> {:?} | {e}",
+                    self.mapping.name,
+                    redirected,
+                    original_path,
+                );
+                Some(original_path)
+            }
+        }
+    }
+
+    /// Permissions recorded for a Simple backup and the full backup it depends on.
+    /// The differential's entries take precedence.
+    #[cfg(not(target_os = "windows"))]
+    fn permissions_for_restore(&self, scan: &ScanInfo) -> FilePermissions {
+        let Some(backup) = scan.backup.as_ref() else {
+            return FilePermissions::default();
+        };
+        let Some((full, diff)) = self.find_by_id(&backup.id()) else {
+            return FilePermissions::default();
+        };
+
+        let mut permissions = FilePermissions::load(&self.path.joined(&full.name).joined(PERMISSIONS_FILE));
+        if let Some(diff) = diff {
+            let diff_permissions = FilePermissions::load(&self.path.joined(&diff.name).joined(PERMISSIONS_FILE));
+            permissions.0.extend(diff_permissions.0);
+        }
+        permissions
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn set_mode(target: &StrictPath, mode: u32) -> Result<(), AnyError> {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(target.as_std_path_buf()?, std::fs::Permissions::from_mode(mode))?;
+        Ok(())
+    }
+
+    /// Recreate a link before restoring its content,
+    /// so that the content is written through to the link target.
+    /// If the link can't be created, then the content is restored as a regular file.
+    fn restore_symlink(&self, target: &StrictPath, symlink: &SymlinkTarget) {
+        if target.read_link().as_ref() == Some(&symlink.0) {
+            return;
+        }
+
+        log::debug!(
+            "[{}] about to restore symlink: {:?} -> {:?}",
+            self.mapping.name,
+            &target,
+            &symlink.0
+        );
+
+        let link_target = if std::path::Path::new(&symlink.0).is_absolute() {
+            StrictPath::new(symlink.0.clone())
+        } else {
+            target.popped().joined(&symlink.0)
+        };
+        let outcome = target
+            .create_parent_dir()
+            .and_then(|_| link_target.create_parent_dir())
+            .and_then(|_| target.create_symlink(&symlink.0));
+        if let Err(e) = outcome {
+            log::warn!(
+                "[{}] unable to restore symlink, restoring as a file instead: {:?} -> {:?} | {e}",
+                self.mapping.name,
+                &target,
+                &symlink.0
+            );
+        }
+    }
+
+    /// Link a file to one that was already restored, since they were hardlinked when backed up.
+    /// Hardlinks can't span file systems (e.g., when a redirect moves one of the files elsewhere),
+    /// so if the link can't be created, then the content is restored as a separate file.
+    fn restore_hardlink(&self, target: &StrictPath, original: &StrictPath) -> HardlinkRestore {
+        if target
+            .hardlink_identity()
+            .is_some_and(|x| original.hardlink_identity() == Some(x))
+        {
+            return HardlinkRestore::Existing;
+        }
+
+        log::debug!(
+            "[{}] about to restore hardlink: {:?} -> {:?}",
+            self.mapping.name,
+            &target,
+            &original
+        );
+
+        match target
+            .create_parent_dir()
+            .and_then(|_| target.create_hardlink(original))
+        {
+            Ok(_) => HardlinkRestore::Created,
+            Err(e) => {
+                log::warn!(
+                    "[{}] unable to restore hardlink, restoring as a separate file instead: {:?} -> {:?} | {e}",
+                    self.mapping.name,
+                    &target,
+                    &original
+                );
+                HardlinkRestore::Failed
+            }
         }
     }
 
@@ -1811,7 +4366,48 @@ impl GameLayout {
             &target
         );
 
-        Ok(scan_key.copy_to_path(&self.mapping.name, target)?)
+        Ok(scan_key.copy_to_path(&self.mapping.name, target, None)?)
+    }
+
+    fn restore_file_from_object(
+        &self,
+        target: &StrictPath,
+        object: &StrictPath,
+        mtime: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<(), AnyError> {
+        self.restore_file_from_simple(target, object)?;
+        if let Some(mtime) = mtime {
+            target.set_mtime(mtime.into())?;
+        }
+        Ok(())
+    }
+
+    fn restore_file_from_delta(
+        &self,
+        target: &StrictPath,
+        scan_key: &StrictPath,
+        original_path: &StrictPath,
+        base: &str,
+    ) -> Result<(), AnyError> {
+        let base_file = self.mapping.game_file_immutable(&self.path, original_path, base);
+        log::trace!(
+            "[{}] about to restore (delta): {:?} -> {:?} (base: {:?})",
+            self.mapping.name,
+            scan_key,
+            &target,
+            &base_file
+        );
+
+        target.create_parent_dir()?;
+        target.unset_readonly()?;
+        delta::decode(
+            base_file.open()?,
+            scan_key.open()?,
+            std::io::BufWriter::new(target.create()?),
+        )?;
+        target.set_mtime(scan_key.get_mtime()?)?;
+
+        Ok(())
     }
 
     fn restore_file_from_zip(
@@ -1819,6 +4415,7 @@ impl GameLayout {
         target: &StrictPath,
         scan_key: &StrictPath,
         archive: &mut zip::ZipArchive<std::fs::File>,
+        password: Option<&str>,
     ) -> Result<(), AnyError> {
         log::debug!(
             "[{}] about to restore (zip): {:?} -> {:?}",
@@ -1827,6 +4424,12 @@ impl GameLayout {
             &target
         );
 
+        // Open the entry first so that a bad password doesn't leave the target truncated.
+        let mut source_file = match password {
+            Some(password) => archive.by_name_decrypt(scan_key.raw(), password.as_bytes())?,
+            None => archive.by_name(scan_key.raw())?,
+        };
+
         if let Err(e) = target.create_parent_dir() {
             log::error!(
                 "[{}] unable to create parent directories: {:?} | {e}",
@@ -1855,7 +4458,6 @@ impl GameLayout {
                 return Err(Box::new(e));
             }
         };
-        let mut source_file = archive.by_name(scan_key.raw())?;
         if let Err(e) = std::io::copy(&mut source_file, &mut target_handle) {
             log::warn!(
                 "[{}] failed to copy to target: {:?} -> {:?} | {e}",
@@ -1866,8 +4468,136 @@ impl GameLayout {
             return Err(Box::new(e));
         }
 
-        let mtime = source_file.last_modified();
-        if let Err(e) = target.set_mtime_zip(mtime) {
+        if let Some(mtime) = source_file.last_modified() {
+            if let Err(e) = target.set_mtime_zip(mtime) {
+                log::error!(
+                    "[{}] unable to set modification time: {:?} -> {:?} to {:#?} | {e:?}",
+                    self.mapping.name,
+                    scan_key,
+                    &target,
+                    mtime
+                );
+                return Err("unable to set modification time".into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restore several files from the same tar archive.
+    /// Tar entries can only be read in sequence,
+    /// so this reads through the archive once for all of the files,
+    /// returning the outcome for each scan key.
+    fn restore_files_from_tar(
+        &self,
+        container: &StrictPath,
+        format: BackupFormat,
+        requests: &[(&StrictPath, &StrictPath)],
+    ) -> HashMap<StrictPath, Result<(), AnyError>> {
+        let mut pending: HashMap<&str, (&StrictPath, &StrictPath)> = requests
+            .iter()
+            .map(|(scan_key, target)| (scan_key.raw(), (*scan_key, *target)))
+            .collect();
+        let mut outcomes = HashMap::new();
+
+        if let Err(e) = self.read_tar_entries(container, format, &mut pending, &mut outcomes) {
+            log::error!(
+                "[{}] failed to read tar archive: {:?} | {e}",
+                self.mapping.name,
+                &container
+            );
+            let message = e.to_string();
+            for (scan_key, _) in pending.into_values() {
+                outcomes.insert(scan_key.clone(), Err(message.clone().into()));
+            }
+            return outcomes;
+        }
+
+        for (scan_key, _) in pending.into_values() {
+            outcomes.insert(
+                scan_key.clone(),
+                Err(format!("file not found in archive: {}", scan_key.raw()).into()),
+            );
+        }
+
+        outcomes
+    }
+
+    fn read_tar_entries(
+        &self,
+        container: &StrictPath,
+        format: BackupFormat,
+        pending: &mut HashMap<&str, (&StrictPath, &StrictPath)>,
+        outcomes: &mut HashMap<StrictPath, Result<(), AnyError>>,
+    ) -> Result<(), AnyError> {
+        let mut archive = open_tar(container, format)?;
+        for entry in archive.entries()? {
+            if pending.is_empty() {
+                break;
+            }
+            let mut entry = entry?;
+            let path = String::from_utf8_lossy(&entry.path_bytes()).to_string();
+            let Some((scan_key, target)) = pending.remove(path.as_str()) else {
+                continue;
+            };
+            outcomes.insert(scan_key.clone(), self.restore_tar_entry(target, scan_key, &mut entry));
+        }
+        Ok(())
+    }
+
+    fn restore_tar_entry(
+        &self,
+        target: &StrictPath,
+        scan_key: &StrictPath,
+        entry: &mut tar::Entry<Box<dyn std::io::Read>>,
+    ) -> Result<(), AnyError> {
+        log::debug!(
+            "[{}] about to restore (tar): {:?} -> {:?}",
+            self.mapping.name,
+            scan_key,
+            &target
+        );
+
+        if let Err(e) = target.create_parent_dir() {
+            log::error!(
+                "[{}] unable to create parent directories: {:?} | {e}",
+                self.mapping.name,
+                &target
+            );
+            return Err(Box::new(e));
+        }
+        if let Err(e) = target.unset_readonly() {
+            log::warn!(
+                "[{}] failed to unset read-only on target: {:?} | {e}",
+                self.mapping.name,
+                &target
+            );
+            return Err(e);
+        }
+        let mut target_handle = match target.create() {
+            Ok(x) => x,
+            Err(e) => {
+                log::warn!(
+                    "[{}] failed to get handle: {:?} -> {:?} | {e}",
+                    self.mapping.name,
+                    scan_key,
+                    &target
+                );
+                return Err(Box::new(e));
+            }
+        };
+        if let Err(e) = std::io::copy(entry, &mut target_handle) {
+            log::warn!(
+                "[{}] failed to copy to target: {:?} -> {:?} | {e}",
+                self.mapping.name,
+                &scan_key,
+                &target,
+            );
+            return Err(Box::new(e));
+        }
+
+        let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(entry.header().mtime()?);
+        if let Err(e) = target.set_mtime(mtime) {
             log::error!(
                 "[{}] unable to set modification time: {:?} -> {:?} to {:#?} | {e:?}",
                 self.mapping.name,
@@ -1929,17 +4659,27 @@ impl GameLayout {
         irrelevant_files
     }
 
-    fn remove_irrelevant_backup_files(&self, backup: &str, relevant_files: &[StrictPath]) {
+    /// Returns any files that could not be removed.
+    fn remove_irrelevant_backup_files(&self, backup: &str, relevant_files: &[StrictPath]) -> Vec<StrictPath> {
         log::trace!(
             "[{}] looking for irrelevant backup files in {}",
             self.mapping.name,
             backup
         );
+        let mut unremovable = vec![];
         for file in self.find_irrelevant_backup_files(backup, relevant_files) {
             log::debug!("[{}] removing irrelevant backup file: {:?}", self.mapping.name, &file);
-            let _ = file.remove();
+            if let Err(e) = file.remove() {
+                log::warn!(
+                    "[{}] unable to remove irrelevant backup file: {:?} | {e:?}",
+                    self.mapping.name,
+                    &file
+                );
+                unremovable.push(file);
+            }
         }
         log::trace!("[{}] done removing irrelevant backup files", self.mapping.name);
+        unremovable
     }
 
     fn remove_empty_backup_subdirs(&self, backup: &str) {
@@ -2012,16 +4752,196 @@ impl GameLayout {
         }
     }
 
-    /// Returns whether the backup is valid.
-    pub fn validate(&self, backup_id: BackupId) -> bool {
-        if let Some((backup, diff)) = self.find_by_id(&backup_id) {
+    /// For archives, this is the hash of the archive file.
+    /// For simple backups, this is a hash of each stored file's path and content hash.
+    fn compute_checksum(&self, backup: &Backup) -> Option<String> {
+        match backup.format() {
+            BackupFormat::Simple => {
+                let files: Vec<(&String, &IndividualMappingFile)> = match backup {
+                    Backup::Full(backup) => backup.files.iter().collect(),
+                    Backup::Differential(backup) => backup
+                        .files
+                        .iter()
+                        .filter_map(|(k, v)| v.as_ref().map(|v| (k, v)))
+                        .collect(),
+                };
+
+                let mut composite = String::new();
+                for (file, info) in files {
+                    let original_path = StrictPath::new(file.to_string());
+                    let stored = self.stored_file(backup.name(), &original_path, info, backup.shared_hardlink(file));
+                    let hash = stored.try_sha1().ok()?;
+                    composite.push_str(&format!("{file}:{hash}\n"));
+                }
+
+                Some(sha1(composite))
+            }
+            BackupFormat::Zip | BackupFormat::Tar | BackupFormat::TarGz => {
+                self.path.joined(backup.name()).try_sha1().ok()
+            }
+        }
+    }
+
+    fn checksum_matches(&self, backup: &Backup) -> bool {
+        match backup.checksum() {
+            Some(expected) => self.compute_checksum(backup).as_ref() == Some(expected),
+            None => true,
+        }
+    }
+
+    /// Re-hash the stored content of a backup without extracting it,
+    /// and return the files whose content no longer matches the mapping.
+    /// Files are identified by their original path,
+    /// while registry content is identified by its file name.
+    pub fn scrub(&self, backup_id: BackupId) -> Vec<StrictPath> {
+        let mut diverged = vec![];
+
+        let Some((full, diff)) = self.find_by_id(&backup_id) else {
+            return diverged;
+        };
+
+        diverged.extend(
+            self.scrub_files(&full.name, full.format(), full.hash_algorithm, full.files.iter(), |x| {
+                full.shared_hardlink(x)
+            }),
+        );
+        if let Some(diff) = diff {
+            diverged.extend(self.scrub_files(
+                &diff.name,
+                diff.format(),
+                diff.hash_algorithm,
+                diff.files.iter().filter_map(|(k, v)| v.as_ref().map(|v| (k, v))),
+                |x| diff.shared_hardlink(x),
+            ));
+        }
+
+        let (expected_registry, algorithm) = match diff.and_then(|x| x.registry.as_ref().map(|r| (r, x))) {
+            Some((registry, diff)) => (registry.hash.as_ref(), diff.hash_algorithm),
+            None => (full.registry.hash.as_ref(), full.hash_algorithm),
+        };
+        if let Some(expected) = expected_registry {
+            let actual = self
+                .registry_content(&backup_id)
+                .and_then(|hives| hives.hash(registry::Format::Reg, algorithm));
+            if actual.as_ref() != Some(expected) {
+                diverged.push(StrictPath::new(registry::Format::Reg.filename().to_string()));
+            }
+        }
+
+        diverged
+    }
+
+    fn scrub_files<'a>(
+        &self,
+        backup: &str,
+        format: BackupFormat,
+        algorithm: HashAlgorithm,
+        files: impl Iterator<Item = (&'a String, &'a IndividualMappingFile)>,
+        shared: impl Fn(&str) -> Option<&'a str>,
+    ) -> Vec<StrictPath> {
+        let mut diverged = vec![];
+
+        match format {
+            BackupFormat::Simple => {
+                for (file, info) in files {
+                    let original_path = StrictPath::new(file.to_string());
+                    let stored = self.stored_file(backup, &original_path, info, shared(file));
+                    let actual = match &info.base {
+                        None => stored.try_hash(algorithm).ok(),
+                        Some(base) => {
+                            let base = self.mapping.game_file_immutable(&self.path, &original_path, base);
+                            let mut hasher = Hasher::new(algorithm);
+                            match (base.open(), stored.open()) {
+                                (Ok(base), Ok(stored)) => {
+                                    delta::decode(base, stored, &mut hasher).ok().map(|_| hasher.finalize())
+                                }
+                                _ => None,
+                            }
+                        }
+                    };
+                    if actual.as_ref() != Some(&info.hash) {
+                        diverged.push(original_path);
+                    }
+                }
+            }
+            BackupFormat::Zip => {
+                // With a missing or wrong password, every file counts as diverged.
+                let password = self.zip_password_for(backup);
+                let mut archive = self
+                    .path
+                    .joined(backup)
+                    .open()
+                    .ok()
+                    .filter(|_| password.is_ok())
+                    .and_then(|handle| zip::ZipArchive::new(handle).ok());
+
+                for (file, info) in files {
+                    let original_path = StrictPath::new(file.to_string());
+                    let stored = self.mapping.game_file_for_zip_immutable(&original_path);
+                    let actual = archive.as_mut().and_then(|archive| {
+                        let mut entry = match password {
+                            Ok(Some(password)) => archive.by_name_decrypt(&stored, password.as_bytes()).ok()?,
+                            _ => archive.by_name(&stored).ok()?,
+                        };
+                        let mut hasher = Hasher::new(algorithm);
+                        std::io::copy(&mut entry, &mut hasher).ok()?;
+                        Some(hasher.finalize())
+                    });
+                    if actual.as_ref() != Some(&info.hash) {
+                        diverged.push(original_path);
+                    }
+                }
+            }
+            BackupFormat::Tar | BackupFormat::TarGz => {
+                let expected: HashMap<_, _> = files
+                    .map(|(file, info)| {
+                        let original_path = StrictPath::new(file.to_string());
+                        (
+                            self.mapping.game_file_for_zip_immutable(&original_path),
+                            (original_path, info),
+                        )
+                    })
+                    .collect();
+
+                let mut actual = HashMap::new();
+                if let Ok(mut archive) = open_tar(&self.path.joined(backup), format) {
+                    if let Ok(entries) = archive.entries() {
+                        for entry in entries {
+                            let Ok(mut entry) = entry else {
+                                break;
+                            };
+                            let name = String::from_utf8_lossy(&entry.path_bytes()).to_string();
+                            if !expected.contains_key(&name) {
+                                continue;
+                            }
+                            let mut hasher = Hasher::new(algorithm);
+                            if std::io::copy(&mut entry, &mut hasher).is_ok() {
+                                actual.insert(name, hasher.finalize());
+                            }
+                        }
+                    }
+                }
+
+                for (stored, (original_path, info)) in expected {
+                    if actual.get(&stored) != Some(&info.hash) {
+                        diverged.push(original_path);
+                    }
+                }
+            }
+        }
+
+        diverged.sort();
+        diverged
+    }
+
+    /// Returns whether the backup is valid.
+    pub fn validate(&self, backup_id: BackupId) -> bool {
+        if let Some((backup, diff)) = self.find_by_id(&backup_id) {
             match backup.format() {
                 BackupFormat::Simple => {
-                    for file in backup.files.keys() {
+                    for (file, info) in &backup.files {
                         let original_path = StrictPath::new(file.to_string());
-                        let stored = self
-                            .mapping
-                            .game_file_immutable(&self.path, &original_path, &backup.name);
+                        let stored = self.stored_file(&backup.name, &original_path, info, backup.shared_hardlink(file));
                         if !stored.is_file() {
                             #[cfg(test)]
                             eprintln!("can't find {}", stored.render());
@@ -2030,6 +4950,11 @@ impl GameLayout {
                     }
                 }
                 BackupFormat::Zip => {
+                    if let Err(e) = self.zip_password_for(&backup.name) {
+                        #[cfg(test)]
+                        eprintln!("can't decrypt {}: {e:?}", backup.name);
+                        return false;
+                    }
                     let Ok(handle) = self.path.joined(&backup.name).open() else {
                         return false;
                     };
@@ -2040,7 +4965,22 @@ impl GameLayout {
                     for file in backup.files.keys() {
                         let original_path = StrictPath::new(file.to_string());
                         let stored = self.mapping.game_file_for_zip_immutable(&original_path);
-                        if archive.by_name(&stored).is_err() {
+                        if archive.index_for_name(&stored).is_none() {
+                            #[cfg(test)]
+                            eprintln!("can't find {}", stored);
+                            return false;
+                        }
+                    }
+                }
+                BackupFormat::Tar | BackupFormat::TarGz => {
+                    let Ok(names) = tar_entry_names(&self.path.joined(&backup.name), backup.format()) else {
+                        return false;
+                    };
+
+                    for file in backup.files.keys() {
+                        let original_path = StrictPath::new(file.to_string());
+                        let stored = self.mapping.game_file_for_zip_immutable(&original_path);
+                        if !names.contains(&stored) {
                             #[cfg(test)]
                             eprintln!("can't find {}", stored);
                             return false;
@@ -2049,19 +4989,30 @@ impl GameLayout {
                 }
             }
 
+            if !self.checksum_matches(&Backup::Full(backup.clone())) {
+                #[cfg(test)]
+                eprintln!("checksum mismatch for {}", backup.name);
+                return false;
+            }
+
             if let Some(backup) = diff {
+                if !self.checksum_matches(&Backup::Differential(backup.clone())) {
+                    #[cfg(test)]
+                    eprintln!("checksum mismatch for {}", backup.name);
+                    return false;
+                }
+
                 match backup.format() {
                     BackupFormat::Simple => {
                         for (file, data) in &backup.files {
-                            if data.is_none() {
+                            let Some(info) = data else {
                                 // File is deliberately omitted.
                                 continue;
-                            }
+                            };
 
                             let original_path = StrictPath::new(file.to_string());
-                            let stored = self
-                                .mapping
-                                .game_file_immutable(&self.path, &original_path, &backup.name);
+                            let stored =
+                                self.stored_file(&backup.name, &original_path, info, backup.shared_hardlink(file));
                             if !stored.is_file() {
                                 #[cfg(test)]
                                 eprintln!("can't find {}", stored.render());
@@ -2070,6 +5021,11 @@ impl GameLayout {
                         }
                     }
                     BackupFormat::Zip => {
+                        if let Err(e) = self.zip_password_for(&backup.name) {
+                            #[cfg(test)]
+                            eprintln!("can't decrypt {}: {e:?}", backup.name);
+                            return false;
+                        }
                         let Ok(handle) = self.path.joined(&backup.name).open() else {
                             return false;
                         };
@@ -2085,7 +5041,27 @@ impl GameLayout {
 
                             let original_path = StrictPath::new(file.to_string());
                             let stored = self.mapping.game_file_for_zip_immutable(&original_path);
-                            if archive.by_name(&stored).is_err() {
+                            if archive.index_for_name(&stored).is_none() {
+                                #[cfg(test)]
+                                eprintln!("can't find {}", stored);
+                                return false;
+                            }
+                        }
+                    }
+                    BackupFormat::Tar | BackupFormat::TarGz => {
+                        let Ok(names) = tar_entry_names(&self.path.joined(&backup.name), backup.format()) else {
+                            return false;
+                        };
+
+                        for (file, data) in &backup.files {
+                            if data.is_none() {
+                                // File is deliberately omitted.
+                                continue;
+                            }
+
+                            let original_path = StrictPath::new(file.to_string());
+                            let stored = self.mapping.game_file_for_zip_immutable(&original_path);
+                            if !names.contains(&stored) {
                                 #[cfg(test)]
                                 eprintln!("can't find {}", stored);
                                 return false;
@@ -2098,9 +5074,59 @@ impl GameLayout {
 
         true
     }
+
+    /// Package the game folder into a single zip file that can be imported elsewhere.
+    /// Any content kept in the shared object folder is included as well,
+    /// so the bundle does not depend on the rest of the backup folder.
+    pub fn export_bundle(&self, target: &StrictPath) -> Result<(), AnyError> {
+        let base = self.path.interpret()?;
+
+        target.create_parent_dir()?;
+        let mut zip = zip::ZipWriter::new(target.create()?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored)
+            .large_file(true);
+
+        for entry in walkdir::WalkDir::new(&base)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|x| crate::scan::filter_map_walkdir(&self.mapping.name, x))
+            .filter(|x| x.file_type().is_file())
+        {
+            let Ok(relative) = entry.path().strip_prefix(&base) else {
+                continue;
+            };
+            let name = relative
+                .components()
+                .map(|x| x.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+            if name.starts_with(MERGE_STAGING) {
+                continue;
+            }
+
+            let source = StrictPath::from(&entry);
+            let options = match source.get_mtime_zip() {
+                Ok(mtime) => options.last_modified_time(mtime),
+                Err(_) => options,
+            };
+            zip.start_file(name, options)?;
+            std::io::copy(&mut source.open()?, &mut zip)?;
+        }
+
+        let hashes: BTreeSet<_> = self.mapping.object_hashes().collect();
+        for hash in hashes {
+            zip.start_file(format!("{OBJECTS_FOLDER}/{hash}"), options)?;
+            std::io::copy(&mut self.object_file(hash).open()?, &mut zip)?;
+        }
+
+        zip.finish()?;
+        Ok(())
+    }
 }
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
 pub enum BackupKind {
     #[default]
     Full,
@@ -2112,6 +5138,19 @@ pub struct BackupLayout {
     pub base: StrictPath,
     games: HashMap<String, StrictPath>,
     games_lowercase: HashMap<String, StrictPath>,
+    folder_template: Option<String>,
+    filename_policy: FilenamePolicy,
+    hooks: BackupHooks,
+    redirect_fallback: bool,
+    wine_prefix: Option<StrictPath>,
+    space_check: SpaceCheck,
+    disks: Arc<OnceLock<Mutex<sysinfo::Disks>>>,
+    conflict_policy: ConflictPolicy,
+    mirrors: Vec<StrictPath>,
+    exclude_empty_dirs: bool,
+    cancel: Option<Arc<AtomicBool>>,
+    registry_filter: BackupFilter,
+    zip_password: Option<String>,
 }
 
 impl BackupLayout {
@@ -2122,9 +5161,107 @@ impl BackupLayout {
             base,
             games,
             games_lowercase,
+            folder_template: None,
+            filename_policy: Default::default(),
+            hooks: Default::default(),
+            redirect_fallback: false,
+            wine_prefix: None,
+            space_check: Default::default(),
+            disks: Default::default(),
+            conflict_policy: Default::default(),
+            mirrors: vec![],
+            exclude_empty_dirs: false,
+            cancel: None,
+            registry_filter: Default::default(),
+            zip_password: None,
         }
     }
 
+    /// Use a template for the folders of games that have not been backed up yet.
+    /// Existing games stay wherever they were found.
+    /// The template must include `{game}`, or else every game would share a folder.
+    pub fn with_folder_template(mut self, folder_template: Option<String>) -> Self {
+        self.folder_template = folder_template.filter(|x| !x.trim().is_empty()).filter(|x| {
+            let valid = x.contains("{game}");
+            if !valid {
+                log::warn!("Ignoring folder template without {{game}}: {x:?}");
+            }
+            valid
+        });
+        self
+    }
+
+    /// Decide which characters to escape in the folders of games that have not been backed up yet.
+    pub fn with_filename_policy(mut self, filename_policy: FilenamePolicy) -> Self {
+        self.filename_policy = filename_policy;
+        self
+    }
+
+    /// Run these commands before and after each game's backup.
+    pub fn with_hooks(mut self, hooks: BackupHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// When a redirected file can't be restored because its folder can't be created,
+    /// restore it to its original location instead.
+    pub fn with_redirect_fallback(mut self, redirect_fallback: bool) -> Self {
+        self.redirect_fallback = redirect_fallback;
+        self
+    }
+
+    /// When restoring a backup from Windows on another OS,
+    /// translate its Windows paths into this Wine prefix.
+    pub fn with_wine_prefix(mut self, wine_prefix: Option<StrictPath>) -> Self {
+        self.wine_prefix = wine_prefix.filter(|x| !x.raw().trim().is_empty());
+        self
+    }
+
+    /// Before making a new backup, check whether it will fit on the backup drive.
+    pub fn with_space_check(mut self, space_check: SpaceCheck) -> Self {
+        self.space_check = space_check;
+        self
+    }
+
+    /// When restoring a file that already exists with different content,
+    /// decide whether to replace it.
+    pub fn with_conflict_policy(mut self, conflict_policy: ConflictPolicy) -> Self {
+        self.conflict_policy = conflict_policy;
+        self
+    }
+
+    /// After each successful backup, copy the game's backup folder
+    /// to the same relative location under each of these folders.
+    pub fn with_mirrors(mut self, mirrors: Vec<StrictPath>) -> Self {
+        self.mirrors = mirrors;
+        self
+    }
+
+    /// When restoring, remove any folders created along the way that end up empty.
+    pub fn with_empty_dir_exclusion(mut self, exclude_empty_dirs: bool) -> Self {
+        self.exclude_empty_dirs = exclude_empty_dirs;
+        self
+    }
+
+    /// Encrypt new zip backups with this password, if enabled,
+    /// and use it to read existing encrypted backups.
+    pub fn with_zip_password(mut self, zip_password: Option<String>) -> Self {
+        self.zip_password = zip_password;
+        self
+    }
+
+    /// When restoring, skip registry keys and values that are excluded from backups.
+    pub fn with_registry_filter(mut self, filter: BackupFilter) -> Self {
+        self.registry_filter = filter;
+        self
+    }
+
+    /// Stop in-progress backups, even partway through a large file, once this flag is set.
+    pub fn with_cancel_flag(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
     pub fn load(base: &StrictPath) -> HashMap<String, StrictPath> {
         let mut overall = HashMap::new();
 
@@ -2133,12 +5270,34 @@ impl BackupLayout {
         };
 
         for game_dir in walkdir::WalkDir::new(base_interpreted)
-            .max_depth(1)
+            .max_depth(MAX_GAME_FOLDER_DEPTH)
             .follow_links(false)
             .into_iter()
+            .filter_entry(|x| {
+                if x.depth() == 0 {
+                    return true;
+                }
+                if !x.file_type().is_dir() {
+                    return false;
+                }
+
+                // Don't look inside of hidden folders (like the object store).
+                let name = x.file_name().to_string_lossy();
+                if name.starts_with('.') {
+                    return false;
+                }
+
+                // A game folder may contain other games' folders from a folder template,
+                // like a game called `Steam` next to `Steam/{game}`,
+                // but we can skip the game's own backups.
+                let in_game_folder = x
+                    .path()
+                    .parent()
+                    .is_some_and(|parent| parent.join("mapping.yaml").is_file());
+                !(in_game_folder && (name.starts_with("drive-") || name.starts_with("backup-")))
+            })
             .skip(1) // the base path itself
             .filter_map(|x| crate::scan::filter_map_walkdir("ludusavi::BackupLayout", x))
-            .filter(|x| x.file_type().is_dir())
         {
             let game_dir = StrictPath::from(&game_dir);
             let mapping_file = game_dir.joined("mapping.yaml");
@@ -2158,7 +5317,13 @@ impl BackupLayout {
     }
 
     pub fn game_layout(&self, name: &str) -> GameLayout {
-        let path = self.game_folder(name);
+        self.game_layout_for(name, None)
+    }
+
+    /// Like `game_layout`, but with the manifest data for the `{store}` and `{id}`
+    /// tokens of the folder template, in case the game does not have a folder yet.
+    pub fn game_layout_for(&self, name: &str, game: Option<&Game>) -> GameLayout {
+        let path = self.game_folder_for(name, game);
 
         match GameLayout::load(path.clone()) {
             Ok(mut x) => {
@@ -2169,15 +5334,92 @@ impl BackupLayout {
                     log::info!("Updating renamed game: {} -> {}", &x.mapping.name, name);
                     x.mapping.name = name.to_string();
                 }
+                x.root = Some(self.base.clone());
+                x.hooks = self.hooks.clone();
+                x.redirect_fallback = self.redirect_fallback;
+                x.wine_prefix = self.wine_prefix.clone();
+                x.space_check = self.space_check;
+                x.disks = self.disks.clone();
+                x.conflict_policy = self.conflict_policy;
+                x.mirrors = self.mirrors.clone();
+                x.exclude_empty_dirs = self.exclude_empty_dirs;
+                x.cancel = self.cancel.clone();
+                x.registry_filter = self.registry_filter.clone();
+                x.zip_password = self.zip_password.clone();
                 x
             }
             Err(_) => GameLayout {
                 path,
                 mapping: IndividualMapping::new(name.to_string()),
+                root: Some(self.base.clone()),
+                hooks: self.hooks.clone(),
+                redirect_fallback: self.redirect_fallback,
+                wine_prefix: self.wine_prefix.clone(),
+                space_check: self.space_check,
+                disks: self.disks.clone(),
+                conflict_policy: self.conflict_policy,
+                mirrors: self.mirrors.clone(),
+                exclude_empty_dirs: self.exclude_empty_dirs,
+                cancel: self.cancel.clone(),
+                registry_filter: self.registry_filter.clone(),
+                zip_password: self.zip_password.clone(),
             },
         }
     }
 
+    /// Remove content from the shared object folder that no game's backups refer to anymore.
+    /// This should run after all games have been backed up or pruned,
+    /// since it looks at every game in the backup folder.
+    /// Mirrors are cleaned up the same way, based on the games that were copied to them.
+    /// Returns the number of bytes that were (or, in preview, would be) freed in the backup folder itself.
+    pub fn collect_garbage(&self, preview: bool) -> u64 {
+        for mirror in &self.mirrors {
+            let freed = BackupLayout::new(mirror.clone()).collect_garbage(preview);
+            log::debug!("Unreferenced objects in mirror: {:?} ({freed} bytes)", mirror);
+        }
+
+        let store = self.base.joined(OBJECTS_FOLDER);
+        if !store.is_dir() {
+            return 0;
+        }
+
+        // Reload in case new games were backed up since we were created.
+        let mut referenced = HashSet::new();
+        for path in Self::load(&self.base).into_values() {
+            match GameLayout::load(path.clone()) {
+                Ok(layout) => referenced.extend(layout.mapping.object_hashes().map(|x| x.to_string())),
+                Err(e) => {
+                    log::warn!(
+                        "Skipping object cleanup because of unloadable mapping: {:?} | {e}",
+                        &path
+                    );
+                    return 0;
+                }
+            }
+        }
+
+        let Ok(entries) = store.read_dir() else {
+            return 0;
+        };
+
+        let mut freed = 0;
+        for entry in entries.filter_map(|x| x.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if referenced.contains(&name) {
+                continue;
+            }
+
+            let object = store.joined(&name);
+            let size = object.size();
+            log::debug!("Removing unreferenced object: {:?}", &object);
+            if preview || object.remove().is_ok() {
+                freed += size;
+            }
+        }
+
+        freed
+    }
+
     pub fn try_game_layout(&self, name: &str) -> Option<GameLayout> {
         let path = self.game_folder(name);
 
@@ -2189,6 +5431,8 @@ impl BackupLayout {
                 log::info!("Updating renamed game: {} -> {}", &x.mapping.name, name);
                 x.mapping.name = name.to_string();
             }
+            x.root = Some(self.base.clone());
+            x.mirrors = self.mirrors.clone();
             x
         })
     }
@@ -2216,8 +5460,11 @@ impl BackupLayout {
                 toggled_paths,
                 only_constructive,
             );
+            let (full, diff) = game_layout.find_by_id(&BackupId::Latest)?;
+            let when = diff.map(|x| x.when).unwrap_or(full.when);
             scan.map(|scan| LatestBackup {
                 scan,
+                when,
                 registry_content: if cfg!(target_os = "windows") {
                     game_layout.registry_content(&BackupId::Latest)
                 } else {
@@ -2229,89 +5476,575 @@ impl BackupLayout {
         }
     }
 
-    fn generate_total_rename(original_name: &str) -> String {
-        format!("ludusavi-renamed-{}", encode_base64_for_folder(original_name))
+    /// When the game was most recently backed up, if ever.
+    pub fn last_backup_time(&self, name: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+        if !self.contains_game(name) {
+            return None;
+        }
+
+        let game_layout = self.game_layout(name);
+        let (full, diff) = game_layout.find_by_id(&BackupId::Latest)?;
+        Some(diff.map(|x| x.when).unwrap_or(full.when))
     }
 
-    pub fn game_folder(&self, game_name: &str) -> StrictPath {
-        match self.games.get::<str>(game_name) {
-            Some(game) => game.clone(),
-            None => {
-                let mut safe_name = escape_folder_name(game_name);
+    /// Size and timing of the game's most recent backup, if ever backed up.
+    /// This only reads the game's own mapping file when asked,
+    /// without preparing a full layout for it.
+    pub fn backup_history(&self, name: &str) -> Option<BackupHistory> {
+        let folder = self.games.get(name)?;
+        let mapping = IndividualMapping::load(&GameLayout::mapping_file(folder)).ok()?;
+        let backup = match mapping.latest_backup()? {
+            (full, None) => Backup::Full(full.clone()),
+            (full, Some(diff)) => Backup::Differential(diff.clone().with_inherited_size(full)),
+        };
+        Some(BackupHistory {
+            size: backup.total_size(),
+            duration_ms: backup.duration_ms(),
+        })
+    }
 
-                if safe_name.matches(SAFE).count() == safe_name.len() {
-                    // It's unreadable now, so do a total rename.
-                    safe_name = Self::generate_total_rename(game_name);
-                }
+    /// Find every game with a backup that contains the file,
+    /// based on the file's original location.
+    pub fn find_file(&self, file: &StrictPath) -> Vec<FileCoverage> {
+        let mut coverage = vec![];
 
-                self.base.joined(&safe_name)
+        for name in self.restorable_game_set() {
+            let Some(game_layout) = self.try_game_layout(&name) else {
+                continue;
+            };
+            if let Some((backup, stored)) = game_layout.latest_backup_with_file(file) {
+                coverage.push(FileCoverage {
+                    game: name,
+                    backup,
+                    file: stored,
+                });
             }
         }
-    }
 
-    pub fn restorable_games(&self) -> Vec<String> {
-        self.games.keys().cloned().collect()
+        coverage
     }
 
-    pub fn restorable_game_set(&self) -> BTreeSet<String> {
-        self.games.keys().cloned().collect()
+    /// Select up to `size` games that have gone the longest without a backup.
+    /// Only games with existing backups or that are `detected` (e.g., installed) are considered,
+    /// since other titles in the manifest are unlikely to have any saves.
+    /// Detected games that have never been backed up come first.
+    /// A game counts as recently backed up if it was picked for a recent batch,
+    /// even if it had no saves to back up at the time.
+    pub fn select_batch(
+        &self,
+        games: &[String],
+        size: usize,
+        attempts: &BTreeMap<String, chrono::DateTime<chrono::Utc>>,
+        detected: impl Fn(&str) -> bool,
+    ) -> Vec<String> {
+        let candidates = games
+            .iter()
+            .filter(|name| self.contains_game(name) || detected(name))
+            .map(|name| {
+                let when = self.last_backup_time(name).max(attempts.get(name).copied());
+                (name.clone(), when)
+            })
+            .collect();
+        Self::sort_by_backup_age(candidates).into_iter().take(size).collect()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use velcro::{btree_map, hash_map};
 
-    use super::*;
-    use crate::testing::{drives_x, make_original_path, mapping_file_key, repo, repo_raw, s};
+    /// Oldest first, with ties broken by name so that repeated runs are stable.
+    fn sort_by_backup_age(mut candidates: Vec<(String, Option<chrono::DateTime<chrono::Utc>>)>) -> Vec<String> {
+        candidates.sort_by(|(name1, when1), (name2, when2)| when1.cmp(when2).then_with(|| name1.cmp(name2)));
+        candidates.into_iter().map(|(name, _)| name).collect()
+    }
 
-    mod individual_mapping {
-        use pretty_assertions::assert_eq;
+    fn generate_total_rename(original_name: &str) -> String {
+        format!("ludusavi-renamed-{}", encode_base64_for_folder(original_name))
+    }
 
-        use super::*;
+    fn safe_folder_name(name: &str, policy: FilenamePolicy) -> String {
+        let safe_name = escape_folder_name_for(name, policy);
 
-        #[test]
-        fn can_generate_drive_folder_name() {
-            let mut mapping = IndividualMapping::new("foo".to_owned());
-            assert_eq!("drive-0", mapping.drive_folder_name(""));
-            assert_eq!("drive-C", mapping.drive_folder_name("C:"));
-            assert_eq!("drive-D", mapping.drive_folder_name("D:"));
-            assert_eq!("drive-____C", mapping.drive_folder_name(r#"\\?\C:"#));
-            assert_eq!("drive-__remote", mapping.drive_folder_name(r#"\\remote"#));
+        if safe_name.matches(SAFE).count() == safe_name.len() {
+            // It's unreadable now, so do a total rename.
+            Self::generate_total_rename(name)
+        } else {
+            safe_name
         }
     }
 
-    mod backup_layout {
-        use pretty_assertions::assert_eq;
+    /// Fill in the folder template for a game.
+    /// Each level is made safe separately, so a game name can't add more levels.
+    fn render_folder_template(
+        template: &str,
+        game_name: &str,
+        game: Option<&Game>,
+        policy: FilenamePolicy,
+    ) -> Vec<String> {
+        let (store, id) = match (game.and_then(|x| x.steam.id), game.and_then(|x| x.gog.id)) {
+            (Some(id), _) => ("Steam", id.to_string()),
+            (None, Some(id)) => ("GOG", id.to_string()),
+            (None, None) => ("Other", "".to_string()),
+        };
 
-        use crate::testing::{repo_file_raw, repo_path, repo_path_raw};
+        template
+            .split(['/', '\\'])
+            .map(|level| {
+                level
+                    .replace("{game}", game_name)
+                    .replace("{store}", store)
+                    .replace("{id}", &id)
+            })
+            .filter(|level| !level.trim().is_empty())
+            .map(|level| Self::safe_folder_name(level.trim(), policy))
+            .collect()
+    }
 
-        use super::*;
+    pub fn game_folder(&self, game_name: &str) -> StrictPath {
+        self.game_folder_for(game_name, None)
+    }
 
-        fn layout() -> BackupLayout {
-            BackupLayout::new(StrictPath::new(format!("{}/tests/backup", repo_raw())))
+    fn game_folder_for(&self, game_name: &str, game: Option<&Game>) -> StrictPath {
+        if let Some(folder) = self.games.get::<str>(game_name) {
+            return folder.clone();
         }
 
-        fn game_layout(name: &str, path: &str) -> GameLayout {
-            GameLayout {
-                path: StrictPath::new(path.to_string()),
-                mapping: IndividualMapping::new(name.to_string()),
-            }
-        }
+        let levels = match &self.folder_template {
+            Some(template) => Self::render_folder_template(template, game_name, game, self.filename_policy),
+            None => vec![],
+        };
 
-        fn drives() -> BTreeMap<String, String> {
-            let (drive, _) = StrictPath::cwd().split_drive();
-            let folder = IndividualMapping::new_drive_folder_name(&drive);
-            btree_map! { folder: drive }
+        if levels.is_empty() {
+            self.base
+                .joined(&Self::safe_folder_name(game_name, self.filename_policy))
+        } else {
+            self.base.joined(&levels.join("/"))
         }
+    }
 
-        #[test]
-        fn can_find_existing_game_folder_with_matching_name() {
-            assert_eq!(repo_path_raw("tests/backup/game1"), layout().game_folder("game1"));
+    /// Folder for a game relative to the backup root, using `/` as the separator.
+    fn relative_game_folder(&self, game_name: &str) -> Option<String> {
+        let base = self.base.interpret().ok()?;
+        let folder = self.game_folder(game_name).interpret().ok()?;
+
+        let relative = std::path::Path::new(&folder).strip_prefix(&base).ok()?;
+        let levels: Vec<_> = relative
+            .components()
+            .map(|x| x.as_os_str().to_string_lossy().to_string())
+            .collect();
+
+        (!levels.is_empty()).then(|| levels.join("/"))
+    }
+
+    /// Folders to sync for these games, relative to the backup root,
+    /// including the shared object folder if there is one.
+    pub fn cloud_folders<'a>(&self, games: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+        let mut folders: Vec<_> = games.into_iter().filter_map(|x| self.relative_game_folder(x)).collect();
+        if self.base.joined(OBJECTS_FOLDER).is_dir() {
+            folders.push(OBJECTS_FOLDER.to_string());
         }
+        folders
+    }
 
-        #[test]
-        fn can_find_existing_game_folder_with_rename() {
+    /// Give these games' single full backups a timestamped name (see `GameLayout::timestamp_solo_backup`).
+    /// If no games are specified, then all games are checked.
+    pub fn timestamp_solo_backups(&self, games: &[String]) {
+        let games: Vec<_> = if games.is_empty() {
+            self.restorable_games()
+        } else {
+            games.to_vec()
+        };
+
+        for game in games {
+            if !self.contains_game(&game) {
+                continue;
+            }
+            if let Err(e) = self.game_layout(&game).timestamp_solo_backup() {
+                log::error!("[{game}] unable to move single full backup for cloud sync | {e}");
+            }
+        }
+    }
+
+    /// Folders of games that are excluded from cloud synchronization,
+    /// along with any shared objects that only those games use.
+    /// Objects that another game still uses are left in the sync.
+    pub fn cloud_excluded_paths<'a>(&self, games: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+        let excluded: HashSet<_> = games.into_iter().filter(|x| self.contains_game(x)).collect();
+        let mut paths: Vec<_> = excluded.iter().filter_map(|x| self.relative_game_folder(x)).collect();
+
+        if excluded.is_empty() || !self.base.joined(OBJECTS_FOLDER).is_dir() {
+            return paths;
+        }
+
+        let mut exclusive = BTreeSet::new();
+        let mut shared = HashSet::new();
+        for name in self.games.keys() {
+            let layout = self.game_layout(name);
+            let hashes = layout.mapping.object_hashes().map(|x| x.to_string());
+            if excluded.contains(name) {
+                exclusive.extend(hashes);
+            } else {
+                shared.extend(hashes);
+            }
+        }
+        paths.extend(
+            exclusive
+                .into_iter()
+                .filter(|x| !shared.contains(x))
+                .map(|x| format!("{OBJECTS_FOLDER}/{x}")),
+        );
+
+        paths
+    }
+
+    pub fn restorable_games(&self) -> Vec<String> {
+        self.games.keys().cloned().collect()
+    }
+
+    pub fn restorable_game_set(&self) -> BTreeSet<String> {
+        self.games.keys().cloned().collect()
+    }
+
+    /// Combined size of all backups for all games.
+    /// Files in the shared object folder are only counted once.
+    pub fn total_size(&self) -> u64 {
+        let mut objects = HashMap::new();
+        let stored: u64 = self
+            .games
+            .keys()
+            .map(|name| self.game_layout(name).mapping.stored_size_without_objects(&mut objects))
+            .sum();
+        stored + objects.values().sum::<u64>()
+    }
+
+    /// Read and check the mapping file of a bundle created by `GameLayout::export_bundle`.
+    pub fn read_bundle_mapping(archive: &mut zip::ZipArchive<std::fs::File>) -> Result<IndividualMapping, String> {
+        use std::io::Read;
+
+        let mut content = String::new();
+        archive
+            .by_name("mapping.yaml")
+            .map_err(|e| e.to_string())?
+            .read_to_string(&mut content)
+            .map_err(|e| e.to_string())?;
+        let mapping = IndividualMapping::load_from_string(&content).map_err(|e| e.to_string())?;
+
+        if mapping.name.trim().is_empty() {
+            return Err("mapping.yaml does not specify a game name".to_string());
+        }
+        if mapping.backups.is_empty() {
+            return Err("mapping.yaml does not list any backups".to_string());
+        }
+
+        let mut names = HashSet::new();
+        for name in mapping
+            .backups
+            .iter()
+            .flat_map(|full| std::iter::once(&full.name).chain(full.children.iter().map(|diff| &diff.name)))
+        {
+            if name != SOLO && (!name.starts_with("backup-") || name.contains(['/', '\\'])) {
+                return Err(format!("mapping.yaml contains an invalid backup name: {name}"));
+            }
+            if !names.insert(name) {
+                return Err(format!("mapping.yaml contains a duplicate backup: {name}"));
+            }
+        }
+
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i).map_err(|e| e.to_string())?;
+            if entry.enclosed_name().is_none() {
+                return Err(format!("bundle contains an unsafe path: {}", entry.name()));
+            }
+        }
+
+        Ok(mapping)
+    }
+
+    /// Add a game's backups from a bundle created by `GameLayout::export_bundle`.
+    /// The game folder is chosen the same way as for a new backup.
+    /// If the game already has backups here, this fails unless `merge` is set,
+    /// in which case only the bundle's backups that aren't here yet are added.
+    /// Returns the name of the imported game.
+    pub fn import_bundle(&mut self, source: &StrictPath, manifest: &Manifest, merge: bool) -> Result<String, Error> {
+        let invalid = |why: String| {
+            log::error!("Invalid backup bundle: {:?} | {why}", source);
+            Error::BundleInvalid {
+                path: source.clone(),
+                why,
+            }
+        };
+        let failed = |why: String| {
+            log::error!("Unable to import backup bundle: {:?} | {why}", source);
+            Error::BundleImportFailed { why }
+        };
+
+        let file = source.open().map_err(|e| invalid(e.to_string()))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| invalid(e.to_string()))?;
+        let incoming = Self::read_bundle_mapping(&mut archive).map_err(invalid)?;
+
+        let name = incoming.name.clone();
+        let mut layout = self.game_layout_for(&name, manifest.0.get(&name));
+        if layout.has_backups() && !merge {
+            return Err(Error::BundleGameAlreadyBackedUp { game: name });
+        }
+
+        for (folder, drive) in &incoming.drives {
+            match layout.mapping.drives.get(folder) {
+                Some(existing) if existing != drive => {
+                    return Err(failed(format!("drive folder {folder} refers to a different drive")));
+                }
+                _ => {}
+            }
+        }
+
+        let mut added = HashSet::new();
+        for full in &incoming.backups {
+            match layout.mapping.backups.iter().position(|x| x.name == full.name) {
+                Some(index) => {
+                    if layout.mapping.backups[index].when != full.when {
+                        return Err(failed(format!("backup {} differs from the existing one", full.name)));
+                    }
+                    for diff in &full.children {
+                        if layout.mapping.backups[index]
+                            .children
+                            .iter()
+                            .any(|x| x.name == diff.name)
+                        {
+                            continue;
+                        }
+                        if layout.mapping.has_backup(&diff.name) {
+                            return Err(failed(format!("backup {} differs from the existing one", diff.name)));
+                        }
+                        layout.mapping.backups[index].children.push_back(diff.clone());
+                        added.insert(diff.name.clone());
+                    }
+                    layout.mapping.backups[index]
+                        .children
+                        .make_contiguous()
+                        .sort_by_key(|x| x.when);
+                }
+                None => {
+                    for diff in &full.children {
+                        if layout.mapping.has_backup(&diff.name) {
+                            return Err(failed(format!("backup {} differs from the existing one", diff.name)));
+                        }
+                        added.insert(diff.name.clone());
+                    }
+                    layout.mapping.backups.push_back(full.clone());
+                    added.insert(full.name.clone());
+                }
+            }
+        }
+        layout.mapping.backups.make_contiguous().sort_by_key(|x| x.when);
+        layout.mapping.drives.extend(incoming.drives.clone());
+
+        if added.is_empty() {
+            log::info!("[{name}] bundle has no new backups to import");
+            return Ok(name);
+        }
+
+        let objects: HashSet<_> = layout.mapping.object_hashes().map(|x| x.to_string()).collect();
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).map_err(|e| failed(e.to_string()))?;
+            if !entry.is_file() {
+                continue;
+            }
+            let Some(relative) = entry.enclosed_name().map(|x| {
+                x.components()
+                    .map(|x| x.as_os_str().to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }) else {
+                continue;
+            };
+
+            let target = if let Some(hash) = relative.strip_prefix(&format!("{OBJECTS_FOLDER}/")) {
+                let object = layout.object_file(hash);
+                if !objects.contains(hash) || object.is_file() {
+                    continue;
+                }
+                object
+            } else if relative == "mapping.yaml" {
+                continue;
+            } else {
+                let owner = relative
+                    .split('/')
+                    .next()
+                    .filter(|x| x.starts_with("backup-"))
+                    .unwrap_or(SOLO);
+                if !added.contains(owner) {
+                    continue;
+                }
+                layout.path.joined(&relative)
+            };
+
+            log::debug!("[{name}] importing: {} -> {:?}", &relative, &target);
+            target.create_parent_dir().map_err(|e| failed(e.to_string()))?;
+            let mut handle = target.create().map_err(|e| failed(e.to_string()))?;
+            std::io::copy(&mut entry, &mut handle).map_err(|e| failed(e.to_string()))?;
+            if let Some(mtime) = entry.last_modified() {
+                if let Err(e) = target.set_mtime_zip(mtime) {
+                    log::warn!("[{name}] unable to set modification time: {:?} | {e:?}", &target);
+                }
+            }
+        }
+
+        layout.save();
+        self.games_lowercase.insert(name.to_lowercase(), layout.path.clone());
+        self.games.insert(name.clone(), layout.path);
+
+        Ok(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use velcro::{btree_map, hash_map};
+
+    use super::*;
+    use crate::testing::{drives_x, make_original_path, mapping_file_key, repo, repo_raw, s};
+
+    mod individual_mapping {
+        use pretty_assertions::assert_eq;
+
+        use super::*;
+
+        #[test]
+        fn can_generate_drive_folder_name() {
+            let mut mapping = IndividualMapping::new("foo".to_owned());
+            assert_eq!("drive-0", mapping.drive_folder_name(""));
+            assert_eq!("drive-C", mapping.drive_folder_name("C:"));
+            assert_eq!("drive-D", mapping.drive_folder_name("D:"));
+            assert_eq!("drive-____C", mapping.drive_folder_name(r#"\\?\C:"#));
+            assert_eq!("drive-__remote", mapping.drive_folder_name(r#"\\remote"#));
+        }
+    }
+
+    mod backup_layout {
+        use pretty_assertions::assert_eq;
+
+        use crate::{
+            resource::manifest::{GogMetadata, SteamMetadata},
+            testing::{repo_file_raw, repo_path, repo_path_raw},
+        };
+
+        use super::*;
+
+        fn layout() -> BackupLayout {
+            BackupLayout::new(StrictPath::new(format!("{}/tests/backup", repo_raw())))
+        }
+
+        fn game_layout(name: &str, path: &str) -> GameLayout {
+            GameLayout {
+                path: StrictPath::new(path.to_string()),
+                mapping: IndividualMapping::new(name.to_string()),
+                ..Default::default()
+            }
+        }
+
+        fn drives() -> BTreeMap<String, String> {
+            let (drive, _) = StrictPath::cwd().split_drive();
+            let folder = IndividualMapping::new_drive_folder_name(&drive);
+            btree_map! { folder: drive }
+        }
+
+        #[test]
+        fn can_find_file_covered_by_backups() {
+            let coverage = layout().find_file(&StrictPath::new(s("X:/file1.txt")));
+
+            assert_eq!(
+                vec![(s("game1"), s(SOLO)), (s("game1-zipped"), s("test.zip"))],
+                coverage
+                    .iter()
+                    .map(|x| (x.game.clone(), x.backup.name().to_string()))
+                    .collect::<Vec<_>>(),
+            );
+            for x in coverage {
+                assert_eq!("3a52ce780950d4d969792a2559cd519d7ee8c727", x.file.hash);
+            }
+        }
+
+        #[test]
+        fn can_find_file_not_covered_by_backups() {
+            assert!(layout().find_file(&StrictPath::new(s("X:/nonexistent.txt"))).is_empty());
+        }
+
+        #[test]
+        fn can_sort_games_by_backup_age() {
+            let when = |day| {
+                chrono::NaiveDate::from_ymd_opt(2000, 1, day)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+                    .and_local_timezone(chrono::Utc)
+                    .single()
+            };
+
+            assert_eq!(
+                vec![s("never"), s("oldest-a"), s("oldest-b"), s("middle"), s("newest")],
+                BackupLayout::sort_by_backup_age(vec![
+                    (s("newest"), when(3)),
+                    (s("oldest-b"), when(1)),
+                    (s("never"), None),
+                    (s("middle"), when(2)),
+                    (s("oldest-a"), when(1)),
+                ]),
+            );
+        }
+
+        #[test]
+        fn can_select_batch_of_games_by_backup_age() {
+            let games = vec![s("game1"), s("game3"), s("nonexistent")];
+            let detected = |name: &str| name == "nonexistent";
+
+            assert_eq!(
+                vec![s("nonexistent"), s("game1")],
+                layout().select_batch(&games, 2, &BTreeMap::new(), detected),
+            );
+            assert_eq!(
+                vec![s("nonexistent"), s("game1"), s("game3")],
+                layout().select_batch(&games, 10, &BTreeMap::new(), detected),
+            );
+        }
+
+        #[test]
+        fn can_select_batch_of_games_without_undetected_titles() {
+            let games = vec![s("game1"), s("game3"), s("nonexistent")];
+
+            assert_eq!(
+                vec![s("game1"), s("game3")],
+                layout().select_batch(&games, 2, &BTreeMap::new(), |_| false),
+            );
+        }
+
+        #[test]
+        fn can_select_batch_of_games_past_ones_without_saves() {
+            let games = vec![s("game1"), s("game3"), s("nonexistent")];
+            let attempts = btree_map! {
+                s("nonexistent"): chrono::Utc::now(),
+            };
+
+            assert_eq!(
+                vec![s("game1"), s("game3")],
+                layout().select_batch(&games, 2, &attempts, |_| true),
+            );
+        }
+
+        #[test]
+        fn can_get_backup_history() {
+            assert_eq!(
+                Some(BackupHistory {
+                    size: 3,
+                    duration_ms: None
+                }),
+                layout().backup_history("game1"),
+            );
+            assert_eq!(None, layout().backup_history("nonexistent"));
+        }
+
+        #[test]
+        fn can_find_existing_game_folder_with_matching_name() {
+            assert_eq!(repo_path_raw("tests/backup/game1"), layout().game_folder("game1"));
+        }
+
+        #[test]
+        fn can_find_existing_game_folder_with_rename() {
             assert_eq!(
                 repo_path_raw("tests/backup/game3-renamed"),
                 layout().game_folder("game3")
@@ -2344,6 +6077,146 @@ mod tests {
             assert_eq!(repo_path("tests/backup/_._"), layout().game_folder("..."));
         }
 
+        #[test]
+        fn can_escape_folder_names_by_policy() {
+            // name, portable, windows, posix
+            let cases = [
+                (".hidden", "_hidden", ".hidden", ".hidden"),
+                ("Game.", "Game_", "Game_", "Game."),
+                ("...", "_._", ".._", "..."),
+                ("..", "__", "._", "__"),
+                (".", "_", "_", "_"),
+                ("a:b*c?", "a_b_c_", "a_b_c_", "a:b*c?"),
+                (r#"<"a">|b"#, "__a___b", "__a___b", r#"<"a">|b"#),
+                (r"a/b\c", "a_b_c", "a_b_c", "a_b_c"),
+                ("a\0b", "a_b", "a_b", "a_b"),
+            ];
+
+            for (name, portable, windows, posix) in cases {
+                assert_eq!(
+                    portable,
+                    escape_folder_name_for(name, FilenamePolicy::Portable),
+                    "{name}"
+                );
+                assert_eq!(windows, escape_folder_name_for(name, FilenamePolicy::Windows), "{name}");
+                assert_eq!(posix, escape_folder_name_for(name, FilenamePolicy::Posix), "{name}");
+            }
+        }
+
+        #[test]
+        fn can_determine_game_folder_that_does_not_exist_with_filename_policy() {
+            let layout = layout().with_filename_policy(FilenamePolicy::Posix);
+            assert_eq!(repo_path("tests/backup/.hidden:1"), layout.game_folder(".hidden:1"));
+            assert_eq!(repo_path("tests/backup/***"), layout.game_folder("***"));
+
+            let layout = layout.with_filename_policy(FilenamePolicy::Windows);
+            assert_eq!(repo_path("tests/backup/.hidden_1"), layout.game_folder(".hidden:1"));
+        }
+
+        #[test]
+        fn can_find_existing_game_folder_regardless_of_filename_policy() {
+            let layout = layout().with_filename_policy(FilenamePolicy::Posix);
+            assert_eq!(repo_path_raw("tests/backup/game3-renamed"), layout.game_folder("game3"));
+        }
+
+        #[test]
+        fn can_determine_game_folder_that_does_not_exist_with_template() {
+            let layout = layout().with_folder_template(Some(s("{store}/{game} [{id}]")));
+
+            let steam = Game {
+                steam: SteamMetadata { id: Some(1) },
+                ..Default::default()
+            };
+            assert_eq!(
+                repo_path("tests/backup/Steam/foo_bar [1]"),
+                layout.game_folder_for("foo:bar", Some(&steam))
+            );
+            assert_eq!(
+                repo_path("tests/backup/Other/a_b []"),
+                layout.game_folder_for("a/b", None)
+            );
+        }
+
+        #[test]
+        fn ignores_folder_template_without_game() {
+            let layout = layout().with_folder_template(Some(s("{store}")));
+            assert_eq!(repo_path("tests/backup/foo"), layout.game_folder("foo"));
+        }
+
+        #[test]
+        fn can_find_existing_game_folder_regardless_of_template() {
+            let layout = layout().with_folder_template(Some(s("{store}/{game}")));
+            assert_eq!(repo_path_raw("tests/backup/game1"), layout.game_folder("game1"));
+        }
+
+        #[test]
+        fn can_find_nested_game_folders_from_template() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-folder-template"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source = path.joined("live/save.txt");
+            source.create_parent_dir().unwrap();
+            source.write_with_content("foo").unwrap();
+
+            let game = Game {
+                gog: GogMetadata { id: Some(2) },
+                ..Default::default()
+            };
+            let scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(3, source.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            let info = BackupLayout::new(backups.clone())
+                .with_folder_template(Some(s("{store}/{game}")))
+                .game_layout_for("game1", Some(&game))
+                .back_up(
+                    &scan,
+                    &chrono::Utc::now(),
+                    &BackupFormats::default(),
+                    Retention::new(1, 1),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert!(info.successful());
+            assert!(backups.joined("GOG/game1/mapping.yaml").is_file());
+
+            let layout = BackupLayout::new(backups.clone());
+            assert_eq!(vec![s("game1")], layout.restorable_games());
+            assert_eq!(vec![s("GOG/game1")], layout.cloud_folders(&[s("game1")]));
+
+            // A game that was backed up before the template, and whose name matches a template folder.
+            let info = BackupLayout::new(backups.clone())
+                .game_layout("GOG")
+                .back_up(
+                    &ScanInfo {
+                        game_name: s("GOG"),
+                        ..scan.clone()
+                    },
+                    &chrono::Utc::now(),
+                    &BackupFormats::default(),
+                    Retention::new(1, 1),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert!(info.successful());
+            assert!(backups.joined("GOG/mapping.yaml").is_file());
+
+            let mut games = BackupLayout::new(backups.clone()).restorable_games();
+            games.sort();
+            assert_eq!(vec![s("GOG"), s("game1")], games);
+
+            let _ = path.remove();
+        }
+
         #[test]
         fn can_find_irrelevant_backup_files() {
             assert_eq!(
@@ -2404,10 +6277,11 @@ mod tests {
             let layout = GameLayout {
                 path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
                 mapping: IndividualMapping::new("game1".to_string()),
+                ..Default::default()
             };
             assert_eq!(
                 None,
-                layout.plan_backup(&scan, &now(), &BackupFormats::default(), Retention::default())
+                layout.plan_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None)
             );
         }
 
@@ -2574,31 +6448,139 @@ mod tests {
                     when: now(),
                     os: Some(Os::HOST),
                     files: btree_map! {
-                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1 },
-                        StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2 },
-                        StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5 },
+                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1, base: None, object: false, mtime: None },
+                        StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2, base: None, object: false, mtime: None },
+                        StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5, base: None, object: false, mtime: None },
                     },
                     ..Default::default()
                 },
-                layout.plan_full_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_full_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
         #[test]
-        #[cfg(target_os = "windows")]
-        fn can_plan_full_backup_with_registry() {
-            use crate::scan::registry::{Entries, Entry, Hives, Keys};
-
-            // `Hives` only loads values that actually exist.
-            // Realistically, if a value is marked as removed`, then it won't exist, so `Hives` won't load it.
-            // The removed value here only makes it into the plan because it actually does exist.
+        fn can_plan_full_backup_with_timestamped_name_when_forced() {
             let scan = ScanInfo {
-                found_registry_keys: hash_map! {
-                    "HKEY_CURRENT_USER/Software/Ludusavi".into(): ScannedRegistry::new().change_as(ScanChange::New).ignored(),
-                    "HKEY_CURRENT_USER/Software/Ludusavi/game3".into(): ScannedRegistry::new().change_as(ScanChange::Different)
-                        .with_value("binary", ScanChange::New, false)
-                        .with_value("dword", ScanChange::Different, false)
-                        .with_value("expandSz", ScanChange::Removed, false)
+                found_files: hash_map! {
+                    repo_file("new").into(): ScannedFile::with_change(1, "n", ScanChange::New),
+                },
+                ..Default::default()
+            };
+            let layout = GameLayout::default();
+            assert_eq!(
+                FullBackup {
+                    name: "backup-20000102T030405Z".to_string(),
+                    when: now(),
+                    os: Some(Os::HOST),
+                    files: btree_map! {
+                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1, base: None, object: false, mtime: None },
+                    },
+                    ..Default::default()
+                },
+                layout.plan_full_backup(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default().with_force_timestamped_name(true),
+                    None,
+                ),
+            );
+        }
+
+        #[test]
+        fn can_plan_full_backup_with_comment_template() {
+            let scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    repo_file("new").into(): ScannedFile::with_change(1, "n", ScanChange::New),
+                    repo_file("ignored").into(): ScannedFile::with_change(2, "i", ScanChange::New).ignored(),
+                },
+                ..Default::default()
+            };
+            let layout = GameLayout::default();
+
+            let backup = layout.plan_full_backup(
+                &scan,
+                &now(),
+                &BackupFormats::default(),
+                Retention::default(),
+                Some("before {game} patch on {date} ({count})"),
+            );
+            assert_eq!(Some(s("before game1 patch on 20000102T030405Z (1)")), backup.comment,);
+
+            let backup = layout.plan_full_backup(
+                &scan,
+                &now(),
+                &BackupFormats::default(),
+                Retention::default(),
+                Some("  "),
+            );
+            assert_eq!(None, backup.comment);
+        }
+
+        #[test]
+        fn can_plan_full_backup_with_hash_algorithm() {
+            let scan = ScanInfo {
+                found_files: hash_map! {
+                    repo_file("new").into(): ScannedFile::with_change(1, "n", ScanChange::New),
+                },
+                hash_algorithm: HashAlgorithm::Sha256,
+                ..Default::default()
+            };
+            let layout = GameLayout::default();
+            assert_eq!(
+                FullBackup {
+                    name: ".".to_string(),
+                    when: now(),
+                    os: Some(Os::HOST),
+                    hash_algorithm: HashAlgorithm::Sha256,
+                    files: btree_map! {
+                        StrictPath::new(repo_file("new")).render(): IndividualMappingFile { hash: "n".into(), size: 1, base: None, object: false, mtime: None },
+                    },
+                    ..Default::default()
+                },
+                layout.plan_full_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
+            );
+        }
+
+        #[test]
+        fn can_plan_full_backup_instead_of_differential_when_hash_algorithm_changes() {
+            let scan = ScanInfo {
+                found_files: hash_map! {
+                    repo_file("new").into(): ScannedFile::with_change(1, "n", ScanChange::New),
+                },
+                hash_algorithm: HashAlgorithm::Sha256,
+                ..Default::default()
+            };
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    backups: VecDeque::from_iter(vec![FullBackup::default()]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            assert_eq!(BackupKind::Differential, layout.plan_backup_kind(Retention::new(1, 1)));
+
+            let backup = layout.plan_backup(&scan, &now(), &BackupFormats::default(), Retention::new(1, 1), None);
+            assert!(matches!(backup, Some(Backup::Full(_))));
+            assert_eq!(Some(HashAlgorithm::Sha256), backup.map(|x| x.hash_algorithm()));
+        }
+
+        #[test]
+        #[cfg(target_os = "windows")]
+        fn can_plan_full_backup_with_registry() {
+            use crate::scan::registry::{Entries, Entry, Hives, Keys};
+
+            // `Hives` only loads values that actually exist.
+            // Realistically, if a value is marked as removed`, then it won't exist, so `Hives` won't load it.
+            // The removed value here only makes it into the plan because it actually does exist.
+            let scan = ScanInfo {
+                found_registry_keys: hash_map! {
+                    "HKEY_CURRENT_USER/Software/Ludusavi".into(): ScannedRegistry::new().change_as(ScanChange::New).ignored(),
+                    "HKEY_CURRENT_USER/Software/Ludusavi/game3".into(): ScannedRegistry::new().change_as(ScanChange::Different)
+                        .with_value("binary", ScanChange::New, false)
+                        .with_value("dword", ScanChange::Different, false)
+                        .with_value("expandSz", ScanChange::Removed, false)
                         .with_value("multiSz", ScanChange::Same, false)
                         .with_value("qword", ScanChange::Same, true)
                         .with_value("sz", ScanChange::Unknown, false),
@@ -2623,12 +6605,14 @@ mod tests {
                     name: SOLO.to_string(),
                     when: now(),
                     os: Some(Os::HOST),
+                    symlinks: BTreeMap::new(),
+                    hardlinks: BTreeMap::new(),
                     registry: IndividualMappingRegistry {
                         hash: hives.sha1(registry::Format::Reg),
                     },
                     ..Default::default()
                 },
-                layout.plan_full_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_full_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
@@ -2651,9 +6635,9 @@ mod tests {
                         name: SOLO.to_string(),
                         when: past(),
                         files: btree_map! {
-                            StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2 },
-                            StrictPath::new(repo_file("removed")).render(): IndividualMappingFile { hash: "r".into(), size: 3 },
-                            StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5 },
+                            StrictPath::new(repo_file("different")).render(): IndividualMappingFile { hash: "d".into(), size: 2, base: None, object: false, mtime: None },
+                            StrictPath::new(repo_file("removed")).render(): IndividualMappingFile { hash: "r".into(), size: 3, base: None, object: false, mtime: None },
+                            StrictPath::new(repo_file("same")).render(): IndividualMappingFile { hash: "s".into(), size: 5, base: None, object: false, mtime: None },
                         },
                         ..Default::default()
                     }]),
@@ -2667,14 +6651,16 @@ mod tests {
                     when: now(),
                     os: Some(Os::HOST),
                     files: btree_map! {
-                        StrictPath::new(repo_file("new")).render(): Some(IndividualMappingFile { hash: "n".into(), size: 1 }),
-                        StrictPath::new(repo_file("different")).render(): Some(IndividualMappingFile { hash: "d+".into(), size: 2 }),
+                        StrictPath::new(repo_file("new")).render(): Some(IndividualMappingFile { hash: "n".into(), size: 1, base: None, object: false, mtime: None }),
+                        StrictPath::new(repo_file("different")).render(): Some(IndividualMappingFile { hash: "d+".into(), size: 2, base: None, object: false, mtime: None }),
                         StrictPath::new(repo_file("removed")).render(): None,
                     },
+                    symlinks: BTreeMap::new(),
+                    hardlinks: BTreeMap::new(),
                     registry: None,
                     ..Default::default()
                 },
-                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
@@ -2698,9 +6684,9 @@ mod tests {
                         name: SOLO.to_string(),
                         when: past(),
                         files: btree_map! {
-                            StrictPath::new(repo_file("file1")).render(): IndividualMappingFile { hash: "1".into(), size: 1 },
-                            StrictPath::new(repo_file("file2")).render(): IndividualMappingFile { hash: "2".into(), size: 2 },
-                            StrictPath::new(repo_file("file3")).render(): IndividualMappingFile { hash: "3".into(), size: 3 },
+                            StrictPath::new(repo_file("file1")).render(): IndividualMappingFile { hash: "1".into(), size: 1, base: None, object: false, mtime: None },
+                            StrictPath::new(repo_file("file2")).render(): IndividualMappingFile { hash: "2".into(), size: 2, base: None, object: false, mtime: None },
+                            StrictPath::new(repo_file("file3")).render(): IndividualMappingFile { hash: "3".into(), size: 3, base: None, object: false, mtime: None },
                         },
                         children: VecDeque::from([DifferentialBackup {
                             name: format!("backup-{}-diff", now_str()),
@@ -2729,10 +6715,12 @@ mod tests {
                         // New ignore:
                         StrictPath::new(repo_file("file2")).render(): None,
                     },
+                    symlinks: BTreeMap::new(),
+                    hardlinks: BTreeMap::new(),
                     registry: None,
                     ..Default::default()
                 },
-                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
@@ -2752,6 +6740,8 @@ mod tests {
                     backups: VecDeque::from_iter(vec![FullBackup {
                         name: SOLO.to_string(),
                         when: past(),
+                        symlinks: BTreeMap::new(),
+                        hardlinks: BTreeMap::new(),
                         registry: IndividualMappingRegistry { hash: None },
                         ..Default::default()
                     }]),
@@ -2769,12 +6759,14 @@ mod tests {
                     name: format!("backup-{}-diff", now_str()),
                     when: now(),
                     os: Some(Os::HOST),
+                    symlinks: BTreeMap::new(),
+                    hardlinks: BTreeMap::new(),
                     registry: Some(IndividualMappingRegistry {
                         hash: hives.sha1(registry::Format::Reg),
                     }),
                     ..Default::default()
                 },
-                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
@@ -2797,6 +6789,8 @@ mod tests {
                     backups: VecDeque::from_iter(vec![FullBackup {
                         name: SOLO.to_string(),
                         when: past(),
+                        symlinks: BTreeMap::new(),
+                        hardlinks: BTreeMap::new(),
                         registry: IndividualMappingRegistry {
                             hash: Some("foo".into()),
                         },
@@ -2816,12 +6810,14 @@ mod tests {
                     name: format!("backup-{}-diff", now_str()),
                     when: now(),
                     os: Some(Os::HOST),
+                    symlinks: BTreeMap::new(),
+                    hardlinks: BTreeMap::new(),
                     registry: Some(IndividualMappingRegistry {
                         hash: hives.sha1(registry::Format::Reg),
                     }),
                     ..Default::default()
                 },
-                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
@@ -2846,6 +6842,8 @@ mod tests {
                     backups: VecDeque::from_iter(vec![FullBackup {
                         name: SOLO.to_string(),
                         when: past(),
+                        symlinks: BTreeMap::new(),
+                        hardlinks: BTreeMap::new(),
                         registry: IndividualMappingRegistry {
                             hash: hives.sha1(registry::Format::Reg),
                         },
@@ -2860,10 +6858,12 @@ mod tests {
                     name: format!("backup-{}-diff", now_str()),
                     when: now(),
                     os: Some(Os::HOST),
+                    symlinks: BTreeMap::new(),
+                    hardlinks: BTreeMap::new(),
                     registry: None,
                     ..Default::default()
                 },
-                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
@@ -2881,6 +6881,8 @@ mod tests {
                     backups: VecDeque::from_iter(vec![FullBackup {
                         name: SOLO.to_string(),
                         when: past(),
+                        symlinks: BTreeMap::new(),
+                        hardlinks: BTreeMap::new(),
                         registry: IndividualMappingRegistry {
                             hash: Some("foo".into()),
                         },
@@ -2895,10 +6897,12 @@ mod tests {
                     name: format!("backup-{}-diff", now_str()),
                     when: now(),
                     os: Some(Os::HOST),
+                    symlinks: BTreeMap::new(),
+                    hardlinks: BTreeMap::new(),
                     registry: Some(IndividualMappingRegistry { hash: None }),
                     ..Default::default()
                 },
-                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default()),
+                layout.plan_differential_backup(&scan, &now(), &BackupFormats::default(), Retention::default(), None),
             );
         }
 
@@ -2935,7 +6939,7 @@ mod tests {
                 ..Default::default()
             };
 
-            layout.forget_excess_backups(Retention::new(1, 1));
+            layout.forget_excess_backups(Retention::new(1, 1), &now());
             assert_eq!(
                 VecDeque::from_iter(vec![FullBackup {
                     name: "2".to_string(),
@@ -2970,7 +6974,7 @@ mod tests {
                 ..Default::default()
             };
 
-            layout.forget_excess_backups(Retention::new(1, 0));
+            layout.forget_excess_backups(Retention::new(1, 0), &now());
             assert_eq!(
                 VecDeque::from_iter(vec![FullBackup {
                     name: SOLO.to_string(),
@@ -3034,7 +7038,7 @@ mod tests {
                 ..Default::default()
             };
 
-            layout.forget_excess_backups(Retention::new(1, 1));
+            layout.forget_excess_backups(Retention::new(1, 1), &now());
             assert_eq!(
                 VecDeque::from_iter(vec![
                     FullBackup {
@@ -3070,170 +7074,454 @@ mod tests {
             );
         }
 
-        fn make_path(file: &str) -> StrictPath {
-            repo_path(&format!("tests/backup/game1/{}", file))
-        }
-
-        fn make_restorable_path(backup: &str, file: &str) -> StrictPath {
-            StrictPath::relative(
-                format!(
-                    "{backup}/drive-{}/{file}",
-                    if cfg!(target_os = "windows") { "X" } else { "0" }
-                ),
-                Some(repo_file_raw("tests/backup/game1")),
-            )
-        }
-
-        fn make_restorable_path_zip(file: &str) -> StrictPath {
-            StrictPath::new(format!(
-                "drive-{}/{file}",
-                if cfg!(target_os = "windows") { "X" } else { "0" }
-            ))
-        }
-
         #[test]
-        fn can_report_restorable_files_for_full_backup_in_simple_format() {
+        fn can_plan_pruning_without_changes() {
             let layout = GameLayout {
-                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
                 mapping: IndividualMapping {
-                    name: "game1".to_string(),
-                    drives: drives_x(),
-                    backups: VecDeque::from(vec![FullBackup {
-                        name: "backup-1".into(),
-                        when: past(),
-                        files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
+                    backups: VecDeque::from_iter(vec![
+                        FullBackup {
+                            name: "1".to_string(),
+                            locked: true,
+                            children: VecDeque::from_iter(vec![
+                                DifferentialBackup {
+                                    name: "1-a".to_string(),
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "1-b".to_string(),
+                                    ..Default::default()
+                                },
+                            ]),
+                            ..Default::default()
                         },
-                        ..Default::default()
-                    }]),
+                        FullBackup {
+                            name: "2".to_string(),
+                            children: VecDeque::from_iter(vec![DifferentialBackup {
+                                name: "2-a".to_string(),
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "3".to_string(),
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
                 },
+                ..Default::default()
             };
+            let original = layout.mapping.clone();
+
             assert_eq!(
-                hash_map! {
-                    make_restorable_path("backup-1", "file1.txt"): ScannedFile {
-                        size: 1,
-                        hash: "old".into(),
-                        original_path: Some(make_original_path("/file1.txt")),
-                        ignored: false,
-                        change: Default::default(),
-                        container: None,
-                        redirected: None,
-                    },
-                    make_restorable_path("backup-1", "file2.txt"): ScannedFile {
-                        size: 2,
-                        hash: "old".into(),
-                        original_path: Some(make_original_path("/file2.txt")),
-                        ignored: false,
-                        change: Default::default(),
-                        container: None,
-                        redirected: None,
-                    },
-                },
-                layout.restorable_files(&BackupId::Latest, ScanKind::Backup, &[], false, &Default::default()),
+                vec![
+                    BackupId::Named("1-a".to_string()),
+                    BackupId::Named("2".to_string()),
+                    BackupId::Named("2-a".to_string()),
+                ],
+                layout.plan_pruning(Retention::new(1, 1), &now()),
             );
+            assert_eq!(original, layout.mapping);
         }
 
         #[test]
-        fn can_report_restorable_files_for_full_backup_in_zip_format() {
-            let layout = GameLayout {
-                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+        fn can_forget_excess_backups_by_age() {
+            let ago = |seconds: i64| now() - chrono::Duration::seconds(seconds);
+            let hour = 60 * 60;
+            let day = 24 * hour;
+
+            let mut layout = GameLayout {
                 mapping: IndividualMapping {
-                    name: "game1".to_string(),
-                    drives: drives_x(),
-                    backups: VecDeque::from(vec![FullBackup {
-                        name: "backup-1.zip".into(),
-                        when: past(),
-                        files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
+                    backups: VecDeque::from_iter(vec![
+                        FullBackup {
+                            name: "1".to_string(),
+                            when: ago(3 * day),
+                            locked: true,
+                            ..Default::default()
                         },
-                        ..Default::default()
-                    }]),
+                        FullBackup {
+                            name: "2".to_string(),
+                            when: ago(2 * day),
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "3".to_string(),
+                            when: ago(day),
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "4".to_string(),
+                            when: ago(2 * hour),
+                            children: VecDeque::from_iter(vec![
+                                DifferentialBackup {
+                                    name: "4-a".to_string(),
+                                    when: ago(50 * 60),
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "4-b".to_string(),
+                                    when: ago(30 * 60),
+                                    ..Default::default()
+                                },
+                            ]),
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let retention = Retention {
+                policy: RetentionPolicy::Age,
+                age: AgeRetention {
+                    within_hours: 0,
+                    hourly: 2,
+                    daily: 2,
+                    weekly: 0,
                 },
+                ..Default::default()
             };
+            layout.forget_excess_backups(retention, &now());
             assert_eq!(
-                hash_map! {
-                    make_restorable_path_zip("file1.txt"): ScannedFile {
-                        size: 1,
-                        hash: "old".into(),
-                        original_path: Some(make_original_path("/file1.txt")),
-                        ignored: false,
-                        change: Default::default(),
-                        container: Some(make_path("backup-1.zip")),
-                        redirected: None,
+                VecDeque::from_iter(vec![
+                    FullBackup {
+                        name: "1".to_string(),
+                        when: ago(3 * day),
+                        locked: true,
+                        ..Default::default()
                     },
-                    make_restorable_path_zip("file2.txt"): ScannedFile {
-                        size: 2,
-                        hash: "old".into(),
-                        original_path: Some(make_original_path("/file2.txt")),
-                        ignored: false,
-                        change: Default::default(),
-                        container: Some(make_path("backup-1.zip")),
-                        redirected: None,
+                    FullBackup {
+                        name: "3".to_string(),
+                        when: ago(day),
+                        ..Default::default()
                     },
-                },
-                layout.restorable_files(&BackupId::Latest, ScanKind::Backup, &[], false, &Default::default()),
+                    FullBackup {
+                        name: "4".to_string(),
+                        when: ago(2 * hour),
+                        children: VecDeque::from_iter(vec![DifferentialBackup {
+                            name: "4-b".to_string(),
+                            when: ago(30 * 60),
+                            ..Default::default()
+                        }]),
+                        ..Default::default()
+                    },
+                ]),
+                layout.mapping.backups,
             );
         }
 
         #[test]
-        fn can_report_restorable_files_for_differential_backup_in_simple_format() {
-            let layout = GameLayout {
-                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+        fn can_forget_excess_backups_by_age_but_keep_newest_when_idle() {
+            let ago = |seconds: i64| now() - chrono::Duration::seconds(seconds);
+            let day = 24 * 60 * 60;
+
+            let mut layout = GameLayout {
                 mapping: IndividualMapping {
-                    name: "game1".to_string(),
-                    drives: drives_x(),
-                    backups: VecDeque::from(vec![FullBackup {
-                        name: "backup-1".into(),
-                        when: past(),
-                        files: btree_map! {
-                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3 },
+                    backups: VecDeque::from_iter(vec![
+                        FullBackup {
+                            name: "1".to_string(),
+                            when: ago(90 * day),
+                            ..Default::default()
                         },
-                        children: VecDeque::from([DifferentialBackup {
-                            name: "backup-2".into(),
-                            when: past2(),
-                            files: btree_map! {
-                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
-                                mapping_file_key("/delete.txt"): None,
-                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
-                            },
+                        FullBackup {
+                            name: "2".to_string(),
+                            when: ago(80 * day),
+                            children: VecDeque::from_iter(vec![
+                                DifferentialBackup {
+                                    name: "2-a".to_string(),
+                                    when: ago(70 * day),
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "2-b".to_string(),
+                                    when: ago(60 * day),
+                                    ..Default::default()
+                                },
+                            ]),
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let retention = Retention {
+                policy: RetentionPolicy::Age,
+                ..Default::default()
+            };
+            layout.forget_excess_backups(retention, &now());
+            assert_eq!(
+                VecDeque::from_iter(vec![FullBackup {
+                    name: "2".to_string(),
+                    when: ago(80 * day),
+                    children: VecDeque::from_iter(vec![DifferentialBackup {
+                        name: "2-b".to_string(),
+                        when: ago(60 * day),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }]),
+                layout.mapping.backups,
+            );
+        }
+
+        #[test]
+        fn can_forget_excess_backups_by_age_with_all_windows_empty() {
+            let ago = |seconds: i64| now() - chrono::Duration::seconds(seconds);
+
+            let mut layout = GameLayout {
+                mapping: IndividualMapping {
+                    backups: VecDeque::from_iter(vec![
+                        FullBackup {
+                            name: "1".to_string(),
+                            when: ago(30),
+                            locked: true,
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "2".to_string(),
+                            when: ago(20),
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "3".to_string(),
+                            when: ago(10),
+                            children: VecDeque::from_iter(vec![
+                                DifferentialBackup {
+                                    name: "3-a".to_string(),
+                                    when: ago(5),
+                                    ..Default::default()
+                                },
+                                DifferentialBackup {
+                                    name: "3-b".to_string(),
+                                    when: ago(1),
+                                    ..Default::default()
+                                },
+                            ]),
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let retention = Retention {
+                policy: RetentionPolicy::Age,
+                age: AgeRetention {
+                    within_hours: 0,
+                    hourly: 0,
+                    daily: 0,
+                    weekly: 0,
+                },
+                ..Default::default()
+            };
+            layout.forget_excess_backups(retention, &now());
+            assert_eq!(
+                VecDeque::from_iter(vec![
+                    FullBackup {
+                        name: "1".to_string(),
+                        when: ago(30),
+                        locked: true,
+                        ..Default::default()
+                    },
+                    FullBackup {
+                        name: "3".to_string(),
+                        when: ago(10),
+                        children: VecDeque::from_iter(vec![DifferentialBackup {
+                            name: "3-b".to_string(),
+                            when: ago(1),
                             ..Default::default()
                         }]),
                         ..Default::default()
+                    },
+                ]),
+                layout.mapping.backups,
+            );
+        }
+
+        #[test]
+        fn can_decide_backups_retained_by_age_at_boundaries() {
+            let at = |y, m, d, h, min| {
+                chrono::NaiveDate::from_ymd_opt(y, m, d)
+                    .unwrap()
+                    .and_hms_opt(h, min, 0)
+                    .unwrap()
+                    .and_local_timezone(chrono::Utc)
+                    .unwrap()
+            };
+            let age = |within_hours, hourly, daily, weekly| AgeRetention {
+                within_hours,
+                hourly,
+                daily,
+                weekly,
+            };
+
+            // Everything within the window is kept, but not at the exact boundary.
+            let backups = [
+                ("old", now() - chrono::Duration::hours(3)),
+                ("new", now() - chrono::Duration::minutes(179)),
+            ];
+            assert_eq!(
+                HashSet::from(["new"]),
+                retained_by_age(&backups, age(3, 0, 0, 0), &now()),
+            );
+
+            // Hours and days are split on the clock, not relative to now.
+            let backups = [
+                ("1:59", at(2000, 1, 2, 1, 59)),
+                ("2:00", at(2000, 1, 2, 2, 0)),
+                ("2:30", at(2000, 1, 2, 2, 30)),
+                ("yesterday", at(2000, 1, 1, 23, 59)),
+            ];
+            assert_eq!(
+                HashSet::from(["1:59", "2:30"]),
+                retained_by_age(&backups, age(0, 3, 0, 0), &now()),
+            );
+            assert_eq!(
+                HashSet::from(["2:30", "yesterday"]),
+                retained_by_age(&backups, age(0, 0, 2, 0), &now()),
+            );
+
+            // Weeks start on Monday. `now` is Sunday, 2000-01-02.
+            let backups = [
+                ("sunday", at(1999, 12, 26, 23, 0)),
+                ("monday-early", at(1999, 12, 27, 0, 30)),
+                ("monday-late", at(1999, 12, 27, 1, 0)),
+            ];
+            assert_eq!(
+                HashSet::from(["sunday", "monday-late"]),
+                retained_by_age(&backups, age(0, 0, 0, 1), &now()),
+            );
+
+            // Nothing is kept past every window.
+            assert_eq!(
+                HashSet::<&str>::new(),
+                retained_by_age(&backups, age(0, 0, 0, 0), &now())
+            );
+        }
+
+        fn make_path(file: &str) -> StrictPath {
+            repo_path(&format!("tests/backup/game1/{}", file))
+        }
+
+        fn make_restorable_path(backup: &str, file: &str) -> StrictPath {
+            StrictPath::relative(
+                format!(
+                    "{backup}/drive-{}/{file}",
+                    if cfg!(target_os = "windows") { "X" } else { "0" }
+                ),
+                Some(repo_file_raw("tests/backup/game1")),
+            )
+        }
+
+        fn make_restorable_path_zip(file: &str) -> StrictPath {
+            StrictPath::new(format!(
+                "drive-{}/{file}",
+                if cfg!(target_os = "windows") { "X" } else { "0" }
+            ))
+        }
+
+        #[test]
+        fn can_report_restorable_files_for_full_backup_in_simple_format() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "backup-1".into(),
+                        when: past(),
+                        files: btree_map! {
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2, base: None, object: false, mtime: None },
+                        },
+                        ..Default::default()
                     }]),
                 },
+                ..Default::default()
             };
             assert_eq!(
                 hash_map! {
-                    make_restorable_path("backup-1", "unchanged.txt"): ScannedFile {
+                    make_restorable_path("backup-1", "file1.txt"): ScannedFile {
                         size: 1,
                         hash: "old".into(),
-                        original_path: Some(make_original_path("/unchanged.txt")),
+                        original_path: Some(make_original_path("/file1.txt")),
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
-                    make_restorable_path("backup-2", "changed.txt"): ScannedFile {
+                    make_restorable_path("backup-1", "file2.txt"): ScannedFile {
                         size: 2,
-                        hash: "new".into(),
-                        original_path: Some(make_original_path("/changed.txt")),
+                        hash: "old".into(),
+                        original_path: Some(make_original_path("/file2.txt")),
                         ignored: false,
                         change: Default::default(),
                         container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
-                    make_restorable_path("backup-2", "added.txt"): ScannedFile {
-                        size: 5,
-                        hash: "new".into(),
-                        original_path: Some(make_original_path("/added.txt")),
+                },
+                layout.restorable_files(&BackupId::Latest, ScanKind::Backup, &[], false, &Default::default()),
+            );
+        }
+
+        #[test]
+        fn can_report_restorable_files_for_full_backup_in_zip_format() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "backup-1.zip".into(),
+                        when: past(),
+                        files: btree_map! {
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "old".into(), size: 2, base: None, object: false, mtime: None },
+                        },
+                        ..Default::default()
+                    }]),
+                },
+                ..Default::default()
+            };
+            assert_eq!(
+                hash_map! {
+                    make_restorable_path_zip("file1.txt"): ScannedFile {
+                        size: 1,
+                        hash: "old".into(),
+                        original_path: Some(make_original_path("/file1.txt")),
                         ignored: false,
                         change: Default::default(),
-                        container: None,
+                        container: Some(make_path("backup-1.zip")),
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
+                        redirected: None,
+                        conflict: false,
+                    },
+                    make_restorable_path_zip("file2.txt"): ScannedFile {
+                        size: 2,
+                        hash: "old".into(),
+                        original_path: Some(make_original_path("/file2.txt")),
+                        ignored: false,
+                        change: Default::default(),
+                        container: Some(make_path("backup-1.zip")),
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 },
                 layout.restorable_files(&BackupId::Latest, ScanKind::Backup, &[], false, &Default::default()),
@@ -3241,264 +7529,2623 @@ mod tests {
         }
 
         #[test]
-        fn can_report_restorable_files_for_differential_backup_in_zip_format() {
+        fn can_locate_files_in_simple_and_zip_formats() {
+            let mut layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![
+                        FullBackup {
+                            name: "backup-1".into(),
+                            when: past(),
+                            files: btree_map! {
+                                mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "old".into(), size: 1, base: None, object: false, mtime: None },
+                            },
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "backup-2.zip".into(),
+                            when: now(),
+                            files: btree_map! {
+                                mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "new".into(), size: 1, base: None, object: false, mtime: None },
+                            },
+                            ..Default::default()
+                        },
+                    ]),
+                },
+                ..Default::default()
+            };
+
+            assert_eq!(
+                btree_map! {
+                    make_original_path("/file1.txt").render(): StoredFile::Simple {
+                        path: make_restorable_path("backup-1", "file1.txt").render(),
+                    },
+                },
+                layout.locate(&BackupId::Named("backup-1".into())),
+            );
+            assert_eq!(
+                btree_map! {
+                    make_original_path("/file1.txt").render(): StoredFile::Archive {
+                        container: make_path("backup-2.zip").render(),
+                        entry: make_restorable_path_zip("file1.txt").raw().to_string(),
+                    },
+                },
+                layout.locate(&BackupId::Latest),
+            );
+
+            layout.mapping.backups.clear();
+            assert_eq!(BTreeMap::new(), layout.locate(&BackupId::Latest));
+        }
+
+        #[test]
+        fn can_report_restorable_files_for_differential_backup_in_simple_format() {
             let layout = GameLayout {
                 path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
                 mapping: IndividualMapping {
                     name: "game1".to_string(),
                     drives: drives_x(),
                     backups: VecDeque::from(vec![FullBackup {
-                        name: "backup-1.zip".into(),
+                        name: "backup-1".into(),
                         when: past(),
                         files: btree_map! {
-                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1 },
-                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2 },
-                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3 },
+                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2, base: None, object: false, mtime: None },
+                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3, base: None, object: false, mtime: None },
                         },
                         children: VecDeque::from([DifferentialBackup {
-                            name: "backup-2.zip".into(),
+                            name: "backup-2".into(),
                             when: past2(),
                             files: btree_map! {
-                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2 }),
+                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2, base: None, object: false, mtime: None }),
                                 mapping_file_key("/delete.txt"): None,
-                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5 }),
+                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5, base: None, object: false, mtime: None }),
                             },
                             ..Default::default()
                         }]),
                         ..Default::default()
                     }]),
                 },
+                ..Default::default()
             };
             assert_eq!(
                 hash_map! {
-                    make_restorable_path_zip("unchanged.txt"): ScannedFile {
+                    make_restorable_path("backup-1", "unchanged.txt"): ScannedFile {
                         size: 1,
                         hash: "old".into(),
                         original_path: Some(make_original_path("/unchanged.txt")),
                         ignored: false,
                         change: Default::default(),
-                        container: Some(make_path("backup-1.zip")),
+                        container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
-                    make_restorable_path_zip("changed.txt"): ScannedFile {
+                    make_restorable_path("backup-2", "changed.txt"): ScannedFile {
                         size: 2,
                         hash: "new".into(),
                         original_path: Some(make_original_path("/changed.txt")),
                         ignored: false,
                         change: Default::default(),
-                        container: Some(make_path("backup-2.zip")),
+                        container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
-                    make_restorable_path_zip("added.txt"): ScannedFile {
+                    make_restorable_path("backup-2", "added.txt"): ScannedFile {
                         size: 5,
                         hash: "new".into(),
                         original_path: Some(make_original_path("/added.txt")),
                         ignored: false,
                         change: Default::default(),
-                        container: Some(make_path("backup-2.zip")),
+                        container: None,
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
                         redirected: None,
+                        conflict: false,
                     },
                 },
                 layout.restorable_files(&BackupId::Latest, ScanKind::Backup, &[], false, &Default::default()),
             );
-        }
-    }
+        }
+
+        #[test]
+        fn can_report_restorable_files_for_differential_backup_in_zip_format() {
+            let layout = GameLayout {
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                mapping: IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "backup-1.zip".into(),
+                        when: past(),
+                        files: btree_map! {
+                            mapping_file_key("/unchanged.txt"): IndividualMappingFile { hash: "old".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/changed.txt"): IndividualMappingFile { hash: "old".into(), size: 2, base: None, object: false, mtime: None },
+                            mapping_file_key("/delete.txt"): IndividualMappingFile { hash: "old".into(), size: 3, base: None, object: false, mtime: None },
+                        },
+                        children: VecDeque::from([DifferentialBackup {
+                            name: "backup-2.zip".into(),
+                            when: past2(),
+                            files: btree_map! {
+                                mapping_file_key("/changed.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 2, base: None, object: false, mtime: None }),
+                                mapping_file_key("/delete.txt"): None,
+                                mapping_file_key("/added.txt"): Some(IndividualMappingFile { hash: "new".into(), size: 5, base: None, object: false, mtime: None }),
+                            },
+                            ..Default::default()
+                        }]),
+                        ..Default::default()
+                    }]),
+                },
+                ..Default::default()
+            };
+            assert_eq!(
+                hash_map! {
+                    make_restorable_path_zip("unchanged.txt"): ScannedFile {
+                        size: 1,
+                        hash: "old".into(),
+                        original_path: Some(make_original_path("/unchanged.txt")),
+                        ignored: false,
+                        change: Default::default(),
+                        container: Some(make_path("backup-1.zip")),
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
+                        redirected: None,
+                        conflict: false,
+                    },
+                    make_restorable_path_zip("changed.txt"): ScannedFile {
+                        size: 2,
+                        hash: "new".into(),
+                        original_path: Some(make_original_path("/changed.txt")),
+                        ignored: false,
+                        change: Default::default(),
+                        container: Some(make_path("backup-2.zip")),
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
+                        redirected: None,
+                        conflict: false,
+                    },
+                    make_restorable_path_zip("added.txt"): ScannedFile {
+                        size: 5,
+                        hash: "new".into(),
+                        original_path: Some(make_original_path("/added.txt")),
+                        ignored: false,
+                        change: Default::default(),
+                        container: Some(make_path("backup-2.zip")),
+                        excluded: None,
+                        symlink: None,
+                        hardlink: None,
+                        redirected: None,
+                        conflict: false,
+                    },
+                },
+                layout.restorable_files(&BackupId::Latest, ScanKind::Backup, &[], false, &Default::default()),
+            );
+        }
+
+        #[test]
+        fn can_determine_backup_sizes() {
+            let file = |size| IndividualMappingFile {
+                hash: "hash".into(),
+                size,
+                base: None,
+                object: false,
+                mtime: None,
+            };
+            let object = |size| IndividualMappingFile {
+                hash: "object".into(),
+                size,
+                base: None,
+                object: true,
+                mtime: None,
+            };
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "backup-1".into(),
+                        when: past(),
+                        files: btree_map! {
+                            mapping_file_key("/unchanged.txt"): file(1),
+                            mapping_file_key("/changed.txt"): file(2),
+                            mapping_file_key("/delete.txt"): file(3),
+                            mapping_file_key("/shared.txt"): object(10),
+                        },
+                        children: VecDeque::from([DifferentialBackup {
+                            name: "backup-2".into(),
+                            when: now(),
+                            files: btree_map! {
+                                mapping_file_key("/changed.txt"): Some(file(5)),
+                                mapping_file_key("/delete.txt"): None,
+                                mapping_file_key("/added.txt"): Some(file(7)),
+                                mapping_file_key("/shared-copy.txt"): Some(object(10)),
+                            },
+                            ..Default::default()
+                        }]),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let sizes: Vec<_> = layout
+                .restorable_backups_flattened()
+                .iter()
+                .map(|x| x.total_size())
+                .collect();
+            assert_eq!(vec![16, 33], sizes);
+            assert_eq!(
+                Some(33),
+                layout
+                    .find_by_id_flattened(&BackupId::Named("backup-2".into()))
+                    .map(|x| x.total_size())
+            );
+            assert_eq!(28, layout.mapping.stored_size());
+        }
+
+        #[test]
+        fn can_find_latest_backup_between_times() {
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    backups: VecDeque::from(vec![
+                        FullBackup {
+                            name: "backup-1".into(),
+                            when: past(),
+                            children: VecDeque::from([DifferentialBackup {
+                                name: "backup-2".into(),
+                                when: past2(),
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "backup-3".into(),
+                            when: now(),
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let name = |backup: Option<Backup>| backup.map(|x| x.name().to_string());
+
+            assert_eq!(Some(s("backup-3")), name(layout.latest_backup_between(None, None)));
+            assert_eq!(
+                Some(s("backup-2")),
+                name(layout.latest_backup_between(None, Some(&now())))
+            );
+            assert_eq!(
+                Some(s("backup-1")),
+                name(layout.latest_backup_between(None, Some(&past2())))
+            );
+            assert_eq!(
+                Some(s("backup-3")),
+                name(layout.latest_backup_between(Some(&past()), None))
+            );
+            assert_eq!(
+                Some(s("backup-2")),
+                name(layout.latest_backup_between(Some(&past()), Some(&now())))
+            );
+            assert_eq!(None, name(layout.latest_backup_between(Some(&past()), Some(&past2()))));
+            assert_eq!(None, name(layout.latest_backup_between(None, Some(&past()))));
+        }
+
+        #[test]
+        fn can_diff_backups() {
+            let file = |hash: &str| IndividualMappingFile {
+                hash: hash.into(),
+                size: 1,
+                ..Default::default()
+            };
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    backups: VecDeque::from(vec![
+                        FullBackup {
+                            name: "backup-1".into(),
+                            when: past(),
+                            files: btree_map! {
+                                s("/changed.txt"): file("old"),
+                                s("/removed.txt"): file("old"),
+                                s("/same.txt"): file("old"),
+                            },
+                            registry: IndividualMappingRegistry {
+                                hash: Some("registry".into()),
+                            },
+                            children: VecDeque::from([DifferentialBackup {
+                                name: "backup-2".into(),
+                                when: past2(),
+                                files: btree_map! {
+                                    s("/changed.txt"): Some(file("new")),
+                                    s("/removed.txt"): None,
+                                    s("/added.txt"): Some(file("new")),
+                                },
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "backup-3".into(),
+                            when: now(),
+                            files: btree_map! {
+                                s("/changed.txt"): file("new"),
+                                s("/added.txt"): file("new"),
+                                s("/same.txt"): file("old"),
+                            },
+                            ..Default::default()
+                        },
+                    ]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            assert_eq!(
+                BackupDiff {
+                    added: BTreeSet::from([s("/added.txt")]),
+                    removed: BTreeSet::from([s("/removed.txt")]),
+                    changed: BTreeSet::from([s("/changed.txt")]),
+                    registry_changed: false,
+                },
+                layout.diff(&BackupId::Named("backup-1".into()), &BackupId::Named("backup-2".into())),
+            );
+            assert_eq!(
+                BackupDiff {
+                    registry_changed: true,
+                    ..Default::default()
+                },
+                layout.diff(&BackupId::Named("backup-2".into()), &BackupId::Latest),
+            );
+        }
+    }
+
+    mod game_layout {
+        use pretty_assertions::assert_eq;
+
+        use crate::{
+            resource::config::{ZipConfig, ZipEncryption},
+            testing::{drives_x_always, drives_x_static, repo_file_raw},
+        };
+
+        use super::*;
+
+        fn now() -> chrono::DateTime<chrono::Utc> {
+            chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+                .unwrap()
+                .and_hms_opt(3, 4, 5)
+                .unwrap()
+                .and_local_timezone(chrono::Utc)
+                .unwrap()
+        }
+
+        fn restorable_file_simple(backup: &str, file: &str) -> StrictPath {
+            StrictPath::relative(
+                format!(
+                    "{backup}/drive-{}/{file}",
+                    if cfg!(target_os = "windows") { "X" } else { "0" }
+                ),
+                Some(repo_file_raw("tests/backup/game1")),
+            )
+        }
+
+        #[test]
+        fn can_scan_game_for_restoration_with_files() {
+            let mut layout = GameLayout::new(
+                StrictPath::new(format!("{}/tests/backup/game1", repo())),
+                IndividualMapping {
+                    name: "game1".to_string(),
+                    drives: drives_x(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: SOLO.into(),
+                        when: now(),
+                        files: btree_map! {
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
+                        },
+                        ..Default::default()
+                    }]),
+                },
+            );
+            let backups = vec![Backup::Full(FullBackup {
+                name: SOLO.to_string(),
+                when: now(),
+                files: btree_map! {
+                    mapping_file_key("/file1.txt"): IndividualMappingFile {
+                        hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
+                        size: 1,
+                        base: None,
+                        object: false,
+                        mtime: None,
+                    },
+                    mapping_file_key("/file2.txt"): IndividualMappingFile {
+                        hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
+                        size: 2,
+                        base: None,
+                        object: false,
+                        mtime: None,
+                    },
+                },
+                ..Default::default()
+            })];
+
+            assert_eq!(
+                ScanInfo {
+                    game_name: s("game1"),
+                    found_files: hash_map! {
+                        restorable_file_simple(SOLO, "file1.txt"): ScannedFile {
+                            size: 1,
+                            hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
+                            original_path: Some(make_original_path("/file1.txt")),
+                            ignored: false,
+                            change: ScanChange::New,
+                            container: None,
+                            excluded: None,
+                            symlink: None,
+                            hardlink: None,
+                            redirected: None,
+                            conflict: false,
+                        },
+                        restorable_file_simple(SOLO, "file2.txt"): ScannedFile {
+                            size: 2,
+                            hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
+                            original_path: Some(make_original_path("/file2.txt")),
+                            ignored: false,
+                            change: ScanChange::New,
+                            container: None,
+                            excluded: None,
+                            symlink: None,
+                            hardlink: None,
+                            redirected: None,
+                            conflict: false,
+                        },
+                    },
+                    found_registry_keys: Default::default(),
+                    available_backups: backups.clone(),
+                    backup: Some(backups[0].clone()),
+                    has_backups: true,
+                    dumped_registry: None,
+                    only_constructive_backups: false,
+                    warnings: vec![],
+                    hash_algorithm: Default::default(),
+                    game_running: false,
+                },
+                layout.scan_for_restoration(
+                    "game1",
+                    &BackupId::Latest,
+                    &[],
+                    false,
+                    &Default::default(),
+                    &Default::default()
+                ),
+            );
+        }
+
+        #[test]
+        fn can_scan_game_for_restoration_with_registry() {
+            let mut layout =
+                BackupLayout::new(StrictPath::new(format!("{}/tests/backup", repo()))).game_layout("game3");
+            if cfg!(target_os = "windows") {
+                assert_eq!(
+                    ScanInfo {
+                        game_name: s("game3"),
+                        found_files: Default::default(),
+                        found_registry_keys: hash_map! {
+                            "HKEY_CURRENT_USER/Software/Ludusavi/game3".into(): ScannedRegistry::new().change_as(ScanChange::Same)
+                                .with_value_same("binary")
+                                .with_value_same("dword")
+                                .with_value_same("expandSz")
+                                .with_value_same("multiSz")
+                                .with_value_same("qword")
+                                .with_value_same("sz")
+                        },
+                        available_backups: vec![Backup::Full(FullBackup {
+                            name: SOLO.to_string(),
+                            when: now(),
+                            symlinks: BTreeMap::new(),
+                            hardlinks: BTreeMap::new(),
+                            registry: IndividualMappingRegistry {
+                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into()),
+                            },
+                            ..Default::default()
+                        })],
+                        backup: Some(Backup::Full(FullBackup {
+                            name: SOLO.to_string(),
+                            when: now(),
+                            symlinks: BTreeMap::new(),
+                            hardlinks: BTreeMap::new(),
+                            registry: IndividualMappingRegistry {
+                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into()),
+                            },
+                            ..Default::default()
+                        })),
+                        has_backups: true,
+                        dumped_registry: Some(registry::Hives(btree_map! {
+                            r"HKEY_CURRENT_USER".into(): registry::Keys(btree_map! {
+                                r"Software\Ludusavi\game3".into(): registry::Entries(btree_map! {
+                                    "binary".into(): registry::Entry::Binary(vec![65]),
+                                    "dword".into(): registry::Entry::Dword(1),
+                                    "expandSz".into(): registry::Entry::ExpandSz("baz".to_string()),
+                                    "multiSz".into(): registry::Entry::MultiSz("bar".to_string()),
+                                    "qword".into(): registry::Entry::Qword(2),
+                                    "sz".into(): registry::Entry::Sz("foo".to_string()),
+                                }),
+                            })
+                        })),
+                        only_constructive_backups: false,
+                        warnings: vec![],
+                        hash_algorithm: Default::default(),
+                        game_running: false,
+                    },
+                    layout.scan_for_restoration(
+                        "game3",
+                        &BackupId::Latest,
+                        &[],
+                        false,
+                        &Default::default(),
+                        &Default::default()
+                    ),
+                );
+            } else {
+                assert_eq!(
+                    ScanInfo {
+                        game_name: s("game3"),
+                        found_files: Default::default(),
+                        found_registry_keys: Default::default(),
+                        available_backups: vec![Backup::Full(FullBackup {
+                            name: SOLO.to_string(),
+                            when: now(),
+                            symlinks: BTreeMap::new(),
+                            hardlinks: BTreeMap::new(),
+                            registry: IndividualMappingRegistry {
+                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into()),
+                            },
+                            ..Default::default()
+                        })],
+                        backup: Some(Backup::Full(FullBackup {
+                            name: SOLO.to_string(),
+                            when: now(),
+                            symlinks: BTreeMap::new(),
+                            hardlinks: BTreeMap::new(),
+                            registry: IndividualMappingRegistry {
+                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into())
+                            },
+                            ..Default::default()
+                        })),
+                        has_backups: true,
+                        dumped_registry: None,
+                        only_constructive_backups: false,
+                        warnings: vec![],
+                        hash_algorithm: Default::default(),
+                        game_running: false,
+                    },
+                    layout.scan_for_restoration(
+                        "game3",
+                        &BackupId::Latest,
+                        &[],
+                        false,
+                        &Default::default(),
+                        &Default::default()
+                    ),
+                );
+            }
+        }
+
+        fn make_maintenance_layout(folder: &str) -> GameLayout {
+            let path = StrictPath::from(std::env::temp_dir().join(folder));
+            let _ = path.remove();
+            for (backup, content) in [("backup-1", "a"), ("backup-2", "bb"), ("backup-3", "ccc")] {
+                let file = path.joined(backup).joined("drive-X").joined("file.txt");
+                file.parent().unwrap().create_dirs().unwrap();
+                file.write_with_content(content).unwrap();
+            }
+
+            let layout = GameLayout::new(
+                path,
+                IndividualMapping {
+                    name: "game".into(),
+                    drives: drives_x_always(),
+                    backups: VecDeque::from(vec![
+                        FullBackup {
+                            name: "backup-1".into(),
+                            files: btree_map! {
+                                mapping_file_key("/file.txt"): IndividualMappingFile { hash: "1".into(), size: 1, base: None, object: false, mtime: None },
+                            },
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "backup-2".into(),
+                            files: btree_map! {
+                                mapping_file_key("/file.txt"): IndividualMappingFile { hash: "2".into(), size: 2, base: None, object: false, mtime: None },
+                            },
+                            ..Default::default()
+                        },
+                        FullBackup {
+                            name: "backup-3".into(),
+                            files: btree_map! {
+                                mapping_file_key("/file.txt"): IndividualMappingFile { hash: "3".into(), size: 3, base: None, object: false, mtime: None },
+                            },
+                            ..Default::default()
+                        },
+                    ]),
+                },
+            );
+            layout.save();
+            layout
+        }
+
+        #[test]
+        fn can_maintain_backups_by_retention() {
+            let mut layout = make_maintenance_layout("ludusavi-test-maintain");
+
+            assert_eq!(
+                MaintenanceInfo {
+                    forgotten: vec![s("backup-1"), s("backup-2")],
+                    freed_bytes: 3,
+                },
+                layout.maintain(Retention::new(1, 0), &now(), false),
+            );
+            assert!(!layout.path.joined("backup-1").exists());
+            assert!(!layout.path.joined("backup-2").exists());
+            assert!(layout.path.joined("backup-3").exists());
+
+            let reloaded = GameLayout::load(layout.path.clone()).unwrap();
+            assert_eq!(
+                vec![s("backup-3")],
+                reloaded
+                    .mapping
+                    .backups
+                    .iter()
+                    .map(|x| x.name.clone())
+                    .collect::<Vec<_>>()
+            );
+
+            let _ = layout.path.remove();
+        }
+
+        #[test]
+        fn can_maintain_backups_in_preview() {
+            let mut layout = make_maintenance_layout("ludusavi-test-maintain-preview");
+
+            assert_eq!(
+                MaintenanceInfo {
+                    forgotten: vec![s("backup-1")],
+                    freed_bytes: 1,
+                },
+                layout.maintain(Retention::new(2, 0), &now(), true),
+            );
+            assert!(layout.path.joined("backup-1").exists());
+
+            let reloaded = GameLayout::load(layout.path.clone()).unwrap();
+            assert_eq!(3, reloaded.mapping.backups.len());
+
+            let _ = layout.path.remove();
+        }
+
+        #[test]
+        fn can_back_up_validate_and_restore_with_tar_gz() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-tar-gz"));
+            let _ = path.remove();
+            path.create_dirs().unwrap();
+
+            let source = StrictPath::new(format!("{}/tests/root3/game5/data/file1.txt", repo_raw()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                },
+                ..Default::default()
+            };
+            let format = BackupFormats {
+                chosen: BackupFormat::TarGz,
+                ..Default::default()
+            };
+
+            let mut layout = GameLayout::new(path.clone(), IndividualMapping::new("game5".to_string()));
+            let info = layout
+                .back_up(&scan, &now(), &format, Retention::default(), false, false, None)
+                .unwrap();
+            assert!(info.successful());
+
+            let name = "backup-20000102T030405Z.tar.gz";
+            assert_eq!(BackupFormat::TarGz, layout.mapping.backups[0].format());
+            assert_eq!(name, layout.mapping.backups[0].name);
+            assert!(layout.mapping.backups[0].duration_ms.is_some());
+            assert!(layout.validate(BackupId::Latest));
+
+            let stored = StrictPath::new(layout.mapping.game_file_for_zip_immutable(&source));
+            let target = path.joined("restored.txt");
+            let mut outcomes = layout.restore_files_from_tar(
+                &path.joined(name),
+                BackupFormat::TarGz,
+                &[(&stored, &target), (&path.joined("missing.txt"), &target)],
+            );
+            outcomes.remove(&stored).unwrap().unwrap();
+            assert!(outcomes.remove(&path.joined("missing.txt")).unwrap().is_err());
+            assert_eq!(Some(s(".")), target.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_scrub_backup() {
+            for format in [BackupFormat::Simple, BackupFormat::Zip, BackupFormat::TarGz] {
+                let path = StrictPath::from(std::env::temp_dir().join(format!("ludusavi-test-scrub-{format:?}")));
+                let _ = path.remove();
+                path.create_dirs().unwrap();
+
+                let source = StrictPath::new(format!("{}/tests/root3/game5/data/file1.txt", repo_raw()));
+                let scan = ScanInfo {
+                    game_name: s("game5"),
+                    found_files: hash_map! {
+                        source.clone(): ScannedFile::new(1, "3a52ce780950d4d969792a2559cd519d7ee8c727").change_new(),
+                    },
+                    ..Default::default()
+                };
+                let format = BackupFormats {
+                    chosen: format,
+                    ..Default::default()
+                };
+
+                let mut layout = GameLayout::new(path.clone(), IndividualMapping::new("game5".to_string()));
+                layout
+                    .back_up(&scan, &now(), &format, Retention::default(), false, false, None)
+                    .unwrap();
+                assert_eq!(Vec::<StrictPath>::new(), layout.scrub(BackupId::Latest));
+
+                let name = layout.mapping.backups[0].name.clone();
+                let stored = match format.chosen {
+                    BackupFormat::Simple => layout.mapping.game_file_immutable(&path, &source, &name),
+                    _ => path.joined(&name),
+                };
+                stored.write_with_content("corrupted").unwrap();
+                assert_eq!(
+                    vec![source.render()],
+                    layout
+                        .scrub(BackupId::Latest)
+                        .iter()
+                        .map(|x| x.render())
+                        .collect::<Vec<_>>(),
+                );
+
+                let _ = path.remove();
+            }
+        }
+
+        #[test]
+        fn can_back_up_and_restore_differential_as_delta() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-delta"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source = path.joined("live/save.txt");
+            source.create_parent_dir().unwrap();
+            let original: String = (0..20_000).map(|i| char::from(b'a' + (i * 7 % 26) as u8)).collect();
+            let mut updated = original.clone();
+            updated.replace_range(5000..5003, "XYZ");
+
+            let format = BackupFormats {
+                delta: true,
+                ..Default::default()
+            };
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+
+            source.write_with_content(&original).unwrap();
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(original.len() as u64, source.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(&scan, &now(), &format, Retention::new(1, 1), false, false, None)
+                .unwrap();
+
+            source.write_with_content(&updated).unwrap();
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(updated.len() as u64, source.sha1()).change_as(ScanChange::Different),
+                },
+                ..Default::default()
+            };
+            let info = layout
+                .back_up(&scan, &now(), &format, Retention::new(1, 1), false, false, None)
+                .unwrap();
+            assert!(info.successful());
+
+            let diff = &layout.mapping.backups[0].children[0];
+            let stored = diff.files[&source.render()].as_ref().unwrap();
+            assert_eq!(Some(s(".")), stored.base);
+            let stored_size = layout.mapping.game_file_immutable(&backups, &source, &diff.name).size();
+            assert!(stored_size < updated.len() as u64 / 2);
+
+            source.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(updated), source.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_back_up_and_restore_flat() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-flat"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source = path.joined("live/save.txt");
+            source.create_parent_dir().unwrap();
+            source.write_with_content("flat").unwrap();
+
+            let format = BackupFormats {
+                flat: true,
+                ..Default::default()
+            };
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(4, source.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            let info = layout
+                .back_up(&scan, &now(), &format, Retention::default(), false, false, None)
+                .unwrap();
+            assert!(info.successful());
+
+            let full = &layout.mapping.backups[0];
+            assert!(full.flat);
+            assert!(layout.mapping.drives.is_empty());
+            let (_, plain_path) = source.split_drive();
+            let stored = backups.joined(&full.name).joined(&plain_path);
+            assert_eq!(Some(s("flat")), stored.read());
+
+            source.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("flat")), source.read());
+
+            let _ = path.remove();
+        }
+
+        fn encrypted_zip_format() -> BackupFormats {
+            BackupFormats {
+                chosen: BackupFormat::Zip,
+                zip: ZipConfig {
+                    encryption: Some(ZipEncryption::default()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn can_back_up_and_restore_encrypted_zip() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-encrypted-zip"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source = path.joined("live/save.txt");
+            source.create_parent_dir().unwrap();
+            source.write_with_content("secret").unwrap();
+
+            let format = encrypted_zip_format();
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            layout.zip_password = Some(s("hunter2"));
+
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(6, source.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            let info = layout
+                .back_up(&scan, &now(), &format, Retention::default(), false, false, None)
+                .unwrap();
+            assert!(info.successful());
+
+            let encryption = layout.mapping.backups[0].encryption.clone().unwrap();
+            assert_eq!(ZipKeyDerivation::Aes256, encryption.key_derivation);
+            assert!(encryption.accepts("hunter2"));
+            let mapping = GameLayout::mapping_file(&backups).read().unwrap();
+            assert!(mapping.contains(&encryption.verifier));
+            assert!(!mapping.contains("hunter2"));
+            assert!(layout.validate(BackupId::Latest));
+
+            source.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("secret")), source.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn cannot_restore_encrypted_zip_with_wrong_password() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-encrypted-zip-wrong-password"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source = path.joined("live/save.txt");
+            source.create_parent_dir().unwrap();
+            source.write_with_content("secret").unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            layout.zip_password = Some(s("hunter2"));
+
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(6, source.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &encrypted_zip_format(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+
+            source.write_with_content("current").unwrap();
+            layout.zip_password = Some(s("wrong"));
+            assert!(!layout.validate(BackupId::Latest));
+
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(!info.successful());
+            assert_eq!(Some(s("current")), source.read());
+
+            layout.zip_password = None;
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(7, source.sha1()).change_as(ScanChange::Different),
+                },
+                ..Default::default()
+            };
+            let info = layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &encrypted_zip_format(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert!(!info.successful());
+            assert_eq!(1, layout.mapping.backups.len());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn does_not_encrypt_differential_that_only_omits_files() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-encrypted-zip-omission"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let kept = path.joined("live/kept.txt");
+            let removed = path.joined("live/removed.txt");
+            kept.create_parent_dir().unwrap();
+            kept.write_with_content("kept").unwrap();
+            removed.write_with_content("removed").unwrap();
+
+            let format = encrypted_zip_format();
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            layout.zip_password = Some(s("hunter2"));
+
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    kept.clone(): ScannedFile::new(4, kept.sha1()).change_new(),
+                    removed.clone(): ScannedFile::new(7, removed.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(&scan, &now(), &format, Retention::new(1, 1), false, false, None)
+                .unwrap();
+
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    kept.clone(): ScannedFile::new(4, kept.sha1()).change_as(ScanChange::Same),
+                },
+                ..Default::default()
+            };
+            let info = layout
+                .back_up(&scan, &now(), &format, Retention::new(1, 1), false, false, None)
+                .unwrap();
+            assert!(info.successful());
+
+            let diff = &layout.mapping.backups[0].children[0];
+            assert_eq!(None, diff.encryption);
+            assert!(!backups.joined(&diff.name).exists());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_move_solo_backup_to_timestamped_folder() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-timestamp-solo"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source = path.joined("live/save.txt");
+            source.create_parent_dir().unwrap();
+            source.write_with_content("solo").unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(4, source.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            let info = layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::new(1, 0),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert!(info.successful());
+            assert_eq!(SOLO, layout.mapping.backups[0].name);
+
+            assert!(layout.timestamp_solo_backup().unwrap());
+            let name = layout.mapping.backups[0].name.clone();
+            assert_eq!(s("backup-20000102T030405Z"), name);
+            assert!(backups.joined(&name).is_dir());
+            assert!(!backups.joined(layout.mapping.drives.keys().next().unwrap()).exists());
+
+            let mut reloaded = GameLayout::load(backups.clone()).unwrap();
+            assert_eq!(name, reloaded.mapping.backups[0].name);
+            assert!(!layout.timestamp_solo_backup().unwrap());
+
+            source.remove().unwrap();
+            let scan = reloaded.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = reloaded.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("solo")), source.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        #[cfg(not(target_os = "windows"))]
+        fn can_keep_unix_permissions_when_moving_solo_backup() {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = |file: &StrictPath| file.metadata().unwrap().permissions().mode() & 0o777;
+
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-timestamp-solo-permissions"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let script = path.joined("live/launch.sh");
+            script.create_parent_dir().unwrap();
+            script.write_with_content("#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(
+                script.as_std_path_buf().unwrap(),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    script.clone(): ScannedFile::new(script.size(), script.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::new(1, 0),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert!(backups.joined(PERMISSIONS_FILE).is_file());
+
+            assert!(layout.timestamp_solo_backup().unwrap());
+            let name = layout.mapping.backups[0].name.clone();
+            assert!(!backups.joined(PERMISSIONS_FILE).exists());
+            assert!(backups.joined(&name).joined(PERMISSIONS_FILE).is_file());
+
+            // Simulate a copy of the backup that did not keep the mode, such as from cloud storage.
+            let stored = layout.mapping.game_file_immutable(&backups, &script, &name);
+            std::fs::set_permissions(
+                stored.as_std_path_buf().unwrap(),
+                std::fs::Permissions::from_mode(0o644),
+            )
+            .unwrap();
+
+            script.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(0o755, mode(&script));
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn flat_backup_falls_back_to_drive_folders_for_multiple_drives() {
+            let format = BackupFormats {
+                flat: true,
+                ..Default::default()
+            };
+            let one_drive = [s("/foo/a.txt"), s("/bar/b.txt")];
+            let two_drives = [s("C:/foo/a.txt"), s("D:/bar/b.txt")];
+
+            assert!(IndividualMapping::can_store_flat(&format, "backup-1", one_drive.iter()));
+            assert!(!IndividualMapping::can_store_flat(
+                &format,
+                "backup-1",
+                two_drives.iter()
+            ));
+            assert!(!IndividualMapping::can_store_flat(
+                &BackupFormats::default(),
+                "backup-1",
+                one_drive.iter()
+            ));
+        }
+
+        #[test]
+        fn can_back_up_and_restore_with_dedupe() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-dedupe"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source1 = path.joined("live/game1/save.txt");
+            let source2 = path.joined("live/game2/save.txt");
+            source1.create_parent_dir().unwrap();
+            source2.create_parent_dir().unwrap();
+            source1.write_with_content("same").unwrap();
+            source2.write_with_content("same").unwrap();
+            let hash = source1.sha1();
+
+            let format = BackupFormats {
+                dedupe: true,
+                ..Default::default()
+            };
+
+            let mut layouts = vec![];
+            for (game, source) in [("game1", &source1), ("game2", &source2)] {
+                let mut layout = GameLayout::new(backups.joined(game), IndividualMapping::new(game.to_string()));
+                let scan = ScanInfo {
+                    game_name: s(game),
+                    found_files: hash_map! {
+                        source.clone(): ScannedFile::new(4, source.sha1()).change_new(),
+                    },
+                    ..Default::default()
+                };
+                let info = layout
+                    .back_up(&scan, &now(), &format, Retention::new(1, 1), false, false, None)
+                    .unwrap();
+                assert!(info.successful());
+                assert!(layout.mapping.backups[0].files[&source.render()].object);
+                assert!(!layout.mapping.game_file_immutable(&layout.path, source, ".").exists());
+                layouts.push(layout);
+            }
+
+            let object = backups.joined(OBJECTS_FOLDER).joined(&hash);
+            assert_eq!(Some(s("same")), object.read());
+            assert_eq!(1, backups.joined(OBJECTS_FOLDER).read_dir().unwrap().count());
+
+            source1.remove().unwrap();
+            let scan = layouts[0].scan_for_restoration(
+                "game1",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layouts[0].restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("same")), source1.read());
+
+            // Still referenced by game1.
+            backups.joined("game2").remove().unwrap();
+            assert_eq!(0, BackupLayout::new(backups.clone()).collect_garbage(false));
+            assert!(object.is_file());
+
+            backups.joined("game1").remove().unwrap();
+            assert_eq!(4, BackupLayout::new(backups.clone()).collect_garbage(false));
+            assert!(!object.exists());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_exclude_objects_used_only_by_games_excluded_from_cloud() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-cloud-excluded-objects"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let shared1 = path.joined("live/game1/save.txt");
+            let shared2 = path.joined("live/game2/save.txt");
+            let exclusive = path.joined("live/game2/other.txt");
+            for (file, content) in [(&shared1, "same"), (&shared2, "same"), (&exclusive, "only2")] {
+                file.create_parent_dir().unwrap();
+                file.write_with_content(content).unwrap();
+            }
+
+            let format = BackupFormats {
+                dedupe: true,
+                ..Default::default()
+            };
+            for (game, files) in [("game1", vec![&shared1]), ("game2", vec![&shared2, &exclusive])] {
+                let mut layout = GameLayout::new(backups.joined(game), IndividualMapping::new(game.to_string()));
+                let scan = ScanInfo {
+                    game_name: s(game),
+                    found_files: files
+                        .into_iter()
+                        .map(|x| (x.clone(), ScannedFile::new(x.size(), x.sha1()).change_new()))
+                        .collect(),
+                    ..Default::default()
+                };
+                let info = layout
+                    .back_up(&scan, &now(), &format, Retention::new(1, 1), false, false, None)
+                    .unwrap();
+                assert!(info.successful());
+            }
+
+            let layout = BackupLayout::new(backups.clone());
+            assert_eq!(
+                vec![s("game2"), format!("{OBJECTS_FOLDER}/{}", exclusive.sha1())],
+                layout.cloud_excluded_paths(&[s("game2")]),
+            );
+            assert_eq!(vec![s("game1")], layout.cloud_excluded_paths(&[s("game1")]));
+            assert_eq!(Vec::<String>::new(), layout.cloud_excluded_paths(&[s("unknown")]));
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_back_up_and_restore_mtime_in_simple_format() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-mtime"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let mtime = |seconds: u64| std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds);
+
+            let source1 = path.joined("live/game1/save.txt");
+            let source2 = path.joined("live/game2/save.txt");
+            for (source, seconds) in [(&source1, 1_600_000_000), (&source2, 1_700_000_000)] {
+                source.create_parent_dir().unwrap();
+                source.write_with_content("same").unwrap();
+                source.set_mtime(mtime(seconds)).unwrap();
+            }
+
+            for dedupe in [false, true] {
+                let _ = backups.remove();
+                let format = BackupFormats {
+                    dedupe,
+                    ..Default::default()
+                };
+
+                let mut layouts = vec![];
+                for (game, source) in [("game1", &source1), ("game2", &source2)] {
+                    let mut layout = GameLayout::new(backups.joined(game), IndividualMapping::new(game.to_string()));
+                    let scan = ScanInfo {
+                        game_name: s(game),
+                        found_files: hash_map! {
+                            source.clone(): ScannedFile::new(4, source.sha1()).change_new(),
+                        },
+                        ..Default::default()
+                    };
+                    let info = layout
+                        .back_up(&scan, &now(), &format, Retention::new(1, 1), false, false, None)
+                        .unwrap();
+                    assert!(info.successful());
+                    layouts.push(layout);
+                }
+
+                // With dedupe, both games share one stored copy, which keeps the first game's time.
+                source2.remove().unwrap();
+                let scan = layouts[1].scan_for_restoration(
+                    "game2",
+                    &BackupId::Latest,
+                    &[],
+                    false,
+                    &ToggledPaths::default(),
+                    &ToggledRegistry::default(),
+                );
+                let info = layouts[1].restore(&scan, &ToggledRegistry::default(), None);
+                assert!(info.successful());
+                assert_eq!(Some(s("same")), source2.read());
+                assert_eq!(mtime(1_700_000_000), source2.get_mtime().unwrap());
+            }
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_cancel_backup_partway_and_discard_it() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-cancel"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source = path.joined("live/save.bin");
+            source.create_parent_dir().unwrap();
+            source.write_with_bytes(&vec![1; 1024 * 1024]).unwrap();
+
+            let scan = ScanInfo {
+                game_name: s("game1"),
+                found_files: hash_map! {
+                    source.clone(): ScannedFile::new(1024 * 1024, source.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+
+            for chosen in [BackupFormat::Simple, BackupFormat::Zip, BackupFormat::Tar] {
+                let _ = backups.remove();
+                let format = BackupFormats {
+                    chosen,
+                    ..Default::default()
+                };
+
+                // Setting the flag up front stops the backup during its first chunk of file content.
+                let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game1".to_string()));
+                layout.cancel = Some(Arc::new(AtomicBool::new(true)));
+                let info = layout.back_up(&scan, &now(), &format, Retention::new(2, 0), false, false, None);
+
+                assert!(info.is_none());
+                assert!(layout.mapping.backups.is_empty());
+                assert!(!backups.exists() || backups.read_dir().unwrap().next().is_none());
+            }
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_finish_single_simple_backup_despite_cancel() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-cancel-solo"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let source1 = path.joined("live/save1.bin");
+            let source2 = path.joined("live/save2.bin");
+            source1.create_parent_dir().unwrap();
+            source1.write_with_bytes(&vec![1; 1024 * 1024]).unwrap();
+
+            let scan = |files: &[&StrictPath]| ScanInfo {
+                game_name: s("game1"),
+                found_files: files
+                    .iter()
+                    .map(|x| ((*x).clone(), ScannedFile::new(x.size(), x.sha1()).change_new()))
+                    .collect(),
+                ..Default::default()
+            };
+
+            let cancel = Arc::new(AtomicBool::new(false));
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game1".to_string()));
+            layout.cancel = Some(cancel.clone());
+            let info = layout
+                .back_up(
+                    &scan(&[&source1]),
+                    &now(),
+                    &Default::default(),
+                    Retention::new(1, 0),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert!(info.successful());
+            assert_eq!(SOLO, layout.mapping.backups[0].name);
+
+            // The single backup is updated in place, so a cancellation during it
+            // would leave the old and new files mixed together. Instead, it runs to completion.
+            source1.write_with_bytes(&vec![2; 1024 * 1024]).unwrap();
+            source2.write_with_bytes(&vec![3; 1024 * 1024]).unwrap();
+            cancel.store(true, Ordering::Relaxed);
+            let info = layout
+                .back_up(
+                    &scan(&[&source1, &source2]),
+                    &now(),
+                    &Default::default(),
+                    Retention::new(1, 0),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+
+            assert!(info.successful());
+            assert_eq!(1, layout.mapping.backups.len());
+            for source in [&source1, &source2] {
+                let stored = layout.mapping.game_file(&layout.path, source, SOLO);
+                assert_eq!(source.sha1(), stored.sha1());
+                assert_eq!(source.sha1(), layout.mapping.backups[0].files[&source.render()].hash);
+            }
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_merge_differential_backup() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-merge"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let file1 = path.joined("live/file1.txt");
+            let file2 = path.joined("live/file2.txt");
+            file1.create_parent_dir().unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let back_up = |layout: &mut GameLayout, seconds: i64, changes: [ScanChange; 2]| {
+                let scan = ScanInfo {
+                    game_name: s("game5"),
+                    found_files: [&file1, &file2]
+                        .into_iter()
+                        .zip(changes)
+                        .map(|(x, change)| ((*x).clone(), ScannedFile::new(x.size(), x.sha1()).change_as(change)))
+                        .collect(),
+                    ..Default::default()
+                };
+                layout
+                    .back_up(
+                        &scan,
+                        &(now() + chrono::Duration::seconds(seconds)),
+                        &BackupFormats::default(),
+                        Retention::new(1, 2),
+                        false,
+                        false,
+                        None,
+                    )
+                    .unwrap();
+            };
+
+            file1.write_with_content("old1").unwrap();
+            file2.write_with_content("old2").unwrap();
+            back_up(&mut layout, 0, [ScanChange::New, ScanChange::New]);
+
+            file1.write_with_content("new1").unwrap();
+            back_up(&mut layout, 1, [ScanChange::Different, ScanChange::Same]);
+            let locked = layout.mapping.backups[0].children[0].name.clone();
+            layout.set_backup_locked(&locked, true);
+
+            file2.write_with_content("new2").unwrap();
+            back_up(&mut layout, 2, [ScanChange::Same, ScanChange::Different]);
+            assert_eq!(2, layout.mapping.backups[0].children.len());
+
+            let merged = layout
+                .merge(
+                    &BackupId::Latest,
+                    &(now() + chrono::Duration::seconds(3)),
+                    &BackupFormats::default(),
+                    true,
+                )
+                .unwrap();
+            assert_eq!(
+                btree_map! {
+                    file1.render(): IndividualMappingFile { hash: file1.sha1(), size: 4, base: None, object: false, mtime: None },
+                    file2.render(): IndividualMappingFile { hash: file2.sha1(), size: 4, base: None, object: false, mtime: None },
+                },
+                merged.files,
+            );
+
+            // The locked differential and the full backup it depends on are preserved.
+            assert_eq!(2, layout.mapping.backups.len());
+            assert_eq!(
+                vec![locked],
+                layout.mapping.backups[0]
+                    .children
+                    .iter()
+                    .map(|x| x.name.clone())
+                    .collect::<Vec<_>>(),
+            );
+            assert_eq!(merged.name, layout.mapping.backups[1].name);
+            assert!(!backups.joined(MERGE_STAGING).exists());
+
+            file1.remove().unwrap();
+            file2.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Named(merged.name.clone()),
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("new1")), file1.read());
+            assert_eq!(Some(s("new2")), file2.read());
+
+            assert_eq!(
+                Err(Error::NoDifferentialBackupToMerge),
+                layout.merge(&BackupId::Latest, &now(), &BackupFormats::default(), true),
+            );
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_delete_backups() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-delete"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let file = path.joined("live/file.txt");
+            file.create_parent_dir().unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game6".to_string()));
+            for (seconds, content, change) in [
+                (0, "a", ScanChange::New),
+                (1, "b", ScanChange::Different),
+                (2, "c", ScanChange::Different),
+            ] {
+                file.write_with_content(content).unwrap();
+                let scan = ScanInfo {
+                    game_name: s("game6"),
+                    found_files: hash_map! {
+                        file.clone(): ScannedFile::new(1, file.sha1()).change_as(change),
+                    },
+                    ..Default::default()
+                };
+                layout
+                    .back_up(
+                        &scan,
+                        &(now() + chrono::Duration::seconds(seconds)),
+                        &BackupFormats::default(),
+                        Retention::new(2, 2),
+                        false,
+                        false,
+                        None,
+                    )
+                    .unwrap();
+            }
+
+            let full = layout.mapping.backups[0].name.clone();
+            let diff1 = layout.mapping.backups[0].children[0].name.clone();
+            let diff2 = layout.mapping.backups[0].children[1].name.clone();
+            assert!(backups.joined(&diff2).exists());
+
+            layout.delete_backup(&BackupId::Named(diff2.clone())).unwrap();
+            assert_eq!(1, layout.mapping.backups[0].children.len());
+            assert!(!backups.joined(&diff2).exists());
+
+            layout.set_backup_locked(&diff1, true);
+            assert_eq!(
+                Err(Error::BackupLocked { backup: diff1.clone() }),
+                layout.delete_backup(&BackupId::Named(full.clone())),
+            );
+            assert_eq!(
+                Err(Error::BackupLocked { backup: diff1.clone() }),
+                layout.delete_backup(&BackupId::Named(diff1.clone())),
+            );
+
+            layout.set_backup_locked(&diff1, false);
+            assert_eq!(
+                vec![diff1.clone()],
+                layout.dependent_backups(&BackupId::Named(full.clone()))
+            );
+            layout.delete_backup(&BackupId::Named(full.clone())).unwrap();
+            assert!(layout.mapping.backups.is_empty());
+            assert!(!backups.joined(&full).exists());
+            assert!(!backups.joined(&diff1).exists());
+
+            assert_eq!(
+                Err(Error::CliInvalidBackupId),
+                layout.delete_backup(&BackupId::Named(full)),
+            );
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_export_and_import_bundle() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-bundle"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            let imported = path.joined("imported");
+            let bundle = path.joined("bundle.zip");
+            backups.create_dirs().unwrap();
+            imported.create_dirs().unwrap();
+
+            let file = path.joined("live/save.txt");
+            file.create_parent_dir().unwrap();
+
+            let format = BackupFormats {
+                dedupe: true,
+                ..Default::default()
+            };
+            let mut layout = GameLayout::new(backups.joined("game1"), IndividualMapping::new("game1".to_string()));
+            for (seconds, content, change) in [(0, "old", ScanChange::New), (1, "new", ScanChange::Different)] {
+                file.write_with_content(content).unwrap();
+                let scan = ScanInfo {
+                    game_name: s("game1"),
+                    found_files: hash_map! {
+                        file.clone(): ScannedFile::new(3, file.sha1()).change_as(change),
+                    },
+                    ..Default::default()
+                };
+                layout
+                    .back_up(
+                        &scan,
+                        &(now() + chrono::Duration::seconds(seconds)),
+                        &format,
+                        Retention::new(1, 1),
+                        false,
+                        false,
+                        None,
+                    )
+                    .unwrap();
+            }
+            layout.export_bundle(&bundle).unwrap();
+
+            let mut target = BackupLayout::new(imported.clone());
+            assert_eq!(
+                Ok(s("game1")),
+                target.import_bundle(&bundle, &Manifest::default(), false)
+            );
+            assert!(imported.joined("game1/mapping.yaml").is_file());
+            assert_eq!(2, imported.joined(OBJECTS_FOLDER).read_dir().unwrap().count());
+
+            file.remove().unwrap();
+            let mut restorer = target.try_game_layout("game1").unwrap();
+            assert_eq!(layout.mapping, restorer.mapping);
+            let scan = restorer.scan_for_restoration(
+                "game1",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = restorer.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("new")), file.read());
+
+            assert_eq!(
+                Err(Error::BundleGameAlreadyBackedUp { game: s("game1") }),
+                target.import_bundle(&bundle, &Manifest::default(), false),
+            );
+            assert_eq!(
+                Ok(s("game1")),
+                target.import_bundle(&bundle, &Manifest::default(), true)
+            );
+            assert_eq!(layout.mapping, target.try_game_layout("game1").unwrap().mapping);
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn rejects_bundle_without_mapping() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-bundle-invalid"));
+            let _ = path.remove();
+            let bundle = path.joined("bundle.zip");
+            bundle.create_parent_dir().unwrap();
+
+            let mut zip = zip::ZipWriter::new(bundle.create().unwrap());
+            zip.start_file("drive-0/save.txt", zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"content").unwrap();
+            zip.finish().unwrap();
+
+            let mut target = BackupLayout::new(path.joined("imported"));
+            assert!(matches!(
+                target.import_bundle(&bundle, &Manifest::default(), false),
+                Err(Error::BundleInvalid { .. })
+            ));
+            assert!(!path.joined("imported").exists());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        #[cfg(not(target_os = "windows"))]
+        fn can_back_up_and_restore_unix_permissions_in_simple_format() {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = |file: &StrictPath| file.metadata().unwrap().permissions().mode() & 0o777;
+
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-permissions"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let script = path.joined("live/launch.sh");
+            script.create_parent_dir().unwrap();
+            script.write_with_content("#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(
+                script.as_std_path_buf().unwrap(),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    script.clone(): ScannedFile::new(script.size(), script.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+
+            let name = layout.mapping.backups[0].name.clone();
+            assert!(backups.joined(&name).joined(PERMISSIONS_FILE).is_file());
+
+            // Simulate a copy of the backup that did not keep the mode, such as from cloud storage.
+            let stored = layout.mapping.game_file_immutable(&backups, &script, &name);
+            std::fs::set_permissions(
+                stored.as_std_path_buf().unwrap(),
+                std::fs::Permissions::from_mode(0o644),
+            )
+            .unwrap();
+
+            script.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(0o755, mode(&script));
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        #[cfg(not(target_os = "windows"))]
+        fn can_back_up_and_restore_symlink_in_simple_format() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-symlink"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let link = path.joined("live/slot.sav");
+            let shared = path.joined("shared/slot.sav");
+            link.create_parent_dir().unwrap();
+            shared.create_parent_dir().unwrap();
+            shared.write_with_content("content").unwrap();
+            link.create_symlink("../shared/slot.sav").unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    link.clone(): ScannedFile {
+                        symlink: Some(SymlinkTarget(s("../shared/slot.sav"))),
+                        ..ScannedFile::new(link.size(), link.sha1()).change_new()
+                    },
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(
+                btree_map! { link.render(): s("../shared/slot.sav") },
+                layout.mapping.backups[0].symlinks,
+            );
+
+            link.remove().unwrap();
+            shared.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+
+            assert_eq!(Some(s("../shared/slot.sav")), link.read_link());
+            assert_eq!(Some(s("content")), shared.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_back_up_and_restore_hardlink() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-hardlink"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let primary = path.joined("live/a.sav");
+            let link = path.joined("live/b.sav");
+            primary.create_parent_dir().unwrap();
+            primary.write_with_content("content").unwrap();
+            link.create_hardlink(&primary).unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    primary.clone(): ScannedFile::new(primary.size(), primary.sha1()).change_new(),
+                    link.clone(): ScannedFile {
+                        hardlink: Some(HardlinkTarget(primary.render())),
+                        ..ScannedFile::new(link.size(), link.sha1()).change_new()
+                    },
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert_eq!(
+                btree_map! { link.render(): primary.render() },
+                layout.mapping.backups[0].hardlinks,
+            );
+            // The link shares the primary file's stored copy.
+            let name = layout.mapping.backups[0].name.clone();
+            assert!(layout.mapping.game_file_immutable(&backups, &primary, &name).is_file());
+            assert!(!layout.mapping.game_file_immutable(&backups, &link, &name).exists());
+            assert!(layout.validate(BackupId::Latest));
+
+            link.remove().unwrap();
+            primary.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+
+            assert_eq!(Some(s("content")), link.read());
+            assert!(primary.hardlink_identity().is_some());
+            assert_eq!(primary.hardlink_identity(), link.hardlink_identity());
+
+            // Without the primary file to link to, the link is restored from the shared copy.
+            link.remove().unwrap();
+            primary.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::new(btree_map! {
+                    s("game5"): btree_map! { StrictPath::new(primary.render()): false },
+                }),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+
+            assert!(!primary.exists());
+            assert_eq!(Some(s("content")), link.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_fall_back_to_original_path_when_redirect_fails() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-redirect-fallback"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let live = path.joined("live/save.txt");
+            live.create_parent_dir().unwrap();
+            live.write_with_content("content").unwrap();
+
+            // A file where the redirected folder should be, so the folder can't be created.
+            let blocker = path.joined("blocker");
+            blocker.write_with_content("").unwrap();
+            let redirects = [RedirectConfig {
+                kind: crate::resource::config::RedirectKind::Restore,
+                source: path.joined("live"),
+                target: blocker.joined("live"),
+            }];
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    live.clone(): ScannedFile::new(live.size(), live.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            live.remove().unwrap();
+
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &redirects,
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(!info.successful());
+            assert!(!live.exists());
+
+            layout.redirect_fallback = true;
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(
+                vec![OperationWarning::RedirectFallback {
+                    path: live.render(),
+                    redirect: blocker.joined("live/save.txt").render(),
+                }],
+                info.warnings,
+            );
+            assert_eq!(Some(s("content")), live.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_restore_only_filtered_files() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-restore-filter"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let slot1 = path.joined("live/slot1/save.txt");
+            let slot2 = path.joined("live/slot2/save.txt");
+            let other = path.joined("live/other.txt");
+            for file in [&slot1, &slot2, &other] {
+                file.create_parent_dir().unwrap();
+                file.write_with_content(&file.render()).unwrap();
+            }
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: [&slot1, &slot2, &other]
+                    .into_iter()
+                    .map(|x| ((*x).clone(), ScannedFile::new(x.size(), x.sha1()).change_new()))
+                    .collect(),
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+
+            for file in [&slot1, &slot2, &other] {
+                file.remove().unwrap();
+            }
+
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let filter = vec![path.joined("live/slot1"), other.clone()];
+            let info = layout.restore(&scan, &ToggledRegistry::default(), Some(&filter));
+            assert!(info.successful());
+
+            assert!(slot1.is_file());
+            assert!(!slot2.exists());
+            assert!(other.is_file());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_remove_empty_dirs_created_during_restore() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-restore-empty-dirs"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let live = path.joined("live/nested/save.txt");
+            live.create_parent_dir().unwrap();
+            live.write_with_content("content").unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    live.clone(): ScannedFile::new(live.size(), live.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+
+            // Make the restore fail after the target folders are created.
+            let name = layout.mapping.backups[0].name.clone();
+            layout
+                .mapping
+                .game_file_immutable(&backups, &live, &name)
+                .remove()
+                .unwrap();
+
+            for exclude_empty_dirs in [false, true] {
+                path.joined("live").remove().unwrap();
+                layout.exclude_empty_dirs = exclude_empty_dirs;
+                let scan = layout.scan_for_restoration(
+                    "game5",
+                    &BackupId::Latest,
+                    &[],
+                    false,
+                    &ToggledPaths::default(),
+                    &ToggledRegistry::default(),
+                );
+                let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+                assert!(!info.successful());
+                assert_eq!(!exclude_empty_dirs, path.joined("live/nested").is_dir());
+                assert_eq!(!exclude_empty_dirs, path.joined("live").exists());
+            }
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_mirror_backups_to_other_folders() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-mirror"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let live = path.joined("live/save.txt");
+            live.create_parent_dir().unwrap();
+            live.write_with_content("content").unwrap();
+
+            // A file where the mirror folder should be, so the mirror can't be created.
+            let mirror = path.joined("mirror");
+            let blocked = path.joined("blocked");
+            blocked.write_with_content("").unwrap();
+
+            let mut layout = GameLayout::new(backups.joined("game5"), IndividualMapping::new("game5".to_string()));
+            layout.mirrors = vec![mirror.clone(), blocked.clone()];
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    live.clone(): ScannedFile::new(live.size(), live.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            let info = layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            assert!(info.successful());
+            assert!(info.mirrors[&mirror].is_ok());
+            assert!(info.mirrors[&blocked].is_err());
+            assert_eq!(1, info.warnings.len());
+            assert!(matches!(info.warnings[0], OperationWarning::MirrorFailed { .. }));
+
+            let mirrored = GameLayout::load(mirror.joined("game5")).unwrap();
+            assert_eq!(layout.mapping, mirrored.mapping);
+            assert_eq!(
+                Some(s("content")),
+                mirrored
+                    .mapping
+                    .game_file_immutable(&mirror.joined("game5"), &live, &mirrored.mapping.backups[0].name)
+                    .read()
+            );
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_sync_mirrors_after_other_changes() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-mirror-sync"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let live = path.joined("live/save.txt");
+            live.create_parent_dir().unwrap();
+            live.write_with_content("content").unwrap();
+
+            let mut layout = GameLayout::new(backups.joined("game5"), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    live.clone(): ScannedFile::new(live.size(), live.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            let backup = layout.mapping.backups[0].name.clone();
+
+            // The mirror is added after the backup was made.
+            let mirror = path.joined("mirror");
+            layout.mirrors = vec![mirror.clone()];
+            layout.maintain(Retention::default(), &now(), false);
+
+            let mirrored = GameLayout::load(mirror.joined("game5")).unwrap();
+            assert_eq!(layout.mapping, mirrored.mapping);
+            let mirrored_file = mirrored
+                .mapping
+                .game_file_immutable(&mirror.joined("game5"), &live, &backup);
+            assert_eq!(Some(s("content")), mirrored_file.read());
+
+            layout.delete_backup(&BackupId::Named(backup)).unwrap();
+            let mirrored = GameLayout::load(mirror.joined("game5")).unwrap();
+            assert_eq!(layout.mapping, mirrored.mapping);
+            assert!(!mirrored_file.exists());
+
+            // Unreferenced objects are cleaned up in the mirror as well.
+            let orphan = mirror.joined(OBJECTS_FOLDER).joined("orphan");
+            orphan.create_parent_dir().unwrap();
+            orphan.write_with_content("orphan").unwrap();
+            BackupLayout::new(backups.clone())
+                .with_mirrors(vec![mirror.clone()])
+                .collect_garbage(false);
+            assert!(!orphan.exists());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn restore_leaves_mapping_file_alone() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-restore-mapping"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let live = path.joined("live/save.txt");
+            live.create_parent_dir().unwrap();
+            live.write_with_content("content").unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    live.clone(): ScannedFile::new(live.size(), live.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+            let mapping_file = GameLayout::mapping_file(&backups);
+            let mapping = mapping_file.read().unwrap();
+            let modified = mapping_file.get_mtime().unwrap();
+
+            live.remove().unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert!(scan.restored_anything(&info));
+            assert_eq!("content", live.read().unwrap());
+
+            // Restoring shouldn't make the backup folder look changed to cloud sync.
+            assert_eq!(mapping, mapping_file.read().unwrap());
+            assert_eq!(modified, mapping_file.get_mtime().unwrap());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_resolve_restore_conflicts() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-restore-conflict"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let live = path.joined("live/save.txt");
+            live.create_parent_dir().unwrap();
+            live.write_with_content("old").unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let scan = ScanInfo {
+                game_name: s("game5"),
+                found_files: hash_map! {
+                    live.clone(): ScannedFile::new(live.size(), live.sha1()).change_new(),
+                },
+                ..Default::default()
+            };
+            layout
+                .back_up(
+                    &scan,
+                    &now(),
+                    &BackupFormats::default(),
+                    Retention::default(),
+                    false,
+                    false,
+                    None,
+                )
+                .unwrap();
+
+            // The backup is dated in the past, so this counts as newer.
+            live.write_with_content("new").unwrap();
+            let scan = layout.scan_for_restoration(
+                "game5",
+                &BackupId::Latest,
+                &[],
+                false,
+                &ToggledPaths::default(),
+                &ToggledRegistry::default(),
+            );
+            assert!(scan.found_files.values().all(|x| x.conflict));
+
+            for policy in [ConflictPolicy::Skip, ConflictPolicy::KeepNewer] {
+                layout.conflict_policy = policy;
+                let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+                assert!(info.successful());
+                assert_eq!(Some(s("new")), live.read());
+            }
+
+            layout.conflict_policy = ConflictPolicy::Backup;
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("old")), live.read());
+            let moved: Vec<_> = path
+                .joined("live")
+                .read_dir()
+                .unwrap()
+                .filter_map(|x| x.ok())
+                .map(|x| x.file_name().to_string_lossy().to_string())
+                .filter(|x| x.ends_with(".bak"))
+                .collect();
+            assert_eq!(1, moved.len());
+            assert_eq!(Some(s("new")), path.joined("live").joined(&moved[0]).read());
+
+            live.write_with_content("new").unwrap();
+            layout.conflict_policy = ConflictPolicy::Overwrite;
+            let info = layout.restore(&scan, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("old")), live.read());
+
+            let _ = path.remove();
+        }
+
+        #[test]
+        fn can_skip_destructive_restore() {
+            let path = StrictPath::from(std::env::temp_dir().join("ludusavi-test-restore-constructive"));
+            let _ = path.remove();
+            let backups = path.joined("backups");
+            backups.create_dirs().unwrap();
+
+            let changed = path.joined("live/changed.txt");
+            let deleted = path.joined("live/deleted.txt");
+            let missing = path.joined("live/missing.txt");
+            changed.create_parent_dir().unwrap();
+
+            let mut layout = GameLayout::new(backups.clone(), IndividualMapping::new("game5".to_string()));
+            let back_up = |layout: &mut GameLayout, seconds: i64, changes: [ScanChange; 3]| {
+                let scan = ScanInfo {
+                    game_name: s("game5"),
+                    found_files: [&changed, &deleted, &missing]
+                        .into_iter()
+                        .zip(changes)
+                        .map(|(x, change)| ((*x).clone(), ScannedFile::new(x.size(), x.sha1()).change_as(change)))
+                        .collect(),
+                    ..Default::default()
+                };
+                layout
+                    .back_up(
+                        &scan,
+                        &(now() + chrono::Duration::seconds(seconds)),
+                        &BackupFormats::default(),
+                        Retention::new(1, 1),
+                        false,
+                        false,
+                        None,
+                    )
+                    .unwrap();
+            };
+
+            changed.write_with_content("old").unwrap();
+            deleted.write_with_content("deleted").unwrap();
+            missing.write_with_content("missing").unwrap();
+            back_up(&mut layout, 0, [ScanChange::New, ScanChange::New, ScanChange::New]);
+            back_up(
+                &mut layout,
+                1,
+                [ScanChange::Same, ScanChange::Removed, ScanChange::Same],
+            );
 
-    mod game_layout {
-        use pretty_assertions::assert_eq;
+            // The backups are dated in the past, so this counts as newer.
+            changed.write_with_content("new").unwrap();
+            missing.remove().unwrap();
 
-        use crate::testing::{drives_x_always, drives_x_static, repo_file_raw};
+            let scan = || {
+                layout.scan_for_restoration(
+                    "game5",
+                    &BackupId::Latest,
+                    &[],
+                    false,
+                    &ToggledPaths::default(),
+                    &ToggledRegistry::default(),
+                )
+            };
+            let change_of = |scan: &ScanInfo, file: &StrictPath| {
+                scan.found_files
+                    .iter()
+                    .find(|(k, v)| v.original_path(k).equivalent(file))
+                    .map(|(_, v)| (v.change, v.conflict))
+            };
 
-        use super::*;
+            let mut constructive = scan();
+            assert_eq!(Some((ScanChange::Different, true)), change_of(&constructive, &changed));
+            assert_eq!(Some((ScanChange::Removed, false)), change_of(&constructive, &deleted));
+            assert_eq!(Some((ScanChange::New, false)), change_of(&constructive, &missing));
+
+            constructive.skip_destructive_restore();
+            assert_eq!(2, constructive.warnings.len());
+            for file in [&changed, &deleted] {
+                assert!(constructive
+                    .warnings
+                    .contains(&OperationWarning::SkippedDestructiveRestore { path: file.render() }));
+            }
+            let info = layout.restore(&constructive, &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("new")), changed.read());
+            assert_eq!(Some(s("deleted")), deleted.read());
+            assert_eq!(Some(s("missing")), missing.read());
 
-        fn now() -> chrono::DateTime<chrono::Utc> {
-            chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
-                .unwrap()
-                .and_hms_opt(3, 4, 5)
-                .unwrap()
-                .and_local_timezone(chrono::Utc)
-                .unwrap()
-        }
+            let info = layout.restore(&scan(), &ToggledRegistry::default(), None);
+            assert!(info.successful());
+            assert_eq!(Some(s("old")), changed.read());
+            assert!(!deleted.exists());
 
-        fn restorable_file_simple(backup: &str, file: &str) -> StrictPath {
-            StrictPath::relative(
-                format!(
-                    "{backup}/drive-{}/{file}",
-                    if cfg!(target_os = "windows") { "X" } else { "0" }
-                ),
-                Some(repo_file_raw("tests/backup/game1")),
-            )
+            let _ = path.remove();
         }
 
         #[test]
-        fn can_scan_game_for_restoration_with_files() {
-            let mut layout = GameLayout::new(
-                StrictPath::new(format!("{}/tests/backup/game1", repo())),
-                IndividualMapping {
-                    name: "game1".to_string(),
-                    drives: drives_x(),
+        fn can_find_latest_backup_with_file() {
+            let layout = GameLayout {
+                mapping: IndividualMapping {
                     backups: VecDeque::from(vec![FullBackup {
-                        name: SOLO.into(),
-                        when: now(),
+                        name: "full".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            s("/file1.txt"): IndividualMappingFile { hash: "old1".into(), size: 1, base: None, object: false, mtime: None },
+                            s("/file2.txt"): IndividualMappingFile { hash: "old2".into(), size: 2, base: None, object: false, mtime: None },
                         },
+                        children: VecDeque::from(vec![
+                            DifferentialBackup {
+                                name: "diff1".into(),
+                                files: btree_map! {
+                                    s("/file1.txt"): Some(IndividualMappingFile { hash: "new1".into(), size: 1, base: None, object: false, mtime: None }),
+                                },
+                                ..Default::default()
+                            },
+                            DifferentialBackup {
+                                name: "diff2".into(),
+                                files: btree_map! {
+                                    s("/file1.txt"): None,
+                                },
+                                ..Default::default()
+                            },
+                        ]),
                         ..Default::default()
                     }]),
-                },
-            );
-            let backups = vec![Backup::Full(FullBackup {
-                name: SOLO.to_string(),
-                when: now(),
-                files: btree_map! {
-                    mapping_file_key("/file1.txt"): IndividualMappingFile {
-                        hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
-                        size: 1,
-                    },
-                    mapping_file_key("/file2.txt"): IndividualMappingFile {
-                        hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
-                        size: 2,
-                    },
+                    ..Default::default()
                 },
                 ..Default::default()
-            })];
+            };
 
-            assert_eq!(
-                ScanInfo {
-                    game_name: s("game1"),
-                    found_files: hash_map! {
-                        restorable_file_simple(SOLO, "file1.txt"): ScannedFile {
-                            size: 1,
-                            hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(),
-                            original_path: Some(make_original_path("/file1.txt")),
-                            ignored: false,
-                            change: ScanChange::New,
-                            container: None,
-                            redirected: None,
-                        },
-                        restorable_file_simple(SOLO, "file2.txt"): ScannedFile {
-                            size: 2,
-                            hash: "9d891e731f75deae56884d79e9816736b7488080".into(),
-                            original_path: Some(make_original_path("/file2.txt")),
-                            ignored: false,
-                            change: ScanChange::New,
-                            container: None,
-                            redirected: None,
-                        },
-                    },
-                    found_registry_keys: Default::default(),
-                    available_backups: backups.clone(),
-                    backup: Some(backups[0].clone()),
-                    has_backups: true,
-                    dumped_registry: None,
-                    only_constructive_backups: false,
-                },
-                layout.scan_for_restoration(
-                    "game1",
-                    &BackupId::Latest,
-                    &[],
-                    false,
-                    &Default::default(),
-                    &Default::default()
-                ),
-            );
+            // Deleted in the latest backup, even though an earlier one stored it.
+            assert_eq!(None, layout.latest_backup_with_file(&StrictPath::new(s("/file1.txt"))));
+
+            let found = layout
+                .latest_backup_with_file(&StrictPath::new(s("/file2.txt")))
+                .unwrap();
+            assert_eq!(("full", "old2"), (found.0.name(), found.1.hash.as_str()));
+
+            assert_eq!(None, layout.latest_backup_with_file(&StrictPath::new(s("/file3.txt"))));
         }
 
         #[test]
-        fn can_scan_game_for_restoration_with_registry() {
-            let mut layout =
-                BackupLayout::new(StrictPath::new(format!("{}/tests/backup", repo()))).game_layout("game3");
-            if cfg!(target_os = "windows") {
-                assert_eq!(
-                    ScanInfo {
-                        game_name: s("game3"),
-                        found_files: Default::default(),
-                        found_registry_keys: hash_map! {
-                            "HKEY_CURRENT_USER/Software/Ludusavi/game3".into(): ScannedRegistry::new().change_as(ScanChange::Same)
-                                .with_value_same("binary")
-                                .with_value_same("dword")
-                                .with_value_same("expandSz")
-                                .with_value_same("multiSz")
-                                .with_value_same("qword")
-                                .with_value_same("sz")
+        fn can_find_latest_backup_with_file_stored_in_latest_differential_backup() {
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "full".into(),
+                        files: btree_map! {
+                            s("/file1.txt"): IndividualMappingFile { hash: "old1".into(), size: 1, base: None, object: false, mtime: None },
                         },
-                        available_backups: vec![Backup::Full(FullBackup {
-                            name: SOLO.to_string(),
-                            when: now(),
-                            registry: IndividualMappingRegistry {
-                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into()),
-                            },
-                            ..Default::default()
-                        })],
-                        backup: Some(Backup::Full(FullBackup {
-                            name: SOLO.to_string(),
-                            when: now(),
-                            registry: IndividualMappingRegistry {
-                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into()),
-                            },
-                            ..Default::default()
-                        })),
-                        has_backups: true,
-                        dumped_registry: Some(registry::Hives(btree_map! {
-                            r"HKEY_CURRENT_USER".into(): registry::Keys(btree_map! {
-                                r"Software\Ludusavi\game3".into(): registry::Entries(btree_map! {
-                                    "binary".into(): registry::Entry::Binary(vec![65]),
-                                    "dword".into(): registry::Entry::Dword(1),
-                                    "expandSz".into(): registry::Entry::ExpandSz("baz".to_string()),
-                                    "multiSz".into(): registry::Entry::MultiSz("bar".to_string()),
-                                    "qword".into(): registry::Entry::Qword(2),
-                                    "sz".into(): registry::Entry::Sz("foo".to_string()),
-                                }),
-                            })
-                        })),
-                        only_constructive_backups: false,
-                    },
-                    layout.scan_for_restoration(
-                        "game3",
-                        &BackupId::Latest,
-                        &[],
-                        false,
-                        &Default::default(),
-                        &Default::default()
-                    ),
-                );
-            } else {
-                assert_eq!(
-                    ScanInfo {
-                        game_name: s("game3"),
-                        found_files: Default::default(),
-                        found_registry_keys: Default::default(),
-                        available_backups: vec![Backup::Full(FullBackup {
-                            name: SOLO.to_string(),
-                            when: now(),
-                            registry: IndividualMappingRegistry {
-                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into()),
+                        children: VecDeque::from(vec![
+                            DifferentialBackup {
+                                name: "diff1".into(),
+                                files: btree_map! {
+                                    s("/file1.txt"): Some(IndividualMappingFile { hash: "new1".into(), size: 1, base: None, object: false, mtime: None }),
+                                },
+                                ..Default::default()
                             },
-                            ..Default::default()
-                        })],
-                        backup: Some(Backup::Full(FullBackup {
-                            name: SOLO.to_string(),
-                            when: now(),
-                            registry: IndividualMappingRegistry {
-                                hash: Some("4e2cab4b4e3ab853e5767fae35f317c26c655c52".into())
+                            DifferentialBackup {
+                                name: "diff2".into(),
+                                files: btree_map! {
+                                    s("/file1.txt"): Some(IndividualMappingFile { hash: "newer1".into(), size: 1, base: None, object: false, mtime: None }),
+                                },
+                                ..Default::default()
                             },
-                            ..Default::default()
-                        })),
-                        has_backups: true,
-                        dumped_registry: None,
-                        only_constructive_backups: false,
-                    },
-                    layout.scan_for_restoration(
-                        "game3",
-                        &BackupId::Latest,
-                        &[],
-                        false,
-                        &Default::default(),
-                        &Default::default()
-                    ),
-                );
-            }
+                        ]),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            let found = layout
+                .latest_backup_with_file(&StrictPath::new(s("/file1.txt")))
+                .unwrap();
+            assert_eq!(("diff2", "newer1"), (found.0.name(), found.1.hash.as_str()));
         }
 
         #[test]
@@ -3509,8 +10156,8 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: SOLO.into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         ..Default::default()
                     }]),
@@ -3530,7 +10177,7 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: SOLO.into(),
                         files: btree_map! {
-                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         ..Default::default()
                     }]),
@@ -3550,14 +10197,14 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: SOLO.into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: SOLO.into(),
                             files: btree_map! {
                                 mapping_file_key("/file1.txt"): None,
-                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3579,13 +10226,13 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: SOLO.into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: SOLO.into(),
                             files: btree_map! {
-                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3607,8 +10254,8 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         ..Default::default()
                     }]),
@@ -3628,7 +10275,7 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/fake.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         ..Default::default()
                     }]),
@@ -3648,14 +10295,14 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: "test.zip".into(),
                             files: btree_map! {
                                 mapping_file_key("/file1.txt"): None,
-                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/file2.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3677,13 +10324,13 @@ mod tests {
                     backups: VecDeque::from(vec![FullBackup {
                         name: "test.zip".into(),
                         files: btree_map! {
-                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
-                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 },
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
                         },
                         children: VecDeque::from(vec![DifferentialBackup {
                             name: "test.zip".into(),
                             files: btree_map! {
-                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2 }),
+                                mapping_file_key("/fake.txt"): Some(IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None }),
                             },
                             ..Default::default()
                         }]),
@@ -3697,6 +10344,75 @@ mod tests {
             assert!(!layout.validate(BackupId::Latest));
         }
 
+        #[test]
+        fn can_validate_a_simple_full_backup_with_checksum() {
+            let layout = |checksum: &str| GameLayout {
+                mapping: IndividualMapping {
+                    drives: drives_x_always(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: SOLO.into(),
+                        checksum: Some(checksum.into()),
+                        files: btree_map! {
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
+                        },
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                path: StrictPath::new(format!("{}/tests/backup/game1", repo_raw())),
+                ..Default::default()
+            };
+
+            let expected = sha1(format!(
+                "{}:3a52ce780950d4d969792a2559cd519d7ee8c727\n{}:9d891e731f75deae56884d79e9816736b7488080\n",
+                mapping_file_key("/file1.txt"),
+                mapping_file_key("/file2.txt"),
+            ));
+            assert!(layout(&expected).validate(BackupId::Latest));
+            assert!(!layout("0000000000000000000000000000000000000000").validate(BackupId::Latest));
+        }
+
+        #[test]
+        fn can_validate_a_zip_full_backup_with_checksum_when_tampered() {
+            let folder = StrictPath::from(std::env::temp_dir().join("ludusavi-test-zip-checksum"));
+            let _ = folder.create_dirs();
+            let archive = folder.joined("test.zip");
+            std::fs::copy(
+                format!("{}/tests/backup/game1-zipped/test.zip", repo_raw()),
+                archive.as_std_path_buf().unwrap(),
+            )
+            .unwrap();
+
+            let layout = GameLayout {
+                mapping: IndividualMapping {
+                    drives: drives_x_always(),
+                    backups: VecDeque::from(vec![FullBackup {
+                        name: "test.zip".into(),
+                        checksum: Some(archive.sha1()),
+                        files: btree_map! {
+                            mapping_file_key("/file1.txt"): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
+                            mapping_file_key("/file2.txt"): IndividualMappingFile { hash: "9d891e731f75deae56884d79e9816736b7488080".into(), size: 2, base: None, object: false, mtime: None },
+                        },
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                },
+                path: folder.clone(),
+                ..Default::default()
+            };
+            assert!(layout.validate(BackupId::Latest));
+
+            // Flip a byte in the first local file header's modification time,
+            // which leaves the archive readable.
+            let mut bytes = std::fs::read(archive.as_std_path_buf().unwrap()).unwrap();
+            bytes[10] ^= 0xFF;
+            std::fs::write(archive.as_std_path_buf().unwrap(), bytes).unwrap();
+            assert!(!layout.validate(BackupId::Latest));
+
+            let _ = folder.remove();
+        }
+
         #[test]
         fn can_migrate_legacy_backup() {
             let layout = BackupLayout::new(StrictPath::new(format!("{}/tests/backup", repo_raw())));
@@ -3712,7 +10428,7 @@ mod tests {
                 backups: VecDeque::from(vec![FullBackup {
                     name: SOLO.into(),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
                     },
                     ..Default::default()
                 }]),
@@ -3754,7 +10470,7 @@ mod tests {
                         .to_utc(),
                         os: Some(Os::Windows),
                         files: btree_map! {
-                            "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                            "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
                         },
                         ..Default::default()
                     },
@@ -3771,7 +10487,7 @@ mod tests {
                         .to_utc(),
                     os: Some(Os::Windows),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
                     },
                     ..Default::default()
                 }]),
@@ -3814,7 +10530,7 @@ mod tests {
                         .to_utc(),
                         os: Some(Os::Windows),
                         files: btree_map! {
-                            "X:/file1.txt".into(): Some(IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 }),
+                            "X:/file1.txt".into(): Some(IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None }),
                         },
                         ..Default::default()
                     }]),
@@ -3832,7 +10548,7 @@ mod tests {
                         .to_utc(),
                     os: Some(Os::Windows),
                     files: btree_map! {
-                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1 },
+                        "X:/file1.txt".into(): IndividualMappingFile { hash: "3a52ce780950d4d969792a2559cd519d7ee8c727".into(), size: 1, base: None, object: false, mtime: None },
                     },
                     ..Default::default()
                 }]),
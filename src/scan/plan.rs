@@ -0,0 +1,34 @@
+use std::collections::HashSet;
+
+use crate::{path::StrictPath, resource::config::Root, scan::registry::RegistryItem};
+
+/// What a backup scan intends to check for a game,
+/// derived from the manifest and config before looking at any file content.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScanPlan {
+    pub game_name: String,
+    /// Roots that will be checked, including any extra Wine prefixes.
+    pub roots: Vec<Root>,
+    /// Paths (possibly globs) to check and whether they require case-sensitive matching.
+    /// If the case sensitivity is unset, then the host OS's default applies.
+    pub paths: HashSet<(StrictPath, Option<bool>)>,
+    /// Which of the `paths` come from manifest entries tagged as saves.
+    pub save_paths: HashSet<StrictPath>,
+    /// Registry keys to check, including any virtualized variants.
+    pub registry: Vec<String>,
+    /// Globally ignored paths that will be excluded from the results.
+    pub ignored_paths: Vec<StrictPath>,
+    /// Globally ignored registry keys that will be excluded from the results.
+    pub ignored_registry: Vec<RegistryItem>,
+    pub exclude_store_screenshots: bool,
+}
+
+impl ScanPlan {
+    /// Paths in a stable order, rendered for display.
+    pub fn sorted_paths(&self) -> Vec<String> {
+        let mut paths: Vec<_> = self.paths.iter().map(|(path, _)| path.render()).collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
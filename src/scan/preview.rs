@@ -1,12 +1,18 @@
 use std::collections::HashMap;
 
+use itertools::Itertools;
+
 use crate::{
     path::StrictPath,
-    resource::config::{ToggledPaths, ToggledRegistry},
+    resource::{
+        config::{HashAlgorithm, ToggledPaths, ToggledRegistry},
+        manifest::Os,
+    },
     scan::{
         layout::Backup,
         registry::{self, RegistryItem},
-        BackupInfo, ScanChange, ScanChangeCount, ScanKind, ScannedFile, ScannedRegistry,
+        BackupInfo, ChangeSummary, OperationWarning, ScanChange, ScanChangeCount, ScanKind, ScannedFile,
+        ScannedRegistry,
     },
 };
 
@@ -18,6 +24,8 @@ pub struct ScanInfo {
     /// and should be used in its raw form.
     pub found_files: HashMap<StrictPath, ScannedFile>,
     pub found_registry_keys: HashMap<RegistryItem, ScannedRegistry>,
+    /// Algorithm that produced the hashes in `found_files`.
+    pub hash_algorithm: HashAlgorithm,
     /// Only populated by a restoration scan.
     pub available_backups: Vec<Backup>,
     /// Only populated by a restoration scan.
@@ -28,6 +36,10 @@ pub struct ScanInfo {
     pub dumped_registry: Option<registry::Hives>,
     /// Last known configuration.
     pub only_constructive_backups: bool,
+    /// Non-fatal problems encountered during the scan.
+    pub warnings: Vec<OperationWarning>,
+    /// Whether the game seemed to be running during a backup scan.
+    pub game_running: bool,
 }
 
 impl ScanInfo {
@@ -55,6 +67,124 @@ impl ScanInfo {
         successful_bytes.checked_sub(failed_bytes).unwrap_or_default()
     }
 
+    /// Warnings from both the scan and the backup, if any.
+    pub fn all_warnings<'a>(&'a self, backup_info: Option<&'a BackupInfo>) -> Vec<&'a OperationWarning> {
+        self.warnings
+            .iter()
+            .chain(backup_info.map(|x| x.warnings.iter()).into_iter().flatten())
+            .collect()
+    }
+
+    /// For restoration, skip any files that would destroy live data:
+    /// files that a differential backup recorded as deleted,
+    /// and files that would overwrite a live copy modified after the backup.
+    /// This way, the restore only adds files or updates older ones.
+    /// Each skipped file is recorded as a warning.
+    pub fn skip_destructive_restore(&mut self) {
+        for (scan_key, file) in self.found_files.iter_mut().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+            if file.ignored || file.scan_kind().is_backup() {
+                continue;
+            }
+            if file.change == ScanChange::Removed || (file.change == ScanChange::Different && file.conflict) {
+                file.ignored = true;
+                self.warnings.push(OperationWarning::SkippedDestructiveRestore {
+                    path: file.readable(scan_key, ScanKind::Restore),
+                });
+            }
+        }
+    }
+
+    /// For restoration, find the original paths of the files whose target matches a glob.
+    /// The target already reflects any redirects,
+    /// so the glob applies to where the files would actually be restored.
+    /// A glob that matches a folder also matches everything inside of it.
+    /// A relative glob can match at any depth, like `saves/*.sav`.
+    pub fn restore_files_matching(&self, glob: &str) -> Vec<StrictPath> {
+        let glob = StrictPath::new(glob.to_string());
+        let normalized = if glob.is_absolute() {
+            glob.globbable()
+        } else {
+            format!("**/{}", glob.globbable())
+        };
+
+        let mut builder = globset::GlobSetBuilder::new();
+        for variant in [normalized.clone(), format!("{}/**", &normalized)] {
+            if let Ok(glob) = globset::GlobBuilder::new(&variant)
+                .literal_separator(true)
+                .backslash_escape(false)
+                .case_insensitive(!Os::HOST.is_case_sensitive())
+                .build()
+            {
+                builder.add(glob);
+            }
+        }
+        let Ok(globs) = builder.build() else {
+            return vec![];
+        };
+
+        self.found_files
+            .iter()
+            .filter(|(scan_key, file)| {
+                file.scan_kind().is_restore() && globs.is_match(file.effective(scan_key).render())
+            })
+            .map(|(scan_key, file)| file.original_path(scan_key).clone())
+            .sorted()
+            .collect()
+    }
+
+    /// For restoration, find the original paths of the files at or inside of a location,
+    /// as shown in the GUI's file tree.
+    pub fn restore_files_within(&self, path: &StrictPath) -> Vec<StrictPath> {
+        self.found_files
+            .iter()
+            .filter(|(scan_key, file)| {
+                let readable = StrictPath::new(file.readable(scan_key, ScanKind::Restore));
+                file.scan_kind().is_restore() && (path.equivalent(&readable) || path.is_prefix_of(&readable))
+            })
+            .map(|(scan_key, file)| file.original_path(scan_key).clone())
+            .sorted()
+            .collect()
+    }
+
+    /// For restoration, ignore any files whose original path is not in the filter.
+    pub fn restrict_restore(&mut self, filter: &[StrictPath]) {
+        for (scan_key, file) in self.found_files.iter_mut() {
+            if !filter.contains(file.original_path(scan_key)) {
+                file.ignored = true;
+            }
+        }
+    }
+
+    /// Find the deepest folder that contains every file to be restored.
+    /// The targets already reflect any redirects.
+    /// Returns nothing if the only common folder is a drive or filesystem root.
+    pub fn restore_root(&self) -> Option<StrictPath> {
+        let mut root: Option<StrictPath> = None;
+
+        for (scan_key, file) in &self.found_files {
+            if !file.scan_kind().is_restore() || file.ignored {
+                continue;
+            }
+
+            let parent = file.effective(scan_key).popped();
+            root = match root {
+                None => Some(parent),
+                Some(mut root) => {
+                    while root != parent && !root.is_prefix_of(&parent) {
+                        let popped = root.popped();
+                        if popped == root {
+                            return None;
+                        }
+                        root = popped;
+                    }
+                    Some(root)
+                }
+            };
+        }
+
+        root.filter(|x| x.parent().is_some())
+    }
+
     pub fn total_possible_bytes(&self) -> u64 {
         self.found_files.values().map(|x| x.size).sum::<u64>()
     }
@@ -84,9 +214,20 @@ impl ScanInfo {
         }
     }
 
+    /// Whether a restore put back at least one item, even if others failed.
+    pub fn restored_anything(&self, backup_info: &BackupInfo) -> bool {
+        self.found_files
+            .iter()
+            .any(|(k, v)| !v.ignored && !backup_info.failed_files.contains_key(k))
+            || self
+                .found_registry_keys
+                .iter()
+                .any(|(k, v)| !v.ignored && !backup_info.failed_registry.contains_key(k))
+    }
+
     pub fn update_ignored(&mut self, toggled_paths: &ToggledPaths, toggled_registry: &ToggledRegistry) {
         for (scan_key, v) in self.found_files.iter_mut() {
-            v.ignored = toggled_paths.is_ignored(&self.game_name, v.effective(scan_key));
+            v.ignored = v.excluded.is_some() || toggled_paths.is_ignored(&self.game_name, v.effective(scan_key));
         }
         for (scan_key, v) in self.found_registry_keys.iter_mut() {
             v.ignored = toggled_registry.is_ignored(&self.game_name, scan_key, None);
@@ -226,6 +367,37 @@ impl ScanInfo {
         count
     }
 
+    /// Unlike `count_changes`, this keeps ignored entries separate
+    /// and counts registry keys and values on their own.
+    pub fn change_summary(&self) -> ChangeSummary {
+        let scan_kind = self.scan_kind();
+        let mut summary = ChangeSummary::default();
+
+        for entry in self.found_files.values() {
+            if entry.ignored {
+                summary.ignored_files += 1;
+            } else {
+                summary.files.add(entry.change());
+            }
+        }
+        for entry in self.found_registry_keys.values() {
+            if entry.ignored {
+                summary.ignored_registry_keys += 1;
+            } else {
+                summary.registry_keys.add(entry.change(scan_kind));
+            }
+            for value in entry.values.values() {
+                if value.ignored {
+                    summary.ignored_registry_values += 1;
+                } else {
+                    summary.registry_values.add(value.change(scan_kind));
+                }
+            }
+        }
+
+        summary
+    }
+
     pub fn overall_change(&self) -> ScanChange {
         // TODO: Disabled games can still be marked as new/updated. Should we suppress that?
         if self.is_total_removal() {
@@ -363,6 +535,142 @@ mod tests {
         assert!(scan.all_inert());
     }
 
+    #[test]
+    fn can_find_restore_files_matching_glob() {
+        let scan = ScanInfo {
+            found_files: hash_map! {
+                "a".into(): ScannedFile {
+                    original_path: Some("/saves/slot1/data.sav".into()),
+                    ..Default::default()
+                },
+                "b".into(): ScannedFile {
+                    original_path: Some("/saves/slot2/data.sav".into()),
+                    ..Default::default()
+                },
+                "c".into(): ScannedFile {
+                    original_path: Some("/old/slot1/data.sav".into()),
+                    redirected: Some("/saves/slot1/other.sav".into()),
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            vec![
+                StrictPath::from("/old/slot1/data.sav"),
+                StrictPath::from("/saves/slot1/data.sav")
+            ],
+            scan.restore_files_matching("/saves/slot1"),
+        );
+        assert_eq!(
+            vec![
+                StrictPath::from("/saves/slot1/data.sav"),
+                StrictPath::from("/saves/slot2/data.sav")
+            ],
+            scan.restore_files_matching("/saves/*/data.sav"),
+        );
+        assert_eq!(Vec::<StrictPath>::new(), scan.restore_files_matching("/old/**"));
+        assert_eq!(
+            vec![
+                StrictPath::from("/old/slot1/data.sav"),
+                StrictPath::from("/saves/slot1/data.sav")
+            ],
+            scan.restore_files_matching("slot1"),
+        );
+        assert_eq!(
+            vec![StrictPath::from("/saves/slot2/data.sav")],
+            scan.restore_files_matching("slot2/*.sav"),
+        );
+        assert_eq!(
+            Os::HOST.is_case_sensitive(),
+            scan.restore_files_matching("/SAVES/slot2").is_empty(),
+        );
+    }
+
+    #[test]
+    fn can_find_restore_files_within_folder() {
+        let mut scan = ScanInfo {
+            found_files: hash_map! {
+                "a".into(): ScannedFile {
+                    original_path: Some("/saves/slot1/data.sav".into()),
+                    ..Default::default()
+                },
+                "b".into(): ScannedFile {
+                    original_path: Some("/saves/slot2/data.sav".into()),
+                    ..Default::default()
+                },
+                "c".into(): ScannedFile {
+                    original_path: Some("/old/slot1/data.sav".into()),
+                    redirected: Some("/saves/slot1/other.sav".into()),
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        };
+
+        let filter = scan.restore_files_within(&StrictPath::from("/saves/slot1"));
+        assert_eq!(
+            vec![
+                StrictPath::from("/old/slot1/data.sav"),
+                StrictPath::from("/saves/slot1/data.sav")
+            ],
+            filter,
+        );
+        assert_eq!(
+            vec![StrictPath::from("/saves/slot2/data.sav")],
+            scan.restore_files_within(&StrictPath::from("/saves/slot2/data.sav")),
+        );
+
+        scan.restrict_restore(&filter);
+        assert!(!scan.found_files[&StrictPath::from("a")].ignored);
+        assert!(scan.found_files[&StrictPath::from("b")].ignored);
+        assert!(!scan.found_files[&StrictPath::from("c")].ignored);
+    }
+
+    #[test]
+    fn can_find_restore_root() {
+        let scan = ScanInfo {
+            found_files: hash_map! {
+                "a".into(): ScannedFile {
+                    original_path: Some("/saves/game/slot1/data.sav".into()),
+                    ..Default::default()
+                },
+                "b".into(): ScannedFile {
+                    original_path: Some("/saves/game/slot2/data.sav".into()),
+                    ..Default::default()
+                },
+                "c".into(): ScannedFile {
+                    original_path: Some("/old/game/config.ini".into()),
+                    redirected: Some("/saves/game/config.ini".into()),
+                    ..Default::default()
+                },
+                "d".into(): ScannedFile {
+                    original_path: Some("/elsewhere/ignored.sav".into()),
+                    ignored: true,
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        };
+        assert_eq!(Some(StrictPath::from("/saves/game")), scan.restore_root());
+
+        let scan = ScanInfo {
+            found_files: hash_map! {
+                "a".into(): ScannedFile {
+                    original_path: Some("/saves/data.sav".into()),
+                    ..Default::default()
+                },
+                "b".into(): ScannedFile {
+                    original_path: Some("/other/data.sav".into()),
+                    ..Default::default()
+                },
+            },
+            ..Default::default()
+        };
+        assert_eq!(None, scan.restore_root());
+    }
+
     #[test]
     fn count_changes_when_all_files_ignored() {
         let scan = ScanInfo {
@@ -392,6 +700,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn change_summary_counts_ignored_entries_separately() {
+        let scan = ScanInfo {
+            found_files: hash_map! {
+                "new".into(): ScannedFile::default().change_as(ScanChange::New),
+                "same".into(): ScannedFile::default().change_as(ScanChange::Same),
+                "removed".into(): ScannedFile::default().change_as(ScanChange::Removed),
+                "ignored".into(): ScannedFile::default().change_as(ScanChange::Different).ignored(),
+            },
+            found_registry_keys: hash_map! {
+                "HKEY_CURRENT_USER/Key".into(): ScannedRegistry::new()
+                    .change_as(ScanChange::Different)
+                    .with_value_new("a")
+                    .with_value_same("b")
+                    .with_value("c", ScanChange::New, true),
+                "HKEY_CURRENT_USER/Ignored".into(): ScannedRegistry::new().change_as(ScanChange::Same).ignored(),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ChangeSummary {
+                files: ScanChangeCount {
+                    new: 1,
+                    removed: 1,
+                    same: 1,
+                    ..Default::default()
+                },
+                registry_keys: ScanChangeCount {
+                    different: 1,
+                    ..Default::default()
+                },
+                registry_values: ScanChangeCount {
+                    new: 1,
+                    same: 1,
+                    ..Default::default()
+                },
+                ignored_files: 1,
+                ignored_registry_keys: 1,
+                ignored_registry_values: 1,
+            },
+            scan.change_summary(),
+        );
+    }
+
     #[test]
     fn overall_change_when_game_is_different_with_removed_file() {
         let scan = ScanInfo {
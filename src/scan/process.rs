@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use crate::prelude::StrictPath;
+
+/// Executables of the processes that were running when this was created.
+#[derive(Clone, Debug, Default)]
+pub struct RunningProcesses(Vec<PathBuf>);
+
+impl RunningProcesses {
+    pub fn scan() -> Self {
+        use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet),
+        );
+
+        let exes: Vec<_> = system
+            .processes()
+            .values()
+            .filter_map(|x| x.exe())
+            .map(|x| x.to_path_buf())
+            .collect();
+        log::debug!("Found {} running processes with a known executable", exes.len());
+
+        Self(exes)
+    }
+
+    #[cfg(test)]
+    pub fn new(exes: Vec<PathBuf>) -> Self {
+        Self(exes)
+    }
+
+    /// Whether any process was launched from inside of one of these folders,
+    /// matching either the full path or just the name of any folder along the way.
+    pub fn any_inside(&self, folders: &[StrictPath], folder_names: &[&str]) -> bool {
+        let folders: Vec<_> = folders
+            .iter()
+            .filter_map(|x| x.interpret().ok())
+            .map(PathBuf::from)
+            .collect();
+
+        self.0.iter().any(|exe| {
+            folders.iter().any(|folder| exe.starts_with(folder))
+                || exe.ancestors().skip(1).filter_map(|x| x.file_name()).any(|name| {
+                    let name = name.to_string_lossy();
+                    folder_names.iter().any(|expected| name.eq_ignore_ascii_case(expected))
+                })
+        })
+    }
+}
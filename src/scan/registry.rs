@@ -5,7 +5,7 @@ pub mod win;
 
 use std::collections::BTreeMap;
 
-use crate::prelude::StrictPath;
+use crate::{prelude::StrictPath, resource::config::HashAlgorithm};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Format {
@@ -34,6 +34,18 @@ impl From<&StrictPath> for Format {
     }
 }
 
+/// Registry hives that Ludusavi can back up and restore.
+pub const HIVES: &[&str] = &["HKEY_CURRENT_USER", "HKEY_LOCAL_MACHINE"];
+
+/// Resolve a hive name or its usual abbreviation (e.g., `HKCU`) to the full hive name.
+pub fn normalize_hive(name: &str) -> Option<&'static str> {
+    match name.to_uppercase().as_str() {
+        "HKCU" | "HKEY_CURRENT_USER" => Some("HKEY_CURRENT_USER"),
+        "HKLM" | "HKEY_LOCAL_MACHINE" => Some("HKEY_LOCAL_MACHINE"),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Hives(pub BTreeMap<String, Keys>);
 
@@ -118,6 +130,25 @@ impl Hives {
         }
     }
 
+    /// Render a `.reg` file that can be imported with regedit.
+    /// Unlike `serialize`, this uses CRLF line endings, like regedit's own exports.
+    pub fn to_regedit_string(&self) -> String {
+        self.serialize(Format::Reg)
+            .lines()
+            .map(|line| format!("{line}\r\n"))
+            .collect()
+    }
+
+    /// Encode `to_regedit_string` as UTF-16LE with a byte order mark,
+    /// which is what regedit writes and expects for version 5.00 files.
+    pub fn to_regedit_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in self.to_regedit_string().encode_utf16() {
+            bytes.extend(unit.to_le_bytes());
+        }
+        bytes
+    }
+
     pub fn deserialize(content: &str, format: Format) -> Option<Self> {
         match format {
             Format::Reg => regashii::Registry::deserialize(content).ok().map(Self::from),
@@ -126,7 +157,11 @@ impl Hives {
     }
 
     pub fn sha1(&self, format: Format) -> Option<String> {
-        (!self.is_empty()).then(|| crate::prelude::sha1(self.serialize(format)))
+        self.hash(format, HashAlgorithm::Sha1)
+    }
+
+    pub fn hash(&self, format: Format, algorithm: HashAlgorithm) -> Option<String> {
+        (!self.is_empty()).then(|| crate::prelude::hash(self.serialize(format), algorithm))
     }
 
     pub fn is_empty(&self) -> bool {
@@ -432,6 +467,14 @@ mod tests {
     use super::*;
     use crate::testing::s;
 
+    #[test]
+    fn can_normalize_hive_names() {
+        assert_eq!(Some("HKEY_CURRENT_USER"), normalize_hive("HKCU"));
+        assert_eq!(Some("HKEY_CURRENT_USER"), normalize_hive("hkcu"));
+        assert_eq!(Some("HKEY_LOCAL_MACHINE"), normalize_hive("HKEY_LOCAL_MACHINE"));
+        assert_eq!(None, normalize_hive("HKCR"));
+    }
+
     #[test]
     fn hives_can_be_serialized() {
         let hives = Hives(btree_map! {
@@ -519,6 +562,50 @@ HKEY_CURRENT_USER:
         );
     }
 
+    #[test]
+    fn hives_can_be_exported_for_regedit() {
+        let hives = Hives(btree_map! {
+            s("HKEY_CURRENT_USER"): Keys(btree_map! {
+                s("Software\\Ludusavi\\game3"): Entries(btree_map! {
+                    s(""): Entry::Sz(s("default")),
+                    s("sz"): Entry::Sz(s(r#"C:\foo "bar""#)),
+                    s("multiSz"): Entry::MultiSz(s("a\nb")),
+                    s("expandSz"): Entry::ExpandSz(s("%a%")),
+                    s("dword"): Entry::Dword(255),
+                    s("qword"): Entry::Qword(256),
+                    s("binary"): Entry::Binary(vec![1, 2, 255]),
+                    s("raw"): Entry::Raw {
+                        kind: RegistryKind::None,
+                        data: vec![1],
+                    },
+                    s("unknown"): Entry::Unknown,
+                }),
+            })
+        });
+
+        let expected = [
+            "Windows Registry Editor Version 5.00",
+            "",
+            r#"[HKEY_CURRENT_USER\Software\Ludusavi\game3]"#,
+            r#"@="default""#,
+            r#""binary"=hex:01,02,ff"#,
+            r#""dword"=dword:000000ff"#,
+            r#""expandSz"=hex(2):25,00,61,00,25,00,00,00"#,
+            r#""multiSz"=hex(7):61,00,00,00,62,00,00,00,00,00"#,
+            r#""qword"=hex(b):00,01,00,00,00,00,00,00"#,
+            r#""raw"=hex(0):01"#,
+            r#""sz"="C:\\foo \"bar\"""#,
+            "",
+        ]
+        .join("\r\n");
+
+        assert_eq!(expected, hives.to_regedit_string());
+
+        let bytes = hives.to_regedit_bytes();
+        assert_eq!(&[0xFF, 0xFE, b'W', 0x00], &bytes[..4]);
+        assert_eq!(2 + expected.encode_utf16().count() * 2, bytes.len());
+    }
+
     #[test]
     fn item_is_prefix_of() {
         assert!(RegistryItem::new(s(r#"HKCU"#)).is_prefix_of(&RegistryItem::new(s("HKCU/foo"))));
@@ -138,9 +138,15 @@ fn scan_registry_key(
     let mut found = HashMap::new();
     let path = RegistryItem::new(format!("{}\\{}", hive_name, key));
 
-    let subkey = winreg::RegKey::predef(hive)
-        .open_subkey(key)
-        .map_err(|_| Error::RegistryIssue)?;
+    let subkey = match winreg::RegKey::predef(hive).open_subkey(key) {
+        Ok(x) => x,
+        // A missing key just means there is nothing to back up.
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(found),
+        Err(e) => {
+            log::warn!("[{game}] Unable to open registry key: {path:?} | {e:?}");
+            return Err(Error::RegistryIssue);
+        }
+    };
 
     if !filter.is_registry_ignored(&path) {
         let live_entries = read_registry_key(&subkey);
@@ -150,7 +156,8 @@ fn scan_registry_key(
             live_values.insert(
                 live_entry_name.clone(),
                 ScannedRegistryValue {
-                    ignored: toggled.is_ignored(game, &path, Some(live_entry_name)),
+                    ignored: toggled.is_ignored(game, &path, Some(live_entry_name))
+                        || filter.is_registry_value_ignored(&path, live_entry_name),
                     change: previous
                         .as_ref()
                         .and_then(|x| x.get(hive_name, key))
@@ -307,6 +314,7 @@ impl Hives {
         &self,
         game_name: &str,
         toggled: &ToggledRegistry,
+        filter: &BackupFilter,
     ) -> Result<(), HashMap<RegistryItem, BackupError>> {
         let mut failed = HashMap::new();
 
@@ -318,18 +326,24 @@ impl Hives {
 
             for (key_name, entries) in keys.0.iter() {
                 let path = RegistryItem::from_hive_and_key(hive_name, key_name);
-
-                let Some(hive) = hive.as_ref() else {
-                    failed.insert(path.clone(), BackupError::Raw(format!("Unknown hive: {}", hive_name)));
-                    continue;
+                let value_ignored = |entry_name: &str| {
+                    toggled.is_ignored(game_name, &path, Some(entry_name))
+                        || filter.is_registry_value_ignored(&path, entry_name)
                 };
 
-                if toggled.is_ignored(game_name, &path, None)
-                    && entries.0.keys().all(|x| toggled.is_ignored(game_name, &path, Some(x)))
+                // Check this first so that a toggled-off hive is skipped quietly,
+                // even if we wouldn't have been able to restore it.
+                if (toggled.is_ignored(game_name, &path, None) || filter.is_registry_ignored(&path))
+                    && entries.0.keys().all(|x| value_ignored(x))
                 {
                     continue;
                 }
 
+                let Some(hive) = hive.as_ref() else {
+                    failed.insert(path.clone(), BackupError::Raw(format!("Unknown hive: {}", hive_name)));
+                    continue;
+                };
+
                 let key = match hive.create_subkey(key_name) {
                     Ok((key, _)) => key,
                     Err(e) => {
@@ -344,7 +358,7 @@ impl Hives {
                 };
 
                 for (entry_name, entry) in entries.0.iter() {
-                    if toggled.is_ignored(game_name, &path, Some(entry_name)) {
+                    if value_ignored(entry_name) {
                         continue;
                     }
 
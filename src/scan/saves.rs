@@ -5,6 +5,26 @@ use crate::{
     scan::{ScanChange, ScanKind},
 };
 
+/// Why a file was left out of the backup regardless of the user's toggles.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FileExclusion {
+    /// The file is larger than the configured maximum size.
+    TooLarge { limit: u64 },
+    /// The file is smaller than the configured minimum size.
+    TooSmall { limit: u64 },
+    /// The file is empty, and empty files are excluded.
+    Empty,
+}
+
+/// Where a symlink pointed, exactly as stored in the link.
+/// This may be relative to the link's parent folder.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SymlinkTarget(pub String);
+
+/// Mapping key of another file that shares the same content on disk through a hardlink.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct HardlinkTarget(pub String);
+
 #[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ScannedFile {
     pub size: u64,
@@ -16,6 +36,17 @@ pub struct ScannedFile {
     /// An enclosing archive file, if any, depending on the `BackupFormat`.
     pub container: Option<StrictPath>,
     pub redirected: Option<StrictPath>,
+    /// If set, then the file is always ignored.
+    pub excluded: Option<FileExclusion>,
+    /// If set, then the file was found as a symlink,
+    /// and its content is that of the link target.
+    pub symlink: Option<SymlinkTarget>,
+    /// If set, then the file was hardlinked to another found file,
+    /// which should be restored first and then linked to.
+    pub hardlink: Option<HardlinkTarget>,
+    /// When restoring, this is set if the live file has different content
+    /// and was modified after the backup was made.
+    pub conflict: bool,
 }
 
 impl ScannedFile {
@@ -28,7 +59,11 @@ impl ScannedFile {
             ignored: false,
             change: Default::default(),
             container: None,
+            excluded: None,
+            symlink: None,
+            hardlink: None,
             redirected: None,
+            conflict: false,
         }
     }
 
@@ -41,7 +76,11 @@ impl ScannedFile {
             ignored: false,
             change,
             container: None,
+            excluded: None,
+            symlink: None,
+            hardlink: None,
             redirected: None,
+            conflict: false,
         }
     }
 
@@ -51,6 +90,13 @@ impl ScannedFile {
         self
     }
 
+    #[cfg(test)]
+    pub fn excluded(mut self, exclusion: FileExclusion) -> Self {
+        self.ignored = true;
+        self.excluded = Some(exclusion);
+        self
+    }
+
     #[cfg(test)]
     pub fn change_as(mut self, change: ScanChange) -> Self {
         self.change = change;
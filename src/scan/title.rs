@@ -1,13 +1,17 @@
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
-    sync::LazyLock,
+    sync::{Arc, LazyLock, OnceLock},
 };
 
 use itertools::Itertools;
 use regex::Regex;
 
 use crate::{
-    resource::{config::Config, manifest::Manifest},
+    path::StrictPath,
+    resource::{
+        config::Config,
+        manifest::{placeholder as p, Manifest},
+    },
     scan::ScanKind,
 };
 
@@ -22,6 +26,22 @@ static RE_YEAR_SUFFIX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r" \(\d+\)$
 static RE_SYMBOLS_GAP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"[™®©:-]"#).unwrap());
 static RE_SYMBOLS_NO_GAP: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"['"‘’“”]"#).unwrap());
 static RE_SPACES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#" {2,}"#).unwrap());
+static RE_PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"<[a-zA-Z]+>"#).unwrap());
+
+/// Placeholders that may expand to more than one folder.
+const MULTI_FOLDER_PLACEHOLDERS: &[&str] = &[
+    p::ROOT,
+    p::HOME,
+    p::WIN_APP_DATA,
+    p::WIN_LOCAL_APP_DATA,
+    p::WIN_LOCAL_APP_DATA_LOW,
+    p::WIN_DOCUMENTS,
+    p::WIN_PUBLIC,
+    p::WIN_PROGRAM_DATA,
+    p::WIN_DIR,
+    p::XDG_DATA,
+    p::XDG_CONFIG,
+];
 
 pub fn normalize_title(title: &str) -> String {
     let normalized = title.to_lowercase();
@@ -54,6 +74,120 @@ struct TitleGameOperationInfo {
     complete: bool,
 }
 
+#[derive(Clone, Debug)]
+enum PathSegment {
+    /// A placeholder like `<home>`, which covers one or more folders.
+    Placeholder,
+    /// A `**` glob, which covers zero or more folders.
+    Globstar,
+    /// A single folder name, possibly with wildcards.
+    Name { pattern: String, literal: bool },
+}
+
+/// A manifest file path, prepared for matching against real folders.
+#[derive(Clone, Debug)]
+struct PathTemplate {
+    segments: Vec<PathSegment>,
+    /// How many segments a folder must cover before it's specific to this game.
+    /// This is everything up to and including the last literal folder name,
+    /// so that a shared parent like `<winDocuments>/My Games` won't match every game under it.
+    required: usize,
+}
+
+impl PathTemplate {
+    fn new(raw: &str) -> Option<Self> {
+        let segments: Vec<_> = raw
+            .split('/')
+            .filter(|x| !x.is_empty())
+            .map(|segment| {
+                if MULTI_FOLDER_PLACEHOLDERS.contains(&segment) {
+                    PathSegment::Placeholder
+                } else if segment == "**" {
+                    PathSegment::Globstar
+                } else {
+                    let pattern = RE_PLACEHOLDER.replace_all(segment, "*").to_lowercase();
+                    let literal = !pattern.contains(['*', '?', '[']);
+                    PathSegment::Name { pattern, literal }
+                }
+            })
+            .collect();
+
+        let required = segments
+            .iter()
+            .rposition(|x| matches!(x, PathSegment::Name { literal: true, .. }))?
+            + 1;
+
+        Some(Self { segments, required })
+    }
+
+    /// If the folder is or contains this template's save location,
+    /// then return how many literal folder names it matched.
+    fn specificity(&self, folders: &[&str]) -> Option<usize> {
+        Self::match_segments(folders, &self.segments, self.required)
+    }
+
+    fn match_segments(folders: &[&str], segments: &[PathSegment], required: usize) -> Option<usize> {
+        let Some((first, rest)) = segments.split_first() else {
+            // The folder is somewhere inside of the save location.
+            return Some(0);
+        };
+        if folders.is_empty() {
+            return (required == 0).then_some(0);
+        }
+        let required = required.saturating_sub(1);
+
+        match first {
+            PathSegment::Placeholder => (1..=folders.len())
+                .filter_map(|i| Self::match_segments(&folders[i..], rest, required))
+                .max(),
+            PathSegment::Globstar => (0..=folders.len())
+                .filter_map(|i| Self::match_segments(&folders[i..], rest, required))
+                .max(),
+            PathSegment::Name { pattern, literal } => {
+                if !wildcard_match(pattern, folders[0]) {
+                    return None;
+                }
+                Self::match_segments(&folders[1..], rest, required).map(|n| n + usize::from(*literal))
+            }
+        }
+    }
+}
+
+/// Match a single folder name against a pattern with `*` and `?` wildcards.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<_> = pattern.chars().collect();
+    let text: Vec<_> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|x| *x == '*')
+}
+
+/// The manifest data needed to build `PathTemplate`s.
+#[derive(Clone, Debug)]
+struct PathSource {
+    name: String,
+    install_dirs: Vec<String>,
+    files: Vec<String>,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TitleFinder {
     games: HashMap<String, TitleGameInfo>,
@@ -62,6 +196,12 @@ pub struct TitleFinder {
     lutris_ids: HashMap<String, String>,
     normalized: HashMap<String, NormalizedTitleGameInfo>,
     aliases: HashMap<String, String>,
+    path_sources: Arc<Vec<PathSource>>,
+    /// Built from `path_sources` the first time that we look up a save folder,
+    /// since most runs never need it.
+    paths: OnceLock<Vec<(String, PathTemplate)>>,
+    /// Minimum score for `find_one_by_fuzzy_name` to pick a title.
+    fuzzy_threshold: f64,
 }
 
 impl TitleFinder {
@@ -89,6 +229,17 @@ impl TitleFinder {
         let lutris_ids = manifest.map_lutris_ids_to_names();
         let aliases = manifest.aliases();
 
+        let path_sources = manifest
+            .0
+            .iter()
+            .filter(|(_, game)| !game.files.is_empty())
+            .map(|(name, game)| PathSource {
+                name: name.clone(),
+                install_dirs: game.install_dir.keys().cloned().collect(),
+                files: game.files.keys().cloned().collect(),
+            })
+            .collect();
+
         let mut normalized: HashMap<String, NormalizedTitleGameInfo> = HashMap::new();
         for title in games.keys() {
             let norm = normalize_title(title);
@@ -115,9 +266,51 @@ impl TitleFinder {
             lutris_ids,
             normalized,
             aliases,
+            path_sources: Arc::new(path_sources),
+            paths: OnceLock::new(),
+            fuzzy_threshold: f64::from(config.scan.fuzzy_match_threshold.min(100)) / 100.0,
         }
     }
 
+    fn paths(&self) -> &[(String, PathTemplate)] {
+        self.paths.get_or_init(|| {
+            let mut paths = vec![];
+            for PathSource {
+                name,
+                install_dirs,
+                files,
+            } in self.path_sources.iter()
+            {
+                let install_dirs: Vec<_> = if install_dirs.is_empty() {
+                    vec![name.as_str()]
+                } else {
+                    install_dirs.iter().map(|x| x.as_str()).collect()
+                };
+
+                for raw in files {
+                    let variants: Vec<_> = if raw.contains(p::BASE) || raw.contains(p::GAME) {
+                        install_dirs
+                            .iter()
+                            .map(|install_dir| {
+                                raw.replace(p::BASE, &format!("{}/{}", p::ROOT, install_dir))
+                                    .replace(p::GAME, install_dir)
+                            })
+                            .collect()
+                    } else {
+                        vec![raw.clone()]
+                    };
+
+                    for variant in variants {
+                        if let Some(template) = PathTemplate::new(&variant) {
+                            paths.push((name.clone(), template));
+                        }
+                    }
+                }
+            }
+            paths
+        })
+    }
+
     fn eligible(&self, game: &str, backup: bool, restore: bool) -> bool {
         let (can_backup, can_restore) = self
             .games
@@ -159,11 +352,69 @@ impl TitleFinder {
         })
     }
 
+    /// Look up a name that may be misspelled.
+    /// If the closest title is similar enough and clearly better than the rest,
+    /// then it is returned.
+    /// Otherwise, this returns the closest titles as suggestions.
+    pub fn find_one_by_fuzzy_name(&self, name: &str) -> Result<String, Vec<String>> {
+        const SUGGESTIONS: usize = 3;
+
+        let ranked: Vec<_> = self
+            .find(TitleQuery {
+                multiple: true,
+                names: vec![name.to_string()],
+                normalized: true,
+                fuzzy: true,
+                ..Default::default()
+            })
+            .into_iter()
+            .sorted_by(compare_ranked_titles)
+            .collect();
+
+        match ranked.as_slice() {
+            [(best, info), rest @ ..]
+                if info.score.is_some_and(|x| x >= self.fuzzy_threshold)
+                    && !rest.first().is_some_and(|(_, next)| next.score >= info.score) =>
+            {
+                Ok(best.clone())
+            }
+            _ => Err(ranked.into_iter().take(SUGGESTIONS).map(|(name, _)| name).collect()),
+        }
+    }
+
+    /// Look up games whose manifest save paths could be in the given folder.
+    /// The score is higher when more of the folder's names are specific to the game.
+    /// Games that share a save folder will all be returned.
+    pub fn find_by_save_dir(&self, save_dir: &StrictPath) -> BTreeMap<String, TitleMatch> {
+        let mut output: BTreeMap<String, TitleMatch> = BTreeMap::new();
+
+        let Ok(interpreted) = save_dir.interpret() else {
+            return output;
+        };
+        let interpreted = interpreted.to_lowercase();
+        let folders: Vec<_> = interpreted.split(['/', '\\']).filter(|x| !x.is_empty()).collect();
+
+        for (name, template) in self.paths() {
+            let Some(specificity) = template.specificity(&folders) else {
+                continue;
+            };
+            let score = specificity as f64 / (specificity + 1) as f64;
+
+            let entry = output.entry(name.clone()).or_default();
+            if entry.score.is_none() || entry.score.is_some_and(|old| score > old) {
+                entry.score = Some(score);
+            }
+        }
+
+        output
+    }
+
     /// Look up games based on certain criteria.
     /// Returns a set of matching game names.
     ///
     /// Only returns one result when querying for exact titles or store IDs.
     /// Precedence: Steam ID -> GOG ID -> exact title -> normalized title.
+    /// Querying by save folder returns all games that could use it.
     ///
     /// Otherwise, returns all results that match the query.
     pub fn find(&self, query: TitleQuery) -> BTreeMap<String, TitleMatch> {
@@ -173,6 +424,7 @@ impl TitleFinder {
             steam_id,
             gog_id,
             lutris_id,
+            save_dir,
             normalized,
             fuzzy,
             backup,
@@ -193,7 +445,8 @@ impl TitleFinder {
                 .or_insert(info);
         };
 
-        let singular = !names.is_empty() || steam_id.is_some() || gog_id.is_some() || lutris_id.is_some();
+        let singular =
+            !names.is_empty() || steam_id.is_some() || gog_id.is_some() || lutris_id.is_some() || save_dir.is_some();
 
         'outer: {
             if singular {
@@ -230,6 +483,19 @@ impl TitleFinder {
                     }
                 }
 
+                if let Some(save_dir) = &save_dir {
+                    let mut found = false;
+                    for (name, info) in self.find_by_save_dir(save_dir) {
+                        if self.eligible(&name, backup, restore) {
+                            update(name, info);
+                            found = true;
+                        }
+                    }
+                    if found && !multiple {
+                        break 'outer;
+                    }
+                }
+
                 for name in &names {
                     if self.games.contains_key(name) && self.eligible(name, backup, restore) {
                         update(name.to_owned(), TitleMatch::perfect());
@@ -353,6 +619,9 @@ pub struct TitleQuery {
     /// Search for a Lutris slug.
     /// This will cause only one result to be returned.
     pub lutris_id: Option<String>,
+    /// Search for games whose save paths could be in this folder.
+    /// This may return multiple results.
+    pub save_dir: Option<StrictPath>,
     /// Search by normalizing the `names`.
     pub normalized: bool,
     /// Search with fuzzy matching.
@@ -432,6 +701,33 @@ mod tests {
         assert_eq!("foo bar", normalize_title("  Foo  Bar  "));
     }
 
+    #[test]
+    fn can_find_one_title_by_fuzzy_name() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            Celeste: {}
+            Celeste Classic: {}
+            "#,
+        )
+        .unwrap();
+
+        let finder = TitleFinder::new(&Default::default(), &manifest, Default::default());
+        assert_eq!(Ok("Celeste".to_string()), finder.find_one_by_fuzzy_name("celest"));
+        assert_eq!(
+            Err(vec!["Celeste".to_string(), "Celeste Classic".to_string()]),
+            finder.find_one_by_fuzzy_name("cel"),
+        );
+        assert_eq!(Err(vec![]), finder.find_one_by_fuzzy_name("zzz"));
+
+        let mut config = Config::default();
+        config.scan.fuzzy_match_threshold = 100;
+        let finder = TitleFinder::new(&config, &manifest, Default::default());
+        assert_eq!(
+            Err(vec!["Celeste".to_string(), "Celeste Classic".to_string()]),
+            finder.find_one_by_fuzzy_name("celest"),
+        );
+    }
+
     #[test]
     fn can_find_one_title() {
         let manifest = Manifest::load_from_string(
@@ -671,4 +967,55 @@ mod tests {
             }),
         );
     }
+
+    #[test]
+    fn can_find_titles_by_save_dir() {
+        let manifest = Manifest::load_from_string(
+            r#"
+            game1:
+                files:
+                    <winAppData>/Publisher/Game1/*.sav: {}
+            game2:
+                files:
+                    <winAppData>/Publisher/Shared: {}
+            game3:
+                files:
+                    <winAppData>/Publisher/Shared/*: {}
+            game4:
+                files:
+                    <base>/saves: {}
+                installDir:
+                    Game4Dir: {}
+            game5:
+                files:
+                    <winAppData>/*.dat: {}
+            "#,
+        )
+        .unwrap();
+
+        let finder = TitleFinder::new(&Default::default(), &manifest, Default::default());
+
+        assert_eq!(
+            btree_map! { "game1".to_string(): TitleMatch { score: Some(2.0 / 3.0) } },
+            finder.find_by_save_dir(&StrictPath::new("/home/user/AppData/Roaming/Publisher/Game1")),
+        );
+        assert_eq!(
+            btree_map! {
+                "game2".to_string(): TitleMatch { score: Some(2.0 / 3.0) },
+                "game3".to_string(): TitleMatch { score: Some(2.0 / 3.0) },
+            },
+            finder.find_by_save_dir(&StrictPath::new("/home/user/AppData/Roaming/publisher/shared/slot1")),
+        );
+        assert_eq!(
+            btree_map! { "game4".to_string(): TitleMatch { score: Some(2.0 / 3.0) } },
+            finder.find(TitleQuery {
+                save_dir: Some(StrictPath::new("/games/Game4Dir/saves")),
+                ..Default::default()
+            }),
+        );
+        assert_eq!(
+            BTreeMap::<String, TitleMatch>::new(),
+            finder.find_by_save_dir(&StrictPath::new("/home/user/AppData/Roaming/Publisher")),
+        );
+    }
 }
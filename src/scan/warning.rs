@@ -0,0 +1,49 @@
+/// A problem that did not stop the operation,
+/// but which may explain missing or unexpected results.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationWarning {
+    /// A file was found, but it could not be processed, so it was skipped.
+    #[serde(rename_all = "camelCase")]
+    SkippedFile { path: String },
+    /// A file was found, but its content could not be read.
+    #[serde(rename_all = "camelCase")]
+    UnreadableFile { path: String },
+    /// A file was last modified in the future,
+    /// which may mean that the system clock is wrong.
+    #[serde(rename_all = "camelCase")]
+    FutureModifiedTime { path: String },
+    /// A registry key exists, but could not be accessed.
+    #[serde(rename_all = "camelCase")]
+    InaccessibleRegistry { key: String },
+    /// Files were found inside of a root that is nested in another root,
+    /// so the same files may be reported more than once.
+    #[serde(rename_all = "camelCase")]
+    OverlappingRoots { parent: String, child: String },
+    /// A leftover file from an older backup could not be removed.
+    #[serde(rename_all = "camelCase")]
+    UnremovableBackupFile { path: String },
+    /// A file was not restored because it would have been removed from the live data.
+    #[serde(rename_all = "camelCase")]
+    SkippedDestructiveRestore { path: String },
+    /// The game seemed to be running during the backup,
+    /// so its saves may have been in the middle of being written.
+    GameRunning,
+    /// A pre-backup or post-backup hook command failed or timed out.
+    #[serde(rename_all = "camelCase")]
+    HookFailed { command: String, message: String },
+    /// The backup drive may not have had enough free space for the backup.
+    #[serde(rename_all = "camelCase")]
+    InsufficientSpace { required: u64, available: u64 },
+    /// A file could not be restored to its redirected location,
+    /// so it was restored to its original location instead.
+    #[serde(rename_all = "camelCase")]
+    RedirectFallback { path: String, redirect: String },
+    /// The game was already restored a short time ago,
+    /// so restoring again may revert newer progress.
+    #[serde(rename_all = "camelCase")]
+    RecentlyRestored { minutes: i64 },
+    /// The backup succeeded, but it could not be copied to one of the mirror folders.
+    #[serde(rename_all = "camelCase")]
+    MirrorFailed { path: String, message: String },
+}
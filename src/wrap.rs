@@ -1,5 +1,14 @@
 pub mod heroic;
 
+use crate::{
+    path::StrictPath,
+    resource::{
+        config::Root,
+        manifest::{Manifest, Os},
+    },
+    scan::launchers::Launchers,
+};
+
 /// Returned game information with whatever we could find
 #[derive(Clone, Default, Debug)]
 pub struct WrapGameInfo {
@@ -34,11 +43,78 @@ pub fn infer_game_from_steam() -> Option<WrapGameInfo> {
     None
 }
 
+/// Command to launch a game, based on the manifest's launch info.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LaunchCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_dir: Option<StrictPath>,
+}
+
+/// Find how to launch an installed game using the manifest's launch info.
+/// This only considers launch paths within the game's install folder,
+/// since we don't know where anything else would be.
+pub fn infer_launch_command(
+    game: &str,
+    manifest: &Manifest,
+    roots: &[Root],
+    launchers: &Launchers,
+) -> Option<LaunchCommand> {
+    use crate::resource::manifest::placeholder::BASE;
+
+    let entries = &manifest.0.get(game)?.launch;
+    if entries.is_empty() {
+        return None;
+    }
+
+    for root in roots {
+        for install_dir in launchers.get_game(root, game).filter_map(|x| x.install_dir.as_ref()) {
+            let Ok(base) = install_dir.interpret() else {
+                continue;
+            };
+
+            for (path, variants) in entries {
+                if !path.starts_with(BASE) {
+                    continue;
+                }
+                let program = StrictPath::new(path.replace(BASE, &base));
+                if !program.is_file() {
+                    continue;
+                }
+
+                for variant in variants.iter().filter(|x| x.applies_to(Os::HOST, root.store())) {
+                    let args = match &variant.arguments {
+                        Some(raw) => match shlex::split(raw) {
+                            Some(args) => args,
+                            None => {
+                                log::warn!("Unable to parse launch arguments for {game}: {raw}");
+                                continue;
+                            }
+                        },
+                        None => vec![],
+                    };
+
+                    let command = LaunchCommand {
+                        program: program.render(),
+                        args,
+                        working_dir: variant
+                            .working_dir
+                            .as_ref()
+                            .map(|x| StrictPath::new(x.replace(BASE, &base))),
+                    };
+                    log::debug!("Inferred launch command for {game}: {:?}", &command);
+                    return Some(command);
+                }
+            }
+        }
+    }
+
+    None
+}
+
 pub mod lutris {
     use super::*;
 
-    use crate::path::StrictPath;
-
     use std::sync::Mutex;
 
     // TODO: Refactor to avoid shared state.
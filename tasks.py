@@ -128,6 +128,7 @@ def docs_schema(ctx):
         "api-output",
         "config",
         "general-output",
+        "manifest",
     ]
 
     for command in commands: